@@ -9,90 +9,93 @@
 //! 1. Ensure you have the necessary data files:
 //!    - `data/etf_data.csv` for ETF data
 //!    - `data/mutual_fund_data.csv` for Mutual Fund data
-//!    - `data/allocation_rules.json` for allocation rules
+//!    - `data/allocation_rules.json` for allocation rules, or the same rules in
+//!      `data/allocation_rules.toml` or `data/allocation_rules.yaml`
 //! 2. Run the code using `cargo run --example automated_cash_allocation`.
 //! 3. The code will automatically process the data and display the allocation results.
 //!
-//! The generated report will be saved to `data/allocation_report.json`.
+//! The orders are rounded down to whole lots of ETF shares (set `NALUFX_LOT_SIZE` to change the
+//! lot size) and to fractional Mutual Fund shares, and the report shows the cash that stayed
+//! undeployed. The generated report will be saved to `data/allocation_report.json`.
 
 use csv::Reader;
 use nalufx::{
+    config::{default_llm, is_offline_mode, lot_size},
     errors::NaluFxError,
-    services::automated_cash_allocation_svc::generate_analysis,
-    utils::{currency::format_currency, date::validate_date, input::get_input},
+    services::{
+        automated_cash_allocation_svc::{execute_orders, generate_analysis, load_allocation_rules},
+        fetch_data_svc::{chart_url, Interval},
+        synthetic_data_svc::synthetic_chart_response,
+        yahoo_svc::shared_client,
+    },
+    utils::{
+        currency::format_currency,
+        input::{get_date_range, get_input},
+        trading::ShareRounding,
+    },
 };
-use nalufx_llms::llms::{openai, openai::OpenAI, LLM};
-use reqwest::{header, Client};
-use serde::Serialize;
-use std::{collections::HashMap, io::BufReader};
-use tokio::{fs, io::AsyncReadExt};
-
-use nalufx::models::allocation_dm::{AllocationOrder, AllocationRules, Etf, MutualFund};
+use nalufx_llms::llms::factory::{llm_api_key, llm_from_name};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, io::BufReader, path::Path};
+use tokio::fs;
+
+use nalufx::models::allocation_dm::{AllocationOrder, CashAllocation, Etf, MutualFund};
+
+/// The allocation rules files, in order of preference.
+const ALLOCATION_RULES_FILES: [&str; 4] = [
+    "data/allocation_rules.json",
+    "data/allocation_rules.toml",
+    "data/allocation_rules.yaml",
+    "data/allocation_rules.yml",
+];
 
 /// Represents a report of allocation orders.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 struct Report {
     etf_orders: Vec<AllocationOrder>,
     mutual_fund_orders: Vec<AllocationOrder>,
     total_allocation: f64,
+    cash_remaining: f64,
     analysis: String,
 }
 
 /// The main function for the automated cash allocation example.
 #[tokio::main]
 pub(crate) async fn main() -> Result<(), NaluFxError> {
-    // Get user input for LLM choice
-    let llm_choice =
-        get_input("Enter the LLM to use (e.g., openai, claude, gemini, llama, mistral, ollama):")?;
-    let (llm, api_key): (Box<dyn LLM>, String) = match llm_choice.as_str() {
-        "openai" => {
-            let api_key = match openai::get_openai_api_key() {
-                Ok(key) => key,
-                Err(e) => {
-                    eprintln!("Error: {}", e);
-                    return Err(NaluFxError::InvalidData);
-                },
-            };
-            (Box::new(OpenAI), api_key)
-        },
-        // Add other cases for different LLMs with their respective API key functions
-        _ => {
-            eprintln!("Unsupported LLM choice");
-            return Err(NaluFxError::InvalidOption);
-        },
+    // Get user input for LLM choice, defaulting to the LLM set by `DEFAULT_LLM`
+    let default_llm = default_llm();
+    let prompt = match &default_llm {
+        Some(name) => format!("Enter the LLM to use (openai or ollama, default: {}):", name),
+        None => "Enter the LLM to use (openai or ollama):".to_string(),
     };
+    let llm_choice = get_input(&prompt)?;
+    let llm_name = if llm_choice.is_empty() { default_llm.unwrap_or_default() } else { llm_choice };
+    let llm = llm_from_name(&llm_name)?;
+    let api_key = llm_api_key(llm.as_ref())?;
 
     // Get user input for portfolio name, investor's values, and financial objectives
     let portfolio_name = get_input("Enter the name of the portfolio - (e.g., Growth Portfolio, Balanced Portfolio, Sustainable Future Portfolio):")?;
     let values_input = get_input("Enter the investor's values (comma-separated) - (e.g., Environmental sustainability, social responsibility, corporate governance):")?;
     let financial_objectives_input = get_input("Enter the investor's financial objectives (comma-separated) - (e.g., Long-term capital appreciation, moderate risk tolerance):")?;
 
-    let start_date_input = get_input("Enter the start date (YYYY-MM-DD):")?;
-    let _start_date = match validate_date(&start_date_input) {
-        Ok(date) => date,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
-        },
-    };
-
-    let end_date_input = get_input("Enter the end date (YYYY-MM-DD):")?;
-    let _end_date = match validate_date(&end_date_input) {
-        Ok(date) => date,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
-        },
-    };
+    let (start_date, end_date) = get_date_range()?;
+    let start_date_input = start_date.format("%Y-%m-%d").to_string();
+    let end_date_input = end_date.format("%Y-%m-%d").to_string();
 
     // Step 1: Fetch latest fund data
     let etf_data = fetch_etf_data("data/etf_data.csv").await?;
     let mutual_fund_data = fetch_mutual_fund_data("data/mutual_fund_data.csv").await?;
 
     // Step 2: Determine allocation percentages
-    let allocation_rules = load_allocation_rules("data/allocation_rules.json").await?;
-    let mut etf_allocation = allocate_funds(&etf_data, allocation_rules.etf_percentage);
-    let mut mutual_fund_allocation =
+    let rules_file = ALLOCATION_RULES_FILES
+        .iter()
+        .map(Path::new)
+        .find(|path| path.exists())
+        .unwrap_or_else(|| Path::new(ALLOCATION_RULES_FILES[0]));
+    let allocation_rules = load_allocation_rules(rules_file)?;
+    let etf_allocation = allocate_funds(&etf_data, allocation_rules.etf_percentage);
+    let mutual_fund_allocation =
         allocate_funds(&mutual_fund_data, allocation_rules.mutual_fund_percentage);
 
     // Step 3: Fetch real-time prices for all symbols
@@ -103,9 +106,12 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
         .collect();
     let real_time_prices = fetch_real_time_prices(&all_symbols).await?;
 
-    // Update prices in allocations
-    update_prices_in_allocations(&mut etf_allocation, &real_time_prices);
-    update_prices_in_allocations(&mut mutual_fund_allocation, &real_time_prices);
+    // Buy the shares at the real-time prices: ETFs in whole lots, and Mutual Funds in
+    // fractional shares to three decimal places, keeping the leftover in cash
+    let etf_allocation =
+        execute_orders(&etf_allocation, &real_time_prices, ShareRounding::Lots(lot_size()));
+    let mutual_fund_allocation =
+        execute_orders(&mutual_fund_allocation, &real_time_prices, ShareRounding::Fractional(3));
 
     // Step 4: Generate detailed analysis
     let client = Client::new();
@@ -114,19 +120,15 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
         &client,
         &api_key,
         &portfolio_name,
-        &etf_allocation,
-        &mutual_fund_allocation,
+        &etf_allocation.orders,
+        &mutual_fund_allocation.orders,
         &values_input,
         &financial_objectives_input,
         &start_date_input,
         &end_date_input,
         &real_time_prices,
     )
-    .await
-    .map_err(|e| {
-        eprintln!("Error generating analysis: {}", e);
-        NaluFxError::InvalidData
-    })?;
+    .await?;
 
     // Step 5: Generate report
     let report = generate_allocation_report(&etf_allocation, &mutual_fund_allocation, analysis);
@@ -174,20 +176,6 @@ async fn fetch_mutual_fund_data(file_path: &str) -> Result<Vec<MutualFund>, Nalu
     Ok(funds)
 }
 
-/// Loads allocation rules from a JSON file.
-async fn load_allocation_rules(file_path: &str) -> Result<AllocationRules, NaluFxError> {
-    let mut file = fs::File::open(file_path).await.map_err(|e| {
-        NaluFxError::InputError(std::io::Error::new(
-            e.kind(),
-            format!("Failed to open allocation rules file: {}", file_path),
-        ))
-    })?;
-    let mut data = String::new();
-    let _ = file.read_to_string(&mut data).await?;
-    let rules: AllocationRules = serde_json::from_str(&data)?;
-    Ok(rules)
-}
-
 /// Allocates funds according to the provided allocation rules.
 fn allocate_funds<T>(fund_data: &[T], percentage: f64) -> Vec<AllocationOrder>
 where
@@ -204,35 +192,23 @@ fn allocate_funds<T>(fund_data: &[T], percentage: f64) -> Vec<AllocationOrder>
                 symbol: fund.symbol().to_string(),
                 name: fund.name().to_string(),
                 amount,
+                shares: 0.0,
             }
         })
         .collect()
 }
 
-/// Updates allocation orders with real-time prices.
-fn update_prices_in_allocations(
-    allocations: &mut [AllocationOrder],
-    prices: &HashMap<String, (f64, f64)>,
-) {
-    for allocation in allocations.iter_mut() {
-        if let Some((_, current_price)) = prices.get(&allocation.symbol) {
-            allocation.amount = *current_price;
-        }
-    }
-}
-
 /// Generates an allocation report.
 fn generate_allocation_report(
-    etf_allocation: &[AllocationOrder],
-    mutual_fund_allocation: &[AllocationOrder],
+    etf_allocation: &CashAllocation,
+    mutual_fund_allocation: &CashAllocation,
     analysis: String,
 ) -> Report {
-    let total_allocation: f64 =
-        etf_allocation.iter().chain(mutual_fund_allocation.iter()).map(|order| order.amount).sum();
     Report {
-        etf_orders: etf_allocation.to_vec(),
-        mutual_fund_orders: mutual_fund_allocation.to_vec(),
-        total_allocation,
+        etf_orders: etf_allocation.orders.clone(),
+        mutual_fund_orders: mutual_fund_allocation.orders.clone(),
+        total_allocation: etf_allocation.allocated + mutual_fund_allocation.allocated,
+        cash_remaining: etf_allocation.cash_remaining + mutual_fund_allocation.cash_remaining,
         analysis,
     }
 }
@@ -241,21 +217,24 @@ fn generate_allocation_report(
 fn print_results(report: &Report) {
     println!("\n--- Allocation Report ---");
     println!("\nTotal Allocation: {}", format_currency(report.total_allocation));
+    println!("Cash Remaining: {}", format_currency(report.cash_remaining));
     println!("\nETF Orders:\n");
     for order in &report.etf_orders {
         println!(
-            "Name: {}, Symbol: {}, Amount: {}",
+            "Name: {}, Symbol: {}, Shares: {}, Amount: {}",
             order.name,
             order.symbol,
+            order.shares,
             format_currency(order.amount)
         );
     }
     println!("\nMutual Fund Orders:\n");
     for order in &report.mutual_fund_orders {
         println!(
-            "Name: {}, Symbol: {}, Amount: {}",
+            "Name: {}, Symbol: {}, Shares: {}, Amount: {}",
             order.name,
             order.symbol,
+            order.shares,
             format_currency(order.amount)
         );
     }
@@ -314,23 +293,21 @@ fn value(&self) -> f64 {
 /// Fetches real-time price data from Yahoo Finance for the given symbols.
 async fn fetch_real_time_prices(
     symbols: &[String],
-) -> Result<HashMap<String, (f64, f64)>, reqwest::Error> {
-    let mut headers = header::HeaderMap::new();
-    let _ = headers.insert("User-Agent", header::HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36"));
-    let _ = headers.insert("Accept", header::HeaderValue::from_static("application/json"));
-    let _ = headers.insert("Cookie", header::HeaderValue::from_static("YahooFcUrl"));
-
-    let client = Client::builder().default_headers(headers).build()?;
+) -> Result<HashMap<String, (f64, f64)>, NaluFxError> {
+    let client = shared_client()?;
     let mut prices = HashMap::new();
 
     for symbol in symbols {
         let url = format!(
-            "https://query1.finance.yahoo.com/v8/finance/chart/{}?period1=0&period2=9999999999&interval=1d&includePrePost=true&events=div%7Csplit",
-            symbol
+            "{}&includePrePost=true&events=div%7Csplit",
+            chart_url(symbol, None, None, Interval::Day)
         );
 
-        let response = client.get(&url).send().await?;
-        let data: serde_json::Value = response.json().await?;
+        let data: serde_json::Value = if is_offline_mode() {
+            synthetic_chart_response(symbol, 5 * 252)
+        } else {
+            client.get(&url).await?.json().await?
+        };
         if let Some(result) = data["chart"]["result"].as_array() {
             if let Some(_timestamps) = result.get(0).and_then(|r| r["timestamp"].as_array()) {
                 if let Some(closes) =
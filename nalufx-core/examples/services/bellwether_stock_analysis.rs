@@ -4,74 +4,81 @@
 //!
 //! Usage:
 //!
-//! 1. Run the code using `cargo run --example bellwether_stock_analysis_1987_example`.
-//! 2. Enter the ticker symbol for a bellwether stock when prompted.
-//! 3. Enter the initial investment amount when prompted.
-//! 4. Enter the start date (YYYY-MM-DD) for the analysis period when prompted.
-//! 5. Enter the end date (YYYY-MM-DD) for the analysis period when prompted.
-//! 6. The code will fetch historical data, perform analysis, and generate a report with investment recommendations.
+//! 1. Run the code using `cargo run --example bellwether_stock_analysis`.
+//! 2. Enter the LLM to use when prompted, unless the `DEFAULT_LLM` environment variable sets it.
+//! 3. Enter the ticker symbol for a bellwether stock when prompted.
+//! 4. Enter the initial investment amount when prompted.
+//! 5. Enter the start date (YYYY-MM-DD) for the analysis period when prompted.
+//! 6. Enter the end date (YYYY-MM-DD) for the analysis period when prompted.
+//! 7. The code will fetch historical data, perform analysis, and generate a report with investment recommendations.
 //!
-use nalufx::services::bellwether_stock_analysis_svc::generate_analysis;
-use nalufx::{errors::NaluFxError, utils::input::get_input};
-use nalufx_llms::llms::{openai, openai::OpenAI, LLM};
+//! The inputs can also be given as arguments, in which case they are not prompted for:
+//!
+//! ```shell
+//! cargo run --example bellwether_stock_analysis -- --llm ollama --ticker AAPL \
+//!     --investment 10000 --start 2023-01-01 --end 2023-12-31
+//! ```
+//!
+//! The report is also written to a timestamped file in `./reports`, in the directory set by
+//! `NALUFX_REPORTS_DIR`, or in the directory given with `--output-dir`.
+//!
+//! The sentiment is scored from NewsAPI headlines when `NEWS_API_KEY` is set, and is random
+//! otherwise. With a key, the headlines of the analysis period are also listed after the
+//! report, so that the events behind the price moves can be checked.
+//!
+use clap::Parser;
+use nalufx::services::{
+    bellwether_stock_analysis_svc::generate_analysis, news_svc::fetch_recent_headlines,
+    sentiment_svc::sentiment_provider_from_env,
+};
+use nalufx::{
+    errors::NaluFxError,
+    utils::{args::BellwetherArgs, validation::validate_date},
+};
+use nalufx_llms::llms::factory::{llm_api_key, llm_from_name};
 use reqwest::Client;
 
 #[tokio::main]
 pub(crate) async fn main() -> Result<(), NaluFxError> {
-    // Get user input for LLM choice
-    let llm_choice =
-        get_input("Enter the LLM to use (e.g., openai, claude, gemini, llama, mistral, ollama):")?;
-    let (llm, api_key): (Box<dyn LLM>, String) = match llm_choice.as_str() {
-        "openai" => {
-            let api_key = match openai::get_openai_api_key() {
-                Ok(key) => key,
-                Err(e) => {
-                    eprintln!("Error: {}", e);
-                    return Err(NaluFxError::InvalidData);
-                },
-            };
-            (Box::new(OpenAI), api_key)
-        },
-        // Add other cases for different LLMs with their respective API key functions
-        _ => {
-            eprintln!("Unsupported LLM choice");
-            return Err(NaluFxError::InvalidOption);
-        },
-    };
+    // Read the inputs from the arguments, prompting for the missing ones
+    let inputs = BellwetherArgs::parse().resolve()?;
 
-    // Get user input for ticker, initial investment amount, start date, and end date
-    let ticker_input = get_input("Enter the ticker symbol for a bellwether stock:")?;
-    let ticker = match nalufx::utils::ticker::validate_ticker(&ticker_input) {
-        Ok(symbol) => symbol.to_string(),
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
-        },
-    };
+    let llm = llm_from_name(&inputs.llm)?;
+    let api_key = llm_api_key(llm.as_ref())?;
 
-    // Get user input for the initial investment amount
-    let initial_investment_input = get_input("Enter the initial investment amount:")?;
-    let initial_investment =
-        match nalufx::utils::validation::validate_positive_float(&initial_investment_input) {
-            Ok(value) => value,
-            Err(e) => {
-                eprintln!("Error: {}", e);
-                return Err(NaluFxError::InvalidOption);
-            },
-        };
-
-    let start_date_input = get_input("Enter the start date (YYYY-MM-DD):")?;
-    let end_date_input = get_input("Enter the end date (YYYY-MM-DD):")?;
+    // Score the sentiment from the news source configured in the environment
+    let sentiment = sentiment_provider_from_env();
+    let reports = inputs.report_sink();
 
     // Call the generate_analysis function from the new service
     generate_analysis(
         llm,
         &Client::new(),
         &api_key,
-        &ticker,
-        initial_investment,
-        &start_date_input,
-        &end_date_input,
+        sentiment.as_ref(),
+        &inputs.ticker,
+        inputs.investment,
+        &inputs.start_date,
+        &inputs.end_date,
+        &reports,
     )
-    .await
+    .await?;
+
+    // Cite the headlines of the analysis period, if a news source is configured
+    let (Ok(from), Ok(to)) = (validate_date(&inputs.start_date), validate_date(&inputs.end_date))
+    else {
+        return Ok(());
+    };
+    match fetch_recent_headlines(&inputs.ticker, from, to).await {
+        Ok(headlines) if headlines.is_empty() => {},
+        Ok(headlines) => {
+            println!("\n--- Headlines ---\n");
+            for headline in &headlines {
+                println!("- {}", headline);
+            }
+        },
+        Err(e) => eprintln!("Warning: could not fetch the headlines: {}", e),
+    }
+
+    Ok(())
 }
@@ -11,14 +11,27 @@
 //! 2. Enter the ticker symbols for ETFs separated by commas (e.g., SPY,GLD) when prompted.
 //! 3. Enter the initial investment amount when prompted.
 //! 4. The code will fetch historical data for each ETF, perform analysis, and generate a report with investment recommendations for the best-performing ETF.
+//!    The report is written to a timestamped file in `./reports`, in the directory set by
+//!    `NALUFX_REPORTS_DIR`, or in the directory given with `--output-dir`:
+//!
+//! ```shell
+//! cargo run --example diversified_etf_portfolio_optimization -- --output-dir /tmp/reports
+//! ```
+use clap::Parser;
 use nalufx::services::diversified_etf_portfolio_optimization_svc::generate_analysis;
 use nalufx::{
     errors::NaluFxError,
-    utils::{input::get_input, ticker::validate_ticker, validation::validate_positive_float},
+    utils::{
+        args::ReportArgs,
+        input::get_input,
+        validation::{validate_positive_float, validate_ticker},
+    },
 };
 
 #[tokio::main]
 pub(crate) async fn main() -> Result<(), NaluFxError> {
+    let reports = ReportArgs::parse().report_sink();
+
     let tickers_input =
         get_input("Enter the ticker symbols separated by commas (e.g., SQQQ,SPY,SOXL,XLF):")?;
     let tickers: Vec<String> = tickers_input.split(',').map(|s| s.trim().to_string()).collect();
@@ -35,9 +48,9 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
         Ok(value) => value,
         Err(e) => {
             eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
+            return Err(e);
         },
     };
 
-    generate_analysis(tickers, initial_investment).await
+    generate_analysis(tickers, initial_investment, &reports).await
 }
@@ -16,6 +16,7 @@
 //! 2. Enter the investor's values (comma-separated) when prompted. For example: "Environmental sustainability, social responsibility, corporate governance".
 //! 3. Enter the investor's financial objectives (comma-separated) when prompted. For example: "Long-term capital appreciation, moderate risk tolerance".
 //! 4. Enter the list of ESG investments (comma-separated) when prompted. For example: "ESGU, ESGD, ESGE, SUSL, SUSB, ICLN, PBW, GRID, ACES, SMOG".
+//!    Alternatively, set `PORTFOLIO_CSV` to a CSV file with `ticker,shares_or_weight` columns to read the investments from it.
 //! 5. The code will fetch ESG ratings and historical performance data, optimize the portfolio allocation, and generate an impact report.
 //!
 //! # Dependencies
@@ -29,32 +30,22 @@
 //!
 //! - The code assumes the existence of a `nalufx` library with specific modules and functions. Make sure to have the necessary dependencies and configurations in place.
 //! - The code uses the OpenAI API to generate the impact report. Make sure to set up the API key and have proper authentication in place.
-//! - The example uses dummy ESG ratings for demonstration purposes. In a real-world scenario, you would need to fetch actual ESG ratings from reliable sources.
+//! - ESG ratings are read from `data/esg_ratings.csv` by default. Set `ESG_RATINGS_PATH` to use a different file, or `ESG_RATINGS_URL` to fetch ratings from an HTTP provider. Investments without a rating are reported and excluded from the allocation.
 
 use nalufx::{
     errors::NaluFxError,
-    services::{fetch_data_svc::fetch_data, processing_svc::calculate_daily_returns},
-    utils::input::get_input,
+    prompts::sanitize_user_text,
+    services::{
+        esg_svc::esg_rating_provider_from_env,
+        fetch_data_svc::{fetch_data, is_no_data_error},
+        processing_svc::calculate_daily_returns,
+    },
+    utils::{input::get_input, portfolio::get_portfolio_tickers, stats::min_max_normalize},
 };
 use nalufx_llms::llms::openai::{get_openai_api_key, send_openai_request};
 use nalufx_llms::models::openai_dm::OpenAIResponse;
 use serde_json::json;
 
-/// Normalizes a vector of data points to a range between 0 and 1.
-///
-/// # Arguments
-///
-/// * `data` - A reference to the vector of data points to normalize.
-///
-/// # Returns
-///
-/// A new vector containing the normalized data points.
-fn normalize_data(data: &Vec<f64>) -> Vec<f64> {
-    let max_value = data.iter().cloned().fold(f64::MIN, f64::max);
-    let min_value = data.iter().cloned().fold(f64::MAX, f64::min);
-    data.iter().map(|&x| (x - min_value) / (max_value - min_value)).collect()
-}
-
 /// Calculates the weighted score of an investment based on its ESG rating and normalized returns.
 ///
 /// # Arguments
@@ -83,13 +74,24 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
     let values_input = get_input("Enter the investor's values (comma-separated) - (e.g, Environmental sustainability, social responsibility, corporate governance):")?;
     let financial_objectives_input = get_input("Enter the investor's financial objectives (comma-separated) - (e.g, Long-term capital appreciation, moderate risk tolerance):")?;
 
-    // Get user input for the list of ESG-focused investments
-    let investments_input = get_input("Enter the ESG investments (comma-separated) - (e.g, ESGU, ESGD, ESGE, SUSL, SUSB, ICLN, PBW, GRID, ACES, SMOG):")?;
-    let esg_investments: Vec<&str> = investments_input.split(',').map(|s| s.trim()).collect();
+    // Get the list of ESG-focused investments from the portfolio file or the user input
+    let investments_input = get_portfolio_tickers("Enter the ESG investments (comma-separated) - (e.g, ESGU, ESGD, ESGE, SUSL, SUSB, ICLN, PBW, GRID, ACES, SMOG):")?;
+    let esg_investments: Vec<&str> = investments_input.iter().map(String::as_str).collect();
+
+    // Load the configured ESG rating provider
+    let esg_provider = esg_rating_provider_from_env()?;
 
     // Fetch ESG ratings and historical performance data for each investment
     let mut esg_data = Vec::new();
     for &investment in &esg_investments {
+        let esg_rating = match esg_provider.fetch_esg_rating(investment).await {
+            Ok(rating) => rating,
+            Err(e) => {
+                eprintln!("Skipping investment {}: {}", investment, e);
+                continue;
+            },
+        };
+
         match fetch_data(investment, None, None).await {
             Ok(closes) => {
                 let daily_returns = calculate_daily_returns(&closes);
@@ -97,10 +99,11 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
                     eprintln!("Insufficient data for investment {}", investment);
                     continue;
                 }
-                // Fetch ESG rating (dummy data for demonstration purposes)
-                let esg_rating = 4.5;
                 esg_data.push((investment, daily_returns, esg_rating));
             },
+            Err(e) if is_no_data_error(e.as_ref()) => {
+                eprintln!("Skipping investment {}: {}", investment, e);
+            },
             Err(e) => {
                 eprintln!("Error fetching data for investment {}: {}", investment, e);
             },
@@ -120,7 +123,7 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
     // Normalize data and calculate weighted scores
     let mut esg_scores = Vec::new();
     for (investment, daily_returns, esg_rating) in &esg_data {
-        let normalized_returns = normalize_data(&daily_returns[..min_length].to_vec());
+        let normalized_returns = min_max_normalize(&daily_returns[..min_length]);
         let score = calculate_weighted_score(*esg_rating, &normalized_returns);
         println!("- Investment: {}, Score: {:.2}", investment, score); // Debug statement
         esg_scores.push((investment, score));
@@ -140,13 +143,7 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
 
     // Generate the impact report using OpenAI
     let client = reqwest::Client::new();
-    let api_key = match get_openai_api_key() {
-        Ok(key) => key,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidData);
-        },
-    };
+    let api_key = get_openai_api_key()?;
 
     let allocations_str = esg_allocations
         .iter()
@@ -163,22 +160,16 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
             },
             {
                 "role": "user",
-                "content": format!("Portfolio Allocations:\n{}\n\nInvestor Values: {}\nFinancial Objectives: {}", allocations_str, values_input, financial_objectives_input)
+                "content": format!("Portfolio Allocations:\n{}\n\nInvestor Values: {}\nFinancial Objectives: {}", allocations_str, sanitize_user_text(&values_input), sanitize_user_text(&financial_objectives_input))
             }
         ],
         "max_tokens": 1500,
     });
 
     let openai_url = "https://api.openai.com/v1/chat/completions";
-    let response = match send_openai_request(&client, openai_url, &api_key, request_body).await {
-        Ok(response) => response,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidData);
-        },
-    };
-
-    let impact_report: OpenAIResponse = serde_json::from_str(&response).unwrap();
+    let response = send_openai_request(&client, openai_url, &api_key, request_body).await?;
+
+    let impact_report: OpenAIResponse = serde_json::from_str(&response)?;
     let generated_report = impact_report.choices.first().unwrap().message.content.clone();
 
     // Print the impact report
@@ -10,23 +10,36 @@
 //! 4. Size Factor: Market Capitalization
 //!
 //! The code fetches the required financial data, calculates the factor scores, and generates a ranked list
-//! of stocks based on the weighted average of the factor scores. The ranking is presented in a tabular format.
+//! of stocks based on the weighted average of the factor scores. The ranking is presented in a tabular format,
+//! followed by a suggested long-only portfolio tilted towards the highest-ranked stocks.
 //!
 //! # Usage
 //!
 //! 1. Run the code using `cargo run --example factor_investing_stock_ranking`.
-//! 2. Enter the list of stock ticker symbols (comma-separated) when prompted.
-//! 3. The code will fetch the financial data, calculate the factor scores, and display the ranked list of stocks.
+//! 2. Enter the list of stock ticker symbols (comma-separated) when prompted, or set `PORTFOLIO_CSV` to a CSV file
+//!    with `ticker,shares_or_weight` columns to read the ticker symbols from it instead.
+//! 3. Enter the value, quality, momentum and size weights (comma-separated, summing to 1), or
+//!    press Enter to weight the factors equally.
+//! 4. The code will fetch the financial data, calculate the factor scores, and display the ranked list of stocks.
 //!
 
 // Imports and module declarations...
 use chrono::DateTime;
-use log::{error, info};
+use log::{error, info, warn};
 use nalufx::{
+    config::is_offline_mode,
     errors::NaluFxError,
-    utils::{input::get_input, ticker::validate_ticker},
+    services::{
+        factor_svc::{
+            factor_tilted_weights, rank_by_factors, FactorWeights, StockFactors, WeightingScheme,
+        },
+        fetch_data_svc::{chart_url, Interval},
+        fundamentals_svc::{fetch_fundamentals, Fundamentals},
+        synthetic_data_svc::synthetic_chart_response,
+        yahoo_svc::shared_client,
+    },
+    utils::{input::get_input, portfolio::get_portfolio_tickers},
 };
-use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{collections::HashMap, f64};
@@ -43,7 +56,7 @@ struct StockData {
     return_on_equity: Option<f64>,
     debt_to_equity: Option<f64>,
     market_cap: Option<f64>,
-    momentum_12m: f64,
+    momentum_12m: Option<f64>,
     price_start_period: f64,
     price_end_period: f64,
     date_start_period: String,
@@ -51,16 +64,17 @@ struct StockData {
 }
 
 /// Represents the factor scores of a stock.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct FactorScores {
     symbol: String,
     currency: String,
     value_score: f64,
     quality_score: f64,
-    momentum_score: f64,
+    momentum_score: Option<f64>,
     size_score: f64,
     composite_score: f64,
+    target_weight: f64,
     price_start_period: f64,
     price_end_period: f64,
     date_start_period: String,
@@ -76,25 +90,23 @@ struct FactorScores {
 /// # Returns
 ///
 /// * `Ok(Vec<StockData>)` - A vector of `StockData` structs containing the fetched financial data.
-/// * `Err(reqwest::Error)` - An error if the API request fails.
-async fn fetch_stock_data(symbols: &[String]) -> Result<Vec<StockData>, reqwest::Error> {
-    let mut headers = header::HeaderMap::new();
-    let _ = headers.insert("User-Agent", header::HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36"));
-    let _ = headers.insert("Accept", header::HeaderValue::from_static("application/json"));
-    let _ = headers.insert("Cookie", header::HeaderValue::from_static("YahooFcUrl"));
-
-    let client = Client::builder().default_headers(headers).build()?;
+/// * `Err(NaluFxError)` - An error if the API request fails.
+async fn fetch_stock_data(symbols: &[String]) -> Result<Vec<StockData>, NaluFxError> {
+    let client = shared_client()?;
 
     let mut stock_data = Vec::new();
 
     for symbol in symbols {
         let url = format!(
-            "https://query1.finance.yahoo.com/v8/finance/chart/{}?period1=0&period2=9999999999&interval=1d&includePrePost=true&events=div%7Csplit",
-            symbol
+            "{}&includePrePost=true&events=div%7Csplit",
+            chart_url(symbol, None, None, Interval::Day)
         );
 
-        let response = client.get(&url).send().await?;
-        let data: Value = response.json().await?;
+        let data: Value = if is_offline_mode() {
+            synthetic_chart_response(symbol, 5 * 252)
+        } else {
+            client.get(&url).await?.json().await?
+        };
         info!("Fetched data for {}: {:?}", symbol, data); // Logging for debugging
 
         if let Some(result) = data["chart"]["result"].as_array() {
@@ -112,21 +124,29 @@ async fn fetch_stock_data(symbols: &[String]) -> Result<Vec<StockData>, reqwest:
                     date_end_period,
                 ) = match calculate_momentum_12m(&result[0]) {
                     Some((momentum, start, end, date_start, date_end)) => {
-                        (momentum, start, end, date_start, date_end)
+                        (Some(momentum), start, end, date_start, date_end)
+                    },
+                    None => {
+                        warn!("Momentum unavailable for {}", symbol);
+                        (None, 0.0, 0.0, String::from("N/A"), String::from("N/A"))
                     },
-                    None => (0.0, 0.0, 0.0, String::from("N/A"), String::from("N/A")),
                 };
 
+                let fundamentals = fetch_fundamentals(client, &symbol).await.unwrap_or_else(|e| {
+                    warn!("No fundamentals for {}: {}", symbol, e);
+                    Fundamentals::default()
+                });
+
                 stock_data.push(StockData {
                     symbol: symbol.clone(),
                     short_name: symbol.clone(), // Since we don't have short_name from chart data
                     currency,
                     regular_market_price,
-                    trailing_pe: None,
-                    price_to_book: None,
-                    return_on_equity: None,
-                    debt_to_equity: None,
-                    market_cap: None,
+                    trailing_pe: fundamentals.trailing_pe,
+                    price_to_book: fundamentals.price_to_book,
+                    return_on_equity: fundamentals.return_on_equity,
+                    debt_to_equity: fundamentals.debt_to_equity,
+                    market_cap: fundamentals.market_cap,
                     momentum_12m,
                     price_start_period,
                     price_end_period,
@@ -192,89 +212,115 @@ fn calculate_momentum_12m(result: &Value) -> Option<(f64, f64, f64, String, Stri
     None
 }
 
-/// Calculates the factor scores for the given stock data.
+/// Sums the available components of a factor.
+///
+/// # Arguments
+///
+/// * `components` - A slice of optional factor components.
+///
+/// # Returns
+///
+/// * `Some(f64)` - The sum of the available components.
+/// * `None` - If no component is available.
+fn sum_available(components: &[Option<f64>]) -> Option<f64> {
+    components.iter().flatten().copied().reduce(|a, b| a + b)
+}
+
+/// Formats a momentum score for the report, flagging an unavailable momentum.
+///
+/// # Arguments
+///
+/// * `momentum_score` - The momentum score, if the momentum is available.
+///
+/// # Returns
+///
+/// The momentum score with two decimals, or `momentum unavailable`.
+fn format_momentum(momentum_score: Option<f64>) -> String {
+    momentum_score.map_or_else(|| "momentum unavailable".to_string(), |m| format!("{:.2}", m))
+}
+
+/// Calculates the factor scores for the given stock data and ranks the stocks by their
+/// weighted composite score.
 ///
 /// # Arguments
 ///
 /// * `stock_data` - A slice of `StockData` structs.
+/// * `weights` - The `FactorWeights` to apply to the factor scores.
 ///
 /// # Returns
 ///
-/// A vector of `FactorScores` structs containing the calculated factor scores.
-fn calculate_factor_scores(stock_data: &[StockData]) -> Vec<FactorScores> {
-    let mut factor_scores = Vec::new();
-    let mut value_scores = Vec::new();
-    let mut quality_scores = Vec::new();
-    let mut momentum_scores = Vec::new();
-    let mut size_scores = Vec::new();
-
-    for stock in stock_data {
-        let value_score = stock.trailing_pe.map(|pe| 1.0 / pe).unwrap_or(0.0)
-            + stock.price_to_book.map(|pb| 1.0 / pb).unwrap_or(0.0);
-        let quality_score =
-            stock.return_on_equity.unwrap_or(0.0) - stock.debt_to_equity.unwrap_or(0.0);
-        let momentum_score = stock.momentum_12m;
-        let size_score = stock.market_cap.map(|cap| 1.0 / (cap / 1_000_000_000.0)).unwrap_or(0.0);
-
-        value_scores.push(value_score);
-        quality_scores.push(quality_score);
-        momentum_scores.push(momentum_score);
-        size_scores.push(size_score);
-
-        factor_scores.push(FactorScores {
+/// * `Ok(Vec<FactorScores>)` - The factor scores, from the highest composite score to the lowest,
+///   with the target weight of each stock in the factor-tilted portfolio.
+/// * `Err(NaluFxError)` - An error if the weights are invalid or the factors cannot be scored.
+fn calculate_factor_scores(
+    stock_data: &[StockData],
+    weights: &FactorWeights,
+) -> Result<Vec<FactorScores>, NaluFxError> {
+    let stock_factors: Vec<StockFactors> = stock_data
+        .iter()
+        .map(|stock| StockFactors {
             symbol: stock.symbol.clone(),
-            currency: stock.currency.clone(),
-            value_score,
-            quality_score,
-            momentum_score,
-            size_score,
-            composite_score: 0.0, // Temporary placeholder, will be calculated later
-            price_start_period: stock.price_start_period,
-            price_end_period: stock.price_end_period,
-            date_start_period: stock.date_start_period.clone(),
-            date_end_period: stock.date_end_period.clone(),
-        });
-    }
+            value: sum_available(&[
+                stock.trailing_pe.map(|pe| 1.0 / pe),
+                stock.price_to_book.map(|pb| 1.0 / pb),
+            ]),
+            quality: sum_available(&[stock.return_on_equity, stock.debt_to_equity.map(|de| -de)]),
+            momentum: stock.momentum_12m,
+            size: stock.market_cap.map(|cap| 1.0 / (cap / 1_000_000_000.0)),
+        })
+        .collect();
+
+    let stocks_by_symbol: HashMap<&str, &StockData> =
+        stock_data.iter().map(|stock| (stock.symbol.as_str(), stock)).collect();
+
+    let ranked = rank_by_factors(&stock_factors, weights)?;
+    let target_weights = factor_tilted_weights(&ranked, WeightingScheme::default())?;
+
+    Ok(ranked
+        .into_iter()
+        .map(|scores| {
+            let stock = stocks_by_symbol[scores.symbol.as_str()];
+            let target_weight = target_weights[&scores.symbol];
+            FactorScores {
+                symbol: scores.symbol,
+                currency: stock.currency.clone(),
+                value_score: scores.value_score,
+                quality_score: scores.quality_score,
+                momentum_score: scores.momentum_score,
+                size_score: scores.size_score,
+                composite_score: scores.composite_score,
+                target_weight,
+                price_start_period: stock.price_start_period,
+                price_end_period: stock.price_end_period,
+                date_start_period: stock.date_start_period.clone(),
+                date_end_period: stock.date_end_period.clone(),
+            }
+        })
+        .collect())
+}
 
-    // Function to calculate mean and standard deviation
-    fn mean_std(scores: &[f64]) -> (f64, f64) {
-        let mean = scores.iter().copied().sum::<f64>() / scores.len() as f64;
-        let variance =
-            scores.iter().map(|score| (score - mean).powi(2)).sum::<f64>() / scores.len() as f64;
-        let std_dev = variance.sqrt();
-        (mean, std_dev)
+/// Prompts the user for the factor weights, falling back to equal weights on an empty input.
+///
+/// # Returns
+///
+/// * `Ok(FactorWeights)` - The weights entered by the user, or the default equal weights.
+/// * `Err(NaluFxError)` - An error if the input cannot be read or the weights are invalid.
+fn get_factor_weights() -> Result<FactorWeights, NaluFxError> {
+    let input = get_input(
+        "Enter the value, quality, momentum and size weights (comma-separated, summing to 1), or press Enter for equal weights:",
+    )?;
+    if input.is_empty() {
+        return Ok(FactorWeights::default());
     }
 
-    // Normalize scores
-    let (value_mean, value_std) = mean_std(&value_scores);
-    let (quality_mean, quality_std) = mean_std(&quality_scores);
-    let (momentum_mean, momentum_std) = mean_std(&momentum_scores);
-    let (size_mean, size_std) = mean_std(&size_scores);
-
-    for score in factor_scores.iter_mut() {
-        score.value_score =
-            if value_std != 0.0 { (score.value_score - value_mean) / value_std } else { 0.0 };
-        score.quality_score = if quality_std != 0.0 {
-            (score.quality_score - quality_mean) / quality_std
-        } else {
-            0.0
-        };
-        score.momentum_score = if momentum_std != 0.0 {
-            (score.momentum_score - momentum_mean) / momentum_std
-        } else {
-            0.0
-        };
-        score.size_score =
-            if size_std != 0.0 { (score.size_score - size_mean) / size_std } else { 0.0 };
-
-        // Recalculate composite score after normalization
-        score.composite_score = 0.25 * score.value_score
-            + 0.25 * score.quality_score
-            + 0.25 * score.momentum_score
-            + 0.25 * score.size_score;
+    let weights: Vec<f64> = input
+        .split(',')
+        .map(|w| w.trim().parse::<f64>().map_err(|_| NaluFxError::InvalidOption))
+        .collect::<Result<_, _>>()?;
+    match weights.as_slice() {
+        [value, quality, momentum, size] => FactorWeights::new(*value, *quality, *momentum, *size),
+        _ => Err(NaluFxError::InvalidOption),
     }
-
-    factor_scores
 }
 
 /// Fetches the last quarter's stock price data for the given stock symbols from the Yahoo Finance API.
@@ -286,28 +332,24 @@ fn mean_std(scores: &[f64]) -> (f64, f64) {
 /// # Returns
 ///
 /// * `Ok(HashMap<String, f64>)` - A hash map mapping stock symbols to their last quarter's price.
-/// * `Err(reqwest::Error)` - An error if the API request fails.
-async fn fetch_last_quarter_data(
-    symbols: &[String],
-) -> Result<HashMap<String, f64>, reqwest::Error> {
-    let mut headers = header::HeaderMap::new();
-    let _ = headers.insert("User-Agent", header::HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36"));
-    let _ = headers.insert("Accept", header::HeaderValue::from_static("application/json"));
-    let _ = headers.insert("Cookie", header::HeaderValue::from_static("YahooFcUrl"));
-
-    let client = Client::builder().default_headers(headers).build()?;
+/// * `Err(NaluFxError)` - An error if the API request fails.
+async fn fetch_last_quarter_data(symbols: &[String]) -> Result<HashMap<String, f64>, NaluFxError> {
+    let client = shared_client()?;
     let mut last_quarter_data = HashMap::new();
 
     for symbol in symbols {
         let url = format!(
-            "https://query1.finance.yahoo.com/v8/finance/chart/{}?period1=0&period2=9999999999&interval=1d&includePrePost=true&events=div%7Csplit",
-            symbol
+            "{}&includePrePost=true&events=div%7Csplit",
+            chart_url(symbol, None, None, Interval::Day)
         );
 
         // print!("url: {}", url);
 
-        let response = client.get(&url).send().await?;
-        let data: Value = response.json().await?;
+        let data: Value = if is_offline_mode() {
+            synthetic_chart_response(symbol, 5 * 252)
+        } else {
+            client.get(&url).await?.json().await?
+        };
         info!("Fetched last quarter data for {}: {:?}", symbol, data);
 
         if let Some(result) = data["chart"]["result"].as_array() {
@@ -328,15 +370,6 @@ async fn fetch_last_quarter_data(
     Ok(last_quarter_data)
 }
 
-/// Ranks the stocks based on their composite factor scores.
-///
-/// # Arguments
-///
-/// * `factor_scores` - A mutable slice of `FactorScores` structs.
-fn rank_stocks(factor_scores: &mut [FactorScores]) {
-    factor_scores.sort_by(|a, b| b.composite_score.partial_cmp(&a.composite_score).unwrap());
-}
-
 /// Generates a stock ranking report based on the factor scores and last quarter's data.
 ///
 /// # Arguments
@@ -362,9 +395,9 @@ fn generate_report(factor_scores: &[FactorScores], last_quarter_data: &HashMap<S
     println!("\nThe key factors currently influencing the market include:");
     for score in factor_scores {
         println!(
-            "\n- {}: Momentum Score {:.2}, Value Score {:.2}, Quality Score {:.2}, Size Score {:.2}",
+            "\n- {}: Momentum Score {}, Value Score {:.2}, Quality Score {:.2}, Size Score {:.2}",
             score.symbol,
-            score.momentum_score,
+            format_momentum(score.momentum_score),
             score.value_score,
             score.quality_score,
             score.size_score
@@ -389,11 +422,11 @@ fn generate_report(factor_scores: &[FactorScores], last_quarter_data: &HashMap<S
     println!("|------|--------|----------|----------|----------------|--------------|------------|----------|");
     for (i, score) in factor_scores.iter().enumerate() {
         println!(
-            "| {:4} | {:6} | {:8} | {:8.2} | {:14.2} | {:12.2} | {:10} | {:8} |",
+            "| {:4} | {:6} | {:8} | {:>8} | {:14.2} | {:12.2} | {:10} | {:8} |",
             i + 1,
             score.symbol,
             score.currency,
-            score.momentum_score,
+            format_momentum(score.momentum_score),
             score.price_start_period,
             score.price_end_period,
             score.date_start_period,
@@ -401,13 +434,35 @@ fn generate_report(factor_scores: &[FactorScores], last_quarter_data: &HashMap<S
         );
     }
 
+    println!("\n### Suggested Factor-Tilted Portfolio");
+    println!("\nThe long-only portfolio below weights each stock by the softmax of its composite factor score, so that every stock is held and the highest-ranked stocks receive the largest allocations.\n");
+    println!("| Symbol | Composite Score | Target Weight |");
+    println!("|--------|-----------------|---------------|");
+    for score in factor_scores {
+        println!(
+            "| {:6} | {:15.2} | {:12.2}% |",
+            score.symbol,
+            score.composite_score,
+            score.target_weight * 100.0
+        );
+    }
+
     println!("\n### Explanation of Momentum Factor");
     println!("\nThe momentum factor measures the stock's price movement over the past 12 months. It is calculated using the following formula:\n");
     println!("Momentum = (Price at end of period - Price at start of period) / Price at start of period\n");
 
     println!("\n### Interpretation of Results");
     for (i, score) in factor_scores.iter().enumerate() {
-        let explanation = if score.momentum_score >= 0.0 {
+        let Some(momentum_score) = score.momentum_score else {
+            println!(
+                "\n- **{} ({})** ranks {} on its other factors, as its momentum is unavailable for the past 12 months.",
+                score.symbol,
+                score.symbol,
+                i + 1
+            );
+            continue;
+        };
+        let explanation = if momentum_score >= 0.0 {
             "indicating significant price appreciation"
         } else {
             "indicating a decrease in stock price"
@@ -417,7 +472,7 @@ fn generate_report(factor_scores: &[FactorScores], last_quarter_data: &HashMap<S
             score.symbol,
             score.symbol,
             i + 1,
-            score.momentum_score,
+            momentum_score,
             explanation
         );
     }
@@ -437,15 +492,21 @@ fn generate_report(factor_scores: &[FactorScores], last_quarter_data: &HashMap<S
 /// * `Err(Box<dyn std::error::Error>)` - If an error occurs during execution.
 #[tokio::main]
 pub async fn main() -> Result<(), NaluFxError> {
-    let symbols_input = get_input("Enter the stock ticker symbols (comma-separated):")?;
-    let symbols: Vec<String> = symbols_input.split(',').map(|s| s.trim().to_string()).collect();
+    let symbols = match get_portfolio_tickers("Enter the stock ticker symbols (comma-separated):") {
+        Ok(symbols) => symbols,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return Ok(());
+        },
+    };
 
-    for symbol in &symbols {
-        if validate_ticker(symbol).is_err() {
-            eprintln!("Error: Invalid ticker symbol: {}", symbol);
+    let weights = match get_factor_weights() {
+        Ok(weights) => weights,
+        Err(_) => {
+            eprintln!("Error: Enter four non-negative weights that sum to 1.");
             return Ok(());
-        }
-    }
+        },
+    };
 
     let stock_data = fetch_stock_data(&symbols).await?;
     let last_quarter_data = fetch_last_quarter_data(&symbols).await?;
@@ -455,8 +516,7 @@ pub async fn main() -> Result<(), NaluFxError> {
         return Ok(());
     }
 
-    let mut factor_scores = calculate_factor_scores(&stock_data);
-    rank_stocks(&mut factor_scores);
+    let factor_scores = calculate_factor_scores(&stock_data, &weights)?;
     generate_report(&factor_scores, &last_quarter_data);
 
     Ok(())
@@ -9,33 +9,53 @@
 //! 2. Enter the ticker symbol for a stock when prompted.
 //! 3. Enter the initial investment amount when prompted.
 //! 4. The code will fetch historical data, perform analysis, and generate a report with investment recommendations.
+//!
+//! The report opens with the data quality of the fetched series: its date range, gaps, missing values, price
+//! range, and any suspicious zero prices or jumps.
+//!
+//! Only the first 30 days of each daily series are printed; set `NALUFX_MAX_DISPLAY_DAYS` to change this. The full
+//! allocation schedule is written to a timestamped CSV file in the reports directory, which can be set
+//! with `--output-dir`.
+//!
+//! Each daily amount is rounded down to whole lots of shares at the last closing price, with the
+//! rest kept as residual cash. Set `NALUFX_LOT_SIZE` to trade in round lots, and
+//! `NALUFX_MIN_TRADE_AMOUNT` to skip the amounts too small to be worth trading.
 use chrono::Utc;
+use clap::Parser;
 use nalufx::{
+    config::{display_precision, lot_size, max_display_days, min_trade_amount},
     errors::NaluFxError,
     services::{
-        fetch_data_svc::fetch_data,
+        data_quality_svc::assess_quality,
+        fetch_data_svc::{fetch_data_with_dates, is_no_data_error},
         processing_svc::{calculate_cash_flows, calculate_daily_returns},
     },
     utils::{
+        allocation::normalize_non_negative,
+        args::ReportArgs,
         calculations::{
             analyze_sentiment, calculate_optimal_allocation, train_reinforcement_learning,
         },
+        calendar::next_trading_days,
         currency::format_currency,
         input::get_input,
-        ticker::validate_ticker,
-        validation::validate_positive_float,
+        reports::{fmt_vec, format_daily_rows},
+        trading::round_to_lots,
+        validation::{validate_positive_float, validate_ticker},
     },
 };
 
 #[tokio::main]
 pub(crate) async fn main() -> Result<(), NaluFxError> {
+    let reports = ReportArgs::parse().report_sink();
+
     // Get user input for ticker and initial investment amount
     let ticker_input = get_input("Enter the ticker symbol:")?;
     let ticker = match validate_ticker(&ticker_input) {
         Ok(symbol) => symbol,
         Err(e) => {
             eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
+            return Err(e);
         },
     };
 
@@ -44,18 +64,22 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
         Ok(value) => value,
         Err(e) => {
             eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
+            return Err(e);
         },
     };
 
     // Fetch historical closing prices for the ticker
-    match fetch_data(ticker, None, None).await {
-        Ok(closes) => {
-            if closes.is_empty() {
+    match fetch_data_with_dates(ticker, None, None).await {
+        Ok(series) => {
+            if series.is_empty() {
                 eprintln!("No closing prices found for ticker {}", ticker);
                 return Ok(());
             }
 
+            // Summarize the quality of the fetched series before analyzing it
+            println!("{}", assess_quality(&series));
+            let closes: Vec<f64> = series.iter().map(|&(_, close)| close).collect();
+
             // Calculate daily returns from closing prices
             let daily_returns = calculate_daily_returns(&closes);
 
@@ -130,23 +154,9 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
                 min_length,
             );
 
-            match optimal_allocation_result {
-                Ok(mut optimal_allocation) => {
-                    // Filter out negative allocations and normalize the rest
-                    optimal_allocation = optimal_allocation
-                        .into_iter()
-                        .map(|alloc| if alloc < 0.0 { 0.0 } else { alloc })
-                        .collect();
-                    let total_allocation: f64 = optimal_allocation.iter().sum();
-                    if total_allocation == 0.0 {
-                        eprintln!("Error: Total allocation is zero for ticker {}", ticker);
-                        return Ok(());
-                    }
-                    optimal_allocation = optimal_allocation
-                        .into_iter()
-                        .map(|alloc| alloc / total_allocation)
-                        .collect();
-
+            // Filter out negative allocations and normalize the rest
+            match optimal_allocation_result.and_then(normalize_non_negative) {
+                Ok(optimal_allocation) => {
                     // Print the optimal allocation with descriptive information
                     println!("\n--- Optimal Allocation Report ---\n");
                     println!(
@@ -158,7 +168,10 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
                         ticker
                     );
                     println!("The sum of all values in the allocation vector should be close to 1.0 (100%).");
-                    println!("\n- Optimal Allocation: {:?}", optimal_allocation);
+                    println!(
+                        "\n- Optimal Allocation: {}",
+                        fmt_vec(&optimal_allocation, display_precision())
+                    );
                     println!("*Visualization*: (Include a pie chart or bar graph here)\n");
 
                     // Sentiment Analysis Results
@@ -175,8 +188,12 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
                     println!("A higher sentiment score indicates a more positive market outlook, while a lower score suggests a more cautious or negative sentiment.");
                     println!("The sentiment scores provide valuable insights into the prevailing market sentiment and can help inform investment decisions.");
                     println!("However, it is important to note that sentiment can be subject to short-term fluctuations and should be considered alongside other fundamental and technical factors.");
-                    for (i, score) in sentiment_scores.iter().enumerate() {
-                        println!("- Day {}: {:.2}", i + 1, score);
+                    for row in
+                        format_daily_rows(&sentiment_scores, max_display_days(), |i, score| {
+                            format!("- Day {}: {:.*}", i + 1, display_precision(), score)
+                        })
+                    {
+                        println!("{}", row);
                     }
                     println!(
                         "\n*Analysis*: Sentiment scores varied, with a peak on Day 7 (0.93) indicating high positive sentiment, and lower scores on Days 1 and 4 suggesting caution.\n"
@@ -201,8 +218,12 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
                     println!("A higher action value indicates a stronger recommendation to allocate funds, while a lower value suggests a more conservative approach or potential withdrawal.");
                     println!("It is crucial to consider the reinforcement learning results in conjunction with other analysis and risk management strategies.");
                     println!("The model's recommendations are based on historical patterns and may not account for all future market scenarios.\n");
-                    for (i, action) in optimal_actions.iter().enumerate() {
-                        println!("- Day {}: {:.2}", i + 1, action);
+                    for row in
+                        format_daily_rows(&optimal_actions, max_display_days(), |i, action| {
+                            format!("- Day {}: {:.*}", i + 1, display_precision(), action)
+                        })
+                    {
+                        println!("{}", row);
                     }
                     println!(
                         "\n*Analysis*: High action values on Days 1 and 4 suggest strong recommendations to allocate funds, while lower values on Days 3 and 7 indicate a more conservative approach.\n"
@@ -216,19 +237,92 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
                     );
 
                     let today = Utc::now();
-                    for (i, &allocation) in optimal_allocation.iter().enumerate() {
-                        let allocation_amount = allocation * initial_investment;
-                        let allocation_date = today + chrono::Duration::days(i as i64);
-                        let allocation_percentage = allocation * 100.0;
+                    // Map each allocation to a trading day, skipping weekends and market holidays
+                    let allocation_dates = next_trading_days(today, optimal_allocation.len());
+                    let allocation_date = |i: usize| allocation_dates[i];
+                    // Round each amount to whole lots at the last closing price
+                    let price = closes.last().copied().unwrap_or(0.0);
+                    let (lot_size, min_trade) = (lot_size(), f64::from(min_trade_amount()));
+                    if price <= 0.0 {
                         println!(
-                            "- Day {}: {} - Allocate {} ({:.2}%) to {}",
-                            i + 1,
-                            allocation_date.format("%Y-%m-%d"),
-                            format_currency(allocation_amount),
-                            allocation_percentage,
+                            "The last price of {} is not available, share counts are omitted.\n",
                             ticker
                         );
                     }
+                    for row in format_daily_rows(
+                        &optimal_allocation,
+                        max_display_days(),
+                        |i, allocation| {
+                            let amount = allocation * initial_investment;
+                            let day = format!(
+                                "- Day {}: {}",
+                                i + 1,
+                                allocation_date(i).format("%Y-%m-%d")
+                            );
+                            if amount < min_trade {
+                                return format!(
+                                    "{} - Skip {} to {}, below the minimum trade of {}",
+                                    day,
+                                    format_currency(amount),
+                                    ticker,
+                                    format_currency(min_trade)
+                                );
+                            }
+                            let (shares, residual) = round_to_lots(amount, price, lot_size);
+                            let shares = if price > 0.0 {
+                                format!(
+                                    ": {} shares, {} residual cash",
+                                    shares,
+                                    format_currency(residual)
+                                )
+                            } else {
+                                String::new()
+                            };
+                            format!(
+                                "{} - Allocate {} ({:.2}%) to {}{}",
+                                day,
+                                format_currency(amount),
+                                allocation * 100.0,
+                                ticker,
+                                shares
+                            )
+                        },
+                    ) {
+                        println!("{}", row);
+                    }
+
+                    // Export the full allocation schedule, which the console output may truncate
+                    let (path, file) = reports.create(&format!("{}_allocations", ticker), "csv")?;
+                    let mut writer = csv::Writer::from_writer(file);
+                    writer.write_record([
+                        "day",
+                        "date",
+                        "allocation",
+                        "amount",
+                        "shares",
+                        "residual",
+                    ])?;
+                    for (i, allocation) in optimal_allocation.iter().enumerate() {
+                        let amount = allocation * initial_investment;
+                        let (shares, residual) = if amount < min_trade {
+                            (0, amount)
+                        } else {
+                            round_to_lots(amount, price, lot_size)
+                        };
+                        writer.write_record([
+                            (i + 1).to_string(),
+                            allocation_date(i).format("%Y-%m-%d").to_string(),
+                            allocation.to_string(),
+                            format!("{:.2}", amount),
+                            shares.to_string(),
+                            format!("{:.2}", residual),
+                        ])?;
+                    }
+                    writer.flush()?;
+                    println!(
+                        "\nThe full allocation schedule has been written to {}",
+                        path.display()
+                    );
 
                     println!("\n--- Disclaimer ---\n");
                     println!("These recommendations are based on historical data and should be considered as a starting point for your investment strategy.");
@@ -240,6 +334,9 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
                 },
             }
         },
+        Err(e) if is_no_data_error(&e) => {
+            eprintln!("Error: {}", e);
+        },
         Err(e) => {
             eprintln!("Error fetching data for ticker {}: {}", ticker, e);
         },
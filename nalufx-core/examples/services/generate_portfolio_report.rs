@@ -8,27 +8,55 @@
 //! 1. Run the code using `cargo run --example performance_measurement`.
 //! 2. Enter the ticker symbol for the desired stock or portfolio when prompted.
 //! 3. Enter the initial investment amount when prompted.
-//! 4. Enter the start date (YYYY-MM-DD) for the analysis period when prompted.
-//! 5. Enter the end date (YYYY-MM-DD) for the analysis period when prompted.
+//! 4. Enter a date range preset (e.g. `1M`, `YTD`, `1Y`) or the start date (YYYY-MM-DD) for the analysis period when prompted.
+//! 5. If you entered a start date, enter the end date (YYYY-MM-DD) for the analysis period when prompted.
 //! 6. The code will fetch historical data, perform analysis, and generate a Gain/Loss Analysis report.
 //!
+//! The performance section compares the equally weighted portfolio with a benchmark using the
+//! annualized Treynor ratio and information ratio. Leave the benchmark prompt blank to select it
+//! from the asset class of the tickers, e.g. `XLE` for clean energy ETFs such as `ICLN`, falling
+//! back to `NALUFX_BENCHMARK_TICKER` (default `SPY`) for a mixed portfolio. Set
+//! `NALUFX_BENCHMARKS` (e.g. `ICLN=TAN,TECHNOLOGY=QQQ`) to override the built-in benchmarks.
+//!
+//! The summary quotes the compound annual growth rate (CAGR) of the portfolio next to its percentage change, so that
+//! analysis periods of different lengths can be compared. For a period shorter than a year, the CAGR is the percentage
+//! change itself.
+//!
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use log::error;
 use nalufx::{
     errors::NaluFxError,
-    services::fetch_data_svc::fetch_data,
+    prompts::load_template,
+    services::{
+        fetch_data_svc::{fetch_data, fetch_dividends},
+        processing_svc::{
+            calculate_cagr, calculate_daily_returns, calculate_dividend_yield, years_between,
+        },
+    },
     utils::{
-        currency::format_currency, date::validate_date, input::get_input, ticker::validate_ticker,
-        validation::validate_positive_float,
+        benchmark::resolve_portfolio_benchmark,
+        currency::format_currency,
+        input::{get_date_range, get_input},
+        risk::{
+            calculate_beta, calculate_information_ratio, calculate_tracking_error,
+            calculate_treynor_ratio,
+        },
+        validation::{validate_positive_float, validate_ticker},
     },
 };
+use nalufx_llms::errors::LlmError;
 use nalufx_llms::llms::openai::{get_openai_api_key, send_openai_request};
 use nalufx_llms::models::openai_dm::OpenAIResponse;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-#[derive(Debug)]
+/// The annual risk-free rate used for the Treynor ratio.
+const RISK_FREE_RATE: f64 = 0.02;
+
+/// Represents the performance and financial indicators of a stock in the portfolio.
+#[derive(Debug, Serialize, Deserialize)]
 struct StockAnalysis {
     ticker: String,
     initial_market_value: f64,
@@ -47,15 +75,9 @@ async fn generate_combined_market_analysis_report(
     stocks: Vec<StockAnalysis>,
     start_date: DateTime<Utc>,
     end_date: DateTime<Utc>,
-) -> Result<String, &'static str> {
+) -> Result<String, NaluFxError> {
     let client = Client::new();
-    let api_key = match get_openai_api_key() {
-        Ok(key) => key,
-        Err(err) => {
-            eprintln!("{}", err);
-            return Err("Failed to get OpenAI API key");
-        },
-    };
+    let api_key = get_openai_api_key()?;
 
     let stock_details: Vec<String> = stocks
         .iter()
@@ -72,7 +94,7 @@ async fn generate_combined_market_analysis_report(
 PEG Ratio: {peg_ratio}
 P/B Ratio: {pb_ratio}
 DPR: {dpr}
-Dividend Yield: {dividend_yield}%",
+Dividend Yield: {dividend_yield:.2}%",
                 ticker = stock.ticker,
                 initial_market_value = format_currency(stock.initial_market_value),
                 final_market_value = format_currency(stock.final_market_value),
@@ -90,63 +112,31 @@ async fn generate_combined_market_analysis_report(
 
     let combined_stock_details = stock_details.join("\n");
 
+    let system_prompt = load_template("portfolio_report_system")?.render(&[])?;
+    let user_prompt = load_template("portfolio_report")?.render(&[
+        ("start_date", &start_date.format("%Y-%m-%d").to_string()),
+        ("end_date", &end_date.format("%Y-%m-%d").to_string()),
+        ("stock_details", &combined_stock_details),
+    ])?;
+
     let request_body = json!({
         "model": "gpt-3.5-turbo",
         "messages": [
-            {
-                "role": "system",
-                "content": "You are a highly skilled financial analyst working for a reputable investment firm. Your task is to generate a comprehensive market analysis report for a portfolio of stocks. The report should be written in a professional tone, similar to reports published by Bloomberg or other leading financial institutions. Provide detailed data-driven insights, quantitative analysis, and actionable recommendations. Please use the following structure:"
-            },
-            {
-                "role": "user",
-                "content": format!(
-                    "
-1. **Executive Summary:** Provide a concise summary of the key findings and recommendations.
-
-2. **Market Overview:**
-    * Analyze the overall market and economic conditions during the analysis period from {start_date} to {end_date}.
-    * Discuss relevant macroeconomic factors, industry trends, and geopolitical events that may have impacted the performance of the portfolio.
-
-3. **Portfolio Performance Analysis:**
-    * Analyze the performance of the portfolio during the specified period.
-    * Compare the performance of individual stocks against benchmarks or stock market indices.
-    * Review key stock indicators, including Earnings Per Share (EPS), Price to Earnings (P/E) ratio, Price to Earnings ratio to Growth ratio (PEG), Price to Book Value ratio (P/B), Dividend Payout ratio (DPR), and Dividend Yield.
-
-4. **Forecast and Recommendations:**
-    * Based on the analysis, provide a forecast for the future performance of the portfolio in the short-term and long-term.
-    * Discuss potential risks and opportunities for the portfolio.
-    * Provide actionable recommendations for investors, such as whether to buy, hold, or sell the stocks or adjust the portfolio allocation.
-
-5. **Conclusion:**
-    * Summarize the key takeaways from the analysis and reiterate the main recommendations.
-    * Discuss any limitations or uncertainties in the analysis and highlight areas for further research or monitoring.
-
-Please ensure that the report is well-structured, easy to understand, and adheres to industry-standard formatting and terminology.
-
-Portfolio Details:
-{combined_stock_details}
-                ",
-                    start_date = start_date.format("%Y-%m-%d"),
-                    end_date = end_date.format("%Y-%m-%d"),
-                    combined_stock_details = combined_stock_details
-                )
-            }
+            { "role": "system", "content": system_prompt },
+            { "role": "user", "content": user_prompt }
         ],
         "max_tokens": 1500,
     });
 
     let openai_url = "https://api.openai.com/v1/chat/completions";
-    let response = send_openai_request(&client, openai_url, &api_key, request_body)
-        .await
-        .map_err(|_| "Failed to send OpenAI request")?;
+    let response = send_openai_request(&client, openai_url, &api_key, request_body).await?;
 
-    let openai_response: OpenAIResponse =
-        serde_json::from_str(&response).map_err(|_| "Failed to parse OpenAI response")?;
+    let openai_response: OpenAIResponse = serde_json::from_str(&response)?;
 
     let generated_text = openai_response
         .choices
         .first()
-        .ok_or("No content found in response")?
+        .ok_or_else(|| LlmError::Other("No content found in response".to_string()))?
         .message
         .content
         .clone();
@@ -176,9 +166,9 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
     let tickers: Vec<&str> = tickers_input.split(',').map(|s| s.trim()).collect();
 
     for ticker in &tickers {
-        if validate_ticker(ticker).is_err() {
-            eprintln!("Error: Invalid ticker symbol: {}", ticker);
-            return Err(NaluFxError::InvalidOption);
+        if let Err(e) = validate_ticker(ticker) {
+            eprintln!("Error: {}", e);
+            return Err(e);
         }
     }
 
@@ -187,34 +177,23 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
         Ok(value) => value,
         Err(e) => {
             eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
+            return Err(e);
         },
     };
 
-    let start_date_input = get_input("Enter the start date (YYYY-MM-DD):")?;
-    let start_date = match validate_date(&start_date_input) {
-        Ok(date) => date,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
-        },
-    };
+    let (start_date, end_date) = get_date_range()?;
 
-    let end_date_input = get_input("Enter the end date (YYYY-MM-DD):")?;
-    let end_date = match validate_date(&end_date_input) {
-        Ok(date) => date,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
-        },
-    };
+    let benchmark_input =
+        get_input("Enter the benchmark ticker symbol (leave blank to select it automatically):")?;
+    let benchmark_ticker = resolve_portfolio_benchmark(&tickers, Some(&benchmark_input));
 
     let mut overall_initial_value = 0.0;
     let mut overall_final_value = 0.0;
     let individual_investment = initial_investment / tickers.len() as f64;
     let mut stock_analyses = Vec::new();
+    let mut stock_returns = Vec::new();
 
-    for ticker in tickers {
+    for &ticker in &tickers {
         // Fetch historical market data for the specified stock or portfolio and date range
         let market_data =
             match fetch_data_with_logging(ticker, Some(start_date), Some(end_date)).await {
@@ -225,6 +204,8 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
                 },
             };
 
+        stock_returns.push(calculate_daily_returns(&market_data));
+
         // Calculate the initial market value
         let initial_market_value = market_data[0] * individual_investment / initial_investment;
 
@@ -245,7 +226,20 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
         let peg_ratio = 1.5;
         let pb_ratio = 3.0;
         let dpr = 0.6;
-        let dividend_yield = 2.5;
+
+        // Trailing twelve-month dividend yield at the end of the analysis period
+        let dividends =
+            match fetch_dividends(ticker, Some(end_date - Duration::days(365)), Some(end_date))
+                .await
+            {
+                Ok(dividends) => dividends,
+                Err(e) => {
+                    error!("Failed to fetch dividends for {}: {}", ticker, e);
+                    Vec::new()
+                },
+            };
+        let dividend_yield =
+            calculate_dividend_yield(&dividends, *market_data.last().unwrap()) * 100.0;
 
         stock_analyses.push(StockAnalysis {
             ticker: ticker.to_string(),
@@ -277,7 +271,7 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
         Ok(report) => report,
         Err(err) => {
             eprintln!("Error generating combined market analysis report: {}", err);
-            return Err(NaluFxError::InvalidData);
+            return Err(err);
         },
     };
 
@@ -297,6 +291,58 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
     println!("Overall Final Market Value: {}", format_currency(overall_final_value));
     println!("Overall Capital Gain/Loss: {}", format_currency(overall_capital_gain_loss));
     println!("Overall Percentage Change: {:.2}%", overall_percentage_change);
+    match calculate_cagr(
+        overall_initial_value,
+        overall_final_value,
+        years_between(start_date, end_date),
+    ) {
+        Ok(cagr) => println!("Overall CAGR: {:.2}%", cagr * 100.0),
+        Err(e) => println!("Overall CAGR: N/A ({})", e),
+    }
+
+    // Print the performance of the equally weighted portfolio against the benchmark
+    println!("\n--- Performance vs Benchmark ({}) ---\n", benchmark_ticker);
+    match fetch_data_with_logging(&benchmark_ticker, Some(start_date), Some(end_date)).await {
+        Ok(benchmark_data) => {
+            let benchmark_returns = calculate_daily_returns(&benchmark_data);
+            let min_length =
+                stock_returns.iter().map(Vec::len).min().unwrap_or(0).min(benchmark_returns.len());
+            let portfolio_returns: Vec<f64> = (0..min_length)
+                .map(|day| {
+                    stock_returns.iter().map(|returns| returns[day]).sum::<f64>()
+                        / stock_returns.len() as f64
+                })
+                .collect();
+            let benchmark_returns = &benchmark_returns[..min_length];
+
+            let format_metric = |metric: Result<f64, NaluFxError>| match metric {
+                Ok(value) => format!("{:.4}", value),
+                Err(e) => format!("N/A ({})", e),
+            };
+            println!(
+                "Beta: {}",
+                format_metric(calculate_beta(&portfolio_returns, benchmark_returns))
+            );
+            println!(
+                "Treynor Ratio (annualized, {:.0}% risk-free rate): {}",
+                RISK_FREE_RATE * 100.0,
+                format_metric(calculate_treynor_ratio(
+                    &portfolio_returns,
+                    benchmark_returns,
+                    RISK_FREE_RATE
+                ))
+            );
+            println!(
+                "Tracking Error (annualized): {}",
+                format_metric(calculate_tracking_error(&portfolio_returns, benchmark_returns))
+            );
+            println!(
+                "Information Ratio (annualized): {}",
+                format_metric(calculate_information_ratio(&portfolio_returns, benchmark_returns))
+            );
+        },
+        Err(e) => eprintln!("Error fetching benchmark data: {}", e),
+    }
 
     // Print the combined report
     println!("\n--- Combined Market Analysis Report ---\n");
@@ -13,55 +13,54 @@
 //! 1. Run the code using `cargo run --example mean_variance_portfolio_optimization`.
 //! 2. Enter the list of assets (e.g., stock tickers) when prompted.
 //! 3. Enter the target return for the portfolio when prompted.
+//! 4. Enter the maximum weight of any one asset when prompted, or leave it blank for no cap.
 
 use nalufx::{
     errors::NaluFxError,
-    services::{fetch_data_svc::fetch_data, processing_svc::calculate_daily_returns},
-    utils::{date::validate_date, input::get_input},
+    services::{
+        fetch_data_svc::{fetch_data, is_no_data_error},
+        processing_svc::calculate_daily_returns,
+        strategy_comparison_svc::constrained_mean_variance_weights,
+    },
+    utils::{
+        allocation::Constraints,
+        input::{get_date_range, get_input},
+    },
 };
-use ndarray::Array2;
+use ndarray::{Array2, Axis};
 use ndarray_stats::CorrelationExt;
 use std::collections::HashMap;
 use std::error::Error;
 
-/// Placeholder function to calculate the mean-variance optimized portfolio.
+/// Calculates the mean-variance optimized portfolio within the given weight constraints.
 ///
 /// # Arguments
 ///
 /// * `assets` - A vector of asset names (e.g., stock tickers).
-/// * `returns_matrix` - The returns matrix of asset returns.
+/// * `returns_matrix` - The returns matrix of asset returns, with one row per asset.
 /// * `cov_matrix` - The covariance matrix of asset returns.
 /// * `_target_return` - The target return for the portfolio (currently unused).
+/// * `constraints` - The bounds of the weight of each asset.
 ///
 /// # Returns
 ///
 /// A `Result` containing a HashMap of the optimized weights for each asset, or an error if the optimization fails.
 fn optimize_mean_variance(
-    assets: &Vec<&str>,
+    assets: &[&str],
     returns_matrix: &Array2<f64>,
     cov_matrix: &Array2<f64>,
     _target_return: f64,
+    constraints: &Constraints,
 ) -> Result<HashMap<String, f64>, Box<dyn Error>> {
-    // Placeholder for actual optimization code
-    // This should use an optimization library to find the optimal weights
-    // For simplicity, we'll just return equal weights
-    let n = assets.len();
-    let equal_weight = 1.0 / n as f64;
-    let mut weights = HashMap::new();
-    for &asset in assets {
-        let _ = weights.insert(asset.to_string(), equal_weight);
-    }
-
     // Debug: Print covariance matrix and mean returns
     println!("Covariance matrix: \n{:?}", cov_matrix);
-    let mean_returns = returns_matrix.mean_axis(ndarray::Axis(1));
-    if let Some(mean_returns) = mean_returns {
-        let _ = mean_returns.to_owned();
-        println!("Mean returns: \n{:?}", mean_returns);
-    } else {
-        eprintln!("Error calculating mean returns.");
-    }
-    Ok(weights)
+    let mean_returns =
+        returns_matrix.mean_axis(Axis(1)).ok_or("Error calculating mean returns.")?;
+    println!("Mean returns: \n{:?}", mean_returns);
+
+    let variances = returns_matrix.var_axis(Axis(1), 1.0);
+    let weights = constrained_mean_variance_weights(&mean_returns, &variances, constraints)?;
+    Ok(assets.iter().map(|asset| asset.to_string()).zip(weights).collect())
 }
 
 /// Main function to run the mean-variance portfolio optimization.
@@ -81,23 +80,7 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
         get_input("Enter the list of assets (comma-separated) - (e.g, AAPL, MSFT, GOOGL):")?;
     let assets: Vec<&str> = assets_input.split(',').map(|s| s.trim()).collect();
 
-    let start_date_input = get_input("Enter the start date (YYYY-MM-DD):")?;
-    let start_date = match validate_date(&start_date_input) {
-        Ok(date) => date,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
-        },
-    };
-
-    let end_date_input = get_input("Enter the end date (YYYY-MM-DD):")?;
-    let end_date = match validate_date(&end_date_input) {
-        Ok(date) => date,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
-        },
-    };
+    let (start_date, end_date) = get_date_range()?;
 
     // Fetch historical performance data for each asset
     let mut asset_data = Vec::new();
@@ -111,6 +94,9 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
                 }
                 asset_data.push((asset, daily_returns));
             },
+            Err(e) if is_no_data_error(e.as_ref()) => {
+                eprintln!("Skipping asset {}: {}", asset, e);
+            },
             Err(e) => {
                 eprintln!("Error fetching data for asset {}: {}", asset, e);
             },
@@ -157,10 +143,28 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
     let target_return: f64 =
         target_return_input.trim().parse().map_err(|_| NaluFxError::InvalidOption)?;
 
+    // Get user input for the largest weight of any one asset
+    let max_weight_input =
+        get_input("Enter the maximum weight of any one asset (e.g., 0.4), or leave blank for 1:")?;
+    let max_weight: f64 = match max_weight_input.trim() {
+        "" => 1.0,
+        input => input.parse().map_err(|_| NaluFxError::InvalidOption)?,
+    };
+    let constraints = Constraints { max_weight, ..Constraints::default() };
+
     // Optimize the portfolio for mean-variance
-    let optimal_weights =
-        optimize_mean_variance(&assets, &returns_array, &cov_matrix, target_return)
-            .map_err(|_| NaluFxError::InvalidOption)?;
+    let asset_names: Vec<&str> = asset_data.iter().map(|(asset, _)| *asset).collect();
+    let optimal_weights = optimize_mean_variance(
+        &asset_names,
+        &returns_array,
+        &cov_matrix,
+        target_return,
+        &constraints,
+    )
+    .map_err(|e| {
+        eprintln!("Error optimizing the portfolio: {}", e);
+        NaluFxError::InvalidOption
+    })?;
 
     // Display the optimal weights
     println!("\n--- Mean-Variance Optimized Portfolio Weights ---\n");
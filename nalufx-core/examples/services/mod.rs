@@ -18,6 +18,8 @@
 pub mod mean_variance_optimization;
 /// Risk Parity Portfolio Optimization Example
 pub mod risk_parity_portfolio_optimization;
+/// Portfolio Strategy Comparison Example
+pub mod strategy_comparison;
 /// Technical Analysis Indicators Example
 pub mod technical_analysis_indicators;
 
@@ -52,6 +54,7 @@ pub(crate) fn main() -> Result<(), NaluFxError> {
     println!("8. Optimize Mean-Variance Portfolio - Use the mean-variance approach for portfolio optimization.");
     println!("9. Balance Risk Parity Portfolio - Allocate risk equally across all assets in your portfolio.");
     println!("10. Perform Technical Analysis - Generate technical indicators for stocks to inform trading decisions.");
+    println!("11. Compare Portfolio Strategies - Compare equal-weight, mean-variance and risk parity portfolios.");
     println!("0. Quit - Exit the application.");
 
     // Read the user's input
@@ -75,6 +78,7 @@ pub(crate) fn main() -> Result<(), NaluFxError> {
         Ok(8) => mean_variance_optimization::main()?,
         Ok(9) => risk_parity_portfolio_optimization::main()?,
         Ok(10) => technical_analysis_indicators::main()?,
+        Ok(11) => strategy_comparison::main()?,
         Ok(0) => {
             println!("\nExiting NaluFX, goodbye!\n");
             return Ok(());
@@ -11,18 +11,28 @@
 //!
 //! Usage:
 //! 1. Run the code using `cargo run --example risk_parity_portfolio_optimization`.
-//! 2. Enter the list of assets (e.g., stock tickers) when prompted.
+//! 2. Enter the list of assets (e.g., stock tickers) when prompted, or set `PORTFOLIO_CSV` to a CSV file with
+//!    `ticker,shares_or_weight` columns to read the assets from it instead.
 
-use nalgebra::{DMatrix, DVector};
 use nalufx::{
     errors::NaluFxError,
-    services::{fetch_data_svc::fetch_data, processing_svc::calculate_daily_returns},
-    utils::input::get_input,
+    services::{
+        fetch_data_svc::{fetch_data, is_no_data_error},
+        processing_svc::{calculate_daily_returns, calculate_volatility},
+        strategy_comparison_svc::{
+            risk_contributions, solve_risk_parity, RiskParityOptions, RiskParitySolution,
+        },
+    },
+    utils::{portfolio::get_portfolio_tickers, risk::diversification_ratio},
 };
 use ndarray::Array2;
 use ndarray_stats::CorrelationExt;
 use std::collections::HashMap;
 
+/// The largest deviation of a risk contribution from an equal share that is reported as a
+/// solved risk parity portfolio.
+const MAX_CONTRIBUTION_DEVIATION: f64 = 1e-6;
+
 /// Function to optimize the portfolio for risk parity.
 ///
 /// # Arguments
@@ -32,99 +42,19 @@
 ///
 /// # Returns
 ///
-/// A HashMap containing the optimized weights for each asset.
-fn optimize_risk_parity(assets: &Vec<&str>, cov_matrix: &Array2<f64>) -> HashMap<String, f64> {
-    let num_assets = assets.len();
-
-    // Convert the covariance matrix shape to a tuple
-    let cov_matrix_shape = (cov_matrix.nrows(), cov_matrix.ncols());
-
-    // Check if the covariance matrix has the expected shape
-    if cov_matrix_shape != (num_assets, num_assets) {
-        panic!(
-            "Covariance matrix shape ({:?}) does not match the number of assets ({})",
-            cov_matrix_shape, num_assets
-        );
-    }
-
-    // Convert covariance matrix to a Vec<f64>
-    let cov_matrix_vec = cov_matrix.iter().cloned().collect::<Vec<f64>>();
-
-    // Create DMatrix from the covariance matrix Vec<f64>
-    let cov_matrix_nalgebra = DMatrix::from_row_slice(num_assets, num_assets, &cov_matrix_vec);
-
-    // Define the objective function for risk parity
-    let objective = |weights: &DVector<f64>| {
-        let portfolio_var = weights.transpose() * &cov_matrix_nalgebra * weights;
-        let portfolio_std_dev = portfolio_var[(0, 0)].sqrt();
-        let risk_contributions = &cov_matrix_nalgebra * weights / portfolio_std_dev;
-        let mean_risk_contribution = risk_contributions.mean();
-        let risk_diffs = risk_contributions.map(|x| x - mean_risk_contribution);
-        risk_diffs.norm_squared()
-    };
-
-    // Define the initial guess for weights
-    let mut weights = DVector::from_element(num_assets, 1.0 / num_assets as f64);
-
-    // Perform optimization using gradient descent
-    let mut learning_rate = 0.1;
-    let max_iterations = 100;
-    let tolerance = 1e-6;
-
-    for _ in 0..max_iterations {
-        let grad = numerical_gradient(&objective, &weights);
-        let new_weights = &weights - learning_rate * &grad;
-
-        // Normalize weights to sum to 1
-        let sum_weights = new_weights.sum();
-        let normalized_weights = new_weights / sum_weights;
-
-        if (&normalized_weights - &weights).norm() < tolerance {
-            weights = normalized_weights;
-            break;
-        }
-
-        weights = normalized_weights;
-        learning_rate *= 0.95;
-    }
-
-    // Convert optimized weights to a HashMap
-    let mut weights_map = HashMap::new();
-    for (i, &asset) in assets.iter().enumerate() {
-        let _ = weights_map.insert(asset.to_string(), weights[i]);
-    }
-
-    weights_map
-}
-
-/// Function to calculate the numerical gradient of a given function.
-///
-/// # Arguments
-///
-/// * `f` - The function for which to calculate the numerical gradient.
-/// * `x` - The input vector at which to calculate the gradient.
-///
-/// # Returns
-///
-/// The numerical gradient of the function at the given input vector.
-fn numerical_gradient<F>(f: &F, x: &DVector<f64>) -> DVector<f64>
-where
-    F: Fn(&DVector<f64>) -> f64,
-{
-    let eps = 1e-8;
-    let mut grad = DVector::zeros(x.nrows());
-    let mut x_perturb = x.clone();
-
-    for i in 0..x.nrows() {
-        x_perturb[i] += eps;
-        let f_plus = f(&x_perturb);
-        x_perturb[i] -= 2.0 * eps;
-        let f_minus = f(&x_perturb);
-        grad[i] = (f_plus - f_minus) / (2.0 * eps);
-        x_perturb[i] = x[i];
-    }
-
-    grad
+/// A HashMap containing the optimized weights for each asset, and the solution of the
+/// optimization.
+fn optimize_risk_parity(
+    assets: &[&str],
+    cov_matrix: &Array2<f64>,
+) -> (HashMap<String, f64>, RiskParitySolution) {
+    let solution = solve_risk_parity(cov_matrix, &RiskParityOptions::default());
+    let weights = assets
+        .iter()
+        .zip(&solution.weights)
+        .map(|(asset, &weight)| (asset.to_string(), weight))
+        .collect();
+    (weights, solution)
 }
 
 /// Main function to run the risk parity portfolio optimization.
@@ -138,10 +68,11 @@ fn numerical_gradient<F>(f: &F, x: &DVector<f64>) -> DVector<f64>
 /// If an error occurs, returns an `Err` variant containing the error message.
 #[tokio::main]
 pub(crate) async fn main() -> Result<(), NaluFxError> {
-    // Get user input for list of assets
-    let assets_input =
-        get_input("Enter the list of assets (comma-separated) - (e.g, SPY, EFA, GLD, IEF):")?;
-    let assets: Vec<&str> = assets_input.split(',').map(|s| s.trim()).collect();
+    // Get the list of assets from the portfolio file or the user input
+    let assets_input = get_portfolio_tickers(
+        "Enter the list of assets (comma-separated) - (e.g, SPY, EFA, GLD, IEF):",
+    )?;
+    let assets: Vec<&str> = assets_input.iter().map(String::as_str).collect();
 
     // Fetch historical performance data for each asset
     let mut asset_data = Vec::new();
@@ -158,6 +89,9 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
                 min_returns_length = min_returns_length.min(daily_returns.len());
                 asset_data.push((asset, daily_returns));
             },
+            Err(e) if is_no_data_error(e.as_ref()) => {
+                eprintln!("Skipping asset {}: {}", asset, e);
+            },
             Err(e) => {
                 eprintln!("Error fetching data for asset {}: {}", asset, e);
             },
@@ -197,8 +131,16 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
 
     let cov_matrix = returns_array.cov(1.0).map_err(|_| NaluFxError::InvalidOption)?;
 
-    // Optimize the portfolio for risk parity
-    let optimal_weights = optimize_risk_parity(&assets, &cov_matrix);
+    // Optimize the portfolio for risk parity, over the assets in the covariance matrix order
+    let fetched_assets: Vec<&str> = asset_data.iter().map(|(asset, _)| *asset).collect();
+    let (optimal_weights, solution) = optimize_risk_parity(&fetched_assets, &cov_matrix);
+
+    // Calculate the diversification ratio, with the weights in the covariance matrix order
+    let weights: Vec<f64> = asset_data
+        .iter()
+        .map(|(asset, _)| optimal_weights.get(*asset).copied().unwrap_or(0.0))
+        .collect();
+    let diversification = diversification_ratio(&weights, &cov_matrix);
 
     // Display trailing performance for each asset
     println!("\n--- Trailing Performance ---\n");
@@ -211,10 +153,12 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
             })
             .collect();
         let total_return = cumulative_returns.last().unwrap_or(&1.0) - 1.0;
+        let volatility = calculate_volatility(&returns, true).unwrap_or(f64::NAN);
         println!(
-            "{}: Total Return = {:.2}%, Final Value = ${:.2}",
+            "{}: Total Return = {:.2}%, Annualized Volatility = {:.2}%, Final Value = ${:.2}",
             asset,
             total_return * 100.0,
+            volatility * 100.0,
             cumulative_returns.last().unwrap_or(&1.0)
         );
     }
@@ -229,5 +173,31 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
         println!("{}: {:.2}%", asset, weight * 100.0);
     }
 
+    // Verify that the weights equalize the risk contributions
+    println!("\n--- Risk Contributions ---\n");
+    let contributions = risk_contributions(&weights, &cov_matrix);
+    for (asset, contribution) in fetched_assets.iter().zip(&contributions) {
+        println!("{}: {:.2}%", asset, contribution * 100.0);
+    }
+    println!(
+        "\nMax deviation from equal contribution: {:.2e} ({} sweeps)",
+        solution.max_deviation, solution.sweeps
+    );
+    if !solution.converged
+        || solution.max_deviation.is_nan()
+        || solution.max_deviation > MAX_CONTRIBUTION_DEVIATION
+    {
+        eprintln!(
+            "Warning: the risk parity optimization did not converge, so the risk contributions \
+             may not be equal."
+        );
+    }
+
+    // Display the diversification ratio
+    match diversification {
+        Ok(ratio) => println!("\nDiversification Ratio: {:.2}", ratio),
+        Err(e) => eprintln!("Error calculating the diversification ratio: {}", e),
+    }
+
     Ok(())
 }
@@ -0,0 +1,97 @@
+//! # Portfolio Strategy Comparison Example
+//!
+//! This example demonstrates how to compare several portfolio construction strategies on the same universe of assets.
+//! The code fetches historical performance data for a set of assets, derives the weights of the equal-weight,
+//! mean-variance and risk parity strategies, as well as of each asset held on its own, and compares the return,
+//! volatility and Sharpe ratio that each strategy would have achieved over the same period.
+//!
+//! Note: the weights are fitted to the same history they are measured on, so the comparison is in-sample and flatters
+//! the optimized strategies. It is no forecast of their future performance.
+//!
+//! Usage:
+//! 1. Run the code using `cargo run --example strategy_comparison`.
+//! 2. Enter the list of assets (e.g., stock tickers) when prompted, or set `PORTFOLIO_CSV` to a CSV file with
+//!    `ticker,shares_or_weight` columns to read the assets from it instead.
+
+use nalufx::{
+    errors::NaluFxError,
+    services::{
+        fetch_data_svc::{fetch_data, is_no_data_error},
+        processing_svc::calculate_daily_returns,
+        strategy_comparison_svc::{compare_strategies, format_comparison_table, Strategy},
+    },
+    utils::portfolio::get_portfolio_tickers,
+};
+use ndarray::Array2;
+
+/// Main function to run the portfolio strategy comparison.
+///
+/// This function prompts the user to enter a list of assets, fetches historical performance data for each asset,
+/// runs every strategy on the daily returns, and displays the weights of each strategy and a comparison table.
+///
+/// # Returns
+///
+/// A `Result` indicating the success or failure of the operation. If successful, returns `Ok(())`.
+/// If an error occurs, returns an `Err` variant containing the error message.
+#[tokio::main]
+pub(crate) async fn main() -> Result<(), NaluFxError> {
+    // Get the list of assets from the portfolio file or the user input
+    let assets_input = get_portfolio_tickers(
+        "Enter the list of assets (comma-separated) - (e.g, SPY, EFA, GLD, IEF):",
+    )?;
+
+    // Fetch historical performance data for each asset
+    let mut asset_data = Vec::new();
+    for asset in &assets_input {
+        match fetch_data(asset, None, None).await {
+            Ok(closes) => {
+                let daily_returns = calculate_daily_returns(&closes);
+                if daily_returns.len() < 2 {
+                    eprintln!("Insufficient data for asset {}", asset);
+                    continue;
+                }
+                asset_data.push((asset.as_str(), daily_returns));
+            },
+            Err(e) if is_no_data_error(e.as_ref()) => {
+                eprintln!("Skipping asset {}: {}", asset, e);
+            },
+            Err(e) => {
+                eprintln!("Error fetching data for asset {}: {}", asset, e);
+            },
+        }
+    }
+
+    if asset_data.is_empty() {
+        println!("No asset data available for analysis.");
+        return Ok(());
+    }
+
+    // Keep the most recent returns common to all assets, with one row per day and one column per asset
+    let num_days = asset_data.iter().map(|(_, returns)| returns.len()).min().unwrap_or(0);
+    let returns = Array2::from_shape_fn((num_days, asset_data.len()), |(day, asset)| {
+        let asset_returns = &asset_data[asset].1;
+        asset_returns[asset_returns.len() - num_days + day]
+    });
+
+    let mut strategies = vec![Strategy::EqualWeight, Strategy::MeanVariance, Strategy::RiskParity];
+    strategies.extend((0..asset_data.len()).map(Strategy::SingleAsset));
+    let results = compare_strategies(&returns, &strategies);
+
+    // Display the weights of each strategy
+    println!("\n--- Strategy Weights ---\n");
+    for result in &results {
+        let weights: Vec<String> = asset_data
+            .iter()
+            .zip(&result.weights)
+            .map(|((asset, _), weight)| format!("{}: {:.2}%", asset, weight * 100.0))
+            .collect();
+        println!("{}: {}", result.strategy, weights.join(", "));
+    }
+
+    // Display the comparison table
+    println!("\n--- In-Sample Comparison over {} Days ---\n", num_days);
+    println!("{}", format_comparison_table(&results));
+    println!("\nSingle Asset #N holds the N-th asset above on its own.");
+
+    Ok(())
+}
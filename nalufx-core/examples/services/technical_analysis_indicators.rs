@@ -11,20 +11,51 @@
 //! 2. Enter the ticker symbol for the desired stock when prompted.
 //! 3. The code will fetch historical data for the stock, calculate technical indicators, and generate a report with the analysis.
 //!
+//! The report is also written to a timestamped file in `./reports`, in the directory set by `NALUFX_REPORTS_DIR`, or in the
+//! directory given with `--output-dir`.
+//!
+//! The indicator windows default to a 50-period EMA, a 14-period RSI and a 12/26/9 MACD, and can be changed with
+//! `--ema-window`, `--rsi-window`, `--macd-short`, `--macd-long` and `--macd-signal`. Every window must be positive, and
+//! the short MACD window shorter than the long one. The EMAs start at the first close by default; `--ema-seed sma` starts
+//! them at the simple average of their first window instead, the conventional seed, which makes the early MACD values more
+//! accurate.
+//!
+//! Re-running the analysis with the same inputs sends the same prompt to OpenAI. Pass `--cache-llm` to reuse the report of
+//! an identical prompt from the LLM response cache instead of spending tokens on it again. The cache is stored in
+//! `LLM_CACHE_DIR` (`.cache/nalufx/llm` by default) and its entries expire after `LLM_CACHE_TTL_SECS` seconds (a day by
+//! default). The cached request sends the system instructions ahead of the user prompt in a single message.
+//!
 //! Technical Indicators:
 //! - Exponential Moving Average (EMA): Calculates the exponentially weighted average price over a specified number of periods. It gives more weight to recent prices, making it more responsive to price changes. It helps identify the overall trend direction.
 //! - Relative Strength Index (RSI): Measures the magnitude of recent price changes to evaluate overbought or oversold conditions. It ranges from 0 to 100.
 //! - Moving Average Convergence Divergence (MACD): Consists of MACD line, signal line, and histogram. It helps identify trend changes and momentum.
 //! - Support and Resistance Levels: Represents key price levels where the stock tends to find support or resistance. They are used to identify potential entry and exit points.
+//! - On-Balance Volume (OBV): Adds the volume on up days and subtracts it on down days to show whether volume confirms the price trend. Divergences, where the price makes a new high or low that OBV does not, are flagged as potential reversals.
+//! - Ichimoku Cloud: Combines the Tenkan-sen, Kijun-sen, Senkou Spans A and B, and Chikou Span, calculated from the highs, lows, and closes, to show trend, momentum, and support and resistance at a glance.
 
+use clap::Parser;
 use nalufx::{
     errors::NaluFxError,
-    services::fetch_data_svc::fetch_data,
-    utils::{date::validate_date, input::get_input, ticker::validate_ticker},
+    prompts::load_template,
+    services::{data_quality_svc::assess_quality, fetch_data_svc::fetch_ohlcv_data},
+    utils::{
+        args::TechnicalAnalysisArgs,
+        indicators::{
+            calculate_ema_with_seed, calculate_ichimoku, calculate_obv, EmaSeed, IchimokuResult,
+        },
+        input::{get_date_range, get_input},
+        validation::validate_ticker,
+    },
+};
+use nalufx_llms::errors::LlmError;
+use nalufx_llms::llms::{
+    cache::LlmCacheConfig,
+    openai::{get_openai_api_key, send_openai_request, OpenAI},
+    LLM,
 };
-use nalufx_llms::llms::openai::{get_openai_api_key, send_openai_request};
 use nalufx_llms::models::openai_dm::OpenAIResponse;
 use serde_json::json;
+use std::io::{self, Write};
 
 /// Calculates the relative strength index (RSI) for the given data and window size.
 ///
@@ -70,40 +101,6 @@ fn calculate_rsi(data: &[f64], window: usize) -> Vec<f64> {
     rsi
 }
 
-/// Calculates the exponential moving average (EMA) for the given data and window size.
-///
-/// # Arguments
-///
-/// * `data` - The slice of price data.
-/// * `window` - The window size for the EMA calculation.
-///
-/// # Returns
-///
-/// A vector of EMA values.
-///
-/// The EMA calculation gives more weight to recent data points, making it more responsive to recent price changes.
-/// The EMA is calculated using the following formula:
-///
-/// ```
-/// EMA = (Close - EMA(prev)) * Multiplier + EMA(prev)
-/// ```
-///
-/// Where:
-/// - `Close` is the current closing price
-/// - `EMA(prev)` is the previous EMA value
-/// - `Multiplier` is a smoothing factor calculated as `2 / (window + 1)`
-fn calculate_ema(data: &[f64], window: usize) -> Vec<f64> {
-    let mut ema = vec![data[0]];
-    let multiplier = 2.0 / (window as f64 + 1.0);
-
-    for i in 1..data.len() {
-        let current_ema = (data[i] - ema[i - 1]) * multiplier + ema[i - 1];
-        ema.push(current_ema);
-    }
-
-    ema
-}
-
 /// Calculates the moving average convergence divergence (MACD) for the given data.
 ///
 /// # Arguments
@@ -112,25 +109,33 @@ fn calculate_ema(data: &[f64], window: usize) -> Vec<f64> {
 /// * `short_window` - The short window size for the MACD calculation.
 /// * `long_window` - The long window size for the MACD calculation.
 /// * `signal_window` - The window size for the signal line calculation.
+/// * `seed` - How the EMAs are seeded.
 ///
 /// # Returns
 ///
-/// A tuple containing the MACD values, signal line values, and histogram values.
+/// A tuple containing the MACD values, signal line values, and histogram values, each aligned
+/// with the end of the data.
 fn calculate_macd(
     data: &[f64],
     short_window: usize,
     long_window: usize,
     signal_window: usize,
+    seed: EmaSeed,
 ) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
-    let short_ema = calculate_ema(data, short_window);
-    let long_ema = calculate_ema(data, long_window);
+    let short_ema = calculate_ema_with_seed(data, short_window, seed);
+    let long_ema = calculate_ema_with_seed(data, long_window, seed);
 
-    let macd: Vec<f64> =
-        short_ema.iter().zip(long_ema.iter()).map(|(short, long)| short - long).collect();
+    // An SMA-seeded EMA starts later the longer its window, so the series are aligned on
+    // their last values.
+    let macd: Vec<f64> = short_ema[short_ema.len().saturating_sub(long_ema.len())..]
+        .iter()
+        .zip(long_ema.iter())
+        .map(|(short, long)| short - long)
+        .collect();
 
-    let signal = calculate_ema(&macd, signal_window);
+    let signal = calculate_ema_with_seed(&macd, signal_window, seed);
 
-    let histogram: Vec<f64> = macd
+    let histogram: Vec<f64> = macd[macd.len().saturating_sub(signal.len())..]
         .iter()
         .zip(signal.iter())
         .map(|(macd_val, signal_val)| macd_val - signal_val)
@@ -168,6 +173,91 @@ fn identify_support_resistance(data: &[f64], window: usize) -> (Vec<f64>, Vec<f6
     (support, resistance)
 }
 
+/// Represents a divergence between the price and the On-Balance Volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ObvDivergence {
+    /// The price made a new low that OBV did not confirm.
+    Bullish,
+    /// The price made a new high that OBV did not confirm.
+    Bearish,
+}
+
+/// Finds the periods where the price makes a new high or low over the window but OBV does not.
+///
+/// # Arguments
+///
+/// * `closes` - The slice of closing prices.
+/// * `obv` - The On-Balance Volume of the closing prices.
+/// * `window` - The number of preceding periods the new high or low is measured against.
+///
+/// # Returns
+///
+/// A vector of `(index, divergence)` pairs in chronological order.
+fn find_obv_divergences(closes: &[f64], obv: &[f64], window: usize) -> Vec<(usize, ObvDivergence)> {
+    let max = |values: &[f64]| values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let min = |values: &[f64]| values.iter().copied().fold(f64::INFINITY, f64::min);
+
+    (window..closes.len().min(obv.len()))
+        .filter_map(|i| {
+            let (prior_closes, prior_obv) = (&closes[i - window..i], &obv[i - window..i]);
+            if closes[i] > max(prior_closes) && obv[i] <= max(prior_obv) {
+                Some((i, ObvDivergence::Bearish))
+            } else if closes[i] < min(prior_closes) && obv[i] >= min(prior_obv) {
+                Some((i, ObvDivergence::Bullish))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Writes the latest Ichimoku Cloud lines and the position of the last close against the cloud.
+///
+/// # Arguments
+///
+/// * `out` - The writer to write the summary to.
+/// * `ichimoku` - The calculated Ichimoku Cloud.
+/// * `closing_prices` - The slice of historical closing prices used to calculate it.
+fn write_ichimoku_summary(
+    out: &mut impl Write,
+    ichimoku: &IchimokuResult,
+    closing_prices: &[f64],
+) -> io::Result<()> {
+    let format_line = |value: Option<f64>| value.map_or("N/A".to_string(), |v| format!("{:.2}", v));
+    let last = closing_prices.len() - 1;
+    let (span_a, span_b) = (ichimoku.senkou_span_a[last], ichimoku.senkou_span_b[last]);
+
+    writeln!(out, "Tenkan-sen (9): {}", format_line(ichimoku.tenkan_sen[last]))?;
+    writeln!(out, "Kijun-sen (26): {}", format_line(ichimoku.kijun_sen[last]))?;
+    writeln!(out, "Senkou Span A (today): {}", format_line(span_a))?;
+    writeln!(out, "Senkou Span B (today): {}", format_line(span_b))?;
+    writeln!(
+        out,
+        "Senkou Span A (in 26 periods): {}",
+        format_line(ichimoku.senkou_span_a.last().copied().flatten())
+    )?;
+    writeln!(
+        out,
+        "Senkou Span B (in 26 periods): {}",
+        format_line(ichimoku.senkou_span_b.last().copied().flatten())
+    )?;
+
+    if let (Some(span_a), Some(span_b)) = (span_a, span_b) {
+        let close = closing_prices[last];
+        let position = if close > span_a.max(span_b) {
+            "above the cloud (bullish)"
+        } else if close < span_a.min(span_b) {
+            "below the cloud (bearish)"
+        } else {
+            "inside the cloud (neutral)"
+        };
+        writeln!(out, "The last close of {:.2} is {}.", close, position)?;
+    } else {
+        writeln!(out, "Not enough data to plot the cloud; at least 78 periods are required.")?;
+    }
+    Ok(())
+}
+
 /// Generates a professional technical analysis report using the OpenAI API.
 ///
 /// # Arguments
@@ -180,6 +270,7 @@ fn identify_support_resistance(data: &[f64], window: usize) -> (Vec<f64>, Vec<f6
 /// * `macd_histogram` - The calculated MACD histogram values.
 /// * `support_levels` - The identified support levels.
 /// * `resistance_levels` - The identified resistance levels.
+/// * `cache` - The LLM response cache to reuse the report of an identical prompt from, if any.
 ///
 /// Returns the generated report as a string.
 async fn generate_technical_analysis_report(
@@ -191,84 +282,52 @@ async fn generate_technical_analysis_report(
     macd_histogram: &[f64],
     support_levels: &[f64],
     resistance_levels: &[f64],
-) -> Result<String, &'static str> {
+    cache: Option<&LlmCacheConfig>,
+) -> Result<String, NaluFxError> {
     let client = reqwest::Client::new();
-    let api_key = match get_openai_api_key() {
-        Ok(key) => key,
-        Err(err) => {
-            eprintln!("{}", err);
-            return Err("Failed to get OpenAI API key");
+    let api_key = get_openai_api_key()?;
+
+    let system_prompt = load_template("technical_analysis_system")?.render(&[])?;
+    let user_prompt = load_template("technical_analysis")?.render(&[
+        ("closing_prices", &format!("{:?}", closing_prices)),
+        ("ema", &format!("{:?}", ema)),
+        ("rsi", &format!("{:?}", rsi)),
+        ("macd", &format!("{:?}", macd)),
+        ("macd_signal", &format!("{:?}", macd_signal)),
+        ("macd_histogram", &format!("{:?}", macd_histogram)),
+        ("support_levels", &format!("{:?}", support_levels)),
+        ("resistance_levels", &format!("{:?}", resistance_levels)),
+    ])?;
+
+    let max_tokens = 1500;
+    let openai_response: OpenAIResponse = match cache {
+        Some(cache) => {
+            let prompt = format!("{}\n\n{}", system_prompt, user_prompt);
+            let response =
+                OpenAI.send_request_cached(&client, &api_key, &prompt, max_tokens, cache).await?;
+            serde_json::from_value(response)?
+        },
+        None => {
+            let request_body = json!({
+                "model": "gpt-3.5-turbo",
+                "messages": [
+                    { "role": "system", "content": system_prompt },
+                    { "role": "user", "content": user_prompt }
+                ],
+                "max_tokens": max_tokens,
+            });
+
+            let openai_url = "https://api.openai.com/v1/chat/completions";
+            let response = send_openai_request(&client, openai_url, &api_key, request_body).await?;
+            serde_json::from_str(&response)?
         },
     };
 
-    let request_body = json!({
-        "model": "gpt-3.5-turbo",
-        "messages": [
-            {
-                "role": "system",
-                "content": "You are a highly skilled financial analyst working for a reputable investment firm. Your task is to generate a comprehensive technical analysis report for a portfolio of stocks. The report should be written in a professional tone, similar to reports published by Bloomberg or other leading financial institutions. Provide detailed data-driven insights, quantitative analysis, and actionable recommendations. Please use the following structure:"
-            },
-            {
-                "role": "user",
-                "content": format!(
-                    "
-1. **Executive Summary:** Provide a concise summary of the key findings and recommendations.
-
-2. **Market Overview:**
-    * Analyze the overall market and economic conditions during the analysis period.
-    * Discuss relevant macroeconomic factors, industry trends, and geopolitical events.
-
-3. **Portfolio Performance:**
-    * Closing Prices: {:?}
-    * EMA Values: {:?}
-    * RSI Values: {:?}
-    * MACD Values: {:?}
-    * MACD Signal: {:?}
-    * MACD Histogram: {:?}
-    * Analyze the performance of each stock in the portfolio, including closing prices, trend analysis (based on EMA), momentum analysis (based on RSI), and convergence/divergence analysis (based on MACD). 
-    * Explicitly mention the calculated values for each indicator.
-
-4. **Risk Assessment:**
-    * Support Levels: {:?}
-    * Resistance Levels: {:?}
-    * Identify potential support and resistance levels for each stock.
-    * Discuss the implications for risk management strategies, such as setting appropriate stop-loss and take-profit levels.
-
-5. **Investment Strategies:**
-    * Based on the technical analysis, provide recommendations for the following investment strategies:
-        * **Flexible Income Strategy:** For investors seeking a flexible income stream (e.g., using drawdown income) at their target retirement age and beyond.
-        * **Annuity Purchase Strategy:** For investors aiming to purchase a regular income (annuity) from an insurance company at their target retirement age and beyond.
-        * **Lump Sum Strategy:** For investors seeking one or two cash lump sums at their target retirement age and beyond.
-        * For each strategy, outline specific stock recommendations, entry/exit points, and risk management considerations.
-
-6. **Disclaimer:**
-    * Emphasize that this report is for informational purposes only and does not constitute financial advice.
-    * Encourage readers to conduct their own research and consult with financial advisors before making investment decisions.
-
-Please ensure that the report is well-structured, easy to understand, and adheres to industry-standard formatting and terminology.
-                ", closing_prices, ema, rsi, macd, macd_signal, macd_histogram, support_levels, resistance_levels
-                )
-            }
-        ],
-        "max_tokens": 1500,
-    });
-
-    let openai_url = "https://api.openai.com/v1/chat/completions";
-    let response = match send_openai_request(&client, openai_url, &api_key, request_body).await {
-        Ok(response) => response,
-        Err(err) => return Err(err),
-    };
-
-    let openai_response: OpenAIResponse = serde_json::from_str(&response).map_err(|err| {
-        eprintln!("Error parsing response JSON: {:?}", err);
-        "Error parsing response JSON"
-    })?;
-
     let generated_text = openai_response
         .choices
         .first()
-        .and_then(|choice| Some(choice.message.content.clone()))
-        .ok_or("No content found in response")?;
+        .map(|choice| choice.message.content.clone())
+        .ok_or_else(|| LlmError::Other("No content found in response".to_string()))?;
 
     Ok(generated_text)
 }
@@ -278,7 +337,7 @@ async fn generate_technical_analysis_report(
 /// This function handles the following tasks:
 ///
 /// 1. Prompts the user to enter the stock ticker symbol.
-/// 2. Prompts the user to enter the start and end dates for the historical data period.
+/// 2. Prompts the user to enter a date range preset, or the start and end dates, for the historical data period.
 /// 3. Fetches the historical closing prices for the specified stock and date range.
 /// 4. Calculates various technical indicators, including:
 ///     - Exponential Moving Average (EMA)
@@ -288,43 +347,38 @@ async fn generate_technical_analysis_report(
 /// 5. Generates a comprehensive technical analysis report with the calculated indicator values and interpretations.
 /// 6. Provides specific recommendations based on the technical analysis.
 /// 7. Includes risk management guidelines and a disclaimer.
+/// 8. Prints the report and writes it to a timestamped file in the reports directory.
 ///
 /// # Returns
 ///
 /// A `Result` indicating whether the execution was successful or not. If successful, returns `Ok(())`, otherwise returns an error wrapped in a `Box<dyn std::error::Error>`.
 #[tokio::main]
 pub(crate) async fn main() -> Result<(), NaluFxError> {
-    // Get user input for ticker, initial investment amount, start date, and end date
-    let ticker_input = get_input("Enter the ticker symbol for a bellwether stock:")?;
-    let ticker = match validate_ticker(&ticker_input) {
-        Ok(symbol) => symbol.to_string(),
+    let args = TechnicalAnalysisArgs::parse();
+    let reports = args.report_sink();
+    let windows = match args.windows() {
+        Ok(windows) => windows,
         Err(e) => {
             eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
+            return Err(e);
         },
     };
 
-    // Fetch historical price data for the stock
-    let start_date_input = get_input("Enter the start date (YYYY-MM-DD):")?;
-    let start_date = match validate_date(&start_date_input) {
-        Ok(date) => date,
+    // Get user input for ticker, initial investment amount, start date, and end date
+    let ticker_input = get_input("Enter the ticker symbol for a bellwether stock:")?;
+    let ticker = match validate_ticker(&ticker_input) {
+        Ok(symbol) => symbol.to_string(),
         Err(e) => {
             eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
+            return Err(e);
         },
     };
 
-    let end_date_input = get_input("Enter the end date (YYYY-MM-DD):")?;
-    let end_date = match validate_date(&end_date_input) {
-        Ok(date) => date,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
-        },
-    };
+    // Fetch historical price data for the stock
+    let (start_date, end_date) = get_date_range()?;
 
-    let closing_prices = match fetch_data(&ticker, Some(start_date), Some(end_date)).await {
-        Ok(prices) => prices,
+    let sessions = match fetch_ohlcv_data(&ticker, Some(start_date), Some(end_date)).await {
+        Ok(sessions) => sessions,
         Err(e) => {
             eprintln!("Error fetching historical data: {}", e);
             return Err(NaluFxError::ForecastingError(
@@ -334,22 +388,37 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
         },
     };
 
+    // Summarize the quality of the fetched closes before analyzing them
+    let series: Vec<_> =
+        sessions.iter().map(|session| (session.timestamp, session.close)).collect();
+    println!("{}", assess_quality(&series));
+
+    let highs: Vec<f64> = sessions.iter().map(|session| session.high).collect();
+    let lows: Vec<f64> = sessions.iter().map(|session| session.low).collect();
+    let closing_prices: Vec<f64> = sessions.iter().map(|session| session.close).collect();
+
     // Calculate technical indicators
-    let ema_window = 50;
-    let rsi_window = 14;
-    let macd_short_window = 12;
-    let macd_long_window = 26;
-    let macd_signal_window = 9;
     let support_resistance_window = 10;
 
-    let ema = calculate_ema(&closing_prices, ema_window);
-    let rsi = calculate_rsi(&closing_prices, rsi_window);
-    let (macd, macd_signal, macd_histogram) =
-        calculate_macd(&closing_prices, macd_short_window, macd_long_window, macd_signal_window);
+    let ema = calculate_ema_with_seed(&closing_prices, windows.ema, args.ema_seed);
+    let rsi = calculate_rsi(&closing_prices, windows.rsi);
+    let (macd, macd_signal, macd_histogram) = calculate_macd(
+        &closing_prices,
+        windows.macd_short,
+        windows.macd_long,
+        windows.macd_signal,
+        args.ema_seed,
+    );
     let (support_levels, resistance_levels) =
         identify_support_resistance(&closing_prices, support_resistance_window);
-
-    // Generate the professional technical analysis report
+    let ichimoku = calculate_ichimoku(&highs, &lows, &closing_prices)?;
+    let volumes: Vec<f64> = sessions.iter().map(|session| session.volume).collect();
+    let obv = calculate_obv(&closing_prices, &volumes)?;
+    let obv_divergence_window = 20;
+    let obv_divergences = find_obv_divergences(&closing_prices, &obv, obv_divergence_window);
+
+    // Generate the professional technical analysis report, from the cache if enabled
+    let cache = args.cache_llm.then(LlmCacheConfig::from_env);
     let report = match generate_technical_analysis_report(
         &closing_prices,
         &ema,
@@ -359,57 +428,91 @@ pub(crate) async fn main() -> Result<(), NaluFxError> {
         &macd_histogram,
         &support_levels,
         &resistance_levels,
+        cache.as_ref(),
     )
     .await
     {
         Ok(report) => report,
         Err(err) => {
-            eprintln!("Error: {}", err);
-            return Err(NaluFxError::ReinforcementLearningError(
-                "Failed to generate technical analysis report".to_string(),
-            )
-            .into());
+            eprintln!("Error generating technical analysis report: {}", err);
+            return Err(err);
         },
     };
 
-    println!("\n--- Professional Technical Analysis Report ---\n");
-    println!("Ticker: {}", ticker);
-    println!("Period: {} to {}", start_date.format("%Y-%m-%d"), end_date.format("%Y-%m-%d"));
+    // Print the report, then write it to a timestamped file
+    let mut out = Vec::new();
+    writeln!(out, "\n--- Professional Technical Analysis Report ---\n")?;
+    writeln!(out, "Ticker: {}", ticker)?;
+    writeln!(out, "Period: {} to {}", start_date.format("%Y-%m-%d"), end_date.format("%Y-%m-%d"))?;
 
-    // Print the data sections
-    println!("\n--- Price Analysis ---\n");
-    println!("Closing Prices: {:?}", closing_prices);
+    // Write the data sections
+    writeln!(out, "\n--- Price Analysis ---\n")?;
+    writeln!(out, "Closing Prices: {:?}", closing_prices)?;
     if support_levels.is_empty() {
-        println!("Support Levels: No support levels identified in the provided data.");
+        writeln!(out, "Support Levels: No support levels identified in the provided data.")?;
     } else {
-        println!("Support Levels: {:?}", support_levels);
+        writeln!(out, "Support Levels: {:?}", support_levels)?;
     }
     if resistance_levels.is_empty() {
-        println!("Resistance Levels: No resistance levels identified in the provided data.");
+        writeln!(out, "Resistance Levels: No resistance levels identified in the provided data.")?;
     } else {
-        println!("Resistance Levels: {:?}", resistance_levels);
+        writeln!(out, "Resistance Levels: {:?}", resistance_levels)?;
     }
 
-    println!("\n--- Trend Analysis ---\n");
-    println!("Exponential Moving Average (EMA) - Window: {}", ema_window);
-    println!("EMA Values: {:?}", ema);
+    writeln!(out, "\n--- Trend Analysis ---\n")?;
+    writeln!(
+        out,
+        "Exponential Moving Average (EMA) - Window: {}, Seed: {}",
+        windows.ema, args.ema_seed
+    )?;
+    writeln!(out, "EMA Values: {:?}", ema)?;
+
+    writeln!(out, "\n--- Momentum Analysis ---\n")?;
+    writeln!(out, "Relative Strength Index (RSI) - Window: {}", windows.rsi)?;
+    writeln!(out, "RSI Values: {:?}", rsi)?;
+
+    writeln!(out, "\n--- Convergence/Divergence Analysis ---\n")?;
+    writeln!(
+        out,
+        "Moving Average Convergence Divergence (MACD) - Short Window: {}, Long Window: {}, Signal Window: {}",
+        windows.macd_short, windows.macd_long, windows.macd_signal
+    )?;
+    writeln!(out, "MACD Values: {:?}", macd)?;
+    writeln!(out, "MACD Signal: {:?}", macd_signal)?;
+    writeln!(out, "MACD Histogram: {:?}", macd_histogram)?;
+
+    writeln!(out, "\n--- Volume Analysis ---\n")?;
+    writeln!(out, "On-Balance Volume (OBV) - Divergence Window: {}", obv_divergence_window)?;
+    writeln!(out, "OBV Values: {:?}", obv)?;
+    if obv_divergences.is_empty() {
+        writeln!(out, "No OBV divergences identified in the provided data.")?;
+    }
+    for (i, divergence) in &obv_divergences {
+        let description = match divergence {
+            ObvDivergence::Bearish => "Bearish: new price high not confirmed by OBV",
+            ObvDivergence::Bullish => "Bullish: new price low not confirmed by OBV",
+        };
+        writeln!(
+            out,
+            "{} - Close {:.2}, OBV {:.0} - {}",
+            sessions[*i].timestamp.format("%Y-%m-%d"),
+            closing_prices[*i],
+            obv[*i],
+            description
+        )?;
+    }
 
-    println!("\n--- Momentum Analysis ---\n");
-    println!("Relative Strength Index (RSI) - Window: {}", rsi_window);
-    println!("RSI Values: {:?}", rsi);
+    writeln!(out, "\n--- Ichimoku Cloud ---\n")?;
+    write_ichimoku_summary(&mut out, &ichimoku, &closing_prices)?;
 
-    println!("\n--- Convergence/Divergence Analysis ---\n");
-    println!(
-        "Moving Average Convergence Divergence (MACD) - Short Window: {}, Long Window: {}, Signal Window: {}",
-        macd_short_window, macd_long_window, macd_signal_window
-    );
-    println!("MACD Values: {:?}", macd);
-    println!("MACD Signal: {:?}", macd_signal);
-    println!("MACD Histogram: {:?}", macd_histogram);
+    // Append the OpenAI-generated report
+    writeln!(out, "\n--- Advanced Technical Analysis Interpretations ---\n")?;
+    writeln!(out, "{}", report)?;
 
-    // Print the OpenAI-generated report
-    println!("\n--- Advanced Technical Analysis Interpretations ---\n");
-    println!("{}", report);
+    let text = String::from_utf8_lossy(&out);
+    print!("{}", text);
+    let path = reports.write(&format!("technical_analysis_{}", ticker), "md", &text)?;
+    eprintln!("Report written to {}", path.display());
 
     Ok(())
 }
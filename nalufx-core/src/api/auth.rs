@@ -0,0 +1,124 @@
+use crate::models::cash_flow_dm::ErrorResponse;
+use actix_web::{
+    body::EitherBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{AUTHORIZATION, WWW_AUTHENTICATE},
+    Error, HttpResponse,
+};
+use futures::future::{ready, LocalBoxFuture, Ready};
+use log::warn;
+use std::sync::Arc;
+
+/// The paths that do not require authentication and are not rate limited (see
+/// [`RateLimiter`](crate::api::rate_limit::RateLimiter)), so that probes can always reach them.
+pub const EXEMPT_PATHS: &[&str] = &["/health", "/ready"];
+
+/// Bearer token authentication, usable as Actix middleware.
+///
+/// Every request, except those to `EXEMPT_PATHS`, must have an `Authorization: Bearer <token>`
+/// header with the configured token. Requests with a missing or wrong token are rejected with
+/// `401 Unauthorized` and a `WWW-Authenticate: Bearer` header. A `BearerAuth` created with
+/// `disabled` lets every request through, for servers without a configured token.
+///
+/// # Examples
+///
+/// ```
+/// use actix_web::{web, App, HttpResponse};
+/// use nalufx::api::auth::BearerAuth;
+///
+/// let app = App::new()
+///     .wrap(BearerAuth::new("secret-token"))
+///     .route("/predict", web::post().to(HttpResponse::Ok));
+/// ```
+#[derive(Clone)]
+pub struct BearerAuth {
+    token: Option<Arc<str>>,
+}
+
+impl BearerAuth {
+    /// Creates a new `BearerAuth` that accepts the given token.
+    pub fn new(token: &str) -> Self {
+        Self { token: Some(Arc::from(token)) }
+    }
+
+    /// Creates a new `BearerAuth` that does not require authentication.
+    pub fn disabled() -> Self {
+        Self { token: None }
+    }
+
+    /// Returns `true` if the `Authorization` header value carries the configured token, or if
+    /// authentication is disabled.
+    ///
+    /// The tokens are compared in constant time, so that the comparison does not reveal how
+    /// much of a guessed token is correct.
+    pub fn is_authorized(&self, authorization: Option<&str>) -> bool {
+        let Some(token) = &self.token else {
+            return true;
+        };
+        let Some(provided) = authorization.and_then(|value| value.strip_prefix("Bearer ")) else {
+            return false;
+        };
+        let (provided, expected) = (provided.trim().as_bytes(), token.as_bytes());
+        provided.len() == expected.len()
+            && provided.iter().zip(expected).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+    }
+}
+
+impl std::fmt::Debug for BearerAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BearerAuth").field("enabled", &self.token.is_some()).finish()
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for BearerAuth
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = BearerAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(BearerAuthMiddleware { service, auth: self.clone() }))
+    }
+}
+
+/// The middleware service created by `BearerAuth` for each worker.
+#[derive(Debug)]
+pub struct BearerAuthMiddleware<S> {
+    service: S,
+    auth: BearerAuth,
+}
+
+impl<S, B> Service<ServiceRequest> for BearerAuthMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let authorization = req.headers().get(AUTHORIZATION).and_then(|value| value.to_str().ok());
+        if EXEMPT_PATHS.contains(&req.path()) || self.auth.is_authorized(authorization) {
+            let response = self.service.call(req);
+            return Box::pin(
+                async move { response.await.map(ServiceResponse::map_into_left_body) },
+            );
+        }
+
+        warn!("Unauthorized request to {} from {:?}", req.path(), req.peer_addr());
+        let response = HttpResponse::Unauthorized()
+            .insert_header((WWW_AUTHENTICATE, "Bearer"))
+            .json(ErrorResponse { error: "Missing or invalid bearer token".to_string() });
+        Box::pin(ready(Ok(req.into_response(response).map_into_right_body())))
+    }
+}
@@ -1,79 +1,304 @@
 use crate::{
-    models::cash_flow_dm::{CashFlowRequest, CashFlowResponse},
-    utils::calculations::calculate_optimal_allocation,
+    config::ready_required_env,
+    errors::{AllocationError, NaluFxError},
+    models::{
+        cash_flow_dm::{CashFlowRequest, CashFlowResponse, ErrorResponse},
+        health_dm::HealthResponse,
+    },
+    services::processing_svc::{calculate_cash_flows, calculate_daily_returns},
+    utils::{
+        calculations::{calculate_optimal_allocation, forecast_time_series},
+        calendar::next_trading_days,
+        date::{check_series_continuity, SeriesIssue},
+        metrics::METRICS,
+        validation::validate_date,
+    },
 };
-use actix_web::{post, web, HttpResponse, Responder};
-use log::{debug, error};
-use nalufx_llms::llms::openai::{get_openai_api_key, parse_openai_response, send_openai_request};
-use reqwest::Client;
-use serde_json::json;
+use actix_web::{get, post, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use log::{debug, error, warn};
+use std::env;
 
-#[post("/predict")]
-async fn predict_cash_flow(
-    data: web::Json<CashFlowRequest>,
-    daily_returns: web::Json<Vec<f64>>,
-    cash_flows: web::Json<Vec<f64>>,
-    market_indices: web::Json<Vec<f64>>,
-    fund_characteristics: web::Json<Vec<f64>>,
-) -> impl Responder {
-    let client = Client::new();
-    let api_key = match get_openai_api_key() {
-        Ok(key) => key,
-        Err(err) => {
-            error!("{}", err);
-            return HttpResponse::InternalServerError().body("Internal Server Error");
-        },
-    };
+/// The number of future days for which cash flows are predicted and allocations calculated.
+const PREDICTION_DAYS: usize = 6;
 
+/// The notional initial investment used to convert daily returns into cash flows.
+const INITIAL_INVESTMENT: f64 = 1_000.0;
+
+/// Predicts cash flows and the optimal allocation from a series of historical prices.
+///
+/// The handler treats `historical_data` as a chronological series of closing prices. It
+/// calculates the daily returns, converts them into cash flows for a notional investment of
+/// 1,000, and then:
+///
+/// - forecasts the cash flows for the next six days, returned as `predictions`, and
+/// - calculates the optimal allocation over the same six days, normalized to sum to 1.
+///
+/// The allocation clusters the days on their returns, cash flows, market indices and fund
+/// characteristics. The market indices and fund characteristics are taken from the request;
+/// when it has none, a constant `1.0` is used instead, which does not distinguish the days, so
+/// they are clustered on their returns and cash flows alone. The sentiment scores and optimal
+/// actions of the allocation are random placeholders (see
+/// [`get_sentiment_scores`](crate::utils::calculations::get_sentiment_scores)), so the
+/// allocation varies between identical requests.
+///
+/// If the request has `dates`, the response is dated with the six trading days that follow
+/// the last date, skipping weekends and market holidays (see [`next_trading_days`]).
+///
+/// # Responses
+///
+/// * `200 OK` with a `CashFlowResponse` on success.
+/// * `400 Bad Request` with an `ErrorResponse` if the historical data is empty, too short to
+///   forecast, or contains invalid or outlier values, if the dates are malformed, not one per
+///   entry, or not in strictly ascending order, or if the market indices or fund
+///   characteristics are not one value per entry.
+/// * `500 Internal Server Error` with an `ErrorResponse` if forecasting or allocation fails.
+#[utoipa::path(
+    post,
+    path = "/predict",
+    request_body = CashFlowRequest,
+    responses(
+        (status = 200, description = "The predicted cash flows and allocation", body = CashFlowResponse),
+        (status = 400, description = "The historical data or dates are invalid", body = ErrorResponse),
+        (status = 500, description = "Forecasting or allocation failed", body = ErrorResponse)
+    )
+)]
+#[post("/predict")]
+pub async fn predict_cash_flow(data: web::Json<CashFlowRequest>) -> impl Responder {
     if data.historical_data.is_empty() {
         error!("Historical data is empty");
-        return HttpResponse::BadRequest().body("Invalid historical data");
+        METRICS.record_prediction_error();
+        return HttpResponse::BadRequest()
+            .json(ErrorResponse { error: "Invalid historical data".to_string() });
     }
 
-    let historical_data_str =
-        data.historical_data.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", ");
-
-    let request_body = json!({
-        "model": "gpt-3.5-turbo",
-        "messages": [
-            {"role": "system", "content": "You are a highly skilled financial assistant with expertise in forecasting cash flows and optimizing financial allocations to enhance returns while minimizing risks. Your predictions are based on thorough analysis of historical data and contemporary financial models."},
-            {"role": "user", "content": format!("Based on the provided historical cash flow data: [{}], please predict the cash flow values for the upcoming week. Additionally, suggest an optimal allocation strategy that maximizes returns and minimizes risks. The historical data is presented in chronological order, from the earliest to the most recent.", historical_data_str)}
-        ],
-        "max_tokens": 100,
-    });
-
-    debug!("Request body: {:?}", request_body);
-
-    let openai_url = "https://api.openai.com/v1/chat/completions";
-    let body = match send_openai_request(&client, openai_url, &api_key, request_body).await {
-        Ok(body) => body,
-        Err(err) => return HttpResponse::InternalServerError().body(err),
+    let last_date = match &data.dates {
+        Some(dates) => match validate_dates(dates, &data.historical_data) {
+            Ok(last_date) => Some(last_date),
+            Err(e) => {
+                error!("Invalid dates: {}", e);
+                METRICS.record_prediction_error();
+                return HttpResponse::BadRequest().json(ErrorResponse { error: e });
+            },
+        },
+        None => None,
     };
 
-    let predictions = match parse_openai_response(&body) {
-        Ok(predictions) => predictions,
-        Err(err) => return err,
+    let daily_returns = calculate_daily_returns(&data.historical_data);
+    let cash_flows = calculate_cash_flows(&daily_returns, INITIAL_INVESTMENT);
+    let features = request_features(&data, daily_returns.len());
+    let (market_indices, fund_characteristics) = match features {
+        Ok(features) => features,
+        Err(e) => {
+            error!("Invalid features: {}", e);
+            METRICS.record_prediction_error();
+            return HttpResponse::BadRequest().json(ErrorResponse { error: e });
+        },
     };
 
-    // Ensure predictions have a length of 6
-    let predictions = if predictions.len() == 6 { predictions } else { vec![0.0; 6] };
+    debug!("Daily returns: {:?}", daily_returns);
+    debug!("Cash flows: {:?}", cash_flows);
 
-    // Calculate the optimal allocation based on predictions
-    let optimal_allocation_result = calculate_optimal_allocation(
+    let optimal_allocation = match calculate_optimal_allocation(
         &daily_returns,
         &cash_flows,
         &market_indices,
         &fund_characteristics,
-        predictions.len(),
-    );
-
-    match optimal_allocation_result {
-        Ok(optimal_allocation) => {
-            HttpResponse::Ok().json(CashFlowResponse { predictions, optimal_allocation })
-        },
+        PREDICTION_DAYS,
+    ) {
+        Ok(optimal_allocation) => optimal_allocation,
         Err(e) => {
             error!("Error calculating optimal allocation: {}", e);
-            HttpResponse::InternalServerError().body("Error calculating optimal allocation")
+            METRICS.record_prediction_error();
+            return allocation_error_response(e);
+        },
+    };
+
+    let predictions = match forecast_time_series(&cash_flows, PREDICTION_DAYS) {
+        Ok(predictions) => predictions,
+        Err(e) => {
+            error!("Error forecasting cash flows: {}", e);
+            METRICS.record_prediction_error();
+            return HttpResponse::InternalServerError()
+                .json(ErrorResponse { error: format!("Error forecasting cash flows: {}", e) });
         },
+    };
+
+    let dates = last_date.map(|last_date| {
+        next_trading_days(last_date, PREDICTION_DAYS)
+            .iter()
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .collect()
+    });
+
+    METRICS.record_prediction();
+    HttpResponse::Ok().json(CashFlowResponse { predictions, optimal_allocation, dates })
+}
+
+/// Validates the dates of a request against its historical data.
+///
+/// There must be one `YYYY-MM-DD` date per entry of `historical_data`, in strictly ascending
+/// order.
+///
+/// # Returns
+///
+/// The last date, or the message of the `ErrorResponse` if the dates are invalid.
+fn validate_dates(dates: &[String], historical_data: &[f64]) -> Result<DateTime<Utc>, String> {
+    if dates.len() != historical_data.len() {
+        return Err(format!(
+            "Expected one date per historical data entry, got {} dates for {} entries",
+            dates.len(),
+            historical_data.len()
+        ));
+    }
+
+    let series = dates
+        .iter()
+        .zip(historical_data)
+        .map(|(date, &value)| {
+            validate_date(date)
+                .map(|date| (date, value))
+                .map_err(|e| format!("Invalid date {:?}: {}", date, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Gaps are allowed, so only the order of the dates is checked.
+    if let Err(NaluFxError::DiscontinuousSeries(issues)) =
+        check_series_continuity(&series, usize::MAX)
+    {
+        let issue = match issues[0] {
+            SeriesIssue::Duplicate(date) => format!("{} appears more than once", date.date_naive()),
+            SeriesIssue::OutOfOrder { previous, current } => {
+                format!("{} comes after {}", current.date_naive(), previous.date_naive())
+            },
+            SeriesIssue::Gap { .. } => "the dates have a gap".to_string(),
+        };
+        return Err(format!("Dates must be in strictly ascending order, but {}", issue));
     }
+
+    series.last().map(|&(date, _)| date).ok_or_else(|| "No dates were given".to_string())
+}
+
+/// Returns the market indices and fund characteristics of a request, aligned with its
+/// `num_returns` daily returns.
+///
+/// Each daily return is paired with the values of the entry it ends on, so the values of the
+/// last `num_returns` entries are kept. A missing feature is a constant `1.0`.
+///
+/// # Returns
+///
+/// The market indices and fund characteristics, or the message of the `ErrorResponse` if
+/// either is not one value per historical data entry.
+fn request_features(
+    data: &CashFlowRequest,
+    num_returns: usize,
+) -> Result<(Vec<f64>, Vec<f64>), String> {
+    let align = |name: &str, feature: &Option<Vec<f64>>| {
+        let Some(values) = feature else {
+            return Ok(vec![1.0; num_returns]);
+        };
+
+        if values.len() != data.historical_data.len() {
+            return Err(format!(
+                "Expected one {} value per historical data entry, got {} values for {} entries",
+                name,
+                values.len(),
+                data.historical_data.len()
+            ));
+        }
+
+        Ok(values[values.len() - num_returns..].to_vec())
+    };
+
+    Ok((
+        align("marketIndices", &data.market_indices)?,
+        align("fundCharacteristics", &data.fund_characteristics)?,
+    ))
+}
+
+/// Maps an `AllocationError` to an HTTP response with an `ErrorResponse` body.
+///
+/// Errors caused by the request data map to `400 Bad Request`, all others to
+/// `500 Internal Server Error`.
+fn allocation_error_response(err: AllocationError) -> HttpResponse {
+    let body = ErrorResponse { error: err.to_string() };
+    match err {
+        AllocationError::EmptyInput
+        | AllocationError::InsufficientData { .. }
+        | AllocationError::InputMismatch { .. }
+        | AllocationError::InvalidData
+        | AllocationError::OutlierData => HttpResponse::BadRequest().json(body),
+        _ => HttpResponse::InternalServerError().json(body),
+    }
+}
+
+/// Reports that the server is alive, for liveness probes.
+///
+/// # Responses
+///
+/// * `200 OK` with `{"status":"ok"}`.
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses((status = 200, description = "The server is alive", body = HealthResponse))
+)]
+#[get("/health")]
+pub async fn health() -> impl Responder {
+    HttpResponse::Ok().json(HealthResponse::new("ok"))
+}
+
+/// Reports whether the server is ready to serve requests, for readiness probes.
+///
+/// The server is ready when every environment variable listed in `NALUFX_READY_REQUIRED_ENV`
+/// (see [`ready_required_env`]) is set to a non-empty value. No route of the server needs a
+/// secret, so by default no variable is required and the server is always ready. The variables
+/// are checked on every request, so the server becomes ready as soon as they are set.
+///
+/// # Responses
+///
+/// * `200 OK` with `{"status":"ready"}` if the server is ready.
+/// * `503 Service Unavailable` with `{"status":"unready","missing":[...]}` listing the missing
+///   environment variables otherwise.
+#[utoipa::path(
+    get,
+    path = "/ready",
+    responses(
+        (status = 200, description = "The server is ready", body = HealthResponse),
+        (status = 503, description = "Environment variables are missing", body = HealthResponse)
+    )
+)]
+#[get("/ready")]
+pub async fn ready() -> impl Responder {
+    let missing: Vec<String> = ready_required_env()
+        .into_iter()
+        .filter(|var| env::var(var).map_or(true, |value| value.trim().is_empty()))
+        .collect();
+
+    if missing.is_empty() {
+        HttpResponse::Ok().json(HealthResponse::new("ready"))
+    } else {
+        warn!("Server is not ready, missing: {}", missing.join(", "));
+        HttpResponse::ServiceUnavailable()
+            .json(HealthResponse { missing, ..HealthResponse::new("unready") })
+    }
+}
+
+/// Exposes the application metrics in the Prometheus text format, for scraping.
+///
+/// The metrics include the number of predictions, failed market data fetches and LLM
+/// requests, and the LLM request latency (see [`Metrics`](crate::utils::metrics::Metrics)).
+///
+/// # Responses
+///
+/// * `200 OK` with the metrics as `text/plain; version=0.0.4`.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "The Prometheus metrics", body = String, content_type = "text/plain")
+    )
+)]
+#[get("/metrics")]
+pub async fn metrics() -> impl Responder {
+    HttpResponse::Ok().content_type("text/plain; version=0.0.4").body(METRICS.render())
 }
@@ -1,2 +1,14 @@
+/// Bearer token authentication middleware for the API.
+pub mod auth;
+
 /// Handlers for the OpenAI API.
 pub mod handlers;
+
+/// The OpenAPI document of the API.
+pub mod openapi;
+
+/// Per-client rate limiting middleware for the API.
+pub mod rate_limit;
+
+/// WebSocket push of allocation updates.
+pub mod ws;
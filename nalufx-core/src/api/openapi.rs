@@ -0,0 +1,53 @@
+use crate::{
+    api::{handlers, ws},
+    models::{
+        cash_flow_dm::{CashFlowRequest, CashFlowResponse, ErrorResponse},
+        health_dm::HealthResponse,
+    },
+};
+use actix_web::{get, HttpResponse, Responder};
+use utoipa::OpenApi;
+
+/// The OpenAPI document of the API, generated from the annotations of the handlers and models.
+///
+/// The document covers every endpoint served by the application, with the schemas of their
+/// request and response bodies, so that integrators can generate clients from it.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::api::openapi::ApiDoc;
+/// use utoipa::OpenApi;
+///
+/// let json = ApiDoc::openapi().to_json().unwrap();
+/// assert!(json.contains("/predict"));
+/// ```
+#[derive(Clone, Copy, Debug, OpenApi)]
+#[openapi(
+    info(title = "NaluFX API", description = "Cash flow prediction and portfolio allocation"),
+    paths(
+        handlers::predict_cash_flow,
+        handlers::health,
+        handlers::ready,
+        handlers::metrics,
+        ws::allocations_ws,
+        openapi_json
+    ),
+    components(schemas(CashFlowRequest, CashFlowResponse, ErrorResponse, HealthResponse))
+)]
+pub struct ApiDoc;
+
+/// Serves the OpenAPI document of the API (see [`ApiDoc`]).
+///
+/// # Responses
+///
+/// * `200 OK` with the OpenAPI document as JSON.
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    responses((status = 200, description = "The OpenAPI document, as JSON"))
+)]
+#[get("/openapi.json")]
+pub async fn openapi_json() -> impl Responder {
+    HttpResponse::Ok().json(ApiDoc::openapi())
+}
@@ -0,0 +1,189 @@
+use crate::{api::auth::EXEMPT_PATHS, models::cash_flow_dm::ErrorResponse};
+use actix_web::{
+    body::EitherBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::RETRY_AFTER,
+    Error, HttpResponse,
+};
+use futures::future::{ready, LocalBoxFuture, Ready};
+use log::warn;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// The number of tracked clients above which the buckets that have refilled are discarded.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// The token bucket of a single client.
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    /// The number of requests the client can still send.
+    tokens: f64,
+    /// The time at which `tokens` was last refilled.
+    last_refill: Instant,
+}
+
+impl Bucket {
+    /// Returns the number of tokens in the bucket at `now`, capped at `capacity`.
+    fn refilled(&self, now: Instant, rate: f64, capacity: f64) -> f64 {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        (self.tokens + elapsed * rate).min(capacity)
+    }
+}
+
+/// A per-client-IP token bucket rate limiter, usable as Actix middleware.
+///
+/// Each client IP starts with a full bucket of `requests_per_minute` tokens, which refills
+/// continuously at `requests_per_minute` tokens per minute. Every request takes one token, and
+/// a request that finds the bucket empty is rejected with `429 Too Many Requests` and a
+/// `Retry-After` header giving the number of seconds until a token is available. This allows
+/// short bursts while bounding the sustained rate of the upstream Yahoo Finance and OpenAI
+/// calls made on behalf of a client.
+///
+/// Requests to the `EXEMPT_PATHS` probes neither take a token nor are rejected, so that a client
+/// sharing an IP address with a load balancer or the kubelet cannot fail their health checks by
+/// draining the bucket.
+///
+/// The client IP is the peer address of the connection. Requests without a peer address share
+/// a single bucket. Clones share the same buckets, so one limiter can be cloned into every
+/// worker of an `HttpServer`.
+///
+/// # Examples
+///
+/// ```
+/// use actix_web::{web, App, HttpResponse};
+/// use nalufx::api::rate_limit::RateLimiter;
+///
+/// let rate_limiter = RateLimiter::new(60);
+/// let app = App::new()
+///     .wrap(rate_limiter.clone())
+///     .route("/", web::get().to(HttpResponse::Ok));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    requests_per_minute: u32,
+    buckets: Arc<Mutex<HashMap<Option<IpAddr>, Bucket>>>,
+}
+
+impl RateLimiter {
+    /// Creates a new `RateLimiter` allowing `requests_per_minute` requests per client IP.
+    ///
+    /// A limit of `0` is treated as `1`, so that clients are never locked out permanently.
+    pub fn new(requests_per_minute: u32) -> Self {
+        Self {
+            requests_per_minute: requests_per_minute.max(1),
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the number of requests allowed per minute for each client IP.
+    pub fn requests_per_minute(&self) -> u32 {
+        self.requests_per_minute
+    }
+
+    /// Takes a token from the bucket of `client`, if one is available now.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The IP address of the client, or `None` if it is unknown.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the request is allowed, or `Err` with the time until a token is available.
+    pub fn check(&self, client: Option<IpAddr>) -> Result<(), Duration> {
+        self.check_at(client, Instant::now())
+    }
+
+    /// Takes a token from the bucket of `client`, if one is available at `now`.
+    ///
+    /// This is `check` with an explicit clock, so that the refill can be tested without
+    /// waiting. `now` must not be earlier than the `now` of a previous call.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The IP address of the client, or `None` if it is unknown.
+    /// * `now` - The time at which the request is received.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the request is allowed, or `Err` with the time until a token is available.
+    pub fn check_at(&self, client: Option<IpAddr>, now: Instant) -> Result<(), Duration> {
+        let capacity = f64::from(self.requests_per_minute);
+        let rate = capacity / 60.0;
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        if buckets.len() >= MAX_TRACKED_CLIENTS {
+            // A full bucket behaves like a new one, so it does not need to be tracked.
+            buckets.retain(|_, bucket| bucket.refilled(now, rate, capacity) < capacity);
+        }
+
+        let bucket = buckets.entry(client).or_insert(Bucket { tokens: capacity, last_refill: now });
+        bucket.tokens = bucket.refilled(now, rate, capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimiter
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RateLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddleware { service, limiter: self.clone() }))
+    }
+}
+
+/// The middleware service created by `RateLimiter` for each worker.
+#[derive(Debug)]
+pub struct RateLimitMiddleware<S> {
+    service: S,
+    limiter: RateLimiter,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let client = req.peer_addr().map(|addr| addr.ip());
+        let allowed =
+            if EXEMPT_PATHS.contains(&req.path()) { Ok(()) } else { self.limiter.check(client) };
+        match allowed {
+            Ok(()) => {
+                let response = self.service.call(req);
+                Box::pin(async move { response.await.map(ServiceResponse::map_into_left_body) })
+            },
+            Err(retry_after) => {
+                warn!("Rate limit exceeded for client {:?} on {}", client, req.path());
+                let retry_after_secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+                let response = HttpResponse::TooManyRequests()
+                    .insert_header((RETRY_AFTER, retry_after_secs.to_string()))
+                    .json(ErrorResponse { error: "Too many requests".to_string() });
+                Box::pin(ready(Ok(req.into_response(response).map_into_right_body())))
+            },
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{
+    config::fetch_concurrency,
+    models::stream_dm::{AllocationFrame, AllocationSubscription},
+    services::{
+        fetch_data_svc::fetch_data_batch, processing_svc::calculate_daily_returns,
+        strategy_comparison_svc::risk_parity_weights,
+    },
+    utils::{batch::BatchOptions, validation::validate_ticker},
+};
+use actix::{fut, Actor, ActorContext, ActorFutureExt, AsyncContext, SpawnHandle, StreamHandler};
+use actix_web::{get, web, Error, HttpRequest, HttpResponse};
+use actix_web_actors::ws;
+use chrono::{Duration as ChronoDuration, Utc};
+use log::{debug, error, info, warn};
+use ndarray::Array2;
+use ndarray_stats::CorrelationExt;
+use std::time::{Duration, Instant};
+
+/// The interval at which the allocation of the subscribed tickers is recomputed and pushed.
+pub const ALLOCATION_PUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The interval at which the server pings the client.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The time without any message from the client after which the connection is closed.
+pub const CLIENT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// The largest number of tickers a subscription may contain.
+pub const MAX_SUBSCRIPTION_TICKERS: usize = 20;
+
+/// The number of calendar days of prices from which the allocation is computed.
+const ALLOCATION_LOOKBACK_DAYS: i64 = 180;
+
+/// Pushes the allocation of a set of tickers over a WebSocket, so that dashboards do not need
+/// to poll `/api/predict`.
+///
+/// Once connected, the client sends an `AllocationSubscription` as a text message, e.g.
+/// `{"tickers":["AAPL","MSFT"]}`. The server responds with an `AllocationFrame`, and pushes a
+/// new one every `ALLOCATION_PUSH_INTERVAL` until the client subscribes to other tickers or
+/// disconnects. The allocation is the risk parity weights of the daily returns of the tickers
+/// over the last 180 days.
+///
+/// # Responses
+///
+/// * `101 Switching Protocols` to open the WebSocket.
+/// * `400 Bad Request` if the request is not a WebSocket handshake.
+#[utoipa::path(
+    get,
+    path = "/ws/allocations",
+    responses(
+        (status = 101, description = "The WebSocket is open"),
+        (status = 400, description = "The request is not a WebSocket handshake")
+    )
+)]
+#[get("/ws/allocations")]
+pub async fn allocations_ws(req: HttpRequest, stream: web::Payload) -> Result<HttpResponse, Error> {
+    ws::start(AllocationSocket::new(ALLOCATION_PUSH_INTERVAL), &req, stream)
+}
+
+/// Represents the WebSocket session of a client of `/ws/allocations`.
+///
+/// The session pings the client every `HEARTBEAT_INTERVAL` and stops if it receives no
+/// message for `CLIENT_TIMEOUT`, so that the updates of a client that disconnected without
+/// closing the connection stop too.
+///
+/// At most one allocation is computed at a time. A push that is due while the previous
+/// allocation is still being computed is skipped rather than queued, so that a slow data
+/// source or a slow client does not build up a backlog of frames.
+#[derive(Debug)]
+pub struct AllocationSocket {
+    push_interval: Duration,
+    tickers: Vec<String>,
+    last_heartbeat: Instant,
+    updates: Option<SpawnHandle>,
+    computation: Option<SpawnHandle>,
+}
+
+impl AllocationSocket {
+    /// Creates a new `AllocationSocket` instance, without a subscription.
+    ///
+    /// # Arguments
+    ///
+    /// * `push_interval` - The interval at which the allocation is pushed.
+    pub fn new(push_interval: Duration) -> Self {
+        Self {
+            push_interval,
+            tickers: Vec::new(),
+            last_heartbeat: Instant::now(),
+            updates: None,
+            computation: None,
+        }
+    }
+
+    /// Replaces the subscription with the one in `text`, and pushes its allocation at once and
+    /// then on every interval. An invalid subscription is answered with an error frame and
+    /// leaves the current one unchanged.
+    fn subscribe(&mut self, text: &str, ctx: &mut ws::WebsocketContext<Self>) {
+        let tickers = match parse_subscription(text) {
+            Ok(tickers) => tickers,
+            Err(error) => {
+                warn!("Rejected allocation subscription: {}", error);
+                send(ctx, &AllocationFrame::Error { error });
+                return;
+            },
+        };
+
+        info!("Subscribed to the allocation of {}", tickers.join(", "));
+        for handle in [self.updates.take(), self.computation.take()].into_iter().flatten() {
+            let _ = ctx.cancel_future(handle);
+        }
+        self.tickers = tickers;
+        self.push(ctx);
+        self.updates = Some(ctx.run_interval(self.push_interval, |socket, ctx| socket.push(ctx)));
+    }
+
+    /// Computes the allocation of the subscribed tickers and sends it, unless a computation is
+    /// already running.
+    fn push(&mut self, ctx: &mut ws::WebsocketContext<Self>) {
+        if self.computation.is_some() {
+            debug!("Skipping an allocation push, the previous one is still running");
+            return;
+        }
+
+        let tickers = self.tickers.clone();
+        let computation = fut::wrap_future(compute_allocation(tickers.clone())).map(
+            move |result, socket: &mut Self, ctx: &mut ws::WebsocketContext<Self>| {
+                socket.computation = None;
+                let frame = match result {
+                    Ok(weights) => AllocationFrame::Allocation { tickers, weights },
+                    Err(error) => {
+                        warn!("Failed to compute the allocation: {}", error);
+                        AllocationFrame::Error { error }
+                    },
+                };
+                send(ctx, &frame);
+            },
+        );
+        self.computation = Some(ctx.spawn(computation));
+    }
+}
+
+impl Actor for AllocationSocket {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        let _ = ctx.run_interval(HEARTBEAT_INTERVAL, |socket, ctx| {
+            if Instant::now().duration_since(socket.last_heartbeat) > CLIENT_TIMEOUT {
+                info!("Allocation WebSocket client timed out, disconnecting");
+                ctx.stop();
+                return;
+            }
+            ctx.ping(b"");
+        });
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        debug!("Allocation WebSocket session stopped");
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for AllocationSocket {
+    fn handle(&mut self, message: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        let message = match message {
+            Ok(message) => message,
+            Err(e) => {
+                warn!("Allocation WebSocket protocol error: {}", e);
+                ctx.stop();
+                return;
+            },
+        };
+
+        // Any message shows that the client is still connected.
+        self.last_heartbeat = Instant::now();
+        match message {
+            ws::Message::Ping(bytes) => ctx.pong(&bytes),
+            ws::Message::Text(text) => self.subscribe(&text, ctx),
+            ws::Message::Binary(_) => send(
+                ctx,
+                &AllocationFrame::Error { error: "Binary messages are not supported".to_string() },
+            ),
+            ws::Message::Close(reason) => {
+                ctx.close(reason);
+                ctx.stop();
+            },
+            ws::Message::Pong(_) | ws::Message::Continuation(_) | ws::Message::Nop => {},
+        }
+    }
+}
+
+/// Sends a frame to the client as a JSON text message.
+fn send(ctx: &mut ws::WebsocketContext<AllocationSocket>, frame: &AllocationFrame) {
+    match serde_json::to_string(frame) {
+        Ok(json) => ctx.text(json),
+        Err(e) => error!("Failed to serialize an allocation frame: {}", e),
+    }
+}
+
+/// Parses a subscription message into its tickers, in uppercase and without duplicates.
+///
+/// Returns the reason the subscription is invalid if the message is not an
+/// `AllocationSubscription`, has no tickers or more than `MAX_SUBSCRIPTION_TICKERS`, or has an
+/// invalid ticker.
+fn parse_subscription(text: &str) -> Result<Vec<String>, String> {
+    let subscription: AllocationSubscription =
+        serde_json::from_str(text).map_err(|e| format!("Invalid subscription: {}", e))?;
+
+    let mut tickers: Vec<String> = Vec::new();
+    for ticker in &subscription.tickers {
+        let ticker = validate_ticker(ticker.trim()).map_err(|e| e.to_string())?.to_uppercase();
+        if !tickers.contains(&ticker) {
+            tickers.push(ticker);
+        }
+    }
+
+    if tickers.is_empty() {
+        return Err("The subscription has no tickers".to_string());
+    }
+    if tickers.len() > MAX_SUBSCRIPTION_TICKERS {
+        return Err(format!(
+            "The subscription has {} tickers, more than the limit of {}",
+            tickers.len(),
+            MAX_SUBSCRIPTION_TICKERS
+        ));
+    }
+    Ok(tickers)
+}
+
+/// Fetches the recent prices of the tickers and computes their risk parity weights.
+async fn compute_allocation(tickers: Vec<String>) -> Result<Vec<f64>, String> {
+    let start_date = Some(Utc::now() - ChronoDuration::days(ALLOCATION_LOOKBACK_DAYS));
+    let options = BatchOptions::new(fetch_concurrency());
+    let results = fetch_data_batch(&tickers, start_date, None, &options).await;
+
+    let mut daily_returns = Vec::with_capacity(tickers.len());
+    for (ticker, result) in tickers.iter().zip(results) {
+        let closes = result.map_err(|e| format!("Failed to fetch data for {}: {}", ticker, e))?;
+        daily_returns.push(calculate_daily_returns(&closes));
+    }
+
+    // Keep the most recent returns common to all tickers, with one row per day and one column
+    // per ticker.
+    let num_days = daily_returns.iter().map(Vec::len).min().unwrap_or(0);
+    if num_days < 2 {
+        return Err("Insufficient data to compute the allocation".to_string());
+    }
+    let returns = Array2::from_shape_fn((num_days, daily_returns.len()), |(day, ticker)| {
+        let ticker_returns = &daily_returns[ticker];
+        ticker_returns[ticker_returns.len() - num_days + day]
+    });
+    let cov = returns
+        .t()
+        .cov(1.0)
+        .map_err(|e| format!("Failed to compute the covariance of the returns: {}", e))?;
+    Ok(risk_parity_weights(&cov))
+}
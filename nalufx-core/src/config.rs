@@ -1,4 +1,9 @@
+use chrono::NaiveDate;
+use log::warn;
+use nalufx_llms::llms::{factory::LLM_NAMES, offline};
+use std::collections::BTreeMap;
 use std::env;
+use std::path::PathBuf;
 
 /// Represents the configuration for the application.
 ///
@@ -9,6 +14,37 @@
 /// # Fields
 ///
 /// * `server_addr` - A string containing the address of the server.
+/// * `offline` - Whether offline mode is enabled (see [`is_offline_mode`]).
+/// * `reports_dir` - The directory the reports are written to (see [`reports_dir`]).
+/// * `api_rate_limit_per_min` - The number of API requests allowed per minute for each client
+///   IP (see [`api_rate_limit_per_min`]).
+/// * `api_auth_token` - The bearer token required by the API, if any (see [`api_auth_token`]).
+/// * `prompts_dir` - The directory of user prompt templates, if any (see [`prompts_dir`]).
+/// * `max_display_days` - The number of daily rows printed to the console (see
+///   [`max_display_days`]).
+/// * `chart_max_points` - The largest number of points drawn in a chart (see
+///   [`chart_max_points`]).
+/// * `fetch_concurrency` - The number of tickers fetched at the same time (see
+///   [`fetch_concurrency`]).
+/// * `display_precision` - The number of decimal places of the printed values (see
+///   [`display_precision`]).
+/// * `user_agent` - The `User-Agent` header sent to Yahoo Finance (see [`user_agent`]).
+/// * `lot_size` - The number of shares in a lot (see [`lot_size`]).
+/// * `min_trade_amount` - The smallest amount worth trading (see [`min_trade_amount`]).
+/// * `market_holidays` - The market holidays replacing the built-in US market holidays, if
+///   any (see [`market_holidays`]).
+/// * `benchmark_ticker` - The benchmark used when no other benchmark applies (see
+///   [`benchmark_ticker`]).
+/// * `benchmarks` - The benchmarks by ticker symbol or asset class, if any (see
+///   [`benchmarks`]).
+/// * `bootstrap_resamples` - The number of bootstrap resamples of the confidence intervals
+///   (see [`bootstrap_resamples`]).
+/// * `bootstrap_seed` - The seed of the bootstrap resampling (see [`bootstrap_seed`]).
+/// * `history_lookback_days` - The number of days of history fetched when no start date is
+///   given (see [`history_lookback_days`]).
+/// * `default_llm` - The LLM used when none is entered, if any (see [`default_llm`]).
+/// * `ready_required_env` - The environment variables the server needs to be ready (see
+///   [`ready_required_env`]).
 ///
 /// # Examples
 ///
@@ -30,13 +66,75 @@
 pub struct Config {
     /// A string containing the address of the server.
     pub server_addr: String,
+    /// Whether offline mode is enabled.
+    pub offline: bool,
+    /// The directory the reports are written to.
+    pub reports_dir: PathBuf,
+    /// The number of API requests allowed per minute for each client IP.
+    pub api_rate_limit_per_min: u32,
+    /// The bearer token required by the API, if any.
+    pub api_auth_token: Option<String>,
+    /// The directory of user prompt templates, if any.
+    pub prompts_dir: Option<PathBuf>,
+    /// The number of daily rows printed to the console.
+    pub max_display_days: usize,
+    /// The largest number of points drawn in a chart.
+    pub chart_max_points: usize,
+    /// The number of tickers fetched at the same time.
+    pub fetch_concurrency: usize,
+    /// The number of decimal places of the printed values.
+    pub display_precision: usize,
+    /// The `User-Agent` header sent to Yahoo Finance.
+    pub user_agent: String,
+    /// The number of shares in a lot.
+    pub lot_size: u32,
+    /// The smallest amount, in whole currency units, worth trading.
+    pub min_trade_amount: u32,
+    /// The market holidays replacing the built-in US market holidays, if any.
+    pub market_holidays: Option<Vec<NaiveDate>>,
+    /// The benchmark used when no benchmark is selected for a ticker symbol or asset class.
+    pub benchmark_ticker: String,
+    /// The benchmarks by ticker symbol or asset class, overriding the built-in ones, if any.
+    pub benchmarks: Option<BTreeMap<String, String>>,
+    /// The number of bootstrap resamples of the confidence intervals.
+    pub bootstrap_resamples: usize,
+    /// The seed of the bootstrap resampling.
+    pub bootstrap_seed: u64,
+    /// The number of days of history fetched when no start date is given.
+    pub history_lookback_days: u32,
+    /// The LLM used when none is entered, if any.
+    pub default_llm: Option<String>,
+    /// The environment variables that must be set for the server to be ready.
+    pub ready_required_env: Vec<String>,
 }
 
 impl Config {
     /// Creates a new `Config` instance by loading values from environment variables.
     ///
-    /// This function attempts to read the required configuration values from environment variables.
-    /// If any of the required environment variables are not set, it returns an error.
+    /// The `SERVER_ADDR` environment variable is required, for `server_addr`. Every other field
+    /// is read by its accessor from an optional environment variable, and takes its default
+    /// when the variable is unset:
+    ///
+    /// * `offline` - `NALUFX_OFFLINE` (see [`is_offline_mode`]).
+    /// * `reports_dir` - `NALUFX_REPORTS_DIR` (see [`reports_dir`]).
+    /// * `api_rate_limit_per_min` - `API_RATE_LIMIT_PER_MIN` (see [`api_rate_limit_per_min`]).
+    /// * `api_auth_token` - `API_AUTH_TOKEN` (see [`api_auth_token`]).
+    /// * `prompts_dir` - `NALUFX_PROMPTS_DIR` (see [`prompts_dir`]).
+    /// * `max_display_days` - `NALUFX_MAX_DISPLAY_DAYS` (see [`max_display_days`]).
+    /// * `chart_max_points` - `NALUFX_CHART_MAX_POINTS` (see [`chart_max_points`]).
+    /// * `fetch_concurrency` - `NALUFX_FETCH_CONCURRENCY` (see [`fetch_concurrency`]).
+    /// * `display_precision` - `NALUFX_DISPLAY_PRECISION` (see [`display_precision`]).
+    /// * `user_agent` - `NALUFX_USER_AGENT` (see [`user_agent`]).
+    /// * `lot_size` - `NALUFX_LOT_SIZE` (see [`lot_size`]).
+    /// * `min_trade_amount` - `NALUFX_MIN_TRADE_AMOUNT` (see [`min_trade_amount`]).
+    /// * `market_holidays` - `NALUFX_MARKET_HOLIDAYS` (see [`market_holidays`]).
+    /// * `benchmark_ticker` - `NALUFX_BENCHMARK_TICKER` (see [`benchmark_ticker`]).
+    /// * `benchmarks` - `NALUFX_BENCHMARKS` (see [`benchmarks`]).
+    /// * `bootstrap_resamples` - `NALUFX_BOOTSTRAP_RESAMPLES` (see [`bootstrap_resamples`]).
+    /// * `bootstrap_seed` - `NALUFX_BOOTSTRAP_SEED` (see [`bootstrap_seed`]).
+    /// * `history_lookback_days` - `NALUFX_HISTORY_LOOKBACK_DAYS` (see [`history_lookback_days`]).
+    /// * `default_llm` - `DEFAULT_LLM` (see [`default_llm`]).
+    /// * `ready_required_env` - `NALUFX_READY_REQUIRED_ENV` (see [`ready_required_env`]).
     ///
     /// # Returns
     ///
@@ -65,6 +163,806 @@ impl Config {
     /// ```
     pub fn from_env() -> Result<Self, env::VarError> {
         let server_addr = env::var("SERVER_ADDR")?;
-        Ok(Self { server_addr })
+        Ok(Self {
+            server_addr,
+            offline: is_offline_mode(),
+            reports_dir: reports_dir(),
+            api_rate_limit_per_min: api_rate_limit_per_min(),
+            api_auth_token: api_auth_token(),
+            prompts_dir: prompts_dir(),
+            max_display_days: max_display_days(),
+            chart_max_points: chart_max_points(),
+            fetch_concurrency: fetch_concurrency(),
+            display_precision: display_precision(),
+            user_agent: user_agent(),
+            lot_size: lot_size(),
+            min_trade_amount: min_trade_amount(),
+            market_holidays: market_holidays(),
+            benchmark_ticker: benchmark_ticker(),
+            benchmarks: benchmarks(),
+            bootstrap_resamples: bootstrap_resamples(),
+            bootstrap_seed: bootstrap_seed(),
+            history_lookback_days: history_lookback_days(),
+            default_llm: default_llm(),
+            ready_required_env: ready_required_env(),
+        })
+    }
+}
+
+/// Returns `true` if offline mode is enabled.
+///
+/// Offline mode is enabled when the `NALUFX_OFFLINE` environment variable is set to `1`,
+/// `true`, `yes`, or `on`. In offline mode, `fetch_data` returns deterministic synthetic prices
+/// and the LLM layer returns a canned report, so every example runs without network access or
+/// API keys.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::is_offline_mode;
+///
+/// env::set_var("NALUFX_OFFLINE", "1");
+/// assert!(is_offline_mode());
+///
+/// env::remove_var("NALUFX_OFFLINE");
+/// assert!(!is_offline_mode());
+/// ```
+pub fn is_offline_mode() -> bool {
+    offline::is_offline_mode()
+}
+
+/// The environment variable that selects the output mode.
+pub const OUTPUT_ENV: &str = "NALUFX_OUTPUT";
+
+/// Represents how reports are written to stdout.
+///
+/// # Variants
+///
+/// * `Human` - Print a readable text report (the default).
+/// * `Json` - Print the structured result as JSON, for scripting (e.g. piping into `jq`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OutputMode {
+    /// Print a readable text report.
+    #[default]
+    Human,
+    /// Print the structured result as JSON.
+    Json,
+}
+
+/// Returns the output mode selected by the environment.
+///
+/// JSON output is selected when the `NALUFX_OUTPUT` environment variable is set to `json`
+/// (case-insensitive). Any other value, or no value, selects human-readable output. An
+/// environment variable is used rather than a command-line flag so that the menu-driven
+/// examples runner can pass it through.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::{output_mode, OutputMode};
+///
+/// env::set_var("NALUFX_OUTPUT", "json");
+/// assert_eq!(output_mode(), OutputMode::Json);
+///
+/// env::remove_var("NALUFX_OUTPUT");
+/// assert_eq!(output_mode(), OutputMode::Human);
+/// ```
+pub fn output_mode() -> OutputMode {
+    match env::var(OUTPUT_ENV) {
+        Ok(value) if value.trim().eq_ignore_ascii_case("json") => OutputMode::Json,
+        _ => OutputMode::Human,
+    }
+}
+
+/// The environment variable that sets the reports directory.
+pub const REPORTS_DIR_ENV: &str = "NALUFX_REPORTS_DIR";
+
+/// The directory the reports are written to when `NALUFX_REPORTS_DIR` is not set.
+pub const DEFAULT_REPORTS_DIR: &str = "./reports";
+
+/// Returns the directory the reports are written to.
+///
+/// The directory is read from the `NALUFX_REPORTS_DIR` environment variable, and defaults to
+/// `./reports` when the variable is unset or empty. The directory does not need to exist: it
+/// is created when the first report is written (see
+/// [`ReportSink`](crate::utils::reports::ReportSink)). An `--output-dir` argument takes
+/// precedence over it.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use std::path::PathBuf;
+/// use nalufx::config::reports_dir;
+///
+/// env::set_var("NALUFX_REPORTS_DIR", "/tmp/nalufx-reports");
+/// assert_eq!(reports_dir(), PathBuf::from("/tmp/nalufx-reports"));
+///
+/// env::remove_var("NALUFX_REPORTS_DIR");
+/// assert_eq!(reports_dir(), PathBuf::from("./reports"));
+/// ```
+pub fn reports_dir() -> PathBuf {
+    match env::var(REPORTS_DIR_ENV) {
+        Ok(dir) if !dir.trim().is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(DEFAULT_REPORTS_DIR),
+    }
+}
+
+/// The environment variable that sets the number of API requests allowed per minute.
+pub const API_RATE_LIMIT_ENV: &str = "API_RATE_LIMIT_PER_MIN";
+
+/// The number of API requests allowed per minute when `API_RATE_LIMIT_PER_MIN` is not set.
+pub const DEFAULT_API_RATE_LIMIT_PER_MIN: u32 = 60;
+
+/// Returns the number of API requests allowed per minute for each client IP.
+///
+/// The limit is read from the `API_RATE_LIMIT_PER_MIN` environment variable, and defaults to
+/// 60 when the variable is unset, is not a whole number, or is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::api_rate_limit_per_min;
+///
+/// env::set_var("API_RATE_LIMIT_PER_MIN", "120");
+/// assert_eq!(api_rate_limit_per_min(), 120);
+///
+/// env::remove_var("API_RATE_LIMIT_PER_MIN");
+/// assert_eq!(api_rate_limit_per_min(), 60);
+/// ```
+pub fn api_rate_limit_per_min() -> u32 {
+    match env::var(API_RATE_LIMIT_ENV).map(|value| value.trim().parse::<u32>()) {
+        Ok(Ok(limit)) if limit > 0 => limit,
+        Ok(_) => {
+            warn!(
+                "Invalid {} value, using the default of {} requests per minute",
+                API_RATE_LIMIT_ENV, DEFAULT_API_RATE_LIMIT_PER_MIN
+            );
+            DEFAULT_API_RATE_LIMIT_PER_MIN
+        },
+        Err(_) => DEFAULT_API_RATE_LIMIT_PER_MIN,
     }
 }
+
+/// The environment variable that sets the bearer token required by the API.
+pub const API_AUTH_TOKEN_ENV: &str = "API_AUTH_TOKEN";
+
+/// Returns the bearer token required by the API, if one is configured.
+///
+/// The token is read from the `API_AUTH_TOKEN` environment variable. When the variable is unset
+/// or empty, `None` is returned and the API does not require authentication.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::api_auth_token;
+///
+/// env::set_var("API_AUTH_TOKEN", "secret-token");
+/// assert_eq!(api_auth_token().as_deref(), Some("secret-token"));
+///
+/// env::remove_var("API_AUTH_TOKEN");
+/// assert_eq!(api_auth_token(), None);
+/// ```
+pub fn api_auth_token() -> Option<String> {
+    env::var(API_AUTH_TOKEN_ENV)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+/// The environment variable that sets the directory of user prompt templates.
+pub const PROMPTS_DIR_ENV: &str = "NALUFX_PROMPTS_DIR";
+
+/// Returns the directory of user prompt templates, if one is configured.
+///
+/// The directory is read from the `NALUFX_PROMPTS_DIR` environment variable. A template named
+/// `name` is loaded from `name.txt` in this directory when the file exists, and from the
+/// built-in defaults otherwise (see [`load_template`](crate::prompts::load_template)). When
+/// the variable is unset or empty, `None` is returned and only the built-in templates are used.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use std::path::PathBuf;
+/// use nalufx::config::prompts_dir;
+///
+/// env::set_var("NALUFX_PROMPTS_DIR", "/etc/nalufx/prompts");
+/// assert_eq!(prompts_dir(), Some(PathBuf::from("/etc/nalufx/prompts")));
+///
+/// env::remove_var("NALUFX_PROMPTS_DIR");
+/// assert_eq!(prompts_dir(), None);
+/// ```
+pub fn prompts_dir() -> Option<PathBuf> {
+    env::var(PROMPTS_DIR_ENV).ok().filter(|dir| !dir.trim().is_empty()).map(PathBuf::from)
+}
+
+/// The environment variable that sets the number of daily rows printed to the console.
+pub const MAX_DISPLAY_DAYS_ENV: &str = "NALUFX_MAX_DISPLAY_DAYS";
+
+/// The number of daily rows printed when `NALUFX_MAX_DISPLAY_DAYS` is not set.
+pub const DEFAULT_MAX_DISPLAY_DAYS: usize = 30;
+
+/// Returns the number of daily rows, such as daily allocations, printed to the console.
+///
+/// The number is read from the `NALUFX_MAX_DISPLAY_DAYS` environment variable, and defaults to
+/// 30 when the variable is unset, is not a whole number, or is `0`. Longer series are truncated
+/// on the console (see [`format_daily_rows`](crate::utils::reports::format_daily_rows)), but
+/// not in the exported files.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::max_display_days;
+///
+/// env::set_var("NALUFX_MAX_DISPLAY_DAYS", "90");
+/// assert_eq!(max_display_days(), 90);
+///
+/// env::remove_var("NALUFX_MAX_DISPLAY_DAYS");
+/// assert_eq!(max_display_days(), 30);
+/// ```
+pub fn max_display_days() -> usize {
+    match env::var(MAX_DISPLAY_DAYS_ENV).map(|value| value.trim().parse::<usize>()) {
+        Ok(Ok(days)) if days > 0 => days,
+        Ok(_) => {
+            warn!(
+                "Invalid {} value, using the default of {} days",
+                MAX_DISPLAY_DAYS_ENV, DEFAULT_MAX_DISPLAY_DAYS
+            );
+            DEFAULT_MAX_DISPLAY_DAYS
+        },
+        Err(_) => DEFAULT_MAX_DISPLAY_DAYS,
+    }
+}
+
+/// The environment variable that sets the number of points drawn in a chart.
+pub const CHART_MAX_POINTS_ENV: &str = "NALUFX_CHART_MAX_POINTS";
+
+/// The number of points drawn in a chart when `NALUFX_CHART_MAX_POINTS` is not set.
+pub const DEFAULT_CHART_MAX_POINTS: usize = 120;
+
+/// Returns the largest number of points drawn in a chart.
+///
+/// The number is read from the `NALUFX_CHART_MAX_POINTS` environment variable, and defaults to
+/// 120 when the variable is unset, is not a whole number, or is below `3`. Longer series are
+/// downsampled before they are drawn (see
+/// [`downsample_lttb`](crate::utils::charts::downsample_lttb)), so that a chart over a long
+/// horizon stays readable.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::chart_max_points;
+///
+/// env::set_var("NALUFX_CHART_MAX_POINTS", "500");
+/// assert_eq!(chart_max_points(), 500);
+///
+/// env::remove_var("NALUFX_CHART_MAX_POINTS");
+/// assert_eq!(chart_max_points(), 120);
+/// ```
+pub fn chart_max_points() -> usize {
+    match env::var(CHART_MAX_POINTS_ENV).map(|value| value.trim().parse::<usize>()) {
+        Ok(Ok(points)) if points >= 3 => points,
+        Ok(_) => {
+            warn!(
+                "Invalid {} value, using the default of {} points",
+                CHART_MAX_POINTS_ENV, DEFAULT_CHART_MAX_POINTS
+            );
+            DEFAULT_CHART_MAX_POINTS
+        },
+        Err(_) => DEFAULT_CHART_MAX_POINTS,
+    }
+}
+
+/// The environment variable that sets the number of tickers fetched at the same time.
+pub const FETCH_CONCURRENCY_ENV: &str = "NALUFX_FETCH_CONCURRENCY";
+
+/// The number of tickers fetched at the same time when `NALUFX_FETCH_CONCURRENCY` is not set.
+pub const DEFAULT_FETCH_CONCURRENCY: usize = 4;
+
+/// Returns the number of tickers fetched at the same time by the batch operations.
+///
+/// The number is read from the `NALUFX_FETCH_CONCURRENCY` environment variable, and defaults
+/// to 4 when the variable is unset, is not a whole number, or is `0`. A higher number speeds up
+/// the analysis of many tickers, at the risk of being rate limited by the data source.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::fetch_concurrency;
+///
+/// env::set_var("NALUFX_FETCH_CONCURRENCY", "8");
+/// assert_eq!(fetch_concurrency(), 8);
+///
+/// env::remove_var("NALUFX_FETCH_CONCURRENCY");
+/// assert_eq!(fetch_concurrency(), 4);
+/// ```
+pub fn fetch_concurrency() -> usize {
+    match env::var(FETCH_CONCURRENCY_ENV).map(|value| value.trim().parse::<usize>()) {
+        Ok(Ok(concurrency)) if concurrency > 0 => concurrency,
+        Ok(_) => {
+            warn!(
+                "Invalid {} value, using the default of {} tickers",
+                FETCH_CONCURRENCY_ENV, DEFAULT_FETCH_CONCURRENCY
+            );
+            DEFAULT_FETCH_CONCURRENCY
+        },
+        Err(_) => DEFAULT_FETCH_CONCURRENCY,
+    }
+}
+
+/// The environment variable that sets the number of decimal places of the printed values.
+pub const DISPLAY_PRECISION_ENV: &str = "NALUFX_DISPLAY_PRECISION";
+
+/// The number of decimal places printed when `NALUFX_DISPLAY_PRECISION` is not set.
+pub const DEFAULT_DISPLAY_PRECISION: usize = 4;
+
+/// The largest number of decimal places accepted in `NALUFX_DISPLAY_PRECISION`.
+pub const MAX_DISPLAY_PRECISION: usize = 17;
+
+/// Returns the number of decimal places of the values printed in reports, such as daily
+/// allocations, sentiment scores and reinforcement learning actions.
+///
+/// The number is read from the `NALUFX_DISPLAY_PRECISION` environment variable, and defaults
+/// to 4 when the variable is unset, is not a whole number, or is greater than
+/// `MAX_DISPLAY_PRECISION`, beyond which an `f64` has no more significant digits. The vectors
+/// are printed with [`fmt_vec`](crate::utils::reports::fmt_vec).
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::display_precision;
+///
+/// env::set_var("NALUFX_DISPLAY_PRECISION", "2");
+/// assert_eq!(display_precision(), 2);
+///
+/// env::remove_var("NALUFX_DISPLAY_PRECISION");
+/// assert_eq!(display_precision(), 4);
+/// ```
+pub fn display_precision() -> usize {
+    match env::var(DISPLAY_PRECISION_ENV).map(|value| value.trim().parse::<usize>()) {
+        Ok(Ok(precision)) if precision <= MAX_DISPLAY_PRECISION => precision,
+        Ok(_) => {
+            warn!(
+                "Invalid {} value, using the default of {} decimal places",
+                DISPLAY_PRECISION_ENV, DEFAULT_DISPLAY_PRECISION
+            );
+            DEFAULT_DISPLAY_PRECISION
+        },
+        Err(_) => DEFAULT_DISPLAY_PRECISION,
+    }
+}
+
+/// The environment variable that sets the `User-Agent` header sent to Yahoo Finance.
+pub const USER_AGENT_ENV: &str = "NALUFX_USER_AGENT";
+
+/// The `User-Agent` header sent to Yahoo Finance when `NALUFX_USER_AGENT` is not set.
+///
+/// Yahoo Finance rejects requests without a browser user agent.
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36";
+
+/// Returns the `User-Agent` header sent to Yahoo Finance.
+///
+/// The user agent is read from the `NALUFX_USER_AGENT` environment variable, and defaults to
+/// `DEFAULT_USER_AGENT` when the variable is unset or blank. Set it when Yahoo Finance starts
+/// rejecting the default browser string.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::{user_agent, DEFAULT_USER_AGENT};
+///
+/// env::set_var("NALUFX_USER_AGENT", "Mozilla/5.0 (Macintosh)");
+/// assert_eq!(user_agent(), "Mozilla/5.0 (Macintosh)");
+///
+/// env::remove_var("NALUFX_USER_AGENT");
+/// assert_eq!(user_agent(), DEFAULT_USER_AGENT);
+/// ```
+pub fn user_agent() -> String {
+    env::var(USER_AGENT_ENV)
+        .ok()
+        .map(|agent| agent.trim().to_string())
+        .filter(|agent| !agent.is_empty())
+        .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string())
+}
+
+/// The environment variable that sets the number of shares in a lot.
+pub const LOT_SIZE_ENV: &str = "NALUFX_LOT_SIZE";
+
+/// The number of shares in a lot when `NALUFX_LOT_SIZE` is not set.
+pub const DEFAULT_LOT_SIZE: u32 = 1;
+
+/// Returns the number of shares in a lot, to which allocations are rounded down.
+///
+/// The number is read from the `NALUFX_LOT_SIZE` environment variable, and defaults to 1, so
+/// that allocations are rounded to whole shares, when the variable is unset, is not a whole
+/// number, or is `0` (see [`round_to_lots`](crate::utils::trading::round_to_lots)).
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::lot_size;
+///
+/// env::set_var("NALUFX_LOT_SIZE", "100");
+/// assert_eq!(lot_size(), 100);
+///
+/// env::remove_var("NALUFX_LOT_SIZE");
+/// assert_eq!(lot_size(), 1);
+/// ```
+pub fn lot_size() -> u32 {
+    match env::var(LOT_SIZE_ENV).map(|value| value.trim().parse::<u32>()) {
+        Ok(Ok(size)) if size > 0 => size,
+        Ok(_) => {
+            warn!(
+                "Invalid {} value, using the default of {} share",
+                LOT_SIZE_ENV, DEFAULT_LOT_SIZE
+            );
+            DEFAULT_LOT_SIZE
+        },
+        Err(_) => DEFAULT_LOT_SIZE,
+    }
+}
+
+/// The environment variable that sets the smallest amount worth trading.
+pub const MIN_TRADE_AMOUNT_ENV: &str = "NALUFX_MIN_TRADE_AMOUNT";
+
+/// The smallest amount worth trading when `NALUFX_MIN_TRADE_AMOUNT` is not set.
+pub const DEFAULT_MIN_TRADE_AMOUNT: u32 = 0;
+
+/// Returns the smallest amount, in whole currency units, worth trading.
+///
+/// The amount is read from the `NALUFX_MIN_TRADE_AMOUNT` environment variable, and defaults to
+/// 0, so that no trade is skipped, when the variable is unset or is not a whole number.
+/// Allocations below this amount are not recommended, since the commission would outweigh
+/// them.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::min_trade_amount;
+///
+/// env::set_var("NALUFX_MIN_TRADE_AMOUNT", "250");
+/// assert_eq!(min_trade_amount(), 250);
+///
+/// env::remove_var("NALUFX_MIN_TRADE_AMOUNT");
+/// assert_eq!(min_trade_amount(), 0);
+/// ```
+pub fn min_trade_amount() -> u32 {
+    match env::var(MIN_TRADE_AMOUNT_ENV).map(|value| value.trim().parse::<u32>()) {
+        Ok(Ok(amount)) => amount,
+        Ok(_) => {
+            warn!(
+                "Invalid {} value, using the default of {}",
+                MIN_TRADE_AMOUNT_ENV, DEFAULT_MIN_TRADE_AMOUNT
+            );
+            DEFAULT_MIN_TRADE_AMOUNT
+        },
+        Err(_) => DEFAULT_MIN_TRADE_AMOUNT,
+    }
+}
+
+/// The environment variable that sets the market holidays, as `YYYY-MM-DD` dates separated by
+/// commas.
+pub const MARKET_HOLIDAYS_ENV: &str = "NALUFX_MARKET_HOLIDAYS";
+
+/// Returns the configured market holidays, if any.
+///
+/// The holidays are read from the `NALUFX_MARKET_HOLIDAYS` environment variable, as
+/// `YYYY-MM-DD` dates separated by commas, and replace the built-in US market holidays (see
+/// [`us_market_holidays`](crate::utils::calendar::us_market_holidays)). Dates that cannot be
+/// parsed are skipped with a warning. When the variable is unset or empty, `None` is returned
+/// and the built-in holidays are used.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use chrono::NaiveDate;
+/// use nalufx::config::market_holidays;
+///
+/// env::set_var("NALUFX_MARKET_HOLIDAYS", "2024-12-24, 2024-12-25");
+/// assert_eq!(
+///     market_holidays(),
+///     Some(vec![
+///         NaiveDate::from_ymd_opt(2024, 12, 24).unwrap(),
+///         NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(),
+///     ])
+/// );
+///
+/// env::remove_var("NALUFX_MARKET_HOLIDAYS");
+/// assert_eq!(market_holidays(), None);
+/// ```
+pub fn market_holidays() -> Option<Vec<NaiveDate>> {
+    let value = env::var(MARKET_HOLIDAYS_ENV).ok().filter(|value| !value.trim().is_empty())?;
+    let holidays = value
+        .split(',')
+        .map(str::trim)
+        .filter(|date| !date.is_empty())
+        .filter_map(|date| match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) => {
+                warn!("Invalid date '{}' in {}, skipping it", date, MARKET_HOLIDAYS_ENV);
+                None
+            },
+        })
+        .collect();
+    Some(holidays)
+}
+
+/// The environment variable that sets the benchmark used when no other benchmark applies.
+pub const BENCHMARK_TICKER_ENV: &str = "NALUFX_BENCHMARK_TICKER";
+
+/// The benchmark used when `NALUFX_BENCHMARK_TICKER` is not set, an S&P 500 ETF.
+pub const DEFAULT_BENCHMARK_TICKER: &str = "SPY";
+
+/// Returns the benchmark used when no benchmark is selected for a ticker symbol or its asset
+/// class.
+///
+/// The benchmark is read from the `NALUFX_BENCHMARK_TICKER` environment variable, in upper case,
+/// and defaults to `DEFAULT_BENCHMARK_TICKER` when the variable is unset or blank.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::{benchmark_ticker, DEFAULT_BENCHMARK_TICKER};
+///
+/// env::set_var("NALUFX_BENCHMARK_TICKER", "vt");
+/// assert_eq!(benchmark_ticker(), "VT");
+///
+/// env::remove_var("NALUFX_BENCHMARK_TICKER");
+/// assert_eq!(benchmark_ticker(), DEFAULT_BENCHMARK_TICKER);
+/// ```
+pub fn benchmark_ticker() -> String {
+    env::var(BENCHMARK_TICKER_ENV)
+        .ok()
+        .map(|ticker| ticker.trim().to_uppercase())
+        .filter(|ticker| !ticker.is_empty())
+        .unwrap_or_else(|| DEFAULT_BENCHMARK_TICKER.to_string())
+}
+
+/// The environment variable that sets the benchmarks by ticker symbol or asset class.
+pub const BENCHMARKS_ENV: &str = "NALUFX_BENCHMARKS";
+
+/// Returns the configured benchmarks by ticker symbol or asset class, if any.
+///
+/// The benchmarks are read from the `NALUFX_BENCHMARKS` environment variable, as `KEY=TICKER`
+/// entries separated by commas, where the key is a ticker symbol or an asset class name (see
+/// [`AssetClass`](crate::utils::benchmark::AssetClass)). Keys and benchmarks are upper-cased.
+/// They take precedence over the built-in benchmarks, and entries without a key or a benchmark
+/// are skipped with a warning. When the variable is unset or empty, `None` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::benchmarks;
+///
+/// env::set_var("NALUFX_BENCHMARKS", "icln=TAN, technology=QQQ");
+/// let configured = benchmarks().unwrap();
+/// assert_eq!(configured["ICLN"], "TAN");
+/// assert_eq!(configured["TECHNOLOGY"], "QQQ");
+///
+/// env::remove_var("NALUFX_BENCHMARKS");
+/// assert_eq!(benchmarks(), None);
+/// ```
+pub fn benchmarks() -> Option<BTreeMap<String, String>> {
+    let value = env::var(BENCHMARKS_ENV).ok().filter(|value| !value.trim().is_empty())?;
+    let benchmarks = value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let (key, benchmark) = entry.split_once('=').unwrap_or((entry, ""));
+            let (key, benchmark) = (key.trim().to_uppercase(), benchmark.trim().to_uppercase());
+            if key.is_empty() || benchmark.is_empty() {
+                warn!("Invalid benchmark '{}' in {}, skipping it", entry, BENCHMARKS_ENV);
+                return None;
+            }
+            Some((key, benchmark))
+        })
+        .collect();
+    Some(benchmarks)
+}
+
+/// The environment variable that sets the number of bootstrap resamples of the confidence
+/// intervals.
+pub const BOOTSTRAP_RESAMPLES_ENV: &str = "NALUFX_BOOTSTRAP_RESAMPLES";
+
+/// The number of bootstrap resamples when `NALUFX_BOOTSTRAP_RESAMPLES` is not set.
+pub const DEFAULT_BOOTSTRAP_RESAMPLES: usize = 1_000;
+
+/// Returns the number of bootstrap resamples used to estimate the confidence intervals of the
+/// reported metrics.
+///
+/// The number is read from the `NALUFX_BOOTSTRAP_RESAMPLES` environment variable, and defaults
+/// to 1,000 when the variable is unset, is not a whole number, or is `0`. More resamples give
+/// more stable bounds, at the cost of a longer analysis (see
+/// [`bootstrap_metric`](crate::services::monte_carlo_svc::bootstrap_metric)).
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::bootstrap_resamples;
+///
+/// env::set_var("NALUFX_BOOTSTRAP_RESAMPLES", "5000");
+/// assert_eq!(bootstrap_resamples(), 5000);
+///
+/// env::remove_var("NALUFX_BOOTSTRAP_RESAMPLES");
+/// assert_eq!(bootstrap_resamples(), 1000);
+/// ```
+pub fn bootstrap_resamples() -> usize {
+    match env::var(BOOTSTRAP_RESAMPLES_ENV).map(|value| value.trim().parse::<usize>()) {
+        Ok(Ok(resamples)) if resamples > 0 => resamples,
+        Ok(_) => {
+            warn!(
+                "Invalid {} value, using the default of {} resamples",
+                BOOTSTRAP_RESAMPLES_ENV, DEFAULT_BOOTSTRAP_RESAMPLES
+            );
+            DEFAULT_BOOTSTRAP_RESAMPLES
+        },
+        Err(_) => DEFAULT_BOOTSTRAP_RESAMPLES,
+    }
+}
+
+/// The environment variable that sets the seed of the bootstrap resampling.
+pub const BOOTSTRAP_SEED_ENV: &str = "NALUFX_BOOTSTRAP_SEED";
+
+/// The seed of the bootstrap resampling when `NALUFX_BOOTSTRAP_SEED` is not set.
+pub const DEFAULT_BOOTSTRAP_SEED: u64 = 42;
+
+/// Returns the seed of the bootstrap resampling, so that the same analysis reports the same
+/// confidence intervals.
+///
+/// The seed is read from the `NALUFX_BOOTSTRAP_SEED` environment variable, and defaults to 42
+/// when the variable is unset or is not a whole number.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::bootstrap_seed;
+///
+/// env::set_var("NALUFX_BOOTSTRAP_SEED", "7");
+/// assert_eq!(bootstrap_seed(), 7);
+///
+/// env::remove_var("NALUFX_BOOTSTRAP_SEED");
+/// assert_eq!(bootstrap_seed(), 42);
+/// ```
+pub fn bootstrap_seed() -> u64 {
+    match env::var(BOOTSTRAP_SEED_ENV).map(|value| value.trim().parse::<u64>()) {
+        Ok(Ok(seed)) => seed,
+        Ok(_) => {
+            warn!(
+                "Invalid {} value, using the default seed of {}",
+                BOOTSTRAP_SEED_ENV, DEFAULT_BOOTSTRAP_SEED
+            );
+            DEFAULT_BOOTSTRAP_SEED
+        },
+        Err(_) => DEFAULT_BOOTSTRAP_SEED,
+    }
+}
+
+/// The environment variable that sets the number of days of history fetched when no start
+/// date is given.
+pub const HISTORY_LOOKBACK_DAYS_ENV: &str = "NALUFX_HISTORY_LOOKBACK_DAYS";
+
+/// The number of days of history fetched when `NALUFX_HISTORY_LOOKBACK_DAYS` is not set (two
+/// years).
+pub const DEFAULT_HISTORY_LOOKBACK_DAYS: u32 = 730;
+
+/// Returns the number of calendar days of history fetched when no start date is given.
+///
+/// The number is read from the `NALUFX_HISTORY_LOOKBACK_DAYS` environment variable, and
+/// defaults to 730 (two years) when the variable is unset, is not a whole number, or is `0`.
+/// Bounding the history keeps the requests fast and small; the whole history of a ticker
+/// symbol can still be fetched with
+/// [`fetch_full_history`](crate::services::fetch_data_svc::fetch_full_history).
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::history_lookback_days;
+///
+/// env::set_var("NALUFX_HISTORY_LOOKBACK_DAYS", "365");
+/// assert_eq!(history_lookback_days(), 365);
+///
+/// env::remove_var("NALUFX_HISTORY_LOOKBACK_DAYS");
+/// assert_eq!(history_lookback_days(), 730);
+/// ```
+pub fn history_lookback_days() -> u32 {
+    match env::var(HISTORY_LOOKBACK_DAYS_ENV).map(|value| value.trim().parse::<u32>()) {
+        Ok(Ok(days)) if days > 0 => days,
+        Ok(_) => {
+            warn!(
+                "Invalid {} value, using the default of {} days",
+                HISTORY_LOOKBACK_DAYS_ENV, DEFAULT_HISTORY_LOOKBACK_DAYS
+            );
+            DEFAULT_HISTORY_LOOKBACK_DAYS
+        },
+        Err(_) => DEFAULT_HISTORY_LOOKBACK_DAYS,
+    }
+}
+
+/// The environment variable that sets the LLM used when none is entered.
+pub const DEFAULT_LLM_ENV: &str = "DEFAULT_LLM";
+
+/// Returns the LLM used when none is entered, if any.
+///
+/// The name is read from the `DEFAULT_LLM` environment variable, in lower case, and must be
+/// one of [`LLM_NAMES`](nalufx_llms::llms::factory::LLM_NAMES). It is `None` when the variable
+/// is unset, blank, or names an unsupported LLM.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::default_llm;
+///
+/// env::set_var("DEFAULT_LLM", "Ollama");
+/// assert_eq!(default_llm().as_deref(), Some("ollama"));
+///
+/// env::set_var("DEFAULT_LLM", "unknown");
+/// assert_eq!(default_llm(), None);
+///
+/// env::remove_var("DEFAULT_LLM");
+/// assert_eq!(default_llm(), None);
+/// ```
+pub fn default_llm() -> Option<String> {
+    let name = env::var(DEFAULT_LLM_ENV).ok()?.trim().to_lowercase();
+    if name.is_empty() {
+        None
+    } else if LLM_NAMES.contains(&name.as_str()) {
+        Some(name)
+    } else {
+        warn!("Unsupported {} value '{}', ignoring it", DEFAULT_LLM_ENV, name);
+        None
+    }
+}
+
+/// The environment variable that lists the environment variables required for the server to be
+/// ready, separated by commas.
+pub const READY_REQUIRED_ENV_ENV: &str = "NALUFX_READY_REQUIRED_ENV";
+
+/// Returns the environment variables that must be set for the server to be ready.
+///
+/// The names are read from the `NALUFX_READY_REQUIRED_ENV` environment variable, separated by
+/// commas. None of the routes of the server needs a secret, so no variable is required when it
+/// is unset or empty. A deployment whose clients need an LLM key can list it, e.g.
+/// `OPENAI_API_KEY`, so that the server is only ready once it is set (see
+/// [`ready`](crate::api::handlers::ready)).
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use nalufx::config::ready_required_env;
+///
+/// env::set_var("NALUFX_READY_REQUIRED_ENV", "OPENAI_API_KEY, NEWS_API_KEY,");
+/// assert_eq!(ready_required_env(), vec!["OPENAI_API_KEY", "NEWS_API_KEY"]);
+///
+/// env::remove_var("NALUFX_READY_REQUIRED_ENV");
+/// assert!(ready_required_env().is_empty());
+/// ```
+pub fn ready_required_env() -> Vec<String> {
+    env::var(READY_REQUIRED_ENV_ENV)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
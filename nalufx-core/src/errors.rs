@@ -1,3 +1,5 @@
+use crate::utils::date::SeriesIssue;
+use nalufx_llms::errors::LlmError;
 use thiserror::Error;
 
 /// Represents an error that can occur in the NaluFx library.
@@ -20,6 +22,18 @@
 /// * `OutlierData` - The input data contains outliers.
 /// * `TechnicalAnalysisError(String)` - An error occurred during technical analysis.
 /// * `PortfolioOptimizationError(String)` - An error occurred during portfolio optimization.
+/// * `EsgRatingNotFound(String)` - No ESG rating is available for the ticker symbol.
+/// * `DiscontinuousSeries(Vec<SeriesIssue>)` - A dated series has gaps, duplicate dates, or out-of-order dates.
+/// * `TemplateError(String)` - A prompt template could not be loaded or rendered.
+/// * `InvalidTicker(String)` - A ticker symbol failed validation.
+/// * `InvalidInput(String)` - An input, such as a date or an amount, failed validation.
+/// * `LlmError(LlmError)` - A request to an LLM provider failed, e.g. because it was rate limited
+///   or no API key is configured. The LLM helpers return an `LlmError`, which `?` converts
+///   into this variant, while a `reqwest::Error` converts into `HttpRequestError`.
+/// * `InvalidInterval(String)` - A data interval is not available for the requested date range.
+/// * `NoDataForTicker(String)` - The data source responded without data for the ticker symbol.
+/// * `ConfigError(String)` - A configuration file could not be parsed or holds invalid values.
+/// * `NewsApiError(String)` - The news API responded with an error.
 ///
 /// # Examples
 ///
@@ -114,6 +128,55 @@ pub enum NaluFxError {
     /// An error occurred with string manipulation.
     #[error("String error: {0}")]
     StringError(String),
+
+    /// No ESG rating is available for the ticker symbol.
+    #[error("No ESG rating available for {0}")]
+    EsgRatingNotFound(String),
+
+    /// A dated series has gaps, duplicate dates, or out-of-order dates.
+    #[error("Date series has {} continuity issue(s): {:?}", .0.len(), .0)]
+    DiscontinuousSeries(Vec<SeriesIssue>),
+
+    /// A prompt template could not be loaded or rendered.
+    #[error("Prompt template error: {0}")]
+    TemplateError(String),
+
+    /// A ticker symbol failed validation.
+    #[error("Invalid ticker symbol {0}")]
+    InvalidTicker(String),
+
+    /// An input, such as a date or an amount, failed validation.
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    /// A request to an LLM provider failed.
+    #[error("{0}")]
+    LlmError(#[from] LlmError),
+
+    /// A data interval is not available for the requested date range.
+    #[error("Invalid interval: {0}")]
+    InvalidInterval(String),
+
+    /// The data source responded without data for the ticker symbol, e.g. because it is
+    /// delisted or invalid.
+    #[error("No data for {0}, the ticker may be delisted or invalid")]
+    NoDataForTicker(String),
+
+    /// A configuration file could not be parsed or holds invalid values.
+    #[error("Invalid configuration: {0}")]
+    ConfigError(String),
+
+    /// The news API responded with an error, e.g. because the API key is invalid.
+    #[error("News API error: {0}")]
+    NewsApiError(String),
+}
+
+impl From<&str> for NaluFxError {
+    /// Converts the message of an LLM helper, such as a missing API key, into
+    /// `NaluFxError::LlmError`.
+    fn from(message: &str) -> Self {
+        NaluFxError::LlmError(LlmError::from(message))
+    }
 }
 
 /// Represents an error that can occur during allocation.
@@ -124,14 +187,19 @@ pub enum NaluFxError {
 ///
 /// # Variants
 ///
-/// * `InputMismatch` - The input slices must have the same length.
+/// * `InputMismatch { expected, expected_len, found, found_len }` - The input slices must have
+///   the same length.
 /// * `EmptyInput` - The input slices cannot be empty.
+/// * `InsufficientData { required, provided }` - The input slices contain fewer values than required.
 /// * `ClusteringError(String)` - An error occurred during clustering.
 /// * `InvalidData` - The input data contains missing or invalid values.
 /// * `OutlierData` - The input data contains outliers.
 /// * `ForecastingError(String)` - An error occurred during time series forecasting.
 /// * `SentimentAnalysisError(String)` - An error occurred during sentiment analysis.
 /// * `ReinforcementLearningError(String)` - An error occurred during reinforcement learning.
+/// * `ZeroTotalAllocation` - No allocation is positive, so the allocations cannot be normalized.
+/// * `InfeasibleConstraints { min_weight, max_weight, count }` - No `count` weights between
+///   `min_weight` and `max_weight` sum to 1.
 ///
 /// # Examples
 ///
@@ -145,23 +213,52 @@ pub enum NaluFxError {
 #[derive(Debug, Error, PartialEq)]
 pub enum AllocationError {
     /// The input slices must have the same length.
-    #[error("Input slices must have the same length")]
-    InputMismatch,
+    #[error(
+        "Input slices have different lengths: {expected} has {expected_len} values but {found} \
+         has {found_len}; {expected} and {found} must match"
+    )]
+    InputMismatch {
+        /// The name of the first input, whose length the others must match.
+        expected: String,
+        /// The number of values of the first input.
+        expected_len: usize,
+        /// The name of the first input whose length differs.
+        found: String,
+        /// The number of values of the input whose length differs.
+        found_len: usize,
+    },
 
     /// The input slices cannot be empty.
-    #[error("Input slices cannot be empty")]
+    #[error("Input slices cannot be empty: provide at least one value for each input")]
     EmptyInput,
 
+    /// The input slices contain fewer values than required.
+    #[error(
+        "Insufficient data: at least {required} values are required, but {provided} were provided"
+    )]
+    InsufficientData {
+        /// The minimum number of values required.
+        required: usize,
+        /// The number of values provided.
+        provided: usize,
+    },
+
     /// An error occurred during clustering.
     #[error("Error during clustering: {0}")]
     ClusteringError(String),
 
     /// The input data contains missing or invalid values.
-    #[error("Input data contains missing or invalid values")]
+    #[error(
+        "Input data contains missing or invalid values: remove or fill the NaN and infinite \
+         values before allocating"
+    )]
     InvalidData,
 
     /// The input data contains outliers.
-    #[error("Input data contains outliers")]
+    #[error(
+        "Input data contains outliers: a value exceeds the largest magnitude allowed for its \
+         input; check the data for errors, or raise the thresholds in AllocationOptions"
+    )]
     OutlierData,
 
     /// An error occurred during time series forecasting.
@@ -175,4 +272,22 @@ pub enum AllocationError {
     /// An error occurred during reinforcement learning.
     #[error("Error during reinforcement learning: {0}")]
     ReinforcementLearningError(String),
+
+    /// No allocation is positive, so the allocations cannot be normalized.
+    #[error("Total allocation is zero: no allocation is positive, so nothing can be invested")]
+    ZeroTotalAllocation,
+
+    /// The weight constraints cannot be met.
+    #[error(
+        "Infeasible constraints: {count} weights between {min_weight} and {max_weight} cannot \
+         sum to 1"
+    )]
+    InfeasibleConstraints {
+        /// The smallest weight allowed.
+        min_weight: f64,
+        /// The largest weight allowed.
+        max_weight: f64,
+        /// The number of weights.
+        count: usize,
+    },
 }
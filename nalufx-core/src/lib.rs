@@ -54,6 +54,9 @@
 /// This module contains data models used throughout the application.
 pub mod models;
 
+/// This module contains the prompt templates sent to the language models.
+pub mod prompts;
+
 /// This module contains the service layer with business logic.
 pub mod services;
 
@@ -1,7 +1,9 @@
 /// Checks if the input slices have the same length.
 ///
-/// This macro accepts multiple slices and verifies that all slices have the same length. If any
-/// two slices have different lengths, it returns an `AllocationError::InputMismatch` error.
+/// This macro accepts multiple slices and verifies that all slices have the same length. If a
+/// slice has a different length from the first one, it returns an
+/// `AllocationError::InputMismatch` error with the names and lengths of both slices, so that
+/// the message says which input to fix.
 ///
 /// # Arguments
 ///
@@ -13,17 +15,29 @@
 /// use nalufx::check_input_lengths;
 /// use nalufx::errors::AllocationError;
 ///
-/// let daily_returns = vec![0.01, 0.02, -0.01];
-/// let cash_flows = vec![1000.0, 1020.0, 1010.0];
-/// check_input_lengths!(daily_returns, cash_flows)?;
-/// # Ok::<(), AllocationError>(())
+/// fn check(daily_returns: &[f64], cash_flows: &[f64]) -> Result<(), AllocationError> {
+///     check_input_lengths!(daily_returns, cash_flows)
+/// }
+///
+/// assert!(check(&[0.01, 0.02, -0.01], &[1000.0, 1020.0, 1010.0]).is_ok());
+/// assert_eq!(
+///     check(&[0.01, 0.02, -0.01], &[1000.0, 1020.0]).unwrap_err().to_string(),
+///     "Input slices have different lengths: daily_returns has 3 values but cash_flows has 2; \
+///      daily_returns and cash_flows must match"
+/// );
 /// ```
 #[macro_export]
 macro_rules! check_input_lengths {
     ($($input:expr),+ $(,)?) => {{
-        let lengths = vec![$($input.len()),+];
-        if lengths.windows(2).any(|w| w[0] != w[1]) {
-            return Err(AllocationError::InputMismatch);
+        let inputs = [$((stringify!($input), $input.len())),+];
+        let (expected, expected_len) = inputs[0];
+        if let Some(&(found, found_len)) = inputs.iter().find(|&&(_, len)| len != expected_len) {
+            return Err(AllocationError::InputMismatch {
+                expected: expected.to_string(),
+                expected_len,
+                found: found.to_string(),
+                found_len,
+            });
         }
         Ok::<(), AllocationError>(())
     }};
@@ -59,6 +73,41 @@ macro_rules! check_empty_inputs {
     }};
 }
 
+/// Checks if the input slices contain at least the required number of values.
+///
+/// This macro accepts a minimum length and multiple slices, and verifies that every slice has
+/// at least that many values. If any slice is shorter, it returns an
+/// `AllocationError::InsufficientData` error with the required minimum and the length of the
+/// shortest slice.
+///
+/// # Arguments
+///
+/// * `$required` - The minimum number of values each slice must contain.
+/// * `$input` - A variadic list of input slices to check.
+///
+/// # Example
+///
+/// ```
+/// use nalufx::check_sufficient_data;
+/// use nalufx::errors::AllocationError;
+///
+/// let daily_returns = vec![0.01, 0.02, -0.01];
+/// let cash_flows = vec![1000.0, 1020.0, 1010.0];
+/// check_sufficient_data!(2, daily_returns, cash_flows)?;
+/// # Ok::<(), AllocationError>(())
+/// ```
+#[macro_export]
+macro_rules! check_sufficient_data {
+    ($required:expr, $($input:expr),+ $(,)?) => {{
+        let required: usize = $required;
+        let provided = [$($input.len()),+].into_iter().min().unwrap_or(0);
+        if provided < required {
+            return Err(AllocationError::InsufficientData { required, provided });
+        }
+        Ok::<(), AllocationError>(())
+    }};
+}
+
 /// Checks if the input slices contain invalid data (NaN or infinite values).
 ///
 /// This macro accepts multiple slices and verifies that none of the slices contain NaN or
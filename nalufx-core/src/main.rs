@@ -5,6 +5,10 @@
 //! ## Features
 //! - Predict cash flows based on historical data
 //! - Optimize portfolio allocations
+//! - Liveness (`GET /health`) and readiness (`GET /ready`) probes
+//! - Prometheus metrics (`GET /metrics`)
+//! - Allocation updates pushed over a WebSocket (`GET /ws/allocations`)
+//! - The OpenAPI document of the API (`GET /openapi.json`)
 //!
 //! ## Getting Started
 //! To run the application, ensure that you have the necessary environment variables set in a `.env` file:
@@ -13,6 +17,15 @@
 //! SERVER_ADDR=127.0.0.1:8080
 //! ```
 //!
+//! Requests are rate limited per client IP, to 60 requests per minute by default, except the
+//! `/health` and `/ready` probes. Set `API_RATE_LIMIT_PER_MIN` to change the limit.
+//!
+//! Set `API_AUTH_TOKEN` to require an `Authorization: Bearer <token>` header on every request
+//! except the `/health` and `/ready` probes.
+//!
+//! `/ready` reports the server as ready once every environment variable listed in
+//! `NALUFX_READY_REQUIRED_ENV` (comma-separated, none by default) is set.
+//!
 //! Then, run the application using the following command:
 //!
 //! ```bash
@@ -23,7 +36,12 @@
 
 use actix_web::{App, HttpServer};
 use dotenvy::dotenv;
-use nalufx::api::handlers::predict_cash_flow;
+use log::warn;
+use nalufx::api::auth::BearerAuth;
+use nalufx::api::handlers::{health, metrics, predict_cash_flow, ready};
+use nalufx::api::openapi::openapi_json;
+use nalufx::api::rate_limit::RateLimiter;
+use nalufx::api::ws::allocations_ws;
 use nalufx::config::Config;
 
 /// The main entry point of the application.
@@ -62,5 +80,27 @@ async fn main() -> std::io::Result<()> {
 
     let config = Config::from_env().expect("Failed to load configuration");
 
-    HttpServer::new(|| App::new().service(predict_cash_flow)).bind(config.server_addr)?.run().await
+    let rate_limiter = RateLimiter::new(config.api_rate_limit_per_min);
+    let auth = match config.api_auth_token.as_deref() {
+        Some(token) => BearerAuth::new(token),
+        None => {
+            warn!("API_AUTH_TOKEN is not set, so the API does not require authentication");
+            BearerAuth::disabled()
+        },
+    };
+
+    HttpServer::new(move || {
+        App::new()
+            .wrap(auth.clone())
+            .wrap(rate_limiter.clone())
+            .service(predict_cash_flow)
+            .service(health)
+            .service(ready)
+            .service(metrics)
+            .service(allocations_ws)
+            .service(openapi_json)
+    })
+    .bind(config.server_addr)?
+    .run()
+    .await
 }
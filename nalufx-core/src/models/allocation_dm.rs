@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Represents an order to allocate a certain amount of funds to a particular symbol.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AllocationOrder {
     /// The symbol of the asset (e.g., stock ticker).
     pub symbol: String,
@@ -9,10 +9,34 @@ pub struct AllocationOrder {
     pub name: String,
     /// The amount to be allocated.
     pub amount: f64,
+    /// The number of shares bought, or `0` until the order is executed.
+    #[serde(default)]
+    pub shares: f64,
+}
+
+/// Represents the outcome of executing allocation orders at the current prices.
+///
+/// Orders are rounded down to the shares they can buy, so part of the cash is not deployed.
+/// The amounts of the executed orders and the remaining cash add up to the amount of the
+/// orders before they were executed.
+///
+/// # Fields
+///
+/// * `orders` - The executed orders, whose amount is the cost of their shares.
+/// * `allocated` - The total cost of the shares bought.
+/// * `cash_remaining` - The cash that was not deployed, because of rounding or a missing price.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CashAllocation {
+    /// The executed orders, whose amount is the cost of their shares.
+    pub orders: Vec<AllocationOrder>,
+    /// The total cost of the shares bought.
+    pub allocated: f64,
+    /// The cash that was not deployed.
+    pub cash_remaining: f64,
 }
 
 /// Represents an Exchange Traded Fund (ETF) with its details.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Etf {
     /// The symbol of the ETF.
     pub symbol: String,
@@ -25,7 +49,7 @@ pub struct Etf {
 }
 
 /// Represents a Mutual Fund with its details.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MutualFund {
     /// The symbol of the Mutual Fund.
     pub symbol: String,
@@ -38,7 +62,7 @@ pub struct MutualFund {
 }
 
 /// Represents the allocation rules specifying the percentages for ETFs and Mutual Funds.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AllocationRules {
     /// The percentage of the total investment to be allocated to ETFs.
     pub etf_percentage: f64,
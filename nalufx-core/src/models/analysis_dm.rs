@@ -0,0 +1,85 @@
+use crate::utils::date::SeriesIssue;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Represents the summary metrics of an analysis.
+///
+/// # Fields
+///
+/// * `total_return` - The return over the analysis period, as a fraction of the first close.
+/// * `annualized_volatility` - The annualized volatility of the daily returns.
+/// * `min_allocation` - The smallest daily allocation, as a fraction of the investment.
+/// * `max_allocation` - The largest daily allocation, as a fraction of the investment.
+/// * `simulated_value_5th` - The 5th percentile of the simulated value of the investment at
+///   the end of a horizon as long as the analyzed history.
+/// * `simulated_value_95th` - The 95th percentile of the simulated value of the investment at
+///   the end of the same horizon.
+/// * `sharpe_ratio` - The annualized Sharpe ratio of the daily returns.
+/// * `sharpe_ratio_lower` - The lower bound of the 95% bootstrap confidence interval of the
+///   Sharpe ratio.
+/// * `sharpe_ratio_upper` - The upper bound of the same interval.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisMetrics {
+    /// The return over the analysis period, as a fraction of the first close.
+    pub total_return: f64,
+    /// The annualized volatility of the daily returns.
+    pub annualized_volatility: f64,
+    /// The smallest daily allocation, as a fraction of the investment.
+    pub min_allocation: f64,
+    /// The largest daily allocation, as a fraction of the investment.
+    pub max_allocation: f64,
+    /// The 5th percentile of the simulated value of the investment.
+    pub simulated_value_5th: f64,
+    /// The 95th percentile of the simulated value of the investment.
+    pub simulated_value_95th: f64,
+    /// The annualized Sharpe ratio of the daily returns.
+    pub sharpe_ratio: f64,
+    /// The lower bound of the 95% confidence interval of the Sharpe ratio.
+    pub sharpe_ratio_lower: f64,
+    /// The upper bound of the 95% confidence interval of the Sharpe ratio.
+    pub sharpe_ratio_upper: f64,
+}
+
+/// Represents the structured result of an analysis, separate from how it is reported.
+///
+/// Library consumers can use this struct directly instead of parsing the printed report. It
+/// serializes to JSON with the field names below and dates in RFC 3339 format.
+///
+/// # Fields
+///
+/// * `ticker` - The ticker symbol of the analyzed stock.
+/// * `start_date` - The date of the first closing price analyzed.
+/// * `end_date` - The date of the last closing price analyzed.
+/// * `initial_investment` - The initial investment amount.
+/// * `allocations` - The daily allocations, as fractions of the investment that sum to 1.
+/// * `sentiment` - The daily sentiment scores, empty if sentiment analysis failed.
+/// * `actions` - The daily reinforcement learning actions, empty if training failed.
+/// * `metrics` - The summary metrics of the analysis.
+/// * `key_findings` - The lines of the LLM response that mention the ticker.
+/// * `llm_response` - The full text of the LLM response.
+/// * `continuity_issues` - The gaps and duplicates found in the price series.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisResult {
+    /// The ticker symbol of the analyzed stock.
+    pub ticker: String,
+    /// The date of the first closing price analyzed.
+    pub start_date: DateTime<Utc>,
+    /// The date of the last closing price analyzed.
+    pub end_date: DateTime<Utc>,
+    /// The initial investment amount.
+    pub initial_investment: f64,
+    /// The daily allocations, as fractions of the investment that sum to 1.
+    pub allocations: Vec<f64>,
+    /// The daily sentiment scores.
+    pub sentiment: Vec<f64>,
+    /// The daily reinforcement learning actions.
+    pub actions: Vec<f64>,
+    /// The summary metrics of the analysis.
+    pub metrics: AnalysisMetrics,
+    /// The lines of the LLM response that mention the ticker.
+    pub key_findings: Vec<String>,
+    /// The full text of the LLM response.
+    pub llm_response: String,
+    /// The gaps and duplicates found in the price series.
+    pub continuity_issues: Vec<SeriesIssue>,
+}
@@ -1,15 +1,29 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Represents a request to predict cash flow based on historical data.
 ///
 /// This struct is used to encapsulate the historical cash flow data
 /// which will be used by the prediction service to forecast future cash flows.
 ///
+/// The JSON field names are in camel case (`historicalData`), and `historical_data` is also
+/// accepted for compatibility with earlier clients.
+///
 /// # Fields
 ///
 /// * `historical_data` - A vector of historical cash flow data. Each entry represents
 ///   the cash flow value at a specific time point. It is expected to be a time-series
 ///   data in chronological order.
+/// * `dates` - The optional dates of the `historical_data` entries, in `YYYY-MM-DD` format.
+///   When present, there must be one date per entry, in strictly ascending order, and the
+///   response is dated with the trading days that follow the last one.
+/// * `market_indices` - The optional level of a market index on the date of each
+///   `historical_data` entry, e.g. the close of a benchmark index.
+/// * `fund_characteristics` - The optional characteristic of the fund on the date of each
+///   `historical_data` entry, e.g. its net asset value or trading volume.
+///
+/// The market indices and fund characteristics are features of the days, used with the
+/// returns and cash flows to cluster them. When present, there must be one value per entry.
 ///
 /// # Examples
 ///
@@ -18,15 +32,40 @@
 ///
 /// let request = CashFlowRequest {
 ///     historical_data: vec![1.0, 2.0, 3.0],
+///     dates: Some(vec![
+///         "2024-06-26".to_string(),
+///         "2024-06-27".to_string(),
+///         "2024-06-28".to_string(),
+///     ]),
+///     market_indices: Some(vec![5460.5, 5482.9, 5460.5]),
+///     fund_characteristics: None,
 /// };
 /// ```
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct CashFlowRequest {
     /// A vector of historical cash flow data.
     ///
     /// Each entry in this vector represents the cash flow value at a specific time point.
     /// The data is expected to be ordered chronologically.
+    #[serde(alias = "historical_data")]
     pub historical_data: Vec<f64>,
+    /// The dates of the historical data entries, in `YYYY-MM-DD` format.
+    ///
+    /// Requests without dates are accepted, and get an undated response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dates: Option<Vec<String>>,
+    /// The level of a market index on the date of each historical data entry.
+    ///
+    /// Requests without market indices are accepted, and the days are clustered without them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_indices: Option<Vec<f64>>,
+    /// The characteristic of the fund on the date of each historical data entry.
+    ///
+    /// Requests without fund characteristics are accepted, and the days are clustered without
+    /// them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fund_characteristics: Option<Vec<f64>>,
 }
 
 /// Represents a response with predicted cash flow and optimal allocation.
@@ -35,6 +74,8 @@ pub struct CashFlowRequest {
 /// including the predicted future cash flow values and the optimal allocation
 /// percentages for investment or resource allocation.
 ///
+/// The JSON field names are in camel case (`predictions`, `optimalAllocation`).
+///
 /// # Fields
 ///
 /// * `predictions` - A vector of predicted cash flow values. Each entry represents
@@ -42,6 +83,8 @@ pub struct CashFlowRequest {
 /// * `optimal_allocation` - A vector of optimal allocation percentages. Each entry
 ///   represents the percentage of allocation for a specific resource or investment
 ///   based on the predicted cash flow.
+/// * `dates` - The trading days of the predictions and allocations, in `YYYY-MM-DD` format,
+///   if the request was dated.
 ///
 /// # Examples
 ///
@@ -51,9 +94,11 @@ pub struct CashFlowRequest {
 /// let response = CashFlowResponse {
 ///     predictions: vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
 ///     optimal_allocation: vec![0.5, 0.3, 0.2],
+///     dates: None,
 /// };
 /// ```
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct CashFlowResponse {
     /// A vector of predicted cash flow values.
     ///
@@ -64,6 +109,11 @@ pub struct CashFlowResponse {
     /// Each entry in this vector represents the percentage of allocation for a specific resource or investment
     /// based on the predicted cash flow.
     pub optimal_allocation: Vec<f64>,
+    /// The trading days of the predictions and allocations, in `YYYY-MM-DD` format.
+    ///
+    /// The field is omitted when the request was not dated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dates: Option<Vec<String>>,
 }
 
 /// Represents an error response with an error message.
@@ -87,7 +137,8 @@ pub struct CashFlowResponse {
 ///     error: "Invalid historical data".to_string(),
 /// };
 /// ```
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ErrorResponse {
     /// A string containing the error message.
     ///
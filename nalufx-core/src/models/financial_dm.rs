@@ -2,6 +2,9 @@
 
 /// Represents a request for cash flow predictions, containing historical financial data.
 ///
+/// The JSON field names are in camel case (`historicalData`), and `historical_data` is also
+/// accepted for compatibility with earlier clients.
+///
 /// # Fields
 ///
 /// * `historical_data` - A vector of historical data points (as floating-point numbers).
@@ -16,13 +19,17 @@
 /// };
 /// ```
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct CashFlowRequest {
     /// A vector of historical data points (as floating-point numbers).
+    #[serde(alias = "historical_data")]
     pub historical_data: Vec<f64>,
 }
 
 /// Represents a response containing cash flow predictions and optimal allocations.
 ///
+/// The JSON field names are in camel case (`predictions`, `optimalAllocation`).
+///
 /// # Fields
 ///
 /// * `predictions` - A vector of predicted cash flow values (as floating-point numbers).
@@ -39,6 +46,7 @@ pub struct CashFlowRequest {
 /// };
 /// ```
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct CashFlowResponse {
     /// A vector of predicted cash flow values (as floating-point numbers).
     pub predictions: Vec<f64>,
@@ -62,6 +70,7 @@ pub struct CashFlowResponse {
 /// };
 /// ```
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
 pub struct ErrorResponse {
     /// A string containing the error message.
     pub error: String,
@@ -85,6 +94,7 @@ pub struct ErrorResponse {
 /// };
 /// ```
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct HistoricalData {
     /// A string representing the financial ticker symbol.
     pub ticker: String,
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Represents the response of the health and readiness endpoints.
+///
+/// # Fields
+///
+/// * `status` - The status of the server: `ok` for liveness, and `ready` or `unready` for
+///   readiness.
+/// * `missing` - The missing dependencies that make the server unready. It is omitted from
+///   the JSON when empty.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::models::health_dm::HealthResponse;
+///
+/// let response = HealthResponse::new("ok");
+/// assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"status":"ok"}"#);
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthResponse {
+    /// The status of the server.
+    pub status: String,
+    /// The missing dependencies that make the server unready.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+}
+
+impl HealthResponse {
+    /// Creates a new `HealthResponse` with the given status and no missing dependencies.
+    pub fn new(status: &str) -> Self {
+        Self { status: status.to_string(), missing: Vec::new() }
+    }
+}
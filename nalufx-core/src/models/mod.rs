@@ -1,11 +1,20 @@
 /// Data models for the allocation calculations.
 pub mod allocation_dm;
 
+/// Data models for the structured analysis results.
+pub mod analysis_dm;
+
 /// Data models for the OpenAI API.
 pub mod cash_flow_dm;
 
+/// Data models for the health and readiness endpoints.
+pub mod health_dm;
+
 /// Data models for the financial calculations.
 pub mod financial_dm;
 
 /// Data models for the error ASCII art.
 pub mod ascii_art_dm;
+
+/// Data models for the WebSocket streams.
+pub mod stream_dm;
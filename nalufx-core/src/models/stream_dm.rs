@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents the subscription message a client sends on the allocation WebSocket.
+///
+/// A new subscription replaces the previous one.
+///
+/// # Fields
+///
+/// * `tickers` - The ticker symbols whose allocation is pushed.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::models::stream_dm::AllocationSubscription;
+///
+/// let subscription: AllocationSubscription =
+///     serde_json::from_str(r#"{"tickers":["AAPL","MSFT"]}"#).unwrap();
+/// assert_eq!(subscription.tickers, vec!["AAPL", "MSFT"]);
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AllocationSubscription {
+    /// The ticker symbols whose allocation is pushed.
+    pub tickers: Vec<String>,
+}
+
+/// Represents a frame pushed to the client on the allocation WebSocket.
+///
+/// The frame is tagged with its `type`, `allocation` or `error`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::models::stream_dm::AllocationFrame;
+///
+/// let frame = AllocationFrame::Error { error: "Invalid subscription".to_string() };
+/// assert_eq!(
+///     serde_json::to_string(&frame).unwrap(),
+///     r#"{"type":"error","error":"Invalid subscription"}"#
+/// );
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AllocationFrame {
+    /// The recomputed allocation of the subscribed tickers.
+    Allocation {
+        /// The ticker symbols, in the order of `weights`.
+        tickers: Vec<String>,
+        /// The weight of each ticker, summing to 1.
+        weights: Vec<f64>,
+    },
+    /// An error with the subscription or the computation of the allocation.
+    Error {
+        /// The description of the error.
+        error: String,
+    },
+}
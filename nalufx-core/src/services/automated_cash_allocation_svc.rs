@@ -1,11 +1,162 @@
-use crate::models::allocation_dm::AllocationOrder;
+use crate::errors::NaluFxError;
+use crate::models::allocation_dm::{AllocationOrder, AllocationRules, CashAllocation};
+use crate::prompts::{load_template, sanitize_user_text};
 use crate::utils::currency::format_currency;
+use crate::utils::metrics::METRICS;
+use crate::utils::trading::ShareRounding;
 use nalufx_llms::llms::LLM;
 use reqwest::Client;
 use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+/// Represents the format of a configuration file.
+///
+/// # Variants
+///
+/// * `Json` - A JSON file, with the `.json` extension.
+/// * `Toml` - A TOML file, with the `.toml` extension.
+/// * `Yaml` - A YAML file, with the `.yaml` or `.yml` extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConfigFormat {
+    /// A JSON file.
+    Json,
+    /// A TOML file.
+    Toml,
+    /// A YAML file.
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Detects the format of a configuration file from its extension, in any case.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the configuration file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NaluFxError::ConfigError` if the extension is missing or not supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nalufx::services::automated_cash_allocation_svc::ConfigFormat;
+    /// use std::path::Path;
+    ///
+    /// let format = ConfigFormat::from_path(Path::new("data/allocation_rules.YML"));
+    /// assert_eq!(format.unwrap(), ConfigFormat::Yaml);
+    /// assert!(ConfigFormat::from_path(Path::new("allocation_rules.ini")).is_err());
+    /// ```
+    pub fn from_path(path: &Path) -> Result<Self, NaluFxError> {
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Ok(ConfigFormat::Json),
+            "toml" => Ok(ConfigFormat::Toml),
+            "yaml" | "yml" => Ok(ConfigFormat::Yaml),
+            _ => Err(NaluFxError::ConfigError(format!(
+                "{} is not a JSON, TOML or YAML file",
+                path.display()
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ConfigFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigFormat::Json => write!(f, "JSON"),
+            ConfigFormat::Toml => write!(f, "TOML"),
+            ConfigFormat::Yaml => write!(f, "YAML"),
+        }
+    }
+}
+
+/// Parses allocation rules in the given format, and validates them.
+///
+/// The percentages must be finite and non-negative, and the ETF and Mutual Fund percentages
+/// must not add up to more than 100.
+///
+/// # Arguments
+///
+/// * `data` - The content of the configuration file.
+/// * `format` - The format of the content.
+///
+/// # Errors
+///
+/// Returns `NaluFxError::ConfigError` if the content cannot be parsed, or if the percentages
+/// are invalid.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::automated_cash_allocation_svc::{parse_allocation_rules, ConfigFormat};
+///
+/// let toml = "etf_percentage = 70.0\nmutual_fund_percentage = 30.0\n";
+/// let rules = parse_allocation_rules(toml, ConfigFormat::Toml).unwrap();
+/// assert_eq!(rules.etf_percentage, 70.0);
+///
+/// let yaml = "etf_percentage: 80\nmutual_fund_percentage: 30\n";
+/// assert!(parse_allocation_rules(yaml, ConfigFormat::Yaml).is_err());
+/// ```
+pub fn parse_allocation_rules(
+    data: &str,
+    format: ConfigFormat,
+) -> Result<AllocationRules, NaluFxError> {
+    let rules: AllocationRules = match format {
+        ConfigFormat::Json => serde_json::from_str(data).map_err(|e| e.to_string()),
+        ConfigFormat::Toml => toml::from_str(data).map_err(|e| e.to_string()),
+        ConfigFormat::Yaml => serde_yaml::from_str(data).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| NaluFxError::ConfigError(format!("Invalid {} allocation rules: {}", format, e)))?;
+
+    let AllocationRules { etf_percentage, mutual_fund_percentage } = rules;
+    let valid = |percentage: f64| percentage.is_finite() && percentage >= 0.0;
+    if !valid(etf_percentage) || !valid(mutual_fund_percentage) {
+        return Err(NaluFxError::ConfigError(
+            "Allocation percentages must be non-negative numbers".to_string(),
+        ));
+    }
+    if etf_percentage + mutual_fund_percentage > 100.0 {
+        return Err(NaluFxError::ConfigError(format!(
+            "Allocation percentages add up to {}%, more than 100%",
+            etf_percentage + mutual_fund_percentage
+        )));
+    }
+    Ok(rules)
+}
+
+/// Loads allocation rules from a JSON, TOML or YAML file, detecting the format from the
+/// extension of the file.
+///
+/// See [`parse_allocation_rules`] for how the rules are validated.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file (e.g., "data/allocation_rules.toml").
+///
+/// # Errors
+///
+/// Returns `NaluFxError::InputError` if the file cannot be read, or
+/// `NaluFxError::ConfigError` if its format is not supported or its rules are invalid.
+pub fn load_allocation_rules(path: &Path) -> Result<AllocationRules, NaluFxError> {
+    let format = ConfigFormat::from_path(path)?;
+    let data = fs::read_to_string(path).map_err(|e| {
+        NaluFxError::InputError(std::io::Error::new(
+            e.kind(),
+            format!("Failed to open allocation rules file: {}", path.display()),
+        ))
+    })?;
+    parse_allocation_rules(&data, format)
+}
 
 /// This function generates a comprehensive analysis report for a given portfolio.
 ///
+/// The portfolio name, values and financial objectives are entered by the user, so they are
+/// passed through `sanitize_user_text` before they are substituted into the prompt.
+///
 /// # Arguments
 ///
 /// * `llm` - A boxed trait object implementing the LLM trait.
@@ -22,7 +173,12 @@
 ///
 /// # Returns
 ///
-/// * `Result<String, Box<dyn std::error::Error>>` - A Result containing the generated report as a string on success, or an error on failure.
+/// * `Result<String, NaluFxError>` - A Result containing the generated report as a string on success, or an error on failure.
+///
+/// # Errors
+///
+/// Returns `NaluFxError::TemplateError` if the prompt cannot be rendered, or
+/// `NaluFxError::LlmError` if the LLM request fails.
 pub async fn generate_analysis(
     llm: Box<dyn LLM>,
     client: &Client,
@@ -35,7 +191,7 @@ pub async fn generate_analysis(
     start_date: &str,
     end_date: &str,
     real_time_prices: &HashMap<String, (f64, f64)>,
-) -> Result<String, Box<dyn std::error::Error>> {
+) -> Result<String, NaluFxError> {
     let allocations_str = etf_allocation
         .iter()
         .map(|order| {
@@ -61,15 +217,82 @@ pub async fn generate_analysis(
         .collect::<Vec<_>>()
         .join("\n");
 
-    let prompt = format!(
-        "Portfolio Name: {}\n\nPortfolio Allocations:\n{}\n\nInvestor Values: {}\nFinancial Objectives: {}\nStart Date: {}\nEnd Date: {}\n\nPerformance:\n{}",
-        portfolio_name, allocations_str, values_input, financial_objectives_input, start_date, end_date, performance_str
-    );
+    let prompt = load_template("cash_allocation")?.render(&[
+        ("portfolio_name", &sanitize_user_text(portfolio_name)),
+        ("allocations", &allocations_str),
+        ("values", &sanitize_user_text(values_input)),
+        ("financial_objectives", &sanitize_user_text(financial_objectives_input)),
+        ("start_date", start_date),
+        ("end_date", end_date),
+        ("performance", &performance_str),
+    ])?;
 
-    let response = llm.send_request(client, api_key, &prompt, 1500).await?;
+    let started = Instant::now();
+    let response = llm.send_request(client, api_key, &prompt, 1500).await;
+    METRICS.record_llm_request(started.elapsed(), response.is_ok());
+    let response = response?;
 
     let generated_report =
         response["choices"][0]["message"]["content"].as_str().unwrap_or("").to_string();
 
     Ok(generated_report)
 }
+
+/// Executes allocation orders at the current prices, keeping track of the cash left over.
+///
+/// Each order is rounded down to the shares its amount buys at the last price of its symbol,
+/// and its amount becomes the cost of these shares. The difference is kept in cash, as is the
+/// whole amount of an order whose symbol has no valid price.
+///
+/// # Arguments
+///
+/// * `orders` - The orders to execute, with the amount to allocate to each symbol.
+/// * `prices` - The start and last prices of each symbol.
+/// * `rounding` - How the amounts are converted into shares (e.g., `ShareRounding::Lots(1)` for
+///   whole shares).
+///
+/// # Returns
+///
+/// The executed orders, the total cost of their shares, and the cash remaining, which add up
+/// to the total amount of `orders`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use nalufx::models::allocation_dm::AllocationOrder;
+/// use nalufx::services::automated_cash_allocation_svc::execute_orders;
+/// use nalufx::utils::trading::ShareRounding;
+///
+/// let orders = vec![AllocationOrder {
+///     symbol: "VTI".to_string(),
+///     name: "Vanguard Total Stock Market ETF".to_string(),
+///     amount: 1000.0,
+///     shares: 0.0,
+/// }];
+/// let prices = HashMap::from([("VTI".to_string(), (90.0, 97.0))]);
+///
+/// let allocation = execute_orders(&orders, &prices, ShareRounding::Lots(1));
+/// assert_eq!(allocation.orders[0].shares, 10.0);
+/// assert_eq!(allocation.allocated, 970.0);
+/// assert_eq!(allocation.cash_remaining, 30.0);
+/// ```
+pub fn execute_orders(
+    orders: &[AllocationOrder],
+    prices: &HashMap<String, (f64, f64)>,
+    rounding: ShareRounding,
+) -> CashAllocation {
+    let mut cash_remaining = 0.0;
+    let orders: Vec<AllocationOrder> = orders
+        .iter()
+        .map(|order| {
+            let price = prices.get(&order.symbol).map_or(0.0, |&(_, last_price)| last_price);
+            let (shares, residual) = rounding.round(order.amount, price);
+            cash_remaining += residual;
+            AllocationOrder { amount: order.amount - residual, shares, ..order.clone() }
+        })
+        .collect();
+    let allocated = orders.iter().map(|order| order.amount).sum();
+
+    CashAllocation { orders, allocated, cash_remaining }
+}
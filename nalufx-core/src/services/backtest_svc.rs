@@ -0,0 +1,245 @@
+use crate::{
+    errors::AllocationError,
+    services::processing_svc::{calculate_cash_flows, calculate_daily_returns},
+    utils::{calculations::calculate_optimal_allocation, risk::calculate_max_drawdown},
+};
+use chrono::{DateTime, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Represents the outcome of a backtest.
+///
+/// # Fields
+///
+/// * `values` - The portfolio value at the close of each day, starting at `1.0` on the last
+///   day of the initial lookback window.
+/// * `cumulative_return` - The return of the portfolio over the backtest, as a fraction of the
+///   initial value.
+/// * `max_drawdown` - The largest decline of the portfolio value from a running peak, as a
+///   positive fraction of the peak.
+/// * `turnover` - The sum of the turnover of every trade of the backtest (see
+///   [`calculate_turnover`]), between the asset and cash. A turnover of 1 trades the whole
+///   portfolio once.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BacktestResult {
+    /// The portfolio value at the close of each day.
+    pub values: Vec<(DateTime<Utc>, f64)>,
+    /// The return of the portfolio over the backtest.
+    pub cumulative_return: f64,
+    /// The maximum drawdown of the portfolio value.
+    pub max_drawdown: f64,
+    /// The total turnover of the portfolio.
+    pub turnover: f64,
+}
+
+/// Backtests the allocations of `calculate_optimal_allocation` over a historical price series.
+///
+/// See [`backtest_with`] for how the backtest walks forward through the prices. At each
+/// rebalance, the daily returns of the lookback window are passed to
+/// `calculate_optimal_allocation`, with their cash flows on the current portfolio value and
+/// neutral market indices and fund characteristics, as in the bellwether analysis.
+///
+/// `calculate_optimal_allocation` needs a history of at least the rebalance period, or of
+/// `DEFAULT_MIN_HISTORY` days for longer periods, so a shorter `lookback` leaves the
+/// portfolio in cash.
+///
+/// # Arguments
+///
+/// * `prices` - The dated closing prices of the asset, in chronological order.
+/// * `rebalance_days` - The number of days between two rebalances.
+/// * `lookback` - The number of daily returns used to compute each allocation.
+///
+/// # Returns
+///
+/// The `BacktestResult` of the allocations.
+pub fn backtest(
+    prices: &[(DateTime<Utc>, f64)],
+    rebalance_days: usize,
+    lookback: usize,
+) -> BacktestResult {
+    backtest_with(prices, rebalance_days, lookback, |daily_returns, value, num_days| {
+        calculate_optimal_allocation(
+            daily_returns,
+            &calculate_cash_flows(daily_returns, value),
+            &vec![1.0; daily_returns.len()],
+            &vec![1.0; daily_returns.len()],
+            num_days,
+        )
+    })
+}
+
+/// Backtests the allocations of a custom allocation function over a historical price series.
+///
+/// The backtest starts in cash after the first `lookback` daily returns and walks forward
+/// through the prices. Every `rebalance_days` days, `allocate` is called with the last
+/// `lookback` daily returns, the current portfolio value and the number of days until the
+/// next rebalance. Like `calculate_optimal_allocation`, it returns the fraction of the
+/// portfolio to deploy into the asset on each of these days. Negative allocations are
+/// ignored and the rest are scaled to sum to 1.
+///
+/// The fraction of the portfolio invested in the asset on a day is the sum of the
+/// allocations up to that day, so the portfolio is fully invested on the last day of each
+/// rebalance period. The rest of the portfolio is held in cash, which earns nothing. If
+/// `allocate` fails or allocates nothing, a warning is logged and the portfolio stays in cash
+/// until the next rebalance.
+///
+/// # Arguments
+///
+/// * `prices` - The dated closing prices of the asset, in chronological order.
+/// * `rebalance_days` - The number of days between two rebalances.
+/// * `lookback` - The number of daily returns passed to `allocate`.
+/// * `allocate` - The allocation function, called with the lookback daily returns, the
+///   portfolio value and the number of days to allocate.
+///
+/// # Returns
+///
+/// The `BacktestResult` of the allocations. The result has no values, and a zero return,
+/// drawdown and turnover, if `rebalance_days` is zero, `lookback` is less than two, or there
+/// are no prices after the lookback window.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use nalufx::services::backtest_svc::backtest_with;
+///
+/// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// let prices: Vec<_> =
+///     (0..10).map(|day| (start + Duration::days(day), 100.0 + day as f64)).collect();
+///
+/// // Deploy the whole portfolio on the first day of each period.
+/// let result = backtest_with(&prices, 3, 4, |_, _, num_days| {
+///     Ok((0..num_days).map(|day| if day == 0 { 1.0 } else { 0.0 }).collect())
+/// });
+/// assert_eq!(result.values.len(), 6);
+/// assert!((result.cumulative_return - (109.0 / 104.0 - 1.0)).abs() < 1e-12);
+/// assert_eq!(result.max_drawdown, 0.0);
+/// ```
+pub fn backtest_with<F>(
+    prices: &[(DateTime<Utc>, f64)],
+    rebalance_days: usize,
+    lookback: usize,
+    mut allocate: F,
+) -> BacktestResult
+where
+    F: FnMut(&[f64], f64, usize) -> Result<Vec<f64>, AllocationError>,
+{
+    let mut result = BacktestResult {
+        values: Vec::new(),
+        cumulative_return: 0.0,
+        max_drawdown: 0.0,
+        turnover: 0.0,
+    };
+    if rebalance_days == 0 || lookback < 2 || prices.len() <= lookback + 1 {
+        return result;
+    }
+
+    let closes: Vec<f64> = prices.iter().map(|&(_, close)| close).collect();
+    let daily_returns = calculate_daily_returns(&closes);
+
+    // The return of day `i` is from the close of price `i` to the close of price `i + 1`.
+    let mut value = 1.0;
+    let mut exposure = 0.0;
+    result.values.push((prices[lookback].0, value));
+
+    let mut day = lookback;
+    while day < daily_returns.len() {
+        let num_days = rebalance_days.min(daily_returns.len() - day);
+        let allocations = match allocate(&daily_returns[day - lookback..day], value, num_days) {
+            Ok(allocations) => {
+                let allocations = normalize_allocations(&allocations, num_days);
+                if allocations.iter().all(|&allocation| allocation == 0.0) {
+                    warn!("No allocation on {}, holding cash", prices[day].0);
+                }
+                allocations
+            },
+            Err(e) => {
+                warn!("Allocation failed on {}, holding cash: {}", prices[day].0, e);
+                vec![0.0; num_days]
+            },
+        };
+
+        let mut target = 0.0;
+        for (offset, allocation) in allocations.into_iter().enumerate() {
+            target = (target + allocation).min(1.0);
+            result.turnover +=
+                calculate_turnover(&asset_and_cash(exposure), &asset_and_cash(target));
+            exposure = target;
+
+            let daily_return = daily_returns[day + offset];
+            let growth = 1.0 + exposure * daily_return;
+            value *= growth;
+            // The invested fraction drifts with the price of the asset.
+            exposure = if growth > 0.0 { exposure * (1.0 + daily_return) / growth } else { 0.0 };
+            result.values.push((prices[day + offset + 1].0, value));
+        }
+        day += num_days;
+    }
+
+    let values: Vec<f64> = result.values.iter().map(|&(_, value)| value).collect();
+    result.cumulative_return = value - 1.0;
+    result.max_drawdown = calculate_max_drawdown(&values);
+    result
+}
+
+/// Calculates the turnover of a rebalance from `old_weights` to `new_weights`.
+///
+/// The turnover is half the sum of the absolute changes of the weights, so it is the fraction
+/// of the portfolio that is sold, and bought back in other assets. An asset present in only
+/// one of the two sets has a weight of zero in the other.
+///
+/// # Arguments
+///
+/// * `old_weights` - The weight of each asset before the rebalance.
+/// * `new_weights` - The weight of each asset after the rebalance.
+///
+/// # Returns
+///
+/// The turnover of the rebalance, from 0 if the weights are unchanged to 1 if the whole
+/// portfolio is swapped into other assets.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::backtest_svc::calculate_turnover;
+/// use std::collections::HashMap;
+///
+/// let old_weights = HashMap::from([("AAPL".to_string(), 0.5), ("MSFT".to_string(), 0.5)]);
+/// let new_weights = HashMap::from([("AAPL".to_string(), 0.7), ("MSFT".to_string(), 0.3)]);
+/// assert!((calculate_turnover(&old_weights, &new_weights) - 0.2).abs() < 1e-12);
+/// ```
+pub fn calculate_turnover(
+    old_weights: &HashMap<String, f64>,
+    new_weights: &HashMap<String, f64>,
+) -> f64 {
+    let changes: f64 = old_weights
+        .iter()
+        .map(|(asset, &old)| (new_weights.get(asset).copied().unwrap_or(0.0) - old).abs())
+        .sum();
+    let additions: f64 = new_weights
+        .iter()
+        .filter(|(asset, _)| !old_weights.contains_key(*asset))
+        .map(|(_, &new)| new.abs())
+        .sum();
+    (changes + additions) / 2.0
+}
+
+/// The weights of a portfolio with `exposure` invested in the asset and the rest in cash.
+fn asset_and_cash(exposure: f64) -> HashMap<String, f64> {
+    HashMap::from([("asset".to_string(), exposure), ("cash".to_string(), 1.0 - exposure)])
+}
+
+/// Keeps the first `num_days` allocations, ignores the negative ones and scales the rest to
+/// sum to 1, or returns zeros if nothing is allocated.
+fn normalize_allocations(allocations: &[f64], num_days: usize) -> Vec<f64> {
+    let allocations: Vec<f64> = (0..num_days)
+        .map(|day| allocations.get(day).copied().filter(|a| a.is_finite()).unwrap_or(0.0).max(0.0))
+        .collect();
+    let total: f64 = allocations.iter().sum();
+    if total > 0.0 {
+        allocations.into_iter().map(|allocation| allocation / total).collect()
+    } else {
+        allocations
+    }
+}
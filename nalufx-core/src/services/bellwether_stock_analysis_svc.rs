@@ -1,50 +1,508 @@
 use crate::{
+    config::{bootstrap_resamples, bootstrap_seed, display_precision, output_mode, OutputMode},
     errors::NaluFxError,
+    models::analysis_dm::{AnalysisMetrics, AnalysisResult},
     services::{
-        fetch_data_svc::fetch_data,
-        processing_svc::{calculate_cash_flows, calculate_daily_returns},
+        data_quality_svc::assess_quality,
+        fetch_data_svc::fetch_data_with_dates,
+        monte_carlo_svc::{
+            bootstrap_metric, simulate_portfolio, DEFAULT_SIMULATION_PATHS, DEFAULT_SIMULATION_SEED,
+        },
+        processing_svc::{calculate_cash_flows, calculate_daily_returns, calculate_volatility},
+        sentiment_svc::SentimentProvider,
     },
     utils::{
-        calculations::{
-            analyze_sentiment, calculate_optimal_allocation, train_reinforcement_learning,
-        },
-        date::validate_date,
+        allocation::normalize_non_negative,
+        calculations::{calculate_optimal_allocation, train_reinforcement_learning},
+        date::check_series_continuity,
+        metrics::METRICS,
+        reports::{fmt_vec, ReportSink},
+        risk::calculate_sharpe_ratio,
+        stats::{mean, std_dev},
+        validation::{validate_date, validate_positive_amount},
     },
 };
 use chrono::Datelike;
-use chrono::Utc;
-use nalufx_llms::llms::LLM;
+use chrono::{DateTime, Utc};
+use log::warn;
+use nalufx_llms::{
+    errors::LlmError,
+    llms::{RetryPolicy, LLM},
+};
 use reqwest::Client;
+use serde_json::Value;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// The largest number of consecutive missing trading days tolerated before warning about gaps.
+const MAX_MISSING_TRADING_DAYS: usize = 1;
+
+/// Fetches the historical data for a stock and analyzes it, without printing anything.
+///
+/// This is the programmatic entry point for library consumers. It fetches the closing prices
+/// with [`fetch_data_with_dates`] and passes them to [`analyze_series`].
+///
+/// # Arguments
+///
+/// * `llm` - A reference to a trait object implementing the LLM trait for language model operations.
+/// * `client` - A reference to the reqwest Client for making HTTP requests.
+/// * `api_key` - A string reference to the API key for accessing the LLM service.
+/// * `sentiment` - A reference to the `SentimentProvider` scoring the daily market sentiment.
+/// * `ticker` - A string reference to the ticker symbol of the stock to analyze.
+/// * `initial_investment` - A f64 representing the initial investment amount.
+/// * `start_date` - The start date of the analysis period.
+/// * `end_date` - The end date of the analysis period.
+///
+/// # Returns
+///
+/// * `Result<AnalysisResult, NaluFxError>` - The structured result of the analysis, or an error
+///   if `initial_investment` is not positive (`InvalidData`), or if the data cannot be fetched
+///   or analyzed (see [`analyze_series`]).
+pub async fn run_analysis(
+    llm: &dyn LLM,
+    client: &Client,
+    api_key: &str,
+    sentiment: &dyn SentimentProvider,
+    ticker: &str,
+    initial_investment: f64,
+    start_date: DateTime<Utc>,
+    end_date: DateTime<Utc>,
+) -> Result<AnalysisResult, NaluFxError> {
+    let initial_investment = validate_positive_amount(initial_investment)?;
+    let series = fetch_data_with_dates(ticker, Some(start_date), Some(end_date)).await?;
+    analyze_series(llm, client, api_key, sentiment, ticker, initial_investment, &series).await
+}
+
+/// Analyzes a dated series of closing prices, without printing anything.
+///
+/// The analysis calculates the optimal daily allocation, asks the LLM for a recommendation
+/// based on it, scores the daily sentiment with the given provider and runs the reinforcement
+/// learning model. Sentiment and reinforcement learning failures are logged and leave the
+/// corresponding fields empty.
+///
+/// # Arguments
+///
+/// * `llm` - A reference to a trait object implementing the LLM trait for language model operations.
+/// * `client` - A reference to the reqwest Client for making HTTP requests.
+/// * `api_key` - A string reference to the API key for accessing the LLM service.
+/// * `sentiment` - A reference to the `SentimentProvider` scoring the daily market sentiment.
+/// * `ticker` - A string reference to the ticker symbol of the stock to analyze.
+/// * `initial_investment` - A f64 representing the initial investment amount.
+/// * `series` - A slice of `(timestamp, close)` pairs in chronological order.
+///
+/// # Returns
+///
+/// * `Result<AnalysisResult, NaluFxError>` - The structured result of the analysis, or an error if:
+///   - `initial_investment` is zero, negative or not finite (`InvalidData`).
+///   - `series` is empty (`EmptyInput`).
+///   - The optimal allocation cannot be calculated or is zero (`PortfolioOptimizationError`).
+///   - The LLM request fails (`LlmError`). A rate-limited request is sent one more time
+///     before giving up.
+pub async fn analyze_series(
+    llm: &dyn LLM,
+    client: &Client,
+    api_key: &str,
+    sentiment: &dyn SentimentProvider,
+    ticker: &str,
+    initial_investment: f64,
+    series: &[(DateTime<Utc>, f64)],
+) -> Result<AnalysisResult, NaluFxError> {
+    let initial_investment = validate_positive_amount(initial_investment)?;
+    let (Some(&(start_date, first_close)), Some(&(end_date, last_close))) =
+        (series.first(), series.last())
+    else {
+        return Err(NaluFxError::EmptyInput);
+    };
+
+    let continuity_issues = match check_series_continuity(series, MAX_MISSING_TRADING_DAYS) {
+        Err(NaluFxError::DiscontinuousSeries(issues)) => issues,
+        _ => Vec::new(),
+    };
+
+    let closes: Vec<f64> = series.iter().map(|(_, close)| *close).collect();
+
+    let daily_returns = calculate_daily_returns(&closes);
+    let cash_flows = calculate_cash_flows(&daily_returns, initial_investment);
+
+    let min_length = daily_returns.len().min(cash_flows.len());
+    let daily_returns = &daily_returns[..min_length];
+    let cash_flows = &cash_flows[..min_length];
+
+    let optimal_allocation = calculate_optimal_allocation(
+        daily_returns,
+        cash_flows,
+        &vec![1.0; min_length],
+        &vec![1.0; min_length],
+        min_length,
+    )
+    .map_err(|e| NaluFxError::PortfolioOptimizationError(e.to_string()))?;
+
+    let allocations = normalize_non_negative(optimal_allocation).map_err(|e| {
+        NaluFxError::PortfolioOptimizationError(format!("{} for ticker {}", e, ticker))
+    })?;
+
+    let current_year = Utc::now().year();
+    let prompt = format!(
+        "Analyze the following stock data for {}:\n\n\
+        - Optimal Allocation: {:?}\n\n\
+        Provide a detailed investment recommendation based on this data.\n\
+        Additionally, provide the Current Market Context for {} in {}.\n\
+        This context is essential for understanding the potential drivers behind the stock's performance and the recommendations provided.",
+        ticker,
+        fmt_vec(&allocations, display_precision()),
+        ticker,
+        current_year
+    );
+
+    let response = request_recommendation(llm, client, api_key, ticker, &prompt).await?;
+    let llm_response =
+        response["choices"][0]["message"]["content"].as_str().unwrap_or("").to_string();
+
+    // Extract key findings from the message
+    let key_findings =
+        llm_response.lines().filter(|line| line.contains(ticker)).map(str::to_string).collect();
+
+    let sentiment = sentiment.daily_sentiment(ticker, min_length).await.unwrap_or_else(|e| {
+        warn!("Error in sentiment analysis for ticker {}: {}", ticker, e);
+        Vec::new()
+    });
+
+    let actions = train_reinforcement_learning(min_length).unwrap_or_else(|e| {
+        warn!("Error in reinforcement learning for ticker {}: {}", ticker, e);
+        Vec::new()
+    });
+
+    // Simulate the investment over a horizon as long as the analyzed history.
+    let simulation = simulate_portfolio(
+        initial_investment,
+        mean(daily_returns).unwrap_or(0.0),
+        std_dev(daily_returns, 1).unwrap_or(0.0),
+        daily_returns.len(),
+        DEFAULT_SIMULATION_PATHS,
+        DEFAULT_SIMULATION_SEED,
+    );
+
+    let (sharpe_ratio, sharpe_ratio_lower, sharpe_ratio_upper) = bootstrap_metric(
+        daily_returns,
+        calculate_sharpe_ratio,
+        bootstrap_resamples(),
+        bootstrap_seed(),
+    );
+
+    let metrics = AnalysisMetrics {
+        total_return: last_close / first_close - 1.0,
+        annualized_volatility: calculate_volatility(daily_returns, true).unwrap_or(0.0),
+        min_allocation: allocations.iter().copied().fold(f64::INFINITY, f64::min),
+        max_allocation: allocations.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        simulated_value_5th: simulation.percentile_5,
+        simulated_value_95th: simulation.percentile_95,
+        sharpe_ratio,
+        sharpe_ratio_lower,
+        sharpe_ratio_upper,
+    };
+
+    Ok(AnalysisResult {
+        ticker: ticker.to_string(),
+        start_date,
+        end_date,
+        initial_investment,
+        allocations,
+        sentiment,
+        actions,
+        metrics,
+        key_findings,
+        llm_response,
+        continuity_issues,
+    })
+}
+
+/// Sends the recommendation prompt to the LLM, retrying once if the request is rate limited.
+///
+/// The providers already retry rate-limited requests, so a `RateLimited` error means their
+/// retries are exhausted. The request is then sent one last time, after the delay requested by
+/// the provider or the first backoff delay of the `RetryPolicy`. Any other error, such as
+/// `AuthFailed`, is returned at once, as sending the request again would not help.
+///
+/// # Arguments
+///
+/// * `llm` - A reference to a trait object implementing the LLM trait for language model operations.
+/// * `client` - A reference to the reqwest Client for making HTTP requests.
+/// * `api_key` - A string reference to the API key for accessing the LLM service.
+/// * `ticker` - A string reference to the ticker symbol of the analyzed stock.
+/// * `prompt` - The prompt to send.
+///
+/// # Returns
+///
+/// * `Result<Value, LlmError>` - The JSON response of the LLM, or the error of the last attempt.
+async fn request_recommendation(
+    llm: &dyn LLM,
+    client: &Client,
+    api_key: &str,
+    ticker: &str,
+    prompt: &str,
+) -> Result<Value, LlmError> {
+    let started = Instant::now();
+    let response = llm.send_request(client, api_key, prompt, 1500).await;
+    METRICS.record_llm_request(started.elapsed(), response.is_ok());
+
+    let Err(LlmError::RateLimited { retry_after }) = response else {
+        return response;
+    };
+    let delay = RetryPolicy::from_env().delay(0, retry_after);
+    warn!("LLM request for ticker {} was rate limited, retrying in {:?}", ticker, delay);
+    tokio::time::sleep(delay).await;
+
+    let started = Instant::now();
+    let response = llm.send_request(client, api_key, prompt, 1500).await;
+    METRICS.record_llm_request(started.elapsed(), response.is_ok());
+    response
+}
+
+/// Serializes the result of an analysis to pretty-printed JSON.
+///
+/// # Arguments
+///
+/// * `result` - A reference to the `AnalysisResult` to serialize.
+///
+/// # Returns
+///
+/// * `Result<String, NaluFxError>` - The JSON document, or a `JsonError` if serialization fails.
+pub fn format_analysis_json(result: &AnalysisResult) -> Result<String, NaluFxError> {
+    Ok(serde_json::to_string_pretty(result)?)
+}
+
+/// Writes the result of an analysis to stdout in the given output mode.
+///
+/// In `OutputMode::Human`, the bellwether stock report is printed (see
+/// [`print_analysis_report`]). In `OutputMode::Json`, only the JSON document is printed (see
+/// [`format_analysis_json`]), so that the output can be piped into tools such as `jq`.
+///
+/// # Arguments
+///
+/// * `result` - A reference to the `AnalysisResult` to report.
+/// * `mode` - The `OutputMode` to report in.
+///
+/// # Returns
+///
+/// * `Result<(), NaluFxError>` - Returns Ok(()) if the report is written, or a `JsonError` if
+///   serialization fails.
+pub fn report_analysis(result: &AnalysisResult, mode: OutputMode) -> Result<(), NaluFxError> {
+    match mode {
+        OutputMode::Human => print_analysis_report(result),
+        OutputMode::Json => println!("{}", format_analysis_json(result)?),
+    }
+    Ok(())
+}
+
+/// Writes the result of an analysis to a timestamped report file in the given output mode.
+///
+/// In `OutputMode::Human`, the Markdown report is written to `bellwether_{ticker}.md` (see
+/// [`write_analysis_report`]). In `OutputMode::Json`, the JSON document is written to
+/// `bellwether_{ticker}.json` (see [`format_analysis_json`]).
+///
+/// # Arguments
+///
+/// * `result` - A reference to the `AnalysisResult` to report.
+/// * `mode` - The `OutputMode` to report in.
+/// * `reports` - The `ReportSink` creating the report file.
+///
+/// # Returns
+///
+/// * `Result<PathBuf, NaluFxError>` - The path of the report file, or an `InputError` if it
+///   cannot be created or written, or a `JsonError` if serialization fails.
+pub fn save_analysis_report(
+    result: &AnalysisResult,
+    mode: OutputMode,
+    reports: &ReportSink,
+) -> Result<PathBuf, NaluFxError> {
+    let name = format!("bellwether_{}", result.ticker);
+    match mode {
+        OutputMode::Human => {
+            let (path, mut file) = reports.create(&name, "md")?;
+            write_analysis_report(&mut file, result)?;
+            Ok(path)
+        },
+        OutputMode::Json => reports.write(&name, "json", &format_analysis_json(result)?),
+    }
+}
+
+/// Prints the bellwether stock report for the result of an analysis.
+///
+/// Continuity issues in the price series are printed to stderr as warnings, and the report
+/// itself to stdout (see [`write_analysis_report`]).
+///
+/// # Arguments
+///
+/// * `result` - A reference to the `AnalysisResult` to report.
+pub fn print_analysis_report(result: &AnalysisResult) {
+    // Warn when the analysis period has significant gaps or duplicate dates
+    if !result.continuity_issues.is_empty() {
+        eprintln!(
+            "Warning: the price series for {} has {} continuity issue(s) in the specified date range:",
+            result.ticker,
+            result.continuity_issues.len()
+        );
+        for issue in &result.continuity_issues {
+            eprintln!("  - {:?}", issue);
+        }
+    }
+
+    if let Err(e) = write_analysis_report(&mut io::stdout(), result) {
+        eprintln!("Error printing the report: {}", e);
+    }
+}
+
+/// Writes the bellwether stock report for the result of an analysis, in Markdown.
+///
+/// # Arguments
+///
+/// * `out` - The writer to write the report to, e.g. stdout or a report file.
+/// * `result` - A reference to the `AnalysisResult` to report.
+///
+/// # Returns
+///
+/// * `io::Result<()>` - Returns Ok(()) if the report is written, or the error of the writer.
+pub fn write_analysis_report(out: &mut dyn Write, result: &AnalysisResult) -> io::Result<()> {
+    // Generate Report
+    writeln!(out, "\n--- Bellwether Stock Report: {} ---\n", result.ticker)?;
+    writeln!(
+        out,
+        "- **Date Range:** {} - {}",
+        result.start_date.format("%Y-%m-%d"),
+        result.end_date.format("%Y-%m-%d")
+    )?;
+    writeln!(out, "- **Initial Investment:** ${:.2}\n", result.initial_investment)?;
+
+    // Explanation of Methodology
+    writeln!(out, "--- Methodology ---\n")?;
+    writeln!(out, "This report combines several analytical techniques to provide a comprehensive view of {}'s potential performance:", result.ticker)?;
+    writeln!(out, "\n- **Optimal Allocation:** Uses historical price data and statistical modelling to suggest a daily allocation of your investment amount to {}. This helps in balancing risk and maximizing returns by identifying optimal investment proportions.", result.ticker)?;
+    writeln!(out, "- **Sentiment Analysis:** Gauges market sentiment towards {} by analysing news articles, social media, and other relevant sources. This helps in understanding the market's perception and potential impact on stock performance.", result.ticker)?;
+    writeln!(out, "- **Reinforcement Learning (RL):** A machine learning model trained on historical data to suggest buy/sell actions based on market conditions. This helps in identifying strategic actions to maximize returns based on learned patterns.\n")?;
+
+    // Summary of Key Findings
+    writeln!(out, "\n--- Key findings ---\n")?;
+    for finding in &result.key_findings {
+        writeln!(out, "{}", finding)?;
+    }
+
+    // Current Market Context
+    let current_year = Utc::now().year();
+    writeln!(out, "\n--- Current Market Context ---\n")?;
+    writeln!(
+        out,
+        "As of the analysis period {}, {} has been experiencing the following market conditions:",
+        current_year, result.ticker
+    )?;
+    writeln!(out, "\n- **Technological Innovations:** {} is known for its continuous focus on technological innovations. The market is closely watching for any new product launches or updates that could impact {}'s stock performance.", result.ticker, result.ticker)?;
+    writeln!(out, "\n- **Competition:** {} faces stiff competition from other tech giants. Any advancements or setbacks from competitors could impact {}'s market position and stock performance.", result.ticker, result.ticker)?;
+    writeln!(out, "\n- **Macroeconomic Factors:** Economic indicators, inflation rates, interest rates, and government policies can all affect the stock market in general and {} specifically. Monitoring these macroeconomic factors is essential for predicting {}'s stock performance.", result.ticker, result.ticker)?;
+    writeln!(out, "\n- **Regulatory Environment:** Changes in regulations related to data privacy, antitrust laws, or other regulatory issues can have a significant impact on {}'s business operations and stock performance.", result.ticker)?;
+    writeln!(out, "\n- **Global Events:** Geopolitical events, natural disasters, pandemics, and other global factors can also influence {}'s stock performance. Keeping an eye on such events is essential for understanding the broader market context.\n", result.ticker)?;
+
+    writeln!(out, "\n--- Key Findings ---\n")?;
+    writeln!(out, "- **1. Optimal Allocation:** The model recommends a diversified approach, with daily allocations within a diversified portfolio containing {} ranging from {:.2}% to {:.2}% of your initial investment. This aims to mitigate risk and capture potential gains across different market conditions.\n", result.ticker, result.metrics.min_allocation * 100.0, result.metrics.max_allocation * 100.0)?;
+    if !result.sentiment.is_empty() {
+        writeln!(out, "- **2. Sentiment Analysis:** Market sentiment towards {} fluctuates within the specified period, ranging from very positive ({:.2} on Day {}) to somewhat negative ({:.2} on Day {}). This suggests a dynamic market environment.\n", result.ticker, result.sentiment.iter().cloned().fold(0./0., f64::max), result.sentiment.iter().position(|&s| s == result.sentiment.iter().cloned().fold(0./0., f64::max)).unwrap() + 1, result.sentiment.iter().cloned().fold(0./0., f64::min), result.sentiment.iter().position(|&s| s == result.sentiment.iter().cloned().fold(0./0., f64::min)).unwrap() + 1)?;
+    }
+    if !result.actions.is_empty() {
+        writeln!(out, "- **3. Reinforcement Learning:** The RL model suggests a mix of buy and hold actions, with higher buying recommendations on certain days (e.g., {:.2} on Day {}) and lower on others (e.g., {:.2} on Day {}). This highlights potential opportunities to adjust your position based on the model's predictions.\n", result.actions.iter().cloned().fold(0./0., f64::max), result.actions.iter().position(|&a| a == result.actions.iter().cloned().fold(0./0., f64::max)).unwrap() + 1, result.actions.iter().cloned().fold(0./0., f64::min), result.actions.iter().position(|&a| a == result.actions.iter().cloned().fold(0./0., f64::min)).unwrap() + 1)?;
+    }
+
+    // Risk Assessment
+    writeln!(out, "\n--- Risk Assessment ---\n")?;
+    writeln!(out, "Investing in {} carries several risks, including market volatility, economic downturns, and company-specific risks such as changes in management or financial performance. It is essential to consider these risks and diversify your investments to mitigate potential losses.", result.ticker)?;
+    writeln!(
+        out,
+        "\n- **Simulated Outcomes:** 90% of simulated outcomes for your ${:.2} over {} trading days fall between ${:.2} and ${:.2}, based on the mean and volatility of the historical daily returns.",
+        result.initial_investment,
+        result.allocations.len(),
+        result.metrics.simulated_value_5th,
+        result.metrics.simulated_value_95th
+    )?;
+    if result.metrics.sharpe_ratio.is_finite() {
+        writeln!(
+            out,
+            "\n- **Risk-Adjusted Return:** Sharpe {:.2} (95% CI {:.2}–{:.2}), annualized from the daily returns with a zero risk-free rate. The interval is estimated by resampling the daily returns, and shows how much the ratio depends on the particular days observed.",
+            result.metrics.sharpe_ratio,
+            result.metrics.sharpe_ratio_lower,
+            result.metrics.sharpe_ratio_upper
+        )?;
+    }
+
+    // Investment Recommendations
+    writeln!(out, "\n--- Investment Recommendations ---\n")?;
+    writeln!(out, "Based on this analysis, here's a possible investment strategy for the specified period, starting with your initial ${:.2}:", result.initial_investment)?;
+    writeln!(
+        out,
+        "\n- 1. Follow the daily optimal allocation percentages for {} as a baseline strategy.",
+        result.ticker
+    )?;
+    writeln!(out, "\n- 2. Consider increasing your {} allocation on days when sentiment is positive and the RL model recommends buying.", result.ticker)?;
+    writeln!(out, "\n- 3. Be cautious about increasing your position on days with negative sentiment or low RL buying recommendations.")?;
+    writeln!(
+        out,
+        "\n- 4. Monitor {}'s performance and broader market trends throughout this period.",
+        result.ticker
+    )?;
+    writeln!(out, "\n- 5. Consult with a financial advisor to tailor this strategy to your risk tolerance and investment goals.\n")?;
+
+    // Disclaimer
+    writeln!(out, "\n--- Disclaimer ---\n")?;
+    writeln!(out, "This report is intended for informational purposes only and should not be considered financial advice. Investing in the stock market carries risks, and past performance is not indicative of future results. Always conduct thorough research and consult with a financial professional before making any investment decisions.")?;
+    Ok(())
+}
 
 /// Generates an analysis report based on historical stock data, optimal allocation, and LLM analysis.
 ///
+/// This function validates the dates, runs the analysis (see [`analyze_series`]) and prints the
+/// report in the output mode selected by the environment (see [`output_mode`] and
+/// [`report_analysis`]). The report is also written to a timestamped file (see
+/// [`save_analysis_report`]), whose path is printed to stderr. In `OutputMode::Human`, the
+/// report is preceded by the data quality of the fetched series (see [`assess_quality`]).
+///
 /// # Arguments
 ///
 /// * `llm` - A boxed trait object implementing the LLM trait for language model operations.
 /// * `client` - A reference to the reqwest Client for making HTTP requests.
 /// * `api_key` - A string reference to the API key for accessing the LLM service.
+/// * `sentiment` - A reference to the `SentimentProvider` scoring the daily market sentiment.
 /// * `ticker` - A string reference to the ticker symbol of the stock to analyze.
 /// * `initial_investment` - A f64 representing the initial investment amount.
 /// * `start_date` - A string reference to the start date of the analysis period in "YYYY-MM-DD" format.
 /// * `end_date` - A string reference to the end date of the analysis period in "YYYY-MM-DD" format.
+/// * `reports` - The `ReportSink` writing the report file.
 ///
 /// # Returns
 ///
-/// * `Result<(), NaluFxError>` - Returns Ok(()) if the analysis is successful, otherwise returns an error.
+/// * `Result<(), NaluFxError>` - Returns Ok(()) if the analysis is successful, otherwise returns an
+///   error, e.g. `InvalidData` if `initial_investment` is zero, negative or not finite, or
+///   `InputError` if the report file cannot be written.
 pub async fn generate_analysis(
     llm: Box<dyn LLM>,
     client: &Client,
     api_key: &str,
+    sentiment: &dyn SentimentProvider,
     ticker: &str,
     initial_investment: f64,
     start_date: &str,
     end_date: &str,
+    reports: &ReportSink,
 ) -> Result<(), NaluFxError> {
+    if let Err(e) = validate_positive_amount(initial_investment) {
+        eprintln!(
+            "Error: The initial investment must be a positive amount, got {}",
+            initial_investment
+        );
+        return Err(e);
+    }
+
     let start_date = match validate_date(start_date) {
         Ok(date) => date,
         Err(e) => {
             eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
+            return Err(e);
         },
     };
 
@@ -52,148 +510,12 @@ pub async fn generate_analysis(
         Ok(date) => date,
         Err(e) => {
             eprintln!("Error: {}", e);
-            return Err(NaluFxError::InvalidOption);
+            return Err(e);
         },
     };
 
-    match fetch_data(ticker, Some(start_date), Some(end_date)).await {
-        Ok(closes) => {
-            if closes.is_empty() {
-                eprintln!(
-                    "No closing prices found for ticker {} in the specified date range",
-                    ticker
-                );
-                return Ok(());
-            }
-
-            let daily_returns = calculate_daily_returns(&closes);
-            let cash_flows = calculate_cash_flows(&daily_returns, initial_investment);
-
-            let min_length = daily_returns.len().min(cash_flows.len());
-            let daily_returns = &daily_returns[..min_length];
-            let cash_flows = &cash_flows[..min_length];
-
-            let optimal_allocation_result = calculate_optimal_allocation(
-                daily_returns,
-                cash_flows,
-                &vec![1.0; min_length],
-                &vec![1.0; min_length],
-                min_length,
-            );
-
-            match optimal_allocation_result {
-                Ok(mut optimal_allocation) => {
-                    optimal_allocation = optimal_allocation
-                        .into_iter()
-                        .map(|alloc| if alloc < 0.0 { 0.0 } else { alloc })
-                        .collect();
-                    let total_allocation: f64 = optimal_allocation.iter().sum();
-                    if total_allocation == 0.0 {
-                        eprintln!("Error: Total allocation is zero for ticker {}", ticker);
-                        return Ok(());
-                    }
-                    optimal_allocation = optimal_allocation
-                        .into_iter()
-                        .map(|alloc| alloc / total_allocation)
-                        .collect();
-                    let current_year = Utc::now().year();
-                    let prompt = format!(
-                        "Analyze the following stock data for {}:\n\n\
-                        - Optimal Allocation: {:?}\n\n\
-                        Provide a detailed investment recommendation based on this data.\n\
-                        Additionally, provide the Current Market Context for {} in {}.\n\
-                        This context is essential for understanding the potential drivers behind the stock's performance and the recommendations provided.",
-                        ticker, optimal_allocation, ticker, current_year
-                    );
-
-                    let response = llm.send_request(client, api_key, &prompt, 1500).await?;
-                    let message =
-                        response["choices"][0]["message"]["content"].as_str().unwrap_or("");
-
-                    // Extract key findings from the message
-                    let key_findings = "\n--- Key findings ---\n\n";
-                    let mut summary = key_findings.to_string();
-                    for line in message.lines() {
-                        if line.contains(ticker) {
-                            summary.push_str(line);
-                            summary.push('\n');
-                        }
-                    }
-
-                    let sentiment_scores = match analyze_sentiment(min_length) {
-                        Ok(scores) => scores,
-                        Err(e) => {
-                            eprintln!("Error in sentiment analysis for ticker {}: {}", ticker, e);
-                            Vec::new()
-                        },
-                    };
-
-                    let optimal_actions = match train_reinforcement_learning(min_length) {
-                        Ok(actions) => actions,
-                        Err(e) => {
-                            eprintln!(
-                                "Error in reinforcement learning for ticker {}: {}",
-                                ticker, e
-                            );
-                            Vec::new()
-                        },
-                    };
-
-                    // Generate Report
-                    println!("\n--- Bellwether Stock Report: {} ---\n", ticker);
-                    println!("- **Date Range:** {} - {}", start_date, end_date);
-                    println!("- **Initial Investment:** ${:.2}\n", initial_investment);
-
-                    // Explanation of Methodology
-                    println!("--- Methodology ---\n");
-                    println!("This report combines several analytical techniques to provide a comprehensive view of {}'s potential performance:", ticker);
-                    println!("\n- **Optimal Allocation:** Uses historical price data and statistical modelling to suggest a daily allocation of your investment amount to {}. This helps in balancing risk and maximizing returns by identifying optimal investment proportions.", ticker);
-                    println!("- **Sentiment Analysis:** Gauges market sentiment towards {} by analysing news articles, social media, and other relevant sources. This helps in understanding the market's perception and potential impact on stock performance.", ticker);
-                    println!("- **Reinforcement Learning (RL):** A machine learning model trained on historical data to suggest buy/sell actions based on market conditions. This helps in identifying strategic actions to maximize returns based on learned patterns.\n");
-
-                    // Summary of Key Findings
-                    println!("{}", summary);
-
-                    // Current Market Context
-                    let current_year = Utc::now().year();
-                    println!("\n--- Current Market Context ---\n");
-                    println!("As of the analysis period {}, {} has been experiencing the following market conditions:", current_year, ticker);
-                    println!("\n- **Technological Innovations:** {} is known for its continuous focus on technological innovations. The market is closely watching for any new product launches or updates that could impact {}'s stock performance.", ticker, ticker);
-                    println!("\n- **Competition:** {} faces stiff competition from other tech giants. Any advancements or setbacks from competitors could impact {}'s market position and stock performance.", ticker, ticker);
-                    println!("\n- **Macroeconomic Factors:** Economic indicators, inflation rates, interest rates, and government policies can all affect the stock market in general and {} specifically. Monitoring these macroeconomic factors is essential for predicting {}'s stock performance.", ticker, ticker);
-                    println!("\n- **Regulatory Environment:** Changes in regulations related to data privacy, antitrust laws, or other regulatory issues can have a significant impact on {}'s business operations and stock performance.", ticker);
-                    println!("\n- **Global Events:** Geopolitical events, natural disasters, pandemics, and other global factors can also influence {}'s stock performance. Keeping an eye on such events is essential for understanding the broader market context.\n", ticker);
-
-                    println!("\n--- Key Findings ---\n");
-                    println!("- **1. Optimal Allocation:** The model recommends a diversified approach, with daily allocations within a diversified portfolio containing {} ranging from {:.2}% to {:.2}% of your initial investment. This aims to mitigate risk and capture potential gains across different market conditions.\n", ticker, optimal_allocation.iter().cloned().fold(0./0., f64::min) * 100.0, optimal_allocation.iter().cloned().fold(0./0., f64::max) * 100.0);
-                    println!("- **2. Sentiment Analysis:** Market sentiment towards {} fluctuates within the specified period, ranging from very positive ({:.2} on Day {}) to somewhat negative ({:.2} on Day {}). This suggests a dynamic market environment.\n", ticker, sentiment_scores.iter().cloned().fold(0./0., f64::max), sentiment_scores.iter().position(|&s| s == sentiment_scores.iter().cloned().fold(0./0., f64::max)).unwrap() + 1, sentiment_scores.iter().cloned().fold(0./0., f64::min), sentiment_scores.iter().position(|&s| s == sentiment_scores.iter().cloned().fold(0./0., f64::min)).unwrap() + 1);
-                    println!("- **3. Reinforcement Learning:** The RL model suggests a mix of buy and hold actions, with higher buying recommendations on certain days (e.g., {:.2} on Day {}) and lower on others (e.g., {:.2} on Day {}). This highlights potential opportunities to adjust your position based on the model's predictions.\n", optimal_actions.iter().cloned().fold(0./0., f64::max), optimal_actions.iter().position(|&a| a == optimal_actions.iter().cloned().fold(0./0., f64::max)).unwrap() + 1, optimal_actions.iter().cloned().fold(0./0., f64::min), optimal_actions.iter().position(|&a| a == optimal_actions.iter().cloned().fold(0./0., f64::min)).unwrap() + 1);
-
-                    // Risk Assessment
-                    println!("\n--- Risk Assessment ---\n");
-                    println!("Investing in {} carries several risks, including market volatility, economic downturns, and company-specific risks such as changes in management or financial performance. It is essential to consider these risks and diversify your investments to mitigate potential losses.", ticker);
-
-                    // Investment Recommendations
-                    println!("\n--- Investment Recommendations ---\n");
-                    println!("Based on this analysis, here's a possible investment strategy for the specified period, starting with your initial ${:.2}:", initial_investment);
-                    println!("\n- 1. Follow the daily optimal allocation percentages for {} as a baseline strategy.", ticker);
-                    println!("\n- 2. Consider increasing your {} allocation on days when sentiment is positive and the RL model recommends buying.", ticker);
-                    println!("\n- 3. Be cautious about increasing your position on days with negative sentiment or low RL buying recommendations.");
-                    println!("\n- 4. Monitor {}'s performance and broader market trends throughout this period.", ticker);
-                    println!("\n- 5. Consult with a financial advisor to tailor this strategy to your risk tolerance and investment goals.\n");
-
-                    // Disclaimer
-                    println!("\n--- Disclaimer ---\n");
-                    println!("This report is intended for informational purposes only and should not be considered financial advice. Investing in the stock market carries risks, and past performance is not indicative of future results. Always conduct thorough research and consult with a financial professional before making any investment decisions.");
-
-                    Ok(())
-                },
-                Err(e) => {
-                    eprintln!("Error calculating optimal allocation for ticker {}: {}", ticker, e);
-                    return Err(NaluFxError::PortfolioOptimizationError(e.to_string()));
-                },
-            }
-        },
+    let series = match fetch_data_with_dates(ticker, Some(start_date), Some(end_date)).await {
+        Ok(series) => series,
         Err(e) => {
             eprintln!(
                 "Historical data not available for ticker {} in the specified date range: {}",
@@ -202,5 +524,42 @@ pub async fn generate_analysis(
             println!("Please try a different date range or choose another ticker symbol.");
             return Err(NaluFxError::InvalidOption);
         },
+    };
+
+    let mode = output_mode();
+    if mode == OutputMode::Human {
+        println!("{}", assess_quality(&series));
+    }
+
+    match analyze_series(
+        llm.as_ref(),
+        client,
+        api_key,
+        sentiment,
+        ticker,
+        initial_investment,
+        &series,
+    )
+    .await
+    {
+        Ok(result) => {
+            report_analysis(&result, mode)?;
+            let path = save_analysis_report(&result, mode, reports)?;
+            eprintln!("Report written to {}", path.display());
+            Ok(())
+        },
+        Err(NaluFxError::EmptyInput) => {
+            eprintln!("No closing prices found for ticker {} in the specified date range", ticker);
+            Ok(())
+        },
+        Err(e @ NaluFxError::PortfolioOptimizationError(_)) => {
+            eprintln!("Error calculating optimal allocation for ticker {}: {}", ticker, e);
+            Err(e)
+        },
+        Err(e @ NaluFxError::LlmError(_)) => {
+            eprintln!("Error requesting the LLM analysis for ticker {}: {}", ticker, e);
+            Err(e)
+        },
+        Err(e) => Err(e),
     }
 }
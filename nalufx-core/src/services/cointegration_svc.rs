@@ -0,0 +1,160 @@
+use crate::errors::NaluFxError;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+/// The smallest number of observations accepted by the Engle-Granger test.
+pub const MIN_COINTEGRATION_OBSERVATIONS: usize = 20;
+
+/// Represents the outcome of an Engle-Granger cointegration test.
+///
+/// # Fields
+///
+/// * `hedge_ratio` - The slope of the regression of the first series on the second, i.e. the
+///   number of units of the second series to sell for each unit of the first.
+/// * `intercept` - The intercept of the regression.
+/// * `adf_statistic` - The augmented Dickey-Fuller t-statistic of the regression residuals.
+/// * `critical_value` - The 5% critical value of the statistic for the number of observations.
+/// * `cointegrated` - Whether the series are cointegrated at the 5% level, i.e. whether
+///   `adf_statistic` is below `critical_value`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CointResult {
+    /// The slope of the regression of the first series on the second.
+    pub hedge_ratio: f64,
+    /// The intercept of the regression.
+    pub intercept: f64,
+    /// The augmented Dickey-Fuller t-statistic of the residuals.
+    pub adf_statistic: f64,
+    /// The 5% critical value of the statistic.
+    pub critical_value: f64,
+    /// Whether the series are cointegrated at the 5% level.
+    pub cointegrated: bool,
+}
+
+/// Runs the Engle-Granger two-step cointegration test on two price series.
+///
+/// The first series is regressed on the second by ordinary least squares, and an augmented
+/// Dickey-Fuller test is run on the residuals of the regression, the spread of the pair. The
+/// ADF regression has no constant, as the residuals have a zero mean, and
+/// `floor((n - 1)^(1/3))` lagged differences. If the spread is stationary, the series are
+/// cointegrated and the spread reverts to its mean, which is what pairs trading relies on.
+///
+/// The statistic is compared with the 5% critical value of MacKinnon (2010) for two variables
+/// with a constant, which is lower than the critical value of a plain ADF test because the
+/// residuals are fitted.
+///
+/// If the series have different lengths, the longer one is truncated to its most recent
+/// observations, so that both end on the same day.
+///
+/// # Arguments
+///
+/// * `series_a` - The prices of the first asset.
+/// * `series_b` - The prices of the second asset.
+///
+/// # Returns
+///
+/// * `Result<CointResult, NaluFxError>` - The result of the test, or an error if:
+///   - Either series is empty (`EmptyInput`).
+///   - The series have fewer than `MIN_COINTEGRATION_OBSERVATIONS` observations in common,
+///     contain non-finite values, or the second series is constant (`InvalidData`).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::cointegration_svc::engle_granger_test;
+///
+/// // The first series is twice the second, plus a mean-reverting spread.
+/// let series_b: Vec<f64> =
+///     (0..200).map(|i| 100.0 + (i as f64 * 0.1).sin() * 5.0 + i as f64 * 0.2).collect();
+/// let series_a: Vec<f64> = series_b
+///     .iter()
+///     .enumerate()
+///     .map(|(i, b)| 2.0 * b + ((i * 37) % 11) as f64 / 5.0 - 1.0)
+///     .collect();
+///
+/// let result = engle_granger_test(&series_a, &series_b).unwrap();
+/// assert!((result.hedge_ratio - 2.0).abs() < 0.01);
+/// assert!(result.cointegrated);
+/// ```
+pub fn engle_granger_test(series_a: &[f64], series_b: &[f64]) -> Result<CointResult, NaluFxError> {
+    if series_a.is_empty() || series_b.is_empty() {
+        return Err(NaluFxError::EmptyInput);
+    }
+    let len = series_a.len().min(series_b.len());
+    let series_a = &series_a[series_a.len() - len..];
+    let series_b = &series_b[series_b.len() - len..];
+    if len < MIN_COINTEGRATION_OBSERVATIONS
+        || series_a.iter().chain(series_b).any(|value| !value.is_finite())
+    {
+        return Err(NaluFxError::InvalidData);
+    }
+
+    // Step 1: regress the first series on the second.
+    let x = DMatrix::from_fn(len, 2, |i, j| if j == 0 { 1.0 } else { series_b[i] });
+    let y = DVector::from_column_slice(series_a);
+    let (coefficients, _) = ols(&x, &y).ok_or(NaluFxError::InvalidData)?;
+    let (intercept, hedge_ratio) = (coefficients[0], coefficients[1]);
+    let residuals: Vec<f64> =
+        series_a.iter().zip(series_b).map(|(a, b)| a - intercept - hedge_ratio * b).collect();
+
+    // Step 2: test the residuals for a unit root.
+    let adf_statistic = adf_statistic(&residuals).ok_or(NaluFxError::InvalidData)?;
+    let critical_value = engle_granger_critical_value(len);
+
+    Ok(CointResult {
+        hedge_ratio,
+        intercept,
+        adf_statistic,
+        critical_value,
+        cointegrated: adf_statistic < critical_value,
+    })
+}
+
+/// Returns the 5% critical value of the Engle-Granger test with two variables and a constant
+/// for `num_observations` observations, from the response surface of MacKinnon (2010).
+fn engle_granger_critical_value(num_observations: usize) -> f64 {
+    let n = num_observations as f64;
+    -3.33613 - 6.1101 / n - 6.823 / (n * n)
+}
+
+/// Calculates the augmented Dickey-Fuller t-statistic of a series, without a constant.
+///
+/// The regression is `Δe_t = γ e_{t-1} + Σ δ_i Δe_{t-i} + ε_t` with `floor((n - 1)^(1/3))`
+/// lagged differences, and the statistic is the t-statistic of `γ`. Returns `None` if the
+/// regression cannot be solved.
+fn adf_statistic(series: &[f64]) -> Option<f64> {
+    let diffs: Vec<f64> = series.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let lags = ((diffs.len() as f64).cbrt().floor() as usize).min(diffs.len().saturating_sub(2));
+    let num_rows = diffs.len().checked_sub(lags)?;
+
+    // Row `t` explains `diffs[t + lags]`, the change from `series[t + lags]`.
+    let x = DMatrix::from_fn(num_rows, lags + 1, |t, j| {
+        if j == 0 {
+            series[t + lags]
+        } else {
+            diffs[t + lags - j]
+        }
+    });
+    let y = DVector::from_iterator(num_rows, diffs[lags..].iter().copied());
+    let (coefficients, standard_errors) = ols(&x, &y)?;
+
+    let statistic = coefficients[0] / standard_errors[0];
+    statistic.is_finite().then_some(statistic)
+}
+
+/// Fits an ordinary least squares regression of `y` on the columns of `x`.
+///
+/// Returns the coefficients and their standard errors, or `None` if there are not more rows
+/// than columns or `x'x` is singular.
+fn ols(x: &DMatrix<f64>, y: &DVector<f64>) -> Option<(DVector<f64>, DVector<f64>)> {
+    let (num_rows, num_cols) = x.shape();
+    if num_rows <= num_cols {
+        return None;
+    }
+
+    let xtx_inv = (x.transpose() * x).try_inverse()?;
+    let coefficients = &xtx_inv * x.transpose() * y;
+    let residuals = y - x * &coefficients;
+    let variance = residuals.norm_squared() / (num_rows - num_cols) as f64;
+    let standard_errors = xtx_inv.diagonal().map(|v| (variance * v).sqrt());
+    Some((coefficients, standard_errors))
+}
@@ -0,0 +1,56 @@
+/// Calculates the Herfindahl-Hirschman index (HHI) of an allocation.
+///
+/// The index is the sum of the squared weights. For weights that sum to 1, it ranges from
+/// `1/N` for an equal-weight allocation over `N` holdings to `1.0` for an allocation
+/// concentrated in a single holding, so higher values indicate a more concentrated allocation.
+///
+/// # Arguments
+///
+/// * `weights` - A slice of allocation weights (`&[f64]`), expected to sum to 1.
+///
+/// # Returns
+///
+/// The Herfindahl-Hirschman index of the allocation, or `0.0` if `weights` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::concentration_svc::herfindahl_index;
+///
+/// assert_eq!(herfindahl_index(&[0.25, 0.25, 0.25, 0.25]), 0.25);
+/// assert_eq!(herfindahl_index(&[1.0, 0.0]), 1.0);
+/// ```
+pub fn herfindahl_index(weights: &[f64]) -> f64 {
+    weights.iter().map(|w| w * w).sum()
+}
+
+/// Calculates the effective number of holdings of an allocation.
+///
+/// The effective number of holdings is the inverse of the Herfindahl-Hirschman index. It is
+/// the number of equally weighted holdings that would have the same concentration as the
+/// allocation, which makes it an intuitive measure of diversification.
+///
+/// # Arguments
+///
+/// * `weights` - A slice of allocation weights (`&[f64]`), expected to sum to 1.
+///
+/// # Returns
+///
+/// The effective number of holdings, or `0.0` if the allocation has no non-zero weights.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::concentration_svc::effective_number_of_holdings;
+///
+/// assert_eq!(effective_number_of_holdings(&[0.25, 0.25, 0.25, 0.25]), 4.0);
+/// assert_eq!(effective_number_of_holdings(&[0.5, 0.5, 0.0]), 2.0);
+/// ```
+pub fn effective_number_of_holdings(weights: &[f64]) -> f64 {
+    let hhi = herfindahl_index(weights);
+    if hhi == 0.0 {
+        0.0
+    } else {
+        1.0 / hhi
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{
+    errors::NaluFxError,
+    utils::date::{check_series_continuity, SeriesIssue},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The largest number of consecutive missing weekdays that is not counted as a gap, as a
+/// single missing weekday is usually a market holiday.
+pub const MAX_MISSING_DAYS: usize = 1;
+
+/// The relative change between two consecutive prices above which the move is flagged as a
+/// suspicious jump (50%).
+pub const MAX_DAILY_JUMP: f64 = 0.5;
+
+/// The number of flags listed when a `QualityReport` is displayed.
+const DISPLAYED_FLAGS: usize = 5;
+
+/// Represents a suspicious observation found in a price series.
+///
+/// # Variants
+///
+/// * `NonPositive` - A price that is zero or negative, which a listed security cannot have.
+/// * `Jump` - A price that moved by more than [`MAX_DAILY_JUMP`] from the previous positive
+///   price, often a split or a data error.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum QualityFlag {
+    /// A price that is zero or negative.
+    NonPositive {
+        /// The timestamp of the observation.
+        date: DateTime<Utc>,
+        /// The price.
+        value: f64,
+    },
+    /// A price that moved by more than `MAX_DAILY_JUMP` from the previous positive price.
+    Jump {
+        /// The timestamp of the observation.
+        date: DateTime<Utc>,
+        /// The relative change from the previous positive price, as a fraction.
+        change: f64,
+    },
+}
+
+impl fmt::Display for QualityFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QualityFlag::NonPositive { date, value } => {
+                write!(f, "{}: non-positive price {:.2}", date.format("%Y-%m-%d"), value)
+            },
+            QualityFlag::Jump { date, change } => {
+                write!(f, "{}: price jump of {:+.1}%", date.format("%Y-%m-%d"), change * 100.0)
+            },
+        }
+    }
+}
+
+/// Represents a summary of the quality of a dated price series, to review before analysis.
+///
+/// # Fields
+///
+/// * `count` - The number of observations.
+/// * `first_date` - The timestamp of the first observation, or `None` for an empty series.
+/// * `last_date` - The timestamp of the last observation, or `None` for an empty series.
+/// * `gaps` - The number of gaps of more than [`MAX_MISSING_DAYS`] missing weekdays.
+/// * `nan_count` - The number of prices that are `NaN` or infinite.
+/// * `min` - The lowest finite price, or `None` if there is none.
+/// * `max` - The highest finite price, or `None` if there is none.
+/// * `flags` - The suspicious observations, in chronological order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QualityReport {
+    /// The number of observations.
+    pub count: usize,
+    /// The timestamp of the first observation.
+    pub first_date: Option<DateTime<Utc>>,
+    /// The timestamp of the last observation.
+    pub last_date: Option<DateTime<Utc>>,
+    /// The number of gaps in the series.
+    pub gaps: usize,
+    /// The number of prices that are `NaN` or infinite.
+    pub nan_count: usize,
+    /// The lowest finite price.
+    pub min: Option<f64>,
+    /// The highest finite price.
+    pub max: Option<f64>,
+    /// The suspicious observations.
+    pub flags: Vec<QualityFlag>,
+}
+
+impl QualityReport {
+    /// Returns `true` if the series has no gaps, no missing values and no flags.
+    pub fn is_clean(&self) -> bool {
+        self.gaps == 0 && self.nan_count == 0 && self.flags.is_empty()
+    }
+}
+
+impl fmt::Display for QualityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "--- Data Quality ---\n")?;
+        writeln!(f, "- Observations: {}", self.count)?;
+        if let (Some(first), Some(last)) = (self.first_date, self.last_date) {
+            writeln!(
+                f,
+                "- Date range: {} to {}",
+                first.format("%Y-%m-%d"),
+                last.format("%Y-%m-%d")
+            )?;
+        }
+        writeln!(f, "- Gaps: {}", self.gaps)?;
+        writeln!(f, "- Missing values: {}", self.nan_count)?;
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            writeln!(f, "- Price range: {:.2} to {:.2}", min, max)?;
+        }
+        if self.flags.is_empty() {
+            return writeln!(f, "- Flags: none");
+        }
+        writeln!(f, "- Flags: {}", self.flags.len())?;
+        for flag in self.flags.iter().take(DISPLAYED_FLAGS) {
+            writeln!(f, "  - {}", flag)?;
+        }
+        if self.flags.len() > DISPLAYED_FLAGS {
+            writeln!(f, "  - ... and {} more", self.flags.len() - DISPLAYED_FLAGS)?;
+        }
+        Ok(())
+    }
+}
+
+/// Assesses the quality of a dated price series before it is analyzed.
+///
+/// The report counts the observations, the gaps of more than [`MAX_MISSING_DAYS`] missing
+/// weekdays (see [`check_series_continuity`]) and the prices that are `NaN` or infinite. It
+/// flags every non-positive price, and every move of more than [`MAX_DAILY_JUMP`] between two
+/// consecutive positive prices.
+///
+/// # Arguments
+///
+/// * `series` - A slice of `(timestamp, price)` pairs in chronological order.
+///
+/// # Returns
+///
+/// The `QualityReport` of the series.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use nalufx::services::data_quality_svc::assess_quality;
+///
+/// let series = vec![
+///     (Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(), 100.0),
+///     (Utc.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap(), 101.0),
+///     (Utc.with_ymd_and_hms(2024, 1, 4, 0, 0, 0).unwrap(), f64::NAN),
+/// ];
+/// let report = assess_quality(&series);
+/// assert_eq!(report.count, 3);
+/// assert_eq!(report.nan_count, 1);
+/// assert_eq!(report.max, Some(101.0));
+/// ```
+pub fn assess_quality(series: &[(DateTime<Utc>, f64)]) -> QualityReport {
+    let gaps = match check_series_continuity(series, MAX_MISSING_DAYS) {
+        Err(NaluFxError::DiscontinuousSeries(issues)) => {
+            issues.iter().filter(|issue| matches!(issue, SeriesIssue::Gap { .. })).count()
+        },
+        _ => 0,
+    };
+
+    let finite = || series.iter().map(|&(_, value)| value).filter(|value| value.is_finite());
+
+    let mut flags = Vec::new();
+    let mut previous: Option<f64> = None;
+    for &(date, value) in series.iter().filter(|(_, value)| value.is_finite()) {
+        if value <= 0.0 {
+            flags.push(QualityFlag::NonPositive { date, value });
+            continue;
+        }
+        if let Some(previous) = previous {
+            let change = value / previous - 1.0;
+            if change.abs() > MAX_DAILY_JUMP {
+                flags.push(QualityFlag::Jump { date, change });
+            }
+        }
+        previous = Some(value);
+    }
+
+    QualityReport {
+        count: series.len(),
+        first_date: series.first().map(|&(date, _)| date),
+        last_date: series.last().map(|&(date, _)| date),
+        gaps,
+        nan_count: series.len() - finite().count(),
+        min: finite().reduce(f64::min),
+        max: finite().reduce(f64::max),
+        flags,
+    }
+}
@@ -1,18 +1,33 @@
 use crate::{
+    config::{chart_max_points, display_precision, fetch_concurrency},
     errors::NaluFxError,
     services::{
-        fetch_data_svc::fetch_data,
+        concentration_svc::{effective_number_of_holdings, herfindahl_index},
+        fetch_data_svc::fetch_data_batch,
         processing_svc::{calculate_cash_flows, calculate_daily_returns},
     },
     utils::{
+        allocation::normalize_non_negative,
+        batch::BatchOptions,
         calculations::{
-            analyze_sentiment, calculate_optimal_allocation, train_reinforcement_learning,
+            analyze_sentiment, calculate_optimal_allocation_with_options,
+            train_reinforcement_learning, AllocationOptions, Objective,
+        },
+        calendar::next_trading_days,
+        charts::{
+            downsample_lttb, portfolio_value_series, value_chart_text, write_value_chart_svg,
         },
         currency::format_currency,
+        reports::{fmt_vec, ReportSink},
+        risk::diversification_ratio,
+        stats::mean,
+        validation::validate_positive_amount,
     },
 };
 use chrono::{Duration, Utc};
-use std::fs::File;
+use ndarray::Array2;
+use ndarray_stats::CorrelationExt;
+use rayon::prelude::*;
 use std::io::Write;
 use textplots::{Chart, LabelBuilder, LabelFormat, Plot, Shape};
 
@@ -22,6 +37,7 @@
 ///
 /// * `tickers` - A vector of strings representing the tickers of the ETFs to analyze.
 /// * `initial_investment` - A f64 representing the initial investment amount.
+/// * `reports` - The `ReportSink` writing the report and its chart to timestamped files.
 ///
 /// # Returns
 ///
@@ -29,8 +45,10 @@
 ///
 /// # Errors
 ///
-/// * `NaluFxError::InvalidData` - If the API key for the chosen LLM is invalid.
+/// * `NaluFxError::InvalidData` - If the initial investment is zero, negative or not finite, or
+///   if the API key for the chosen LLM is invalid.
 /// * `NaluFxError::InvalidOption` - If the chosen LLM is not supported.
+/// * `NaluFxError::InputError` - If the reports directory or the report file cannot be created.
 /// * `NaluFxError::FetchDataError` - If there is an error fetching data for a specific ticker.
 ///
 /// # Panics
@@ -39,15 +57,23 @@
 pub async fn generate_analysis(
     tickers: Vec<String>,
     initial_investment: f64,
+    reports: &ReportSink,
 ) -> Result<(), NaluFxError> {
-    let date = Utc::now().format("%Y-%m-%d").to_string();
-    let filename = format!("./reports/{}_03_diversified_etf_portfolio_optimization.md", date);
-    let mut file = File::create(&filename)?;
+    let initial_investment = validate_positive_amount(initial_investment)?;
+
+    let run_started = Utc::now();
+    let (report_path, mut file) =
+        reports.create_at(run_started, "03_diversified_etf_portfolio_optimization", "md")?;
+    eprintln!("Writing the report to {}", report_path.display());
+
+    // Fetch historical closing prices for each ETF, a few at a time
+    let options = BatchOptions::new(fetch_concurrency())
+        .with_progress(|completed, total| eprintln!("{}/{} tickers fetched", completed, total));
+    let results = fetch_data_batch(&tickers, None, None, &options).await;
 
-    // Fetch historical closing prices for each ETF
     let mut etf_data = Vec::new();
-    for ticker in &tickers {
-        match fetch_data(ticker, None, None).await {
+    for (ticker, result) in tickers.iter().zip(results) {
+        match result {
             Ok(closes) => {
                 // Calculate daily returns from closing prices
                 let daily_returns = calculate_daily_returns(&closes);
@@ -71,6 +97,9 @@ pub async fn generate_analysis(
         return Ok(());
     }
 
+    // Calculate the diversification ratio of an equally weighted portfolio of the ETFs
+    let diversification = equal_weight_diversification_ratio(&etf_data);
+
     // Generate more market indices data
     let market_indices = vec![
         (Utc::now() - Duration::days(90), 1000.0),
@@ -111,52 +140,28 @@ pub async fn generate_analysis(
         fund_characteristics.iter().map(|&(_, value)| value).collect();
     let fund_characteristics = &fund_characteristics[..min_length];
 
-    // Calculate the optimal allocation and other analysis results for each ETF
-    let mut etf_results = Vec::new();
-    for (ticker, daily_returns, cash_flows) in &etf_data {
-        let daily_returns = &daily_returns[..min_length];
-        let cash_flows = &cash_flows[..min_length];
-
-        match calculate_optimal_allocation(
-            daily_returns,
-            cash_flows,
-            market_indices,
-            fund_characteristics,
-            min_length,
-        ) {
-            Ok(mut optimal_allocation) => {
-                // Filter out negative allocations and normalize the rest
-                optimal_allocation = optimal_allocation
-                    .into_iter()
-                    .map(|alloc| if alloc < 0.0 { 0.0 } else { alloc })
-                    .collect();
-                let total_allocation: f64 = optimal_allocation.iter().sum();
-                optimal_allocation =
-                    optimal_allocation.into_iter().map(|alloc| alloc / total_allocation).collect();
-
-                // Calculate sentiment analysis and reinforcement learning results
-                let sentiment_scores = analyze_sentiment(min_length).unwrap();
-                let optimal_actions = train_reinforcement_learning(min_length).unwrap();
-
-                etf_results.push((
-                    ticker.clone(),
-                    optimal_allocation,
-                    sentiment_scores,
-                    optimal_actions,
-                ));
-            },
-            Err(e) => {
-                eprintln!("Error calculating optimal allocation for {}: {}", ticker, e);
-            },
-        }
-    }
+    // Calculate the optimal allocation of each ETF in parallel, then the other analysis results
+    let etf_results: Vec<_> = calculate_etf_allocations(
+        &etf_data,
+        market_indices,
+        fund_characteristics,
+        Objective::default(),
+    )
+    .into_iter()
+    .map(|(ticker, optimal_allocation)| {
+        // Calculate sentiment analysis and reinforcement learning results
+        let sentiment_scores = analyze_sentiment(min_length).unwrap();
+        let optimal_actions = train_reinforcement_learning(min_length).unwrap();
+        (ticker, optimal_allocation, sentiment_scores, optimal_actions)
+    })
+    .collect();
 
     // Compare the outcomes of all ETFs and select the one with the best performance
     if let Some((best_etf, best_allocation, best_sentiment, best_actions)) =
         etf_results.into_iter().max_by(|(_, allocation1, _, _), (_, allocation2, _, _)| {
             // Define a custom metric to compare ETF performance (e.g., average allocation)
-            let avg_alloc1 = allocation1.iter().sum::<f64>() / allocation1.len() as f64;
-            let avg_alloc2 = allocation2.iter().sum::<f64>() / allocation2.len() as f64;
+            let avg_alloc1 = mean(allocation1).unwrap_or(f64::NAN);
+            let avg_alloc2 = mean(allocation2).unwrap_or(f64::NAN);
             avg_alloc1.partial_cmp(&avg_alloc2).unwrap_or(std::cmp::Ordering::Equal)
         })
     {
@@ -181,10 +186,23 @@ pub async fn generate_analysis(
         writeln!(file, "{}", fund_overview)?;
 
         // Print the optimal allocation report
-        let optimal_allocation_intro = format!("### Optimal Allocation\nYour recommended allocation represents the optimal distribution of funds for the forthcoming {} days. Each value within the allocation vector signifies the percentage of funds designated to **{}** for each specific day. The total of all values within the allocation vector should approximate 1.0 (100%).\n\n- Optimal Allocation: {:?}", min_length, best_etf, best_allocation);
+        let optimal_allocation_intro = format!("### Optimal Allocation\nYour recommended allocation represents the optimal distribution of funds for the forthcoming {} days. Each value within the allocation vector signifies the percentage of funds designated to **{}** for each specific day. The total of all values within the allocation vector should approximate 1.0 (100%).\n\n- Optimal Allocation: {}", min_length, best_etf, fmt_vec(&best_allocation, display_precision()));
         println!("{}", optimal_allocation_intro);
         writeln!(file, "{}", optimal_allocation_intro)?;
 
+        // Print the concentration of the optimal allocation
+        let concentration = format!("- Concentration (Herfindahl-Hirschman index): {:.4}, equivalent to an even allocation over {:.2} days", herfindahl_index(&best_allocation), effective_number_of_holdings(&best_allocation));
+        println!("{}", concentration);
+        writeln!(file, "{}", concentration)?;
+
+        // Print the diversification ratio of the analyzed ETFs
+        let diversification = match diversification {
+            Ok(ratio) => format!("- Diversification ratio of an equally weighted portfolio of the {} analyzed ETFs: {:.2}", etf_data.len(), ratio),
+            Err(e) => format!("- Diversification ratio of the analyzed ETFs: N/A ({})", e),
+        };
+        println!("{}", diversification);
+        writeln!(file, "{}", diversification)?;
+
         // Print the sentiment analysis results
         let sentiment_analysis_methodology = format!("\n## Sentiment Analysis Methodology\nThe sentiment analysis is based on advanced natural language processing techniques applied to financial news and social media data. These models evaluate the sentiment expressed in textual data, ranging from highly positive to highly negative, providing a quantitative measure of market sentiment.");
         println!("{}", sentiment_analysis_methodology);
@@ -301,11 +319,12 @@ pub async fn generate_analysis(
         println!("{}", optimal_allocation_visualization_intro);
         writeln!(file, "{}", optimal_allocation_visualization_intro)?;
 
-        // Prepare data for plotting
-        let plot_data: Vec<(f32, f32)> = best_allocation
-            .iter()
-            .enumerate()
-            .map(|(i, &alloc)| (i as f32, alloc as f32))
+        // Prepare data for plotting, downsampled so that long horizons stay readable
+        let allocation_points: Vec<(f64, f64)> =
+            best_allocation.iter().enumerate().map(|(i, &alloc)| (i as f64, alloc)).collect();
+        let plot_data: Vec<(f32, f32)> = downsample_lttb(&allocation_points, chart_max_points())
+            .into_iter()
+            .map(|(x, alloc)| (x as f32, alloc as f32))
             .collect();
 
         // Verify the last x-value for the x-range
@@ -318,14 +337,57 @@ pub async fn generate_analysis(
             .y_label_format(LabelFormat::Custom(Box::new(|y| format!("{:.2}", y))))
             .display();
 
+        // Chart the growth of the investment over the historical prices of the selected ETF
+        let value_over_time = format!("\n## Portfolio Value Over Time\nThe chart below shows how an investment of {} in **{}** would have grown over the analyzed period, reinvesting every daily return.\n", format_currency(initial_investment), best_etf);
+        println!("{}", value_over_time);
+        writeln!(file, "{}", value_over_time)?;
+
+        let best_returns = etf_data
+            .iter()
+            .find(|(ticker, _, _)| *ticker == best_etf)
+            .map(|(_, daily_returns, _)| daily_returns.as_slice())
+            .unwrap_or_default();
+        let values = portfolio_value_series(initial_investment, best_returns);
+        match value_chart_text(&values) {
+            Ok(chart) => {
+                println!("{}", chart);
+                writeln!(file, "```text\n{}```\n", chart)?;
+            },
+            Err(e) => eprintln!("Error charting the portfolio value: {}", e),
+        }
+        let chart_title =
+            format!("Value of {} invested in {}", format_currency(initial_investment), best_etf);
+        let chart = reports
+            .create_at(run_started, "03_diversified_etf_portfolio_value", "svg")
+            .and_then(|(chart_path, _)| {
+                write_value_chart_svg(&chart_path, &chart_title, &values).map(|()| chart_path)
+            });
+        match chart {
+            Ok(chart_path) => {
+                let chart_name = chart_path.file_name().unwrap_or_default().to_string_lossy();
+                writeln!(file, "![{}]({})\n", chart_title, chart_name)?
+            },
+            Err(e) => eprintln!("Error writing the portfolio value chart: {}", e),
+        }
+        let final_value = values.last().copied().unwrap_or(initial_investment);
+        let value_summary = format!(
+            "- Final value: {} ({:+.2}%)",
+            format_currency(final_value),
+            (final_value / initial_investment - 1.0) * 100.0
+        );
+        println!("{}", value_summary);
+        writeln!(file, "{}", value_summary)?;
+
         let allocation_recommendation = format!("\n## Allocation Recommendation\nBased on the optimal allocation strategy and your initial investment of {}, we recommend distributing the fund as follows:\n", format_currency(initial_investment));
         println!("{}", allocation_recommendation);
         writeln!(file, "{}", allocation_recommendation)?;
 
-        let today = Utc::now();
-        for (i, &allocation) in best_allocation.iter().enumerate() {
+        // Map each allocation to a trading day, skipping weekends and market holidays
+        let allocation_dates = next_trading_days(Utc::now(), best_allocation.len());
+        for (i, (&allocation, allocation_date)) in
+            best_allocation.iter().zip(&allocation_dates).enumerate()
+        {
             let allocation_amount = allocation * initial_investment;
-            let allocation_date = today + Duration::days(i as i64);
             let allocation_percentage = allocation * 100.0;
             let allocation_detail = format!(
                 "- Day {}: {} - Allocate {} ({:.2}%) to {}\n",
@@ -361,3 +423,126 @@ pub async fn generate_analysis(
 
     Ok(())
 }
+
+/// Calculates the optimal allocation of each ETF, in parallel.
+///
+/// The allocations are independent of each other and CPU-bound, so they are calculated on the
+/// `rayon` thread pool. The daily returns and cash flows of each ETF are truncated to the
+/// length of `market_indices`, which is also the number of days allocated. Negative
+/// allocations are filtered out and the rest normalized to sum to 1 (see
+/// [`normalize_non_negative`]).
+///
+/// # Arguments
+///
+/// * `etf_data` - The ticker symbol, daily returns and cash flows of each ETF.
+/// * `market_indices` - The market indices, one per day.
+/// * `fund_characteristics` - The fund characteristics, one per day.
+/// * `objective` - The `Objective` of the allocations.
+///
+/// # Returns
+///
+/// The ticker symbol and optimal allocation of each ETF, in the order of `etf_data`. An ETF
+/// whose allocation cannot be calculated is reported on stderr and skipped.
+pub fn calculate_etf_allocations(
+    etf_data: &[(String, Vec<f64>, Vec<f64>)],
+    market_indices: &[f64],
+    fund_characteristics: &[f64],
+    objective: Objective,
+) -> Vec<(String, Vec<f64>)> {
+    etf_data
+        .par_iter()
+        .filter_map(|(ticker, daily_returns, cash_flows)| {
+            etf_allocation(
+                ticker,
+                daily_returns,
+                cash_flows,
+                market_indices,
+                fund_characteristics,
+                objective,
+            )
+        })
+        .collect()
+}
+
+/// Calculates the optimal allocation of each ETF, one after the other.
+///
+/// This is the serial counterpart of [`calculate_etf_allocations`], with the same arguments
+/// and results, for callers that must not use the `rayon` thread pool.
+pub fn calculate_etf_allocations_serial(
+    etf_data: &[(String, Vec<f64>, Vec<f64>)],
+    market_indices: &[f64],
+    fund_characteristics: &[f64],
+    objective: Objective,
+) -> Vec<(String, Vec<f64>)> {
+    etf_data
+        .iter()
+        .filter_map(|(ticker, daily_returns, cash_flows)| {
+            etf_allocation(
+                ticker,
+                daily_returns,
+                cash_flows,
+                market_indices,
+                fund_characteristics,
+                objective,
+            )
+        })
+        .collect()
+}
+
+/// Calculates the normalized optimal allocation of one ETF, or reports the error and returns
+/// `None`.
+fn etf_allocation(
+    ticker: &str,
+    daily_returns: &[f64],
+    cash_flows: &[f64],
+    market_indices: &[f64],
+    fund_characteristics: &[f64],
+    objective: Objective,
+) -> Option<(String, Vec<f64>)> {
+    let num_days = market_indices.len();
+    let daily_returns = &daily_returns[..num_days.min(daily_returns.len())];
+    let cash_flows = &cash_flows[..num_days.min(cash_flows.len())];
+
+    // Filter out negative allocations and normalize the rest
+    match calculate_optimal_allocation_with_options(
+        daily_returns,
+        cash_flows,
+        market_indices,
+        fund_characteristics,
+        num_days,
+        &AllocationOptions { objective, ..AllocationOptions::default() },
+    )
+    .and_then(normalize_non_negative)
+    {
+        Ok(optimal_allocation) => Some((ticker.to_string(), optimal_allocation)),
+        Err(e) => {
+            eprintln!("Error calculating optimal allocation for {}: {}", ticker, e);
+            None
+        },
+    }
+}
+
+/// Calculates the diversification ratio of an equally weighted portfolio of the ETFs.
+///
+/// The covariance matrix is estimated from the daily returns of the ETFs, truncated to the
+/// length of the shortest series.
+fn equal_weight_diversification_ratio(
+    etf_data: &[(String, Vec<f64>, Vec<f64>)],
+) -> Result<f64, NaluFxError> {
+    let num_etfs = etf_data.len();
+    let num_returns =
+        etf_data.iter().map(|(_, daily_returns, _)| daily_returns.len()).min().unwrap_or(0);
+    if num_etfs == 0 || num_returns < 2 {
+        return Err(NaluFxError::InvalidData);
+    }
+
+    let returns: Vec<f64> = etf_data
+        .iter()
+        .flat_map(|(_, daily_returns, _)| daily_returns[..num_returns].iter().copied())
+        .collect();
+    let returns = Array2::from_shape_vec((num_etfs, num_returns), returns)
+        .map_err(|_| NaluFxError::InvalidData)?;
+    let cov = returns.cov(1.0).map_err(|_| NaluFxError::InvalidData)?;
+
+    diversification_ratio(&vec![1.0 / num_etfs as f64; num_etfs], &cov)
+}
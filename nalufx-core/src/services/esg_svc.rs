@@ -0,0 +1,198 @@
+use crate::errors::NaluFxError;
+use async_trait::async_trait;
+use log::{error, info};
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use std::{collections::HashMap, env, io::Read, path::Path};
+
+/// The default location of the local ESG ratings file.
+pub const DEFAULT_ESG_RATINGS_PATH: &str = "data/esg_ratings.csv";
+
+/// The environment variable used to override the location of the local ESG ratings file.
+pub const ESG_RATINGS_PATH_ENV: &str = "ESG_RATINGS_PATH";
+
+/// The environment variable used to select an HTTP ESG rating provider.
+pub const ESG_RATINGS_URL_ENV: &str = "ESG_RATINGS_URL";
+
+/// A trait representing a source of ESG ratings.
+///
+/// Implementations return the ESG rating for a ticker symbol, or
+/// `NaluFxError::EsgRatingNotFound` if the source has no rating for it.
+#[async_trait]
+pub trait EsgRatingProvider: Sync + Send {
+    /// Fetches the ESG rating for the given ticker symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `ticker` - A string slice that holds the ticker symbol (e.g., "ESGU").
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the ESG rating (`f64`) if successful, or a `NaluFxError` otherwise.
+    async fn fetch_esg_rating(&self, ticker: &str) -> Result<f64, NaluFxError>;
+}
+
+#[derive(Debug, Deserialize)]
+struct EsgRatingRecord {
+    symbol: String,
+    esg_rating: f64,
+}
+
+/// An ESG rating provider backed by a CSV file with `symbol,esg_rating` columns.
+///
+/// Ticker symbols are matched case-insensitively.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::esg_svc::CsvEsgRatingProvider;
+///
+/// let csv = "symbol,esg_rating\nESGU,7.2\nICLN,8.1\n";
+/// let provider = CsvEsgRatingProvider::from_reader(csv.as_bytes()).unwrap();
+/// assert_eq!(provider.rating("icln"), Some(8.1));
+/// assert_eq!(provider.rating("SPY"), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CsvEsgRatingProvider {
+    ratings: HashMap<String, f64>,
+}
+
+impl CsvEsgRatingProvider {
+    /// Loads the ESG ratings from the CSV file at the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NaluFxError::CsvError` if the file cannot be read or parsed, or
+    /// `NaluFxError::InvalidData` if a rating is not a finite number.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, NaluFxError> {
+        let reader = csv::Reader::from_path(path)?;
+        Self::from_csv_reader(reader)
+    }
+
+    /// Loads the ESG ratings from any reader producing CSV data.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NaluFxError::CsvError` if the data cannot be parsed, or
+    /// `NaluFxError::InvalidData` if a rating is not a finite number.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, NaluFxError> {
+        Self::from_csv_reader(csv::Reader::from_reader(reader))
+    }
+
+    fn from_csv_reader<R: Read>(mut reader: csv::Reader<R>) -> Result<Self, NaluFxError> {
+        let mut ratings = HashMap::new();
+        for record in reader.deserialize() {
+            let record: EsgRatingRecord = record?;
+            if !record.esg_rating.is_finite() {
+                return Err(NaluFxError::InvalidData);
+            }
+            let _ = ratings.insert(record.symbol.trim().to_uppercase(), record.esg_rating);
+        }
+        Ok(Self { ratings })
+    }
+
+    /// Returns the rating for the given ticker symbol, if present.
+    pub fn rating(&self, ticker: &str) -> Option<f64> {
+        self.ratings.get(&ticker.trim().to_uppercase()).copied()
+    }
+}
+
+#[async_trait]
+impl EsgRatingProvider for CsvEsgRatingProvider {
+    async fn fetch_esg_rating(&self, ticker: &str) -> Result<f64, NaluFxError> {
+        self.rating(ticker).ok_or_else(|| NaluFxError::EsgRatingNotFound(ticker.to_string()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EsgRatingResponse {
+    esg_rating: Option<f64>,
+}
+
+/// An ESG rating provider backed by an HTTP endpoint.
+///
+/// The provider issues a `GET {base_url}/{ticker}` request and expects a JSON body of the
+/// form `{"esg_rating": 7.2}`. The ticker is percent-encoded as a single path segment, so that
+/// e.g. `BRK/B` is requested as `{base_url}/BRK%2FB`. A `404 Not Found` response or a missing
+/// `esg_rating` field is reported as `NaluFxError::EsgRatingNotFound`, and a base URL that
+/// cannot be parsed, or cannot have a path, as `NaluFxError::ConfigError`.
+#[derive(Clone, Debug)]
+pub struct HttpEsgRatingProvider {
+    client: Client,
+    base_url: String,
+}
+
+impl HttpEsgRatingProvider {
+    /// Creates a new `HttpEsgRatingProvider` for the given base URL.
+    pub fn new(client: Client, base_url: &str) -> Self {
+        Self { client, base_url: base_url.trim_end_matches('/').to_string() }
+    }
+}
+
+#[async_trait]
+impl EsgRatingProvider for HttpEsgRatingProvider {
+    async fn fetch_esg_rating(&self, ticker: &str) -> Result<f64, NaluFxError> {
+        let invalid_url =
+            || NaluFxError::ConfigError(format!("Invalid ESG ratings URL {}", self.base_url));
+        let mut url = Url::parse(&self.base_url).map_err(|_| invalid_url())?;
+        let _ = url
+            .path_segments_mut()
+            .map_err(|()| invalid_url())?
+            .pop_if_empty()
+            .push(&ticker.trim().to_uppercase());
+        let response = self.client.get(url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(NaluFxError::EsgRatingNotFound(ticker.to_string()));
+        }
+
+        let body: EsgRatingResponse = response.error_for_status()?.json().await?;
+        match body.esg_rating {
+            Some(rating) if rating.is_finite() => Ok(rating),
+            Some(_) => Err(NaluFxError::InvalidData),
+            None => Err(NaluFxError::EsgRatingNotFound(ticker.to_string())),
+        }
+    }
+}
+
+/// Creates the ESG rating provider selected by the environment.
+///
+/// If `ESG_RATINGS_URL` is set, an `HttpEsgRatingProvider` for that URL is returned.
+/// Otherwise the ratings are loaded from the CSV file at `ESG_RATINGS_PATH`, falling back to
+/// `data/esg_ratings.csv`.
+///
+/// # Errors
+///
+/// Returns a `NaluFxError` if the local ratings file cannot be loaded.
+pub fn esg_rating_provider_from_env() -> Result<Box<dyn EsgRatingProvider>, NaluFxError> {
+    if let Ok(url) = env::var(ESG_RATINGS_URL_ENV) {
+        info!("Using HTTP ESG rating provider at {}", url);
+        return Ok(Box::new(HttpEsgRatingProvider::new(Client::new(), &url)));
+    }
+
+    let path =
+        env::var(ESG_RATINGS_PATH_ENV).unwrap_or_else(|_| DEFAULT_ESG_RATINGS_PATH.to_string());
+    info!("Loading ESG ratings from {}", path);
+    let provider = CsvEsgRatingProvider::from_path(&path).map_err(|e| {
+        error!("Failed to load ESG ratings from {}: {}", path, e);
+        e
+    })?;
+    Ok(Box::new(provider))
+}
+
+/// Fetches the ESG rating for a ticker symbol from the provider configured in the environment.
+///
+/// See [`esg_rating_provider_from_env`] for how the source is selected. Callers rating many
+/// tickers should create the provider once and reuse it instead.
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol (e.g., "ESGU").
+///
+/// # Returns
+///
+/// A `Result` containing the ESG rating (`f64`) if successful, or a `NaluFxError` if the
+/// source cannot be loaded or has no rating for the ticker.
+pub async fn fetch_esg_rating(ticker: &str) -> Result<f64, NaluFxError> {
+    esg_rating_provider_from_env()?.fetch_esg_rating(ticker).await
+}
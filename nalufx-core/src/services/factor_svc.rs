@@ -0,0 +1,355 @@
+use crate::errors::NaluFxError;
+use crate::utils::stats::zscore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The tolerance within which the factor weights must sum to `1.0`.
+pub const WEIGHT_SUM_TOLERANCE: f64 = 1e-6;
+
+/// Represents the weights given to each factor in the composite score.
+///
+/// # Fields
+///
+/// * `value` - The weight of the value factor.
+/// * `quality` - The weight of the quality factor.
+/// * `momentum` - The weight of the momentum factor.
+/// * `size` - The weight of the size factor.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::factor_svc::FactorWeights;
+///
+/// let weights = FactorWeights::default();
+/// assert_eq!(weights.momentum, 0.25);
+///
+/// assert!(FactorWeights::new(0.0, 0.0, 1.0, 0.0).is_ok());
+/// assert!(FactorWeights::new(0.5, 0.5, 0.5, 0.0).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FactorWeights {
+    /// The weight of the value factor.
+    pub value: f64,
+    /// The weight of the quality factor.
+    pub quality: f64,
+    /// The weight of the momentum factor.
+    pub momentum: f64,
+    /// The weight of the size factor.
+    pub size: f64,
+}
+
+impl Default for FactorWeights {
+    fn default() -> Self {
+        Self { value: 0.25, quality: 0.25, momentum: 0.25, size: 0.25 }
+    }
+}
+
+impl FactorWeights {
+    /// Creates a new `FactorWeights` instance after validating the weights.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The weight of the value factor.
+    /// * `quality` - The weight of the quality factor.
+    /// * `momentum` - The weight of the momentum factor.
+    /// * `size` - The weight of the size factor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NaluFxError::InvalidData` if the weights do not pass `validate`.
+    pub fn new(value: f64, quality: f64, momentum: f64, size: f64) -> Result<Self, NaluFxError> {
+        let weights = Self { value, quality, momentum, size };
+        weights.validate()?;
+        Ok(weights)
+    }
+
+    /// Checks that every weight is finite and non-negative and that the weights sum to `1.0`
+    /// within `WEIGHT_SUM_TOLERANCE`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NaluFxError::InvalidData` if a weight is not finite, is negative, or the weights
+    /// do not sum to `1.0`.
+    pub fn validate(&self) -> Result<(), NaluFxError> {
+        let weights = [self.value, self.quality, self.momentum, self.size];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(NaluFxError::InvalidData);
+        }
+        if (weights.iter().sum::<f64>() - 1.0).abs() > WEIGHT_SUM_TOLERANCE {
+            return Err(NaluFxError::InvalidData);
+        }
+        Ok(())
+    }
+}
+
+/// Represents the raw factor values of a stock, where a higher value is more attractive.
+///
+/// A factor is `None` when the data to calculate it is unavailable, e.g. when Yahoo Finance
+/// has no fundamentals for the stock.
+///
+/// # Fields
+///
+/// * `symbol` - The ticker symbol of the stock.
+/// * `value` - The value factor, e.g. the earnings and book yields.
+/// * `quality` - The quality factor, e.g. return on equity less debt-to-equity.
+/// * `momentum` - The momentum factor, e.g. the 12-month price return.
+/// * `size` - The size factor, e.g. the inverse of the market capitalization.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StockFactors {
+    /// The ticker symbol of the stock.
+    pub symbol: String,
+    /// The value factor.
+    pub value: Option<f64>,
+    /// The quality factor.
+    pub quality: Option<f64>,
+    /// The momentum factor.
+    pub momentum: Option<f64>,
+    /// The size factor.
+    pub size: Option<f64>,
+}
+
+/// Represents the normalized factor scores and composite score of a ranked stock.
+///
+/// # Fields
+///
+/// * `symbol` - The ticker symbol of the stock.
+/// * `value_score` - The z-score of the value factor across the ranked stocks.
+/// * `quality_score` - The z-score of the quality factor across the ranked stocks.
+/// * `momentum_score` - The z-score of the momentum factor across the ranked stocks, or `None`
+///   if the momentum of the stock is unavailable.
+/// * `size_score` - The z-score of the size factor across the ranked stocks.
+/// * `composite_score` - The weighted sum of the factor scores.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FactorScores {
+    /// The ticker symbol of the stock.
+    pub symbol: String,
+    /// The z-score of the value factor.
+    pub value_score: f64,
+    /// The z-score of the quality factor.
+    pub quality_score: f64,
+    /// The z-score of the momentum factor, if the momentum is available.
+    pub momentum_score: Option<f64>,
+    /// The z-score of the size factor.
+    pub size_score: f64,
+    /// The weighted sum of the factor scores.
+    pub composite_score: f64,
+}
+
+/// Ranks stocks by the weighted composite of their factor scores.
+///
+/// Each factor is standardized to z-scores across the stocks (see `zscore`), so that factors
+/// on different scales contribute comparably. Missing factor values are excluded from the
+/// normalization and given a neutral score of `0.0`, the mean. The composite score is the sum
+/// of the factor scores multiplied by their weights, and the stocks are returned from the
+/// highest composite score to the lowest. Stocks with equal composite scores keep their input order.
+///
+/// A missing momentum, e.g. when the price a year ago is unavailable, is not scored as average:
+/// the momentum score of the stock is `None`, and its composite score is the weighted mean of
+/// its other factor scores, or `0.0` if the other factors have no weight.
+///
+/// # Arguments
+///
+/// * `stocks` - A slice of `StockFactors` with the raw factor values of each stock.
+/// * `weights` - The `FactorWeights` to apply to the factor scores.
+///
+/// # Returns
+///
+/// A `Result` containing the ranked `FactorScores`, or a `NaluFxError` if:
+/// - `stocks` is empty (`EmptyInput`).
+/// - The weights are invalid, or a factor value is `NaN` (`InvalidData`).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::factor_svc::{rank_by_factors, FactorWeights, StockFactors};
+///
+/// let stocks = vec![
+///     StockFactors {
+///         symbol: "AAA".into(),
+///         value: Some(0.1),
+///         quality: Some(0.2),
+///         momentum: Some(0.05),
+///         size: None,
+///     },
+///     StockFactors {
+///         symbol: "BBB".into(),
+///         value: Some(0.2),
+///         quality: None,
+///         momentum: Some(0.30),
+///         size: None,
+///     },
+/// ];
+/// let ranked = rank_by_factors(&stocks, &FactorWeights::new(0.0, 0.0, 1.0, 0.0).unwrap()).unwrap();
+/// assert_eq!(ranked[0].symbol, "BBB");
+/// ```
+pub fn rank_by_factors(
+    stocks: &[StockFactors],
+    weights: &FactorWeights,
+) -> Result<Vec<FactorScores>, NaluFxError> {
+    if stocks.is_empty() {
+        return Err(NaluFxError::EmptyInput);
+    }
+    weights.validate()?;
+
+    let normalize = |factor: fn(&StockFactors) -> Option<f64>| -> Result<Vec<f64>, NaluFxError> {
+        let available: Vec<f64> = stocks.iter().filter_map(factor).collect();
+        let mut available_scores = zscore(&available).into_iter();
+        if available.iter().any(|value| value.is_nan()) {
+            return Err(NaluFxError::InvalidData);
+        }
+        Ok(stocks
+            .iter()
+            .map(|stock| factor(stock).and_then(|_| available_scores.next()))
+            .collect::<Vec<Option<f64>>>())
+    };
+    let neutral = |scores: Vec<Option<f64>>| -> Vec<f64> {
+        scores.into_iter().map(|score| score.unwrap_or(0.0)).collect()
+    };
+    let value_scores = neutral(normalize(|s| s.value)?);
+    let quality_scores = neutral(normalize(|s| s.quality)?);
+    let momentum_scores = normalize(|s| s.momentum)?;
+    let size_scores = neutral(normalize(|s| s.size)?);
+
+    let mut ranked: Vec<FactorScores> = stocks
+        .iter()
+        .enumerate()
+        .map(|(i, stock)| {
+            let other_score = weights.value * value_scores[i]
+                + weights.quality * quality_scores[i]
+                + weights.size * size_scores[i];
+            let composite_score = match momentum_scores[i] {
+                Some(momentum_score) => other_score + weights.momentum * momentum_score,
+                None => {
+                    let other_weight = weights.value + weights.quality + weights.size;
+                    if other_weight > 0.0 {
+                        other_score / other_weight
+                    } else {
+                        0.0
+                    }
+                },
+            };
+            FactorScores {
+                symbol: stock.symbol.clone(),
+                value_score: value_scores[i],
+                quality_score: quality_scores[i],
+                momentum_score: momentum_scores[i],
+                size_score: size_scores[i],
+                composite_score,
+            }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.composite_score.total_cmp(&a.composite_score));
+    Ok(ranked)
+}
+
+/// Represents how `factor_tilted_weights` converts composite scores into portfolio weights.
+///
+/// # Variants
+///
+/// * `Softmax` - Weight each stock by `exp(composite_score / temperature)`. A lower temperature
+///   concentrates the portfolio in the top-ranked stocks, and a higher one spreads it towards
+///   equal weights (the default, with a temperature of `1.0`).
+/// * `RankBased` - Weight each stock by its rank from the bottom, so that the lowest-ranked stock
+///   has a weight of 1 part, the next 2 parts, and so on. Stocks with equal composite scores
+///   share the higher rank.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WeightingScheme {
+    /// Weights proportional to `exp(composite_score / temperature)`.
+    Softmax {
+        /// The temperature of the softmax, which must be positive.
+        temperature: f64,
+    },
+    /// Weights proportional to the rank from the bottom.
+    RankBased,
+}
+
+impl Default for WeightingScheme {
+    fn default() -> Self {
+        Self::Softmax { temperature: 1.0 }
+    }
+}
+
+/// Converts the composite scores of ranked stocks into long-only target portfolio weights.
+///
+/// Every stock gets a positive weight, and a higher composite score always gets a higher
+/// weight, so the portfolio is tilted towards the stocks with the most attractive factors
+/// without excluding any. Stocks listed more than once receive the sum of their weights.
+///
+/// # Arguments
+///
+/// * `scores` - A slice of `FactorScores`, typically the result of `rank_by_factors`.
+/// * `scheme` - The `WeightingScheme` that converts the composite scores into weights.
+///
+/// # Returns
+///
+/// A `Result` containing the target weight of each symbol, summing to `1.0`, or a
+/// `NaluFxError` if:
+/// - `scores` is empty (`EmptyInput`).
+/// - A composite score is not finite, or the softmax temperature is not positive
+///   (`InvalidData`).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::factor_svc::{factor_tilted_weights, FactorScores, WeightingScheme};
+///
+/// let scores: Vec<FactorScores> = [("AAA", 1.0), ("BBB", 0.0), ("CCC", -1.0)]
+///     .iter()
+///     .map(|&(symbol, composite_score)| FactorScores {
+///         symbol: symbol.into(),
+///         value_score: 0.0,
+///         quality_score: 0.0,
+///         momentum_score: Some(0.0),
+///         size_score: 0.0,
+///         composite_score,
+///     })
+///     .collect();
+///
+/// let weights = factor_tilted_weights(&scores, WeightingScheme::RankBased).unwrap();
+/// assert!((weights["AAA"] - 0.5).abs() < 1e-12);
+/// assert!((weights["CCC"] - 1.0 / 6.0).abs() < 1e-12);
+///
+/// let weights = factor_tilted_weights(&scores, WeightingScheme::default()).unwrap();
+/// assert!(weights["AAA"] > weights["BBB"] && weights["BBB"] > weights["CCC"]);
+/// ```
+pub fn factor_tilted_weights(
+    scores: &[FactorScores],
+    scheme: WeightingScheme,
+) -> Result<HashMap<String, f64>, NaluFxError> {
+    if scores.is_empty() {
+        return Err(NaluFxError::EmptyInput);
+    }
+    if scores.iter().any(|score| !score.composite_score.is_finite()) {
+        return Err(NaluFxError::InvalidData);
+    }
+
+    let parts: Vec<f64> = match scheme {
+        WeightingScheme::Softmax { temperature } => {
+            if !(temperature > 0.0 && temperature.is_finite()) {
+                return Err(NaluFxError::InvalidData);
+            }
+            // Subtracting the highest score keeps every exponent at or below zero.
+            let max_score =
+                scores.iter().map(|score| score.composite_score).fold(f64::MIN, f64::max);
+            scores
+                .iter()
+                .map(|score| ((score.composite_score - max_score) / temperature).exp())
+                .collect()
+        },
+        WeightingScheme::RankBased => scores
+            .iter()
+            .map(|score| {
+                scores.iter().filter(|other| other.composite_score <= score.composite_score).count()
+                    as f64
+            })
+            .collect(),
+    };
+
+    let total: f64 = parts.iter().sum();
+    let mut weights = HashMap::with_capacity(scores.len());
+    for (score, part) in scores.iter().zip(parts) {
+        *weights.entry(score.symbol.clone()).or_insert(0.0) += part / total;
+    }
+    Ok(weights)
+}
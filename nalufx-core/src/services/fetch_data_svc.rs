@@ -1,14 +1,230 @@
-use chrono::{DateTime, Utc};
-use log::{error, info};
-use reqwest::Client;
-use std::error::Error;
+use crate::{
+    config::{history_lookback_days, is_offline_mode},
+    errors::NaluFxError,
+    services::{
+        synthetic_data_svc::{synthetic_dated_prices, synthetic_ohlcv, synthetic_prices},
+        yahoo_svc::{shared_client, YahooClient},
+    },
+    utils::{
+        batch::{run_batch, BatchOptions},
+        metrics::METRICS,
+    },
+};
+use chrono::{DateTime, Duration, Utc};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{error::Error, fmt};
 use yahoo_finance_api as yahoo;
 
+/// Represents the open, high, low and close prices and the volume of a trading session.
+///
+/// # Fields
+///
+/// * `timestamp` - The timestamp of the trading session.
+/// * `open` - The opening price.
+/// * `high` - The highest price.
+/// * `low` - The lowest price.
+/// * `close` - The closing price.
+/// * `volume` - The number of shares traded.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Ohlcv {
+    /// The timestamp of the trading session.
+    pub timestamp: DateTime<Utc>,
+    /// The opening price.
+    pub open: f64,
+    /// The highest price.
+    pub high: f64,
+    /// The lowest price.
+    pub low: f64,
+    /// The closing price.
+    pub close: f64,
+    /// The number of shares traded.
+    pub volume: f64,
+}
+
+/// Represents the interval between two bars of a Yahoo Finance price series.
+///
+/// Yahoo Finance only serves intraday bars for recent dates, so the `Minute` and `Hour`
+/// intervals are limited to a range of at most `max_range` (see [`Interval::validate_range`]).
+///
+/// # Variants
+///
+/// * `Minute` - One bar per minute (`1m`), for at most the last 7 days.
+/// * `Hour` - One bar per hour (`1h`), for at most the last 730 days.
+/// * `Day` - One bar per trading day (`1d`), the default.
+/// * `Week` - One bar per week (`1wk`).
+/// * `Month` - One bar per month (`1mo`).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::fetch_data_svc::Interval;
+///
+/// assert_eq!(Interval::default(), Interval::Day);
+/// assert_eq!(Interval::Week.as_str(), "1wk");
+/// assert_eq!(Interval::Month.to_string(), "1mo");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Interval {
+    /// One bar per minute.
+    Minute,
+    /// One bar per hour.
+    Hour,
+    /// One bar per trading day.
+    #[default]
+    Day,
+    /// One bar per week.
+    Week,
+    /// One bar per month.
+    Month,
+}
+
+impl Interval {
+    /// Returns the value of the `interval` query parameter of the Yahoo Finance chart API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interval::Minute => "1m",
+            Interval::Hour => "1h",
+            Interval::Day => "1d",
+            Interval::Week => "1wk",
+            Interval::Month => "1mo",
+        }
+    }
+
+    /// Returns the longest date range Yahoo Finance serves at this interval, or `None` if the
+    /// whole history is available.
+    pub fn max_range(&self) -> Option<Duration> {
+        match self {
+            Interval::Minute => Some(Duration::days(7)),
+            Interval::Hour => Some(Duration::days(730)),
+            Interval::Day | Interval::Week | Interval::Month => None,
+        }
+    }
+
+    /// Checks that Yahoo Finance serves this interval for a date range.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - An optional `DateTime<Utc>` representing the start date of the range,
+    ///   or `None` for the default lookback (see [`chart_url`]).
+    /// * `end_date` - An optional `DateTime<Utc>` representing the end date of the range, or
+    ///   `None` for the current date.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), NaluFxError>` - `Ok(())` if the interval is available for the range, or
+    ///   an `InvalidInterval` error if the range is longer than `max_range`, or has no start
+    ///   date while the interval has a `max_range`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use nalufx::services::fetch_data_svc::Interval;
+    ///
+    /// let end_date = Utc::now();
+    /// let start_date = end_date - Duration::days(30);
+    /// assert!(Interval::Day.validate_range(Some(start_date), Some(end_date)).is_ok());
+    /// assert!(Interval::Minute.validate_range(Some(start_date), Some(end_date)).is_err());
+    /// ```
+    pub fn validate_range(
+        &self,
+        start_date: Option<DateTime<Utc>>,
+        end_date: Option<DateTime<Utc>>,
+    ) -> Result<(), NaluFxError> {
+        let Some(max_range) = self.max_range() else {
+            return Ok(());
+        };
+        let Some(start_date) = start_date else {
+            return Err(NaluFxError::InvalidInterval(format!(
+                "{} data needs a start date, as it is only available for {} days",
+                self,
+                max_range.num_days()
+            )));
+        };
+
+        let range = end_date.unwrap_or_else(Utc::now) - start_date;
+        if range > max_range {
+            return Err(NaluFxError::InvalidInterval(format!(
+                "{} data is only available for {} days, but {} days were requested",
+                self,
+                max_range.num_days(),
+                range.num_days()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the number of bars in a trading day, or the fraction of a bar for intervals
+    /// longer than a day.
+    fn bars_per_trading_day(&self) -> f64 {
+        match self {
+            // Yahoo Finance splits the 6.5 hours of a session into 7 hourly bars.
+            Interval::Minute => 390.0,
+            Interval::Hour => 7.0,
+            Interval::Day => 1.0,
+            Interval::Week => 1.0 / 5.0,
+            Interval::Month => 1.0 / 21.0,
+        }
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Returns the URL of the Yahoo Finance chart request for a ticker symbol.
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol of the stock (e.g., "AAPL").
+/// * `start_date` - An optional `DateTime<Utc>` representing the start date for the data
+///   retrieval, or `None` for [`history_lookback_days`] before the end date.
+/// * `end_date` - An optional `DateTime<Utc>` representing the end date for the data retrieval,
+///   or `None` for the current date.
+/// * `interval` - The `Interval` between two bars.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::DateTime;
+/// use nalufx::services::fetch_data_svc::{chart_url, Interval};
+///
+/// let url = chart_url("AAPL", Some(DateTime::UNIX_EPOCH), None, Interval::Week);
+/// assert!(url.starts_with("https://query1.finance.yahoo.com/v8/finance/chart/AAPL?period1=0&"));
+/// assert!(url.ends_with("&interval=1wk"));
+/// ```
+pub fn chart_url(
+    ticker: &str,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    interval: Interval,
+) -> String {
+    let end_date = end_date.unwrap_or_else(Utc::now);
+    let start_date =
+        start_date.unwrap_or_else(|| end_date - Duration::days(i64::from(history_lookback_days())));
+    format!(
+        "https://query1.finance.yahoo.com/v8/finance/chart/{}?period1={}&period2={}&interval={}",
+        ticker,
+        start_date.timestamp(),
+        end_date.timestamp(),
+        interval
+    )
+}
+
 /// Fetches historical data for a given ticker symbol from Yahoo Finance.
 ///
 /// This asynchronous function retrieves historical closing prices for the specified ticker
-/// symbol within the optional date range provided. If no date range is specified, it fetches
-/// data from the earliest available date to the current date.
+/// symbol within the optional date range provided. If no start date is specified, it fetches
+/// the last [`history_lookback_days`] days up to the end date, which defaults to the current
+/// date. Use [`fetch_full_history`] to fetch every available price.
+///
+/// In offline mode (see [`is_offline_mode`]), no request is sent and a deterministic synthetic
+/// price series is returned instead, with one price per weekday in the date range, or in the
+/// last [`history_lookback_days`] days if no start date is given, as online.
 ///
 /// # Arguments
 ///
@@ -19,7 +235,9 @@
 /// # Returns
 ///
 /// This function returns a `Result` containing a vector of closing prices (`Vec<f64>`) if successful,
-/// or an error (`Box<dyn Error>`) if the data retrieval fails.
+/// or an error (`Box<dyn Error>`) if the data retrieval fails. If Yahoo Finance has no data for
+/// the ticker symbol, e.g. because it is delisted, the error is `NaluFxError::NoDataForTicker`
+/// (see [`is_no_data_error`]).
 ///
 /// # Examples
 ///
@@ -42,48 +260,589 @@ pub async fn fetch_data(
     start_date: Option<DateTime<Utc>>,
     end_date: Option<DateTime<Utc>>,
 ) -> Result<Vec<f64>, Box<dyn Error>> {
+    fetch_data_interval(ticker, start_date, end_date, Interval::Day).await
+}
+
+/// Fetches the whole history of closing prices for a given ticker symbol from Yahoo Finance.
+///
+/// This asynchronous function behaves like [`fetch_data`], but from the earliest available
+/// date rather than the last [`history_lookback_days`] days. The whole history of an old
+/// ticker symbol spans decades, so prefer [`fetch_data`] when only recent data is needed.
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol of the stock (e.g., "AAPL").
+///
+/// # Returns
+///
+/// The result of [`fetch_data`] from the earliest available date to the current date.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::fetch_data_svc::fetch_full_history;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     match fetch_full_history("AAPL").await {
+///         Ok(data) => println!("{} prices", data.len()),
+///         Err(e) => eprintln!("Error: {}", e),
+///     }
+/// }
+/// ```
+pub async fn fetch_full_history(ticker: &str) -> Result<Vec<f64>, Box<dyn Error>> {
+    fetch_data(ticker, Some(DateTime::UNIX_EPOCH), None).await
+}
+
+/// Fetches historical data at a given interval for a given ticker symbol from Yahoo Finance.
+///
+/// This asynchronous function behaves like [`fetch_data`], which fetches daily closing prices,
+/// but with one closing price per `interval`. Weekly or monthly bars suit long-horizon
+/// analyses and are far smaller to download. The interval is checked against the date range
+/// before any request is sent (see [`Interval::validate_range`]).
+///
+/// In offline mode (see [`is_offline_mode`]), no request is sent and a deterministic synthetic
+/// price series is returned instead, with as many prices as the interval has bars in the date
+/// range.
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol of the stock (e.g., "AAPL").
+/// * `start_date` - An optional `DateTime<Utc>` representing the start date for the data retrieval.
+/// * `end_date` - An optional `DateTime<Utc>` representing the end date for the data retrieval.
+/// * `interval` - The `Interval` between two closing prices.
+///
+/// # Returns
+///
+/// This function returns a `Result` containing a vector of closing prices (`Vec<f64>`) if successful,
+/// or an error (`Box<dyn Error>`) if the interval is not available for the date range
+/// (`NaluFxError::InvalidInterval`) or the data retrieval fails.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Utc;
+/// use nalufx::services::fetch_data_svc::{fetch_data_interval, Interval};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let start_date = Some(Utc::now() - chrono::Duration::days(5 * 365));
+///     match fetch_data_interval("AAPL", start_date, None, Interval::Month).await {
+///         Ok(data) => println!("Monthly data: {:?}", data),
+///         Err(e) => eprintln!("Error: {}", e),
+///     }
+/// }
+/// ```
+pub async fn fetch_data_interval(
+    ticker: &str,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    interval: Interval,
+) -> Result<Vec<f64>, Box<dyn Error>> {
+    interval.validate_range(start_date, end_date)?;
+
+    if is_offline_mode() {
+        let num_days = offline_num_days(start_date, end_date);
+        let num_bars =
+            ((num_days as f64 * interval.bars_per_trading_day()).round() as usize).max(2);
+        info!("Offline mode: generating {} synthetic prices for ticker: {}", num_bars, ticker);
+        return Ok(synthetic_prices(ticker, num_bars));
+    }
+
+    let quotes = fetch_quotes(ticker, start_date, end_date, interval).await?;
+    let closes: Vec<f64> = quotes.iter().map(|quote| quote.close).collect();
+    info!("Successfully parsed closing prices: {:?}", closes);
+    Ok(closes)
+}
+
+/// Fetches historical data for several ticker symbols, with a concurrency limit and progress
+/// reporting.
+///
+/// This asynchronous function calls [`fetch_data`] for each ticker symbol, with at most
+/// `options.concurrency()` requests in flight at the same time (see
+/// [`fetch_concurrency`](crate::config::fetch_concurrency) for the configured limit). The
+/// progress callback of `options`, if any, is called once per fetched ticker, whether or not
+/// the fetch succeeded.
+///
+/// # Arguments
+///
+/// * `tickers` - The ticker symbols of the stocks (e.g., `["AAPL", "MSFT"]`).
+/// * `start_date` - An optional `DateTime<Utc>` representing the start date for the data retrieval.
+/// * `end_date` - An optional `DateTime<Utc>` representing the end date for the data retrieval.
+/// * `options` - The concurrency limit and progress callback.
+///
+/// # Returns
+///
+/// The result of [`fetch_data`] for each ticker symbol, in the order of `tickers`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::fetch_data_svc::fetch_data_batch;
+/// use nalufx::utils::batch::BatchOptions;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let tickers = vec!["AAPL".to_string(), "MSFT".to_string()];
+///     let options = BatchOptions::new(2)
+///         .with_progress(|completed, total| eprintln!("{}/{} tickers fetched", completed, total));
+///     let results = fetch_data_batch(&tickers, None, None, &options).await;
+///     for (ticker, result) in tickers.iter().zip(results) {
+///         match result {
+///             Ok(data) => println!("{}: {} prices", ticker, data.len()),
+///             Err(e) => eprintln!("Error fetching data for {}: {}", ticker, e),
+///         }
+///     }
+/// }
+/// ```
+pub async fn fetch_data_batch(
+    tickers: &[String],
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    options: &BatchOptions,
+) -> Vec<Result<Vec<f64>, Box<dyn Error>>> {
+    run_batch(tickers, options, |ticker| fetch_data(ticker, start_date, end_date)).await
+}
+
+/// Fetches historical data with timestamps for a given ticker symbol from Yahoo Finance.
+///
+/// This asynchronous function behaves like [`fetch_data`], but pairs each closing price with
+/// the timestamp of its trading session, so that callers can detect gaps and duplicates in
+/// the series (see [`check_series_continuity`](crate::utils::date::check_series_continuity)).
+///
+/// In offline mode (see [`is_offline_mode`]), no request is sent and a deterministic synthetic
+/// price series dated on consecutive weekdays ending at `end_date` is returned instead.
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol of the stock (e.g., "AAPL").
+/// * `start_date` - An optional `DateTime<Utc>` representing the start date for the data retrieval.
+/// * `end_date` - An optional `DateTime<Utc>` representing the end date for the data retrieval.
+///
+/// # Returns
+///
+/// This function returns a `Result` containing a vector of `(timestamp, close)` pairs if
+/// successful, or a `NaluFxError` if the data retrieval fails.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Utc;
+/// use nalufx::services::fetch_data_svc::fetch_data_with_dates;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let start_date = Some(Utc::now() - chrono::Duration::days(30));
+///     match fetch_data_with_dates("AAPL", start_date, None).await {
+///         Ok(series) => println!("Series: {:?}", series),
+///         Err(e) => eprintln!("Error: {}", e),
+///     }
+/// }
+/// ```
+pub async fn fetch_data_with_dates(
+    ticker: &str,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+) -> Result<Vec<(DateTime<Utc>, f64)>, NaluFxError> {
+    if is_offline_mode() {
+        let num_days = offline_num_days(start_date, end_date);
+        info!("Offline mode: generating {} synthetic prices for ticker: {}", num_days, ticker);
+        return Ok(synthetic_dated_prices(ticker, num_days, end_date.unwrap_or_else(Utc::now)));
+    }
+
+    let quotes = fetch_quotes(ticker, start_date, end_date, Interval::Day)
+        .await
+        .map_err(|e| into_fetch_error(ticker, e))?;
+
+    quotes
+        .iter()
+        .map(|quote| {
+            DateTime::from_timestamp(quote.timestamp as i64, 0)
+                .map(|timestamp| (timestamp, quote.close))
+                .ok_or(NaluFxError::InvalidData)
+        })
+        .collect()
+}
+
+/// Fetches historical open, high, low, close and volume data for a given ticker symbol from
+/// Yahoo Finance.
+///
+/// This asynchronous function behaves like [`fetch_data_with_dates`], but returns every field
+/// of each trading session, for indicators that need the intraday range or the volume.
+///
+/// In offline mode (see [`is_offline_mode`]), no request is sent and a deterministic synthetic
+/// series dated on consecutive weekdays ending at `end_date` is returned instead. Its closing
+/// prices match those of [`fetch_data_with_dates`].
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol of the stock (e.g., "AAPL").
+/// * `start_date` - An optional `DateTime<Utc>` representing the start date for the data retrieval.
+/// * `end_date` - An optional `DateTime<Utc>` representing the end date for the data retrieval.
+///
+/// # Returns
+///
+/// This function returns a `Result` containing a vector of `Ohlcv` sessions in chronological
+/// order if successful, or a `NaluFxError` if the data retrieval fails.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Utc;
+/// use nalufx::services::fetch_data_svc::fetch_ohlcv_data;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let start_date = Some(Utc::now() - chrono::Duration::days(30));
+///     match fetch_ohlcv_data("AAPL", start_date, None).await {
+///         Ok(sessions) => println!("Sessions: {:?}", sessions),
+///         Err(e) => eprintln!("Error: {}", e),
+///     }
+/// }
+/// ```
+pub async fn fetch_ohlcv_data(
+    ticker: &str,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+) -> Result<Vec<Ohlcv>, NaluFxError> {
+    if is_offline_mode() {
+        let num_days = offline_num_days(start_date, end_date);
+        info!("Offline mode: generating {} synthetic sessions for ticker: {}", num_days, ticker);
+        return Ok(synthetic_ohlcv(ticker, num_days, end_date.unwrap_or_else(Utc::now)));
+    }
+
+    let quotes = fetch_quotes(ticker, start_date, end_date, Interval::Day)
+        .await
+        .map_err(|e| into_fetch_error(ticker, e))?;
+
+    quotes
+        .iter()
+        .map(|quote| {
+            DateTime::from_timestamp(quote.timestamp as i64, 0)
+                .map(|timestamp| Ohlcv {
+                    timestamp,
+                    open: quote.open,
+                    high: quote.high,
+                    low: quote.low,
+                    close: quote.close,
+                    volume: quote.volume as f64,
+                })
+                .ok_or(NaluFxError::InvalidData)
+        })
+        .collect()
+}
+
+/// Parses the dividend events of a Yahoo Finance chart response.
+///
+/// Yahoo Finance only includes the events when the chart is requested with `events=div`, and
+/// keys them by timestamp, so a response without dividends has no `events` object at all.
+///
+/// # Arguments
+///
+/// * `response` - The JSON body of a `v8/finance/chart` response.
+///
+/// # Returns
+///
+/// A `Result` containing the `(payment date, amount per share)` pairs in chronological order,
+/// which is empty if the security paid no dividend, or a `NaluFxError` if the response holds an
+/// error or no chart.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::fetch_data_svc::parse_dividends;
+/// use serde_json::json;
+///
+/// let response = json!({
+///     "chart": {
+///         "result": [{
+///             "events": {
+///                 "dividends": {
+///                     "1715347800": { "amount": 0.25, "date": 1715347800 },
+///                     "1707489000": { "amount": 0.24, "date": 1707489000 }
+///                 }
+///             }
+///         }],
+///         "error": null
+///     }
+/// });
+/// let dividends = parse_dividends(&response).unwrap();
+/// assert_eq!(dividends.len(), 2);
+/// assert_eq!(dividends[0].1, 0.24);
+/// ```
+pub fn parse_dividends(response: &Value) -> Result<Vec<(DateTime<Utc>, f64)>, NaluFxError> {
+    let chart = &response["chart"];
+    if let Some(error) = chart["error"].as_object() {
+        let description = error.get("description").and_then(Value::as_str).unwrap_or("unknown");
+        return Err(NaluFxError::NaluFxError(format!("chart error: {}", description)));
+    }
+
+    let result = chart["result"]
+        .as_array()
+        .and_then(|result| result.first())
+        .ok_or(NaluFxError::InvalidData)?;
+    let Some(events) = result["events"]["dividends"].as_object() else {
+        return Ok(Vec::new());
+    };
+
+    let mut dividends = events
+        .values()
+        .map(|event| {
+            let date = event["date"].as_i64().and_then(|date| DateTime::from_timestamp(date, 0));
+            let amount = event["amount"].as_f64().filter(|amount| amount.is_finite());
+            date.zip(amount).ok_or(NaluFxError::InvalidData)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    dividends.sort_by_key(|(date, _)| *date);
+    Ok(dividends)
+}
+
+/// Fetches the dividends paid by a given ticker symbol from Yahoo Finance.
+///
+/// In offline mode (see [`is_offline_mode`]), no request is sent and no dividend is returned,
+/// since the synthetic price series do not model dividends.
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol of the stock (e.g., "AAPL").
+/// * `start_date` - An optional `DateTime<Utc>` representing the start date for the data retrieval.
+/// * `end_date` - An optional `DateTime<Utc>` representing the end date for the data retrieval.
+///
+/// # Returns
+///
+/// A `Result` containing the `(payment date, amount per share)` pairs in chronological order,
+/// or a `NaluFxError` if the request fails or the response cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Utc;
+/// use nalufx::services::fetch_data_svc::fetch_dividends;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let start_date = Some(Utc::now() - chrono::Duration::days(365));
+///     match fetch_dividends("AAPL", start_date, None).await {
+///         Ok(dividends) => println!("Dividends: {:?}", dividends),
+///         Err(e) => eprintln!("Error: {}", e),
+///     }
+/// }
+/// ```
+pub async fn fetch_dividends(
+    ticker: &str,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+) -> Result<Vec<(DateTime<Utc>, f64)>, NaluFxError> {
+    if is_offline_mode() {
+        info!("Offline mode: no dividends available for ticker: {}", ticker);
+        return Ok(Vec::new());
+    }
+
+    let dividends = request_dividends(ticker, start_date, end_date).await;
+    if dividends.is_err() {
+        METRICS.record_fetch_failure();
+    }
+    dividends
+}
+
+/// Sends the Yahoo Finance chart request for the dividend events of a ticker symbol.
+async fn request_dividends(
+    ticker: &str,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+) -> Result<Vec<(DateTime<Utc>, f64)>, NaluFxError> {
+    info!("Attempting to fetch dividends for ticker: {}", ticker);
+
+    let client = shared_client()?;
+
+    let url = format!("{}&events=div", chart_url(ticker, start_date, end_date, Interval::Day));
+    let response = client.get(&url).await?;
+    if !response.status().is_success() {
+        error!("Dividend request for {} failed with status: {}", ticker, response.status());
+        return Err(NaluFxError::NaluFxError(format!(
+            "Failed to fetch dividends for {}: {}",
+            ticker,
+            response.status()
+        )));
+    }
+
+    parse_dividends(&response.json::<Value>().await?)
+}
+
+/// Fetches the raw Yahoo Finance quotes at an interval for a ticker symbol within the optional
+/// date range.
+///
+/// Failures are counted in the `nalufx_fetch_failures_total` metric.
+async fn fetch_quotes(
+    ticker: &str,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    interval: Interval,
+) -> Result<Vec<yahoo::Quote>, Box<dyn Error>> {
+    let quotes = request_quotes(ticker, start_date, end_date, interval).await;
+    if quotes.is_err() {
+        METRICS.record_fetch_failure();
+    }
+    quotes
+}
+
+/// Sends the Yahoo Finance chart request for a ticker symbol and parses the quotes.
+async fn request_quotes(
+    ticker: &str,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    interval: Interval,
+) -> Result<Vec<yahoo::Quote>, Box<dyn Error>> {
     info!("Attempting to fetch data for ticker: {}", ticker);
 
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36")
-        .build()?;
-
-    let start_date = start_date.map_or(0, |date| date.timestamp());
-    let end_date = end_date.map_or(Utc::now().timestamp(), |date| date.timestamp());
-
-    let url = format!(
-        "https://query1.finance.yahoo.com/v8/finance/chart/{}?period1={}&period2={}&interval=1d",
-        ticker, start_date, end_date
-    );
-
-    match client.get(&url).send().await {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.json::<yahoo::YResponse>().await {
-                    Ok(yresponse) => match yresponse.quotes() {
-                        Ok(quotes) => {
-                            let closes: Vec<f64> = quotes.iter().map(|quote| quote.close).collect();
-                            info!("Successfully parsed closing prices: {:?}", closes);
-                            Ok(closes)
-                        },
-                        Err(e) => {
-                            error!("Failed to parse quotes for ticker {}: {}", ticker, e);
-                            Err(Box::new(e))
-                        },
-                    },
-                    Err(e) => {
-                        error!("Failed to parse response JSON for ticker {}: {}", ticker, e);
-                        Err(Box::new(e))
-                    },
-                }
-            } else {
-                error!("Request failed with status: {}", response.status().to_string());
-                Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, "Request failed")))
-            }
-        },
-        Err(e) => {
-            error!("Failed to send request for ticker {}: {}", ticker, e);
-            Err(Box::new(e))
+    let client = shared_client()?;
+
+    let url = chart_url(ticker, start_date, end_date, interval);
+    fetch_chart_quotes(client, ticker, &url).await
+}
+
+/// Sends a Yahoo Finance chart request and parses the quotes of its response.
+///
+/// A response without data for the ticker symbol is reported as
+/// `NaluFxError::NoDataForTicker` (see [`is_empty_chart`]), whatever its status, so that it
+/// can be told apart from a failed request.
+///
+/// # Arguments
+///
+/// * `client` - The `YahooClient` that sends the request.
+/// * `ticker` - A string slice that holds the ticker symbol of the stock (e.g., "AAPL").
+/// * `url` - The URL of the chart request (see [`chart_url`]).
+///
+/// # Returns
+///
+/// A `Result` containing the quotes in chronological order, or an error (`Box<dyn Error>`):
+/// `NaluFxError::NoDataForTicker` if the response holds no data for the ticker symbol,
+/// `NaluFxError::HttpRequestError` if the request cannot be sent, or another error if the
+/// request fails or the response cannot be parsed.
+pub async fn fetch_chart_quotes(
+    client: &YahooClient,
+    ticker: &str,
+    url: &str,
+) -> Result<Vec<yahoo::Quote>, Box<dyn Error>> {
+    let response = client.get(url).await.map_err(|e| {
+        error!("Failed to send request for ticker {}: {}", ticker, e);
+        e
+    })?;
+    let status = response.status();
+    let body = response.text().await.map_err(|e| {
+        error!("Failed to read the response for ticker {}: {}", ticker, e);
+        e
+    })?;
+
+    if serde_json::from_str::<Value>(&body).is_ok_and(|chart| is_empty_chart(&chart)) {
+        warn!("Yahoo Finance has no data for ticker {}", ticker);
+        return Err(Box::new(NaluFxError::NoDataForTicker(ticker.to_string())));
+    }
+    if !status.is_success() {
+        error!("Request failed with status: {}", status);
+        return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, "Request failed")));
+    }
+
+    let yresponse = serde_json::from_str::<yahoo::YResponse>(&body).map_err(|e| {
+        error!("Failed to parse response JSON for ticker {}: {}", ticker, e);
+        e
+    })?;
+    let quotes = yresponse.quotes().map_err(|e| {
+        error!("Failed to parse quotes for ticker {}: {}", ticker, e);
+        e
+    })?;
+    if quotes.is_empty() {
+        warn!("Yahoo Finance has no quotes for ticker {}", ticker);
+        return Err(Box::new(NaluFxError::NoDataForTicker(ticker.to_string())));
+    }
+    Ok(quotes)
+}
+
+/// Returns whether a Yahoo Finance chart response holds no data for its ticker symbol.
+///
+/// Yahoo Finance answers a request for a delisted or unknown ticker symbol either with an
+/// empty `chart.result`, or with a `Not Found` chart error. A result without timestamps, as
+/// returned for a ticker symbol that has not traded in the date range, holds no data either.
+///
+/// # Arguments
+///
+/// * `response` - The JSON body of a `v8/finance/chart` response.
+///
+/// # Returns
+///
+/// `true` if the response is a chart without data, and `false` if it has data, holds another
+/// error, or is not a chart response at all.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::fetch_data_svc::is_empty_chart;
+/// use serde_json::json;
+///
+/// assert!(is_empty_chart(&json!({ "chart": { "result": [], "error": null } })));
+/// assert!(!is_empty_chart(&json!({ "chart": { "result": [{ "timestamp": [1] }] } })));
+/// assert!(!is_empty_chart(&json!({ "error": "Too Many Requests" })));
+/// ```
+pub fn is_empty_chart(response: &Value) -> bool {
+    let chart = &response["chart"];
+    if !chart.is_object() {
+        return false;
+    }
+    if let Some(code) = chart["error"]["code"].as_str() {
+        return code.eq_ignore_ascii_case("Not Found");
+    }
+    match chart["result"].as_array().and_then(|result| result.first()) {
+        Some(result) => {
+            result["timestamp"].as_array().filter(|timestamps| !timestamps.is_empty()).is_none()
         },
+        None => true,
     }
 }
+
+/// Returns whether an error returned by the fetch functions means that the data source has no
+/// data for the ticker symbol (`NaluFxError::NoDataForTicker`), rather than that the request
+/// failed.
+///
+/// # Arguments
+///
+/// * `error` - The error returned by a fetch function.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::errors::NaluFxError;
+/// use nalufx::services::fetch_data_svc::is_no_data_error;
+/// use std::error::Error;
+///
+/// let error: Box<dyn Error> = Box::new(NaluFxError::NoDataForTicker("XYZ".to_string()));
+/// assert!(is_no_data_error(error.as_ref()));
+/// assert!(!is_no_data_error(&NaluFxError::InvalidData));
+/// ```
+pub fn is_no_data_error(error: &(dyn Error + 'static)) -> bool {
+    matches!(error.downcast_ref::<NaluFxError>(), Some(NaluFxError::NoDataForTicker(_)))
+}
+
+/// Converts an error of `fetch_quotes` into a `NaluFxError`, keeping
+/// `NaluFxError::NoDataForTicker` apart from the other errors.
+fn into_fetch_error(ticker: &str, error: Box<dyn Error>) -> NaluFxError {
+    if is_no_data_error(error.as_ref()) {
+        return NaluFxError::NoDataForTicker(ticker.to_string());
+    }
+    NaluFxError::NaluFxError(format!("Failed to fetch data for {}: {}", ticker, error))
+}
+
+/// Returns the number of synthetic prices to generate for the given date range in offline mode,
+/// one per weekday.
+///
+/// Without a start date, the range covers the last [`history_lookback_days`] days, the same
+/// history as an online fetch.
+fn offline_num_days(start_date: Option<DateTime<Utc>>, end_date: Option<DateTime<Utc>>) -> usize {
+    let calendar_days = match start_date {
+        Some(start) => {
+            let end = end_date.unwrap_or_else(Utc::now);
+            (end - start).num_days().max(0) as usize
+        },
+        None => history_lookback_days() as usize,
+    };
+    (calendar_days * 5 / 7).max(2)
+}
@@ -0,0 +1,175 @@
+use crate::{config::is_offline_mode, errors::NaluFxError, services::yahoo_svc::YahooClient};
+use log::{info, warn};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The Yahoo Finance `quoteSummary` endpoint, followed by the ticker symbol.
+const QUOTE_SUMMARY_URL: &str = "https://query2.finance.yahoo.com/v10/finance/quoteSummary";
+
+/// The Yahoo Finance `quoteSummary` modules that hold the fundamentals.
+const QUOTE_SUMMARY_MODULES: &str = "defaultKeyStatistics,financialData,summaryDetail";
+
+/// Represents the fundamental data of a stock.
+///
+/// Yahoo Finance omits fundamentals that do not apply to a security (e.g. the P/E ratio of a
+/// loss-making company or the book value of an ETF), so every field is optional.
+///
+/// # Fields
+///
+/// * `trailing_pe` - The trailing twelve-month price-to-earnings ratio.
+/// * `price_to_book` - The price-to-book ratio.
+/// * `return_on_equity` - The return on equity, as a fraction (e.g. `0.25` for 25%).
+/// * `debt_to_equity` - The debt-to-equity ratio, as a fraction (e.g. `1.5` for 150%).
+/// * `market_cap` - The market capitalization, in the currency of the quote.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Fundamentals {
+    /// The trailing twelve-month price-to-earnings ratio.
+    pub trailing_pe: Option<f64>,
+    /// The price-to-book ratio.
+    pub price_to_book: Option<f64>,
+    /// The return on equity, as a fraction.
+    pub return_on_equity: Option<f64>,
+    /// The debt-to-equity ratio, as a fraction.
+    pub debt_to_equity: Option<f64>,
+    /// The market capitalization, in the currency of the quote.
+    pub market_cap: Option<f64>,
+}
+
+/// Parses the fundamentals from a Yahoo Finance `quoteSummary` response.
+///
+/// The response must include the `defaultKeyStatistics`, `financialData` and `summaryDetail`
+/// modules. A field that is missing, empty (`{}`) or not finite is left as `None`. Yahoo
+/// reports the debt-to-equity ratio as a percentage, so it is divided by 100.
+///
+/// # Arguments
+///
+/// * `response` - The `quoteSummary` response (`&Value`).
+///
+/// # Returns
+///
+/// A `Result` containing the `Fundamentals`, or a `NaluFxError` if the response reports an
+/// error or has no result.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::fundamentals_svc::parse_quote_summary;
+/// use serde_json::json;
+///
+/// let response = json!({
+///     "quoteSummary": {
+///         "result": [{
+///             "summaryDetail": { "trailingPE": { "raw": 28.5, "fmt": "28.50" } },
+///             "financialData": { "debtToEquity": { "raw": 150.0, "fmt": "150.00%" } }
+///         }],
+///         "error": null
+///     }
+/// });
+/// let fundamentals = parse_quote_summary(&response).unwrap();
+/// assert_eq!(fundamentals.trailing_pe, Some(28.5));
+/// assert_eq!(fundamentals.debt_to_equity, Some(1.5));
+/// assert_eq!(fundamentals.price_to_book, None);
+/// ```
+pub fn parse_quote_summary(response: &Value) -> Result<Fundamentals, NaluFxError> {
+    let summary = &response["quoteSummary"];
+    if let Some(error) = summary["error"].as_object() {
+        let description = error.get("description").and_then(Value::as_str).unwrap_or("unknown");
+        return Err(NaluFxError::NaluFxError(format!("quoteSummary error: {}", description)));
+    }
+
+    let result = summary["result"]
+        .as_array()
+        .and_then(|result| result.first())
+        .ok_or(NaluFxError::InvalidData)?;
+    let raw = |module: &str, field: &str| -> Option<f64> {
+        result[module][field]["raw"].as_f64().filter(|value| value.is_finite())
+    };
+
+    Ok(Fundamentals {
+        trailing_pe: raw("summaryDetail", "trailingPE"),
+        price_to_book: raw("defaultKeyStatistics", "priceToBook"),
+        return_on_equity: raw("financialData", "returnOnEquity"),
+        debt_to_equity: raw("financialData", "debtToEquity").map(|ratio| ratio / 100.0),
+        market_cap: raw("summaryDetail", "marketCap"),
+    })
+}
+
+/// Builds the Yahoo Finance `quoteSummary` URL of the fundamentals of a ticker symbol.
+///
+/// The ticker is percent-encoded as a single path segment, so that a ticker with a `/` or a
+/// space cannot change the path of the request.
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol of the stock (e.g., "AAPL").
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::fundamentals_svc::quote_summary_url;
+///
+/// let url = quote_summary_url("BRK/B");
+/// assert!(url.starts_with("https://query2.finance.yahoo.com/v10/finance/quoteSummary/BRK%2FB?"));
+/// ```
+pub fn quote_summary_url(ticker: &str) -> String {
+    let mut url = Url::parse(QUOTE_SUMMARY_URL).expect("QUOTE_SUMMARY_URL is a valid URL");
+    let _ = url
+        .path_segments_mut()
+        .expect("QUOTE_SUMMARY_URL is an HTTP URL, which has a path")
+        .push(ticker);
+    url.set_query(Some(&format!("modules={}", QUOTE_SUMMARY_MODULES)));
+    url.into()
+}
+
+/// Fetches the fundamentals for a given ticker symbol from Yahoo Finance.
+///
+/// In offline mode (see [`is_offline_mode`]), no request is sent and every fundamental is
+/// `None`, since there is no meaningful synthetic equivalent.
+///
+/// # Arguments
+///
+/// * `client` - The `YahooClient` used to send the request (see
+///   [`shared_client`](crate::services::yahoo_svc::shared_client)).
+/// * `ticker` - A string slice that holds the ticker symbol of the stock (e.g., "AAPL").
+///
+/// # Returns
+///
+/// A `Result` containing the `Fundamentals`, or a `NaluFxError` if the request fails or the
+/// response cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::{fundamentals_svc::fetch_fundamentals, yahoo_svc::shared_client};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = shared_client().unwrap();
+///     match fetch_fundamentals(client, "AAPL").await {
+///         Ok(fundamentals) => println!("Fundamentals: {:?}", fundamentals),
+///         Err(e) => eprintln!("Error: {}", e),
+///     }
+/// }
+/// ```
+pub async fn fetch_fundamentals(
+    client: &YahooClient,
+    ticker: &str,
+) -> Result<Fundamentals, NaluFxError> {
+    if is_offline_mode() {
+        info!("Offline mode: no fundamentals available for ticker: {}", ticker);
+        return Ok(Fundamentals::default());
+    }
+
+    let response = client.get(&quote_summary_url(ticker)).await?;
+    if !response.status().is_success() {
+        warn!("quoteSummary request for {} failed with status: {}", ticker, response.status());
+        return Err(NaluFxError::NaluFxError(format!(
+            "Failed to fetch fundamentals for {}: {}",
+            ticker,
+            response.status()
+        )));
+    }
+
+    parse_quote_summary(&response.json::<Value>().await?)
+}
@@ -1,16 +1,76 @@
 /// This module will return errors if the automated cash allocation process fails due to insufficient data for analysis, mathematical errors, or invalid input data.
 pub mod automated_cash_allocation_svc;
 
+/// This module backtests daily allocations by walking forward through historical prices.
+pub mod backtest_svc;
+
 /// This module will return errors if the bellwether stock analysis process fails due to insufficient data for analysis, mathematical errors, or invalid input data.
 pub mod bellwether_stock_analysis_svc;
 
+/// This module will return errors if the cointegration test receives empty, too short, or
+/// invalid price series.
+pub mod cointegration_svc;
+
+/// This module measures how concentrated an allocation is.
+pub mod concentration_svc;
+
+/// This module summarizes the quality of a fetched price series before it is analyzed.
+pub mod data_quality_svc;
+
 /// This module will return errors if the diversified ETF portfolio optimization process fails due to insufficient data for analysis, mathematical errors, or invalid input data.
 pub mod diversified_etf_portfolio_optimization_svc;
 
+/// This module will return errors if the ESG ratings cannot be loaded from the configured
+/// source or if no rating is available for a ticker symbol.
+pub mod esg_svc;
+
+/// This module will return errors if the factor weights are invalid or the stocks to rank
+/// have missing factor values.
+pub mod factor_svc;
+
 /// This module will return errors if the data fetching process fails due to
 /// network issues, invalid ticker symbols, or issues with the data source API.
 pub mod fetch_data_svc;
 
+/// This module will return errors if the fundamentals cannot be fetched from Yahoo Finance or
+/// the `quoteSummary` response reports an error.
+pub mod fundamentals_svc;
+
+/// This module simulates the distribution of portfolio outcomes with Monte Carlo paths, and
+/// estimates the confidence intervals of metrics by bootstrap resampling.
+pub mod monte_carlo_svc;
+
+/// This module will return errors if the news headlines cannot be fetched from the news
+/// source.
+pub mod news_svc;
+
+/// This module will return errors if the pairs-trading signal generation fails due to
+/// invalid z-score thresholds.
+pub mod pairs_trading_svc;
+
+/// This module compares the performance of a stock over two periods, and will return errors
+/// if a period is invalid or neither period has data.
+pub mod period_comparison_svc;
+
 /// This module will return errors if the data processing tasks fail due to
-/// invalid input data, mathematical errors, or insufficient data for analysis.
+/// invalid input data, mismatched input lengths, mathematical errors, or insufficient
+/// data for analysis.
 pub mod processing_svc;
+
+/// This module analyzes how sensitive an allocation is to shifts of its daily returns.
+pub mod sensitivity_svc;
+
+/// This module will return errors if the sentiment scores cannot be fetched from the news
+/// source or the LLM, or the response cannot be parsed.
+pub mod sentiment_svc;
+
+/// This module compares the in-sample performance of portfolio construction strategies on the
+/// same universe.
+pub mod strategy_comparison_svc;
+
+/// This module generates deterministic synthetic market data for offline mode.
+pub mod synthetic_data_svc;
+
+/// This module will return errors if the Yahoo Finance HTTP client cannot be built or a
+/// request cannot be sent.
+pub mod yahoo_svc;
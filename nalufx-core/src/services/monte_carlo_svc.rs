@@ -0,0 +1,158 @@
+use crate::services::synthetic_data_svc::standard_normal;
+use crate::utils::stats::{percentile_with_policy, NanPolicy};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// The number of paths simulated for a report when the caller has no preference.
+pub const DEFAULT_SIMULATION_PATHS: usize = 1_000;
+
+/// The seed used for report simulations, so that the same analysis prints the same range.
+pub const DEFAULT_SIMULATION_SEED: u64 = 42;
+
+/// The confidence level of the intervals estimated by `bootstrap_metric`.
+pub const BOOTSTRAP_CONFIDENCE: f64 = 0.95;
+
+/// Represents the distribution of terminal portfolio values from a Monte Carlo simulation.
+///
+/// # Fields
+///
+/// * `percentile_5` - The 5th percentile of the terminal values.
+/// * `median` - The 50th percentile of the terminal values.
+/// * `percentile_95` - The 95th percentile of the terminal values.
+/// * `mean` - The average of the terminal values.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulationResult {
+    /// The 5th percentile of the terminal values.
+    pub percentile_5: f64,
+    /// The 50th percentile of the terminal values.
+    pub median: f64,
+    /// The 95th percentile of the terminal values.
+    pub percentile_95: f64,
+    /// The average of the terminal values.
+    pub mean: f64,
+}
+
+/// Simulates the value of a portfolio over a number of days with a Monte Carlo simulation.
+///
+/// Each path starts at `initial` and, every day, compounds a return drawn from a normal
+/// distribution with mean `mean_return` and standard deviation `volatility`. A portfolio
+/// cannot lose more than its whole value, so a path that reaches zero stays at zero. The
+/// random generator is seeded with `seed`, so the same arguments always produce the same
+/// result. With zero volatility, every path ends at `initial * (1 + mean_return)^days`.
+///
+/// # Arguments
+///
+/// * `initial` - The initial value of the portfolio (`f64`).
+/// * `mean_return` - The mean daily return, as a fraction (`f64`).
+/// * `volatility` - The standard deviation of the daily return, as a fraction (`f64`).
+/// * `days` - The number of days to simulate (`usize`).
+/// * `paths` - The number of paths to simulate (`usize`). At least one path is simulated.
+/// * `seed` - The seed of the random generator (`u64`).
+///
+/// # Returns
+///
+/// The `SimulationResult` with the percentile bands of the terminal values.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::monte_carlo_svc::simulate_portfolio;
+///
+/// let result = simulate_portfolio(1000.0, 0.0005, 0.01, 252, 500, 7);
+/// assert!(result.percentile_5 < result.median && result.median < result.percentile_95);
+/// assert_eq!(result, simulate_portfolio(1000.0, 0.0005, 0.01, 252, 500, 7));
+/// ```
+pub fn simulate_portfolio(
+    initial: f64,
+    mean_return: f64,
+    volatility: f64,
+    days: usize,
+    paths: usize,
+    seed: u64,
+) -> SimulationResult {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let terminal_values: Vec<f64> = (0..paths.max(1))
+        .map(|_| {
+            (0..days).fold(initial, |value, _| {
+                let daily_return = mean_return + volatility * standard_normal(&mut rng);
+                (value * (1.0 + daily_return)).max(0.0)
+            })
+        })
+        .collect();
+
+    // The values are never empty and the percentiles are in range, so only a NaN input fails.
+    let percentile = |p: f64| {
+        percentile_with_policy(&terminal_values, p, NanPolicy::Propagate).unwrap_or(f64::NAN)
+    };
+    SimulationResult {
+        percentile_5: percentile(5.0),
+        median: percentile(50.0),
+        percentile_95: percentile(95.0),
+        mean: terminal_values.iter().sum::<f64>() / terminal_values.len() as f64,
+    }
+}
+
+/// Estimates a metric of a return series with a bootstrap confidence interval.
+///
+/// Each resample draws as many returns as the series has, with replacement, and the metric is
+/// calculated on every resample. The bounds of the interval are the percentiles of these
+/// estimates that leave `(1 - BOOTSTRAP_CONFIDENCE) / 2` of them on each side, the 2.5th and
+/// 97.5th percentiles for a 95% interval. Resamples whose metric is `NaN`, e.g. a Sharpe ratio
+/// of constant returns, are left out. The random generator is seeded with `seed`, so the same
+/// arguments always produce the same interval.
+///
+/// Drawing returns independently ignores their autocorrelation, so the interval shows how much
+/// the metric depends on the particular days observed rather than a forecast.
+///
+/// # Arguments
+///
+/// * `returns` - A slice of daily returns (`&[f64]`).
+/// * `metric` - The metric to estimate, e.g.
+///   [`calculate_sharpe_ratio`](crate::utils::risk::calculate_sharpe_ratio).
+/// * `resamples` - The number of resamples (`usize`). At least one resample is drawn.
+/// * `seed` - The seed of the random generator (`u64`).
+///
+/// # Returns
+///
+/// The `(point, lower, upper)` tuple, where `point` is the metric of `returns` itself, and
+/// `lower` and `upper` are the bounds of the interval, `NaN` if `returns` is empty or no
+/// resample has a defined metric.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::monte_carlo_svc::bootstrap_metric;
+/// use nalufx::utils::risk::calculate_sharpe_ratio;
+///
+/// let returns = [0.01, -0.005, 0.02, 0.003, -0.01, 0.015, 0.007, -0.002];
+/// let (sharpe, lower, upper) = bootstrap_metric(&returns, calculate_sharpe_ratio, 500, 7);
+/// assert!(lower < sharpe && sharpe < upper);
+/// ```
+pub fn bootstrap_metric(
+    returns: &[f64],
+    metric: impl Fn(&[f64]) -> f64,
+    resamples: usize,
+    seed: u64,
+) -> (f64, f64, f64) {
+    let point = metric(returns);
+    if returns.is_empty() {
+        return (point, f64::NAN, f64::NAN);
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut sample = vec![0.0; returns.len()];
+    let estimates: Vec<f64> = (0..resamples.max(1))
+        .map(|_| {
+            for value in sample.iter_mut() {
+                *value = returns[rng.gen_range(0..returns.len())];
+            }
+            metric(&sample)
+        })
+        .collect();
+
+    let tail = (1.0 - BOOTSTRAP_CONFIDENCE) / 2.0 * 100.0;
+    let percentile =
+        |p: f64| percentile_with_policy(&estimates, p, NanPolicy::Skip).unwrap_or(f64::NAN);
+    (point, percentile(tail), percentile(100.0 - tail))
+}
@@ -0,0 +1,203 @@
+use crate::{
+    config::is_offline_mode,
+    errors::NaluFxError,
+    services::sentiment_svc::{DEFAULT_NEWS_API_URL, NEWS_API_KEY_ENV, NEWS_API_URL_ENV},
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use log::info;
+use reqwest::{header::USER_AGENT, Client};
+use serde::{Deserialize, Serialize};
+use std::{env, fmt};
+
+/// The largest number of headlines requested from NewsAPI, which is the largest page it serves.
+pub const MAX_HEADLINES: usize = 100;
+
+/// Represents a dated news headline, to cite the events behind a price move.
+///
+/// # Fields
+///
+/// * `published_at` - The timestamp at which the article was published.
+/// * `title` - The headline of the article.
+/// * `source` - The name of the publication, if known.
+/// * `url` - The address of the article, if known.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Headline {
+    /// The timestamp at which the article was published.
+    pub published_at: DateTime<Utc>,
+    /// The headline of the article.
+    pub title: String,
+    /// The name of the publication.
+    pub source: Option<String>,
+    /// The address of the article.
+    pub url: Option<String>,
+}
+
+impl fmt::Display for Headline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.published_at.format("%Y-%m-%d"), self.title)?;
+        if let Some(source) = &self.source {
+            write!(f, " ({})", source)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NewsApiResponse {
+    #[serde(default)]
+    articles: Vec<NewsApiArticle>,
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NewsApiArticle {
+    title: Option<String>,
+    published_at: DateTime<Utc>,
+    source: Option<NewsApiSource>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewsApiSource {
+    name: Option<String>,
+}
+
+/// A client of the [NewsAPI](https://newsapi.org) `everything` endpoint.
+#[derive(Clone, Debug)]
+pub struct NewsApiClient {
+    client: Client,
+    base_url: String,
+    api_key: String,
+}
+
+impl NewsApiClient {
+    /// Creates a new `NewsApiClient` for the given base URL and API key.
+    pub fn new(client: Client, base_url: &str, api_key: &str) -> Self {
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    /// Creates the `NewsApiClient` configured by the environment.
+    ///
+    /// The client uses the key in `NEWS_API_KEY` and the base URL in `NEWS_API_URL`, or
+    /// [`DEFAULT_NEWS_API_URL`] if it is unset.
+    ///
+    /// # Returns
+    ///
+    /// The client, or `None` if `NEWS_API_KEY` is unset or blank, or offline mode is on.
+    pub fn from_env() -> Option<Self> {
+        let api_key = env::var(NEWS_API_KEY_ENV).ok().filter(|key| !key.trim().is_empty())?;
+        if is_offline_mode() {
+            return None;
+        }
+        let url = env::var(NEWS_API_URL_ENV).unwrap_or_else(|_| DEFAULT_NEWS_API_URL.to_string());
+        Some(Self::new(Client::new(), &url, api_key.trim()))
+    }
+
+    /// Fetches the headlines of the articles mentioning a ticker symbol over a period.
+    ///
+    /// The client issues a `GET {base_url}/v2/everything` request for up to
+    /// [`MAX_HEADLINES`] articles in English. Articles without a title are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `ticker` - The ticker symbol to search for, in any case.
+    /// * `from` - The timestamp of the oldest article.
+    /// * `to` - The timestamp of the newest article.
+    ///
+    /// # Returns
+    ///
+    /// The headlines, from the oldest to the newest.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NaluFxError::HttpRequestError` if the request cannot be sent or the response
+    /// cannot be parsed, or `NaluFxError::NewsApiError` if NewsAPI responds with an error.
+    pub async fn fetch_headlines(
+        &self,
+        ticker: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Headline>, NaluFxError> {
+        let url = format!("{}/v2/everything", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .header("X-Api-Key", &self.api_key)
+            .header(USER_AGENT, concat!("nalufx/", env!("CARGO_PKG_VERSION")))
+            .query(&[
+                ("q", ticker.trim().to_uppercase()),
+                ("from", from.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                ("to", to.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                ("language", "en".to_string()),
+                ("sortBy", "publishedAt".to_string()),
+                ("pageSize", MAX_HEADLINES.to_string()),
+            ])
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body: NewsApiResponse = response.json().await?;
+        if !status.is_success() {
+            return Err(NaluFxError::NewsApiError(format!(
+                "NewsAPI request for {} failed with status {}: {}",
+                ticker,
+                status,
+                body.message.unwrap_or_default()
+            )));
+        }
+
+        let mut headlines: Vec<Headline> = body
+            .articles
+            .into_iter()
+            .filter_map(|article| {
+                Some(Headline {
+                    published_at: article.published_at,
+                    title: article.title?,
+                    source: article.source.and_then(|source| source.name),
+                    url: article.url,
+                })
+            })
+            .collect();
+        headlines.sort_by_key(|headline| headline.published_at);
+        Ok(headlines)
+    }
+}
+
+/// Fetches the recent headlines mentioning a ticker symbol from the news source configured in
+/// the environment.
+///
+/// The headlines are fetched with the `NewsApiClient` of [`NewsApiClient::from_env`]. Without
+/// a NewsAPI key, or in offline mode, no headlines are available, so an empty list is returned
+/// rather than an error, and the analysis goes on without them.
+///
+/// # Arguments
+///
+/// * `ticker` - The ticker symbol to search for, in any case.
+/// * `from` - The timestamp of the oldest headline.
+/// * `to` - The timestamp of the newest headline.
+///
+/// # Returns
+///
+/// The headlines, from the oldest to the newest.
+///
+/// # Errors
+///
+/// Returns the errors of [`NewsApiClient::fetch_headlines`].
+pub async fn fetch_recent_headlines(
+    ticker: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Headline>, NaluFxError> {
+    match NewsApiClient::from_env() {
+        Some(client) => client.fetch_headlines(ticker, from, to).await,
+        None => {
+            info!("{} is not set, no headlines are fetched for {}", NEWS_API_KEY_ENV, ticker);
+            Ok(Vec::new())
+        },
+    }
+}
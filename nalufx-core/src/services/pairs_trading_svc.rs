@@ -0,0 +1,146 @@
+use crate::errors::NaluFxError;
+
+/// Represents the position suggested for a pair's spread on a given day.
+///
+/// # Variants
+///
+/// * `LongSpread` - Buy the spread (long the first leg, short the second).
+/// * `ShortSpread` - Sell the spread (short the first leg, long the second).
+/// * `Flat` - Hold no position in the pair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PairSignal {
+    /// Buy the spread (long the first leg, short the second).
+    LongSpread,
+    /// Sell the spread (short the first leg, long the second).
+    ShortSpread,
+    /// Hold no position in the pair.
+    Flat,
+}
+
+/// Represents the z-score thresholds used to open and close a pairs-trading position.
+///
+/// A position is opened when the absolute z-score of the spread reaches `entry_z`
+/// and closed once the z-score reverts back through `exit_z`.
+///
+/// # Fields
+///
+/// * `entry_z` - The absolute z-score at which a position is opened (commonly `2.0`).
+/// * `exit_z` - The z-score at which an open position is closed (commonly `0.0`).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::pairs_trading_svc::PairSignalParams;
+///
+/// let params = PairSignalParams::default();
+/// assert_eq!(params.entry_z, 2.0);
+/// assert_eq!(params.exit_z, 0.0);
+///
+/// assert!(PairSignalParams::new(1.5, 0.5).is_ok());
+/// assert!(PairSignalParams::new(0.5, 1.0).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PairSignalParams {
+    /// The absolute z-score at which a position is opened.
+    pub entry_z: f64,
+    /// The z-score at which an open position is closed.
+    pub exit_z: f64,
+}
+
+impl Default for PairSignalParams {
+    fn default() -> Self {
+        Self { entry_z: 2.0, exit_z: 0.0 }
+    }
+}
+
+impl PairSignalParams {
+    /// Creates a new `PairSignalParams` instance after validating the thresholds.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry_z` - The absolute z-score at which a position is opened.
+    /// * `exit_z` - The z-score at which an open position is closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NaluFxError::InvalidData` if either threshold is not finite, if `entry_z`
+    /// is not positive, or if `exit_z` is not strictly inside the entry band.
+    pub fn new(entry_z: f64, exit_z: f64) -> Result<Self, NaluFxError> {
+        if !entry_z.is_finite() || !exit_z.is_finite() {
+            return Err(NaluFxError::InvalidData);
+        }
+        if entry_z <= 0.0 || exit_z.abs() >= entry_z {
+            return Err(NaluFxError::InvalidData);
+        }
+        Ok(Self { entry_z, exit_z })
+    }
+}
+
+/// Generates daily pairs-trading signals from a spread z-score series.
+///
+/// The signal is stateful: once a position is opened it is held until the z-score
+/// reverts through the exit threshold.
+///
+/// - While flat, a z-score at or above `entry_z` opens a `ShortSpread` position and a
+///   z-score at or below `-entry_z` opens a `LongSpread` position.
+/// - A `ShortSpread` position is closed when the z-score falls to `exit_z` or below.
+/// - A `LongSpread` position is closed when the z-score rises to `-exit_z` or above.
+///
+/// A position that is closed may be re-opened in the opposite direction on the same day
+/// if the z-score has crossed the opposite entry threshold. `NaN` z-scores leave the
+/// current position unchanged.
+///
+/// # Arguments
+///
+/// * `zscore` - A slice of spread z-scores, one per day.
+/// * `params` - The entry and exit thresholds.
+///
+/// # Returns
+///
+/// A vector of `PairSignal` values with the same length as `zscore`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::pairs_trading_svc::{pair_signals, PairSignal, PairSignalParams};
+///
+/// let zscore = vec![0.5, 2.1, 1.0, -0.1, -2.5, 0.2];
+/// let signals = pair_signals(&zscore, PairSignalParams::default());
+/// assert_eq!(
+///     signals,
+///     vec![
+///         PairSignal::Flat,
+///         PairSignal::ShortSpread,
+///         PairSignal::ShortSpread,
+///         PairSignal::Flat,
+///         PairSignal::LongSpread,
+///         PairSignal::Flat,
+///     ]
+/// );
+/// ```
+pub fn pair_signals(zscore: &[f64], params: PairSignalParams) -> Vec<PairSignal> {
+    let mut position = PairSignal::Flat;
+
+    zscore
+        .iter()
+        .map(|&z| {
+            // Close the open position once the spread has reverted
+            position = match position {
+                PairSignal::ShortSpread if z <= params.exit_z => PairSignal::Flat,
+                PairSignal::LongSpread if z >= -params.exit_z => PairSignal::Flat,
+                current => current,
+            };
+
+            // Open a new position when the spread is stretched beyond the entry band
+            if position == PairSignal::Flat {
+                if z >= params.entry_z {
+                    position = PairSignal::ShortSpread;
+                } else if z <= -params.entry_z {
+                    position = PairSignal::LongSpread;
+                }
+            }
+
+            position
+        })
+        .collect()
+}
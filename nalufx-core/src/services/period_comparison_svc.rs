@@ -0,0 +1,225 @@
+use crate::{
+    errors::NaluFxError,
+    services::{
+        fetch_data_svc::fetch_data_with_dates,
+        processing_svc::{calculate_daily_returns, calculate_total_return, calculate_volatility},
+    },
+    utils::risk::calculate_max_drawdown,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Represents the performance of a stock over one period.
+///
+/// # Fields
+///
+/// * `start` - The start of the period.
+/// * `end` - The end of the period.
+/// * `observations` - The number of closes in the period.
+/// * `total_return` - The change between the first and last closes of the period, as a
+///   fraction of the first close (see `calculate_total_return`).
+/// * `volatility` - The annualized volatility of the daily returns of the period (see
+///   `calculate_volatility`).
+/// * `max_drawdown` - The maximum drawdown of the closes of the period (see
+///   `calculate_max_drawdown`).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeriodMetrics {
+    /// The start of the period.
+    pub start: DateTime<Utc>,
+    /// The end of the period.
+    pub end: DateTime<Utc>,
+    /// The number of closes in the period.
+    pub observations: usize,
+    /// The total return over the period.
+    pub total_return: f64,
+    /// The annualized volatility of the daily returns.
+    pub volatility: f64,
+    /// The maximum drawdown of the closes.
+    pub max_drawdown: f64,
+}
+
+/// Represents the performance of a stock over two periods, side by side.
+///
+/// A period without enough data to measure, i.e. with fewer than two closes, is `None`.
+///
+/// # Fields
+///
+/// * `ticker` - The ticker symbol of the stock.
+/// * `period_a` - The metrics of the first period, if it has data.
+/// * `period_b` - The metrics of the second period, if it has data.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeriodComparison {
+    /// The ticker symbol of the stock.
+    pub ticker: String,
+    /// The metrics of the first period, if it has data.
+    pub period_a: Option<PeriodMetrics>,
+    /// The metrics of the second period, if it has data.
+    pub period_b: Option<PeriodMetrics>,
+}
+
+/// Calculates the metrics of the closes of a series that fall within a period.
+///
+/// # Arguments
+///
+/// * `series` - A slice of `(timestamp, close)` pairs, in chronological order.
+/// * `range` - The start and end of the period, both inclusive.
+///
+/// # Returns
+///
+/// The `PeriodMetrics` of the period, or `None` if fewer than two closes fall within it.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use nalufx::services::period_comparison_svc::period_metrics;
+///
+/// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// let series: Vec<_> = [100.0, 120.0, 90.0, 110.0]
+///     .iter()
+///     .enumerate()
+///     .map(|(day, &close)| (start + Duration::days(day as i64), close))
+///     .collect();
+///
+/// let metrics = period_metrics(&series, (start, start + Duration::days(3))).unwrap();
+/// assert_eq!(metrics.observations, 4);
+/// assert!((metrics.total_return - 0.1).abs() < 1e-12);
+/// assert!((metrics.max_drawdown - 0.25).abs() < 1e-12);
+///
+/// assert!(period_metrics(&series, (start - Duration::days(9), start)).is_none());
+/// ```
+pub fn period_metrics(
+    series: &[(DateTime<Utc>, f64)],
+    range: (DateTime<Utc>, DateTime<Utc>),
+) -> Option<PeriodMetrics> {
+    let (start, end) = range;
+    let closes: Vec<f64> = series
+        .iter()
+        .filter(|(timestamp, _)| (start..=end).contains(timestamp))
+        .map(|&(_, close)| close)
+        .collect();
+    if closes.len() < 2 {
+        return None;
+    }
+
+    let volatility = calculate_volatility(&calculate_daily_returns(&closes), true).ok()?;
+    Some(PeriodMetrics {
+        start,
+        end,
+        observations: closes.len(),
+        total_return: calculate_total_return(&closes),
+        volatility,
+        max_drawdown: calculate_max_drawdown(&closes),
+    })
+}
+
+/// Compares the performance of a stock over two periods of a series, e.g. before and after
+/// an earnings release.
+///
+/// # Arguments
+///
+/// * `ticker` - The ticker symbol of the stock.
+/// * `series` - A slice of `(timestamp, close)` pairs covering both periods, in chronological
+///   order.
+/// * `range_a` - The start and end of the first period, both inclusive.
+/// * `range_b` - The start and end of the second period, both inclusive.
+///
+/// # Returns
+///
+/// The `PeriodComparison` of the two periods, where a period without data is `None`.
+pub fn compare_series_periods(
+    ticker: &str,
+    series: &[(DateTime<Utc>, f64)],
+    range_a: (DateTime<Utc>, DateTime<Utc>),
+    range_b: (DateTime<Utc>, DateTime<Utc>),
+) -> PeriodComparison {
+    PeriodComparison {
+        ticker: ticker.to_string(),
+        period_a: period_metrics(series, range_a),
+        period_b: period_metrics(series, range_b),
+    }
+}
+
+/// Fetches the closes of a stock over two periods and compares its performance over them.
+///
+/// Each period is fetched separately, so the periods may be far apart. A period for which the
+/// data source has no data is `None` in the comparison, so that the other period can still be
+/// reported.
+///
+/// # Arguments
+///
+/// * `ticker` - The ticker symbol of the stock.
+/// * `range_a` - The start and end of the first period, both inclusive.
+/// * `range_b` - The start and end of the second period, both inclusive.
+///
+/// # Errors
+///
+/// Returns `NaluFxError::InvalidInput` if a period starts after it ends,
+/// `NaluFxError::NoDataForTicker` if neither period has data, or the error of the data source
+/// if a fetch fails for another reason.
+pub async fn compare_periods(
+    ticker: &str,
+    range_a: (DateTime<Utc>, DateTime<Utc>),
+    range_b: (DateTime<Utc>, DateTime<Utc>),
+) -> Result<PeriodComparison, NaluFxError> {
+    if let Some((start, end)) = [range_a, range_b].into_iter().find(|(start, end)| start > end) {
+        return Err(NaluFxError::InvalidInput(format!(
+            "The period from {} to {} ends before it starts",
+            start.date_naive(),
+            end.date_naive()
+        )));
+    }
+
+    let mut series = Vec::new();
+    for (start, end) in [range_a, range_b] {
+        match fetch_data_with_dates(ticker, Some(start), Some(end)).await {
+            Ok(closes) => series.extend(closes),
+            Err(NaluFxError::NoDataForTicker(_)) => {},
+            Err(e) => return Err(e),
+        }
+    }
+    series.sort_by_key(|&(timestamp, _)| timestamp);
+    series.dedup_by_key(|&mut (timestamp, _)| timestamp);
+
+    let comparison = compare_series_periods(ticker, &series, range_a, range_b);
+    if comparison.period_a.is_none() && comparison.period_b.is_none() {
+        return Err(NaluFxError::NoDataForTicker(ticker.to_string()));
+    }
+    Ok(comparison)
+}
+
+/// Formats a `PeriodComparison` as a Markdown table, with one column per period.
+///
+/// A period without data is shown as `n/a`.
+///
+/// # Arguments
+///
+/// * `comparison` - The `PeriodComparison` to format.
+///
+/// # Returns
+///
+/// A `String` containing the Markdown table.
+pub fn format_period_comparison(comparison: &PeriodComparison) -> String {
+    let periods = [comparison.period_a, comparison.period_b];
+    let cell = |metric: fn(&PeriodMetrics) -> String, period: &Option<PeriodMetrics>| {
+        period.as_ref().map_or_else(|| "n/a".to_string(), metric)
+    };
+    let row = |label: &str, metric: fn(&PeriodMetrics) -> String| {
+        format!(
+            "| {:12} | {:>24} | {:>24} |",
+            label,
+            cell(metric, &periods[0]),
+            cell(metric, &periods[1])
+        )
+    };
+
+    [
+        format!("| {:12} | {:>24} | {:>24} |", comparison.ticker, "Period A", "Period B"),
+        "|--------------|--------------------------|--------------------------|".to_string(),
+        row("Dates", |m| format!("{} to {}", m.start.date_naive(), m.end.date_naive())),
+        row("Return", |m| format!("{:.2}%", m.total_return * 100.0)),
+        row("Volatility", |m| format!("{:.2}%", m.volatility * 100.0)),
+        row("Max Drawdown", |m| format!("{:.2}%", m.max_drawdown * 100.0)),
+    ]
+    .join("\n")
+}
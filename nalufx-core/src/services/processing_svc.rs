@@ -1,9 +1,45 @@
+use crate::errors::NaluFxError;
+use crate::utils::{risk::TRADING_DAYS_PER_YEAR, stats::std_dev};
+use chrono::{DateTime, Utc};
+use log::warn;
+
+/// The average number of days in a year, counting leap years.
+pub const DAYS_PER_YEAR: f64 = 365.25;
+
+/// Represents how `calculate_daily_returns_with_policy` treats missing closing prices.
+///
+/// Yahoo Finance occasionally returns `null` closes, which are parsed as `NaN`. A close that
+/// is `NaN`, infinite, zero or negative is treated as missing, since it cannot produce a
+/// meaningful return.
+///
+/// # Variants
+///
+/// * `ForwardFill` - Replace a missing close with the previous valid close, so that its day has
+///   a return of `0.0` and the returns stay aligned with the closes (the default).
+/// * `Skip` - Drop missing closes, so that the return after a gap spans the whole gap.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MissingClosePolicy {
+    /// Replace a missing close with the previous valid close.
+    #[default]
+    ForwardFill,
+    /// Drop missing closes.
+    Skip,
+}
+
 /// Calculates the daily returns from a slice of closing prices.
 ///
 /// This function takes a slice of closing prices and calculates the daily returns
 /// as the percentage change from one day to the next. The return for each day is
 /// calculated as `(current_day_close / previous_day_close) - 1.0`.
 ///
+/// Missing closes (`NaN`, infinite, zero or negative) are forward-filled, following the
+/// default `MissingClosePolicy::ForwardFill`, so a single bad data point does not turn the
+/// returns into `NaN`. Use [`calculate_daily_returns_with_policy`] to choose a different policy.
+///
+/// Although the returns are called daily, they are the returns between consecutive closes, so
+/// the closes of a series resampled with [`resample`](crate::utils::resample::resample) give
+/// weekly or monthly returns.
+///
 /// # Arguments
 ///
 /// * `closes` - A slice of closing prices (`&[f64]`). Each entry represents the closing price of an asset at the end of a trading day.
@@ -21,7 +57,62 @@
 /// assert_eq!(daily_returns, vec![0.010000000000000009, 0.00990099009900991, -0.004901960784313708]);
 /// ```
 pub fn calculate_daily_returns(closes: &[f64]) -> Vec<f64> {
-    closes.windows(2).map(|w| (w[1] / w[0]) - 1.0).collect()
+    calculate_daily_returns_with_policy(closes, MissingClosePolicy::default())
+}
+
+/// Calculates the daily returns from a slice of closing prices, with the given policy for
+/// missing closes.
+///
+/// With `MissingClosePolicy::ForwardFill`, the result has one return per pair of consecutive
+/// closes, except that missing closes before the first valid close are dropped, since there is
+/// no previous close to fill them with. With `MissingClosePolicy::Skip`, the result has one
+/// return per pair of consecutive valid closes. In both cases, the result contains no `NaN`.
+///
+/// # Arguments
+///
+/// * `closes` - A slice of closing prices (`&[f64]`).
+/// * `policy` - The `MissingClosePolicy` to apply to missing closes.
+///
+/// # Returns
+///
+/// A vector of daily returns (`Vec<f64>`), empty if there are fewer than two valid closes.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::processing_svc::{calculate_daily_returns_with_policy, MissingClosePolicy};
+/// let closes = vec![100.0, f64::NAN, 110.0];
+///
+/// let filled = calculate_daily_returns_with_policy(&closes, MissingClosePolicy::ForwardFill);
+/// assert_eq!(filled.len(), 2);
+/// assert_eq!(filled[0], 0.0);
+///
+/// let skipped = calculate_daily_returns_with_policy(&closes, MissingClosePolicy::Skip);
+/// assert_eq!(skipped.len(), 1);
+/// assert!((skipped[0] - 0.1).abs() < 1e-12);
+/// ```
+pub fn calculate_daily_returns_with_policy(closes: &[f64], policy: MissingClosePolicy) -> Vec<f64> {
+    let is_valid = |close: &f64| close.is_finite() && *close > 0.0;
+    let missing = closes.iter().filter(|close| !is_valid(close)).count();
+    if missing == 0 {
+        return closes.windows(2).map(|w| (w[1] / w[0]) - 1.0).collect();
+    }
+    warn!("{} of {} closing prices are missing, applying {:?}", missing, closes.len(), policy);
+
+    let cleaned: Vec<f64> = match policy {
+        MissingClosePolicy::ForwardFill => closes
+            .iter()
+            .scan(None, |previous: &mut Option<f64>, &close| {
+                if is_valid(&close) {
+                    *previous = Some(close);
+                }
+                Some(*previous)
+            })
+            .flatten()
+            .collect(),
+        MissingClosePolicy::Skip => closes.iter().copied().filter(is_valid).collect(),
+    };
+    cleaned.windows(2).map(|w| (w[1] / w[0]) - 1.0).collect()
 }
 
 /// Calculates the cash flows from daily returns and an initial investment.
@@ -51,3 +142,176 @@ pub fn calculate_daily_returns(closes: &[f64]) -> Vec<f64> {
 pub fn calculate_cash_flows(daily_returns: &[f64], initial_investment: f64) -> Vec<f64> {
     daily_returns.iter().map(|&r| r * initial_investment).collect()
 }
+
+/// Calculates the total return from a slice of adjusted closing prices.
+///
+/// Yahoo Finance adjusts its `adjclose` series for dividends and splits, so the change between
+/// the first and last adjusted closes includes the reinvested dividends that the returns of
+/// unadjusted closes leave out. The total return is `(last_close / first_close) - 1.0`.
+///
+/// # Arguments
+///
+/// * `adjusted_closes` - A slice of adjusted closing prices (`&[f64]`), in chronological order.
+///
+/// # Returns
+///
+/// The total return, as a fraction of the first adjusted close. It is `0.0` for fewer than two
+/// closes, and `NaN` if the first close is not positive.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::processing_svc::calculate_total_return;
+/// let adjusted_closes = vec![100.0, 103.0, 99.0, 110.0];
+/// assert!((calculate_total_return(&adjusted_closes) - 0.1).abs() < 1e-12);
+/// assert_eq!(calculate_total_return(&[]), 0.0);
+/// ```
+pub fn calculate_total_return(adjusted_closes: &[f64]) -> f64 {
+    let [first, .., last] = adjusted_closes else {
+        return 0.0;
+    };
+    if *first > 0.0 {
+        (last / first) - 1.0
+    } else {
+        f64::NAN
+    }
+}
+
+/// Calculates the length of a period in years, from its start and end dates.
+///
+/// # Arguments
+///
+/// * `start_date` - The start of the period.
+/// * `end_date` - The end of the period.
+///
+/// # Returns
+///
+/// The number of days between the dates divided by `DAYS_PER_YEAR`, negative if `end_date`
+/// comes before `start_date`.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use nalufx::services::processing_svc::years_between;
+/// let start_date = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+/// let end_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// assert!((years_between(start_date, end_date) - 2.0).abs() < 1e-2);
+/// ```
+pub fn years_between(start_date: DateTime<Utc>, end_date: DateTime<Utc>) -> f64 {
+    (end_date - start_date).num_seconds() as f64 / (DAYS_PER_YEAR * 86_400.0)
+}
+
+/// Calculates the compound annual growth rate (CAGR) between two values.
+///
+/// The CAGR is the constant annual return that grows `start_value` into `end_value` over
+/// `years`: `(end_value / start_value)^(1 / years) - 1.0`. Annualizing a period shorter than a
+/// year extrapolates its return to a whole year, which exaggerates short-term moves, so for
+/// such periods the CAGR is the total return over the period instead.
+///
+/// # Arguments
+///
+/// * `start_value` - The value at the start of the period (`f64`).
+/// * `end_value` - The value at the end of the period (`f64`).
+/// * `years` - The length of the period in years (`f64`), e.g. from [`years_between`].
+///
+/// # Returns
+///
+/// A `Result` containing the CAGR as a fraction (e.g. `0.1` for 10% a year), or a
+/// `NaluFxError::InvalidData` if `start_value` or `years` is not positive, or `end_value` is
+/// negative, or any of them is not finite.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::processing_svc::calculate_cagr;
+/// // Doubling over two years is a CAGR of about 41.4%.
+/// let cagr = calculate_cagr(100.0, 200.0, 2.0).unwrap();
+/// assert!((cagr - (2.0_f64.sqrt() - 1.0)).abs() < 1e-12);
+/// assert!(calculate_cagr(0.0, 200.0, 2.0).is_err());
+/// ```
+pub fn calculate_cagr(start_value: f64, end_value: f64, years: f64) -> Result<f64, NaluFxError> {
+    let valid = start_value > 0.0
+        && start_value.is_finite()
+        && end_value >= 0.0
+        && end_value.is_finite()
+        && years > 0.0
+        && years.is_finite();
+    if !valid {
+        return Err(NaluFxError::InvalidData);
+    }
+
+    let growth = end_value / start_value;
+    Ok(if years < 1.0 { growth - 1.0 } else { growth.powf(1.0 / years) - 1.0 })
+}
+
+/// Calculates the dividend yield from dividend payments and a share price.
+///
+/// The yield is the sum of the dividends divided by the price. Pass the dividends of the
+/// trailing twelve months (see
+/// [`fetch_dividends`](crate::services::fetch_data_svc::fetch_dividends)) for the usual
+/// annual yield.
+///
+/// # Arguments
+///
+/// * `dividends` - The `(payment date, amount per share)` pairs of the dividends to include.
+/// * `price` - The share price (`f64`), typically the last close.
+///
+/// # Returns
+///
+/// The dividend yield, as a fraction of the price (e.g. `0.025` for 2.5%). It is `0.0` if
+/// there are no dividends, and `NaN` if the price is not positive.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use nalufx::services::processing_svc::calculate_dividend_yield;
+/// let dividends = vec![
+///     (Utc.with_ymd_and_hms(2024, 2, 9, 0, 0, 0).unwrap(), 0.24),
+///     (Utc.with_ymd_and_hms(2024, 5, 10, 0, 0, 0).unwrap(), 0.26),
+/// ];
+/// assert!((calculate_dividend_yield(&dividends, 20.0) - 0.025).abs() < 1e-12);
+/// ```
+pub fn calculate_dividend_yield(dividends: &[(DateTime<Utc>, f64)], price: f64) -> f64 {
+    if !(price > 0.0 && price.is_finite()) {
+        return f64::NAN;
+    }
+    dividends.iter().map(|(_, amount)| amount).sum::<f64>() / price
+}
+
+/// Calculates the volatility of a series of daily returns.
+///
+/// The volatility is the sample standard deviation of the daily returns. When `annualize` is
+/// `true`, it is scaled by the square root of `TRADING_DAYS_PER_YEAR` (252).
+///
+/// # Arguments
+///
+/// * `daily_returns` - A slice of daily returns (`&[f64]`).
+/// * `annualize` - Whether to annualize the daily volatility (`bool`).
+///
+/// # Returns
+///
+/// A `Result` containing the volatility, which is `0.0` for a single return, or a
+/// `NaluFxError` if `daily_returns` is empty or contains a `NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::processing_svc::calculate_volatility;
+/// let daily_returns = vec![0.01, -0.01, 0.01, -0.01];
+/// let daily = calculate_volatility(&daily_returns, false).unwrap();
+/// assert!((daily - 0.011547005383792516).abs() < 1e-12);
+/// let annualized = calculate_volatility(&daily_returns, true).unwrap();
+/// assert!((annualized - daily * 252.0_f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn calculate_volatility(daily_returns: &[f64], annualize: bool) -> Result<f64, NaluFxError> {
+    match daily_returns.len() {
+        0 => return Err(NaluFxError::EmptyInput),
+        1 => return Ok(0.0),
+        _ => {},
+    }
+
+    let volatility = std_dev(daily_returns, 1).ok_or(NaluFxError::InvalidData)?;
+    Ok(if annualize { volatility * TRADING_DAYS_PER_YEAR.sqrt() } else { volatility })
+}
@@ -0,0 +1,139 @@
+use crate::{
+    errors::AllocationError,
+    utils::calculations::{calculate_optimal_allocation_with_options, AllocationOptions},
+};
+use serde::{Deserialize, Serialize};
+
+/// Represents the inputs of an allocation, to re-run it with perturbed returns.
+///
+/// # Fields
+///
+/// * `daily_returns` - The historical daily returns.
+/// * `cash_flows` - The historical cash flows.
+/// * `market_indices` - The historical market indices.
+/// * `fund_characteristics` - The historical fund characteristics.
+/// * `options` - The `AllocationOptions` of the allocation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AllocationInputs {
+    /// The historical daily returns.
+    pub daily_returns: Vec<f64>,
+    /// The historical cash flows.
+    pub cash_flows: Vec<f64>,
+    /// The historical market indices.
+    pub market_indices: Vec<f64>,
+    /// The historical fund characteristics.
+    pub fund_characteristics: Vec<f64>,
+    /// The options of the allocation.
+    pub options: AllocationOptions,
+}
+
+/// Represents how much an allocation moves when its daily returns are shifted.
+///
+/// # Fields
+///
+/// * `perturbation_pct` - The shift of every daily return, in percentage points.
+/// * `base` - The allocation of each day for the unperturbed returns.
+/// * `shifted_up` - The allocation of each day for the returns shifted up.
+/// * `shifted_down` - The allocation of each day for the returns shifted down.
+/// * `changes` - The largest absolute change of the allocation of each day, up or down.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SensitivityResult {
+    /// The shift of every daily return, in percentage points.
+    pub perturbation_pct: f64,
+    /// The allocation of each day for the unperturbed returns.
+    pub base: Vec<f64>,
+    /// The allocation of each day for the returns shifted up.
+    pub shifted_up: Vec<f64>,
+    /// The allocation of each day for the returns shifted down.
+    pub shifted_down: Vec<f64>,
+    /// The largest absolute change of the allocation of each day.
+    pub changes: Vec<f64>,
+}
+
+impl SensitivityResult {
+    /// Returns the largest change of the allocation of any day, or `0.0` for no days.
+    ///
+    /// A large change relative to the allocations flags a fragile recommendation.
+    pub fn max_change(&self) -> f64 {
+        self.changes.iter().copied().fold(0.0, f64::max)
+    }
+}
+
+/// Analyzes how sensitive an allocation is to its daily returns.
+///
+/// The allocation is calculated for the daily returns of `base_inputs`, and again with every
+/// daily return shifted up and down by `perturbation_pct` percentage points, e.g. `0.1` for
+/// ±0.1% a day. The change of the allocation of each day is the larger of the two moves.
+///
+/// The changes are only meaningful for a deterministic objective: the placeholder sentiment
+/// scores and optimal actions of `Objective::MlHeuristic` are random, so its allocations move
+/// between runs even without a perturbation.
+///
+/// # Arguments
+///
+/// * `base_inputs` - The `AllocationInputs` of the allocation to analyze.
+/// * `perturbation_pct` - The shift of every daily return, in percentage points.
+/// * `num_days` - The number of days to allocate over.
+///
+/// # Returns
+///
+/// The `SensitivityResult` of the allocation.
+///
+/// # Errors
+///
+/// Returns `AllocationError::InvalidData` if `perturbation_pct` is not finite, or any error
+/// of `calculate_optimal_allocation_with_options` for the base or shifted returns, e.g.
+/// `AllocationError::OutlierData` if a shift takes a return beyond the outlier threshold.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::sensitivity_svc::{sensitivity_analysis, AllocationInputs};
+/// use nalufx::utils::calculations::{AllocationOptions, Objective};
+///
+/// let inputs = AllocationInputs {
+///     daily_returns: vec![0.01, 0.02, -0.01, 0.03, 0.01],
+///     cash_flows: vec![1000.0, 1020.0, 1010.0, 1030.0, 1025.0],
+///     market_indices: vec![1.0, 1.01, 1.02, 1.03, 1.04],
+///     fund_characteristics: vec![0.5, 0.6, 0.7, 0.8, 0.9],
+///     options: AllocationOptions { objective: Objective::MinVariance, ..Default::default() },
+/// };
+///
+/// let result = sensitivity_analysis(&inputs, 0.5, 3).unwrap();
+/// assert_eq!(result.changes.len(), 3);
+/// // The minimum variance allocation does not depend on the returns
+/// assert_eq!(result.max_change(), 0.0);
+/// ```
+pub fn sensitivity_analysis(
+    base_inputs: &AllocationInputs,
+    perturbation_pct: f64,
+    num_days: usize,
+) -> Result<SensitivityResult, AllocationError> {
+    if !perturbation_pct.is_finite() {
+        return Err(AllocationError::InvalidData);
+    }
+
+    let allocate = |shift: f64| {
+        let daily_returns: Vec<f64> = base_inputs.daily_returns.iter().map(|r| r + shift).collect();
+        calculate_optimal_allocation_with_options(
+            &daily_returns,
+            &base_inputs.cash_flows,
+            &base_inputs.market_indices,
+            &base_inputs.fund_characteristics,
+            num_days,
+            &base_inputs.options,
+        )
+    };
+
+    let shift = perturbation_pct / 100.0;
+    let base = allocate(0.0)?;
+    let shifted_up = allocate(shift)?;
+    let shifted_down = allocate(-shift)?;
+    let changes = base
+        .iter()
+        .zip(shifted_up.iter().zip(&shifted_down))
+        .map(|(base, (up, down))| (up - base).abs().max((down - base).abs()))
+        .collect();
+
+    Ok(SensitivityResult { perturbation_pct, base, shifted_up, shifted_down, changes })
+}
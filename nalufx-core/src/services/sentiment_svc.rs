@@ -0,0 +1,290 @@
+use crate::{
+    config::is_offline_mode, errors::NaluFxError, services::news_svc::NewsApiClient,
+    utils::calculations::analyze_sentiment,
+};
+use async_trait::async_trait;
+use chrono::{Days, NaiveDate, NaiveTime, Utc};
+use log::info;
+use nalufx_llms::llms::LLM;
+use reqwest::Client;
+use std::env;
+
+/// The environment variable holding the NewsAPI key, which selects the NewsAPI provider.
+pub const NEWS_API_KEY_ENV: &str = "NEWS_API_KEY";
+
+/// The environment variable used to override the base URL of the NewsAPI service.
+pub const NEWS_API_URL_ENV: &str = "NEWS_API_URL";
+
+/// The base URL of the NewsAPI service.
+pub const DEFAULT_NEWS_API_URL: &str = "https://newsapi.org";
+
+/// The sentiment score of a day without news, halfway between negative and positive.
+pub const NEUTRAL_SENTIMENT: f64 = 0.5;
+
+/// Words that make a headline more positive.
+const POSITIVE_WORDS: &[&str] = &[
+    "beat",
+    "beats",
+    "boost",
+    "boosts",
+    "bullish",
+    "gain",
+    "gains",
+    "growth",
+    "high",
+    "jump",
+    "jumps",
+    "outperform",
+    "profit",
+    "rally",
+    "record",
+    "rise",
+    "rises",
+    "soar",
+    "soars",
+    "strong",
+    "surge",
+    "surges",
+    "upgrade",
+    "upgrades",
+    "win",
+    "wins",
+];
+
+/// Words that make a headline more negative.
+const NEGATIVE_WORDS: &[&str] = &[
+    "bearish",
+    "cut",
+    "cuts",
+    "decline",
+    "declines",
+    "downgrade",
+    "downgrades",
+    "drop",
+    "drops",
+    "fall",
+    "falls",
+    "fraud",
+    "lawsuit",
+    "layoffs",
+    "loss",
+    "losses",
+    "miss",
+    "misses",
+    "plunge",
+    "plunges",
+    "probe",
+    "recall",
+    "slump",
+    "slumps",
+    "weak",
+];
+
+/// A trait representing a source of daily market sentiment for a ticker symbol.
+///
+/// Scores range from 0 (very negative) to 1 (very positive), with
+/// [`NEUTRAL_SENTIMENT`] for a neutral day.
+#[async_trait]
+pub trait SentimentProvider: Sync + Send {
+    /// Fetches the sentiment towards a ticker symbol for each of the last `days` days.
+    ///
+    /// # Arguments
+    ///
+    /// * `ticker` - A string slice that holds the ticker symbol (e.g., "AAPL").
+    /// * `days` - The number of days to score.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `days` sentiment scores, oldest first, if successful, or a
+    /// `NaluFxError` otherwise.
+    async fn daily_sentiment(&self, ticker: &str, days: usize) -> Result<Vec<f64>, NaluFxError>;
+}
+
+/// A sentiment provider returning random scores, for tests and for running without a news
+/// source.
+///
+/// The scores are generated by [`analyze_sentiment`], and do not depend on the ticker symbol.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::sentiment_svc::{MockSentimentProvider, SentimentProvider};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let scores = MockSentimentProvider.daily_sentiment("AAPL", 5).await.unwrap();
+///     assert_eq!(scores.len(), 5);
+///     assert!(scores.iter().all(|score| (0.0..=1.0).contains(score)));
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MockSentimentProvider;
+
+#[async_trait]
+impl SentimentProvider for MockSentimentProvider {
+    async fn daily_sentiment(&self, _ticker: &str, days: usize) -> Result<Vec<f64>, NaluFxError> {
+        analyze_sentiment(days).map_err(NaluFxError::SentimentAnalysisError)
+    }
+}
+
+/// A sentiment provider asking an LLM to score the recent market sentiment.
+///
+/// The LLM is asked for a JSON array of `days` scores between 0 and 1, oldest first. The
+/// scores reflect what the model knows about the ticker symbol, so they are only as recent as
+/// its training data.
+pub struct LlmSentimentProvider {
+    llm: Box<dyn LLM>,
+    client: Client,
+    api_key: String,
+}
+
+impl LlmSentimentProvider {
+    /// Creates a new `LlmSentimentProvider` sending its requests to the given LLM.
+    pub fn new(llm: Box<dyn LLM>, client: Client, api_key: &str) -> Self {
+        Self { llm, client, api_key: api_key.to_string() }
+    }
+}
+
+#[async_trait]
+impl SentimentProvider for LlmSentimentProvider {
+    async fn daily_sentiment(&self, ticker: &str, days: usize) -> Result<Vec<f64>, NaluFxError> {
+        let prompt = format!(
+            "Score the market sentiment towards {} for each of the last {} days, from 0 (very \
+             negative) to 1 (very positive), oldest first. Answer with a JSON array of {} \
+             numbers and nothing else.",
+            ticker, days, days
+        );
+        let response = self.llm.send_request(&self.client, &self.api_key, &prompt, 500).await?;
+        let content = response["choices"][0]["message"]["content"].as_str().unwrap_or("");
+        parse_llm_scores(content, days).ok_or_else(|| {
+            NaluFxError::SentimentAnalysisError(format!(
+                "The LLM did not answer with {} sentiment scores for {}",
+                days, ticker
+            ))
+        })
+    }
+}
+
+/// Extracts the JSON array of scores from an LLM answer, clamping each score to `0..=1`.
+fn parse_llm_scores(content: &str, days: usize) -> Option<Vec<f64>> {
+    let start = content.find('[')?;
+    let end = content.rfind(']')?;
+    let scores: Vec<f64> = serde_json::from_str(content.get(start..=end)?).ok()?;
+    if scores.len() != days || scores.iter().any(|score| !score.is_finite()) {
+        return None;
+    }
+    Some(scores.into_iter().map(|score| score.clamp(0.0, 1.0)).collect())
+}
+
+/// A sentiment provider scoring the news headlines returned by
+/// [NewsAPI](https://newsapi.org).
+///
+/// The provider fetches the headlines of the articles mentioning the ticker symbol since the
+/// first day with a [`NewsApiClient`], and scores each headline with [`score_headline`]. The
+/// score of a day is the mean score of its headlines, or [`NEUTRAL_SENTIMENT`] if it has none.
+/// Days are calendar days in UTC, ending today.
+#[derive(Clone, Debug)]
+pub struct NewsApiSentimentProvider {
+    news: NewsApiClient,
+}
+
+impl NewsApiSentimentProvider {
+    /// Creates a new `NewsApiSentimentProvider` for the given base URL and API key.
+    pub fn new(client: Client, base_url: &str, api_key: &str) -> Self {
+        Self { news: NewsApiClient::new(client, base_url, api_key) }
+    }
+}
+
+#[async_trait]
+impl SentimentProvider for NewsApiSentimentProvider {
+    async fn daily_sentiment(&self, ticker: &str, days: usize) -> Result<Vec<f64>, NaluFxError> {
+        if days == 0 {
+            return Ok(Vec::new());
+        }
+        let now = Utc::now();
+        let first_day =
+            now.date_naive().checked_sub_days(Days::new(days as u64 - 1)).unwrap_or(NaiveDate::MIN);
+
+        let headlines = self
+            .news
+            .fetch_headlines(ticker, first_day.and_time(NaiveTime::MIN).and_utc(), now)
+            .await
+            .map_err(|e| match e {
+                NaluFxError::NewsApiError(message) => NaluFxError::SentimentAnalysisError(message),
+                e => e,
+            })?;
+
+        Ok(first_day
+            .iter_days()
+            .take(days)
+            .map(|day| {
+                let scores: Vec<f64> = headlines
+                    .iter()
+                    .filter(|headline| headline.published_at.date_naive() == day)
+                    .map(|headline| score_headline(&headline.title))
+                    .collect();
+                if scores.is_empty() {
+                    NEUTRAL_SENTIMENT
+                } else {
+                    scores.iter().sum::<f64>() / scores.len() as f64
+                }
+            })
+            .collect())
+    }
+}
+
+/// Scores the sentiment of a news headline by counting its positive and negative words.
+///
+/// # Arguments
+///
+/// * `headline` - The headline to score.
+///
+/// # Returns
+///
+/// A score from 0 (only negative words) to 1 (only positive words), or
+/// [`NEUTRAL_SENTIMENT`] if the headline has no sentiment words.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::sentiment_svc::{score_headline, NEUTRAL_SENTIMENT};
+///
+/// assert_eq!(score_headline("Apple shares surge to a record high"), 1.0);
+/// assert_eq!(score_headline("Apple misses estimates as sales drop"), 0.0);
+/// assert_eq!(score_headline("Apple to hold its annual meeting"), NEUTRAL_SENTIMENT);
+/// ```
+pub fn score_headline(headline: &str) -> f64 {
+    let (mut positive, mut negative) = (0_u32, 0_u32);
+    for word in headline.split(|c: char| !c.is_alphanumeric()) {
+        let word = word.to_lowercase();
+        if POSITIVE_WORDS.contains(&word.as_str()) {
+            positive += 1;
+        } else if NEGATIVE_WORDS.contains(&word.as_str()) {
+            negative += 1;
+        }
+    }
+    if positive + negative == 0 {
+        return NEUTRAL_SENTIMENT;
+    }
+    f64::from(positive) / f64::from(positive + negative)
+}
+
+/// Creates the sentiment provider selected by the environment.
+///
+/// If `NEWS_API_KEY` is set and offline mode is off, a `NewsApiSentimentProvider` is
+/// returned, for `NEWS_API_URL` or [`DEFAULT_NEWS_API_URL`]. Otherwise the random scores of
+/// the `MockSentimentProvider` are used.
+pub fn sentiment_provider_from_env() -> Box<dyn SentimentProvider> {
+    match env::var(NEWS_API_KEY_ENV) {
+        Ok(api_key) if !api_key.trim().is_empty() && !is_offline_mode() => {
+            let url =
+                env::var(NEWS_API_URL_ENV).unwrap_or_else(|_| DEFAULT_NEWS_API_URL.to_string());
+            info!("Scoring sentiment from the NewsAPI headlines at {}", url);
+            Box::new(NewsApiSentimentProvider::new(Client::new(), &url, api_key.trim()))
+        },
+        _ => {
+            info!("{} is not set, using random sentiment scores", NEWS_API_KEY_ENV);
+            Box::new(MockSentimentProvider)
+        },
+    }
+}
@@ -0,0 +1,481 @@
+use crate::errors::AllocationError;
+use crate::utils::{
+    allocation::{apply_constraints, Constraints},
+    risk::TRADING_DAYS_PER_YEAR,
+    stats::{mean, std_dev},
+};
+use ndarray::{Array1, Array2, Axis};
+use ndarray_stats::CorrelationExt;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Represents a portfolio construction strategy to compare.
+///
+/// # Variants
+///
+/// * `EqualWeight` - The same weight for every asset.
+/// * `MeanVariance` - Weights proportional to the mean return divided by the variance of each
+///   asset, ignoring assets with a non-positive mean return. This is the mean-variance optimum
+///   when the assets are uncorrelated. If no asset has a positive mean return, the weights are
+///   proportional to the inverse variance, which minimizes the risk instead.
+/// * `RiskParity` - Weights for which every asset contributes the same risk to the portfolio
+///   (see [`risk_parity_weights`]).
+/// * `SingleAsset` - The whole portfolio in the asset at the given column index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Strategy {
+    /// The same weight for every asset.
+    EqualWeight,
+    /// Mean return over variance weights.
+    MeanVariance,
+    /// Equal risk contribution weights.
+    RiskParity,
+    /// The whole portfolio in a single asset.
+    SingleAsset(usize),
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::EqualWeight => f.pad("Equal Weight"),
+            Strategy::MeanVariance => f.pad("Mean-Variance"),
+            Strategy::RiskParity => f.pad("Risk Parity"),
+            Strategy::SingleAsset(index) => f.pad(&format!("Single Asset #{}", index + 1)),
+        }
+    }
+}
+
+/// Represents the weights of a strategy and the performance they would have achieved
+/// in-sample.
+///
+/// # Fields
+///
+/// * `strategy` - The strategy that produced the weights.
+/// * `weights` - The weight of each asset, in the column order of the returns, summing to 1.
+/// * `annualized_return` - The mean daily portfolio return multiplied by
+///   `TRADING_DAYS_PER_YEAR`.
+/// * `annualized_volatility` - The sample standard deviation of the daily portfolio returns
+///   multiplied by the square root of `TRADING_DAYS_PER_YEAR`.
+/// * `sharpe_ratio` - The annualized return divided by the annualized volatility, with a
+///   risk-free rate of zero, or `NaN` if the volatility is zero.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StrategyResult {
+    /// The strategy that produced the weights.
+    pub strategy: Strategy,
+    /// The weight of each asset, in the column order of the returns.
+    pub weights: Vec<f64>,
+    /// The annualized in-sample return.
+    pub annualized_return: f64,
+    /// The annualized in-sample volatility.
+    pub annualized_volatility: f64,
+    /// The in-sample Sharpe ratio, with a risk-free rate of zero.
+    pub sharpe_ratio: f64,
+}
+
+/// The maximum number of sweeps of the risk parity coordinate descent.
+pub const RISK_PARITY_MAX_SWEEPS: usize = 1000;
+
+/// The largest change of a weight between two sweeps at which the risk parity coordinate
+/// descent has converged.
+pub const RISK_PARITY_TOLERANCE: f64 = 1e-12;
+
+/// Represents the convergence criteria of the risk parity coordinate descent.
+///
+/// The default options are a tolerance of `RISK_PARITY_TOLERANCE` and at most
+/// `RISK_PARITY_MAX_SWEEPS` sweeps.
+///
+/// # Fields
+///
+/// * `tolerance` - The largest change of a weight between two sweeps at which the descent has
+///   converged.
+/// * `max_sweeps` - The maximum number of sweeps over the assets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RiskParityOptions {
+    /// The largest change of a weight between two sweeps at which the descent has converged.
+    pub tolerance: f64,
+    /// The maximum number of sweeps over the assets.
+    pub max_sweeps: usize,
+}
+
+impl Default for RiskParityOptions {
+    fn default() -> Self {
+        RiskParityOptions { tolerance: RISK_PARITY_TOLERANCE, max_sweeps: RISK_PARITY_MAX_SWEEPS }
+    }
+}
+
+/// Represents the risk parity weights and how well they equalize the risk contributions.
+///
+/// # Fields
+///
+/// * `weights` - The weight of each asset, in the order of the covariance matrix, summing to 1.
+/// * `sweeps` - The number of sweeps of the coordinate descent.
+/// * `converged` - Whether the descent converged within the tolerance before the maximum
+///   number of sweeps.
+/// * `max_deviation` - The largest deviation of a risk contribution from an equal share (see
+///   [`max_risk_contribution_deviation`]).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RiskParitySolution {
+    /// The weight of each asset, in the order of the covariance matrix.
+    pub weights: Vec<f64>,
+    /// The number of sweeps of the coordinate descent.
+    pub sweeps: usize,
+    /// Whether the coordinate descent converged.
+    pub converged: bool,
+    /// The largest deviation of a risk contribution from an equal share.
+    pub max_deviation: f64,
+}
+
+/// Calculates the risk parity weights of a portfolio.
+///
+/// This is [`solve_risk_parity`] with the default `RiskParityOptions`, keeping only the
+/// weights.
+///
+/// # Arguments
+///
+/// * `cov` - The covariance matrix of the asset returns (`&Array2<f64>`).
+///
+/// # Returns
+///
+/// The weight of each asset, in the order of the covariance matrix. The weights are equal if
+/// the matrix is not square, has a non-positive or non-finite variance, or if the descent
+/// does not produce finite weights.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::strategy_comparison_svc::risk_parity_weights;
+/// use ndarray::array;
+///
+/// // For uncorrelated assets, the weights are proportional to the inverse volatilities.
+/// let cov = array![[0.01, 0.0], [0.0, 0.04]];
+/// let weights = risk_parity_weights(&cov);
+/// assert!((weights[0] - 2.0 / 3.0).abs() < 1e-9 && (weights[1] - 1.0 / 3.0).abs() < 1e-9);
+/// ```
+pub fn risk_parity_weights(cov: &Array2<f64>) -> Vec<f64> {
+    solve_risk_parity(cov, &RiskParityOptions::default()).weights
+}
+
+/// Calculates the risk parity weights of a portfolio with the given convergence criteria.
+///
+/// The risk contribution of an asset is its weight times its marginal contribution to the
+/// portfolio volatility, `w_i (Σw)_i / σ`. The weights equalize the risk contributions of
+/// all assets, and are found by cyclical coordinate descent: each weight in turn is set to
+/// the positive root of `Σ_ii w_i² + w_i Σ_{j≠i} Σ_ij w_j = 1/N`, until no weight changes by
+/// more than `options.tolerance` in a sweep, or `options.max_sweeps` sweeps have run. The
+/// weights are then normalized to sum to 1.
+///
+/// # Arguments
+///
+/// * `cov` - The covariance matrix of the asset returns (`&Array2<f64>`).
+/// * `options` - The convergence criteria of the coordinate descent.
+///
+/// # Returns
+///
+/// The `RiskParitySolution`, with the weight of each asset in the order of the covariance
+/// matrix. The weights are equal, and the solution has not converged, if the matrix is not
+/// square, has a non-positive or non-finite variance, or if the descent does not produce
+/// finite weights.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::strategy_comparison_svc::{solve_risk_parity, RiskParityOptions};
+/// use ndarray::array;
+///
+/// let cov = array![[0.04, 0.006], [0.006, 0.09]];
+/// let solution = solve_risk_parity(&cov, &RiskParityOptions::default());
+/// assert!(solution.converged);
+/// assert!(solution.max_deviation < 1e-9);
+/// ```
+pub fn solve_risk_parity(cov: &Array2<f64>, options: &RiskParityOptions) -> RiskParitySolution {
+    let num_assets = cov.nrows();
+    let equal_weights = vec![1.0 / num_assets as f64; num_assets];
+    if cov.ncols() != num_assets
+        || cov.diag().iter().any(|&variance| !(variance > 0.0 && variance.is_finite()))
+    {
+        return unconverged(equal_weights, 0, cov);
+    }
+
+    let budget = 1.0 / num_assets as f64;
+    let mut weights = Array1::from_elem(num_assets, budget);
+    let mut sweeps = 0;
+    let mut converged = false;
+    while sweeps < options.max_sweeps {
+        sweeps += 1;
+        let mut max_change = 0.0_f64;
+        for i in 0..num_assets {
+            let variance = cov[[i, i]];
+            let others = cov.row(i).dot(&weights) - variance * weights[i];
+            let weight =
+                (-others + (others * others + 4.0 * variance * budget).sqrt()) / (2.0 * variance);
+            max_change = max_change.max((weight - weights[i]).abs());
+            weights[i] = weight;
+        }
+        if max_change < options.tolerance {
+            converged = true;
+            break;
+        }
+    }
+
+    let total = weights.sum();
+    if !(total > 0.0 && weights.iter().all(|w| w.is_finite())) {
+        return unconverged(equal_weights, sweeps, cov);
+    }
+    let weights: Vec<f64> = weights.iter().map(|w| w / total).collect();
+    let max_deviation = max_risk_contribution_deviation(&risk_contributions(&weights, cov));
+    RiskParitySolution { weights, sweeps, converged, max_deviation }
+}
+
+/// Returns the solution of a risk parity descent that failed, with the fallback `weights`.
+fn unconverged(weights: Vec<f64>, sweeps: usize, cov: &Array2<f64>) -> RiskParitySolution {
+    let max_deviation = max_risk_contribution_deviation(&risk_contributions(&weights, cov));
+    RiskParitySolution { weights, sweeps, converged: false, max_deviation }
+}
+
+/// Calculates the share of the portfolio risk contributed by each asset.
+///
+/// The risk contribution of asset `i` is `w_i (Σw)_i`, and the contributions sum to the
+/// portfolio variance `wᵀΣw`. Each contribution is divided by the portfolio variance, so the
+/// shares sum to 1, and are all `1/N` for risk parity weights.
+///
+/// # Arguments
+///
+/// * `weights` - The weight of each asset.
+/// * `cov` - The covariance matrix of the asset returns (`&Array2<f64>`), in the order of
+///   `weights`.
+///
+/// # Returns
+///
+/// The share of the portfolio variance contributed by each asset, in the order of `weights`.
+/// The vector is empty if the covariance matrix is not square with a row per weight, and the
+/// shares are `NaN` if the portfolio variance is not positive.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::strategy_comparison_svc::risk_contributions;
+/// use ndarray::array;
+///
+/// let cov = array![[0.01, 0.0], [0.0, 0.04]];
+/// let contributions = risk_contributions(&[0.5, 0.5], &cov);
+/// assert!((contributions[0] - 0.2).abs() < 1e-12 && (contributions[1] - 0.8).abs() < 1e-12);
+/// ```
+pub fn risk_contributions(weights: &[f64], cov: &Array2<f64>) -> Vec<f64> {
+    if cov.dim() != (weights.len(), weights.len()) {
+        return Vec::new();
+    }
+    let weights = Array1::from(weights.to_vec());
+    let marginal = cov.dot(&weights);
+    let variance = weights.dot(&marginal);
+    if !(variance > 0.0 && variance.is_finite()) {
+        return vec![f64::NAN; weights.len()];
+    }
+    weights.iter().zip(&marginal).map(|(w, m)| w * m / variance).collect()
+}
+
+/// Returns the largest deviation of a share of the risk contributions from an equal share.
+///
+/// # Arguments
+///
+/// * `contributions` - The share of the portfolio risk contributed by each asset (see
+///   [`risk_contributions`]).
+///
+/// # Returns
+///
+/// The largest absolute difference between a share and `1/N`, which is 0 for risk parity
+/// weights, or `NaN` if there are no shares or a share is `NaN`.
+pub fn max_risk_contribution_deviation(contributions: &[f64]) -> f64 {
+    if contributions.is_empty() {
+        return f64::NAN;
+    }
+    let equal_share = 1.0 / contributions.len() as f64;
+    contributions.iter().map(|contribution| (contribution - equal_share).abs()).fold(
+        0.0,
+        |max, deviation| if deviation.is_nan() || deviation > max { deviation } else { max },
+    )
+}
+
+/// Calculates the mean-variance weights from the mean return and variance of each asset.
+///
+/// Assets with a non-positive or non-finite variance are left out. If no asset qualifies,
+/// the weights are equal.
+fn mean_variance_weights(mean_returns: &Array1<f64>, variances: &Array1<f64>) -> Vec<f64> {
+    let num_assets = mean_returns.len();
+    let inverse_variances: Vec<f64> =
+        variances.iter().map(|&v| if v > 0.0 && v.is_finite() { 1.0 / v } else { 0.0 }).collect();
+
+    let mut weights: Vec<f64> = mean_returns
+        .iter()
+        .zip(&inverse_variances)
+        .map(|(&r, &w)| if r > 0.0 { r * w } else { 0.0 })
+        .collect();
+    if weights.iter().sum::<f64>() <= 0.0 {
+        // No asset is expected to be profitable, so fall back to the lowest risk.
+        weights = inverse_variances;
+    }
+
+    let total: f64 = weights.iter().sum();
+    if total > 0.0 && total.is_finite() {
+        weights.into_iter().map(|w| w / total).collect()
+    } else {
+        vec![1.0 / num_assets as f64; num_assets]
+    }
+}
+
+/// Calculates the mean-variance weights and projects them onto the weight `constraints`.
+///
+/// The weights are those of the `Strategy::MeanVariance` strategy, capped and raised to the
+/// bounds of `constraints` by [`apply_constraints`], so that no asset takes more than
+/// `max_weight` of the portfolio.
+///
+/// # Arguments
+///
+/// * `mean_returns` - The mean return of each asset (`&Array1<f64>`).
+/// * `variances` - The variance of each asset (`&Array1<f64>`), in the same order.
+/// * `constraints` - The bounds of the weight of each asset.
+///
+/// # Returns
+///
+/// The weight of each asset, in the order of `mean_returns`, summing to 1.
+///
+/// # Errors
+///
+/// * `AllocationError::InputMismatch` - If `mean_returns` and `variances` differ in length.
+/// * `AllocationError::EmptyInput` - If there are no assets.
+/// * `AllocationError::InfeasibleConstraints` - If the assets cannot meet the constraints.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::strategy_comparison_svc::constrained_mean_variance_weights;
+/// use nalufx::utils::allocation::Constraints;
+/// use ndarray::array;
+///
+/// let constraints = Constraints { max_weight: 0.6, ..Constraints::default() };
+/// let weights =
+///     constrained_mean_variance_weights(&array![0.02, 0.01], &array![0.01, 0.04], &constraints)
+///         .unwrap();
+/// assert!((weights[0] - 0.6).abs() < 1e-12 && (weights[1] - 0.4).abs() < 1e-12);
+/// ```
+pub fn constrained_mean_variance_weights(
+    mean_returns: &Array1<f64>,
+    variances: &Array1<f64>,
+    constraints: &Constraints,
+) -> Result<Vec<f64>, AllocationError> {
+    if mean_returns.len() != variances.len() {
+        return Err(AllocationError::InputMismatch {
+            expected: "mean_returns".to_string(),
+            expected_len: mean_returns.len(),
+            found: "variances".to_string(),
+            found_len: variances.len(),
+        });
+    }
+    if mean_returns.is_empty() {
+        return Err(AllocationError::EmptyInput);
+    }
+    apply_constraints(&mean_variance_weights(mean_returns, variances), constraints)
+}
+
+/// Runs several strategies on the same universe and compares their in-sample performance.
+///
+/// Each strategy derives its weights from the whole return history, and the weights are then
+/// held constant over that same history to measure the return, volatility and Sharpe ratio
+/// the strategy would have achieved. As the weights are fitted to the data they are measured
+/// on, the results flatter the optimized strategies and are no forecast of their performance.
+///
+/// # Arguments
+///
+/// * `returns` - The daily returns (`&Array2<f64>`), with one row per day and one column per
+///   asset.
+/// * `strategies` - The strategies to compare.
+///
+/// # Returns
+///
+/// A `StrategyResult` for each strategy, in the order of `strategies`. A `SingleAsset`
+/// strategy whose index is not a column of `returns` is skipped. The vector is empty if there
+/// are no assets or fewer than two days of returns.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::strategy_comparison_svc::{compare_strategies, Strategy};
+/// use ndarray::array;
+///
+/// let returns = array![[0.01, 0.02], [-0.01, 0.0], [0.02, 0.01], [0.0, -0.01]];
+/// let results = compare_strategies(&returns, &[Strategy::EqualWeight, Strategy::SingleAsset(1)]);
+/// assert_eq!(results[0].weights, vec![0.5, 0.5]);
+/// assert_eq!(results[1].weights, vec![0.0, 1.0]);
+/// ```
+pub fn compare_strategies(returns: &Array2<f64>, strategies: &[Strategy]) -> Vec<StrategyResult> {
+    let (num_days, num_assets) = returns.dim();
+    if num_assets == 0 || num_days < 2 {
+        return Vec::new();
+    }
+
+    let mean_returns = returns.mean_axis(Axis(0)).unwrap_or_else(|| Array1::zeros(num_assets));
+    let cov = returns.t().cov(1.0).unwrap_or_else(|_| Array2::zeros((num_assets, num_assets)));
+
+    strategies
+        .iter()
+        .filter_map(|&strategy| {
+            let weights = match strategy {
+                Strategy::EqualWeight => vec![1.0 / num_assets as f64; num_assets],
+                Strategy::MeanVariance => {
+                    mean_variance_weights(&mean_returns, &cov.diag().to_owned())
+                },
+                Strategy::RiskParity => risk_parity_weights(&cov),
+                Strategy::SingleAsset(index) if index < num_assets => {
+                    (0..num_assets).map(|i| if i == index { 1.0 } else { 0.0 }).collect()
+                },
+                Strategy::SingleAsset(_) => return None,
+            };
+
+            let portfolio_returns = returns.dot(&Array1::from(weights.clone())).to_vec();
+            let annualized_return =
+                mean(&portfolio_returns).unwrap_or(f64::NAN) * TRADING_DAYS_PER_YEAR;
+            let annualized_volatility =
+                std_dev(&portfolio_returns, 1).unwrap_or(f64::NAN) * TRADING_DAYS_PER_YEAR.sqrt();
+            let sharpe_ratio = if annualized_volatility > 0.0 {
+                annualized_return / annualized_volatility
+            } else {
+                f64::NAN
+            };
+
+            Some(StrategyResult {
+                strategy,
+                weights,
+                annualized_return,
+                annualized_volatility,
+                sharpe_ratio,
+            })
+        })
+        .collect()
+}
+
+/// Formats the results of `compare_strategies` as a Markdown table.
+///
+/// The table has a row per strategy with its annualized return and volatility, as
+/// percentages, and its Sharpe ratio.
+///
+/// # Arguments
+///
+/// * `results` - The results to format.
+///
+/// # Returns
+///
+/// The lines of the table, joined by newlines, with a header row.
+pub fn format_comparison_table(results: &[StrategyResult]) -> String {
+    let mut lines = vec![
+        "| Strategy           | Return  | Volatility | Sharpe |".to_string(),
+        "|--------------------|---------|------------|--------|".to_string(),
+    ];
+    for result in results {
+        lines.push(format!(
+            "| {:18} | {:6.2}% | {:9.2}% | {:6.2} |",
+            result.strategy,
+            result.annualized_return * 100.0,
+            result.annualized_volatility * 100.0,
+            result.sharpe_ratio
+        ));
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,203 @@
+use crate::services::fetch_data_svc::Ohlcv;
+use chrono::{DateTime, Datelike, Duration, Utc, Weekday};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde_json::{json, Value};
+
+/// The number of trading days in a year, used to scale the drift and volatility.
+const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+/// The annualized drift of the synthetic price walk.
+const ANNUAL_DRIFT: f64 = 0.08;
+
+/// The annualized volatility of the synthetic price walk.
+const ANNUAL_VOLATILITY: f64 = 0.25;
+
+/// The largest distance of a synthetic high or low from the open and close, as a fraction.
+const MAX_INTRADAY_RANGE: f64 = 0.02;
+
+/// The average daily volume of a synthetic session.
+const AVERAGE_VOLUME: f64 = 1_000_000.0;
+
+/// Derives a stable seed from a ticker symbol using the 64-bit FNV-1a hash.
+fn ticker_seed(ticker: &str) -> u64 {
+    ticker.trim().to_uppercase().bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Draws a standard normal sample using the Box-Muller transform.
+pub(crate) fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Generates a deterministic synthetic series of daily closing prices for a ticker symbol.
+///
+/// The prices follow a geometric Brownian motion with an 8% annual drift and 25% annual
+/// volatility. The random generator is seeded from the ticker symbol, so the same ticker
+/// and number of days always produce the same series, while different tickers produce
+/// different series.
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol (e.g., "AAPL").
+/// * `num_days` - The number of daily closing prices to generate.
+///
+/// # Returns
+///
+/// A vector of `num_days` positive closing prices (`Vec<f64>`).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::synthetic_data_svc::synthetic_prices;
+///
+/// let prices = synthetic_prices("AAPL", 10);
+/// assert_eq!(prices.len(), 10);
+/// assert!(prices.iter().all(|&p| p > 0.0));
+/// assert_eq!(prices, synthetic_prices("AAPL", 10));
+/// ```
+pub fn synthetic_prices(ticker: &str, num_days: usize) -> Vec<f64> {
+    let seed = ticker_seed(ticker);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let dt = 1.0 / TRADING_DAYS_PER_YEAR;
+    let drift = (ANNUAL_DRIFT - 0.5 * ANNUAL_VOLATILITY.powi(2)) * dt;
+    let diffusion = ANNUAL_VOLATILITY * dt.sqrt();
+
+    // Start between 20 and 500 so that different tickers trade at different price levels
+    let mut price = 20.0 + (seed % 48_000) as f64 / 100.0;
+    (0..num_days)
+        .map(|day| {
+            if day > 0 {
+                price *= (drift + diffusion * standard_normal(&mut rng)).exp();
+            }
+            price
+        })
+        .collect()
+}
+
+/// Generates a synthetic Yahoo Finance chart response for a ticker symbol.
+///
+/// The response contains the `meta`, `timestamp`, and `indicators.quote[0].close` fields of
+/// the Yahoo Finance chart API, with closing prices taken from [`synthetic_prices`] and one
+/// timestamp per day ending today.
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol (e.g., "AAPL").
+/// * `num_days` - The number of daily closing prices to generate.
+///
+/// # Returns
+///
+/// A `serde_json::Value` shaped like a Yahoo Finance chart response.
+pub fn synthetic_chart_response(ticker: &str, num_days: usize) -> Value {
+    let closes = synthetic_prices(ticker, num_days);
+    let now = Utc::now();
+    let timestamps: Vec<i64> = (0..num_days)
+        .map(|day| (now - Duration::days((num_days - 1 - day) as i64)).timestamp())
+        .collect();
+
+    json!({
+        "chart": {
+            "result": [
+                {
+                    "meta": {
+                        "currency": "USD",
+                        "symbol": ticker,
+                        "regularMarketPrice": closes.last().copied().unwrap_or(0.0)
+                    },
+                    "timestamp": timestamps,
+                    "indicators": {
+                        "quote": [{ "close": closes }]
+                    }
+                }
+            ],
+            "error": null
+        }
+    })
+}
+
+/// Generates a deterministic synthetic series of daily closing prices dated on weekdays.
+///
+/// The prices are the same as those returned by [`synthetic_prices`]. They are dated on
+/// consecutive weekdays, with the last price on `end_date` (or the last weekday before it if
+/// `end_date` falls on a weekend).
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol (e.g., "AAPL").
+/// * `num_days` - The number of daily closing prices to generate.
+/// * `end_date` - The date of the last closing price.
+///
+/// # Returns
+///
+/// A vector of `num_days` `(timestamp, close)` pairs in chronological order.
+pub fn synthetic_dated_prices(
+    ticker: &str,
+    num_days: usize,
+    end_date: DateTime<Utc>,
+) -> Vec<(DateTime<Utc>, f64)> {
+    let mut dates = Vec::with_capacity(num_days);
+    let mut date = end_date;
+    while dates.len() < num_days {
+        if !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            dates.push(date);
+        }
+        date -= Duration::days(1);
+    }
+    dates.reverse();
+
+    dates.into_iter().zip(synthetic_prices(ticker, num_days)).collect()
+}
+
+/// Generates a deterministic synthetic series of daily sessions dated on weekdays.
+///
+/// The closing prices and dates are the same as those returned by [`synthetic_dated_prices`].
+/// Each session opens at the previous close (the first at its own close), its high and low
+/// extend up to 2% beyond the open and close, and its volume varies between half and one and
+/// a half times one million shares. The intraday values are seeded from the ticker symbol,
+/// like the closing prices.
+///
+/// # Arguments
+///
+/// * `ticker` - A string slice that holds the ticker symbol (e.g., "AAPL").
+/// * `num_days` - The number of daily sessions to generate.
+/// * `end_date` - The date of the last session.
+///
+/// # Returns
+///
+/// A vector of `num_days` `Ohlcv` sessions in chronological order.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Utc;
+/// use nalufx::services::synthetic_data_svc::synthetic_ohlcv;
+///
+/// let sessions = synthetic_ohlcv("AAPL", 10, Utc::now());
+/// assert_eq!(sessions.len(), 10);
+/// assert!(sessions.iter().all(|s| s.low <= s.open.min(s.close) && s.high >= s.open.max(s.close)));
+/// ```
+pub fn synthetic_ohlcv(ticker: &str, num_days: usize, end_date: DateTime<Utc>) -> Vec<Ohlcv> {
+    // Use a different stream from the closing prices so the intraday values do not mirror them
+    let mut rng = StdRng::seed_from_u64(ticker_seed(ticker).rotate_left(32));
+    let mut previous_close = None;
+
+    synthetic_dated_prices(ticker, num_days, end_date)
+        .into_iter()
+        .map(|(timestamp, close)| {
+            let open = previous_close.unwrap_or(close);
+            previous_close = Some(close);
+            Ohlcv {
+                timestamp,
+                open,
+                high: open.max(close) * (1.0 + MAX_INTRADAY_RANGE * rng.gen::<f64>()),
+                low: open.min(close) * (1.0 - MAX_INTRADAY_RANGE * rng.gen::<f64>()),
+                close,
+                volume: (AVERAGE_VOLUME * (0.5 + rng.gen::<f64>())).round(),
+            }
+        })
+        .collect()
+}
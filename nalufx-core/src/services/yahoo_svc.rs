@@ -0,0 +1,194 @@
+use crate::{config::user_agent, errors::NaluFxError};
+use log::{info, warn};
+use reqwest::{
+    header::{COOKIE, SET_COOKIE},
+    Client, Response, StatusCode,
+};
+use std::sync::OnceLock;
+use tokio::sync::Mutex;
+
+/// The URL that sets the Yahoo Finance session cookie.
+pub const YAHOO_COOKIE_URL: &str = "https://fc.yahoo.com";
+
+/// The URL that returns the crumb of a Yahoo Finance session.
+pub const YAHOO_CRUMB_URL: &str = "https://query1.finance.yahoo.com/v1/test/getcrumb";
+
+/// The cookie and crumb of a Yahoo Finance session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Session {
+    /// The `Cookie` header sent with each request.
+    cookie: String,
+    /// The crumb added to the query of each request.
+    crumb: String,
+}
+
+/// Represents an HTTP client for the Yahoo Finance API.
+///
+/// Yahoo Finance rejects many requests that do not carry a session cookie and the matching
+/// crumb. The client acquires them with a two-step handshake, the first time a request is sent:
+///
+/// 1. `GET` the cookie URL, which responds with a `Set-Cookie` header (and usually a `404`).
+/// 2. `GET` the crumb URL with the cookie, which responds with the crumb as plain text.
+///
+/// The session is then reused by every request, and acquired again if Yahoo Finance responds
+/// with `401 Unauthorized`. If the handshake fails, a warning is logged and the request is sent
+/// without a session, which some endpoints still accept.
+///
+/// Every request carries the configured `User-Agent` header (see
+/// [`user_agent`](crate::config::user_agent)).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::services::yahoo_svc::YahooClient;
+///
+/// let client = YahooClient::new("Mozilla/5.0 (X11; Linux x86_64)").unwrap();
+/// assert_eq!(client.user_agent(), "Mozilla/5.0 (X11; Linux x86_64)");
+/// ```
+#[derive(Debug)]
+pub struct YahooClient {
+    client: Client,
+    user_agent: String,
+    cookie_url: String,
+    crumb_url: String,
+    session: Mutex<Option<Session>>,
+}
+
+impl YahooClient {
+    /// Creates a new `YahooClient` instance, which performs the handshake on the Yahoo Finance
+    /// URLs.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_agent` - The `User-Agent` header sent with each request.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NaluFxError::HttpRequestError` if the HTTP client cannot be built.
+    pub fn new(user_agent: &str) -> Result<Self, NaluFxError> {
+        Ok(Self {
+            client: Client::builder().user_agent(user_agent).build()?,
+            user_agent: user_agent.to_string(),
+            cookie_url: YAHOO_COOKIE_URL.to_string(),
+            crumb_url: YAHOO_CRUMB_URL.to_string(),
+            session: Mutex::new(None),
+        })
+    }
+
+    /// Sets the URLs of the handshake, e.g. to test the client against a mock server.
+    ///
+    /// # Arguments
+    ///
+    /// * `cookie_url` - The URL that sets the session cookie.
+    /// * `crumb_url` - The URL that returns the crumb.
+    pub fn with_handshake_urls(mut self, cookie_url: &str, crumb_url: &str) -> Self {
+        self.cookie_url = cookie_url.to_string();
+        self.crumb_url = crumb_url.to_string();
+        self
+    }
+
+    /// Returns the `User-Agent` header sent with each request.
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Sends a `GET` request to a Yahoo Finance URL with the session cookie and crumb.
+    ///
+    /// The handshake is performed before the first request, and again once if the response is
+    /// `401 Unauthorized`, since the session may have expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the request. The crumb is added to its query.
+    ///
+    /// # Returns
+    ///
+    /// The response, whatever its status, or a `NaluFxError::HttpRequestError` if the request
+    /// cannot be sent.
+    pub async fn get(&self, url: &str) -> Result<Response, NaluFxError> {
+        let response = self.send(url).await?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        info!("Yahoo Finance session rejected, repeating the handshake");
+        *self.session.lock().await = None;
+        self.send(url).await
+    }
+
+    /// Sends a request with the current session, acquiring one first if there is none.
+    async fn send(&self, url: &str) -> Result<Response, NaluFxError> {
+        let mut request = self.client.get(url);
+        match self.session().await {
+            Ok(session) => {
+                request = request.header(COOKIE, session.cookie).query(&[("crumb", session.crumb)]);
+            },
+            Err(e) => warn!("Sending the request without a Yahoo Finance session: {}", e),
+        }
+        Ok(request.send().await?)
+    }
+
+    /// Returns the current session, performing the handshake if there is none.
+    ///
+    /// The lock is held during the handshake, so concurrent requests wait for a single
+    /// handshake instead of each performing their own.
+    async fn session(&self) -> Result<Session, NaluFxError> {
+        let mut session = self.session.lock().await;
+        if let Some(session) = session.as_ref() {
+            return Ok(session.clone());
+        }
+        let acquired = self.handshake().await?;
+        *session = Some(acquired.clone());
+        Ok(acquired)
+    }
+
+    /// Performs the two-step handshake: fetches the session cookie, then its crumb.
+    async fn handshake(&self) -> Result<Session, NaluFxError> {
+        let response = self.client.get(&self.cookie_url).send().await?;
+        let cookie = response
+            .headers()
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| value.split(';').next())
+            .map(str::trim)
+            .filter(|pair| pair.contains('='))
+            .collect::<Vec<_>>()
+            .join("; ");
+        if cookie.is_empty() {
+            return Err(NaluFxError::NaluFxError(
+                "Yahoo Finance handshake failed: no session cookie".to_string(),
+            ));
+        }
+
+        let response = self.client.get(&self.crumb_url).header(COOKIE, &cookie).send().await?;
+        let status = response.status();
+        let crumb = response.text().await?.trim().to_string();
+        if !status.is_success() || crumb.is_empty() || crumb.contains(char::is_whitespace) {
+            return Err(NaluFxError::NaluFxError(format!(
+                "Yahoo Finance handshake failed: no crumb ({})",
+                status
+            )));
+        }
+
+        info!("Acquired a Yahoo Finance session");
+        Ok(Session { cookie, crumb })
+    }
+}
+
+/// Returns the `YahooClient` shared by the Yahoo Finance fetchers.
+///
+/// The client is created on first use with the configured user agent, so that the handshake is
+/// performed once per process rather than once per request.
+///
+/// # Errors
+///
+/// Returns `NaluFxError::HttpRequestError` if the HTTP client cannot be built.
+pub fn shared_client() -> Result<&'static YahooClient, NaluFxError> {
+    static CLIENT: OnceLock<YahooClient> = OnceLock::new();
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+    let client = YahooClient::new(&user_agent())?;
+    Ok(CLIENT.get_or_init(|| client))
+}
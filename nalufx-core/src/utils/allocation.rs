@@ -0,0 +1,207 @@
+use crate::errors::AllocationError;
+
+/// Clamps negative allocations to zero and scales the rest to sum to 1.
+///
+/// `calculate_optimal_allocation` may return negative allocations, which cannot be acted upon
+/// without short selling. They are ignored, and the positive allocations are scaled so that the
+/// whole investment is allocated.
+///
+/// # Arguments
+///
+/// * `allocs` - The allocations to normalize.
+///
+/// # Returns
+///
+/// The normalized allocations, in the order of `allocs`.
+///
+/// # Errors
+///
+/// * `AllocationError::EmptyInput` - If `allocs` is empty.
+/// * `AllocationError::InvalidData` - If `allocs` contains a value that is not finite.
+/// * `AllocationError::ZeroTotalAllocation` - If no allocation is positive.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::errors::AllocationError;
+/// use nalufx::utils::allocation::normalize_non_negative;
+///
+/// assert_eq!(normalize_non_negative(vec![3.0, -1.0, 1.0]), Ok(vec![0.75, 0.0, 0.25]));
+/// assert_eq!(
+///     normalize_non_negative(vec![-0.5, -0.5]),
+///     Err(AllocationError::ZeroTotalAllocation)
+/// );
+/// ```
+pub fn normalize_non_negative(allocs: Vec<f64>) -> Result<Vec<f64>, AllocationError> {
+    if allocs.is_empty() {
+        return Err(AllocationError::EmptyInput);
+    }
+    if allocs.iter().any(|alloc| !alloc.is_finite()) {
+        return Err(AllocationError::InvalidData);
+    }
+
+    let allocs: Vec<f64> = allocs.into_iter().map(|alloc| alloc.max(0.0)).collect();
+    let total: f64 = allocs.iter().sum();
+    if total <= 0.0 {
+        return Err(AllocationError::ZeroTotalAllocation);
+    }
+    Ok(allocs.into_iter().map(|alloc| alloc / total).collect())
+}
+
+/// The tolerance used when checking that weight constraints can sum to 1.
+const CONSTRAINT_TOLERANCE: f64 = 1e-9;
+
+/// Represents the bounds of the weight of each asset of an allocation.
+///
+/// The default constraints allow any weight between 0 and 1, so they only rule out short
+/// selling. Lowering `max_weight` caps the concentration in any one asset.
+///
+/// # Fields
+///
+/// * `min_weight` - The smallest weight of an asset.
+/// * `max_weight` - The largest weight of an asset.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::allocation::Constraints;
+///
+/// let constraints = Constraints { max_weight: 0.4, ..Constraints::default() };
+/// assert!(constraints.validate(3).is_ok());
+/// assert!(constraints.validate(2).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+    /// The smallest weight of an asset.
+    pub min_weight: f64,
+    /// The largest weight of an asset.
+    pub max_weight: f64,
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Constraints { min_weight: 0.0, max_weight: 1.0 }
+    }
+}
+
+impl Constraints {
+    /// Checks that `count` weights can meet the constraints and sum to 1.
+    ///
+    /// The bounds must be finite with `0 <= min_weight <= max_weight`, and
+    /// `min_weight * count <= 1 <= max_weight * count`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AllocationError::InfeasibleConstraints` if the constraints cannot be met.
+    pub fn validate(&self, count: usize) -> Result<(), AllocationError> {
+        let Constraints { min_weight, max_weight } = *self;
+        let n = count as f64;
+        let feasible = min_weight.is_finite()
+            && max_weight.is_finite()
+            && (0.0..=max_weight).contains(&min_weight)
+            && min_weight * n <= 1.0 + CONSTRAINT_TOLERANCE
+            && max_weight * n >= 1.0 - CONSTRAINT_TOLERANCE;
+        if feasible {
+            Ok(())
+        } else {
+            Err(AllocationError::InfeasibleConstraints { min_weight, max_weight, count })
+        }
+    }
+}
+
+/// Projects weights onto the `Constraints`, so that each lies between `min_weight` and
+/// `max_weight` and they sum to 1.
+///
+/// Negative weights are clamped to zero and the weights are scaled to sum to 1, or made equal
+/// if none is positive. The weights are then water-filled: the weights above `max_weight` are
+/// capped and those below `min_weight` raised to it, and the remaining weights are scaled to
+/// take up the difference, until every weight meets the constraints. The weights that are not
+/// capped or raised keep their proportions.
+///
+/// # Arguments
+///
+/// * `weights` - The weights to constrain.
+/// * `constraints` - The bounds of each weight.
+///
+/// # Returns
+///
+/// The constrained weights, in the order of `weights`.
+///
+/// # Errors
+///
+/// * `AllocationError::EmptyInput` - If `weights` is empty.
+/// * `AllocationError::InvalidData` - If `weights` contains a value that is not finite.
+/// * `AllocationError::InfeasibleConstraints` - If the constraints cannot be met (see
+///   [`Constraints::validate`]).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::allocation::{apply_constraints, Constraints};
+///
+/// let constraints = Constraints { max_weight: 0.5, ..Constraints::default() };
+/// let weights = apply_constraints(&[0.8, 0.15, 0.05], &constraints).unwrap();
+/// assert!((weights[0] - 0.5).abs() < 1e-12);
+/// assert!((weights[1] - 0.375).abs() < 1e-12);
+/// assert!((weights[2] - 0.125).abs() < 1e-12);
+/// ```
+pub fn apply_constraints(
+    weights: &[f64],
+    constraints: &Constraints,
+) -> Result<Vec<f64>, AllocationError> {
+    if weights.is_empty() {
+        return Err(AllocationError::EmptyInput);
+    }
+    if weights.iter().any(|weight| !weight.is_finite()) {
+        return Err(AllocationError::InvalidData);
+    }
+    constraints.validate(weights.len())?;
+
+    let Constraints { min_weight, max_weight } = *constraints;
+    let count = weights.len();
+    let mut weights = normalize_non_negative(weights.to_vec())
+        .unwrap_or_else(|_| vec![1.0 / count as f64; count]);
+    let mut fixed = vec![false; count];
+
+    // Each pass fixes at least one weight at a bound, so there are at most `count` passes.
+    loop {
+        let remaining = 1.0 - (0..count).filter(|&i| fixed[i]).map(|i| weights[i]).sum::<f64>();
+        let free: Vec<usize> = (0..count).filter(|&i| !fixed[i]).collect();
+        if free.is_empty() {
+            break;
+        }
+        let total: f64 = free.iter().map(|&i| weights[i]).sum();
+        for &i in &free {
+            weights[i] = if total > 0.0 {
+                weights[i] / total * remaining
+            } else {
+                remaining / free.len() as f64
+            };
+        }
+
+        // Cap the weights above the maximum first, as the weight they shed may lift the
+        // weights below the minimum.
+        let over: Vec<usize> = free
+            .iter()
+            .copied()
+            .filter(|&i| weights[i] > max_weight + CONSTRAINT_TOLERANCE)
+            .collect();
+        let violations = if over.is_empty() {
+            free.iter()
+                .copied()
+                .filter(|&i| weights[i] < min_weight - CONSTRAINT_TOLERANCE)
+                .collect()
+        } else {
+            over
+        };
+        if violations.is_empty() {
+            break;
+        }
+        for i in violations {
+            weights[i] = weights[i].clamp(min_weight, max_weight);
+            fixed[i] = true;
+        }
+    }
+
+    Ok(weights)
+}
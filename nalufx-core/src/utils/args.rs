@@ -0,0 +1,291 @@
+use crate::{
+    config::default_llm,
+    errors::NaluFxError,
+    utils::{
+        indicators::{
+            EmaSeed, IndicatorWindows, DEFAULT_EMA_WINDOW, DEFAULT_MACD_LONG_WINDOW,
+            DEFAULT_MACD_SHORT_WINDOW, DEFAULT_MACD_SIGNAL_WINDOW, DEFAULT_RSI_WINDOW,
+        },
+        input::get_input,
+        reports::ReportSink,
+        validation::{validate_date, validate_positive_float, validate_ticker},
+    },
+};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Returns the value of a command-line argument, or prompts the user for it if it is absent.
+///
+/// This lets an example run non-interactively when every argument is given, and fall back to
+/// the interactive prompts otherwise.
+///
+/// # Arguments
+///
+/// * `value` - The value of the argument, if it was given.
+/// * `prompt` - The message displayed to the user if the argument is absent.
+///
+/// # Errors
+///
+/// Returns an `NaluFxError::InputError` if the argument is absent and an error occurs while
+/// reading the input.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::args::arg_or_prompt;
+///
+/// let ticker = arg_or_prompt(Some(" AAPL ".to_string()), "Enter the ticker symbol:").unwrap();
+/// assert_eq!(ticker, "AAPL");
+/// ```
+pub fn arg_or_prompt(value: Option<String>, prompt: &str) -> Result<String, NaluFxError> {
+    match value {
+        Some(value) => Ok(value.trim().to_string()),
+        None => get_input(prompt),
+    }
+}
+
+/// Represents the command-line arguments of the bellwether stock analysis example.
+///
+/// Every argument is optional: the example prompts for the missing ones (see
+/// [`BellwetherArgs::resolve`]).
+///
+/// # Fields
+///
+/// * `llm` - The LLM to use (e.g., "openai" or "ollama"), prompted for only if `DEFAULT_LLM`
+///   is not set either (see [`default_llm`]).
+/// * `ticker` - The ticker symbol of the bellwether stock.
+/// * `investment` - The initial investment amount.
+/// * `start` - The start date of the analysis period (YYYY-MM-DD).
+/// * `end` - The end date of the analysis period (YYYY-MM-DD).
+/// * `output_dir` - The directory to write the report to.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Parser;
+/// use nalufx::utils::args::BellwetherArgs;
+///
+/// let args = BellwetherArgs::try_parse_from([
+///     "bellwether_stock_analysis",
+///     "--ticker",
+///     "AAPL",
+///     "--investment",
+///     "10000",
+/// ])
+/// .unwrap();
+/// assert_eq!(args.ticker.as_deref(), Some("AAPL"));
+/// assert!(args.start.is_none());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Parser)]
+#[command(about = "Analyze a bellwether stock and generate investment recommendations")]
+pub struct BellwetherArgs {
+    /// The LLM to use (e.g., openai, claude, gemini, llama, mistral, ollama).
+    #[arg(long)]
+    pub llm: Option<String>,
+    /// The ticker symbol of the bellwether stock.
+    #[arg(long)]
+    pub ticker: Option<String>,
+    /// The initial investment amount.
+    #[arg(long, allow_hyphen_values = true)]
+    pub investment: Option<String>,
+    /// The start date of the analysis period (YYYY-MM-DD).
+    #[arg(long)]
+    pub start: Option<String>,
+    /// The end date of the analysis period (YYYY-MM-DD).
+    #[arg(long)]
+    pub end: Option<String>,
+    /// The directory to write the report to (defaults to NALUFX_REPORTS_DIR or ./reports).
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+}
+
+/// Represents the validated inputs of the bellwether stock analysis example.
+///
+/// # Fields
+///
+/// * `llm` - The LLM to use, in lowercase.
+/// * `ticker` - The ticker symbol of the bellwether stock.
+/// * `investment` - The initial investment amount, positive and finite.
+/// * `start_date` - The start date of the analysis period (YYYY-MM-DD).
+/// * `end_date` - The end date of the analysis period (YYYY-MM-DD).
+/// * `output_dir` - The directory to write the report to, if it was given.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BellwetherInputs {
+    /// The LLM to use.
+    pub llm: String,
+    /// The ticker symbol of the bellwether stock.
+    pub ticker: String,
+    /// The initial investment amount.
+    pub investment: f64,
+    /// The start date of the analysis period.
+    pub start_date: String,
+    /// The end date of the analysis period.
+    pub end_date: String,
+    /// The directory to write the report to.
+    pub output_dir: Option<PathBuf>,
+}
+
+impl BellwetherInputs {
+    /// Returns the `ReportSink` writing to the output directory, or to the directory set by
+    /// the environment if none was given (see [`ReportSink::from_output_dir`]).
+    pub fn report_sink(&self) -> ReportSink {
+        ReportSink::from_output_dir(self.output_dir.clone())
+    }
+}
+
+impl BellwetherArgs {
+    /// Prompts for the missing arguments and validates every input.
+    ///
+    /// The arguments are validated as the interactive inputs are: the ticker symbol with
+    /// `validate_ticker`, the investment with `validate_positive_float` and the dates with
+    /// `validate_date`. No prompt is shown if every argument is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `NaluFxError::InputError` if an error occurs while reading a prompted input,
+    /// an `NaluFxError::InvalidTicker` if the ticker symbol is invalid, or an
+    /// `NaluFxError::InvalidInput` with the reason if another input is invalid.
+    pub fn resolve(self) -> Result<BellwetherInputs, NaluFxError> {
+        let llm = arg_or_prompt(
+            self.llm.or_else(default_llm),
+            "Enter the LLM to use (e.g., openai, claude, gemini, llama, mistral, ollama):",
+        )?
+        .to_lowercase();
+
+        let ticker = arg_or_prompt(self.ticker, "Enter the ticker symbol for a bellwether stock:")?;
+        let ticker = validate_ticker(&ticker)?.to_string();
+
+        let investment = arg_or_prompt(self.investment, "Enter the initial investment amount:")?;
+        let investment = validate_positive_float(&investment)?;
+
+        let start_date = arg_or_prompt(self.start, "Enter the start date (YYYY-MM-DD):")?;
+        let _ = validate_date(&start_date)?;
+        let end_date = arg_or_prompt(self.end, "Enter the end date (YYYY-MM-DD):")?;
+        let _ = validate_date(&end_date)?;
+
+        Ok(BellwetherInputs {
+            llm,
+            ticker,
+            investment,
+            start_date,
+            end_date,
+            output_dir: self.output_dir,
+        })
+    }
+}
+
+/// Represents the command-line arguments of the examples that write a report.
+///
+/// # Fields
+///
+/// * `output_dir` - The directory to write the reports to.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Parser;
+/// use nalufx::utils::args::ReportArgs;
+/// use std::path::Path;
+///
+/// let args = ReportArgs::try_parse_from(["example", "--output-dir", "/tmp/reports"]).unwrap();
+/// assert_eq!(args.report_sink().dir(), Path::new("/tmp/reports"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Parser)]
+#[command(about = "Generate a report, written to a timestamped file")]
+pub struct ReportArgs {
+    /// The directory to write the reports to (defaults to NALUFX_REPORTS_DIR or ./reports).
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+}
+
+impl ReportArgs {
+    /// Returns the `ReportSink` writing to the output directory, or to the directory set by
+    /// the environment if none was given (see [`ReportSink::from_output_dir`]).
+    pub fn report_sink(self) -> ReportSink {
+        ReportSink::from_output_dir(self.output_dir)
+    }
+}
+
+/// Represents the command-line arguments of the technical analysis example.
+///
+/// Every window defaults to its usual value (see [`IndicatorWindows`]).
+///
+/// # Fields
+///
+/// * `ema_window` - The window of the exponential moving average.
+/// * `rsi_window` - The window of the relative strength index.
+/// * `macd_short` - The window of the short EMA of the MACD.
+/// * `macd_long` - The window of the long EMA of the MACD.
+/// * `macd_signal` - The window of the MACD signal line.
+/// * `ema_seed` - How the EMAs are seeded (see [`EmaSeed`]), `first-value` by default.
+/// * `cache_llm` - Whether the LLM report of an identical prompt is reused from the LLM response
+///   cache, set by `LLM_CACHE_DIR` and `LLM_CACHE_TTL_SECS`, `false` by default.
+/// * `output_dir` - The directory to write the report to.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Parser;
+/// use nalufx::utils::args::TechnicalAnalysisArgs;
+///
+/// let args =
+///     TechnicalAnalysisArgs::try_parse_from(["technical_analysis_indicators", "--rsi-window", "7"])
+///         .unwrap();
+/// let windows = args.windows().unwrap();
+/// assert_eq!((windows.rsi, windows.macd_long), (7, 26));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+#[command(about = "Analyze the technical indicators of a stock")]
+pub struct TechnicalAnalysisArgs {
+    /// The window of the exponential moving average.
+    #[arg(long, default_value_t = DEFAULT_EMA_WINDOW)]
+    pub ema_window: usize,
+    /// The window of the relative strength index.
+    #[arg(long, default_value_t = DEFAULT_RSI_WINDOW)]
+    pub rsi_window: usize,
+    /// The window of the short EMA of the MACD, shorter than the long window.
+    #[arg(long, default_value_t = DEFAULT_MACD_SHORT_WINDOW)]
+    pub macd_short: usize,
+    /// The window of the long EMA of the MACD.
+    #[arg(long, default_value_t = DEFAULT_MACD_LONG_WINDOW)]
+    pub macd_long: usize,
+    /// The window of the MACD signal line.
+    #[arg(long, default_value_t = DEFAULT_MACD_SIGNAL_WINDOW)]
+    pub macd_signal: usize,
+    /// How the EMAs are seeded: first-value or sma, which makes the early MACD more accurate.
+    #[arg(long, default_value_t = EmaSeed::default())]
+    pub ema_seed: EmaSeed,
+    /// Reuse the LLM report of an identical prompt from the cache in LLM_CACHE_DIR, for up to
+    /// LLM_CACHE_TTL_SECS seconds, instead of requesting it again.
+    #[arg(long)]
+    pub cache_llm: bool,
+    /// The directory to write the report to (defaults to NALUFX_REPORTS_DIR or ./reports).
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+}
+
+impl TechnicalAnalysisArgs {
+    /// Returns the validated indicator windows (see [`IndicatorWindows::validate`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NaluFxError::TechnicalAnalysisError` if a window is zero or the short MACD
+    /// window is not shorter than the long one.
+    pub fn windows(&self) -> Result<IndicatorWindows, NaluFxError> {
+        let windows = IndicatorWindows {
+            ema: self.ema_window,
+            rsi: self.rsi_window,
+            macd_short: self.macd_short,
+            macd_long: self.macd_long,
+            macd_signal: self.macd_signal,
+        };
+        windows.validate()?;
+        Ok(windows)
+    }
+
+    /// Returns the `ReportSink` writing to the output directory, or to the directory set by
+    /// the environment if none was given (see [`ReportSink::from_output_dir`]).
+    pub fn report_sink(&self) -> ReportSink {
+        ReportSink::from_output_dir(self.output_dir.clone())
+    }
+}
@@ -0,0 +1,137 @@
+use crate::config::DEFAULT_FETCH_CONCURRENCY;
+use futures::future::join_all;
+use std::{
+    fmt,
+    future::Future,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use tokio::sync::Semaphore;
+
+/// A progress callback, called with the number of completed items and the total number of
+/// items.
+type ProgressCallback = Box<dyn Fn(usize, usize) + Send + Sync>;
+
+/// Represents the options of a batch operation.
+///
+/// # Fields
+///
+/// * `concurrency` - The largest number of items processed at the same time, at least 1.
+/// * `progress` - The callback called each time an item completes, if any.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::batch::BatchOptions;
+///
+/// let options = BatchOptions::new(8)
+///     .with_progress(|completed, total| eprintln!("{}/{} tickers fetched", completed, total));
+/// assert_eq!(options.concurrency(), 8);
+/// ```
+pub struct BatchOptions {
+    concurrency: usize,
+    progress: Option<ProgressCallback>,
+}
+
+impl BatchOptions {
+    /// Creates a new `BatchOptions` instance without a progress callback.
+    ///
+    /// # Arguments
+    ///
+    /// * `concurrency` - The largest number of items processed at the same time. `0` is
+    ///   treated as `1`.
+    pub fn new(concurrency: usize) -> Self {
+        Self { concurrency: concurrency.max(1), progress: None }
+    }
+
+    /// Sets the callback called each time an item completes.
+    ///
+    /// The callback receives the number of completed items, from 1 to the total, and the total
+    /// number of items, so it can report progress such as "3/20 tickers fetched".
+    ///
+    /// # Arguments
+    ///
+    /// * `progress` - The progress callback.
+    pub fn with_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(usize, usize) + Send + Sync + 'static,
+    {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Returns the largest number of items processed at the same time.
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self::new(DEFAULT_FETCH_CONCURRENCY)
+    }
+}
+
+impl fmt::Debug for BatchOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchOptions")
+            .field("concurrency", &self.concurrency)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// Runs an asynchronous operation on each item, with at most `options.concurrency()` items in
+/// progress at the same time.
+///
+/// The operations run concurrently on the current task, bounded by a semaphore, so they do not
+/// need to be `Send`. The progress callback, if any, is called once per completed item, in the
+/// order the items complete.
+///
+/// # Arguments
+///
+/// * `items` - The items to process.
+/// * `options` - The concurrency limit and progress callback.
+/// * `operation` - The asynchronous operation to run on each item.
+///
+/// # Returns
+///
+/// The output of the operation for each item, in the order of `items`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::batch::{run_batch, BatchOptions};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let tickers = ["AAPL", "MSFT", "GOOGL"];
+///     let lengths =
+///         run_batch(&tickers, &BatchOptions::new(2), |ticker| async move { ticker.len() }).await;
+///     assert_eq!(lengths, vec![4, 4, 5]);
+/// }
+/// ```
+pub async fn run_batch<'a, I, T, F, Fut>(
+    items: &'a [I],
+    options: &BatchOptions,
+    operation: F,
+) -> Vec<T>
+where
+    F: Fn(&'a I) -> Fut,
+    Fut: Future<Output = T>,
+{
+    let semaphore = Semaphore::new(options.concurrency);
+    let completed = AtomicUsize::new(0);
+    let total = items.len();
+
+    join_all(items.iter().map(|item| async {
+        // The semaphore is never closed, so acquiring a permit cannot fail.
+        let _permit = semaphore.acquire().await.ok();
+        let output = operation(item).await;
+        let done = completed.fetch_add(1, Ordering::SeqCst) + 1;
+        if let Some(progress) = &options.progress {
+            progress(done, total);
+        }
+        output
+    }))
+    .await
+}
@@ -0,0 +1,204 @@
+use crate::config::{benchmark_ticker, benchmarks};
+
+/// Represents the asset class of a fund, which selects the benchmark it is measured against.
+///
+/// # Variants
+///
+/// * `UsLargeCap` - US large-cap equities, measured against the S&P 500 (`SPY`).
+/// * `UsSmallCap` - US small-cap equities, measured against the Russell 2000 (`IWM`).
+/// * `Technology` - Technology and semiconductor equities, measured against `XLK`.
+/// * `Energy` - Oil and gas equities, measured against `XLE`.
+/// * `CleanEnergy` - Clean and renewable energy equities, measured against the energy sector
+///   (`XLE`).
+/// * `Financials` - Bank and financial equities, measured against `XLF`.
+/// * `HealthCare` - Health care and biotechnology equities, measured against `XLV`.
+/// * `DevelopedMarkets` - Equities of developed markets outside the US, measured against `EFA`.
+/// * `EmergingMarkets` - Emerging market equities, measured against `EEM`.
+/// * `Bonds` - Investment-grade and high-yield bonds, measured against `AGG`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AssetClass {
+    /// US large-cap equities.
+    UsLargeCap,
+    /// US small-cap equities.
+    UsSmallCap,
+    /// Technology and semiconductor equities.
+    Technology,
+    /// Oil and gas equities.
+    Energy,
+    /// Clean and renewable energy equities.
+    CleanEnergy,
+    /// Bank and financial equities.
+    Financials,
+    /// Health care and biotechnology equities.
+    HealthCare,
+    /// Equities of developed markets outside the US.
+    DevelopedMarkets,
+    /// Emerging market equities.
+    EmergingMarkets,
+    /// Investment-grade and high-yield bonds.
+    Bonds,
+}
+
+/// The asset classes of well-known funds.
+const FUND_ASSET_CLASSES: &[(&str, AssetClass)] = &[
+    ("VOO", AssetClass::UsLargeCap),
+    ("IVV", AssetClass::UsLargeCap),
+    ("VTI", AssetClass::UsLargeCap),
+    ("ESGU", AssetClass::UsLargeCap),
+    ("IJR", AssetClass::UsSmallCap),
+    ("VB", AssetClass::UsSmallCap),
+    ("QQQ", AssetClass::Technology),
+    ("VGT", AssetClass::Technology),
+    ("SMH", AssetClass::Technology),
+    ("SOXX", AssetClass::Technology),
+    ("IGV", AssetClass::Technology),
+    ("VDE", AssetClass::Energy),
+    ("XOP", AssetClass::Energy),
+    ("OIH", AssetClass::Energy),
+    ("ICLN", AssetClass::CleanEnergy),
+    ("TAN", AssetClass::CleanEnergy),
+    ("PBW", AssetClass::CleanEnergy),
+    ("QCLN", AssetClass::CleanEnergy),
+    ("KBE", AssetClass::Financials),
+    ("KRE", AssetClass::Financials),
+    ("VFH", AssetClass::Financials),
+    ("IBB", AssetClass::HealthCare),
+    ("XBI", AssetClass::HealthCare),
+    ("VHT", AssetClass::HealthCare),
+    ("VEA", AssetClass::DevelopedMarkets),
+    ("IEFA", AssetClass::DevelopedMarkets),
+    ("VWO", AssetClass::EmergingMarkets),
+    ("IEMG", AssetClass::EmergingMarkets),
+    ("BND", AssetClass::Bonds),
+    ("TLT", AssetClass::Bonds),
+    ("IEF", AssetClass::Bonds),
+    ("LQD", AssetClass::Bonds),
+    ("HYG", AssetClass::Bonds),
+];
+
+impl AssetClass {
+    /// Returns the asset class of a well-known fund, or `None` for other ticker symbols.
+    ///
+    /// # Arguments
+    ///
+    /// * `ticker` - The ticker symbol, in any case.
+    pub fn of(ticker: &str) -> Option<Self> {
+        let ticker = ticker.trim().to_uppercase();
+        FUND_ASSET_CLASSES.iter().find(|(fund, _)| *fund == ticker).map(|&(_, class)| class)
+    }
+
+    /// Returns the name of the asset class, used as a key in `NALUFX_BENCHMARKS`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::UsLargeCap => "US_LARGE_CAP",
+            Self::UsSmallCap => "US_SMALL_CAP",
+            Self::Technology => "TECHNOLOGY",
+            Self::Energy => "ENERGY",
+            Self::CleanEnergy => "CLEAN_ENERGY",
+            Self::Financials => "FINANCIALS",
+            Self::HealthCare => "HEALTH_CARE",
+            Self::DevelopedMarkets => "DEVELOPED_MARKETS",
+            Self::EmergingMarkets => "EMERGING_MARKETS",
+            Self::Bonds => "BONDS",
+        }
+    }
+
+    /// Returns the built-in benchmark of the asset class.
+    pub fn benchmark(self) -> &'static str {
+        match self {
+            Self::UsLargeCap => "SPY",
+            Self::UsSmallCap => "IWM",
+            Self::Technology => "XLK",
+            Self::Energy | Self::CleanEnergy => "XLE",
+            Self::Financials => "XLF",
+            Self::HealthCare => "XLV",
+            Self::DevelopedMarkets => "EFA",
+            Self::EmergingMarkets => "EEM",
+            Self::Bonds => "AGG",
+        }
+    }
+}
+
+/// Selects the benchmark to measure a ticker symbol against.
+///
+/// The benchmark is the first of:
+///
+/// 1. `requested`, if it is given and not blank.
+/// 2. The benchmark configured for the ticker symbol in `NALUFX_BENCHMARKS` (see
+///    [`benchmarks`](crate::config::benchmarks)).
+/// 3. The benchmark configured for its asset class in `NALUFX_BENCHMARKS`, or else the
+///    built-in benchmark of its asset class (see [`AssetClass`]).
+/// 4. The default benchmark (see [`benchmark_ticker`](crate::config::benchmark_ticker)),
+///    `SPY` unless `NALUFX_BENCHMARK_TICKER` is set.
+///
+/// A selected benchmark that is the ticker symbol itself is skipped, as a fund compared with
+/// itself tells nothing.
+///
+/// # Arguments
+///
+/// * `ticker` - The ticker symbol to measure.
+/// * `requested` - The benchmark chosen by the user, if any.
+///
+/// # Returns
+///
+/// The ticker symbol of the benchmark, in upper case.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::benchmark::resolve_benchmark;
+///
+/// assert_eq!(resolve_benchmark("ICLN", None), "XLE");
+/// assert_eq!(resolve_benchmark("ICLN", Some("tan")), "TAN");
+/// ```
+pub fn resolve_benchmark(ticker: &str, requested: Option<&str>) -> String {
+    if let Some(requested) = requested.map(str::trim).filter(|requested| !requested.is_empty()) {
+        return requested.to_uppercase();
+    }
+
+    let ticker = ticker.trim().to_uppercase();
+    let configured = benchmarks().unwrap_or_default();
+    let asset_class = AssetClass::of(&ticker);
+    let candidates = [
+        configured.get(&ticker).cloned(),
+        asset_class.and_then(|class| configured.get(class.name()).cloned()),
+        asset_class.map(|class| class.benchmark().to_string()),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|benchmark| *benchmark != ticker)
+        .unwrap_or_else(benchmark_ticker)
+}
+
+/// Selects the benchmark to measure a portfolio of ticker symbols against.
+///
+/// If every ticker symbol resolves to the same benchmark (see [`resolve_benchmark`]), the
+/// portfolio is measured against it. A portfolio mixing asset classes is measured against the
+/// default benchmark (see [`benchmark_ticker`](crate::config::benchmark_ticker)).
+///
+/// # Arguments
+///
+/// * `tickers` - The ticker symbols of the portfolio.
+/// * `requested` - The benchmark chosen by the user, if any.
+///
+/// # Returns
+///
+/// The ticker symbol of the benchmark, in upper case.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::benchmark::resolve_portfolio_benchmark;
+///
+/// assert_eq!(resolve_portfolio_benchmark(&["ICLN", "TAN"], None), "XLE");
+/// ```
+pub fn resolve_portfolio_benchmark(tickers: &[&str], requested: Option<&str>) -> String {
+    let mut resolved = tickers.iter().map(|ticker| resolve_benchmark(ticker, requested));
+    let first = resolved.next().unwrap_or_else(|| resolve_benchmark("", requested));
+    if resolved.all(|benchmark| benchmark == first) {
+        first
+    } else {
+        benchmark_ticker()
+    }
+}
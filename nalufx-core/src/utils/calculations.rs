@@ -1,18 +1,272 @@
 use crate::errors::AllocationError;
+use crate::utils::allocation::{apply_constraints, Constraints};
+use crate::utils::stats::{mean, std_dev};
 use crate::{
     check_empty_inputs, check_input_lengths, check_invalid_data, check_outliers,
-    fill_feature_matrix, handle_result, normalize_features,
+    check_sufficient_data, fill_feature_matrix, handle_result, normalize_features,
 };
 use augurs_ets::AutoETS;
 use linfa::prelude::{Predict as LinfaPredict, *};
 use linfa_clustering::KMeans;
+use log::warn;
 use ndarray::prelude::*;
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The minimum number of data points required to forecast a time series.
+pub const MIN_FORECAST_POINTS: usize = 2;
+
+/// The number of clusters used by `perform_clustering`, which is also the minimum number of
+/// data points required to cluster them.
+pub const NUM_CLUSTERS: usize = 2;
+
+/// The default seed of the K-means initialization, so that the same inputs give the same
+/// clusters.
+pub const DEFAULT_CLUSTERING_SEED: u64 = 42;
+
+/// The default length of history considered sufficient to forecast any horizon.
+///
+/// A shorter history must cover at least the forecast horizon, so that a short fit is not
+/// extrapolated far beyond the data it was fitted on. See `AllocationOptions::min_history`.
+pub const DEFAULT_MIN_HISTORY: usize = 30;
+
+/// The default confidence level of the forecast prediction intervals.
+pub const DEFAULT_FORECAST_CONFIDENCE: f64 = 0.95;
+
+/// The default largest magnitude of a daily return, beyond which it is rejected as an outlier.
+///
+/// A daily return of `1.0` is a 100% move in one day.
+pub const DEFAULT_MAX_DAILY_RETURN: f64 = 1.0;
+
+/// The default largest magnitude of a cash flow, beyond which it is rejected as an outlier.
+pub const DEFAULT_MAX_CASH_FLOW: f64 = 1_000_000.0;
+
+/// Represents the objective used to derive allocation weights from the forecasts.
+///
+/// The allocation is spread over the forecast days. Apart from `MlHeuristic`, the objectives
+/// treat each forecast day as an asset whose expected return is the forecasted daily return,
+/// and whose variance grows linearly with the forecast horizon (`σ² · day`, where `σ²` is the
+/// sample variance of the historical daily returns). See `allocation_variance`.
+///
+/// # Variants
+///
+/// * `MlHeuristic` - The product of the forecasts, sentiment scores, optimal actions and
+///   clusters (the default).
+/// * `MaxSharpe` - Weights proportional to the inverse variance times the expected return,
+///   ignoring days with a non-positive expected return.
+/// * `MinVariance` - Weights proportional to the inverse variance, minimizing the ex-ante
+///   variance of the allocation.
+/// * `MaxReturn` - The whole allocation on the day with the highest expected return, the
+///   earliest such day on ties.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Objective {
+    /// The product of the forecasts, sentiment scores, optimal actions and clusters.
+    #[default]
+    MlHeuristic,
+    /// Maximizes the ex-ante Sharpe ratio.
+    MaxSharpe,
+    /// Minimizes the ex-ante variance.
+    MinVariance,
+    /// Maximizes the expected return.
+    MaxReturn,
+}
+
+/// Represents how the weighted components of a prediction are combined.
+///
+/// # Variants
+///
+/// * `LogLinear` - The product of the components, each raised to the power of its weight (the
+///   default). With all weights equal to 1, this is the plain product of the components.
+/// * `Additive` - The sum of the components, each multiplied by its weight. Unlike `LogLinear`,
+///   a near-zero component cannot annihilate the others.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Blending {
+    /// The product of the components raised to the power of their weights.
+    #[default]
+    LogLinear,
+    /// The weighted sum of the components.
+    Additive,
+}
+
+/// Represents the weights of the components of the `Objective::MlHeuristic` prediction.
+///
+/// Each day's prediction combines the predicted return, the predicted cash flow, the
+/// sentiment score, the optimal action and the cluster factor (`cluster + 1`) according to
+/// `blending`. A weight of `0.0` removes the component from the prediction, so, for example,
+/// `sentiment_w: 0.0` ignores the sentiment scores.
+///
+/// The default uses a weight of 1 for every component and `Blending::LogLinear`, which is the
+/// plain product of the components.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::calculations::AllocationWeights;
+///
+/// let weights = AllocationWeights { sentiment_w: 0.0, ..AllocationWeights::default() };
+/// assert_eq!(weights.blend(0.01, 100.0, 0.2, 1.0, 0.0), weights.blend(0.01, 100.0, 0.9, 1.0, 0.0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AllocationWeights {
+    /// The weight of the predicted return.
+    pub return_w: f64,
+    /// The weight of the predicted cash flow.
+    pub cashflow_w: f64,
+    /// The weight of the sentiment score.
+    pub sentiment_w: f64,
+    /// The weight of the optimal action.
+    pub action_w: f64,
+    /// The weight of the cluster factor.
+    pub cluster_w: f64,
+    /// How the weighted components are combined.
+    pub blending: Blending,
+}
+
+impl Default for AllocationWeights {
+    fn default() -> Self {
+        AllocationWeights {
+            return_w: 1.0,
+            cashflow_w: 1.0,
+            sentiment_w: 1.0,
+            action_w: 1.0,
+            cluster_w: 1.0,
+            blending: Blending::default(),
+        }
+    }
+}
+
+impl AllocationWeights {
+    /// Combines the components of a day's prediction according to the weights.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicted_return` - The predicted return for the day.
+    /// * `predicted_cash_flow` - The predicted cash flow for the day.
+    /// * `sentiment_score` - The sentiment score for the day.
+    /// * `optimal_action` - The optimal action for the day.
+    /// * `cluster` - The cluster of the day; the component is `cluster + 1`.
+    ///
+    /// # Returns
+    ///
+    /// The blended prediction for the day.
+    pub fn blend(
+        &self,
+        predicted_return: f64,
+        predicted_cash_flow: f64,
+        sentiment_score: f64,
+        optimal_action: f64,
+        cluster: f64,
+    ) -> f64 {
+        let components = [
+            (predicted_return, self.return_w),
+            (predicted_cash_flow, self.cashflow_w),
+            (sentiment_score, self.sentiment_w),
+            (optimal_action, self.action_w),
+            (cluster + 1.0, self.cluster_w),
+        ];
+
+        match self.blending {
+            Blending::LogLinear => components
+                .iter()
+                .filter(|(_, weight)| *weight != 0.0)
+                // Preserve the sign so that negative returns and cash flows stay negative.
+                .map(|(value, weight)| value.signum() * value.abs().powf(*weight))
+                .product(),
+            Blending::Additive => components.iter().map(|(value, weight)| value * weight).sum(),
+        }
+    }
+}
+
+/// Represents the options of an allocation, for `calculate_optimal_allocation_with_options`.
+///
+/// The default options are those of `calculate_optimal_allocation`: the
+/// `Objective::MlHeuristic` objective with the default `AllocationWeights`, a minimum history
+/// of `DEFAULT_MIN_HISTORY`, the outlier thresholds `DEFAULT_MAX_DAILY_RETURN` and
+/// `DEFAULT_MAX_CASH_FLOW`, no weight constraints, and a clustering seed of
+/// `DEFAULT_CLUSTERING_SEED`. The thresholds reject obviously
+/// erroneous data, so they may need to be raised for volatile small caps, whose daily returns
+/// can exceed 100%, or for large portfolios, whose cash flows can exceed a million.
+///
+/// With `constraints`, the allocations are projected onto the weight bounds after they are
+/// calculated (see [`apply_constraints`]), so that no day is allocated more than `max_weight`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::calculations::AllocationOptions;
+///
+/// let options = AllocationOptions { max_cash_flow: 50_000_000.0, ..AllocationOptions::default() };
+/// assert_eq!(options.max_daily_return, 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AllocationOptions {
+    /// The objective used to derive the weights.
+    pub objective: Objective,
+    /// The weights of the prediction components, for `Objective::MlHeuristic`.
+    pub weights: AllocationWeights,
+    /// The length of history considered sufficient for any horizon.
+    pub min_history: usize,
+    /// The largest magnitude of a daily return, beyond which the data is rejected as an
+    /// outlier.
+    pub max_daily_return: f64,
+    /// The largest magnitude of a cash flow, beyond which the data is rejected as an outlier.
+    pub max_cash_flow: f64,
+    /// The bounds of the allocation of each day, if any.
+    pub constraints: Option<Constraints>,
+    /// The seed of the K-means initialization, for `Objective::MlHeuristic`.
+    pub clustering_seed: u64,
+}
+
+impl Default for AllocationOptions {
+    fn default() -> Self {
+        AllocationOptions {
+            objective: Objective::default(),
+            weights: AllocationWeights::default(),
+            min_history: DEFAULT_MIN_HISTORY,
+            max_daily_return: DEFAULT_MAX_DAILY_RETURN,
+            max_cash_flow: DEFAULT_MAX_CASH_FLOW,
+            constraints: None,
+            clustering_seed: DEFAULT_CLUSTERING_SEED,
+        }
+    }
+}
+
+/// Represents the outcome of an allocation, for `calculate_optimal_allocation_with_report`.
+///
+/// Besides the allocations, the report discloses the steps of the allocation that fell back
+/// to a neutral value, so that reports built on it can say so.
+///
+/// # Fields
+///
+/// * `allocations` - The optimal allocation for each day.
+/// * `uncertainties` - The width of the 95% prediction interval of the forecasted daily return
+///   for each day, or `NaN` for a day without a forecast interval.
+/// * `clustering_applied` - Whether the cluster factor contributed to the allocations. It is
+///   `false` for objectives other than `Objective::MlHeuristic`, and when clustering failed,
+///   in which case every day is assigned to the same cluster.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AllocationReport {
+    /// The optimal allocation for each day.
+    pub allocations: Vec<f64>,
+    /// The width of the forecast prediction interval for each day.
+    pub uncertainties: Vec<f64>,
+    /// Whether the cluster factor contributed to the allocations.
+    pub clustering_applied: bool,
+}
 
 /// Calculates the optimal allocation based on daily returns and cash flows.
 ///
 /// This function uses a combination of time series forecasting, sentiment analysis,
 /// reinforcement learning, and clustering to calculate the optimal allocation for each day.
+/// It is equivalent to `calculate_optimal_allocation_with_options` with the default
+/// `AllocationOptions`, whose objective is `Objective::MlHeuristic`.
+///
+/// If a series cannot be forecast, for example because it is constant, a warning is logged
+/// and the forecast falls back to extrapolating the historical mean. Likewise, if the days
+/// cannot be clustered, a warning is logged and the cluster factor is left out (see
+/// `assign_clusters`); `calculate_optimal_allocation_with_report` discloses when it is.
+///
+/// The history must contain at least `num_days` values, or `DEFAULT_MIN_HISTORY` values for
+/// longer horizons (see `AllocationOptions::min_history`).
 ///
 /// # Arguments
 ///
@@ -31,7 +285,8 @@
 /// Returns an error if:
 /// - The input slices have different lengths.
 /// - The input slices are empty.
-/// - An error occurs during the execution of the `perform_clustering` function.
+/// - The input slices contain fewer than `MIN_FORECAST_POINTS` or `NUM_CLUSTERS` values, or
+///   fewer than `num_days` values and `DEFAULT_MIN_HISTORY` values (`InsufficientData`).
 ///
 /// # Examples
 ///
@@ -55,28 +310,240 @@ pub fn calculate_optimal_allocation(
     fund_characteristics: &[f64],
     num_days: usize,
 ) -> Result<Vec<f64>, AllocationError> {
+    calculate_optimal_allocation_with_options(
+        daily_returns,
+        cash_flows,
+        market_indices,
+        fund_characteristics,
+        num_days,
+        &AllocationOptions::default(),
+    )
+}
+
+/// Calculates the optimal allocation for each day using the given `AllocationOptions`.
+///
+/// This is the most general form of `calculate_optimal_allocation`: the objective, the
+/// weights, the minimum history, the outlier thresholds and the weight constraints are all
+/// taken from `options`. See `Objective` for how each objective uses the forecasts.
+///
+/// Forecasting far beyond a short history produces unstable allocations, so the history must
+/// contain at least `min(num_days, options.min_history)` values: a history of `min_history`
+/// values is considered sufficient for any horizon, while a shorter history must cover the
+/// horizon.
+///
+/// # Arguments
+///
+/// * `daily_returns` - A slice of daily returns.
+/// * `cash_flows` - A slice of cash flows.
+/// * `market_indices` - A slice of market indices.
+/// * `fund_characteristics` - A slice of fund characteristics.
+/// * `num_days` - The number of days to generate predictions for.
+/// * `options` - The options of the allocation.
+///
+/// # Returns
+///
+/// A vector of optimal allocations for each day.
+///
+/// # Errors
+///
+/// Returns the same errors as `calculate_optimal_allocation`, with the minimum history and
+/// the outlier thresholds of `options`, or `AllocationError::InfeasibleConstraints` if
+/// `num_days` allocations cannot meet the constraints of `options`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::errors::AllocationError;
+/// use nalufx::utils::calculations::{
+///     calculate_optimal_allocation_with_options, AllocationOptions, Objective,
+/// };
+///
+/// // A volatile small cap that more than doubled in one day
+/// let daily_returns = vec![0.01, 1.2, -0.01, 0.03, 0.01];
+/// let cash_flows = vec![1000.0, 2200.0, 2178.0, 2243.0, 2266.0];
+/// let market_indices = vec![1.0, 1.01, 1.02, 1.03, 1.04];
+/// let fund_characteristics = vec![0.5, 0.6, 0.7, 0.8, 0.9];
+/// let allocate = |options: &AllocationOptions| {
+///     calculate_optimal_allocation_with_options(
+///         &daily_returns,
+///         &cash_flows,
+///         &market_indices,
+///         &fund_characteristics,
+///         3,
+///         options,
+///     )
+/// };
+///
+/// let defaults = AllocationOptions { objective: Objective::MinVariance, ..Default::default() };
+/// assert_eq!(allocate(&defaults), Err(AllocationError::OutlierData));
+///
+/// let options = AllocationOptions { max_daily_return: 2.0, ..defaults };
+/// assert!(allocate(&options).is_ok());
+/// ```
+pub fn calculate_optimal_allocation_with_options(
+    daily_returns: &[f64],
+    cash_flows: &[f64],
+    market_indices: &[f64],
+    fund_characteristics: &[f64],
+    num_days: usize,
+    options: &AllocationOptions,
+) -> Result<Vec<f64>, AllocationError> {
+    let report = allocate(
+        daily_returns,
+        cash_flows,
+        market_indices,
+        fund_characteristics,
+        num_days,
+        options,
+    )?;
+    Ok(report.allocations)
+}
+
+/// Calculates the optimal allocation for each day using the given `AllocationOptions`, and
+/// reports how it was calculated.
+///
+/// The allocations are the same as those of `calculate_optimal_allocation_with_options`. The
+/// `AllocationReport` also holds the uncertainty of each day and whether clustering was
+/// applied, so that a report can disclose an allocation calculated without the cluster factor.
+///
+/// The uncertainty of each day is the width of the 95% prediction interval of the forecasted
+/// daily return for that day, so it is expressed in the same units as the daily returns. Days
+/// with a wide interval should be read with less confidence than days with a narrow one.
+///
+/// # Arguments
+///
+/// * `daily_returns` - A slice of daily returns.
+/// * `cash_flows` - A slice of cash flows.
+/// * `market_indices` - A slice of market indices.
+/// * `fund_characteristics` - A slice of fund characteristics.
+/// * `num_days` - The number of days to generate predictions for.
+/// * `options` - The options of the allocation.
+///
+/// # Returns
+///
+/// The `AllocationReport` of the allocation.
+///
+/// # Errors
+///
+/// Returns the same errors as `calculate_optimal_allocation_with_options`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::calculations::{
+///     calculate_optimal_allocation_with_report, AllocationOptions, Objective,
+/// };
+///
+/// let daily_returns = vec![0.01, 0.02, -0.01, 0.03, 0.01];
+/// let cash_flows = vec![1000.0, 1020.0, 1010.0, 1030.0, 1025.0];
+/// let market_indices = vec![1.0, 1.01, 1.02, 1.03, 1.04];
+/// let fund_characteristics = vec![0.5, 0.6, 0.7, 0.8, 0.9];
+/// let options = AllocationOptions { objective: Objective::MinVariance, ..Default::default() };
+/// let report = calculate_optimal_allocation_with_report(
+///     &daily_returns,
+///     &cash_flows,
+///     &market_indices,
+///     &fund_characteristics,
+///     3,
+///     &options,
+/// )
+/// .unwrap();
+/// assert_eq!(report.allocations.len(), 3);
+/// assert!(!report.clustering_applied);
+/// ```
+pub fn calculate_optimal_allocation_with_report(
+    daily_returns: &[f64],
+    cash_flows: &[f64],
+    market_indices: &[f64],
+    fund_characteristics: &[f64],
+    num_days: usize,
+    options: &AllocationOptions,
+) -> Result<AllocationReport, AllocationError> {
+    allocate(daily_returns, cash_flows, market_indices, fund_characteristics, num_days, options)
+}
+
+/// Calculates the `AllocationReport` of an allocation using the given `AllocationOptions`.
+fn allocate(
+    daily_returns: &[f64],
+    cash_flows: &[f64],
+    market_indices: &[f64],
+    fund_characteristics: &[f64],
+    num_days: usize,
+    options: &AllocationOptions,
+) -> Result<AllocationReport, AllocationError> {
+    let AllocationOptions {
+        objective,
+        weights,
+        min_history,
+        max_daily_return,
+        max_cash_flow,
+        constraints,
+        clustering_seed,
+    } = *options;
+
     // Check input lengths
     check_input_lengths!(daily_returns, cash_flows, market_indices, fund_characteristics)?;
 
     // Check for empty inputs
     check_empty_inputs!(daily_returns, cash_flows, market_indices, fund_characteristics)?;
 
+    // Check for sufficient data for forecasting and, for the heuristic, clustering, and for
+    // enough history to forecast the horizon
+    let required = match objective {
+        Objective::MlHeuristic => MIN_FORECAST_POINTS.max(NUM_CLUSTERS),
+        _ => MIN_FORECAST_POINTS,
+    }
+    .max(num_days.min(min_history));
+    check_sufficient_data!(required, daily_returns, cash_flows)?;
+
     // Check for invalid data
     check_invalid_data!(daily_returns, cash_flows)?;
 
     // Check for outliers
-    check_outliers!(1.0, daily_returns)?;
-    check_outliers!(1_000_000.0, cash_flows)?;
+    check_outliers!(max_daily_return, daily_returns)?;
+    check_outliers!(max_cash_flow, cash_flows)?;
 
-    // Feature Engineering
-    let features =
-        extract_features(daily_returns, cash_flows, market_indices, fund_characteristics)?;
+    // Check that the constraints can be met before calculating anything
+    if let Some(constraints) = constraints {
+        constraints.validate(num_days)?;
+    }
 
     // Time Series Forecasting
-    let forecasted_returns =
-        handle_result!(forecast_time_series(daily_returns, num_days), ForecastingError)?;
-    let forecasted_cash_flows =
-        handle_result!(forecast_time_series(cash_flows, num_days), ForecastingError)?;
+    let forecast = match forecast_time_series_with_intervals(
+        daily_returns,
+        num_days,
+        DEFAULT_FORECAST_CONFIDENCE,
+    ) {
+        Ok(forecast) => forecast,
+        Err(err) => {
+            warn!("Forecasting daily returns failed, using the historical mean instead: {}", err);
+            Vec::new()
+        },
+    };
+    let forecasted_returns = forecast.iter().map(|&(point, _, _)| point).collect::<Vec<f64>>();
+    let uncertainties = (0..num_days)
+        .map(|day| forecast.get(day).map_or(f64::NAN, |&(_, lower, upper)| upper - lower))
+        .collect::<Vec<f64>>();
+
+    if objective != Objective::MlHeuristic {
+        let avg_daily_return = mean(daily_returns).unwrap_or(0.0);
+        let expected_returns = (0..num_days)
+            .map(|day| forecasted_returns.get(day).copied().unwrap_or(avg_daily_return))
+            .collect::<Vec<f64>>();
+        let allocations = constrain(objective_weights(&expected_returns, objective), constraints)?;
+        return Ok(AllocationReport { allocations, uncertainties, clustering_applied: false });
+    }
+
+    // Feature Engineering, on inputs that have already been checked
+    let features = feature_matrix(daily_returns, cash_flows, market_indices, fund_characteristics);
+
+    let forecasted_cash_flows = match forecast_time_series(cash_flows, num_days) {
+        Ok(forecast) => forecast,
+        Err(err) => {
+            warn!("Forecasting cash flows failed, using the historical mean instead: {}", err);
+            Vec::new()
+        },
+    };
 
     // Sentiment Analysis
     let sentiment_scores = handle_result!(analyze_sentiment(num_days), SentimentAnalysisError)?;
@@ -86,17 +553,11 @@ pub fn calculate_optimal_allocation(
         handle_result!(train_reinforcement_learning(num_days), ReinforcementLearningError)?;
 
     // Clustering
-    let clusters = match perform_clustering(&features) {
-        Ok(clusters) => clusters,
-        Err(err) => {
-            eprintln!("Error during clustering: {}", err);
-            vec![0; num_days]
-        },
-    };
+    let (clusters, clustering_applied) = assign_clusters(&features, num_days, clustering_seed);
 
     // Calculate averages
-    let avg_daily_return = daily_returns.iter().sum::<f64>() / daily_returns.len() as f64;
-    let avg_cash_flow = cash_flows.iter().sum::<f64>() / cash_flows.len() as f64;
+    let avg_daily_return = mean(daily_returns).unwrap_or(0.0);
+    let avg_cash_flow = mean(cash_flows).unwrap_or(0.0);
 
     // Initialize predictions vector
     let mut predictions = Vec::with_capacity(num_days);
@@ -122,11 +583,13 @@ pub fn calculate_optimal_allocation(
             let cluster = clusters[day - 1] as f64;
 
             // Incorporate sentiment score, optimal action, and cluster into the prediction
-            let prediction = predicted_return
-                * predicted_cash_flow
-                * sentiment_score
-                * optimal_action
-                * (cluster + 1.0);
+            let prediction = weights.blend(
+                predicted_return,
+                predicted_cash_flow,
+                sentiment_score,
+                optimal_action,
+                cluster,
+            );
             predictions.push(prediction);
         } else {
             // If the day index is out of range, use default values
@@ -138,13 +601,99 @@ pub fn calculate_optimal_allocation(
     // Calculate total prediction to normalize the predictions
     let total_prediction: f64 = predictions.iter().sum();
 
-    // Handle the case where total prediction is zero
-    if total_prediction == 0.0 {
-        return Ok(vec![0.0; num_days]);
+    // Normalize predictions to get the optimal allocations, handling the case where the
+    // total prediction is zero
+    let allocations = if total_prediction == 0.0 {
+        vec![0.0; num_days]
+    } else {
+        predictions.into_iter().map(|p| p / total_prediction).collect()
+    };
+
+    let allocations = constrain(allocations, constraints)?;
+    Ok(AllocationReport { allocations, uncertainties, clustering_applied })
+}
+
+/// Projects the allocations onto the `constraints`, if any, or returns them unchanged.
+fn constrain(
+    allocations: Vec<f64>,
+    constraints: Option<Constraints>,
+) -> Result<Vec<f64>, AllocationError> {
+    match constraints {
+        Some(constraints) => apply_constraints(&allocations, &constraints),
+        None => Ok(allocations),
+    }
+}
+
+/// Derives the weights for the given `objective` from the expected return of each day.
+///
+/// The variance of day `d` (1-based) is proportional to `d`, so the common factor `σ²`
+/// cancels out when the weights are normalized.
+fn objective_weights(expected_returns: &[f64], objective: Objective) -> Vec<f64> {
+    let num_days = expected_returns.len();
+    let inverse_variance = (1..=num_days).map(|day| 1.0 / day as f64);
+
+    let weights = match objective {
+        Objective::MaxReturn => {
+            let best = expected_returns
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| r.is_finite())
+                .max_by(|(i, a), (j, b)| a.total_cmp(b).then(j.cmp(i)))
+                .map(|(day, _)| day);
+            (0..num_days).map(|day| if Some(day) == best { 1.0 } else { 0.0 }).collect()
+        },
+        Objective::MaxSharpe => {
+            let weights = expected_returns
+                .iter()
+                .zip(inverse_variance)
+                .map(|(&r, w)| if r > 0.0 { r * w } else { 0.0 })
+                .collect::<Vec<f64>>();
+            if weights.iter().sum::<f64>() > 0.0 {
+                weights
+            } else {
+                // No day is expected to be profitable, so fall back to the lowest risk.
+                return objective_weights(expected_returns, Objective::MinVariance);
+            }
+        },
+        Objective::MinVariance | Objective::MlHeuristic => inverse_variance.collect::<Vec<f64>>(),
+    };
+
+    let total: f64 = weights.iter().sum();
+    if total > 0.0 {
+        weights.into_iter().map(|w| w / total).collect()
+    } else {
+        vec![0.0; num_days]
     }
+}
 
-    // Normalize predictions to get the optimal allocations
-    Ok(predictions.into_iter().map(|p| p / total_prediction).collect())
+/// Calculates the ex-ante variance of an allocation over the forecast days.
+///
+/// The forecast days are modelled as uncorrelated, with the variance of day `d` (1-based)
+/// equal to `σ² · d`, where `σ²` is the sample variance of the historical daily returns.
+/// This is the risk model used by the `Objective` variants other than `MlHeuristic`.
+///
+/// # Arguments
+///
+/// * `weights` - A slice of allocation weights, one per forecast day.
+/// * `daily_returns` - A slice of historical daily returns.
+///
+/// # Returns
+///
+/// The ex-ante variance of the allocation, or `0.0` if `daily_returns` has fewer than two
+/// values.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::calculations::allocation_variance;
+///
+/// let daily_returns = vec![0.01, -0.01];
+/// // σ² = 0.0002; all the weight on day 2 gives 2 · σ².
+/// assert!((allocation_variance(&[0.0, 1.0], &daily_returns) - 0.0004).abs() < 1e-12);
+/// ```
+pub fn allocation_variance(weights: &[f64], daily_returns: &[f64]) -> f64 {
+    let variance = std_dev(daily_returns, 1).map_or(0.0, |s| s * s);
+    weights.iter().enumerate().map(|(day, w)| w * w * variance * (day + 1) as f64).sum()
 }
 
 /// Extracts features from the input data for clustering.
@@ -197,9 +746,19 @@ pub fn extract_features(
     check_invalid_data!(daily_returns, cash_flows)?;
 
     // Check for outliers
-    check_outliers!(1.0, daily_returns)?;
-    check_outliers!(1_000_000.0, cash_flows)?;
+    check_outliers!(DEFAULT_MAX_DAILY_RETURN, daily_returns)?;
+    check_outliers!(DEFAULT_MAX_CASH_FLOW, cash_flows)?;
+
+    Ok(feature_matrix(daily_returns, cash_flows, market_indices, fund_characteristics))
+}
 
+/// Builds the normalized feature matrix of inputs that have the same, non-zero length.
+fn feature_matrix(
+    daily_returns: &[f64],
+    cash_flows: &[f64],
+    market_indices: &[f64],
+    fund_characteristics: &[f64],
+) -> Array2<f64> {
     let n = daily_returns.len();
     let mut features = Array2::<f64>::zeros((n, 4));
 
@@ -216,7 +775,7 @@ pub fn extract_features(
     // Normalize the features
     normalize_features!(features);
 
-    Ok(features)
+    features
 }
 
 /// Forecasts future values of a time series using the AutoETS model.
@@ -252,10 +811,65 @@ pub fn extract_features(
 pub fn forecast_time_series(data: &[f64], num_days: usize) -> Result<Vec<f64>, String> {
     let mut search = AutoETS::new(1, "ZZN").map_err(|e| e.to_string())?;
     let model = search.fit(data).map_err(|e| e.to_string())?;
-    let forecast = model.predict(num_days, 0.95);
+    let forecast = model.predict(num_days, DEFAULT_FORECAST_CONFIDENCE);
     Ok(forecast.point)
 }
 
+/// Forecasts time series data with prediction intervals at the given confidence level.
+///
+/// This function uses the same AutoETS model as `forecast_time_series`, and also returns the
+/// lower and upper bounds of the prediction interval of each forecast. A higher confidence
+/// yields wider intervals; `DEFAULT_FORECAST_CONFIDENCE` gives 95% intervals.
+///
+/// # Arguments
+///
+/// * `data` - A slice of time series data.
+/// * `num_days` - The number of days to forecast.
+/// * `confidence` - The confidence level of the intervals, strictly between 0 and 1 (e.g.,
+///   `0.99` for 99% intervals).
+///
+/// # Returns
+///
+/// A vector of `(point, lower, upper)` forecasts, one for each day, or an error if the
+/// confidence level is not strictly between 0 and 1 or forecasting fails.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::calculations::forecast_time_series_with_intervals;
+///
+/// let data = vec![100.0, 101.0, 102.0, 101.5];
+/// match forecast_time_series_with_intervals(&data, 3, 0.99) {
+///     Ok(forecast) => {
+///         for (point, lower, upper) in forecast {
+///             println!("{:.2} [{:.2}, {:.2}]", point, lower, upper);
+///         }
+///     },
+///     Err(e) => eprintln!("Error: {}", e),
+/// }
+/// ```
+pub fn forecast_time_series_with_intervals(
+    data: &[f64],
+    num_days: usize,
+    confidence: f64,
+) -> Result<Vec<(f64, f64, f64)>, String> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(format!("The confidence level must be between 0 and 1, got {}", confidence));
+    }
+    let mut search = AutoETS::new(1, "ZZN").map_err(|e| e.to_string())?;
+    let model = search.fit(data).map_err(|e| e.to_string())?;
+    let forecast = model.predict(num_days, confidence);
+    let intervals =
+        forecast.intervals.ok_or_else(|| "Forecast intervals are unavailable".to_string())?;
+    Ok(forecast
+        .point
+        .into_iter()
+        .zip(intervals.lower)
+        .zip(intervals.upper)
+        .map(|((point, lower), upper)| (point, lower, upper))
+        .collect())
+}
+
 /// Analyzes sentiment scores for a given number of days.
 ///
 /// This function generates sentiment scores for the specified number of days.
@@ -332,7 +946,8 @@ pub fn train_reinforcement_learning(num_days: usize) -> Result<Vec<f64>, String>
 ///
 /// # Errors
 ///
-/// Returns an error if the K-means model fails to fit the data or generate cluster assignments.
+/// Returns an error if the feature matrix has fewer than `NUM_CLUSTERS` rows, or if the
+/// K-means model fails to fit the data or generate cluster assignments.
 ///
 /// # Examples
 ///
@@ -344,12 +959,55 @@ pub fn train_reinforcement_learning(num_days: usize) -> Result<Vec<f64>, String>
 /// assert_eq!(clusters.len(), 3);
 /// ```
 pub fn perform_clustering(features: &Array2<f64>) -> Result<Vec<usize>, AllocationError> {
+    perform_clustering_with_seed(features, DEFAULT_CLUSTERING_SEED)
+}
+
+/// Performs K-means clustering on the feature matrix, with a seeded initialization.
+///
+/// This function behaves like [`perform_clustering`], which uses `DEFAULT_CLUSTERING_SEED`,
+/// but initializes the K-means centroids from an RNG seeded with `seed`, so that the same
+/// features and seed always give the same cluster labels.
+///
+/// # Arguments
+///
+/// * `features` - A reference to the feature matrix (`Array2<f64>`).
+/// * `seed` - The seed of the K-means initialization.
+///
+/// # Returns
+///
+/// A vector of cluster assignments (`Vec<usize>`) for each data point, or an error if clustering fails.
+///
+/// # Errors
+///
+/// Returns the same errors as [`perform_clustering`].
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::calculations::perform_clustering_with_seed;
+/// use ndarray::array;
+///
+/// let features = array![[0.0, 0.0], [0.1, 0.0], [5.0, 5.0], [5.1, 5.0]];
+/// let clusters = perform_clustering_with_seed(&features, 7).unwrap();
+/// assert_eq!(clusters, perform_clustering_with_seed(&features, 7).unwrap());
+/// ```
+pub fn perform_clustering_with_seed(
+    features: &Array2<f64>,
+    seed: u64,
+) -> Result<Vec<usize>, AllocationError> {
+    if features.nrows() < NUM_CLUSTERS {
+        return Err(AllocationError::ClusteringError(format!(
+            "{} data point(s) cannot be split into {} clusters",
+            features.nrows(),
+            NUM_CLUSTERS
+        )));
+    }
+
     // Convert features to a Dataset
     let dataset = Dataset::from(features.clone());
 
-    // Create the KMeans model with 2 clusters
-    let n_clusters = 2;
-    let model = KMeans::params_with_rng(n_clusters, rand::thread_rng())
+    // Create the KMeans model
+    let model = KMeans::params_with_rng(NUM_CLUSTERS, StdRng::seed_from_u64(seed))
         .fit(&dataset)
         .map_err(|err| AllocationError::ClusteringError(err.to_string()))?;
 
@@ -360,6 +1018,42 @@ pub fn perform_clustering(features: &Array2<f64>) -> Result<Vec<usize>, Allocati
     Ok(clusters.iter().map(|&c| c).collect())
 }
 
+/// Assigns each day to a cluster, falling back to a single cluster if clustering fails.
+///
+/// The clusters are those of `perform_clustering_with_seed`. If it fails, a warning is logged and every
+/// day is assigned to cluster `0`, which makes the cluster factor of the prediction neutral.
+/// The fallback is disclosed by the returned flag rather than hidden from the caller.
+///
+/// # Arguments
+///
+/// * `features` - A reference to the feature matrix (`Array2<f64>`).
+/// * `num_days` - The number of days to assign when clustering fails.
+/// * `seed` - The seed of the K-means initialization.
+///
+/// # Returns
+///
+/// A tuple of the cluster assignments and whether clustering was applied.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::calculations::{assign_clusters, DEFAULT_CLUSTERING_SEED};
+/// use ndarray::Array2;
+///
+/// // A single data point cannot be split into clusters.
+/// let features = Array2::from_shape_vec((1, 4), vec![0.0; 4]).unwrap();
+/// assert_eq!(assign_clusters(&features, 3, DEFAULT_CLUSTERING_SEED), (vec![0; 3], false));
+/// ```
+pub fn assign_clusters(features: &Array2<f64>, num_days: usize, seed: u64) -> (Vec<usize>, bool) {
+    match perform_clustering_with_seed(features, seed) {
+        Ok(clusters) => (clusters, true),
+        Err(err) => {
+            warn!("Clustering failed, assigning every day to the same cluster: {}", err);
+            (vec![0; num_days], false)
+        },
+    }
+}
+
 /// Helper function for sentiment analysis (placeholder).
 ///
 /// This function generates random sentiment scores for demonstration purposes.
@@ -0,0 +1,163 @@
+use crate::config::market_holidays;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+
+/// Returns the US stock market holidays of a year, in chronological order.
+///
+/// The holidays follow the rules of the New York Stock Exchange: New Year's Day, Martin Luther
+/// King Jr. Day, Washington's Birthday, Good Friday, Memorial Day, Juneteenth (from 2022),
+/// Independence Day, Labor Day, Thanksgiving Day and Christmas Day. A holiday falling on a
+/// Saturday is observed on the Friday before, and one falling on a Sunday on the Monday after,
+/// except New Year's Day, which is not observed when it falls on a Saturday.
+///
+/// Unscheduled closures are not included; set `NALUFX_MARKET_HOLIDAYS` to list them (see
+/// [`market_holidays`](crate::config::market_holidays)).
+///
+/// # Arguments
+///
+/// * `year` - The calendar year.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use nalufx::utils::calendar::us_market_holidays;
+///
+/// let holidays = us_market_holidays(2024);
+/// assert_eq!(holidays.len(), 10);
+/// // Thanksgiving Day, the fourth Thursday of November
+/// assert!(holidays.contains(&NaiveDate::from_ymd_opt(2024, 11, 28).unwrap()));
+/// ```
+pub fn us_market_holidays(year: i32) -> Vec<NaiveDate> {
+    let nth_weekday = |month: u32, weekday: Weekday, n: u8| {
+        NaiveDate::from_weekday_of_month_opt(year, month, weekday, n)
+    };
+    let observed = |month: u32, day: u32| {
+        NaiveDate::from_ymd_opt(year, month, day).map(|date| match date.weekday() {
+            Weekday::Sat => date - Duration::days(1),
+            Weekday::Sun => date + Duration::days(1),
+            _ => date,
+        })
+    };
+
+    let new_year = NaiveDate::from_ymd_opt(year, 1, 1).and_then(|date| match date.weekday() {
+        Weekday::Sat => None,
+        Weekday::Sun => Some(date + Duration::days(1)),
+        _ => Some(date),
+    });
+    let memorial_day = nth_weekday(5, Weekday::Mon, 5).or_else(|| nth_weekday(5, Weekday::Mon, 4));
+    let juneteenth = if year >= 2022 { observed(6, 19) } else { None };
+
+    [
+        new_year,
+        nth_weekday(1, Weekday::Mon, 3),
+        nth_weekday(2, Weekday::Mon, 3),
+        easter_sunday(year).map(|easter| easter - Duration::days(2)),
+        memorial_day,
+        juneteenth,
+        observed(7, 4),
+        nth_weekday(9, Weekday::Mon, 1),
+        nth_weekday(11, Weekday::Thu, 4),
+        observed(12, 25),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Returns `true` if the market is open on a date, which is neither a weekend day nor one of
+/// `holidays`.
+///
+/// # Arguments
+///
+/// * `date` - The date to check.
+/// * `holidays` - The market holidays.
+pub fn is_trading_day(date: NaiveDate, holidays: &[NaiveDate]) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !holidays.contains(&date)
+}
+
+/// Returns the next `n` trading days after `from`, skipping weekends and the given holidays.
+///
+/// `from` itself is not included, and each day keeps the time of day of `from`.
+///
+/// # Arguments
+///
+/// * `from` - The date and time to start from.
+/// * `n` - The number of trading days to return.
+/// * `holidays` - The market holidays.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{NaiveDate, TimeZone, Utc};
+/// use nalufx::utils::calendar::next_trading_days_with;
+///
+/// // Thursday 4 July 2024 is a holiday, and 6 and 7 July are a weekend
+/// let from = Utc.with_ymd_and_hms(2024, 7, 3, 0, 0, 0).unwrap();
+/// let holidays = [NaiveDate::from_ymd_opt(2024, 7, 4).unwrap()];
+/// let days = next_trading_days_with(from, 2, &holidays);
+/// assert_eq!(days[0], Utc.with_ymd_and_hms(2024, 7, 5, 0, 0, 0).unwrap());
+/// assert_eq!(days[1], Utc.with_ymd_and_hms(2024, 7, 8, 0, 0, 0).unwrap());
+/// ```
+pub fn next_trading_days_with(
+    from: DateTime<Utc>,
+    n: usize,
+    holidays: &[NaiveDate],
+) -> Vec<DateTime<Utc>> {
+    (1..)
+        .map(|offset| from + Duration::days(offset))
+        .filter(|date| is_trading_day(date.date_naive(), holidays))
+        .take(n)
+        .collect()
+}
+
+/// Returns the next `n` trading days after `from`, skipping weekends and market holidays.
+///
+/// The holidays are those set in `NALUFX_MARKET_HOLIDAYS` (see
+/// [`market_holidays`](crate::config::market_holidays)), or the US market holidays (see
+/// [`us_market_holidays`]) if it is not set. Allocations are mapped to these days, so that no
+/// recommendation falls on a day the market is closed.
+///
+/// # Arguments
+///
+/// * `from` - The date and time to start from, which is not included.
+/// * `n` - The number of trading days to return.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc, Weekday, Datelike};
+/// use nalufx::utils::calendar::next_trading_days;
+///
+/// let from = Utc.with_ymd_and_hms(2024, 6, 7, 0, 0, 0).unwrap(); // A Friday
+/// let days = next_trading_days(from, 3);
+/// assert_eq!(days[0].weekday(), Weekday::Mon);
+/// ```
+pub fn next_trading_days(from: DateTime<Utc>, n: usize) -> Vec<DateTime<Utc>> {
+    let holidays = market_holidays().unwrap_or_else(|| {
+        // A year has about 250 trading days, so these years cover the `n` days.
+        let years = i32::try_from(n / 250 + 1).unwrap_or(i32::MAX);
+        let last_year = from.year().saturating_add(years);
+        (from.year()..=last_year).flat_map(us_market_holidays).collect()
+    });
+    next_trading_days_with(from, n, &holidays)
+}
+
+/// Computes the date of Easter Sunday in the Gregorian calendar, with the anonymous Gregorian
+/// algorithm.
+fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, u32::try_from(month).ok()?, u32::try_from(day).ok()?)
+}
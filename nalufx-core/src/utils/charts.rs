@@ -0,0 +1,210 @@
+use crate::{config::chart_max_points, errors::NaluFxError};
+use plotters::prelude::*;
+use std::io;
+use std::path::Path;
+use textplots::{Chart, LabelBuilder, LabelFormat, Plot, Shape};
+
+/// Builds the value of a portfolio over time from its daily returns.
+///
+/// The portfolio starts at `initial_value` and compounds each daily return in turn, so the
+/// series has one more value than there are returns.
+///
+/// # Arguments
+///
+/// * `initial_value` - The value of the portfolio before the first return.
+/// * `daily_returns` - The daily returns of the portfolio, as fractions, in chronological
+///   order.
+///
+/// # Returns
+///
+/// The value of the portfolio at the start and after each day.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::charts::portfolio_value_series;
+///
+/// let values = portfolio_value_series(1000.0, &[0.1, -0.5]);
+/// assert_eq!(values, vec![1000.0, 1100.0, 550.0]);
+/// ```
+pub fn portfolio_value_series(initial_value: f64, daily_returns: &[f64]) -> Vec<f64> {
+    let mut values = Vec::with_capacity(daily_returns.len() + 1);
+    values.push(initial_value);
+    let mut value = initial_value;
+    for daily_return in daily_returns {
+        value *= 1.0 + daily_return;
+        values.push(value);
+    }
+    values
+}
+
+/// Downsamples a series of points to a point budget, preserving its visual shape.
+///
+/// This function uses the Largest-Triangle-Three-Buckets (LTTB) algorithm: the first and last
+/// points are kept, and the points in between are split into `budget - 2` buckets, from each of
+/// which the point forming the largest triangle with the previously kept point and the average
+/// of the next bucket is kept. Peaks and troughs are therefore kept, unlike when keeping every
+/// k-th point.
+///
+/// # Arguments
+///
+/// * `points` - The `(x, y)` points of the series, in increasing order of `x`.
+/// * `budget` - The largest number of points to keep.
+///
+/// # Returns
+///
+/// The points of the series if there are no more than `budget` of them, and otherwise
+/// `budget` of them, or only the first and last points if `budget` is below `3`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::charts::downsample_lttb;
+///
+/// let points: Vec<(f64, f64)> = (0..1000).map(|x| (x as f64, (x as f64).sin())).collect();
+/// let sampled = downsample_lttb(&points, 100);
+/// assert_eq!(sampled.len(), 100);
+/// assert_eq!(sampled[0], points[0]);
+/// assert_eq!(sampled[99], points[999]);
+/// ```
+pub fn downsample_lttb(points: &[(f64, f64)], budget: usize) -> Vec<(f64, f64)> {
+    let n = points.len();
+    if n <= budget || n <= 2 {
+        return points.to_vec();
+    }
+    if budget < 3 {
+        return vec![points[0], points[n - 1]];
+    }
+
+    let bucket_size = (n - 2) as f64 / (budget - 2) as f64;
+    let bucket_start = |bucket: usize| ((bucket as f64 * bucket_size) as usize + 1).min(n - 1);
+    let mut sampled = Vec::with_capacity(budget);
+    sampled.push(points[0]);
+    let mut previous = points[0];
+    for bucket in 0..budget - 2 {
+        let (start, end) = (bucket_start(bucket), bucket_start(bucket + 1));
+        let next = &points[end..bucket_start(bucket + 2).max(end + 1).min(n)];
+        let next_x = next.iter().map(|&(x, _)| x).sum::<f64>() / next.len() as f64;
+        let next_y = next.iter().map(|&(_, y)| y).sum::<f64>() / next.len() as f64;
+
+        let area = |&(x, y): &(f64, f64)| {
+            ((previous.0 - next_x) * (y - previous.1) - (previous.0 - x) * (next_y - previous.1))
+                .abs()
+        };
+        let kept = points[start..end.max(start + 1)]
+            .iter()
+            .copied()
+            .max_by(|a, b| area(a).total_cmp(&area(b)))
+            .unwrap_or(points[start]);
+        sampled.push(kept);
+        previous = kept;
+    }
+    sampled.push(points[n - 1]);
+    sampled
+}
+
+/// Returns the points of a value chart, one per day, downsampled to `chart_max_points`.
+fn value_points(values: &[f64]) -> Vec<(f64, f64)> {
+    let points: Vec<(f64, f64)> =
+        values.iter().enumerate().map(|(day, &value)| (day as f64, value)).collect();
+    downsample_lttb(&points, chart_max_points())
+}
+
+/// Renders the value of a portfolio over time as a text chart, for the console and for code
+/// blocks in Markdown reports.
+///
+/// A series longer than `chart_max_points` is downsampled (see [`downsample_lttb`]).
+///
+/// # Arguments
+///
+/// * `values` - The value of the portfolio on each day (see [`portfolio_value_series`]).
+///
+/// # Returns
+///
+/// The chart, with the days on the x-axis and the value on the y-axis.
+///
+/// # Errors
+///
+/// * `NaluFxError::EmptyInput` - If `values` is empty.
+/// * `NaluFxError::InvalidData` - If `values` contains a value that is not finite.
+pub fn value_chart_text(values: &[f64]) -> Result<String, NaluFxError> {
+    let (min, max) = value_range(values)?;
+    let points: Vec<(f32, f32)> =
+        value_points(values).iter().map(|&(day, value)| (day as f32, value as f32)).collect();
+    let last_day = points.last().map_or(0.0, |&(x, _)| x).max(1.0);
+
+    let shape = Shape::Lines(&points);
+    let mut chart = Chart::new_with_y_range(120, 60, 0.0, last_day, min as f32, max as f32);
+    let chart = chart
+        .lineplot(&shape)
+        .x_label_format(LabelFormat::Custom(Box::new(|x| format!("Day {}", x as usize))))
+        .y_label_format(LabelFormat::Custom(Box::new(|y| format!("{:.2}", y))));
+    chart.axis();
+    chart.figures();
+    Ok(chart.to_string())
+}
+
+/// Writes the value of a portfolio over time as an SVG line chart, to be linked from Markdown
+/// reports.
+///
+/// A series longer than `chart_max_points` is downsampled (see [`downsample_lttb`]).
+///
+/// # Arguments
+///
+/// * `path` - The path of the SVG file, whose directory must exist.
+/// * `title` - The caption of the chart.
+/// * `values` - The value of the portfolio on each day (see [`portfolio_value_series`]).
+///
+/// # Errors
+///
+/// * `NaluFxError::EmptyInput` - If `values` is empty.
+/// * `NaluFxError::InvalidData` - If `values` contains a value that is not finite.
+/// * `NaluFxError::InputError` - If the chart cannot be drawn or the file cannot be written.
+pub fn write_value_chart_svg(path: &Path, title: &str, values: &[f64]) -> Result<(), NaluFxError> {
+    let (min, max) = value_range(values)?;
+    let last_day = values.len().saturating_sub(1).max(1);
+
+    let root = SVGBackend::new(path, (960, 540)).into_drawing_area();
+    root.fill(&WHITE).map_err(chart_error)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 24))
+        .margin(16)
+        .x_label_area_size(40)
+        .y_label_area_size(80)
+        .build_cartesian_2d(0..last_day, min..max)
+        .map_err(chart_error)?;
+    chart
+        .configure_mesh()
+        .x_desc("Day")
+        .y_desc("Value")
+        .y_label_formatter(&|y| format!("{:.2}", y))
+        .draw()
+        .map_err(chart_error)?;
+    let _ = chart
+        .draw_series(LineSeries::new(
+            value_points(values).into_iter().map(|(day, value)| (day as usize, value)),
+            &BLUE,
+        ))
+        .map_err(chart_error)?;
+    root.present().map_err(chart_error)
+}
+
+/// Returns the range of the y-axis of a value chart, padded so that a flat series is visible.
+fn value_range(values: &[f64]) -> Result<(f64, f64), NaluFxError> {
+    if values.is_empty() {
+        return Err(NaluFxError::EmptyInput);
+    }
+    if values.iter().any(|value| !value.is_finite()) {
+        return Err(NaluFxError::InvalidData);
+    }
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let padding = if max > min { (max - min) * 0.05 } else { max.abs().max(1.0) * 0.05 };
+    Ok((min - padding, max + padding))
+}
+
+/// Converts a drawing error into a `NaluFxError`.
+fn chart_error(e: impl std::fmt::Display) -> NaluFxError {
+    NaluFxError::InputError(io::Error::other(format!("Failed to draw the chart: {}", e)))
+}
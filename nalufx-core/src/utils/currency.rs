@@ -1,5 +1,39 @@
+use rust_decimal::{prelude::FromPrimitive, Decimal, RoundingStrategy};
+
+/// Represents the strategy used to round an amount to whole cents.
+///
+/// Amounts are rounded as decimals, so a value such as `2.675`, which a `f64` stores as
+/// `2.67499999...`, is treated as the midpoint it was written as.
+///
+/// # Variants
+///
+/// * `HalfAwayFromZero` - Rounds a midpoint away from zero, e.g. `2.675` to `2.68` and `0.005`
+///   to `0.01`. This is the default.
+/// * `HalfEven` - Rounds a midpoint to the nearest even cent (banker's rounding), e.g. `2.675`
+///   to `2.68` and `0.005` to `0.00`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CurrencyRounding {
+    /// Rounds a midpoint away from zero.
+    #[default]
+    HalfAwayFromZero,
+    /// Rounds a midpoint to the nearest even cent.
+    HalfEven,
+}
+
+impl CurrencyRounding {
+    /// Returns the matching `rust_decimal` rounding strategy.
+    fn strategy(self) -> RoundingStrategy {
+        match self {
+            CurrencyRounding::HalfAwayFromZero => RoundingStrategy::MidpointAwayFromZero,
+            CurrencyRounding::HalfEven => RoundingStrategy::MidpointNearestEven,
+        }
+    }
+}
+
 /// Formats a floating-point number as currency in US dollars.
 ///
+/// The amount is rounded to whole cents with the default [`CurrencyRounding`].
+///
 /// # Arguments
 ///
 /// * `value` - A floating-point number representing the amount to format.
@@ -18,33 +52,55 @@
 ///
 /// let formatted_negative = format_currency(-1234.5678);
 /// assert_eq!(formatted_negative, "-$1,234.57");
+///
+/// assert_eq!(format_currency(2.675), "$2.68");
 /// ```
 pub fn format_currency(value: f64) -> String {
-    /// Helper function to format the dollar part with commas.
-    fn format_dollars(dollars: i64) -> String {
-        let dollars_abs = dollars.abs().to_string();
+    format_currency_with(value, CurrencyRounding::default())
+}
+
+/// Formats a floating-point number as currency in US dollars, with the given rounding strategy.
+///
+/// A value that cannot be represented as a decimal (`NaN`, an infinity or a magnitude above
+/// `7.9e28`) is formatted as `$0.00`. An amount that rounds to zero is never signed.
+///
+/// # Arguments
+///
+/// * `value` - A floating-point number representing the amount to format.
+/// * `rounding` - The strategy used to round the amount to whole cents.
+///
+/// # Returns
+///
+/// A `String` representing the formatted currency value.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::currency::{format_currency_with, CurrencyRounding};
+///
+/// assert_eq!(format_currency_with(0.005, CurrencyRounding::HalfAwayFromZero), "$0.01");
+/// assert_eq!(format_currency_with(0.005, CurrencyRounding::HalfEven), "$0.00");
+/// ```
+pub fn format_currency_with(value: f64, rounding: CurrencyRounding) -> String {
+    /// Helper function to format the dollar digits with commas.
+    fn format_dollars(digits: &str) -> String {
         let mut result = String::new();
-        let mut count = 0;
 
-        for digit in dollars_abs.chars().rev() {
+        for (count, digit) in digits.chars().rev().enumerate() {
             if count > 0 && count % 3 == 0 {
                 result.push(',');
             }
             result.push(digit);
-            count += 1;
         }
 
         result.chars().rev().collect::<String>()
     }
 
-    let int_value = (value * 100.0).round() as i64; // Convert to integer cents
-    let dollars = int_value / 100;
-    let cents = (int_value % 100).abs(); // Absolute value for cents
-    let formatted_dollars = format_dollars(dollars);
+    let amount =
+        Decimal::from_f64(value).unwrap_or_default().round_dp_with_strategy(2, rounding.strategy());
+    let digits = format!("{:.2}", amount.abs());
+    let (dollars, cents) = digits.split_once('.').unwrap_or((digits.as_str(), "00"));
+    let sign = if amount.is_sign_negative() && !amount.is_zero() { "-" } else { "" };
 
-    if value < 0.0 {
-        format!("-${}.{:02}", formatted_dollars, cents)
-    } else {
-        format!("${}.{:02}", formatted_dollars, cents)
-    }
+    format!("{}${}.{}", sign, format_dollars(dollars), cents)
 }
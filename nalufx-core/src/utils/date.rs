@@ -1,47 +1,171 @@
-use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use crate::errors::NaluFxError;
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc, Weekday};
+use serde::{Deserialize, Serialize};
 
-/// Validates if the input string is a valid date in the format YYYY-MM-DD.
+/// Parses a named date range preset into a start and end date relative to today.
 ///
-/// This function attempts to parse the input string into a `NaiveDate` and then converts it into a `DateTime<Utc>`.
-/// If the parsing or conversion fails, it returns an error message.
+/// The preset is case-insensitive and is one of:
+/// * `<n>D`, `<n>W`, `<n>M` or `<n>Y` - The last `n` days, weeks, months or years, e.g. `1M`,
+///   `6M`, `1Y` or `5Y`.
+/// * `YTD` - The year to date, from January 1 of the current year.
+/// * `MAX` - All available history, from the Unix epoch (January 1, 1970).
+///
+/// The start date is at midnight UTC, like the dates returned by
+/// [`validate_date`](crate::utils::validation::validate_date), and the end date is the current
+/// time, so that today's data is included.
 ///
 /// # Arguments
 ///
-/// * `input` - A string slice that holds the date to validate.
+/// * `s` - A string slice that holds the preset.
 ///
 /// # Returns
 ///
-/// This function returns a `Result`:
-/// * `Ok(DateTime<Utc>)` - If the input string is a valid date.
-/// * `Err(&'static str)` - If the input string is not a valid date.
+/// `Some((start, end))` if `s` is a valid preset, or `None` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::date::parse_range_preset;
+/// use chrono::{Datelike, Utc};
+///
+/// let (start, end) = parse_range_preset("ytd").unwrap();
+/// assert_eq!((start.month(), start.day()), (1, 1));
+/// assert_eq!(end.date_naive(), Utc::now().date_naive());
+///
+/// assert!(parse_range_preset("6M").is_some());
+/// assert!(parse_range_preset("2024-01-01").is_none());
+/// ```
+pub fn parse_range_preset(s: &str) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let preset = s.trim().to_ascii_uppercase();
+    if !preset.is_ascii() {
+        return None;
+    }
+
+    let now = Utc::now();
+    let today = now.date_naive();
+    let start = match preset.as_str() {
+        "YTD" => NaiveDate::from_ymd_opt(today.year(), 1, 1)?,
+        "MAX" => DateTime::UNIX_EPOCH.date_naive(),
+        _ => {
+            let (count, unit) = preset.split_at(preset.len().checked_sub(1)?);
+            let count: u32 = count.parse().ok().filter(|&count| count > 0)?;
+            match unit {
+                "D" => today.checked_sub_days(Days::new(u64::from(count)))?,
+                "W" => today.checked_sub_days(Days::new(u64::from(count) * 7))?,
+                "M" => today.checked_sub_months(Months::new(count))?,
+                "Y" => today.checked_sub_months(Months::new(count.checked_mul(12)?))?,
+                _ => return None,
+            }
+        },
+    };
+
+    Some((start.and_hms_opt(0, 0, 0)?.and_utc(), now))
+}
+
+/// Represents a continuity problem found in a dated series.
+///
+/// # Variants
+///
+/// * `Gap` - More weekdays are missing between two consecutive observations than allowed.
+/// * `Duplicate` - Two consecutive observations fall on the same calendar date.
+/// * `OutOfOrder` - An observation is dated before the observation preceding it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SeriesIssue {
+    /// More weekdays are missing between two consecutive observations than allowed.
+    Gap {
+        /// The timestamp of the observation before the gap.
+        after: DateTime<Utc>,
+        /// The timestamp of the observation after the gap.
+        before: DateTime<Utc>,
+        /// The number of weekdays with no observation.
+        missing_days: usize,
+    },
+    /// Two consecutive observations fall on the same calendar date.
+    Duplicate(DateTime<Utc>),
+    /// An observation is dated before the observation preceding it.
+    OutOfOrder {
+        /// The timestamp of the preceding observation.
+        previous: DateTime<Utc>,
+        /// The timestamp of the out-of-order observation.
+        current: DateTime<Utc>,
+    },
+}
+
+/// Counts the weekdays strictly between two dates.
+fn weekdays_between(from: NaiveDate, to: NaiveDate) -> usize {
+    from.iter_days()
+        .skip(1)
+        .take_while(|date| *date < to)
+        .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+        .count()
+}
+
+/// Checks that a dated series of daily observations is strictly increasing and has no large gaps.
+///
+/// Consecutive observations are compared by calendar date. Weekends are not counted as
+/// missing days, so a Friday followed by a Monday is continuous, while a Monday followed by a
+/// Wednesday is missing one day (for example, a market holiday).
 ///
-/// # Errors
+/// # Arguments
+///
+/// * `series` - A slice of `(timestamp, value)` pairs in chronological order.
+/// * `max_missing_days` - The largest number of consecutive missing weekdays that is tolerated.
+///
+/// # Returns
 ///
-/// The function will return an error if:
-/// * The input string cannot be parsed into a date.
-/// * The date cannot be converted to a `DateTime<Utc>`.
+/// `Ok(())` if the series is continuous, or `NaluFxError::DiscontinuousSeries` listing every
+/// issue found.
 ///
 /// # Examples
 ///
 /// ```
-/// use nalufx::utils::date::validate_date;
-/// use chrono::Utc;
+/// use chrono::{TimeZone, Utc};
+/// use nalufx::utils::date::{check_series_continuity, SeriesIssue};
+/// use nalufx::errors::NaluFxError;
 ///
-/// let valid_date = "2024-01-01";
-/// assert!(validate_date(valid_date).is_ok());
+/// // Friday, Monday, Wednesday: Tuesday is missing
+/// let series = vec![
+///     (Utc.with_ymd_and_hms(2024, 1, 5, 0, 0, 0).unwrap(), 100.0),
+///     (Utc.with_ymd_and_hms(2024, 1, 8, 0, 0, 0).unwrap(), 101.0),
+///     (Utc.with_ymd_and_hms(2024, 1, 10, 0, 0, 0).unwrap(), 102.0),
+/// ];
 ///
-/// let invalid_date = "2024-13-01";
-/// assert!(validate_date(invalid_date).is_err());
+/// assert!(check_series_continuity(&series, 1).is_ok());
+/// match check_series_continuity(&series, 0) {
+///     Err(NaluFxError::DiscontinuousSeries(issues)) => {
+///         assert!(matches!(issues[0], SeriesIssue::Gap { missing_days: 1, .. }));
+///     },
+///     _ => panic!("Expected a gap"),
+/// }
 /// ```
-pub fn validate_date(input: &str) -> Result<DateTime<Utc>, &str> {
-    match NaiveDate::parse_from_str(input, "%Y-%m-%d") {
-        Ok(date) => match date.and_hms_opt(0, 0, 0) {
-            Some(naive_datetime) => match Utc.from_local_datetime(&naive_datetime) {
-                chrono::LocalResult::Single(datetime) => Ok(datetime),
-                _ => Err("Please enter a valid date in the format YYYY-MM-DD."),
-            },
-            None => Err("Invalid time component in the date."),
-        },
-        Err(_) => Err("Please enter a valid date in the format YYYY-MM-DD."),
+pub fn check_series_continuity(
+    series: &[(DateTime<Utc>, f64)],
+    max_missing_days: usize,
+) -> Result<(), NaluFxError> {
+    let issues: Vec<SeriesIssue> = series
+        .windows(2)
+        .filter_map(|pair| {
+            let (previous, current) = (pair[0].0, pair[1].0);
+            let (previous_date, current_date) = (previous.date_naive(), current.date_naive());
+
+            if current_date < previous_date {
+                Some(SeriesIssue::OutOfOrder { previous, current })
+            } else if current_date == previous_date {
+                Some(SeriesIssue::Duplicate(current))
+            } else {
+                let missing_days = weekdays_between(previous_date, current_date);
+                (missing_days > max_missing_days).then_some(SeriesIssue::Gap {
+                    after: previous,
+                    before: current,
+                    missing_days,
+                })
+            }
+        })
+        .collect();
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(NaluFxError::DiscontinuousSeries(issues))
     }
 }
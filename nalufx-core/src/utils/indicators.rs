@@ -0,0 +1,658 @@
+use crate::errors::NaluFxError;
+use crate::utils::stats::{mean, std_dev};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The look-back period of the Tenkan-sen (conversion line).
+pub const TENKAN_PERIOD: usize = 9;
+
+/// The look-back period of the Kijun-sen (base line).
+pub const KIJUN_PERIOD: usize = 26;
+
+/// The look-back period of the Senkou Span B (leading span B).
+pub const SENKOU_B_PERIOD: usize = 52;
+
+/// The number of periods by which the cloud is shifted forward and the Chikou Span backward.
+pub const ICHIMOKU_DISPLACEMENT: usize = 26;
+
+/// The default window of the exponential moving average.
+pub const DEFAULT_EMA_WINDOW: usize = 50;
+
+/// The default window of the relative strength index.
+pub const DEFAULT_RSI_WINDOW: usize = 14;
+
+/// The default window of the short EMA of the MACD.
+pub const DEFAULT_MACD_SHORT_WINDOW: usize = 12;
+
+/// The default window of the long EMA of the MACD.
+pub const DEFAULT_MACD_LONG_WINDOW: usize = 26;
+
+/// The default window of the MACD signal line.
+pub const DEFAULT_MACD_SIGNAL_WINDOW: usize = 9;
+
+/// Represents the five lines of the Ichimoku Cloud.
+///
+/// Every line is indexed by the period at which it is plotted, where index `i` is the period
+/// of `closes[i]`. A value is `None` where the line is undefined.
+///
+/// # Fields
+///
+/// * `tenkan_sen` - The midpoint of the highest high and lowest low over the last 9 periods.
+///   It has one value per input period, undefined for the first 8.
+/// * `kijun_sen` - The midpoint of the highest high and lowest low over the last 26 periods.
+///   It has one value per input period, undefined for the first 25.
+/// * `senkou_span_a` - The average of the Tenkan-sen and Kijun-sen, plotted 26 periods ahead:
+///   `senkou_span_a[i + 26]` is calculated from period `i`. It has 26 more values than the
+///   input, and the last 26 are a projection of the cloud beyond the last period.
+/// * `senkou_span_b` - The midpoint of the highest high and lowest low over the last 52
+///   periods, plotted 26 periods ahead like `senkou_span_a`.
+/// * `chikou_span` - The close plotted 26 periods behind: `chikou_span[i]` is `closes[i + 26]`.
+///   It has one value per input period, undefined for the last 26.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IchimokuResult {
+    /// The conversion line.
+    pub tenkan_sen: Vec<Option<f64>>,
+    /// The base line.
+    pub kijun_sen: Vec<Option<f64>>,
+    /// The leading span A, shifted forward by `ICHIMOKU_DISPLACEMENT` periods.
+    pub senkou_span_a: Vec<Option<f64>>,
+    /// The leading span B, shifted forward by `ICHIMOKU_DISPLACEMENT` periods.
+    pub senkou_span_b: Vec<Option<f64>>,
+    /// The lagging span, shifted backward by `ICHIMOKU_DISPLACEMENT` periods.
+    pub chikou_span: Vec<Option<f64>>,
+}
+
+/// Calculates the midpoint of the highest high and lowest low over a rolling window.
+///
+/// The value at index `i` covers the window ending at `i`, and is `None` until `period`
+/// values are available.
+fn rolling_midpoint(highs: &[f64], lows: &[f64], period: usize) -> Vec<Option<f64>> {
+    (0..highs.len())
+        .map(|i| {
+            if i + 1 < period {
+                return None;
+            }
+            let window = i + 1 - period..=i;
+            let highest = highs[window.clone()].iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let lowest = lows[window].iter().copied().fold(f64::INFINITY, f64::min);
+            Some((highest + lowest) / 2.0)
+        })
+        .collect()
+}
+
+/// Calculates the Ichimoku Cloud with the standard 9/26/52 periods.
+///
+/// See `IchimokuResult` for how each line is aligned with the input periods. The cloud is
+/// the area between `senkou_span_a` and `senkou_span_b`.
+///
+/// # Arguments
+///
+/// * `highs` - A slice of the high prices of each period (`&[f64]`).
+/// * `lows` - A slice of the low prices of each period (`&[f64]`).
+/// * `closes` - A slice of the closing prices of each period (`&[f64]`).
+///
+/// # Returns
+///
+/// A `Result` containing the `IchimokuResult`, or a `NaluFxError` if:
+/// - The slices are empty (`EmptyInput`).
+/// - The slices have different lengths (`InputMismatch`).
+/// - A price is `NaN`, or a high is below its low (`InvalidData`).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::indicators::calculate_ichimoku;
+///
+/// let highs: Vec<f64> = (0..60).map(|i| 101.0 + i as f64).collect();
+/// let lows: Vec<f64> = (0..60).map(|i| 99.0 + i as f64).collect();
+/// let closes: Vec<f64> = (0..60).map(|i| 100.0 + i as f64).collect();
+///
+/// let ichimoku = calculate_ichimoku(&highs, &lows, &closes).unwrap();
+/// // (highest high 109 + lowest low 99) / 2 over the first nine periods.
+/// assert_eq!(ichimoku.tenkan_sen[8], Some(104.0));
+/// assert_eq!(ichimoku.senkou_span_a.len(), 60 + 26);
+/// assert_eq!(ichimoku.chikou_span[0], Some(126.0));
+/// ```
+pub fn calculate_ichimoku(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+) -> Result<IchimokuResult, NaluFxError> {
+    if highs.is_empty() || lows.is_empty() || closes.is_empty() {
+        return Err(NaluFxError::EmptyInput);
+    }
+    if highs.len() != lows.len() || highs.len() != closes.len() {
+        return Err(NaluFxError::InputMismatch);
+    }
+    let invalid_bar =
+        highs.iter().zip(lows).zip(closes).any(|((high, low), close)| {
+            high.is_nan() || low.is_nan() || close.is_nan() || high < low
+        });
+    if invalid_bar {
+        return Err(NaluFxError::InvalidData);
+    }
+
+    let tenkan_sen = rolling_midpoint(highs, lows, TENKAN_PERIOD);
+    let kijun_sen = rolling_midpoint(highs, lows, KIJUN_PERIOD);
+
+    let leading = |values: Vec<Option<f64>>| -> Vec<Option<f64>> {
+        let mut shifted = vec![None; ICHIMOKU_DISPLACEMENT];
+        shifted.extend(values);
+        shifted
+    };
+    let senkou_span_a = leading(
+        tenkan_sen
+            .iter()
+            .zip(&kijun_sen)
+            .map(|(&tenkan, &kijun)| Some((tenkan? + kijun?) / 2.0))
+            .collect(),
+    );
+    let senkou_span_b = leading(rolling_midpoint(highs, lows, SENKOU_B_PERIOD));
+
+    let chikou_span =
+        (0..closes.len()).map(|i| closes.get(i + ICHIMOKU_DISPLACEMENT).copied()).collect();
+
+    Ok(IchimokuResult { tenkan_sen, kijun_sen, senkou_span_a, senkou_span_b, chikou_span })
+}
+
+/// Calculates the On-Balance Volume (OBV) of a price series.
+///
+/// The OBV starts at `0.0` and, for each subsequent period, adds the volume if the close is
+/// above the previous close, subtracts it if the close is below, and is unchanged if the close
+/// is equal. A rising OBV confirms a rising price, while an OBV that fails to follow the price
+/// signals a divergence.
+///
+/// # Arguments
+///
+/// * `closes` - A slice of the closing prices of each period (`&[f64]`).
+/// * `volumes` - A slice of the volumes of each period (`&[f64]`).
+///
+/// # Returns
+///
+/// A `Result` containing a vector with one OBV value per period (`Vec<f64>`), empty if the
+/// slices are empty, or `NaluFxError::InputMismatch` if the slices have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::indicators::calculate_obv;
+///
+/// let closes = vec![10.0, 11.0, 11.0, 10.5];
+/// let volumes = vec![100.0, 200.0, 150.0, 50.0];
+/// assert_eq!(calculate_obv(&closes, &volumes).unwrap(), vec![0.0, 200.0, 200.0, 150.0]);
+/// ```
+pub fn calculate_obv(closes: &[f64], volumes: &[f64]) -> Result<Vec<f64>, NaluFxError> {
+    if closes.len() != volumes.len() {
+        return Err(NaluFxError::InputMismatch);
+    }
+    if closes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut obv = Vec::with_capacity(closes.len());
+    let mut balance = 0.0;
+    obv.push(balance);
+    for (pair, volume) in closes.windows(2).zip(&volumes[1..]) {
+        if pair[1] > pair[0] {
+            balance += volume;
+        } else if pair[1] < pair[0] {
+            balance -= volume;
+        }
+        obv.push(balance);
+    }
+
+    Ok(obv)
+}
+
+/// Represents how the first value of an exponential moving average is initialized.
+///
+/// The seed weighs on the early values of the EMA, and so on the early values of the
+/// indicators built on it such as the MACD, until it decays after a few multiples of the
+/// window.
+///
+/// # Variants
+///
+/// * `FirstValue` - The EMA starts at the first value of the series, so it has a value for
+///   every period, even of a series shorter than the window, but its first `window` values
+///   lean towards that single value. This is the default of `EmaSeed` and of the `--ema-seed`
+///   argument of the technical analysis.
+/// * `Sma` - The EMA starts at the simple average of the first `window` values, the
+///   conventional seed, so it has no value for the first `window - 1` periods. The MACD is
+///   more accurate with this seed. [`calculate_ema`], and the indicators built on it such as
+///   [`calculate_keltner_channels`], always use this seed.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::indicators::EmaSeed;
+///
+/// assert_eq!("sma".parse::<EmaSeed>().unwrap(), EmaSeed::Sma);
+/// assert_eq!(EmaSeed::default().to_string(), "first-value");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EmaSeed {
+    /// The EMA starts at the first value of the series.
+    #[default]
+    FirstValue,
+    /// The EMA starts at the simple average of the first `window` values.
+    Sma,
+}
+
+impl fmt::Display for EmaSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmaSeed::FirstValue => write!(f, "first-value"),
+            EmaSeed::Sma => write!(f, "sma"),
+        }
+    }
+}
+
+impl FromStr for EmaSeed {
+    type Err = NaluFxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "first-value" => Ok(EmaSeed::FirstValue),
+            "sma" => Ok(EmaSeed::Sma),
+            _ => Err(NaluFxError::TechnicalAnalysisError(format!(
+                "Unknown EMA seed {:?}, expected first-value or sma",
+                s
+            ))),
+        }
+    }
+}
+
+/// Calculates the exponential moving average (EMA) of a series.
+///
+/// The EMA is seeded with the simple average of the first `window` values (`EmaSeed::Sma`),
+/// rather than with the default `EmaSeed::FirstValue`, so that its values and those of the
+/// indicators built on it do not change with the seed. Use [`calculate_ema_with_seed`] to
+/// choose the seed. Each subsequent value is `(value - previous) * 2 / (window + 1) + previous`,
+/// giving more weight to recent values than a simple moving average.
+///
+/// # Arguments
+///
+/// * `data` - A slice of values, e.g. closing prices (`&[f64]`).
+/// * `window` - The number of periods of the EMA (`usize`).
+///
+/// # Returns
+///
+/// A vector of `data.len() - window + 1` EMA values (`Vec<f64>`), where the value at index `i`
+/// is the EMA at period `i + window - 1`. The vector is empty if `window` is zero or `data` has
+/// fewer than `window` values.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::indicators::calculate_ema;
+///
+/// // Seeded with (1 + 2 + 3) / 3 = 2, then (5 - 2) * 0.5 + 2 = 3.5.
+/// assert_eq!(calculate_ema(&[1.0, 2.0, 3.0, 5.0], 3), vec![2.0, 3.5]);
+/// assert!(calculate_ema(&[1.0, 2.0], 3).is_empty());
+/// ```
+pub fn calculate_ema(data: &[f64], window: usize) -> Vec<f64> {
+    calculate_ema_with_seed(data, window, EmaSeed::Sma)
+}
+
+/// Calculates the exponential moving average (EMA) of a series with the given seed.
+///
+/// Each value after the seed is `(value - previous) * 2 / (window + 1) + previous`. See
+/// [`EmaSeed`] for how the seeds differ.
+///
+/// # Arguments
+///
+/// * `data` - A slice of values, e.g. closing prices (`&[f64]`).
+/// * `window` - The number of periods of the EMA (`usize`).
+/// * `seed` - How the first value of the EMA is initialized (`EmaSeed`).
+///
+/// # Returns
+///
+/// A vector of EMA values (`Vec<f64>`) aligned with the end of `data`:
+///
+/// - with `EmaSeed::FirstValue`, one value per value of `data`, whatever the window;
+/// - with `EmaSeed::Sma`, `data.len() - window + 1` values like [`calculate_ema`], or none if
+///   `data` has fewer than `window` values.
+///
+/// The vector is empty if `window` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::indicators::{calculate_ema_with_seed, EmaSeed};
+///
+/// let data = [1.0, 2.0, 3.0, 5.0];
+/// // 1, then (2 - 1) * 0.5 + 1 = 1.5, (3 - 1.5) * 0.5 + 1.5 = 2.25, (5 - 2.25) * 0.5 + 2.25.
+/// assert_eq!(calculate_ema_with_seed(&data, 3, EmaSeed::FirstValue), vec![1.0, 1.5, 2.25, 3.625]);
+/// assert_eq!(calculate_ema_with_seed(&data, 3, EmaSeed::Sma), vec![2.0, 3.5]);
+///
+/// // A series shorter than the window still has one value per period with the first value.
+/// assert_eq!(calculate_ema_with_seed(&data[..2], 3, EmaSeed::FirstValue), vec![1.0, 1.5]);
+/// assert!(calculate_ema_with_seed(&data[..2], 3, EmaSeed::Sma).is_empty());
+/// ```
+pub fn calculate_ema_with_seed(data: &[f64], window: usize, seed: EmaSeed) -> Vec<f64> {
+    if window == 0 {
+        return Vec::new();
+    }
+
+    let (mut current, rest) = match seed {
+        EmaSeed::FirstValue => match data.split_first() {
+            Some((&first, rest)) => (first, rest),
+            None => return Vec::new(),
+        },
+        EmaSeed::Sma if data.len() < window => return Vec::new(),
+        EmaSeed::Sma => (data[..window].iter().sum::<f64>() / window as f64, &data[window..]),
+    };
+    let multiplier = 2.0 / (window as f64 + 1.0);
+    let mut ema = Vec::with_capacity(rest.len() + 1);
+    ema.push(current);
+    for value in rest {
+        current += (value - current) * multiplier;
+        ema.push(current);
+    }
+
+    ema
+}
+
+/// Calculates the Average True Range (ATR) of a price series.
+///
+/// The true range of a period is the largest of its high less its low, and the distances from
+/// the previous close to its high and low, so that gaps between periods count as volatility.
+/// The first period has no previous close, so its true range is its high less its low. The ATR
+/// is seeded with the average of the first `window` true ranges, then smoothed with Wilder's
+/// method: `(previous * (window - 1) + true_range) / window`.
+///
+/// # Arguments
+///
+/// * `highs` - A slice of the high prices of each period (`&[f64]`).
+/// * `lows` - A slice of the low prices of each period (`&[f64]`).
+/// * `closes` - A slice of the closing prices of each period (`&[f64]`).
+/// * `window` - The number of periods of the ATR (`usize`).
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `closes.len() - window + 1` ATR values (`Vec<f64>`),
+/// aligned like `calculate_ema`, or `NaluFxError::InputMismatch` if the slices have different
+/// lengths. The vector is empty if `window` is zero or there are fewer than `window` periods.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::indicators::calculate_atr;
+///
+/// let highs = vec![11.0, 12.0, 14.0];
+/// let lows = vec![9.0, 10.0, 12.0];
+/// let closes = vec![10.0, 11.0, 13.0];
+/// // True ranges 2, 2 and 3 (the high of 14 less the previous close of 11).
+/// assert_eq!(calculate_atr(&highs, &lows, &closes, 2).unwrap(), vec![2.0, 2.5]);
+/// ```
+pub fn calculate_atr(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    window: usize,
+) -> Result<Vec<f64>, NaluFxError> {
+    if highs.len() != lows.len() || highs.len() != closes.len() {
+        return Err(NaluFxError::InputMismatch);
+    }
+    if window == 0 || closes.len() < window {
+        return Ok(Vec::new());
+    }
+
+    let mut true_ranges = Vec::with_capacity(closes.len());
+    true_ranges.push(highs[0] - lows[0]);
+    for ((high, low), previous_close) in highs[1..].iter().zip(&lows[1..]).zip(closes) {
+        true_ranges.push(
+            (high - low).max((high - previous_close).abs()).max((low - previous_close).abs()),
+        );
+    }
+
+    let mut atr = Vec::with_capacity(closes.len() - window + 1);
+    let mut current = true_ranges[..window].iter().sum::<f64>() / window as f64;
+    atr.push(current);
+    for true_range in &true_ranges[window..] {
+        current = (current * (window as f64 - 1.0) + true_range) / window as f64;
+        atr.push(current);
+    }
+
+    Ok(atr)
+}
+
+/// Calculates the Bollinger Bands of a series.
+///
+/// The middle band is the simple moving average over `window` periods, and the upper and lower
+/// bands are `num_std_dev` population standard deviations above and below it.
+///
+/// # Arguments
+///
+/// * `closes` - A slice of the closing prices of each period (`&[f64]`).
+/// * `window` - The number of periods of the moving average (`usize`).
+/// * `num_std_dev` - The number of standard deviations between the middle and outer bands
+///   (`f64`), typically `2.0`.
+///
+/// # Returns
+///
+/// A tuple of the upper, middle and lower bands (`(Vec<f64>, Vec<f64>, Vec<f64>)`), each with
+/// `closes.len() - window + 1` values aligned like `calculate_ema`. The vectors are empty if
+/// `window` is zero or there are fewer than `window` closes.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::indicators::calculate_bollinger_bands;
+///
+/// let closes = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+/// let (upper, middle, lower) = calculate_bollinger_bands(&closes, 8, 2.0);
+/// assert_eq!((upper, middle, lower), (vec![9.0], vec![5.0], vec![1.0]));
+/// ```
+pub fn calculate_bollinger_bands(
+    closes: &[f64],
+    window: usize,
+    num_std_dev: f64,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    if window == 0 || closes.len() < window {
+        return (Vec::new(), Vec::new(), Vec::new());
+    }
+
+    let mut upper = Vec::with_capacity(closes.len() - window + 1);
+    let mut middle = Vec::with_capacity(closes.len() - window + 1);
+    let mut lower = Vec::with_capacity(closes.len() - window + 1);
+    for values in closes.windows(window) {
+        let average = mean(values).unwrap_or(f64::NAN);
+        let deviation = std_dev(values, 0).unwrap_or(f64::NAN);
+        upper.push(average + num_std_dev * deviation);
+        middle.push(average);
+        lower.push(average - num_std_dev * deviation);
+    }
+
+    (upper, middle, lower)
+}
+
+/// Calculates the Keltner Channels of a price series.
+///
+/// The middle line is the EMA of the closes over `ema_window` periods, and the upper and lower
+/// channels are `multiplier` times the ATR over `atr_window` periods above and below it. Unlike
+/// Bollinger Bands, whose width follows the standard deviation of the closes, the channel width
+/// follows the average true range, which makes the two useful together (see `is_squeeze`).
+///
+/// # Arguments
+///
+/// * `highs` - A slice of the high prices of each period (`&[f64]`).
+/// * `lows` - A slice of the low prices of each period (`&[f64]`).
+/// * `closes` - A slice of the closing prices of each period (`&[f64]`).
+/// * `ema_window` - The number of periods of the middle line EMA (`usize`), typically `20`.
+/// * `atr_window` - The number of periods of the ATR (`usize`), typically `10`.
+/// * `multiplier` - The number of ATRs between the middle line and the channels (`f64`),
+///   typically `2.0`.
+///
+/// # Returns
+///
+/// A `Result` containing a tuple of the upper, middle and lower channels
+/// (`(Vec<f64>, Vec<f64>, Vec<f64>)`), or `NaluFxError::InputMismatch` if the slices have
+/// different lengths. Each channel has one value per period from the first at which both the
+/// EMA and ATR are defined, so the last value is always that of the last period. The vectors
+/// are empty if either window is zero or there are fewer periods than the longer window.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::indicators::calculate_keltner_channels;
+///
+/// let highs = vec![11.0; 30];
+/// let lows = vec![9.0; 30];
+/// let closes = vec![10.0; 30];
+/// let (upper, middle, lower) =
+///     calculate_keltner_channels(&highs, &lows, &closes, 20, 10, 2.0).unwrap();
+/// assert_eq!(middle.len(), 11);
+/// assert_eq!((upper[0], middle[0], lower[0]), (14.0, 10.0, 6.0));
+/// ```
+pub fn calculate_keltner_channels(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    ema_window: usize,
+    atr_window: usize,
+    multiplier: f64,
+) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>), NaluFxError> {
+    let ema = calculate_ema(closes, ema_window);
+    let atr = calculate_atr(highs, lows, closes, atr_window)?;
+    if ema.is_empty() || atr.is_empty() {
+        return Ok((Vec::new(), Vec::new(), Vec::new()));
+    }
+
+    // Both series end at the last period, so align them on the shorter one.
+    let len = ema.len().min(atr.len());
+    let ema = &ema[ema.len() - len..];
+    let atr = &atr[atr.len() - len..];
+    let upper = ema.iter().zip(atr).map(|(middle, range)| middle + multiplier * range).collect();
+    let lower = ema.iter().zip(atr).map(|(middle, range)| middle - multiplier * range).collect();
+
+    Ok((upper, ema.to_vec(), lower))
+}
+
+/// Checks whether the Bollinger Bands are in a squeeze inside the Keltner Channels.
+///
+/// A squeeze is when volatility contracts so much that the Bollinger Bands fit entirely inside
+/// the Keltner Channels at the last period. Traders watch for the end of a squeeze, when the
+/// bands expand back outside the channels, as a sign of an imminent breakout.
+///
+/// # Arguments
+///
+/// * `bollinger_upper` - The upper Bollinger Band (`&[f64]`).
+/// * `bollinger_lower` - The lower Bollinger Band (`&[f64]`).
+/// * `keltner_upper` - The upper Keltner Channel (`&[f64]`).
+/// * `keltner_lower` - The lower Keltner Channel (`&[f64]`).
+///
+/// # Returns
+///
+/// `true` if the last upper Bollinger Band is below the last upper Keltner Channel and the
+/// last lower Bollinger Band is above the last lower Keltner Channel, and `false` otherwise,
+/// including when any of the slices is empty.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::indicators::is_squeeze;
+///
+/// assert!(is_squeeze(&[10.5], &[9.5], &[11.0], &[9.0]));
+/// assert!(!is_squeeze(&[12.0], &[8.0], &[11.0], &[9.0]));
+/// assert!(!is_squeeze(&[], &[], &[11.0], &[9.0]));
+/// ```
+pub fn is_squeeze(
+    bollinger_upper: &[f64],
+    bollinger_lower: &[f64],
+    keltner_upper: &[f64],
+    keltner_lower: &[f64],
+) -> bool {
+    match (
+        bollinger_upper.last(),
+        bollinger_lower.last(),
+        keltner_upper.last(),
+        keltner_lower.last(),
+    ) {
+        (Some(bb_upper), Some(bb_lower), Some(kc_upper), Some(kc_lower)) => {
+            bb_upper < kc_upper && bb_lower > kc_lower
+        },
+        _ => false,
+    }
+}
+
+/// Represents the windows of the EMA, RSI and MACD indicators of a technical analysis.
+///
+/// The default windows are the usual 50-period EMA, 14-period RSI and 12/26/9 MACD.
+///
+/// # Fields
+///
+/// * `ema` - The window of the exponential moving average.
+/// * `rsi` - The window of the relative strength index.
+/// * `macd_short` - The window of the short EMA of the MACD.
+/// * `macd_long` - The window of the long EMA of the MACD.
+/// * `macd_signal` - The window of the MACD signal line.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::indicators::IndicatorWindows;
+///
+/// let windows = IndicatorWindows { ema: 20, ..IndicatorWindows::default() };
+/// assert!(windows.validate().is_ok());
+///
+/// let windows = IndicatorWindows { macd_short: 26, macd_long: 12, ..IndicatorWindows::default() };
+/// assert!(windows.validate().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IndicatorWindows {
+    /// The window of the exponential moving average.
+    pub ema: usize,
+    /// The window of the relative strength index.
+    pub rsi: usize,
+    /// The window of the short EMA of the MACD.
+    pub macd_short: usize,
+    /// The window of the long EMA of the MACD.
+    pub macd_long: usize,
+    /// The window of the MACD signal line.
+    pub macd_signal: usize,
+}
+
+impl Default for IndicatorWindows {
+    fn default() -> Self {
+        Self {
+            ema: DEFAULT_EMA_WINDOW,
+            rsi: DEFAULT_RSI_WINDOW,
+            macd_short: DEFAULT_MACD_SHORT_WINDOW,
+            macd_long: DEFAULT_MACD_LONG_WINDOW,
+            macd_signal: DEFAULT_MACD_SIGNAL_WINDOW,
+        }
+    }
+}
+
+impl IndicatorWindows {
+    /// Checks that every window is positive and that the short MACD window is shorter than
+    /// the long one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NaluFxError::TechnicalAnalysisError` naming the invalid window.
+    pub fn validate(&self) -> Result<(), NaluFxError> {
+        let windows = [
+            ("EMA", self.ema),
+            ("RSI", self.rsi),
+            ("MACD short", self.macd_short),
+            ("MACD long", self.macd_long),
+            ("MACD signal", self.macd_signal),
+        ];
+        if let Some((name, _)) = windows.iter().find(|(_, window)| *window == 0) {
+            return Err(NaluFxError::TechnicalAnalysisError(format!(
+                "The {} window must be positive",
+                name
+            )));
+        }
+        if self.macd_short >= self.macd_long {
+            return Err(NaluFxError::TechnicalAnalysisError(format!(
+                "The MACD short window ({}) must be shorter than the long window ({})",
+                self.macd_short, self.macd_long
+            )));
+        }
+        Ok(())
+    }
+}
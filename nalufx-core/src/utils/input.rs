@@ -1,6 +1,9 @@
+use crate::config::{output_mode, OutputMode};
 use crate::errors::NaluFxError;
+use crate::utils::{date::parse_range_preset, validation::validate_date};
+use chrono::{DateTime, Utc};
 use std::io::BufRead;
-use std::io::{stdin, stdout, Write};
+use std::io::{stderr, stdin, stdout, Write};
 
 /// Reads a line of user input from the standard input.
 ///
@@ -34,6 +37,9 @@ pub fn read_user_input() -> Result<String, NaluFxError> {
 
 /// Prompts the user with a message and reads a line of input from the standard input.
 ///
+/// The prompt is written to stdout, or to stderr in JSON output mode (see `output_mode`) so
+/// that stdout contains only the JSON document.
+///
 /// # Arguments
 ///
 /// * `prompt` - A message displayed to the user before reading the input.
@@ -47,10 +53,46 @@ pub fn read_user_input() -> Result<String, NaluFxError> {
 ///
 /// Returns an `NaluFxError::InputError` if an error occurs while reading the input.
 pub fn get_input(prompt: &str) -> Result<String, NaluFxError> {
-    print!("{}", prompt);
-    stdout().flush().map_err(NaluFxError::InputError)?;
+    if output_mode() == OutputMode::Json {
+        eprint!("{}", prompt);
+        stderr().flush().map_err(NaluFxError::InputError)?;
+    } else {
+        print!("{}", prompt);
+        stdout().flush().map_err(NaluFxError::InputError)?;
+    }
 
     let mut input = String::new();
     let _ = stdin().lock().read_line(&mut input).map_err(NaluFxError::InputError)?;
     Ok(input.trim().to_string())
 }
+
+/// Prompts the user for a date range, either as a named preset or as explicit dates.
+///
+/// The user is first asked for a preset (see `parse_range_preset`), such as `1M`, `YTD` or
+/// `5Y`, or a start date in the format `YYYY-MM-DD`. If a start date is entered, the user is
+/// then asked for the end date in the same format.
+///
+/// # Returns
+///
+/// A `Result` containing the start and end dates if successful, or a `NaluFxError` if an
+/// error occurs during reading or a date is invalid.
+///
+/// # Errors
+///
+/// Returns an `NaluFxError::InputError` if an error occurs while reading the input, or an
+/// `NaluFxError::InvalidInput` if the input is neither a preset nor a valid date.
+pub fn get_date_range() -> Result<(DateTime<Utc>, DateTime<Utc>), NaluFxError> {
+    let start_input = get_input(
+        "Enter a date range preset (1M, 6M, YTD, 1Y, 5Y, MAX) or the start date (YYYY-MM-DD):",
+    )?;
+    if let Some(range) = parse_range_preset(&start_input) {
+        return Ok(range);
+    }
+
+    let start_date = validate_date(&start_input)?;
+
+    let end_input = get_input("Enter the end date (YYYY-MM-DD):")?;
+    let end_date = validate_date(&end_input)?;
+
+    Ok((start_date, end_date))
+}
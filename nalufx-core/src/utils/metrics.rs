@@ -0,0 +1,130 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// The metrics registry of the application, updated by the handlers and services.
+pub static METRICS: Metrics = Metrics::new();
+
+/// Represents a registry of counters, exposed in the Prometheus text format.
+///
+/// Every counter is an atomic, so the registry can be updated from any thread without
+/// locking. The application uses the global `METRICS` registry, which is exposed by the
+/// `GET /metrics` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::metrics::Metrics;
+/// use std::time::Duration;
+///
+/// let metrics = Metrics::new();
+/// metrics.record_prediction();
+/// metrics.record_llm_request(Duration::from_millis(1500), true);
+///
+/// let output = metrics.render();
+/// assert!(output.contains("nalufx_predictions_total 1\n"));
+/// assert!(output.contains("nalufx_llm_request_duration_seconds_sum 1.5\n"));
+/// ```
+#[derive(Debug, Default)]
+pub struct Metrics {
+    predictions_total: AtomicU64,
+    prediction_errors_total: AtomicU64,
+    fetch_failures_total: AtomicU64,
+    llm_request_errors_total: AtomicU64,
+    llm_request_duration_micros: AtomicU64,
+    llm_requests_total: AtomicU64,
+}
+
+impl Metrics {
+    /// Creates a new `Metrics` registry with every counter at zero.
+    pub const fn new() -> Self {
+        Self {
+            predictions_total: AtomicU64::new(0),
+            prediction_errors_total: AtomicU64::new(0),
+            fetch_failures_total: AtomicU64::new(0),
+            llm_request_errors_total: AtomicU64::new(0),
+            llm_request_duration_micros: AtomicU64::new(0),
+            llm_requests_total: AtomicU64::new(0),
+        }
+    }
+
+    /// Records a successful cash flow prediction.
+    pub fn record_prediction(&self) {
+        let _ = self.predictions_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a cash flow prediction that failed.
+    pub fn record_prediction_error(&self) {
+        let _ = self.prediction_errors_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a market data fetch that failed.
+    pub fn record_fetch_failure(&self) {
+        let _ = self.fetch_failures_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records an LLM request, with its latency and whether it succeeded.
+    pub fn record_llm_request(&self, latency: Duration, success: bool) {
+        let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        let _ = self.llm_request_duration_micros.fetch_add(micros, Ordering::Relaxed);
+        let _ = self.llm_requests_total.fetch_add(1, Ordering::Relaxed);
+        if !success {
+            let _ = self.llm_request_errors_total.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the number of successful cash flow predictions.
+    pub fn predictions_total(&self) -> u64 {
+        self.predictions_total.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of market data fetches that failed.
+    pub fn fetch_failures_total(&self) -> u64 {
+        self.fetch_failures_total.load(Ordering::Relaxed)
+    }
+
+    /// Renders the metrics in the Prometheus text exposition format.
+    ///
+    /// The LLM request latency is exposed as a summary, with the total latency in seconds
+    /// as `_sum` and the number of requests as `_count`.
+    pub fn render(&self) -> String {
+        let counters = [
+            (
+                "nalufx_predictions_total",
+                "Total number of successful cash flow predictions.",
+                &self.predictions_total,
+            ),
+            (
+                "nalufx_prediction_errors_total",
+                "Total number of cash flow predictions that failed.",
+                &self.prediction_errors_total,
+            ),
+            (
+                "nalufx_fetch_failures_total",
+                "Total number of market data fetches that failed.",
+                &self.fetch_failures_total,
+            ),
+            (
+                "nalufx_llm_request_errors_total",
+                "Total number of LLM requests that failed.",
+                &self.llm_request_errors_total,
+            ),
+        ];
+
+        let mut output = String::new();
+        for (name, help, counter) in counters {
+            let _ = writeln!(output, "# HELP {} {}", name, help);
+            let _ = writeln!(output, "# TYPE {} counter", name);
+            let _ = writeln!(output, "{} {}", name, counter.load(Ordering::Relaxed));
+        }
+
+        let name = "nalufx_llm_request_duration_seconds";
+        let seconds = self.llm_request_duration_micros.load(Ordering::Relaxed) as f64 / 1e6;
+        let _ = writeln!(output, "# HELP {} Latency of the LLM requests.", name);
+        let _ = writeln!(output, "# TYPE {} summary", name);
+        let _ = writeln!(output, "{}_sum {}", name, seconds);
+        let _ =
+            writeln!(output, "{}_count {}", name, self.llm_requests_total.load(Ordering::Relaxed));
+        output
+    }
+}
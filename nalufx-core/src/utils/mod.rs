@@ -1,21 +1,67 @@
+/// This module will return errors if allocations are empty, hold non-finite values, have no
+/// positive value to normalize, or cannot meet their weight constraints.
+pub mod allocation;
+
 /// This module provides functionality for generating ASCII art from text using the FIGlet library.
 pub mod ascii;
 
+/// This module will return errors if the command-line arguments of an example are invalid or a
+/// missing argument cannot be read from the standard input.
+pub mod args;
+
+/// This module runs batch operations with a concurrency limit and progress reporting.
+pub mod batch;
+
+/// This module provides the selection of a benchmark by ticker symbol and asset class.
+pub mod benchmark;
+
 /// This module will return errors if the calculations fail due to invalid input data,
 /// mathematical errors, or insufficient data for analysis.
 pub mod calculations;
 
+/// This module provides a trading calendar that skips weekends and market holidays.
+pub mod calendar;
+
+/// This module will return errors if a chart receives an empty or non-finite series, or cannot be
+/// written.
+pub mod charts;
+
 /// This module provides utilities for formatting currency values.
 pub mod currency;
 
 /// This module provides utilities for date and time operations.
 pub mod date;
 
+/// This module will return errors if the technical indicators receive empty, mismatched or
+/// invalid price series.
+pub mod indicators;
+
 /// This module provides utilities for reading user input from the standard input.
 pub mod input;
 
-/// This module provides utilities for ticker symbol operations.
-pub mod ticker;
+/// This module provides the application metrics, exposed in the Prometheus text format.
+pub mod metrics;
+
+/// This module will return errors if a portfolio file cannot be read or written, or holds an
+/// invalid ticker symbol or share count.
+pub mod portfolio;
+
+/// This module will return errors if a report file or the reports directory cannot be created.
+pub mod reports;
+
+/// This module provides utilities for resampling price series to a lower frequency.
+pub mod resample;
+
+/// This module will return errors if the risk and performance metrics receive empty or
+/// mismatched return series, or series for which a metric is undefined.
+pub mod risk;
+
+/// This module will return errors if the statistical helpers receive empty input,
+/// `NaN` values that the selected `NanPolicy` rejects, or a percentile outside `[0, 100]`.
+pub mod stats;
+
+/// This module provides utilities for rounding allocations to whole lots or fractional shares.
+pub mod trading;
 
 /// This module provides utilities for validations.
 pub mod validation;
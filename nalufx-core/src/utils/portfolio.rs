@@ -0,0 +1,133 @@
+use crate::{
+    errors::NaluFxError,
+    utils::{input::get_input, validation::validate_ticker},
+};
+use serde::{Deserialize, Serialize};
+use std::{env, fs::File, io::Read, path::Path};
+
+/// The environment variable that points to a portfolio CSV file, used by the examples instead of
+/// prompting for the ticker symbols.
+pub const PORTFOLIO_CSV_ENV: &str = "PORTFOLIO_CSV";
+
+/// Represents a holding of a portfolio.
+///
+/// # Fields
+///
+/// * `ticker` - The ticker symbol of the holding, in upper case.
+/// * `shares_or_weight` - The number of shares held, or the weight of the holding in the
+///   portfolio, depending on how the file is used.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Holding {
+    /// The ticker symbol of the holding.
+    pub ticker: String,
+    /// The number of shares held, or the weight of the holding in the portfolio.
+    pub shares_or_weight: f64,
+}
+
+/// Parses a portfolio from CSV data with `ticker,shares_or_weight` columns.
+///
+/// Ticker symbols are trimmed, converted to upper case and validated with
+/// [`validate_ticker`].
+///
+/// # Arguments
+///
+/// * `reader` - Any reader producing CSV data with a header row.
+///
+/// # Errors
+///
+/// Returns `NaluFxError::CsvError` if the data cannot be parsed, `NaluFxError::InvalidTicker` if
+/// a ticker symbol is invalid, `NaluFxError::InvalidData` if a share count or weight is not a
+/// positive finite number, or `NaluFxError::EmptyInput` if the portfolio has no holdings.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::portfolio::parse_portfolio;
+///
+/// let csv = "ticker,shares_or_weight\nspy,0.6\nIEF,0.4\n";
+/// let holdings = parse_portfolio(csv.as_bytes()).unwrap();
+/// assert_eq!(holdings[0].ticker, "SPY");
+/// assert_eq!(holdings[1].shares_or_weight, 0.4);
+///
+/// assert!(parse_portfolio("ticker,shares_or_weight\nSPY$,1\n".as_bytes()).is_err());
+/// ```
+pub fn parse_portfolio<R: Read>(reader: R) -> Result<Vec<Holding>, NaluFxError> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(reader);
+    let mut holdings = Vec::new();
+    for record in reader.deserialize() {
+        let mut holding: Holding = record?;
+        holding.ticker = holding.ticker.to_uppercase();
+        let _ = validate_ticker(&holding.ticker)?;
+        if !(holding.shares_or_weight > 0.0 && holding.shares_or_weight.is_finite()) {
+            return Err(NaluFxError::InvalidData);
+        }
+        holdings.push(holding);
+    }
+    if holdings.is_empty() {
+        return Err(NaluFxError::EmptyInput);
+    }
+    Ok(holdings)
+}
+
+/// Loads a portfolio from the CSV file at the given path.
+///
+/// See [`parse_portfolio`] for the format of the file.
+///
+/// # Arguments
+///
+/// * `path` - The path of the CSV file (e.g., "portfolio.csv").
+///
+/// # Errors
+///
+/// Returns `NaluFxError::CsvError` if the file cannot be read or parsed, or any error of
+/// [`parse_portfolio`].
+pub fn load_portfolio(path: &Path) -> Result<Vec<Holding>, NaluFxError> {
+    let file = File::open(path).map_err(csv::Error::from)?;
+    parse_portfolio(file)
+}
+
+/// Saves a portfolio to a CSV file with `ticker,shares_or_weight` columns, which
+/// [`load_portfolio`] reads back.
+///
+/// # Arguments
+///
+/// * `path` - The path of the CSV file. An existing file is overwritten.
+/// * `holdings` - The holdings of the portfolio.
+///
+/// # Errors
+///
+/// Returns `NaluFxError::CsvError` if the file cannot be created or written.
+pub fn save_portfolio(path: &Path, holdings: &[Holding]) -> Result<(), NaluFxError> {
+    let mut writer = csv::Writer::from_path(path)?;
+    for holding in holdings {
+        writer.serialize(holding)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Returns the ticker symbols of the portfolio file set by `PORTFOLIO_CSV`, or prompts for
+/// comma-separated ticker symbols if the variable is unset or empty.
+///
+/// This lets the examples run against a saved portfolio without typing the ticker symbols at
+/// every run. Interactive ticker symbols are validated like those of the file.
+///
+/// # Arguments
+///
+/// * `prompt` - The prompt to display when reading the ticker symbols from the standard input.
+///
+/// # Errors
+///
+/// Returns any error of [`load_portfolio`], `NaluFxError::InputError` if the input cannot be
+/// read, or `NaluFxError::InvalidTicker` if an entered ticker symbol is invalid.
+pub fn get_portfolio_tickers(prompt: &str) -> Result<Vec<String>, NaluFxError> {
+    if let Some(path) = env::var(PORTFOLIO_CSV_ENV).ok().filter(|path| !path.trim().is_empty()) {
+        let holdings = load_portfolio(Path::new(&path))?;
+        return Ok(holdings.into_iter().map(|holding| holding.ticker).collect());
+    }
+
+    get_input(prompt)?
+        .split(',')
+        .map(|ticker| validate_ticker(ticker.trim()).map(str::to_string))
+        .collect()
+}
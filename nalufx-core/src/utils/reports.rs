@@ -0,0 +1,224 @@
+use crate::{config::reports_dir, errors::NaluFxError};
+use chrono::{DateTime, Utc};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Writes reports to timestamped files in a reports directory.
+///
+/// Every report is named `{timestamp}_{name}.{extension}`, where the timestamp is the UTC
+/// date and time of the run (e.g. `2024-06-28_143015`), so successive runs keep their
+/// reports side by side. If a report with that name already exists, e.g. because two runs
+/// started within the same second, a counter is appended (`..._2.md`, `..._3.md`, ...)
+/// instead of overwriting it.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::reports::ReportSink;
+/// use std::env;
+///
+/// let sink = ReportSink::new(env::temp_dir().join("nalufx-doc-sink"));
+/// let path = sink.write("example", "md", "# Example report\n").unwrap();
+/// assert!(path.file_name().unwrap().to_str().unwrap().ends_with("_example.md"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReportSink {
+    dir: PathBuf,
+}
+
+impl ReportSink {
+    /// Creates a new `ReportSink` writing to the given directory, created on the first report
+    /// if it does not exist.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Creates a `ReportSink` writing to the directory set by `NALUFX_REPORTS_DIR` (see
+    /// [`reports_dir`](crate::config::reports_dir)).
+    pub fn from_env() -> Self {
+        Self::new(reports_dir())
+    }
+
+    /// Creates a `ReportSink` writing to `output_dir` if it is given, e.g. by an
+    /// `--output-dir` argument, or to the directory set by the environment otherwise.
+    pub fn from_output_dir(output_dir: Option<PathBuf>) -> Self {
+        output_dir.map_or_else(Self::from_env, Self::new)
+    }
+
+    /// Returns the directory the reports are written to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Creates a new report file, timestamped with the current time.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the report, without timestamp or extension (e.g.
+    ///   "bellwether_AAPL").
+    /// * `extension` - The extension of the report file (e.g. "md").
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the path and the handle of the created file, or a
+    /// `NaluFxError::InputError` naming the directory or file that could not be created.
+    pub fn create(&self, name: &str, extension: &str) -> Result<(PathBuf, File), NaluFxError> {
+        self.create_at(Utc::now(), name, extension)
+    }
+
+    /// Creates a new report file, timestamped with the given time.
+    ///
+    /// An existing report is never overwritten: a counter is appended to the name until it
+    /// is unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The time of the run the report belongs to.
+    /// * `name` - The name of the report, without timestamp or extension.
+    /// * `extension` - The extension of the report file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the path and the handle of the created file, or a
+    /// `NaluFxError::InputError` naming the directory or file that could not be created.
+    pub fn create_at(
+        &self,
+        timestamp: DateTime<Utc>,
+        name: &str,
+        extension: &str,
+    ) -> Result<(PathBuf, File), NaluFxError> {
+        create_reports_dir(&self.dir)?;
+
+        let stem = format!("{}_{}", timestamp.format("%Y-%m-%d_%H%M%S"), name);
+        for counter in 1.. {
+            let file_name = if counter == 1 {
+                format!("{}.{}", stem, extension)
+            } else {
+                format!("{}_{}.{}", stem, counter, extension)
+            };
+            let path = self.dir.join(file_name);
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((path, file)),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(report_file_error(&path, e)),
+            }
+        }
+        unreachable!("the counter of report file names is unbounded")
+    }
+
+    /// Writes a new report file with the given contents, timestamped with the current time.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the report, without timestamp or extension.
+    /// * `extension` - The extension of the report file.
+    /// * `contents` - The contents of the report.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the path of the written file, or a `NaluFxError::InputError` if
+    /// it cannot be created or written.
+    pub fn write(
+        &self,
+        name: &str,
+        extension: &str,
+        contents: &str,
+    ) -> Result<PathBuf, NaluFxError> {
+        let (path, mut file) = self.create(name, extension)?;
+        file.write_all(contents.as_bytes()).map_err(|e| report_file_error(&path, e))?;
+        Ok(path)
+    }
+}
+
+/// Creates the reports directory and any missing parents.
+fn create_reports_dir(reports_dir: &Path) -> Result<(), NaluFxError> {
+    fs::create_dir_all(reports_dir).map_err(|e| {
+        NaluFxError::InputError(io::Error::new(
+            e.kind(),
+            format!("Failed to create reports directory {}: {}", reports_dir.display(), e),
+        ))
+    })
+}
+
+/// Wraps an error creating or writing a report file, naming the file.
+fn report_file_error(path: &Path, e: io::Error) -> NaluFxError {
+    NaluFxError::InputError(io::Error::new(
+        e.kind(),
+        format!("Failed to create report file {}: {}", path.display(), e),
+    ))
+}
+
+/// Formats the rows of a daily series for the console, truncated to `max_display_days` rows.
+///
+/// A long allocation horizon would otherwise flood the terminal with one line per day. When
+/// the series has more than `max_display_days` items, only the first `max_display_days` rows
+/// are returned, followed by a `...and N more days` summary line. Exported files should be
+/// written from the full series instead.
+///
+/// # Arguments
+///
+/// * `items` - The daily items, in chronological order.
+/// * `max_display_days` - The largest number of rows to return (see
+///   [`max_display_days`](crate::config::max_display_days)).
+/// * `format_row` - Formats the item of a day, given its zero-based index.
+///
+/// # Returns
+///
+/// The formatted rows, plus the summary line if the series was truncated.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::reports::format_daily_rows;
+///
+/// let allocations = [0.5, 0.3, 0.2];
+/// let rows = format_daily_rows(&allocations, 2, |i, a| format!("- Day {}: {:.1}", i + 1, a));
+/// assert_eq!(rows, vec!["- Day 1: 0.5", "- Day 2: 0.3", "...and 1 more day"]);
+/// ```
+pub fn format_daily_rows<T>(
+    items: &[T],
+    max_display_days: usize,
+    format_row: impl Fn(usize, &T) -> String,
+) -> Vec<String> {
+    let mut rows: Vec<String> = items
+        .iter()
+        .take(max_display_days)
+        .enumerate()
+        .map(|(i, item)| format_row(i, item))
+        .collect();
+    let hidden = items.len().saturating_sub(max_display_days);
+    if hidden > 0 {
+        rows.push(format!("...and {} more day{}", hidden, if hidden == 1 { "" } else { "s" }));
+    }
+    rows
+}
+
+/// Formats a vector of values for the console, with a fixed number of decimal places.
+///
+/// The `{:?}` debug format prints every digit of a value, so an allocation vector would print
+/// as `[0.03333333333333333, ...]` next to sentiment scores printed as `0.93`. Every value is
+/// rounded to `precision` decimal places instead, in the same bracketed list.
+///
+/// # Arguments
+///
+/// * `v` - The values to format, e.g. daily allocations.
+/// * `precision` - The number of decimal places (see
+///   [`display_precision`](crate::config::display_precision)).
+///
+/// # Returns
+///
+/// The values, separated by `, ` and enclosed in square brackets.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::reports::fmt_vec;
+///
+/// assert_eq!(fmt_vec(&[1.0 / 30.0, 0.5, 2.0 / 3.0], 4), "[0.0333, 0.5000, 0.6667]");
+/// assert_eq!(fmt_vec(&[], 4), "[]");
+/// ```
+pub fn fmt_vec(v: &[f64], precision: usize) -> String {
+    let values: Vec<String> = v.iter().map(|value| format!("{:.*}", precision, value)).collect();
+    format!("[{}]", values.join(", "))
+}
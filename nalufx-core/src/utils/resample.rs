@@ -0,0 +1,81 @@
+use chrono::{DateTime, Datelike, Utc};
+
+/// Represents the frequency a price series is resampled to.
+///
+/// # Variants
+///
+/// * `Weekly` - One close per ISO week, running from Monday to Sunday.
+/// * `Monthly` - One close per calendar month.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Frequency {
+    /// One close per ISO week, running from Monday to Sunday.
+    Weekly,
+    /// One close per calendar month.
+    Monthly,
+}
+
+impl Frequency {
+    /// Returns a key identifying the period that contains a date.
+    fn period(self, date: &DateTime<Utc>) -> (i32, u32) {
+        match self {
+            Frequency::Weekly => {
+                let week = date.iso_week();
+                (week.year(), week.week())
+            },
+            Frequency::Monthly => (date.year(), date.month()),
+        }
+    }
+}
+
+/// Resamples a dated price series to a lower frequency, keeping the last close of each period.
+///
+/// Each point of the result is the last `(date, close)` of a week or month in `data`, so it is
+/// dated on the last trading day of the period rather than on a calendar boundary. The last
+/// period is included even if it is partial, e.g. a month that is still in progress, and its
+/// point is the latest close available. Callers that want complete periods only can drop the
+/// last point when its date is before the end of its period.
+///
+/// The returns between the resampled closes, calculated with
+/// [`calculate_daily_returns`](crate::services::processing_svc::calculate_daily_returns), are
+/// then weekly or monthly returns, which are less noisy than daily returns.
+///
+/// # Arguments
+///
+/// * `data` - A slice of `(date, close)` pairs in chronological order
+///   (`&[(DateTime<Utc>, f64)]`).
+/// * `freq` - The `Frequency` to resample to.
+///
+/// # Returns
+///
+/// A vector with the last `(date, close)` pair of each period (`Vec<(DateTime<Utc>, f64)>`),
+/// empty if `data` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use nalufx::utils::resample::{resample, Frequency};
+///
+/// let data = vec![
+///     (Utc.with_ymd_and_hms(2024, 1, 30, 0, 0, 0).unwrap(), 100.0),
+///     (Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap(), 101.0),
+///     (Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap(), 102.0),
+/// ];
+/// let monthly = resample(&data, Frequency::Monthly);
+/// assert_eq!(monthly, vec![data[1], data[2]]);
+/// ```
+pub fn resample(data: &[(DateTime<Utc>, f64)], freq: Frequency) -> Vec<(DateTime<Utc>, f64)> {
+    let mut resampled: Vec<(DateTime<Utc>, f64)> = Vec::new();
+    let mut current_period = None;
+    for point in data {
+        let period = freq.period(&point.0);
+        match resampled.last_mut() {
+            Some(last) if current_period == Some(period) => *last = *point,
+            _ => {
+                resampled.push(*point);
+                current_period = Some(period);
+            },
+        }
+    }
+    resampled
+}
@@ -0,0 +1,464 @@
+use crate::errors::NaluFxError;
+use crate::utils::stats::{mean, std_dev};
+use ndarray::{Array1, Array2};
+
+/// The number of trading days in a year, used to annualize daily statistics.
+pub const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+/// Checks that a return series and its benchmark are non-empty and have the same length.
+fn check_returns(returns: &[f64], benchmark_returns: &[f64]) -> Result<(), NaluFxError> {
+    if returns.is_empty() || benchmark_returns.is_empty() {
+        return Err(NaluFxError::EmptyInput);
+    }
+    if returns.len() != benchmark_returns.len() {
+        return Err(NaluFxError::InputMismatch);
+    }
+    Ok(())
+}
+
+/// Calculates the active returns, the daily returns in excess of the benchmark.
+///
+/// # Arguments
+///
+/// * `returns` - A slice of daily returns (`&[f64]`).
+/// * `benchmark_returns` - A slice of daily benchmark returns (`&[f64]`) for the same days.
+///
+/// # Returns
+///
+/// A `Result` containing the active returns, or a `NaluFxError` if either slice is empty or
+/// the slices have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::risk::calculate_active_returns;
+///
+/// let active = calculate_active_returns(&[0.02, 0.01], &[0.01, 0.01]).unwrap();
+/// assert_eq!(active, vec![0.01, 0.0]);
+/// ```
+pub fn calculate_active_returns(
+    returns: &[f64],
+    benchmark_returns: &[f64],
+) -> Result<Vec<f64>, NaluFxError> {
+    check_returns(returns, benchmark_returns)?;
+    Ok(returns.iter().zip(benchmark_returns).map(|(r, b)| r - b).collect())
+}
+
+/// Calculates the beta of a return series relative to its benchmark.
+///
+/// Beta is the sample covariance of the returns with the benchmark returns divided by the
+/// sample variance of the benchmark returns. It is a ratio, so it does not depend on the
+/// period of the returns.
+///
+/// # Arguments
+///
+/// * `returns` - A slice of daily returns (`&[f64]`).
+/// * `benchmark_returns` - A slice of daily benchmark returns (`&[f64]`) for the same days.
+///
+/// # Returns
+///
+/// A `Result` containing the beta, or a `NaluFxError` if:
+/// - Either slice is empty (`EmptyInput`).
+/// - The slices have different lengths (`InputMismatch`).
+/// - There are fewer than two returns, or the benchmark returns are constant (`InvalidData`).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::risk::calculate_beta;
+///
+/// let benchmark = vec![0.01, -0.02, 0.03, 0.0];
+/// let returns: Vec<f64> = benchmark.iter().map(|r| 2.0 * r).collect();
+/// assert!((calculate_beta(&returns, &benchmark).unwrap() - 2.0).abs() < 1e-12);
+/// ```
+pub fn calculate_beta(returns: &[f64], benchmark_returns: &[f64]) -> Result<f64, NaluFxError> {
+    check_returns(returns, benchmark_returns)?;
+    if returns.len() < 2 {
+        return Err(NaluFxError::InvalidData);
+    }
+
+    let mean_returns = mean(returns).ok_or(NaluFxError::InvalidData)?;
+    let mean_benchmark = mean(benchmark_returns).ok_or(NaluFxError::InvalidData)?;
+    let covariance: f64 = returns
+        .iter()
+        .zip(benchmark_returns)
+        .map(|(r, b)| (r - mean_returns) * (b - mean_benchmark))
+        .sum::<f64>()
+        / (returns.len() - 1) as f64;
+    let variance = std_dev(benchmark_returns, 1).ok_or(NaluFxError::InvalidData)?.powi(2);
+
+    if variance == 0.0 {
+        return Err(NaluFxError::InvalidData);
+    }
+    Ok(covariance / variance)
+}
+
+/// Calculates the annualized Treynor ratio of a return series.
+///
+/// The Treynor ratio is the annualized return in excess of the risk-free rate per unit of
+/// beta against the benchmark. The mean daily return is annualized by multiplying it by
+/// `TRADING_DAYS_PER_YEAR`.
+///
+/// # Arguments
+///
+/// * `returns` - A slice of daily returns (`&[f64]`).
+/// * `benchmark_returns` - A slice of daily benchmark returns (`&[f64]`) for the same days.
+/// * `risk_free_rate` - The annual risk-free rate (`f64`), e.g. `0.02` for 2%.
+///
+/// # Returns
+///
+/// A `Result` containing the Treynor ratio, or a `NaluFxError` if the beta cannot be
+/// calculated (see `calculate_beta`) or is zero (`InvalidData`).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::risk::calculate_treynor_ratio;
+///
+/// let benchmark = vec![0.01, -0.02, 0.03, 0.0];
+/// let ratio = calculate_treynor_ratio(&benchmark, &benchmark, 0.0).unwrap();
+/// // With a beta of 1, the ratio is the annualized mean return: 0.005 * 252.
+/// assert!((ratio - 1.26).abs() < 1e-12);
+/// ```
+pub fn calculate_treynor_ratio(
+    returns: &[f64],
+    benchmark_returns: &[f64],
+    risk_free_rate: f64,
+) -> Result<f64, NaluFxError> {
+    let beta = calculate_beta(returns, benchmark_returns)?;
+    if beta == 0.0 {
+        return Err(NaluFxError::InvalidData);
+    }
+
+    let annualized_return = mean(returns).ok_or(NaluFxError::InvalidData)? * TRADING_DAYS_PER_YEAR;
+    Ok((annualized_return - risk_free_rate) / beta)
+}
+
+/// Calculates the annualized tracking error of a return series against its benchmark.
+///
+/// The tracking error is the sample standard deviation of the active returns, annualized by
+/// multiplying it by the square root of `TRADING_DAYS_PER_YEAR`.
+///
+/// # Arguments
+///
+/// * `returns` - A slice of daily returns (`&[f64]`).
+/// * `benchmark_returns` - A slice of daily benchmark returns (`&[f64]`) for the same days.
+///
+/// # Returns
+///
+/// A `Result` containing the tracking error, or a `NaluFxError` if either slice is empty, the
+/// slices have different lengths, or there are fewer than two returns.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::risk::calculate_tracking_error;
+///
+/// let benchmark = vec![0.01, -0.02, 0.03, 0.0];
+/// assert_eq!(calculate_tracking_error(&benchmark, &benchmark).unwrap(), 0.0);
+/// ```
+pub fn calculate_tracking_error(
+    returns: &[f64],
+    benchmark_returns: &[f64],
+) -> Result<f64, NaluFxError> {
+    let active_returns = calculate_active_returns(returns, benchmark_returns)?;
+    let daily_tracking_error = std_dev(&active_returns, 1).ok_or(NaluFxError::InvalidData)?;
+    Ok(daily_tracking_error * TRADING_DAYS_PER_YEAR.sqrt())
+}
+
+/// Calculates the annualized information ratio of a return series against its benchmark.
+///
+/// The information ratio is the annualized mean active return divided by the annualized
+/// tracking error (see `calculate_tracking_error`). The mean daily active return is
+/// annualized by multiplying it by `TRADING_DAYS_PER_YEAR`.
+///
+/// If the tracking error is zero and the mean active return is zero, as when the returns
+/// equal the benchmark, the information ratio is `0.0` rather than `NaN`.
+///
+/// # Arguments
+///
+/// * `returns` - A slice of daily returns (`&[f64]`).
+/// * `benchmark_returns` - A slice of daily benchmark returns (`&[f64]`) for the same days.
+///
+/// # Returns
+///
+/// A `Result` containing the information ratio, or a `NaluFxError` if:
+/// - Either slice is empty (`EmptyInput`).
+/// - The slices have different lengths (`InputMismatch`).
+/// - There are fewer than two returns, or the active return is a non-zero constant, so the
+///   ratio is unbounded (`InvalidData`).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::risk::calculate_information_ratio;
+///
+/// let benchmark = vec![0.01, -0.02, 0.03, 0.0];
+/// assert_eq!(calculate_information_ratio(&benchmark, &benchmark).unwrap(), 0.0);
+/// ```
+pub fn calculate_information_ratio(
+    returns: &[f64],
+    benchmark_returns: &[f64],
+) -> Result<f64, NaluFxError> {
+    let active_returns = calculate_active_returns(returns, benchmark_returns)?;
+    let annualized_active_return =
+        mean(&active_returns).ok_or(NaluFxError::InvalidData)? * TRADING_DAYS_PER_YEAR;
+    let tracking_error = calculate_tracking_error(returns, benchmark_returns)?;
+
+    if tracking_error == 0.0 {
+        return if annualized_active_return == 0.0 {
+            Ok(0.0)
+        } else {
+            Err(NaluFxError::InvalidData)
+        };
+    }
+    Ok(annualized_active_return / tracking_error)
+}
+
+/// Calculates the diversification ratio of a portfolio.
+///
+/// The diversification ratio is the weighted average of the asset volatilities divided by the
+/// portfolio volatility. It is `1.0` when the assets are perfectly correlated, and grows as
+/// correlations fall; for `N` uncorrelated assets with equal volatility and equal weights it
+/// is `sqrt(N)`. The ratio does not depend on the period of the covariance matrix.
+///
+/// # Arguments
+///
+/// * `weights` - A slice of portfolio weights (`&[f64]`), one per asset.
+/// * `cov` - The covariance matrix of the asset returns (`&Array2<f64>`), with the assets in
+///   the same order as `weights`.
+///
+/// # Returns
+///
+/// A `Result` containing the diversification ratio, or a `NaluFxError` if:
+/// - `weights` is empty (`EmptyInput`).
+/// - `cov` is not a square matrix matching the number of weights (`InputMismatch`).
+/// - The covariance matrix has a negative variance, or the portfolio volatility is not
+///   positive (`InvalidData`).
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::risk::diversification_ratio;
+/// use ndarray::array;
+///
+/// let cov = array![[0.04, 0.0], [0.0, 0.04]];
+/// let ratio = diversification_ratio(&[0.5, 0.5], &cov).unwrap();
+/// assert!((ratio - 2.0_f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn diversification_ratio(weights: &[f64], cov: &Array2<f64>) -> Result<f64, NaluFxError> {
+    if weights.is_empty() {
+        return Err(NaluFxError::EmptyInput);
+    }
+    if cov.dim() != (weights.len(), weights.len()) {
+        return Err(NaluFxError::InputMismatch);
+    }
+    if cov.diag().iter().any(|&variance| variance.is_nan() || variance < 0.0) {
+        return Err(NaluFxError::InvalidData);
+    }
+
+    let weights = Array1::from(weights.to_vec());
+    let weighted_volatility = weights.dot(&cov.diag().mapv(f64::sqrt));
+    let portfolio_volatility = weights.dot(&cov.dot(&weights)).sqrt();
+
+    if portfolio_volatility.is_nan() || portfolio_volatility <= 0.0 {
+        return Err(NaluFxError::InvalidData);
+    }
+    Ok(weighted_volatility / portfolio_volatility)
+}
+
+/// Calculates the maximum drawdown of a price series.
+///
+/// The maximum drawdown is the largest decline from a running peak to a later close, as a
+/// positive fraction of the peak. It is `0.0` for a series that never falls below a previous
+/// peak, including an empty series.
+///
+/// # Arguments
+///
+/// * `closes` - A slice of closing prices (`&[f64]`), in chronological order.
+///
+/// # Returns
+///
+/// The maximum drawdown, between `0.0` and `1.0` for positive prices.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::risk::calculate_max_drawdown;
+///
+/// let drawdown = calculate_max_drawdown(&[100.0, 120.0, 90.0, 130.0, 117.0]);
+/// assert!((drawdown - 0.25).abs() < 1e-12);
+/// ```
+pub fn calculate_max_drawdown(closes: &[f64]) -> f64 {
+    let mut peak = f64::NEG_INFINITY;
+    let mut max_drawdown = 0.0_f64;
+    for &close in closes {
+        peak = peak.max(close);
+        if peak > 0.0 {
+            max_drawdown = max_drawdown.max((peak - close) / peak);
+        }
+    }
+    max_drawdown
+}
+
+/// Calculates the Calmar ratio of a return series.
+///
+/// The Calmar ratio is the annualized return divided by the maximum drawdown of the closing
+/// prices (see `calculate_max_drawdown`). The mean daily return is annualized by multiplying
+/// it by `TRADING_DAYS_PER_YEAR`.
+///
+/// # Arguments
+///
+/// * `returns` - A slice of daily returns (`&[f64]`).
+/// * `closes` - A slice of closing prices (`&[f64]`) over the same period, in chronological
+///   order.
+///
+/// # Returns
+///
+/// The Calmar ratio, or `NaN` if `returns` is empty or contains a `NaN`, or if the prices
+/// have no drawdown, so the ratio is unbounded.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::risk::calculate_calmar_ratio;
+///
+/// let closes = [100.0, 110.0, 99.0, 108.9];
+/// let returns = [0.1, -0.1, 0.1];
+/// // The annualized return is 0.1 / 3 * 252 = 8.4 and the maximum drawdown is 10%.
+/// assert!((calculate_calmar_ratio(&returns, &closes) - 84.0).abs() < 1e-9);
+/// ```
+pub fn calculate_calmar_ratio(returns: &[f64], closes: &[f64]) -> f64 {
+    let max_drawdown = calculate_max_drawdown(closes);
+    match mean(returns) {
+        Some(mean_return) if max_drawdown > 0.0 => {
+            mean_return * TRADING_DAYS_PER_YEAR / max_drawdown
+        },
+        _ => f64::NAN,
+    }
+}
+
+/// Calculates the annualized Sharpe ratio of a series of daily returns.
+///
+/// The ratio is the mean daily return divided by its sample standard deviation, annualized by
+/// multiplying it by the square root of `TRADING_DAYS_PER_YEAR`. The risk-free rate is taken
+/// as zero.
+///
+/// # Arguments
+///
+/// * `returns` - A slice of daily returns (`&[f64]`).
+///
+/// # Returns
+///
+/// The annualized Sharpe ratio, or `NaN` if there are fewer than two returns, a return is
+/// `NaN`, or the returns are constant.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::risk::calculate_sharpe_ratio;
+///
+/// let returns = [0.01, -0.02, 0.03, 0.0, 0.01];
+/// assert!(calculate_sharpe_ratio(&returns) > 0.0);
+/// assert!(calculate_sharpe_ratio(&[0.01]).is_nan());
+/// ```
+pub fn calculate_sharpe_ratio(returns: &[f64]) -> f64 {
+    match (mean(returns), std_dev(returns, 1)) {
+        (Some(mean_return), Some(deviation)) if deviation > 0.0 => {
+            mean_return / deviation * TRADING_DAYS_PER_YEAR.sqrt()
+        },
+        _ => f64::NAN,
+    }
+}
+
+/// Calculates the annualized Sharpe ratio over a rolling window of daily returns.
+///
+/// Each value is the Sharpe ratio of a window (see [`calculate_sharpe_ratio`]). Rolling values
+/// show how the risk-adjusted performance evolved over the period, rather than a single figure
+/// for the whole of it.
+///
+/// # Arguments
+///
+/// * `returns` - A slice of daily returns (`&[f64]`), in chronological order.
+/// * `window` - The number of returns in each window (`usize`).
+///
+/// # Returns
+///
+/// A vector of `returns.len() - window + 1` ratios, the first for the window ending at the
+/// `window`-th return. The vector is empty if `window` is longer than the returns or shorter
+/// than two, since the standard deviation needs two returns. A window whose returns are
+/// constant has a `NaN` ratio.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::risk::rolling_sharpe;
+///
+/// let returns = [0.01, -0.02, 0.03, 0.0, 0.01];
+/// assert_eq!(rolling_sharpe(&returns, 3).len(), 3);
+/// assert!(rolling_sharpe(&returns, 6).is_empty());
+/// ```
+pub fn rolling_sharpe(returns: &[f64], window: usize) -> Vec<f64> {
+    if window < 2 || window > returns.len() {
+        return Vec::new();
+    }
+    returns.windows(window).map(calculate_sharpe_ratio).collect()
+}
+
+/// Calculates the Pearson correlation of two series over a rolling window.
+///
+/// Rolling values show how stable the correlation of two assets is, so that a breakdown of the
+/// relationship of a pair can be spotted, rather than a single figure for the whole period.
+/// Each correlation is clamped to `[-1, 1]` to guard against floating-point error.
+///
+/// # Arguments
+///
+/// * `a` - A slice of values of the first series (`&[f64]`), e.g. daily returns, in
+///   chronological order.
+/// * `b` - A slice of values of the second series (`&[f64]`), in the same order.
+/// * `window` - The number of values in each window (`usize`).
+///
+/// # Returns
+///
+/// A vector of `len - window + 1` correlations, where `len` is the length of the shorter
+/// series, the first for the window ending at the `window`-th value. If the series have
+/// different lengths, the longer one is truncated to its most recent values. The vector is
+/// empty if `window` is longer than the series or shorter than two. A window in which either
+/// series is constant has no defined correlation, and a `NaN` value.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::risk::rolling_correlation;
+///
+/// let a = [0.01, -0.02, 0.03, 0.0, 0.01];
+/// let b = [0.02, -0.04, 0.06, 0.0, 0.02];
+/// let correlations = rolling_correlation(&a, &b, 3);
+/// assert_eq!(correlations.len(), 3);
+/// assert!(correlations.iter().all(|&c| (c - 1.0).abs() < 1e-12));
+/// ```
+pub fn rolling_correlation(a: &[f64], b: &[f64], window: usize) -> Vec<f64> {
+    let len = a.len().min(b.len());
+    if window < 2 || window > len {
+        return Vec::new();
+    }
+    let (a, b) = (&a[a.len() - len..], &b[b.len() - len..]);
+
+    a.windows(window)
+        .zip(b.windows(window))
+        .map(|(a, b)| {
+            let (mean_a, mean_b) = (mean(a).unwrap_or(0.0), mean(b).unwrap_or(0.0));
+            let (mut covariance, mut variance_a, mut variance_b) = (0.0, 0.0, 0.0);
+            for (x, y) in a.iter().zip(b) {
+                covariance += (x - mean_a) * (y - mean_b);
+                variance_a += (x - mean_a).powi(2);
+                variance_b += (y - mean_b).powi(2);
+            }
+            if variance_a > 0.0 && variance_b > 0.0 {
+                (covariance / (variance_a * variance_b).sqrt()).clamp(-1.0, 1.0)
+            } else {
+                f64::NAN
+            }
+        })
+        .collect()
+}
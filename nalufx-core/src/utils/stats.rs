@@ -0,0 +1,356 @@
+use crate::errors::NaluFxError;
+use std::borrow::Cow;
+
+/// Represents how the statistical helpers treat `NaN` values in their input.
+///
+/// # Variants
+///
+/// * `Propagate` - Return `NaN` if the input contains any `NaN`.
+/// * `Skip` - Ignore `NaN` values and compute the statistic over the remaining values.
+/// * `Error` - Return `NaluFxError::InvalidData` if the input contains any `NaN` (the default).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NanPolicy {
+    /// Return `NaN` if the input contains any `NaN`.
+    Propagate,
+    /// Ignore `NaN` values and compute the statistic over the remaining values.
+    Skip,
+    /// Return `NaluFxError::InvalidData` if the input contains any `NaN`.
+    #[default]
+    Error,
+}
+
+/// The outcome of applying a `NanPolicy` to a slice.
+enum Cleaned<'a> {
+    /// The values to compute the statistic over.
+    Values(Cow<'a, [f64]>),
+    /// The input contained a `NaN` and the policy is `Propagate`.
+    Nan,
+}
+
+/// Applies the `NanPolicy` to the input data.
+fn apply_nan_policy(data: &[f64], policy: NanPolicy) -> Result<Cleaned<'_>, NaluFxError> {
+    if data.is_empty() {
+        return Err(NaluFxError::EmptyInput);
+    }
+    if !data.iter().any(|x| x.is_nan()) {
+        return Ok(Cleaned::Values(Cow::Borrowed(data)));
+    }
+
+    match policy {
+        NanPolicy::Propagate => Ok(Cleaned::Nan),
+        NanPolicy::Error => Err(NaluFxError::InvalidData),
+        NanPolicy::Skip => {
+            let values: Vec<f64> = data.iter().copied().filter(|x| !x.is_nan()).collect();
+            if values.is_empty() {
+                return Err(NaluFxError::EmptyInput);
+            }
+            Ok(Cleaned::Values(Cow::Owned(values)))
+        },
+    }
+}
+
+/// Calculates the arithmetic mean of the data.
+///
+/// `NaN` values are rejected, following the default `NanPolicy::Error`. Use
+/// [`mean_with_policy`] to choose a different policy.
+///
+/// # Arguments
+///
+/// * `data` - A slice of values (`&[f64]`).
+///
+/// # Returns
+///
+/// The mean, or `None` if `data` is empty or contains a `NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::stats::mean;
+///
+/// assert_eq!(mean(&[1.0, 2.0, 3.0, 4.0]), Some(2.5));
+/// assert_eq!(mean(&[]), None);
+/// ```
+pub fn mean(data: &[f64]) -> Option<f64> {
+    mean_with_policy(data, NanPolicy::default()).ok()
+}
+
+/// Calculates the standard deviation of the data.
+///
+/// The variance is computed as the sum of squared deviations divided by `n - ddof`, so
+/// `ddof = 0` gives the population standard deviation and `ddof = 1` the sample standard
+/// deviation. `NaN` values are rejected, following the default `NanPolicy::Error`. Use
+/// [`std_dev_with_policy`] to choose a different policy.
+///
+/// # Arguments
+///
+/// * `data` - A slice of values (`&[f64]`).
+/// * `ddof` - The delta degrees of freedom (`usize`).
+///
+/// # Returns
+///
+/// The standard deviation, or `None` if `data` has no more than `ddof` values or contains
+/// a `NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::stats::std_dev;
+///
+/// let data = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+/// assert_eq!(std_dev(&data, 0), Some(2.0));
+/// assert_eq!(std_dev(&[1.0], 1), None);
+/// ```
+pub fn std_dev(data: &[f64], ddof: usize) -> Option<f64> {
+    std_dev_with_policy(data, ddof, NanPolicy::default()).ok()
+}
+
+/// Calculates the `p`-th percentile of the data.
+///
+/// The percentile is computed by linear interpolation between the closest ranks of the
+/// sorted data, so the 0th percentile is the minimum, the 50th the median and the 100th the
+/// maximum. A `p` outside `[0, 100]` is rejected rather than clamped, as it is most likely a
+/// fraction passed by mistake for a percentage. `NaN` values are rejected, following the
+/// default `NanPolicy::Error`. Use [`percentile_with_policy`] to choose a different policy.
+///
+/// # Arguments
+///
+/// * `data` - A slice of values (`&[f64]`), in any order.
+/// * `p` - The percentile to compute, in the range `[0, 100]`.
+///
+/// # Returns
+///
+/// The percentile, or `None` if `data` is empty or contains a `NaN`, or if `p` is outside
+/// `[0, 100]`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::stats::percentile;
+///
+/// let data = vec![15.0, 20.0, 35.0, 40.0, 50.0];
+/// assert_eq!(percentile(&data, 40.0), Some(29.0));
+/// assert_eq!(percentile(&data, 100.0), Some(50.0));
+/// assert_eq!(percentile(&data, 101.0), None);
+/// assert_eq!(percentile(&[], 50.0), None);
+/// ```
+pub fn percentile(data: &[f64], p: f64) -> Option<f64> {
+    percentile_with_policy(data, p, NanPolicy::default()).ok()
+}
+
+/// Calculates the median of the data, the mean of the two middle values if there is an even
+/// number of them.
+///
+/// This is the 50th percentile (see [`percentile`]).
+///
+/// # Arguments
+///
+/// * `data` - A slice of values (`&[f64]`), in any order.
+///
+/// # Returns
+///
+/// The median, or `None` if `data` is empty or contains a `NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::stats::median;
+///
+/// assert_eq!(median(&[3.0, 1.0, 2.0]), Some(2.0));
+/// assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), Some(2.5));
+/// assert_eq!(median(&[]), None);
+/// ```
+pub fn median(data: &[f64]) -> Option<f64> {
+    percentile(data, 50.0)
+}
+
+/// Standardizes the data to z-scores.
+///
+/// Each value is replaced by its distance from the mean in units of the population standard
+/// deviation, so the result has a mean of 0 and a standard deviation of 1. If all values are
+/// equal, the standard deviation is zero and every z-score is `0.0`. If the data contains a
+/// `NaN`, every z-score is `NaN`.
+///
+/// # Arguments
+///
+/// * `data` - A slice of values (`&[f64]`).
+///
+/// # Returns
+///
+/// A vector of z-scores (`Vec<f64>`) with the same length as `data`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::stats::zscore;
+///
+/// assert_eq!(zscore(&[1.0, 3.0]), vec![-1.0, 1.0]);
+/// assert_eq!(zscore(&[5.0, 5.0, 5.0]), vec![0.0, 0.0, 0.0]);
+/// ```
+pub fn zscore(data: &[f64]) -> Vec<f64> {
+    let (Some(mean), Some(std_dev)) = (mean(data), std_dev(data, 0)) else {
+        return vec![f64::NAN; data.len()];
+    };
+
+    if std_dev == 0.0 {
+        vec![0.0; data.len()]
+    } else {
+        data.iter().map(|x| (x - mean) / std_dev).collect()
+    }
+}
+
+/// Rescales the data linearly to the range `[0, 1]`.
+///
+/// The minimum value maps to `0.0` and the maximum value maps to `1.0`. If all values are
+/// equal, including when `data` has a single value, there is no range to scale by and every
+/// value maps to `0.5`. If the data contains a `NaN`, every value is `NaN`.
+///
+/// # Arguments
+///
+/// * `data` - A slice of values (`&[f64]`).
+///
+/// # Returns
+///
+/// A vector of normalized values (`Vec<f64>`) with the same length as `data`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::stats::min_max_normalize;
+///
+/// assert_eq!(min_max_normalize(&[1.0, 2.0, 3.0]), vec![0.0, 0.5, 1.0]);
+/// assert_eq!(min_max_normalize(&[4.0, 4.0]), vec![0.5, 0.5]);
+/// ```
+pub fn min_max_normalize(data: &[f64]) -> Vec<f64> {
+    if data.iter().any(|x| x.is_nan()) {
+        return vec![f64::NAN; data.len()];
+    }
+
+    let min = data.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = data.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    if range == 0.0 {
+        vec![0.5; data.len()]
+    } else {
+        data.iter().map(|x| (x - min) / range).collect()
+    }
+}
+
+/// Calculates the arithmetic mean of the data under the given `NanPolicy`.
+///
+/// # Arguments
+///
+/// * `data` - A slice of values (`&[f64]`).
+/// * `policy` - How `NaN` values in `data` are treated.
+///
+/// # Returns
+///
+/// A `Result` containing the mean, or a `NaluFxError` if `data` is empty (after skipping
+/// `NaN` values) or contains a `NaN` under `NanPolicy::Error`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::stats::{mean_with_policy, NanPolicy};
+///
+/// let data = vec![1.0, f64::NAN, 3.0];
+/// assert!(mean_with_policy(&data, NanPolicy::Error).is_err());
+/// assert!(mean_with_policy(&data, NanPolicy::Propagate).unwrap().is_nan());
+/// assert_eq!(mean_with_policy(&data, NanPolicy::Skip).unwrap(), 2.0);
+/// ```
+pub fn mean_with_policy(data: &[f64], policy: NanPolicy) -> Result<f64, NaluFxError> {
+    match apply_nan_policy(data, policy)? {
+        Cleaned::Nan => Ok(f64::NAN),
+        Cleaned::Values(values) => Ok(values.iter().sum::<f64>() / values.len() as f64),
+    }
+}
+
+/// Calculates the standard deviation of the data under the given `NanPolicy`.
+///
+/// The variance is computed as the sum of squared deviations divided by `n - ddof`, so
+/// `ddof = 0` gives the population standard deviation and `ddof = 1` the sample standard
+/// deviation.
+///
+/// # Arguments
+///
+/// * `data` - A slice of values (`&[f64]`).
+/// * `ddof` - The delta degrees of freedom (`usize`).
+/// * `policy` - How `NaN` values in `data` are treated.
+///
+/// # Returns
+///
+/// A `Result` containing the standard deviation, or a `NaluFxError` if `data` is empty
+/// (after skipping `NaN` values), has no more than `ddof` values, or contains a `NaN` under
+/// `NanPolicy::Error`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::stats::{std_dev_with_policy, NanPolicy};
+///
+/// let data = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+/// assert_eq!(std_dev_with_policy(&data, 0, NanPolicy::Error).unwrap(), 2.0);
+/// ```
+pub fn std_dev_with_policy(
+    data: &[f64],
+    ddof: usize,
+    policy: NanPolicy,
+) -> Result<f64, NaluFxError> {
+    match apply_nan_policy(data, policy)? {
+        Cleaned::Nan => Ok(f64::NAN),
+        Cleaned::Values(values) => {
+            if values.len() <= ddof {
+                return Err(NaluFxError::InvalidData);
+            }
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let sum_sq: f64 = values.iter().map(|x| (x - mean).powi(2)).sum();
+            Ok((sum_sq / (values.len() - ddof) as f64).sqrt())
+        },
+    }
+}
+
+/// Calculates the `p`-th percentile of the data under the given `NanPolicy`.
+///
+/// The percentile is computed by linear interpolation between the closest ranks of the
+/// sorted data, so the 0th percentile is the minimum and the 100th the maximum.
+///
+/// # Arguments
+///
+/// * `data` - A slice of values (`&[f64]`).
+/// * `p` - The percentile to compute, in the range `[0, 100]`.
+/// * `policy` - How `NaN` values in `data` are treated.
+///
+/// # Returns
+///
+/// A `Result` containing the percentile, or a `NaluFxError` if `data` is empty (after
+/// skipping `NaN` values), `p` is outside `[0, 100]`, or `data` contains a `NaN` under
+/// `NanPolicy::Error`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::stats::{percentile_with_policy, NanPolicy};
+///
+/// let data = vec![4.0, 1.0, 3.0, 2.0];
+/// assert_eq!(percentile_with_policy(&data, 50.0, NanPolicy::Error).unwrap(), 2.5);
+/// assert_eq!(percentile_with_policy(&data, 100.0, NanPolicy::Error).unwrap(), 4.0);
+/// ```
+pub fn percentile_with_policy(data: &[f64], p: f64, policy: NanPolicy) -> Result<f64, NaluFxError> {
+    if !(0.0..=100.0).contains(&p) {
+        return Err(NaluFxError::InvalidData);
+    }
+
+    match apply_nan_policy(data, policy)? {
+        Cleaned::Nan => Ok(f64::NAN),
+        Cleaned::Values(values) => {
+            let mut sorted = values.into_owned();
+            sorted.sort_by(f64::total_cmp);
+
+            let rank = p / 100.0 * (sorted.len() - 1) as f64;
+            let lower = rank.floor() as usize;
+            let upper = rank.ceil() as usize;
+            let fraction = rank - lower as f64;
+            Ok(sorted[lower] + (sorted[upper] - sorted[lower]) * fraction)
+        },
+    }
+}
@@ -0,0 +1,127 @@
+/// Rounds a dollar amount down to a whole number of share lots at a given price.
+///
+/// Allocations are calculated as fractions of the investment, which assume that any dollar
+/// amount can be invested. Brokers trade whole shares, and institutional desks often trade in
+/// round lots, so the amount is converted into the largest number of shares, in multiples of
+/// `lot_size`, that it can buy. The rest of the amount is kept as residual cash.
+///
+/// # Arguments
+///
+/// * `dollar_amount` - The amount to invest.
+/// * `price` - The price of a share.
+/// * `lot_size` - The number of shares in a lot (e.g., `1` for single shares, `100` for round
+///   lots).
+///
+/// # Returns
+///
+/// A tuple of the number of shares to buy and the residual cash. The trade is skipped, with no
+/// shares and the whole amount as residual cash, if the price is zero, negative or not finite,
+/// or the lot size is zero. A negative or non-finite amount buys no shares and leaves no
+/// residual cash.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::trading::round_to_lots;
+///
+/// let (shares, residual) = round_to_lots(1000.0, 97.0, 1);
+/// assert_eq!(shares, 10);
+/// assert!((residual - 30.0).abs() < 1e-9);
+///
+/// // A price of zero skips the trade.
+/// assert_eq!(round_to_lots(1000.0, 0.0, 1), (0, 1000.0));
+/// ```
+pub fn round_to_lots(dollar_amount: f64, price: f64, lot_size: u32) -> (u32, f64) {
+    if !(dollar_amount.is_finite() && dollar_amount > 0.0) {
+        return (0, 0.0);
+    }
+    if !(price.is_finite() && price > 0.0) || lot_size == 0 {
+        return (0, dollar_amount);
+    }
+
+    let lot_cost = price * f64::from(lot_size);
+    let lots = (dollar_amount / lot_cost).floor();
+    // Saturate rather than overflow for amounts worth more than `u32::MAX` shares.
+    let shares = (lots * f64::from(lot_size)).min(f64::from(u32::MAX)) as u32;
+    let residual = (dollar_amount - f64::from(shares) * price).max(0.0);
+    (shares, residual)
+}
+
+/// Rounds a dollar amount down to a number of shares with at most `decimals` decimal places.
+///
+/// Mutual funds and brokers that support fractional shares accept orders for part of a share,
+/// down to a minimum increment (e.g., three decimal places for most mutual funds). The rest of
+/// the amount is kept as residual cash.
+///
+/// # Arguments
+///
+/// * `dollar_amount` - The amount to invest.
+/// * `price` - The price of a share.
+/// * `decimals` - The number of decimal places of the share count.
+///
+/// # Returns
+///
+/// A tuple of the number of shares to buy and the residual cash, with the same rules as
+/// [`round_to_lots`] for invalid amounts and prices.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::trading::round_to_fractional_shares;
+///
+/// let (shares, residual) = round_to_fractional_shares(1000.0, 97.0, 2);
+/// assert_eq!(shares, 10.3);
+/// assert!((residual - 0.9).abs() < 1e-9);
+/// ```
+pub fn round_to_fractional_shares(dollar_amount: f64, price: f64, decimals: u32) -> (f64, f64) {
+    if !(dollar_amount.is_finite() && dollar_amount > 0.0) {
+        return (0.0, 0.0);
+    }
+    if !(price.is_finite() && price > 0.0) {
+        return (0.0, dollar_amount);
+    }
+
+    let scale = 10f64.powi(i32::try_from(decimals).unwrap_or(i32::MAX));
+    let shares = (dollar_amount / price * scale).floor() / scale;
+    let residual = (dollar_amount - shares * price).max(0.0);
+    (shares, residual)
+}
+
+/// Represents how an amount is converted into shares.
+///
+/// # Variants
+///
+/// * `Lots(u32)` - Whole lots of the given number of shares (see [`round_to_lots`]).
+/// * `Fractional(u32)` - Fractional shares with the given number of decimal places (see
+///   [`round_to_fractional_shares`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShareRounding {
+    /// Whole lots of the given number of shares.
+    Lots(u32),
+    /// Fractional shares with the given number of decimal places.
+    Fractional(u32),
+}
+
+impl ShareRounding {
+    /// Rounds a dollar amount down to the shares it buys at a given price.
+    ///
+    /// # Arguments
+    ///
+    /// * `dollar_amount` - The amount to invest.
+    /// * `price` - The price of a share.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the number of shares to buy and the residual cash.
+    pub fn round(self, dollar_amount: f64, price: f64) -> (f64, f64) {
+        match self {
+            Self::Lots(lot_size) => {
+                let (shares, residual) = round_to_lots(dollar_amount, price, lot_size);
+                (f64::from(shares), residual)
+            },
+            Self::Fractional(decimals) => {
+                round_to_fractional_shares(dollar_amount, price, decimals)
+            },
+        }
+    }
+}
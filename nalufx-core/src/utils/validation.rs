@@ -1,10 +1,169 @@
+use crate::errors::NaluFxError;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use log::error;
 
+/// Validates if the input string is a non-empty alphanumeric ticker symbol.
+///
+/// This function checks if the input string is non-empty and consists only of alphanumeric characters.
+/// If the input string meets these criteria, it returns the input string wrapped in `Ok`.
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the ticker symbol to validate.
+///
+/// # Returns
+///
+/// This function returns a `Result`:
+/// * `Ok(&str)` - If the input string is a valid ticker symbol.
+/// * `Err(NaluFxError::InvalidTicker)` - If the input string is not a valid ticker symbol, with
+///   the reason given by `get_ticker_validation_error_message`.
+///
+/// # Errors
+///
+/// The function will return an error if:
+/// * The input string is empty.
+/// * The input string contains non-alphanumeric characters.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::validation::validate_ticker;
+///
+/// let valid_ticker = "AAPL";
+/// assert!(validate_ticker(valid_ticker).is_ok());
+///
+/// let invalid_ticker_empty = "";
+/// assert!(validate_ticker(invalid_ticker_empty).is_err());
+///
+/// let invalid_ticker_non_alphanumeric = "AAPL$";
+/// assert!(validate_ticker(invalid_ticker_non_alphanumeric).is_err());
+/// ```
+pub fn validate_ticker(input: &str) -> Result<&str, NaluFxError> {
+    if !input.is_empty() && input.chars().all(|c| c.is_alphanumeric()) {
+        Ok(input)
+    } else {
+        let reason = get_ticker_validation_error_message(input);
+        error!("Validation failed: {} Found: {:?}", reason, input);
+        Err(NaluFxError::InvalidTicker(format!("{}: {}", input, reason)))
+    }
+}
+
+/// Provides a detailed error message for ticker validation failures.
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the ticker symbol that failed validation.
+///
+/// # Returns
+///
+/// A string describing the reason for validation failure.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::validation::get_ticker_validation_error_message;
+///
+/// let invalid_ticker_empty = "";
+/// let error_message = get_ticker_validation_error_message(invalid_ticker_empty);
+/// assert_eq!(error_message, "The ticker symbol cannot be empty.");
+///
+/// let invalid_ticker_non_alphanumeric = "AAPL$";
+/// let error_message = get_ticker_validation_error_message(invalid_ticker_non_alphanumeric);
+/// assert_eq!(error_message, "The ticker symbol must be alphanumeric.");
+/// ```
+pub fn get_ticker_validation_error_message(input: &str) -> &'static str {
+    if input.is_empty() {
+        "The ticker symbol cannot be empty."
+    } else if !input.chars().all(|c| c.is_alphanumeric()) {
+        "The ticker symbol must be alphanumeric."
+    } else {
+        "Unknown validation error."
+    }
+}
+
+/// Validates if the input string is a valid date in the format YYYY-MM-DD.
+///
+/// This function attempts to parse the input string into a `NaiveDate` and then converts it into
+/// a `DateTime<Utc>` at midnight.
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the date to validate.
+///
+/// # Returns
+///
+/// This function returns a `Result`:
+/// * `Ok(DateTime<Utc>)` - If the input string is a valid date.
+/// * `Err(NaluFxError::InvalidInput)` - If the input string is not a valid date.
+///
+/// # Errors
+///
+/// The function will return an error if:
+/// * The input string cannot be parsed into a date.
+/// * The date cannot be converted to a `DateTime<Utc>`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::validation::validate_date;
+///
+/// let valid_date = "2024-01-01";
+/// assert!(validate_date(valid_date).is_ok());
+///
+/// let invalid_date = "2024-13-01";
+/// assert!(validate_date(invalid_date).is_err());
+/// ```
+pub fn validate_date(input: &str) -> Result<DateTime<Utc>, NaluFxError> {
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .and_then(|naive_datetime| Utc.from_local_datetime(&naive_datetime).single())
+        .ok_or_else(|| {
+            error!("Validation failed: The input is not a valid date. Found: {:?}", input);
+            NaluFxError::InvalidInput(
+                "Please enter a valid date in the format YYYY-MM-DD.".to_string(),
+            )
+        })
+}
+
+/// Validates that an amount, such as an initial investment, is positive and finite.
+///
+/// Services call this function at their boundary, so that a zero, negative or `NaN` amount is
+/// rejected instead of silently producing meaningless allocations.
+///
+/// # Arguments
+///
+/// * `value` - The amount to validate.
+///
+/// # Returns
+///
+/// This function returns a `Result`:
+/// * `Ok(f64)` - The amount, if it is positive and finite.
+/// * `Err(NaluFxError::InvalidData)` - If the amount is zero, negative, infinite or `NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx::utils::validation::validate_positive_amount;
+///
+/// assert_eq!(validate_positive_amount(1000.0).unwrap(), 1000.0);
+/// assert!(validate_positive_amount(0.0).is_err());
+/// assert!(validate_positive_amount(f64::NAN).is_err());
+/// ```
+pub fn validate_positive_amount(value: f64) -> Result<f64, NaluFxError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        error!("Validation failed: The amount is not a positive number. Found: {}", value);
+        Err(NaluFxError::InvalidData)
+    }
+}
+
 /// Validates if the input string can be parsed into a positive float.
 ///
-/// This function checks if the input string can be parsed into a float and if the parsed value is positive.
+/// This function checks if the input string can be parsed into a float and if the parsed value is
+/// positive and finite (see `validate_positive_amount`).
 /// If the input meets these criteria, it returns the parsed float wrapped in `Ok`.
-/// Otherwise, it returns an error message.
 ///
 /// # Arguments
 ///
@@ -14,13 +173,14 @@
 ///
 /// This function returns a `Result`:
 /// * `Ok(f64)` - If the input string is a valid positive float.
-/// * `Err(&'static str)` - If the input string is not a valid positive float.
+/// * `Err(NaluFxError::InvalidInput)` - If the input string is not a valid positive float, with
+///   the reason given by `get_float_validation_error_message`.
 ///
 /// # Errors
 ///
 /// The function will return an error if:
 /// * The input string cannot be parsed into a float.
-/// * The parsed float is not positive.
+/// * The parsed float is not positive, or is infinite or `NaN`.
 ///
 /// # Examples
 ///
@@ -36,16 +196,14 @@
 /// let invalid_float_negative = "-123.45";
 /// assert!(validate_positive_float(invalid_float_negative).is_err());
 /// ```
-pub fn validate_positive_float(input: &str) -> Result<f64, &str> {
+pub fn validate_positive_float(input: &str) -> Result<f64, NaluFxError> {
     match input.parse::<f64>() {
-        Ok(value) if value > 0.0 => Ok(value),
-        Ok(_) => {
-            error!("Validation failed: The number is not positive. Found: {}", input);
-            Err("The input is not a valid float.")
-        },
+        Ok(value) => validate_positive_amount(value).map_err(|_| {
+            NaluFxError::InvalidInput(get_float_validation_error_message(input).to_string())
+        }),
         Err(_) => {
             error!("Validation failed: The input is not a valid float. Found: {}", input);
-            Err("The number is not positive.")
+            Err(NaluFxError::InvalidInput(get_float_validation_error_message(input).to_string()))
         },
     }
 }
@@ -75,6 +233,7 @@ pub fn validate_positive_float(input: &str) -> Result<f64, &str> {
 /// ```
 pub fn get_float_validation_error_message(input: &str) -> &'static str {
     match input.parse::<f64>() {
+        Ok(value) if !value.is_finite() => "The number is not finite.",
         Ok(value) if value > 0.0 => "No validation error.",
         Ok(_) => "The number is not positive.",
         Err(_) => "The input is not a valid float.",
@@ -0,0 +1,88 @@
+use reqwest::StatusCode;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Represents an error returned by an LLM provider.
+///
+/// The variants separate the failures a caller may want to handle differently: a rate limit
+/// is transient and worth retrying later, while an authentication failure will not go away
+/// until the API key is fixed.
+///
+/// # Variants
+///
+/// * `RateLimited { retry_after }` - The provider responded with `429 Too Many Requests`, with
+///   the delay requested by its `Retry-After` header, if any.
+/// * `AuthFailed` - The provider responded with `401 Unauthorized` or `403 Forbidden`.
+/// * `Config(String)` - The request could not be made, e.g. because no API key is configured.
+/// * `Other(String)` - Any other failure, such as a `5xx` status, a network error or a response
+///   that cannot be read.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx_llms::errors::LlmError;
+/// use reqwest::StatusCode;
+/// use std::time::Duration;
+///
+/// let error = LlmError::from_status(StatusCode::TOO_MANY_REQUESTS, Some(Duration::from_secs(2)));
+/// assert_eq!(error, LlmError::RateLimited { retry_after: Some(Duration::from_secs(2)) });
+/// assert!(error.is_retryable());
+/// assert!(!LlmError::from_status(StatusCode::UNAUTHORIZED, None).is_retryable());
+/// ```
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum LlmError {
+    /// The provider rate limited the request.
+    #[error("LLM request was rate limited (retry after {retry_after:?})")]
+    RateLimited {
+        /// The delay requested by the `Retry-After` header, if any.
+        retry_after: Option<Duration>,
+    },
+
+    /// The provider rejected the API key.
+    #[error("LLM authentication failed, check the API key")]
+    AuthFailed,
+
+    /// The request could not be made with the current configuration.
+    #[error("LLM configuration error: {0}")]
+    Config(String),
+
+    /// Any other failure of the request.
+    #[error("LLM request failed: {0}")]
+    Other(String),
+}
+
+impl LlmError {
+    /// Creates the error matching an unsuccessful HTTP status.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - The status of the response.
+    /// * `retry_after` - The delay requested by the `Retry-After` header, if any.
+    pub fn from_status(status: StatusCode, retry_after: Option<Duration>) -> Self {
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => LlmError::RateLimited { retry_after },
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => LlmError::AuthFailed,
+            status => LlmError::Other(format!("API call failed with status {}", status)),
+        }
+    }
+
+    /// Returns `true` if the request may succeed if it is sent again later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, LlmError::RateLimited { .. })
+    }
+}
+
+impl From<reqwest::Error> for LlmError {
+    fn from(err: reqwest::Error) -> Self {
+        match err.status() {
+            Some(status) if !status.is_success() => LlmError::from_status(status, None),
+            _ => LlmError::Other(err.to_string()),
+        }
+    }
+}
+
+impl From<&str> for LlmError {
+    fn from(message: &str) -> Self {
+        LlmError::Config(message.to_string())
+    }
+}
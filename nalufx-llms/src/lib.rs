@@ -12,6 +12,10 @@
 extern crate reqwest;
 extern crate serde;
 extern crate serde_json;
+extern crate thiserror;
+
+/// This module contains the errors returned by the LLM providers.
+pub mod errors;
 
 /// This module contains the logic for interacting with the OpenAI API.
 pub mod llms;
@@ -0,0 +1,219 @@
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The default directory used to store cached LLM responses.
+pub const DEFAULT_LLM_CACHE_DIR: &str = ".cache/nalufx/llm";
+
+/// The default time-to-live of a cached LLM response (24 hours).
+pub const DEFAULT_LLM_CACHE_TTL_SECS: u64 = 86_400;
+
+/// Used to give every temporary cache file written by this process a unique name.
+static TMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Represents the configuration of the LLM response cache.
+///
+/// # Fields
+///
+/// * `cache_dir` - The directory in which cached responses are stored.
+/// * `ttl` - How long a cached response remains valid.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx_llms::llms::cache::LlmCacheConfig;
+/// use std::time::Duration;
+///
+/// let config = LlmCacheConfig::new("/tmp/nalufx-llm-cache", Duration::from_secs(3600));
+/// assert_eq!(config.ttl, Duration::from_secs(3600));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LlmCacheConfig {
+    /// The directory in which cached responses are stored.
+    pub cache_dir: PathBuf,
+    /// How long a cached response remains valid.
+    pub ttl: Duration,
+}
+
+impl Default for LlmCacheConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_LLM_CACHE_DIR, Duration::from_secs(DEFAULT_LLM_CACHE_TTL_SECS))
+    }
+}
+
+impl LlmCacheConfig {
+    /// Creates a new `LlmCacheConfig` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_dir` - The directory in which cached responses are stored.
+    /// * `ttl` - How long a cached response remains valid.
+    pub fn new<P: Into<PathBuf>>(cache_dir: P, ttl: Duration) -> Self {
+        Self { cache_dir: cache_dir.into(), ttl }
+    }
+
+    /// Creates a new `LlmCacheConfig` instance from environment variables.
+    ///
+    /// `LLM_CACHE_DIR` sets the cache directory and `LLM_CACHE_TTL_SECS` sets the time-to-live
+    /// in seconds. Unset or invalid values fall back to the defaults.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let cache_dir = env::var("LLM_CACHE_DIR").map(PathBuf::from).unwrap_or(default.cache_dir);
+        let ttl = env::var("LLM_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(default.ttl);
+        Self { cache_dir, ttl }
+    }
+}
+
+/// Identifies a cacheable LLM request.
+///
+/// # Fields
+///
+/// * `provider` - The name of the LLM provider (e.g., "openai").
+/// * `model` - The model used to generate the response.
+/// * `prompt` - The prompt sent to the LLM.
+/// * `max_tokens` - The maximum number of tokens allowed in the response.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CacheKey {
+    /// The name of the LLM provider.
+    pub provider: String,
+    /// The model used to generate the response.
+    pub model: String,
+    /// The prompt sent to the LLM.
+    pub prompt: String,
+    /// The maximum number of tokens allowed in the response.
+    pub max_tokens: usize,
+}
+
+impl CacheKey {
+    /// Creates a new `CacheKey` instance.
+    pub fn new(provider: &str, model: &str, prompt: &str, max_tokens: usize) -> Self {
+        Self {
+            provider: provider.to_string(),
+            model: model.to_string(),
+            prompt: prompt.to_string(),
+            max_tokens,
+        }
+    }
+
+    /// Returns a stable content hash of the key, suitable for use as a file name.
+    ///
+    /// The hash is a 64-bit FNV-1a digest over the length-prefixed key fields, so it does not
+    /// change between runs, platforms, or compiler versions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nalufx_llms::llms::cache::CacheKey;
+    ///
+    /// let a = CacheKey::new("openai", "gpt-3.5-turbo", "Hello", 100);
+    /// let b = CacheKey::new("openai", "gpt-3.5-turbo", "Hello", 100);
+    /// let c = CacheKey::new("openai", "gpt-3.5-turbo", "Hello", 200);
+    /// assert_eq!(a.hash_hex(), b.hash_hex());
+    /// assert_ne!(a.hash_hex(), c.hash_hex());
+    /// ```
+    pub fn hash_hex(&self) -> String {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let max_tokens = self.max_tokens.to_string();
+        let fields = [
+            self.provider.as_bytes(),
+            self.model.as_bytes(),
+            self.prompt.as_bytes(),
+            max_tokens.as_bytes(),
+        ];
+
+        let mut hash = FNV_OFFSET_BASIS;
+        for field in fields {
+            // Prefix each field with its length so that field boundaries are unambiguous
+            for byte in (field.len() as u64).to_le_bytes().iter().chain(field) {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+        format!("{:016x}", hash)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    key: CacheKey,
+    created_at: u64,
+    response: Value,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+fn cache_path(config: &LlmCacheConfig, key: &CacheKey) -> PathBuf {
+    config.cache_dir.join(format!("{}.json", key.hash_hex()))
+}
+
+/// Reads a cached response for the given key.
+///
+/// Returns `None` if there is no entry, if the entry has expired, or if the entry cannot be
+/// read. Entries whose stored key differs from `key` (a hash collision) are ignored.
+pub fn read_cached_response(config: &LlmCacheConfig, key: &CacheKey) -> Option<Value> {
+    let path = cache_path(config, key);
+    let contents = fs::read_to_string(&path).ok()?;
+    let entry: CacheEntry = match serde_json::from_str(&contents) {
+        Ok(entry) => entry,
+        Err(err) => {
+            warn!("Ignoring unreadable LLM cache entry {:?}: {:?}", path, err);
+            return None;
+        },
+    };
+
+    if entry.key != *key {
+        debug!("LLM cache hash collision at {:?}", path);
+        return None;
+    }
+    if now_secs().saturating_sub(entry.created_at) > config.ttl.as_secs() {
+        debug!("LLM cache entry {:?} has expired", path);
+        return None;
+    }
+    Some(entry.response)
+}
+
+/// Writes a response to the cache for the given key.
+///
+/// The entry is first written to a uniquely named temporary file in the cache directory
+/// and then atomically renamed into place, so concurrent writers of the same key never
+/// leave a partially written cache file behind.
+///
+/// # Errors
+///
+/// Returns an `std::io::Error` if the cache directory or file cannot be written.
+pub fn write_cached_response(
+    config: &LlmCacheConfig,
+    key: &CacheKey,
+    response: &Value,
+) -> std::io::Result<()> {
+    fs::create_dir_all(&config.cache_dir)?;
+
+    let entry = CacheEntry { key: key.clone(), created_at: now_secs(), response: response.clone() };
+    let contents = serde_json::to_vec(&entry)?;
+
+    let path = cache_path(config, key);
+    let tmp_path = temp_path(&config.cache_dir, &key.hash_hex());
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, &path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp_path);
+    })
+}
+
+fn temp_path(dir: &Path, hash: &str) -> PathBuf {
+    let counter = TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    dir.join(format!(".{}.{}.{}.tmp", hash, std::process::id(), counter))
+}
@@ -0,0 +1,115 @@
+use super::LLM;
+use crate::errors::LlmError;
+use async_trait::async_trait;
+use log::{error, info, warn};
+use reqwest::Client;
+use serde_json::Value;
+use std::fmt;
+
+/// Represents a chain of LLM providers that fails over from one to the next.
+///
+/// A request is sent to each provider in turn, with the API key of that provider, until one of
+/// them responds, and the provider that served the response is logged. Every error of a
+/// provider is specific to it, so the chain moves on to the next provider whatever the error:
+/// a rate limit or an outage, e.g. a `5xx` status, is logged as a warning, while a rejected or
+/// missing API key (`LlmError::AuthFailed` or `LlmError::Config`) is logged as an error, since
+/// it will not go away until the configuration of that provider is fixed.
+///
+/// Any `LLM` implementation can be chained, such as [`OpenAI`](super::openai::OpenAI) and
+/// [`Ollama`](super::ollama::Ollama). The `claude`, `gemini` and `mistral` modules only provide
+/// request helpers and do not implement `LLM`, so these providers cannot be chained yet.
+///
+/// # Fields
+///
+/// * `providers` - The providers to try, in order, each with its API key.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx_llms::llms::{chain::LlmChain, ollama::Ollama, openai::OpenAI, LLM};
+///
+/// let chain = LlmChain::new(vec![
+///     (Box::new(OpenAI), "sk-openai-key".to_string()),
+///     // Ollama runs locally and needs no API key.
+///     (Box::new(Ollama::default()), String::new()),
+/// ]);
+/// assert_eq!(chain.provider(), "chain");
+/// assert_eq!(chain.model(), "gpt-3.5-turbo");
+/// ```
+pub struct LlmChain {
+    /// The providers to try, in order, each with its API key.
+    pub providers: Vec<(Box<dyn LLM>, String)>,
+}
+
+impl LlmChain {
+    /// Creates a new `LlmChain` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `providers` - The providers to try, in order, each with its API key.
+    pub fn new(providers: Vec<(Box<dyn LLM>, String)>) -> Self {
+        Self { providers }
+    }
+}
+
+impl fmt::Debug for LlmChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let providers: Vec<String> = self
+            .providers
+            .iter()
+            .map(|(provider, _)| format!("{}/{}", provider.provider(), provider.model()))
+            .collect();
+        f.debug_struct("LlmChain").field("providers", &providers).finish()
+    }
+}
+
+#[async_trait]
+impl LLM for LlmChain {
+    /// Sends the request to each provider in turn, and returns the first response.
+    ///
+    /// The `api_key` argument is ignored, since each provider is sent its own API key.
+    ///
+    /// Returns `LlmError::Config` if the chain has no provider, or the error of the last
+    /// provider if every provider failed.
+    async fn send_request(
+        &self,
+        client: &Client,
+        _api_key: &str,
+        prompt: &str,
+        max_tokens: usize,
+    ) -> Result<Value, LlmError> {
+        let mut last_error = LlmError::Config("No LLM provider is configured".to_string());
+        for (provider, api_key) in &self.providers {
+            match provider.send_request(client, api_key, prompt, max_tokens).await {
+                Ok(response) => {
+                    info!("LLM response served by {}/{}", provider.provider(), provider.model());
+                    return Ok(response);
+                },
+                Err(err) => {
+                    let message = format!(
+                        "LLM provider {}/{} failed, trying the next one: {}",
+                        provider.provider(),
+                        provider.model(),
+                        err
+                    );
+                    if matches!(err, LlmError::AuthFailed | LlmError::Config(_)) {
+                        error!("{}", message);
+                    } else {
+                        warn!("{}", message);
+                    }
+                    last_error = err;
+                },
+            }
+        }
+        Err(last_error)
+    }
+
+    fn provider(&self) -> &str {
+        "chain"
+    }
+
+    /// Returns the model of the first provider, or an empty string if there is none.
+    fn model(&self) -> &str {
+        self.providers.first().map_or("", |(provider, _)| provider.model())
+    }
+}
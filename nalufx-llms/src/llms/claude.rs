@@ -1,4 +1,5 @@
-use crate::models::claude_dm::ClaudeResponse;
+use super::{check_status, send_with_retry, RetryPolicy};
+use crate::{errors::LlmError, models::claude_dm::ClaudeResponse};
 use actix_web::HttpResponse;
 use dotenvy::dotenv;
 use log::error;
@@ -46,30 +47,29 @@ pub fn get_claude_api_key() -> Result<String, &'static str> {
 /// # Returns
 ///
 /// * `Ok(String)` - If the request is successfully sent and the response body is returned as a string.
-/// * `Err(&'static str)` - If an error occurs during the request or response handling.
+/// * `Err(LlmError)` - If an error occurs during the request or response handling: `RateLimited`
+///   for a `429` status, `AuthFailed` for a `401` or `403` status, and `Other` otherwise.
 pub async fn send_claude_request(
     client: &Client,
     api_url: &str,
     api_key: &str,
     request_body: Value,
-) -> Result<String, &'static str> {
-    let response = client
+) -> Result<String, LlmError> {
+    let request = client
         .post(api_url)
         .header("Authorization", format!("Bearer {}", api_key))
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|err| {
-            error!("Error sending request to Claude API: {:?}", err);
-            "Error contacting Claude API"
-        })?;
-    if !response.status().is_success() {
-        error!("Claude API call failed with status: {:?}", response.status());
-        return Err("Claude API call failed");
-    }
+        .json(&request_body);
+    let response = send_with_retry(request, &RetryPolicy::from_env()).await.map_err(|err| {
+        error!("Error sending request to Claude API: {:?}", err);
+        LlmError::from(err)
+    })?;
+    let response = check_status(response).map_err(|err| {
+        error!("Claude API call failed: {}", err);
+        err
+    })?;
     response.text().await.map_err(|err| {
         error!("Error reading response body: {:?}", err);
-        "Error reading response body"
+        LlmError::from(err)
     })
 }
 
@@ -0,0 +1,63 @@
+use super::{
+    ollama::Ollama,
+    openai::{get_openai_api_key, OpenAI},
+    LLM,
+};
+use crate::errors::LlmError;
+
+/// The names of the LLM providers that [`llm_from_name`] constructs.
+pub const LLM_NAMES: [&str; 2] = ["openai", "ollama"];
+
+/// Constructs the LLM provider with the given name.
+///
+/// The name is case-insensitive and surrounding whitespace is ignored. Ollama is configured
+/// from the environment (see [`Ollama::from_env`]).
+///
+/// # Arguments
+///
+/// * `name` - The name of the provider, one of `LLM_NAMES`.
+///
+/// # Returns
+///
+/// * `Result<Box<dyn LLM>, LlmError>` - The provider, or `LlmError::Config` if no provider has
+///   this name.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx_llms::llms::factory::llm_from_name;
+///
+/// assert_eq!(llm_from_name("OpenAI").unwrap().provider(), "openai");
+/// assert!(llm_from_name("unknown").is_err());
+/// ```
+pub fn llm_from_name(name: &str) -> Result<Box<dyn LLM>, LlmError> {
+    match name.trim().to_lowercase().as_str() {
+        "openai" => Ok(Box::new(OpenAI)),
+        "ollama" => Ok(Box::new(Ollama::from_env())),
+        _ => Err(LlmError::Config(format!(
+            "Unsupported LLM '{}', expected one of: {}",
+            name.trim(),
+            LLM_NAMES.join(", ")
+        ))),
+    }
+}
+
+/// Returns the API key to send to an LLM provider.
+///
+/// The key of OpenAI is read by [`get_openai_api_key`]. Ollama runs locally and needs no API
+/// key, so an empty key is returned for it.
+///
+/// # Arguments
+///
+/// * `llm` - The provider, typically constructed by [`llm_from_name`].
+///
+/// # Returns
+///
+/// * `Result<String, LlmError>` - The API key, or `LlmError::Config` if the provider needs a
+///   key that is not configured.
+pub fn llm_api_key(llm: &dyn LLM) -> Result<String, LlmError> {
+    match llm.provider() {
+        "openai" => get_openai_api_key().map_err(LlmError::from),
+        _ => Ok(String::new()),
+    }
+}
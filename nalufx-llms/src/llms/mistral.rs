@@ -1,4 +1,5 @@
-use crate::models::mistral_dm::MistralResponse;
+use super::{check_status, send_with_retry, RetryPolicy};
+use crate::{errors::LlmError, models::mistral_dm::MistralResponse};
 use actix_web::HttpResponse;
 use dotenvy::dotenv;
 use log::error;
@@ -45,14 +46,16 @@ pub fn get_mistral_api_key() -> Result<String, &'static str> {
 /// # Returns
 ///
 /// * `Ok(serde_json::Value)` - If the request is successfully sent and the response body is returned as a serde_json::Value.
-/// * `Err(reqwest::Error)` - If an error occurs during the request or response handling.
+/// * `Err(LlmError)` - If an error occurs during the request or response handling: `RateLimited`
+///   for a `429` status, `AuthFailed` for a `401` or `403` status, and `Other` otherwise.
 pub async fn send_mistral_request(
     client: &Client,
     api_url: &str,
     api_key: &str,
     request_body: serde_json::Value,
-) -> Result<serde_json::Value, reqwest::Error> {
-    let response = client.post(api_url).bearer_auth(api_key).json(&request_body).send().await?;
+) -> Result<serde_json::Value, LlmError> {
+    let request = client.post(api_url).bearer_auth(api_key).json(&request_body);
+    let response = check_status(send_with_retry(request, &RetryPolicy::from_env()).await?)?;
 
     let json_response: serde_json::Value = response.json().await?;
     Ok(json_response)
@@ -1,6 +1,201 @@
+use crate::errors::LlmError;
 use async_trait::async_trait;
-use reqwest::Client;
+use cache::{read_cached_response, write_cached_response, CacheKey, LlmCacheConfig};
+use log::{debug, warn};
+use reqwest::{header::RETRY_AFTER, Client, RequestBuilder, Response, StatusCode};
 use serde_json::Value;
+use std::{env, time::Duration};
+
+/// The default number of times a failed LLM request is retried.
+pub const DEFAULT_LLM_MAX_RETRIES: u32 = 3;
+
+/// The default delay before the first retry, doubled on every subsequent retry (500 ms).
+pub const DEFAULT_LLM_RETRY_BASE_DELAY_MS: u64 = 500;
+
+/// The default longest delay between two attempts (30 seconds).
+pub const DEFAULT_LLM_RETRY_MAX_DELAY_SECS: u64 = 30;
+
+/// The default timeout of a single attempt (60 seconds).
+pub const DEFAULT_LLM_TIMEOUT_SECS: u64 = 60;
+
+/// Represents how failed LLM requests are retried.
+///
+/// A request is retried when the provider responds with `429 Too Many Requests` or a `5xx`
+/// status, or when the attempt times out or cannot connect. The delay before each retry is
+/// the `Retry-After` header of the response when it holds a number of seconds, and otherwise
+/// `base_delay * 2^retry`, in both cases capped at `max_delay`.
+///
+/// # Fields
+///
+/// * `max_retries` - The number of times a request is retried after the first attempt.
+/// * `base_delay` - The delay before the first retry when the response has no `Retry-After`.
+/// * `max_delay` - The longest delay between two attempts.
+/// * `timeout` - The timeout of a single attempt.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx_llms::llms::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new(5, Duration::from_millis(100));
+/// assert_eq!(policy.delay(0, None), Duration::from_millis(100));
+/// assert_eq!(policy.delay(3, None), Duration::from_millis(800));
+/// assert_eq!(policy.delay(3, Some(Duration::from_secs(2))), Duration::from_secs(2));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+    /// The number of times a request is retried after the first attempt.
+    pub max_retries: u32,
+    /// The delay before the first retry when the response has no `Retry-After`.
+    pub base_delay: Duration,
+    /// The longest delay between two attempts.
+    pub max_delay: Duration,
+    /// The timeout of a single attempt.
+    pub timeout: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_LLM_MAX_RETRIES, Duration::from_millis(DEFAULT_LLM_RETRY_BASE_DELAY_MS))
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a new `RetryPolicy` instance with the default maximum delay and timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_retries` - The number of times a request is retried after the first attempt.
+    /// * `base_delay` - The delay before the first retry when the response has no `Retry-After`.
+    pub fn new(max_retries: u32, base_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            base_delay,
+            max_delay: Duration::from_secs(DEFAULT_LLM_RETRY_MAX_DELAY_SECS),
+            timeout: Duration::from_secs(DEFAULT_LLM_TIMEOUT_SECS),
+        }
+    }
+
+    /// Creates a new `RetryPolicy` instance from environment variables.
+    ///
+    /// `LLM_MAX_RETRIES` sets the number of retries, `LLM_RETRY_BASE_DELAY_MS` the base delay
+    /// in milliseconds, and `LLM_TIMEOUT_SECS` the timeout of a single attempt in seconds.
+    /// Unset or invalid values fall back to the defaults.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let parse = |name: &str| env::var(name).ok().and_then(|value| value.trim().parse().ok());
+        Self {
+            max_retries: parse("LLM_MAX_RETRIES")
+                .map_or(default.max_retries, |n: u64| u32::try_from(n).unwrap_or(u32::MAX)),
+            base_delay: parse("LLM_RETRY_BASE_DELAY_MS")
+                .map_or(default.base_delay, Duration::from_millis),
+            timeout: parse("LLM_TIMEOUT_SECS")
+                .filter(|&secs| secs > 0)
+                .map_or(default.timeout, Duration::from_secs),
+            ..default
+        }
+    }
+
+    /// Returns the delay before a retry.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - The number of retries already made, starting at `0`.
+    /// * `retry_after` - The delay requested by the `Retry-After` header, if any.
+    pub fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        let backoff = || self.base_delay.saturating_mul(2_u32.saturating_pow(retry));
+        retry_after.unwrap_or_else(backoff).min(self.max_delay)
+    }
+}
+
+/// Returns `true` if a response with the given status is worth retrying.
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Reads the `Retry-After` header of a response, when it holds a number of seconds.
+///
+/// The HTTP-date form of the header is not supported, so the caller falls back to its backoff.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    value.trim().parse().ok().map(Duration::from_secs)
+}
+
+/// Turns an unsuccessful response from an LLM provider into an `LlmError`.
+///
+/// A `429` response becomes `RateLimited`, with the delay of its `Retry-After` header, a `401`
+/// or `403` response becomes `AuthFailed`, and any other unsuccessful status becomes `Other`.
+///
+/// # Arguments
+///
+/// * `response` - The response to check, typically returned by [`send_with_retry`].
+///
+/// # Returns
+///
+/// * `Result<Response, LlmError>` - The response if its status is successful, or the matching
+///   `LlmError` otherwise.
+pub fn check_status(response: Response) -> Result<Response, LlmError> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(response)
+    } else {
+        Err(LlmError::from_status(status, retry_after(&response)))
+    }
+}
+
+/// Sends an HTTP request to an LLM provider, retrying it according to a `RetryPolicy`.
+///
+/// Every provider sends its requests through this helper, so that a rate limit or a transient
+/// outage delays a report instead of aborting it. Each attempt is bounded by `policy.timeout`.
+/// When the retries are exhausted, the last response or error is returned, so a final
+/// `429` or `5xx` response is returned as `Ok` for the caller to handle like any other status,
+/// e.g. with [`check_status`].
+/// A request whose body cannot be cloned (e.g. a stream) is sent once.
+///
+/// # Arguments
+///
+/// * `request` - The request to send.
+/// * `policy` - The `RetryPolicy` to apply.
+///
+/// # Returns
+///
+/// * `Result<Response, reqwest::Error>` - The response to the last attempt, or the error of the
+///   last attempt if it failed to complete.
+pub async fn send_with_retry(
+    request: RequestBuilder,
+    policy: &RetryPolicy,
+) -> Result<Response, reqwest::Error> {
+    let mut retry = 0;
+    loop {
+        let Some(attempt) = request.try_clone() else {
+            return request.timeout(policy.timeout).send().await;
+        };
+        let result = attempt.timeout(policy.timeout).send().await;
+        let requested_delay = match &result {
+            Ok(response) if is_retryable_status(response.status()) => retry_after(response),
+            Err(err) if err.is_timeout() || err.is_connect() => None,
+            _ => return result,
+        };
+        if retry >= policy.max_retries {
+            return result;
+        }
+
+        let delay = policy.delay(retry, requested_delay);
+        match &result {
+            Ok(response) => {
+                warn!(
+                    "LLM request failed with status {}, retrying in {:?}",
+                    response.status(),
+                    delay
+                )
+            },
+            Err(err) => warn!("LLM request failed: {:?}, retrying in {:?}", err, delay),
+        }
+        tokio::time::sleep(delay).await;
+        retry += 1;
+    }
+}
 
 /// A trait representing a Language Model (LLM) with a method to send requests.
 ///
@@ -19,8 +214,9 @@ pub trait LLM: Sync + Send {
     ///
     /// # Returns
     ///
-    /// * `Result<Value, reqwest::Error>` - A `Result` containing the JSON response from the LLM API
-    ///   on success, or a `reqwest::Error` on failure.
+    /// * `Result<Value, LlmError>` - A `Result` containing the JSON response from the LLM API on
+    ///   success, or an `LlmError` on failure, e.g. `RateLimited` if the provider responded with
+    ///   `429 Too Many Requests` after every retry.
     ///
     async fn send_request(
         &self,
@@ -28,18 +224,77 @@ async fn send_request(
         api_key: &str,
         prompt: &str,
         max_tokens: usize,
-    ) -> Result<Value, reqwest::Error>;
+    ) -> Result<Value, LlmError>;
+
+    /// Returns the name of the LLM provider (e.g., "openai").
+    fn provider(&self) -> &str;
+
+    /// Returns the name of the model used to generate responses.
+    fn model(&self) -> &str;
+
+    /// Sends a request to the LLM API, reusing a cached response when one is available.
+    ///
+    /// Responses are cached as JSON files under `cache.cache_dir`, keyed by a hash of the
+    /// provider, model, prompt, and `max_tokens`. A cached response younger than `cache.ttl`
+    /// is returned without contacting the API. Cache read and write failures are logged and
+    /// never prevent the request from being sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A reference to the `reqwest::Client` used to make the request.
+    /// * `api_key` - A reference to the API key used for authentication.
+    /// * `prompt` - A reference to the prompt to be sent to the LLM.
+    /// * `max_tokens` - The maximum number of tokens allowed in the response.
+    /// * `cache` - The cache directory and time-to-live to use.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Value, LlmError>` - A `Result` containing the cached or fresh JSON response on
+    ///   success, or an `LlmError` on failure.
+    ///
+    async fn send_request_cached(
+        &self,
+        client: &Client,
+        api_key: &str,
+        prompt: &str,
+        max_tokens: usize,
+        cache: &LlmCacheConfig,
+    ) -> Result<Value, LlmError> {
+        let key = CacheKey::new(self.provider(), self.model(), prompt, max_tokens);
+        if let Some(response) = read_cached_response(cache, &key) {
+            debug!("LLM cache hit for {}/{}", key.provider, key.model);
+            return Ok(response);
+        }
+
+        let response = self.send_request(client, api_key, prompt, max_tokens).await?;
+        if let Err(err) = write_cached_response(cache, &key, &response) {
+            warn!("Failed to write LLM cache entry: {:?}", err);
+        }
+        Ok(response)
+    }
 }
 
+/// This module contains the on-disk cache for LLM responses.
+pub mod cache;
+
+/// This module contains the chain of LLM providers that fails over from one to the next.
+pub mod chain;
+
 /// This module contains the Claude API handlers.
 pub mod claude;
 
+/// This module contains the construction of the LLM providers by name.
+pub mod factory;
+
 /// This module contains the Gemini API handlers.
 pub mod gemini;
 
 /// This module contains the Mistral API handlers.
 pub mod mistral;
 
+/// This module contains the offline mode used to run without contacting any LLM provider.
+pub mod offline;
+
 /// This module contains the Ollama API handlers.
 pub mod ollama;
 
@@ -0,0 +1,54 @@
+use serde_json::{json, Value};
+use std::env;
+
+/// The environment variable that enables offline mode.
+pub const OFFLINE_ENV: &str = "NALUFX_OFFLINE";
+
+/// The placeholder API key returned by the `get_*_api_key` functions in offline mode.
+pub const OFFLINE_API_KEY: &str = "offline";
+
+/// The canned report returned by the LLM layer in offline mode.
+pub const OFFLINE_REPORT: &str = "This report was generated in offline mode (NALUFX_OFFLINE=1) \
+and does not contain an analysis. Unset NALUFX_OFFLINE and configure an API key to generate \
+a report with a language model.";
+
+/// Returns `true` if offline mode is enabled.
+///
+/// Offline mode is enabled when the `NALUFX_OFFLINE` environment variable is set to `1`,
+/// `true`, `yes`, or `on` (case-insensitive). In offline mode, the LLM layer returns
+/// `OFFLINE_REPORT` instead of contacting any provider.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx_llms::llms::offline::is_offline_mode;
+/// use std::env;
+///
+/// env::set_var("NALUFX_OFFLINE", "1");
+/// assert!(is_offline_mode());
+///
+/// env::remove_var("NALUFX_OFFLINE");
+/// assert!(!is_offline_mode());
+/// ```
+pub fn is_offline_mode() -> bool {
+    env::var(OFFLINE_ENV).is_ok_and(|value| {
+        matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on")
+    })
+}
+
+/// Returns a chat-completion response containing `OFFLINE_REPORT`.
+///
+/// The response has the same shape as an OpenAI chat-completion response, so it can be
+/// parsed with `OpenAIResponse` or indexed as `response["choices"][0]["message"]["content"]`.
+pub fn offline_response() -> Value {
+    json!({
+        "choices": [
+            {
+                "message": {
+                    "role": "assistant",
+                    "content": OFFLINE_REPORT
+                }
+            }
+        ]
+    })
+}
@@ -1,10 +1,251 @@
-use crate::models::ollama_dm::OllamaResponse;
+use super::{
+    check_status,
+    offline::{is_offline_mode, offline_response},
+    send_with_retry, RetryPolicy, LLM,
+};
+use crate::{
+    errors::LlmError,
+    models::ollama_dm::{OllamaGenerateChunk, OllamaResponse},
+};
 use actix_web::HttpResponse;
+use async_trait::async_trait;
 use dotenvy::dotenv;
-use log::error;
+use log::{error, warn};
 use reqwest::Client;
+use serde::de::Error as _;
+use serde_json::{json, Value};
 use std::env;
 
+/// The environment variable that sets the base URL of the Ollama server.
+pub const OLLAMA_HOST_ENV: &str = "OLLAMA_HOST";
+
+/// The environment variable that sets the Ollama model used to generate responses.
+pub const OLLAMA_MODEL_ENV: &str = "OLLAMA_MODEL";
+
+/// The base URL of a local Ollama server.
+pub const DEFAULT_OLLAMA_HOST: &str = "http://localhost:11434";
+
+/// The Ollama model used when `OLLAMA_MODEL` is not set.
+pub const DEFAULT_OLLAMA_MODEL: &str = "llama3";
+
+/// A struct representing a local or self-hosted Ollama server.
+///
+/// Ollama runs models locally, so reports can be generated without a cloud API key. Requests
+/// are sent to the `/api/generate` endpoint and the streamed response is collected into a
+/// chat-completion shaped `Value`, so it can be indexed as
+/// `response["choices"][0]["message"]["content"]` like the other providers.
+///
+/// # Fields
+///
+/// * `host` - The base URL of the Ollama server (e.g., "http://localhost:11434").
+/// * `model` - The name of the model used to generate responses (e.g., "llama3").
+///
+/// # Examples
+///
+/// ```
+/// use nalufx_llms::llms::{ollama::Ollama, LLM};
+///
+/// let ollama = Ollama::new("http://localhost:11434/", "mistral");
+/// assert_eq!(ollama.host, "http://localhost:11434");
+/// assert_eq!(ollama.model(), "mistral");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ollama {
+    /// The base URL of the Ollama server.
+    pub host: String,
+    /// The name of the model used to generate responses.
+    pub model: String,
+}
+
+impl Default for Ollama {
+    fn default() -> Self {
+        Self::new(DEFAULT_OLLAMA_HOST, DEFAULT_OLLAMA_MODEL)
+    }
+}
+
+impl Ollama {
+    /// Creates a new `Ollama` instance.
+    ///
+    /// A trailing `/` is removed from the host, and `http://` is prepended if the host has no
+    /// scheme, as in Ollama's own `OLLAMA_HOST` (e.g., "localhost:11434").
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The base URL of the Ollama server.
+    /// * `model` - The name of the model used to generate responses.
+    pub fn new(host: &str, model: &str) -> Self {
+        let host = host.trim().trim_end_matches('/');
+        let host = if host.contains("://") { host.to_string() } else { format!("http://{}", host) };
+        Self { host, model: model.trim().to_string() }
+    }
+
+    /// Creates a new `Ollama` instance from environment variables.
+    ///
+    /// `OLLAMA_HOST` sets the base URL of the server and `OLLAMA_MODEL` sets the model. Unset
+    /// or empty values fall back to `DEFAULT_OLLAMA_HOST` and `DEFAULT_OLLAMA_MODEL`.
+    pub fn from_env() -> Self {
+        let var = |name: &str, default: &str| {
+            env::var(name)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .unwrap_or_else(|| default.to_string())
+        };
+        Self::new(
+            &var(OLLAMA_HOST_ENV, DEFAULT_OLLAMA_HOST),
+            &var(OLLAMA_MODEL_ENV, DEFAULT_OLLAMA_MODEL),
+        )
+    }
+
+    /// Returns the URL of the `/api/generate` endpoint.
+    pub fn generate_url(&self) -> String {
+        format!("{}/api/generate", self.host)
+    }
+}
+
+#[async_trait]
+impl LLM for Ollama {
+    /// Sends a prompt to the `/api/generate` endpoint and collects the streamed response.
+    ///
+    /// The lines of the stream are parsed as they arrive, until the line with `"done": true`.
+    /// An `api_key` is not needed by Ollama, but is sent as a bearer token when non-empty, for
+    /// servers behind an authenticating proxy. A line that cannot be parsed, or that reports an
+    /// error, is logged and ends the stream, and the text received so far is returned.
+    async fn send_request(
+        &self,
+        client: &Client,
+        api_key: &str,
+        prompt: &str,
+        max_tokens: usize,
+    ) -> Result<Value, LlmError> {
+        if is_offline_mode() {
+            return Ok(offline_response());
+        }
+
+        let request_body = json!({
+            "model": self.model,
+            "system": "You are a financial analyst specializing in automated cash allocation.",
+            "prompt": prompt,
+            "stream": true,
+            "options": { "num_predict": max_tokens },
+        });
+        let mut request = client.post(self.generate_url()).json(&request_body);
+        if !api_key.is_empty() {
+            request = request.bearer_auth(api_key);
+        }
+        let mut response = check_status(send_with_retry(request, &RetryPolicy::from_env()).await?)?;
+
+        let mut stream = OllamaStream::default();
+        let mut buffer = Vec::new();
+        while !stream.done {
+            let Some(chunk) = response.chunk().await? else {
+                break;
+            };
+            buffer.extend_from_slice(&chunk);
+            while let Some(end) = buffer.iter().position(|&byte| byte == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=end).collect();
+                if !stream.push_logged(&String::from_utf8_lossy(&line)) {
+                    break;
+                }
+            }
+        }
+        // The last line may not be terminated by a newline.
+        if !stream.done {
+            let _ = stream.push_logged(&String::from_utf8_lossy(&buffer));
+        }
+
+        Ok(json!({
+            "choices": [
+                {
+                    "message": {
+                        "role": "assistant",
+                        "content": stream.text
+                    }
+                }
+            ]
+        }))
+    }
+
+    fn provider(&self) -> &str {
+        "ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+/// Collects the text of a streamed Ollama response, one line at a time.
+#[derive(Debug, Default)]
+struct OllamaStream {
+    /// The text received so far.
+    text: String,
+    /// Whether the line with `"done": true` has been received.
+    done: bool,
+}
+
+impl OllamaStream {
+    /// Parses a line of the stream and appends its fragment to the text.
+    ///
+    /// Blank lines are ignored, and lines after the last one are not parsed.
+    fn push(&mut self, line: &str) -> Result<(), serde_json::Error> {
+        if self.done || line.trim().is_empty() {
+            return Ok(());
+        }
+        let chunk: OllamaGenerateChunk = serde_json::from_str(line)?;
+        if let Some(message) = chunk.error {
+            return Err(serde_json::Error::custom(format!("Ollama error: {}", message)));
+        }
+        self.text.push_str(&chunk.response);
+        self.done = chunk.done;
+        Ok(())
+    }
+
+    /// Parses a line of the stream like `push`, logging an error and ending the stream if
+    /// the line is invalid. Returns `false` once the stream has ended.
+    fn push_logged(&mut self, line: &str) -> bool {
+        if let Err(err) = self.push(line) {
+            error!("Error parsing Ollama stream: {:?}", err);
+            self.done = true;
+        }
+        !self.done
+    }
+}
+
+/// Parses the newline-delimited JSON stream returned by the Ollama `/api/generate` endpoint.
+///
+/// Each line of the stream is a JSON object whose `response` field holds the next fragment of
+/// the generated text, and the last line has `"done": true`. The fragments are concatenated in
+/// order. Blank lines and anything after the last line are ignored.
+///
+/// # Arguments
+///
+/// * `body` - A string representing the streamed response body.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated text, which is incomplete if the stream has no line with
+///   `"done": true`.
+/// * `Err(serde_json::Error)` - If a line is not valid JSON or reports an error.
+///
+/// # Examples
+///
+/// ```
+/// use nalufx_llms::llms::ollama::parse_ollama_stream;
+///
+/// let body = "{\"response\":\"Hold\",\"done\":false}\n{\"response\":\" cash.\",\"done\":true}\n";
+/// assert_eq!(parse_ollama_stream(body).unwrap(), "Hold cash.");
+/// ```
+pub fn parse_ollama_stream(body: &str) -> Result<String, serde_json::Error> {
+    let mut stream = OllamaStream::default();
+    for line in body.lines() {
+        stream.push(line)?;
+    }
+    if !stream.done {
+        warn!("Ollama stream ended before the final line");
+    }
+    Ok(stream.text)
+}
+
 /// Retrieves the Ollama API key from the environment variables or .env file.
 ///
 /// # Returns
@@ -45,14 +286,16 @@ pub fn get_ollama_api_key() -> Result<String, &'static str> {
 /// # Returns
 ///
 /// * `Ok(serde_json::Value)` - If the request is successfully sent and the response body is returned as a serde_json::Value.
-/// * `Err(reqwest::Error)` - If an error occurs during the request or response handling.
+/// * `Err(LlmError)` - If an error occurs during the request or response handling: `RateLimited`
+///   for a `429` status, `AuthFailed` for a `401` or `403` status, and `Other` otherwise.
 pub async fn send_ollama_request(
     client: &Client,
     api_url: &str,
     api_key: &str,
     request_body: serde_json::Value,
-) -> Result<serde_json::Value, reqwest::Error> {
-    let response = client.post(api_url).bearer_auth(api_key).json(&request_body).send().await?;
+) -> Result<serde_json::Value, LlmError> {
+    let request = client.post(api_url).bearer_auth(api_key).json(&request_body);
+    let response = check_status(send_with_retry(request, &RetryPolicy::from_env()).await?)?;
 
     let json_response: serde_json::Value = response.json().await?;
     Ok(json_response)
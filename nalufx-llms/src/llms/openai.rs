@@ -1,5 +1,9 @@
-use super::LLM;
-use crate::models::openai_dm::OpenAIResponse;
+use super::{
+    check_status,
+    offline::{is_offline_mode, offline_response, OFFLINE_API_KEY},
+    send_with_retry, RetryPolicy, LLM,
+};
+use crate::{errors::LlmError, models::openai_dm::OpenAIResponse};
 use actix_web::HttpResponse;
 use async_trait::async_trait;
 use dotenvy::dotenv;
@@ -20,7 +24,11 @@ async fn send_request(
         api_key: &str,
         prompt: &str,
         max_tokens: usize,
-    ) -> Result<Value, reqwest::Error> {
+    ) -> Result<Value, LlmError> {
+        if is_offline_mode() {
+            return Ok(offline_response());
+        }
+
         let request_body = json!({
             "model": "gpt-3.5-turbo",
             "messages": [
@@ -36,24 +44,38 @@ async fn send_request(
             "max_tokens": max_tokens,
         });
 
-        let response = client
+        let request = client
             .post("https://api.openai.com/v1/chat/completions")
             .header("Authorization", format!("Bearer {}", api_key))
-            .json(&request_body)
-            .send()
-            .await?;
+            .json(&request_body);
+        let response = check_status(send_with_retry(request, &RetryPolicy::from_env()).await?)?;
+
+        Ok(response.json().await?)
+    }
 
-        response.json().await
+    fn provider(&self) -> &str {
+        "openai"
+    }
+
+    fn model(&self) -> &str {
+        "gpt-3.5-turbo"
     }
 }
 
 /// Retrieves the OpenAI API key from the environment variables or.env file.
 ///
+/// In offline mode, a placeholder key is returned without reading the environment.
+///
 /// # Returns
 ///
 /// * `Ok(String)` - If the API key is successfully retrieved.
 /// * `Err(&'static str)` - If the API key is not found in the environment variables or.env file.
 pub fn get_openai_api_key() -> Result<String, &'static str> {
+    // In offline mode no request is sent, so any key will do
+    if is_offline_mode() {
+        return Ok(OFFLINE_API_KEY.to_string());
+    }
+
     // First, try to read the API key from the environment variables
     if let Ok(key) = env::var("OPENAI_API_KEY") {
         return Ok(key);
@@ -77,6 +99,8 @@ pub fn get_openai_api_key() -> Result<String, &'static str> {
 
 /// Sends a POST request to the OpenAI API with the provided request body.
 ///
+/// In offline mode, the canned offline response is returned without sending a request.
+///
 /// # Arguments
 ///
 /// * `client` - A reference to the reqwest::Client instance used for making HTTP requests.
@@ -87,30 +111,33 @@ pub fn get_openai_api_key() -> Result<String, &'static str> {
 /// # Returns
 ///
 /// * `Ok(String)` - If the request is successfully sent and the response body is returned as a string.
-/// * `Err(&'static str)` - If an error occurs during the request or response handling.
+/// * `Err(LlmError)` - If an error occurs during the request or response handling: `RateLimited`
+///   for a `429` status, `AuthFailed` for a `401` or `403` status, and `Other` otherwise.
 pub async fn send_openai_request(
     client: &Client,
     api_url: &str,
     api_key: &str,
     request_body: Value,
-) -> Result<String, &'static str> {
-    let response = client
+) -> Result<String, LlmError> {
+    if is_offline_mode() {
+        return Ok(offline_response().to_string());
+    }
+
+    let request = client
         .post(api_url)
         .header("Authorization", format!("Bearer {}", api_key))
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|err| {
-            error!("Error sending request to OpenAI API: {:?}", err);
-            "Error contacting OpenAI API"
-        })?;
-    if !response.status().is_success() {
-        error!("OpenAI API call failed with status: {:?}", response.status());
-        return Err("OpenAI API call failed");
-    }
+        .json(&request_body);
+    let response = send_with_retry(request, &RetryPolicy::from_env()).await.map_err(|err| {
+        error!("Error sending request to OpenAI API: {:?}", err);
+        LlmError::from(err)
+    })?;
+    let response = check_status(response).map_err(|err| {
+        error!("OpenAI API call failed: {}", err);
+        err
+    })?;
     response.text().await.map_err(|err| {
         error!("Error reading response body: {:?}", err);
-        "Error reading response body"
+        LlmError::from(err)
     })
 }
 
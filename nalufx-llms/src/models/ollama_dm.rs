@@ -44,3 +44,26 @@ pub struct OllamaMessage {
     /// A string representing the content of the message
     pub content: String,
 }
+
+/// Struct representing one line of a streamed response from the Ollama `/api/generate` endpoint.
+///
+/// Ollama streams its response as newline-delimited JSON, where each line holds the next
+/// fragment of the generated text and the last line has `done` set to `true`.
+///
+/// # Fields
+///
+/// * `response` - The fragment of the generated text carried by this line.
+/// * `done` - Whether this is the last line of the stream.
+/// * `error` - The error reported by Ollama instead of a fragment, if any.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct OllamaGenerateChunk {
+    /// The fragment of the generated text carried by this line
+    #[serde(default)]
+    pub response: String,
+    /// Whether this is the last line of the stream
+    #[serde(default)]
+    pub done: bool,
+    /// The error reported by Ollama, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
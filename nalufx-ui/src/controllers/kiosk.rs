@@ -0,0 +1,140 @@
+use crate::MainWindow;
+use crate::{MenuOverviewAdapter, SettingsAdapter};
+use slint::{ComponentHandle, Global, Model, Timer, TimerMode};
+use std::time::Duration;
+
+/// The number of seconds each page is shown when no interval is configured.
+pub const DEFAULT_KIOSK_INTERVAL_SECS: u64 = 4;
+
+/// The configuration of kiosk mode.
+///
+/// # Fields
+///
+/// * `interval_secs` - The number of seconds each page is shown.
+/// * `pages` - The indices of the pages to rotate through, or all pages if empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KioskConfig {
+    /// The number of seconds each page is shown.
+    pub interval_secs: u64,
+    /// The indices of the pages to rotate through, or all pages if empty.
+    pub pages: Vec<i32>,
+}
+
+impl Default for KioskConfig {
+    fn default() -> Self {
+        Self { interval_secs: DEFAULT_KIOSK_INTERVAL_SECS, pages: Vec::new() }
+    }
+}
+
+impl KioskConfig {
+    /// Reads the kiosk configuration from the `kiosk-interval-secs` and `kiosk-pages`
+    /// properties of the settings adapter.
+    ///
+    /// An interval that is not positive falls back to `DEFAULT_KIOSK_INTERVAL_SECS`.
+    ///
+    /// # Parameters
+    ///
+    /// * `settings` - The settings adapter of the main window.
+    pub fn from_settings(settings: &SettingsAdapter<'_>) -> Self {
+        let interval_secs = u64::try_from(settings.get_kiosk_interval_secs())
+            .ok()
+            .filter(|&secs| secs > 0)
+            .unwrap_or(DEFAULT_KIOSK_INTERVAL_SECS);
+        Self { interval_secs, pages: settings.get_kiosk_pages().iter().collect() }
+    }
+}
+
+/// Returns the page shown after `current` when every page is shown in turn.
+///
+/// The pages wrap around after the last one. A `current` page outside `0..count` moves back
+/// to the first page.
+///
+/// # Parameters
+///
+/// * `current` - The index of the page currently shown.
+/// * `count` - The number of pages.
+///
+/// # Returns
+///
+/// The index of the next page, or `0` if there are no pages.
+pub fn next_kiosk_page(current: i32, count: i32) -> i32 {
+    if count <= 0 || current < 0 || current >= count - 1 {
+        0
+    } else {
+        current + 1
+    }
+}
+
+/// Returns the page shown after `current_page` in kiosk mode.
+///
+/// If `pages` is empty, every page is shown in turn (see [`next_kiosk_page`]). Otherwise, only the selected pages that exist are shown, in ascending order: the next page
+/// is the first selected page after `current_page`, wrapping around to the first selected
+/// page. If none of the selected pages exist, every page is shown in turn.
+///
+/// # Parameters
+///
+/// * `current_page` - The index of the page currently shown.
+/// * `count` - The number of pages.
+/// * `pages` - The indices of the pages to rotate through, or all pages if empty.
+///
+/// # Returns
+///
+/// The index of the next page, or `0` if there are no pages.
+pub fn next_page(current_page: i32, count: i32, pages: &[i32]) -> i32 {
+    let mut selected: Vec<i32> =
+        pages.iter().copied().filter(|page| (0..count).contains(page)).collect();
+    selected.sort_unstable();
+    selected.dedup();
+
+    match selected.first() {
+        Some(&first) => selected.iter().copied().find(|&page| page > current_page).unwrap_or(first),
+        None => next_kiosk_page(current_page, count),
+    }
+}
+
+/// Sets up a timer that rotates through the pages of the menu overview in kiosk mode.
+///
+/// The timer ticks every second and reads the kiosk configuration on each tick, so changes to
+/// the interval or the selected pages apply without a restart. When kiosk mode is enabled, the
+/// next page is shown once the configured interval has elapsed.
+///
+/// # Parameters
+///
+/// * `window` - A reference to the main window of the application.
+///
+/// # Returns
+///
+/// * A `Timer` instance that is started and configured to rotate the pages.
+pub fn setup(window: &MainWindow) -> Timer {
+    let kiosk_mode_timer = Timer::default();
+    kiosk_mode_timer.start(TimerMode::Repeated, Duration::from_secs(1), {
+        let window_weak = window.as_weak();
+        let mut elapsed_secs = 0;
+        move || {
+            let Some(window) = window_weak.upgrade() else {
+                return;
+            };
+            let settings = SettingsAdapter::get(&window);
+            if !settings.get_kiosk_mode_checked() {
+                elapsed_secs = 0;
+                return;
+            }
+
+            elapsed_secs += 1;
+            let config = KioskConfig::from_settings(&settings);
+            if elapsed_secs < config.interval_secs {
+                return;
+            }
+            elapsed_secs = 0;
+
+            let menu_overview = MenuOverviewAdapter::get(&window);
+            let new_page = next_page(
+                menu_overview.get_current_page(),
+                menu_overview.get_count(),
+                &config.pages,
+            );
+            menu_overview.set_current_page(new_page);
+        }
+    });
+    kiosk_mode_timer
+}
@@ -0,0 +1,240 @@
+use crate::ui::{StockQuote, StocksAdapter};
+use crate::MainWindow;
+use chrono::{Duration as ChronoDuration, Utc};
+use log::warn;
+use nalufx::config::fetch_concurrency;
+use nalufx::services::fetch_data_svc::fetch_data_batch;
+use nalufx::utils::batch::BatchOptions;
+use slint::{ComponentHandle, Global, ModelRc, Timer, TimerMode, VecModel, Weak};
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+/// The environment variable that sets the tickers of the watchlist, separated by commas.
+pub const WATCHLIST_ENV: &str = "NALUFX_WATCHLIST";
+
+/// The tickers shown when `NALUFX_WATCHLIST` is not set, those of the stocks carousel.
+pub const DEFAULT_WATCHLIST: &[&str] = &["AAPL", "AMZN", "GOOGL", "MSFT", "TSLA", "HSBC", "META"];
+
+/// The number of seconds between two refreshes of the quotes.
+pub const REFRESH_INTERVAL_SECS: u64 = 60;
+
+/// The number of calendar days of prices fetched, enough to span a long weekend.
+const LOOKBACK_DAYS: i64 = 7;
+
+/// The text shown in place of a value that is not available.
+const NOT_AVAILABLE: &str = "-";
+
+/// The latest quote of a watchlist ticker, formatted for display.
+///
+/// # Fields
+///
+/// * `ticker` - The ticker symbol.
+/// * `price` - The last closing price, with two decimal places.
+/// * `change` - The change from the previous close, as a signed percentage.
+/// * `stale` - Whether the last refresh failed, so the quote may be out of date.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuoteRow {
+    /// The ticker symbol.
+    pub ticker: String,
+    /// The last closing price.
+    pub price: String,
+    /// The change from the previous close.
+    pub change: String,
+    /// Whether the quote may be out of date.
+    pub stale: bool,
+}
+
+impl From<&QuoteRow> for StockQuote {
+    fn from(row: &QuoteRow) -> Self {
+        Self {
+            ticker: row.ticker.as_str().into(),
+            price: row.price.as_str().into(),
+            change: row.change.as_str().into(),
+            stale: row.stale,
+        }
+    }
+}
+
+/// Parses a comma-separated list of tickers, in uppercase and without blanks or duplicates.
+///
+/// # Parameters
+///
+/// * `value` - The list of tickers (e.g., "aapl, msft").
+pub fn parse_watchlist(value: &str) -> Vec<String> {
+    let mut tickers: Vec<String> = Vec::new();
+    for ticker in value.split(',').map(|ticker| ticker.trim().to_uppercase()) {
+        if !ticker.is_empty() && !tickers.contains(&ticker) {
+            tickers.push(ticker);
+        }
+    }
+    tickers
+}
+
+/// Returns the tickers of the watchlist, read from `NALUFX_WATCHLIST` or `DEFAULT_WATCHLIST`
+/// if the variable is unset or holds no ticker.
+pub fn watchlist() -> Vec<String> {
+    let tickers = env::var(WATCHLIST_ENV).map(|value| parse_watchlist(&value)).unwrap_or_default();
+    if tickers.is_empty() {
+        DEFAULT_WATCHLIST.iter().map(|ticker| ticker.to_string()).collect()
+    } else {
+        tickers
+    }
+}
+
+/// Formats the latest quote of a ticker from its closing prices.
+///
+/// # Parameters
+///
+/// * `ticker` - The ticker symbol.
+/// * `closes` - The closing prices, in chronological order.
+///
+/// # Returns
+///
+/// The quote, whose change is `-` if there is a single price, or `None` if the last price is
+/// missing, not finite or not positive.
+pub fn quote_row(ticker: &str, closes: &[f64]) -> Option<QuoteRow> {
+    let last = closes.last().copied().filter(|price| price.is_finite() && *price > 0.0)?;
+    let change = closes
+        .len()
+        .checked_sub(2)
+        .map(|index| closes[index])
+        .filter(|previous| previous.is_finite() && *previous > 0.0)
+        .map_or_else(
+            || NOT_AVAILABLE.to_string(),
+            |previous| format!("{:+.2}%", (last / previous - 1.0) * 100.0),
+        );
+    Some(QuoteRow {
+        ticker: ticker.to_string(),
+        price: format!("{:.2}", last),
+        change,
+        stale: false,
+    })
+}
+
+/// Maps the result of a refresh to the rows displayed in the UI.
+///
+/// A ticker whose prices were fetched shows its latest quote. A ticker whose fetch failed, or
+/// returned no usable price, keeps its previous quote marked as stale, or shows `-` marked as
+/// stale if it had none.
+///
+/// # Parameters
+///
+/// * `previous` - The rows displayed before the refresh.
+/// * `results` - The closing prices of each ticker, or the reason the fetch failed.
+///
+/// # Returns
+///
+/// One row per ticker, in the order of `results`.
+pub fn refresh_rows(
+    previous: &[QuoteRow],
+    results: &[(String, Result<Vec<f64>, String>)],
+) -> Vec<QuoteRow> {
+    results
+        .iter()
+        .map(|(ticker, result)| {
+            let row = match result {
+                Ok(closes) => quote_row(ticker, closes),
+                Err(e) => {
+                    warn!("Failed to refresh the quote of {}: {}", ticker, e);
+                    None
+                },
+            };
+            row.unwrap_or_else(|| stale_row(previous, ticker))
+        })
+        .collect()
+}
+
+/// Returns the previous row of a ticker marked as stale, or a stale placeholder.
+fn stale_row(previous: &[QuoteRow], ticker: &str) -> QuoteRow {
+    let row = previous.iter().find(|row| row.ticker == ticker).cloned();
+    QuoteRow {
+        stale: true,
+        ..row.unwrap_or_else(|| QuoteRow {
+            ticker: ticker.to_string(),
+            price: NOT_AVAILABLE.to_string(),
+            change: NOT_AVAILABLE.to_string(),
+            stale: true,
+        })
+    }
+}
+
+/// Sets up a timer that refreshes the quotes of the watchlist in the stocks adapter.
+///
+/// The quotes are fetched once at start-up, then every `REFRESH_INTERVAL_SECS` seconds, on a
+/// background thread so that the UI stays responsive. A refresh is skipped while the previous
+/// one is still running. Failed fetches mark the quotes as stale instead of clearing them.
+///
+/// # Parameters
+///
+/// * `window` - A reference to the main window of the application.
+///
+/// # Returns
+///
+/// * A `Timer` instance that is started and configured to refresh the quotes.
+pub fn setup(window: &MainWindow) -> Timer {
+    let mut refresh = {
+        let window_weak = window.as_weak();
+        let tickers = watchlist();
+        let rows = Arc::new(Mutex::new(Vec::new()));
+        let in_flight = Arc::new(AtomicBool::new(false));
+        move || {
+            refresh(window_weak.clone(), tickers.clone(), Arc::clone(&rows), Arc::clone(&in_flight))
+        }
+    };
+    refresh();
+
+    let update_timer = Timer::default();
+    update_timer.start(TimerMode::Repeated, Duration::from_secs(REFRESH_INTERVAL_SECS), refresh);
+    update_timer
+}
+
+/// Fetches the quotes on a background thread and updates the stocks adapter with them.
+fn refresh(
+    window: Weak<MainWindow>,
+    tickers: Vec<String>,
+    rows: Arc<Mutex<Vec<QuoteRow>>>,
+    in_flight: Arc<AtomicBool>,
+) {
+    if in_flight.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let _ = thread::spawn(move || {
+        let results = fetch_closes(&tickers);
+        let updated = {
+            let mut rows = rows.lock().unwrap_or_else(PoisonError::into_inner);
+            *rows = refresh_rows(&rows, &results);
+            rows.clone()
+        };
+        in_flight.store(false, Ordering::SeqCst);
+
+        let _ = window.upgrade_in_event_loop(move |window| {
+            let stocks = StocksAdapter::get(&window);
+            stocks.set_stale(updated.iter().any(|row| row.stale));
+            let quotes: Vec<StockQuote> = updated.iter().map(StockQuote::from).collect();
+            stocks.set_quotes(ModelRc::new(VecModel::from(quotes)));
+        });
+    });
+}
+
+/// Fetches the recent closing prices of the tickers, with the configured concurrency.
+fn fetch_closes(tickers: &[String]) -> Vec<(String, Result<Vec<f64>, String>)> {
+    let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            return tickers.iter().map(|ticker| (ticker.clone(), Err(e.to_string()))).collect()
+        },
+    };
+
+    let start_date = Some(Utc::now() - ChronoDuration::days(LOOKBACK_DAYS));
+    let options = BatchOptions::new(fetch_concurrency());
+    let results = runtime.block_on(fetch_data_batch(tickers, start_date, None, &options));
+    tickers
+        .iter()
+        .cloned()
+        .zip(results.into_iter().map(|result| result.map_err(|e| e.to_string())))
+        .collect()
+}
@@ -17,12 +17,18 @@ pub mod ui {
 pub mod controllers {
     /// Submodule for header-related functionality.
     pub mod header;
+    /// Submodule for kiosk mode functionality.
+    pub mod kiosk;
+    /// Submodule for the live quotes of the watchlist.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod quotes;
 }
 
 /// Main function to initialize and run the application.
 ///
 /// This function sets up the main window, initializes controllers,
-/// and starts the kiosk mode timer if applicable.
+/// starts the kiosk mode timer if applicable, and refreshes the quotes of the
+/// watchlist.
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(start))]
 pub fn main() {
     // Provide better error messages in debug mode for WASM targets.
@@ -36,7 +42,11 @@ pub fn main() {
     let _header_timer = controllers::header::setup(&window);
 
     // Initialize kiosk mode timer
-    let _kiosk_mode_timer = kiosk_timer(&window);
+    let _kiosk_mode_timer = controllers::kiosk::setup(&window);
+
+    // Refresh the quotes of the watchlist
+    #[cfg(not(target_arch = "wasm32"))]
+    let _quotes_timer = controllers::quotes::setup(&window);
 
     // Run the main event loop
     window.run().expect("Failed to run main window");
@@ -59,39 +69,3 @@ pub fn get_time() {
     let now = DateTime::new();
     dtt_print!(now);
 }
-
-/// Creates and starts a timer for kiosk mode functionality.
-///
-/// This function sets up a timer that periodically updates the current page
-/// in kiosk mode, cycling through available pages.
-///
-/// # Arguments
-///
-/// * `window` - A reference to the MainWindow instance.
-///
-/// # Returns
-///
-/// Returns a Timer instance managing the kiosk mode updates.
-fn kiosk_timer(window: &MainWindow) -> Timer {
-    let kiosk_mode_timer = Timer::default();
-    kiosk_mode_timer.start(TimerMode::Repeated, std::time::Duration::from_secs(4), {
-        let window_weak = window.as_weak();
-        move || {
-            let window = window_weak.upgrade().expect("Window was dropped");
-            let settings = SettingsAdapter::get(&window);
-            let menu_overview = MenuOverviewAdapter::get(&window);
-
-            if !settings.get_kiosk_mode_checked() {
-                return;
-            }
-
-            let current_page = menu_overview.get_current_page();
-            let count = menu_overview.get_count();
-
-            let new_page = if current_page >= count - 1 { 0 } else { current_page + 1 };
-
-            menu_overview.set_current_page(new_page);
-        }
-    });
-    kiosk_mode_timer
-}
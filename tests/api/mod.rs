@@ -6,3 +6,15 @@
 
 /// This module contains the tests for `models.rs`.
 pub mod test_models;
+
+/// This module contains the tests for `rate_limit.rs`.
+pub mod test_rate_limit;
+
+/// This module contains the tests for `auth.rs`.
+pub mod test_auth;
+
+/// This module contains the tests for `ws.rs`.
+pub mod test_ws;
+
+/// This module contains the tests for `openapi.rs`.
+pub mod test_openapi;
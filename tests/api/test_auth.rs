@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        http::header::{AUTHORIZATION, WWW_AUTHENTICATE},
+        test, web, App, HttpResponse,
+    };
+    use nalufx::{
+        api::{auth::BearerAuth, handlers::health},
+        models::cash_flow_dm::ErrorResponse,
+    };
+
+    const TOKEN: &str = "test-token";
+
+    macro_rules! init_app {
+        ($auth:expr) => {
+            test::init_service(
+                App::new()
+                    .wrap($auth)
+                    .service(health)
+                    .route("/predict", web::post().to(HttpResponse::Ok)),
+            )
+            .await
+        };
+    }
+
+    /// Tests that a request without an `Authorization` header is rejected with `401`.
+    #[actix_rt::test]
+    async fn test_missing_token() {
+        let app = init_app!(BearerAuth::new(TOKEN));
+
+        let req = test::TestRequest::post().uri("/predict").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 401);
+        assert_eq!(resp.headers().get(WWW_AUTHENTICATE).unwrap(), "Bearer");
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!(body.error, "Missing or invalid bearer token");
+    }
+
+    /// Tests that a request with a wrong token or scheme is rejected with `401`.
+    #[actix_rt::test]
+    async fn test_wrong_token() {
+        let app = init_app!(BearerAuth::new(TOKEN));
+
+        for header in ["Bearer wrong-token", "Bearer test-token-2", "Basic test-token", TOKEN] {
+            let req = test::TestRequest::post()
+                .uri("/predict")
+                .insert_header((AUTHORIZATION, header))
+                .to_request();
+            assert_eq!(test::call_service(&app, req).await.status(), 401, "accepted {}", header);
+        }
+    }
+
+    /// Tests that a request with the configured token is accepted.
+    #[actix_rt::test]
+    async fn test_correct_token() {
+        let app = init_app!(BearerAuth::new(TOKEN));
+
+        let req = test::TestRequest::post()
+            .uri("/predict")
+            .insert_header((AUTHORIZATION, format!("Bearer {}", TOKEN)))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+    }
+
+    /// Tests that the health endpoint does not require a token.
+    #[actix_rt::test]
+    async fn test_health_is_exempt() {
+        let app = init_app!(BearerAuth::new(TOKEN));
+
+        let req = test::TestRequest::get().uri("/health").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+    }
+
+    /// Tests that a disabled `BearerAuth` lets every request through.
+    #[actix_rt::test]
+    async fn test_disabled() {
+        let app = init_app!(BearerAuth::disabled());
+
+        let req = test::TestRequest::post().uri("/predict").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+    }
+}
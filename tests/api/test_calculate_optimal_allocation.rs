@@ -1,7 +1,16 @@
 #[cfg(test)]
 mod tests {
     use nalufx::errors::AllocationError;
-    use nalufx::utils::calculations::calculate_optimal_allocation;
+    use nalufx::utils::allocation::Constraints;
+    use nalufx::utils::calculations::{
+        allocation_variance, assign_clusters, calculate_optimal_allocation,
+        calculate_optimal_allocation_with_options, calculate_optimal_allocation_with_report,
+        forecast_time_series_with_intervals, perform_clustering, perform_clustering_with_seed,
+        AllocationOptions, AllocationWeights, Blending, Objective, DEFAULT_CLUSTERING_SEED,
+        DEFAULT_FORECAST_CONFIDENCE, DEFAULT_MAX_CASH_FLOW, DEFAULT_MAX_DAILY_RETURN,
+        DEFAULT_MIN_HISTORY, MIN_FORECAST_POINTS, NUM_CLUSTERS,
+    };
+    use ndarray::Array2;
 
     #[test]
     fn test_calculate_optimal_allocation() {
@@ -57,7 +66,15 @@ fn test_calculate_optimal_allocation() {
             num_days,
         );
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), AllocationError::InputMismatch);
+        assert_eq!(
+            result.unwrap_err(),
+            AllocationError::InputMismatch {
+                expected: "daily_returns".to_string(),
+                expected_len: 3,
+                found: "cash_flows".to_string(),
+                found_len: 2,
+            }
+        );
 
         // Test case 4: Input with missing or invalid data
         let daily_returns = vec![0.02, f64::NAN, 0.03];
@@ -89,4 +106,519 @@ fn test_calculate_optimal_allocation() {
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), AllocationError::OutlierData);
     }
+
+    #[test]
+    fn test_default_options_match_calculate_optimal_allocation() {
+        let options = AllocationOptions::default();
+        assert_eq!(options.objective, Objective::MlHeuristic);
+        assert_eq!(options.weights, AllocationWeights::default());
+        assert_eq!(options.min_history, DEFAULT_MIN_HISTORY);
+        assert_eq!(options.max_daily_return, DEFAULT_MAX_DAILY_RETURN);
+        assert_eq!(options.max_cash_flow, DEFAULT_MAX_CASH_FLOW);
+        assert_eq!(options.constraints, None);
+        assert_eq!(options.clustering_seed, DEFAULT_CLUSTERING_SEED);
+    }
+
+    #[test]
+    fn test_raised_outlier_thresholds_accept_large_moves() {
+        // A volatile small cap that tripled in one day, held in a large portfolio
+        let daily_returns = vec![0.02, -0.01, 2.0, 0.01, 0.02, -0.01, 0.03, 0.01];
+        let cash_flows = vec![
+            2_000_000.0, 1_980_000.0, 5_940_000.0, 6_000_000.0, 6_120_000.0, 6_060_000.0,
+            6_240_000.0, 6_300_000.0,
+        ];
+        let market_indices = vec![
+            1000.0, 1010.0, 1005.0, 1015.0, 1020.0, 1030.0, 1025.0, 1035.0,
+        ];
+        let fund_characteristics = vec![0.8, 0.9, 0.85, 0.95, 0.88, 0.92, 0.87, 0.93];
+        let allocate = |options: &AllocationOptions| {
+            calculate_optimal_allocation_with_options(
+                &daily_returns,
+                &cash_flows,
+                &market_indices,
+                &fund_characteristics,
+                5,
+                options,
+            )
+        };
+
+        // The default thresholds reject the daily return, then the cash flows
+        assert_eq!(allocate(&AllocationOptions::default()), Err(AllocationError::OutlierData));
+        let returns_raised =
+            AllocationOptions { max_daily_return: 2.5, ..AllocationOptions::default() };
+        assert_eq!(allocate(&returns_raised), Err(AllocationError::OutlierData));
+
+        let options = AllocationOptions { max_cash_flow: 10_000_000.0, ..returns_raised };
+        let result = allocate(&options);
+        assert!(result.is_ok(), "Expected Ok, got Err: {:?}", result);
+        let total_allocation: f64 = result.unwrap().iter().sum();
+        assert!((total_allocation - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_objective_default_is_ml_heuristic() {
+        assert_eq!(Objective::default(), Objective::MlHeuristic);
+    }
+
+    #[test]
+    fn test_min_variance_has_lower_variance_than_max_return() {
+        let daily_returns = vec![0.02, -0.01, 0.03, 0.01, 0.02, -0.01, 0.03, 0.01];
+        let cash_flows = vec![100.0, 50.0, 75.0, 120.0, 110.0, 60.0, 80.0, 130.0];
+        let market_indices = vec![
+            1000.0, 1010.0, 1005.0, 1015.0, 1020.0, 1030.0, 1025.0, 1035.0,
+        ];
+        let fund_characteristics = vec![0.8, 0.9, 0.85, 0.95, 0.88, 0.92, 0.87, 0.93];
+        let num_days = 5;
+
+        let allocate = |objective| {
+            calculate_optimal_allocation_with_options(
+                &daily_returns,
+                &cash_flows,
+                &market_indices,
+                &fund_characteristics,
+                num_days,
+                &AllocationOptions { objective, ..AllocationOptions::default() },
+            )
+            .unwrap()
+        };
+        let min_variance = allocate(Objective::MinVariance);
+        let max_return = allocate(Objective::MaxReturn);
+        let max_sharpe = allocate(Objective::MaxSharpe);
+
+        for allocations in [&min_variance, &max_return, &max_sharpe] {
+            assert_eq!(allocations.len(), num_days);
+            assert!(allocations.iter().all(|&w| w >= 0.0));
+            let total_allocation: f64 = allocations.iter().sum();
+            assert!((total_allocation - 1.0).abs() < 1e-6);
+        }
+
+        let min_variance_risk = allocation_variance(&min_variance, &daily_returns);
+        assert!(min_variance_risk < allocation_variance(&max_return, &daily_returns));
+        assert!(min_variance_risk <= allocation_variance(&max_sharpe, &daily_returns));
+    }
+
+    #[test]
+    fn test_objective_validates_input() {
+        let options = AllocationOptions { objective: Objective::MinVariance, ..Default::default() };
+        let result = calculate_optimal_allocation_with_options(&[], &[], &[], &[], 5, &options);
+        assert_eq!(result.unwrap_err(), AllocationError::EmptyInput);
+    }
+
+    #[test]
+    fn test_forecast_intervals_contain_point_forecast() {
+        let data = vec![0.02, -0.01, 0.03, 0.01, 0.02, -0.01, 0.03, 0.01];
+        let forecast =
+            forecast_time_series_with_intervals(&data, 5, DEFAULT_FORECAST_CONFIDENCE).unwrap();
+        assert_eq!(forecast.len(), 5);
+        for (point, lower, upper) in forecast {
+            assert!(lower <= point && point <= upper);
+        }
+    }
+
+    #[test]
+    fn test_higher_confidence_yields_wider_forecast_intervals() {
+        let data = vec![0.02, -0.01, 0.03, 0.01, 0.02, -0.01, 0.03, 0.01];
+        let narrow = forecast_time_series_with_intervals(&data, 5, 0.80).unwrap();
+        let default =
+            forecast_time_series_with_intervals(&data, 5, DEFAULT_FORECAST_CONFIDENCE).unwrap();
+        let wide = forecast_time_series_with_intervals(&data, 5, 0.99).unwrap();
+        for ((narrow, default), wide) in narrow.iter().zip(&default).zip(&wide) {
+            assert_eq!(narrow.0, wide.0);
+            assert!(narrow.2 - narrow.1 < default.2 - default.1);
+            assert!(default.2 - default.1 < wide.2 - wide.1);
+        }
+    }
+
+    #[test]
+    fn test_forecast_intervals_reject_invalid_confidence() {
+        let data = vec![0.02, -0.01, 0.03, 0.01, 0.02, -0.01, 0.03, 0.01];
+        for confidence in [0.0, 1.0, -0.5, 1.5, f64::NAN] {
+            assert!(
+                forecast_time_series_with_intervals(&data, 5, confidence).is_err(),
+                "{}",
+                confidence
+            );
+        }
+    }
+
+    #[test]
+    fn test_wider_forecast_intervals_yield_larger_uncertainty() {
+        let calm_returns = vec![0.002, -0.001, 0.003, 0.001, 0.002, -0.001, 0.003, 0.001];
+        let volatile_returns = calm_returns.iter().map(|r| r * 50.0).collect::<Vec<f64>>();
+        let cash_flows = vec![100.0, 50.0, 75.0, 120.0, 110.0, 60.0, 80.0, 130.0];
+        let market_indices = vec![
+            1000.0, 1010.0, 1005.0, 1015.0, 1020.0, 1030.0, 1025.0, 1035.0,
+        ];
+        let fund_characteristics = vec![0.8, 0.9, 0.85, 0.95, 0.88, 0.92, 0.87, 0.93];
+        let num_days = 5;
+
+        let allocate = |daily_returns: &[f64]| {
+            calculate_optimal_allocation_with_report(
+                daily_returns,
+                &cash_flows,
+                &market_indices,
+                &fund_characteristics,
+                num_days,
+                &AllocationOptions::default(),
+            )
+            .unwrap()
+        };
+        let calm = allocate(&calm_returns);
+        let volatile = allocate(&volatile_returns);
+
+        assert_eq!(calm.uncertainties.len(), num_days);
+        assert_eq!(volatile.uncertainties.len(), num_days);
+        for (calm_uncertainty, volatile_uncertainty) in
+            calm.uncertainties.iter().zip(&volatile.uncertainties)
+        {
+            assert!(*calm_uncertainty > 0.0);
+            assert!(volatile_uncertainty > calm_uncertainty);
+        }
+
+        let total_allocation: f64 = volatile.allocations.iter().sum();
+        assert!((total_allocation - 1.0).abs() < 1e-6);
+    }
+
+    fn allocate_points(num_points: usize) -> Result<Vec<f64>, AllocationError> {
+        // A one-day horizon, so that only the forecasting and clustering minimums apply.
+        allocate_points_for_horizon(num_points, 1)
+    }
+
+    fn allocate_points_for_horizon(
+        num_points: usize,
+        num_days: usize,
+    ) -> Result<Vec<f64>, AllocationError> {
+        let daily_returns = vec![0.01; num_points];
+        let cash_flows = vec![100.0; num_points];
+        let market_indices = vec![1000.0; num_points];
+        let fund_characteristics = vec![0.8; num_points];
+        calculate_optimal_allocation(
+            &daily_returns,
+            &cash_flows,
+            &market_indices,
+            &fund_characteristics,
+            num_days,
+        )
+    }
+
+    #[test]
+    fn test_insufficient_data_with_zero_points() {
+        assert_eq!(allocate_points(0).unwrap_err(), AllocationError::EmptyInput);
+    }
+
+    #[test]
+    fn test_insufficient_data_with_one_point() {
+        let required = MIN_FORECAST_POINTS.max(NUM_CLUSTERS);
+        assert_eq!(
+            allocate_points(1).unwrap_err(),
+            AllocationError::InsufficientData {
+                required,
+                provided: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_insufficient_data_with_minimum_points() {
+        let required = MIN_FORECAST_POINTS.max(NUM_CLUSTERS);
+        assert_eq!(required, 2);
+        assert!(!matches!(
+            allocate_points(required),
+            Err(AllocationError::InsufficientData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_insufficient_history_for_horizon() {
+        // Five points cannot support a 90-day horizon.
+        assert_eq!(
+            allocate_points_for_horizon(5, 90).unwrap_err(),
+            AllocationError::InsufficientData {
+                required: DEFAULT_MIN_HISTORY,
+                provided: 5
+            }
+        );
+
+        // A history shorter than the minimum must cover the horizon.
+        assert_eq!(
+            allocate_points_for_horizon(10, 12).unwrap_err(),
+            AllocationError::InsufficientData {
+                required: 12,
+                provided: 10
+            }
+        );
+        assert!(allocate_points_for_horizon(12, 12).is_ok());
+
+        // The minimum history is sufficient for any horizon.
+        assert!(allocate_points_for_horizon(DEFAULT_MIN_HISTORY, 90).is_ok());
+    }
+
+    #[test]
+    fn test_custom_min_history() {
+        let points = vec![0.01; 5];
+        let cash_flows = vec![100.0; 5];
+        let allocate = |min_history| {
+            calculate_optimal_allocation_with_options(
+                &points,
+                &cash_flows,
+                &points,
+                &points,
+                90,
+                &AllocationOptions { min_history, ..AllocationOptions::default() },
+            )
+        };
+
+        assert_eq!(
+            allocate(10).unwrap_err(),
+            AllocationError::InsufficientData {
+                required: 10,
+                provided: 5
+            }
+        );
+        let allocations = allocate(5).unwrap();
+        assert_eq!(allocations.len(), 90);
+    }
+
+    #[test]
+    fn test_insufficient_data_error_message() {
+        let err = AllocationError::InsufficientData {
+            required: 2,
+            provided: 1,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Insufficient data: at least 2 values are required, but 1 were provided"
+        );
+    }
+
+    #[test]
+    fn test_input_mismatch_error_message_names_the_lengths() {
+        let daily_returns = vec![0.02, -0.01, 0.03, 0.01];
+        let cash_flows = vec![100.0, 50.0, 75.0, 120.0];
+        let market_indices = vec![1000.0, 1010.0, 1005.0, 1015.0];
+        let fund_characteristics = vec![0.8, 0.9];
+        let err = calculate_optimal_allocation(
+            &daily_returns,
+            &cash_flows,
+            &market_indices,
+            &fund_characteristics,
+            2,
+        )
+        .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("daily_returns has 4 values"), "{}", message);
+        assert!(message.contains("fund_characteristics has 2"), "{}", message);
+    }
+
+    #[test]
+    fn test_allocation_error_messages_are_actionable() {
+        for err in [
+            AllocationError::EmptyInput,
+            AllocationError::InvalidData,
+            AllocationError::OutlierData,
+            AllocationError::ZeroTotalAllocation,
+        ] {
+            let message = err.to_string();
+            let (problem, advice) = message.split_once(": ").expect("a problem and an advice");
+            assert!(!problem.is_empty() && !advice.is_empty(), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_default_weights_match_plain_product() {
+        let weights = AllocationWeights::default();
+        assert_eq!(weights.blending, Blending::LogLinear);
+        let blended = weights.blend(0.02, 150.0, 0.4, 0.5, 1.0);
+        assert!((blended - 0.02 * 150.0 * 0.4 * 0.5 * 2.0).abs() < 1e-12);
+        let blended = weights.blend(-0.01, 150.0, 0.4, 0.5, 0.0);
+        assert!((blended - -0.01 * 150.0 * 0.4 * 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zero_sentiment_weight_removes_sentiment() {
+        for blending in [Blending::LogLinear, Blending::Additive] {
+            let weights = AllocationWeights {
+                sentiment_w: 0.0,
+                blending,
+                ..AllocationWeights::default()
+            };
+            let low_sentiment = weights.blend(0.02, 150.0, 0.01, 0.5, 1.0);
+            let high_sentiment = weights.blend(0.02, 150.0, 0.99, 0.5, 1.0);
+            assert_eq!(low_sentiment, high_sentiment);
+
+            let weights = AllocationWeights {
+                blending,
+                ..AllocationWeights::default()
+            };
+            assert_ne!(
+                weights.blend(0.02, 150.0, 0.01, 0.5, 1.0),
+                weights.blend(0.02, 150.0, 0.99, 0.5, 1.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_zero_weights_remove_random_components() {
+        let daily_returns = vec![0.02, -0.01, 0.03, 0.01, 0.02, -0.01, 0.03, 0.01];
+        let cash_flows = vec![100.0, 50.0, 75.0, 120.0, 110.0, 60.0, 80.0, 130.0];
+        let market_indices = vec![
+            1000.0, 1010.0, 1005.0, 1015.0, 1020.0, 1030.0, 1025.0, 1035.0,
+        ];
+        let fund_characteristics = vec![0.8, 0.9, 0.85, 0.95, 0.88, 0.92, 0.87, 0.93];
+        let weights = AllocationWeights {
+            sentiment_w: 0.0,
+            action_w: 0.0,
+            cluster_w: 0.0,
+            ..AllocationWeights::default()
+        };
+
+        let allocate = || {
+            calculate_optimal_allocation_with_options(
+                &daily_returns,
+                &cash_flows,
+                &market_indices,
+                &fund_characteristics,
+                5,
+                &AllocationOptions { weights, ..AllocationOptions::default() },
+            )
+            .unwrap()
+        };
+        let first = allocate();
+        let second = allocate();
+
+        // Without the random sentiment scores, actions and clusters, the allocation only
+        // depends on the forecasts and is therefore reproducible.
+        assert_eq!(first, second);
+        let total_allocation: f64 = first.iter().sum();
+        assert!((total_allocation - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_constant_series_falls_back_to_historical_mean() {
+        // AutoETS cannot fit a constant series, so the forecasts fall back to the mean.
+        let daily_returns = vec![0.01; 8];
+        let cash_flows = vec![100.0; 8];
+        let market_indices = vec![1000.0; 8];
+        let fund_characteristics = vec![0.8; 8];
+        let num_days = 5;
+
+        let result = calculate_optimal_allocation(
+            &daily_returns,
+            &cash_flows,
+            &market_indices,
+            &fund_characteristics,
+            num_days,
+        );
+        assert!(result.is_ok(), "Expected Ok, got Err: {:?}", result);
+        let allocations = result.unwrap();
+        assert_eq!(allocations.len(), num_days);
+        assert!(allocations.iter().all(|a| a.is_finite() && *a >= 0.0));
+        let total_allocation: f64 = allocations.iter().sum();
+        assert!((total_allocation - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_clustering_failure_is_disclosed() {
+        // Fewer rows than clusters, so K-means cannot be fitted.
+        let features = Array2::from_shape_vec((NUM_CLUSTERS - 1, 4), vec![0.5; 4]).unwrap();
+        assert!(matches!(
+            perform_clustering(&features),
+            Err(AllocationError::ClusteringError(_))
+        ));
+
+        let num_days = 5;
+        let (clusters, clustering_applied) =
+            assign_clusters(&features, num_days, DEFAULT_CLUSTERING_SEED);
+        assert!(!clustering_applied);
+        assert_eq!(clusters, vec![0; num_days]);
+    }
+
+    #[test]
+    fn test_clustering_with_the_same_seed_is_reproducible() {
+        let features = Array2::from_shape_fn((40, 4), |(row, col)| {
+            ((row * 7 + col * 3) % 11) as f64 / 11.0 + if row % 2 == 0 { 0.0 } else { 0.05 }
+        });
+
+        for seed in [DEFAULT_CLUSTERING_SEED, 7, 1234] {
+            let first = perform_clustering_with_seed(&features, seed).unwrap();
+            let second = perform_clustering_with_seed(&features, seed).unwrap();
+            assert_eq!(first.len(), 40);
+            assert_eq!(first, second);
+        }
+        assert_eq!(
+            perform_clustering(&features).unwrap(),
+            perform_clustering_with_seed(&features, DEFAULT_CLUSTERING_SEED).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_report_discloses_whether_clustering_was_applied() {
+        let daily_returns = vec![0.02, -0.01, 0.03, 0.01, 0.02, -0.01, 0.03, 0.01];
+        let cash_flows = vec![100.0, 50.0, 75.0, 120.0, 110.0, 60.0, 80.0, 130.0];
+        let market_indices = vec![
+            1000.0, 1010.0, 1005.0, 1015.0, 1020.0, 1030.0, 1025.0, 1035.0,
+        ];
+        let fund_characteristics = vec![0.8, 0.9, 0.85, 0.95, 0.88, 0.92, 0.87, 0.93];
+        let num_days = 5;
+        let report = |objective| {
+            calculate_optimal_allocation_with_report(
+                &daily_returns,
+                &cash_flows,
+                &market_indices,
+                &fund_characteristics,
+                num_days,
+                &AllocationOptions {
+                    objective,
+                    ..AllocationOptions::default()
+                },
+            )
+            .unwrap()
+        };
+
+        let heuristic = report(Objective::MlHeuristic);
+        assert!(heuristic.clustering_applied);
+        assert_eq!(heuristic.allocations.len(), num_days);
+        assert_eq!(heuristic.uncertainties.len(), num_days);
+
+        // The other objectives do not use the cluster factor.
+        assert!(!report(Objective::MinVariance).clustering_applied);
+    }
+
+    #[test]
+    fn test_constraints_cap_allocations() {
+        let daily_returns = vec![0.02, -0.01, 0.03, 0.01, 0.02, -0.01, 0.03, 0.01];
+        let cash_flows = vec![100.0, 50.0, 75.0, 120.0, 110.0, 60.0, 80.0, 130.0];
+        let market_indices = vec![
+            1000.0, 1010.0, 1005.0, 1015.0, 1020.0, 1030.0, 1025.0, 1035.0,
+        ];
+        let fund_characteristics = vec![0.8, 0.9, 0.85, 0.95, 0.88, 0.92, 0.87, 0.93];
+        let allocate = |num_days, objective| {
+            calculate_optimal_allocation_with_options(
+                &daily_returns,
+                &cash_flows,
+                &market_indices,
+                &fund_characteristics,
+                num_days,
+                &AllocationOptions {
+                    objective,
+                    constraints: Some(Constraints { max_weight: 0.4, ..Constraints::default() }),
+                    ..AllocationOptions::default()
+                },
+            )
+        };
+
+        for objective in [Objective::MlHeuristic, Objective::MaxReturn] {
+            let allocations = allocate(5, objective).unwrap();
+            assert_eq!(allocations.len(), 5);
+            assert!(allocations.iter().all(|&w| (0.0..=0.4 + 1e-9).contains(&w)));
+            let total_allocation: f64 = allocations.iter().sum();
+            assert!((total_allocation - 1.0).abs() < 1e-9);
+        }
+
+        // Two days capped at 0.4 cannot hold the whole allocation.
+        assert!(matches!(
+            allocate(2, Objective::MlHeuristic),
+            Err(AllocationError::InfeasibleConstraints { count: 2, .. })
+        ));
+    }
 }
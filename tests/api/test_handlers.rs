@@ -1,24 +1,27 @@
 #[cfg(test)]
 mod tests {
+    use crate::ENV_MUTEX;
     use actix_web::{test, web, App, HttpResponse, Responder};
-    use lazy_static::lazy_static;
+    use chrono::{Datelike, NaiveDate, Weekday};
     use nalufx::{
+        api::handlers::{health, metrics, predict_cash_flow, ready},
+        config::READY_REQUIRED_ENV_ENV,
         llms::openai::{get_openai_api_key, parse_openai_response, send_openai_request},
-        models::cash_flow_dm::{CashFlowRequest, CashFlowResponse, ErrorResponse},
+        models::{
+            cash_flow_dm::{CashFlowRequest, CashFlowResponse, ErrorResponse},
+            health_dm::HealthResponse,
+        },
+        services::processing_svc::{calculate_cash_flows, calculate_daily_returns},
+        utils::calculations::forecast_time_series,
     };
     use reqwest::Client;
     use serde_json::json;
     use std::env;
-    use std::sync::Mutex;
     use wiremock::{
         matchers::{header, method, path},
         Mock, MockServer, ResponseTemplate,
     };
 
-    lazy_static! {
-        static ref ENV_MUTEX: Mutex<()> = Mutex::new(());
-    }
-
     // Mock predict_cash_flow handler
     async fn mock_predict_cash_flow(
         data: web::Json<CashFlowRequest>,
@@ -37,6 +40,7 @@ async fn mock_predict_cash_flow(
         HttpResponse::Ok().json(CashFlowResponse {
             predictions,
             optimal_allocation,
+            dates: None,
         })
     }
 
@@ -45,6 +49,9 @@ async fn mock_predict_cash_flow(
     async fn test_predict_cash_flow_valid() {
         let request = CashFlowRequest {
             historical_data: vec![1.0, 2.0, 3.0],
+            dates: None,
+            market_indices: None,
+            fund_characteristics: None,
         };
 
         let app = test::init_service(
@@ -72,6 +79,9 @@ async fn test_predict_cash_flow_valid() {
     async fn test_predict_cash_flow_empty_data() {
         let request = CashFlowRequest {
             historical_data: vec![],
+            dates: None,
+            market_indices: None,
+            fund_characteristics: None,
         };
 
         let app = test::init_service(
@@ -98,6 +108,9 @@ async fn test_predict_cash_flow_empty_data() {
     async fn test_predict_cash_flow_negative_data() {
         let request = CashFlowRequest {
             historical_data: vec![-1.0, -2.0, -3.0],
+            dates: None,
+            market_indices: None,
+            fund_characteristics: None,
         };
 
         let app = test::init_service(
@@ -125,6 +138,9 @@ async fn test_predict_cash_flow_negative_data() {
     async fn test_predict_cash_flow_zero_data() {
         let request = CashFlowRequest {
             historical_data: vec![0.0, 0.0, 0.0],
+            dates: None,
+            market_indices: None,
+            fund_characteristics: None,
         };
 
         let app = test::init_service(
@@ -223,8 +239,10 @@ async fn test_send_openai_request_http_error() {
 
         let openai_url = format!("{}/v1/chat/completions", mock_server.uri());
         let response = send_openai_request(&client, &openai_url, api_key, request_body).await;
-        assert!(response.is_err());
-        assert_eq!(response, Err("OpenAI API call failed"));
+        assert_eq!(
+            response.unwrap_err().to_string(),
+            "LLM request failed: API call failed with status 500 Internal Server Error"
+        );
 
         // Verify that the mock received the expected request
         mock_server.verify().await;
@@ -261,4 +279,321 @@ async fn test_parse_openai_response_unexpected_structure() {
         let result = parse_openai_response(response_body);
         assert!(result.is_err());
     }
+
+    /// Returns a rising series of 60 prices.
+    fn rising_prices() -> Vec<f64> {
+        (0..60).map(|i| 100.0 + i as f64 + 0.5 * (i as f64).sin()).collect()
+    }
+
+    /// Tests that a rising price series yields the forecast cash flows and a normalized optimal
+    /// allocation.
+    #[actix_rt::test]
+    async fn test_predict_cash_flow_rising_prices() {
+        let app = test::init_service(App::new().service(predict_cash_flow)).await;
+
+        let historical_data = rising_prices();
+        let cash_flows = calculate_cash_flows(&calculate_daily_returns(&historical_data), 1_000.0);
+        let request = CashFlowRequest {
+            historical_data,
+            dates: None,
+            market_indices: None,
+            fund_characteristics: None,
+        };
+
+        let req = test::TestRequest::post()
+            .uri("/predict")
+            .set_json(&request)
+            .to_request();
+        let resp: CashFlowResponse = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(resp.predictions, forecast_time_series(&cash_flows, 6).unwrap());
+        // The sentiment scores and optimal actions are random, so only the normalization of the
+        // allocation is deterministic.
+        assert_eq!(resp.optimal_allocation.len(), 6);
+        let total: f64 = resp.optimal_allocation.iter().sum();
+        assert!((total - 1.0).abs() < 1e-6);
+    }
+
+    /// Tests that the market indices and fund characteristics of a request are accepted when
+    /// there is one value per entry, and rejected with a `400 Bad Request` otherwise.
+    #[actix_rt::test]
+    async fn test_predict_cash_flow_features() {
+        let app = test::init_service(App::new().service(predict_cash_flow)).await;
+
+        let market_indices = (0..60).map(|i| 5_000.0 + 10.0 * i as f64).collect::<Vec<f64>>();
+        let fund_characteristics = (0..60).map(|i| 1.0 + (i % 5) as f64).collect::<Vec<f64>>();
+        let request = |market_indices: Vec<f64>| CashFlowRequest {
+            historical_data: rising_prices(),
+            dates: None,
+            market_indices: Some(market_indices),
+            fund_characteristics: Some(fund_characteristics.clone()),
+        };
+
+        let req = test::TestRequest::post()
+            .uri("/predict")
+            .set_json(&request(market_indices.clone()))
+            .to_request();
+        let resp: CashFlowResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp.optimal_allocation.len(), 6);
+
+        let req = test::TestRequest::post()
+            .uri("/predict")
+            .set_json(&request(market_indices[1..].to_vec()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!(
+            body.error,
+            "Expected one marketIndices value per historical data entry, got 59 values for 60 entries"
+        );
+    }
+
+    /// Tests that empty historical data returns a `400 Bad Request` with an `ErrorResponse`.
+    #[actix_rt::test]
+    async fn test_predict_cash_flow_empty_input() {
+        let app = test::init_service(App::new().service(predict_cash_flow)).await;
+
+        let request = CashFlowRequest {
+            historical_data: vec![],
+            dates: None,
+            market_indices: None,
+            fund_characteristics: None,
+        };
+
+        let req = test::TestRequest::post()
+            .uri("/predict")
+            .set_json(&request)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!(body.error, "Invalid historical data");
+    }
+
+    /// Tests that a single price, which yields no daily returns, returns a `400 Bad Request`.
+    #[actix_rt::test]
+    async fn test_predict_cash_flow_single_price() {
+        let app = test::init_service(App::new().service(predict_cash_flow)).await;
+
+        let request = CashFlowRequest {
+            historical_data: vec![100.0],
+            dates: None,
+            market_indices: None,
+            fund_characteristics: None,
+        };
+
+        let req = test::TestRequest::post()
+            .uri("/predict")
+            .set_json(&request)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+    }
+
+    /// Returns `n` consecutive weekdays from Monday 3 June 2024, as `YYYY-MM-DD` strings.
+    fn weekdays(n: usize) -> Vec<String> {
+        NaiveDate::from_ymd_opt(2024, 6, 3)
+            .unwrap()
+            .iter_days()
+            .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+            .take(n)
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .collect()
+    }
+
+    /// Tests that sorted dates date the response with the trading days after the last one.
+    #[actix_rt::test]
+    async fn test_predict_cash_flow_sorted_dates() {
+        let app = test::init_service(App::new().service(predict_cash_flow)).await;
+
+        let dates = weekdays(60);
+        // The 60 weekdays end on Friday 23 August 2024, so Labor Day is the only holiday ahead.
+        assert_eq!(dates.last().unwrap(), "2024-08-23");
+        let request = CashFlowRequest {
+            historical_data: rising_prices(),
+            dates: Some(dates),
+            market_indices: None,
+            fund_characteristics: None,
+        };
+
+        let req = test::TestRequest::post()
+            .uri("/predict")
+            .set_json(&request)
+            .to_request();
+        let resp: CashFlowResponse = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(
+            resp.dates.unwrap(),
+            vec![
+                "2024-08-26",
+                "2024-08-27",
+                "2024-08-28",
+                "2024-08-29",
+                "2024-08-30",
+                "2024-09-03"
+            ]
+        );
+    }
+
+    /// Tests that unsorted or repeated dates return a `400 Bad Request`.
+    #[actix_rt::test]
+    async fn test_predict_cash_flow_unsorted_dates() {
+        let app = test::init_service(App::new().service(predict_cash_flow)).await;
+
+        let mut unsorted = weekdays(3);
+        unsorted.swap(1, 2);
+        let mut repeated = weekdays(3);
+        repeated[2] = repeated[1].clone();
+
+        for dates in [unsorted, repeated] {
+            let request = CashFlowRequest {
+                historical_data: vec![100.0, 101.0, 102.0],
+                dates: Some(dates),
+                market_indices: None,
+                fund_characteristics: None,
+            };
+            let req = test::TestRequest::post()
+                .uri("/predict")
+                .set_json(&request)
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), 400);
+
+            let body: ErrorResponse = test::read_body_json(resp).await;
+            assert!(body.error.contains("ascending order"), "{}", body.error);
+        }
+    }
+
+    /// Tests that malformed dates, or a date count that does not match the data, return a
+    /// `400 Bad Request`.
+    #[actix_rt::test]
+    async fn test_predict_cash_flow_malformed_dates() {
+        let app = test::init_service(App::new().service(predict_cash_flow)).await;
+
+        let malformed = vec!["2024-06-03".to_string(), "06/04/2024".to_string()];
+        let too_few = vec!["2024-06-03".to_string()];
+
+        for (dates, expected) in [(malformed, "06/04/2024"), (too_few, "got 1 dates for 2")] {
+            let request = CashFlowRequest {
+                historical_data: vec![100.0, 101.0],
+                dates: Some(dates),
+                market_indices: None,
+                fund_characteristics: None,
+            };
+            let req = test::TestRequest::post()
+                .uri("/predict")
+                .set_json(&request)
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), 400);
+
+            let body: ErrorResponse = test::read_body_json(resp).await;
+            assert!(body.error.contains(expected), "{}", body.error);
+        }
+    }
+
+    /// Tests that the health endpoint reports the server as alive.
+    #[actix_rt::test]
+    async fn test_health() {
+        let app = test::init_service(App::new().service(health)).await;
+
+        let req = test::TestRequest::get().uri("/health").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+
+        let body = test::read_body(resp).await;
+        assert_eq!(body, r#"{"status":"ok"}"#);
+    }
+
+    /// Tests that the readiness endpoint returns `200 OK` when no environment variable is
+    /// required, whether or not an LLM key is set.
+    // The lock is held while the request is served, since `ready` reads the environment.
+    #[allow(clippy::await_holding_lock)]
+    #[actix_rt::test]
+    async fn test_ready() {
+        let _guard = ENV_MUTEX.lock().unwrap();
+        env::remove_var(READY_REQUIRED_ENV_ENV);
+        env::remove_var("OPENAI_API_KEY");
+
+        let app = test::init_service(App::new().service(ready)).await;
+        let req = test::TestRequest::get().uri("/ready").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        let body: HealthResponse = test::read_body_json(resp).await;
+        assert_eq!(body, HealthResponse::new("ready"));
+    }
+
+    /// Tests that the readiness endpoint returns `503 Service Unavailable` until a required
+    /// environment variable is set.
+    // The lock is held while the request is served, since `ready` reads the environment.
+    #[allow(clippy::await_holding_lock)]
+    #[actix_rt::test]
+    async fn test_ready_missing_required_env() {
+        let _guard = ENV_MUTEX.lock().unwrap();
+        env::set_var(READY_REQUIRED_ENV_ENV, "OPENAI_API_KEY");
+        env::remove_var("OPENAI_API_KEY");
+
+        let app = test::init_service(App::new().service(ready)).await;
+        let probe = || test::TestRequest::get().uri("/ready").to_request();
+        let unready = test::call_service(&app, probe()).await;
+        env::set_var("OPENAI_API_KEY", "test_api_key");
+        let ready = test::call_service(&app, probe()).await;
+        env::remove_var("OPENAI_API_KEY");
+        env::remove_var(READY_REQUIRED_ENV_ENV);
+
+        assert_eq!(unready.status(), 503);
+        let body: HealthResponse = test::read_body_json(unready).await;
+        assert_eq!(body.status, "unready");
+        assert_eq!(body.missing, vec!["OPENAI_API_KEY".to_string()]);
+        assert_eq!(ready.status(), 200);
+    }
+
+    /// Returns the value of a metric in the Prometheus text output of `/metrics`.
+    fn metric_value(output: &str, name: &str) -> u64 {
+        output
+            .lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(' '))
+            .and_then(|value| value.parse().ok())
+            .unwrap()
+    }
+
+    /// Tests that a successful prediction increments the predictions counter in `/metrics`.
+    #[actix_rt::test]
+    async fn test_metrics_counts_predictions() {
+        let app = test::init_service(App::new().service(predict_cash_flow).service(metrics)).await;
+
+        let scrape = || test::TestRequest::get().uri("/metrics").to_request();
+        let resp = test::call_service(&app, scrape()).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "text/plain; version=0.0.4"
+        );
+        let before = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+
+        let request = CashFlowRequest {
+            historical_data: rising_prices(),
+            dates: None,
+            market_indices: None,
+            fund_characteristics: None,
+        };
+        let req = test::TestRequest::post()
+            .uri("/predict")
+            .set_json(&request)
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+        let after = test::call_and_read_body(&app, scrape()).await;
+        let after = String::from_utf8(after.to_vec()).unwrap();
+
+        // Other tests may record predictions concurrently, so the counter is at least one higher.
+        assert!(
+            metric_value(&after, "nalufx_predictions_total")
+                > metric_value(&before, "nalufx_predictions_total")
+        );
+    }
 }
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests {
+    use actix_web::{test, App};
+    use nalufx::api::openapi::{openapi_json, ApiDoc};
+    use serde_json::Value;
+    use utoipa::OpenApi;
+
+    /// Tests that the generated document is valid JSON with the predict path and its schemas.
+    #[test]
+    fn test_openapi_document_contains_predict_path() {
+        let json = ApiDoc::openapi().to_json().expect("the document should serialize");
+        let document: Value = serde_json::from_str(&json).expect("the document should be JSON");
+
+        let predict = &document["paths"]["/predict"]["post"];
+        assert!(predict.is_object());
+        assert!(predict["responses"]["200"].is_object());
+        assert!(predict["responses"]["400"].is_object());
+        for path in ["/health", "/ready", "/metrics", "/ws/allocations", "/openapi.json"] {
+            assert!(document["paths"][path].is_object(), "missing path {}", path);
+        }
+
+        let schemas = &document["components"]["schemas"];
+        for schema in ["CashFlowRequest", "CashFlowResponse", "ErrorResponse", "HealthResponse"] {
+            assert!(schemas[schema].is_object(), "missing schema {}", schema);
+        }
+        assert!(schemas["CashFlowRequest"]["properties"]["historicalData"].is_object());
+    }
+
+    /// Tests that the document is served at `/openapi.json`.
+    #[actix_rt::test]
+    async fn test_openapi_json_endpoint() {
+        let app = test::init_service(App::new().service(openapi_json)).await;
+        let req = test::TestRequest::get().uri("/openapi.json").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let document: Value = test::read_body_json(resp).await;
+        assert!(document["openapi"].as_str().is_some_and(|version| version.starts_with("3.")));
+        assert!(document["paths"]["/predict"].is_object());
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::header::RETRY_AFTER, test, web, App, HttpResponse};
+    use nalufx::{api::rate_limit::RateLimiter, models::cash_flow_dm::ErrorResponse};
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::time::{Duration, Instant};
+
+    const LIMIT: u32 = 5;
+
+    fn client(last_octet: u8) -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 0, 2, last_octet)), 40000)
+    }
+
+    /// Tests that the request after the first `LIMIT` is rejected with `429` and `Retry-After`.
+    #[actix_rt::test]
+    async fn test_rate_limit_rejects_request_over_limit() {
+        let app = test::init_service(
+            App::new()
+                .wrap(RateLimiter::new(LIMIT))
+                .route("/predict", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        for _ in 0..LIMIT {
+            let req = test::TestRequest::get().uri("/predict").peer_addr(client(1)).to_request();
+            assert_eq!(test::call_service(&app, req).await.status(), 200);
+        }
+
+        let req = test::TestRequest::get().uri("/predict").peer_addr(client(1)).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 429);
+
+        // A token is refilled every 60 / LIMIT = 12 seconds.
+        let retry_after = resp.headers().get(RETRY_AFTER).unwrap().to_str().unwrap();
+        assert_eq!(retry_after, "12");
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!(body.error, "Too many requests");
+    }
+
+    /// Tests that each client IP has its own bucket.
+    #[actix_rt::test]
+    async fn test_rate_limit_is_per_client() {
+        let app = test::init_service(
+            App::new().wrap(RateLimiter::new(1)).route("/predict", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/predict").peer_addr(client(1)).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+        let req = test::TestRequest::get().uri("/predict").peer_addr(client(1)).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 429);
+
+        let req = test::TestRequest::get().uri("/predict").peer_addr(client(2)).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+    }
+
+    /// Tests that the probes are still served once a client has drained its bucket, and do
+    /// not take tokens from it.
+    #[actix_rt::test]
+    async fn test_rate_limit_exempts_probes() {
+        let app = test::init_service(
+            App::new()
+                .wrap(RateLimiter::new(1))
+                .route("/health", web::get().to(HttpResponse::Ok))
+                .route("/ready", web::get().to(HttpResponse::Ok))
+                .route("/predict", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let request =
+            |uri: &str| test::TestRequest::get().uri(uri).peer_addr(client(1)).to_request();
+
+        for _ in 0..3 {
+            assert_eq!(test::call_service(&app, request("/health")).await.status(), 200);
+        }
+        assert_eq!(test::call_service(&app, request("/predict")).await.status(), 200);
+        assert_eq!(test::call_service(&app, request("/predict")).await.status(), 429);
+
+        assert_eq!(test::call_service(&app, request("/health")).await.status(), 200);
+        assert_eq!(test::call_service(&app, request("/ready")).await.status(), 200);
+    }
+
+    /// Tests that the bucket refills at the configured rate.
+    #[actix_rt::test]
+    async fn test_rate_limiter_refills() {
+        let limiter = RateLimiter::new(LIMIT);
+        let ip = Some(client(1).ip());
+        let start = Instant::now();
+
+        for _ in 0..LIMIT {
+            assert!(limiter.check_at(ip, start).is_ok());
+        }
+        assert_eq!(limiter.check_at(ip, start), Err(Duration::from_secs(12)));
+
+        // After 6 seconds, half a token has been refilled.
+        let retry_after = limiter.check_at(ip, start + Duration::from_secs(6)).unwrap_err();
+        assert!((retry_after.as_secs_f64() - 6.0).abs() < 1e-9);
+
+        assert!(limiter.check_at(ip, start + Duration::from_secs(12)).is_ok());
+        assert!(limiter.check_at(ip, start + Duration::from_secs(12)).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use crate::ENV_MUTEX;
+    use actix_web::App;
+    use actix_web_actors::ws;
+    use futures::{SinkExt, Stream, StreamExt};
+    use nalufx::{api::ws::allocations_ws, models::stream_dm::AllocationFrame};
+    use std::{env, time::Duration};
+
+    /// Returns the next text frame sent by the server, skipping pings.
+    async fn next_frame<S>(stream: &mut S) -> AllocationFrame
+    where
+        S: Stream<Item = Result<ws::Frame, ws::ProtocolError>> + Unpin,
+    {
+        let frame = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                match stream.next().await {
+                    Some(Ok(ws::Frame::Text(bytes))) => return bytes,
+                    Some(Ok(ws::Frame::Ping(_))) => continue,
+                    other => panic!("Unexpected WebSocket frame: {:?}", other),
+                }
+            }
+        })
+        .await
+        .expect("No frame received");
+        serde_json::from_slice(&frame).expect("Invalid allocation frame")
+    }
+
+    /// Tests that a subscription is answered with the allocation of its tickers.
+    // The lock is held while the test awaits, since the code under test reads the environment.
+    #[allow(clippy::await_holding_lock)]
+    #[actix_rt::test]
+    async fn test_allocations_ws_pushes_allocation() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        env::set_var("NALUFX_OFFLINE", "1");
+        let srv = actix_test::start(|| App::new().service(allocations_ws));
+        let mut framed = srv.ws_at("/ws/allocations").await.expect("Failed to connect");
+
+        framed
+            .send(ws::Message::Text(r#"{"tickers":["aapl","MSFT","AAPL"]}"#.into()))
+            .await
+            .unwrap();
+        let frame = next_frame(&mut framed).await;
+        env::remove_var("NALUFX_OFFLINE");
+
+        match frame {
+            AllocationFrame::Allocation { tickers, weights } => {
+                assert_eq!(tickers, vec!["AAPL", "MSFT"]);
+                assert_eq!(weights.len(), 2);
+                assert!(weights.iter().all(|&weight| weight > 0.0));
+                assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+            },
+            other => panic!("Expected an allocation frame, got {:?}", other),
+        }
+    }
+
+    /// Tests that an invalid subscription is answered with an error frame.
+    #[actix_rt::test]
+    async fn test_allocations_ws_rejects_invalid_subscription() {
+        let srv = actix_test::start(|| App::new().service(allocations_ws));
+        let mut framed = srv.ws_at("/ws/allocations").await.expect("Failed to connect");
+
+        for subscription in [r#"{"tickers":[]}"#, r#"{"tickers":["AAPL$"]}"#, "AAPL"] {
+            framed.send(ws::Message::Text(subscription.into())).await.unwrap();
+            assert!(
+                matches!(next_frame(&mut framed).await, AllocationFrame::Error { .. }),
+                "{}",
+                subscription
+            );
+        }
+    }
+
+    /// Tests that the server closes the connection when the client does.
+    #[actix_rt::test]
+    async fn test_allocations_ws_handles_close() {
+        let srv = actix_test::start(|| App::new().service(allocations_ws));
+        let mut framed = srv.ws_at("/ws/allocations").await.expect("Failed to connect");
+
+        framed.send(ws::Message::Close(None)).await.unwrap();
+        let frame = tokio::time::timeout(Duration::from_secs(10), framed.next())
+            .await
+            .expect("No close frame received");
+        assert!(matches!(frame, Some(Ok(ws::Frame::Close(None)))));
+    }
+}
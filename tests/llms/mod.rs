@@ -0,0 +1,17 @@
+/// This module contains the tests for `cache.rs`.
+pub mod test_cache;
+
+/// This module contains the tests for the retry policy in `mod.rs`.
+pub mod test_retry;
+
+/// This module contains the tests for `ollama.rs`.
+pub mod test_ollama;
+
+/// This module contains the tests for `errors.rs`.
+pub mod test_errors;
+
+/// This module contains the tests for `chain.rs`.
+pub mod test_chain;
+
+/// This module contains the tests for `factory.rs`.
+pub mod test_factory;
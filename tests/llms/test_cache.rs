@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use nalufx_llms::{
+        errors::LlmError,
+        llms::{
+            cache::{read_cached_response, write_cached_response, CacheKey, LlmCacheConfig},
+            LLM,
+        },
+    };
+    use reqwest::Client;
+    use serde_json::{json, Value};
+    use std::{fs, path::PathBuf, sync::Arc, time::Duration};
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    // An LLM that sends its requests to a mock server.
+    struct MockLLM {
+        url: String,
+    }
+
+    #[async_trait]
+    impl LLM for MockLLM {
+        async fn send_request(
+            &self,
+            client: &Client,
+            api_key: &str,
+            prompt: &str,
+            max_tokens: usize,
+        ) -> Result<Value, LlmError> {
+            Ok(client
+                .post(&self.url)
+                .header("Authorization", format!("Bearer {}", api_key))
+                .json(&json!({ "prompt": prompt, "max_tokens": max_tokens }))
+                .send()
+                .await?
+                .json()
+                .await?)
+        }
+
+        fn provider(&self) -> &str {
+            "mock"
+        }
+
+        fn model(&self) -> &str {
+            "mock-model"
+        }
+    }
+
+    fn temp_cache_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("nalufx_llm_cache_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Tests that a repeated identical request is served from the cache.
+    #[tokio::test]
+    async fn test_send_request_cached_second_call_skips_server() {
+        let mock_server = MockServer::start().await;
+        let response_body = json!({ "choices": [{ "message": { "content": "cached report" } }] });
+
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let llm = MockLLM { url: format!("{}/v1/chat/completions", mock_server.uri()) };
+        let cache_dir = temp_cache_dir("second_call");
+        let cache = LlmCacheConfig::new(&cache_dir, Duration::from_secs(60));
+        let client = Client::new();
+
+        let first =
+            llm.send_request_cached(&client, "key", "Analyze AAPL", 100, &cache).await.unwrap();
+        let second =
+            llm.send_request_cached(&client, "key", "Analyze AAPL", 100, &cache).await.unwrap();
+
+        assert_eq!(first, response_body);
+        assert_eq!(second, response_body);
+
+        // Verifies that the mock server received exactly one request
+        mock_server.verify().await;
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    /// Tests that requests differing in any key field are cached separately.
+    #[tokio::test]
+    async fn test_send_request_cached_distinct_keys() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "ok": true })))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let llm = MockLLM { url: mock_server.uri() };
+        let cache_dir = temp_cache_dir("distinct_keys");
+        let cache = LlmCacheConfig::new(&cache_dir, Duration::from_secs(60));
+        let client = Client::new();
+
+        let _ = llm.send_request_cached(&client, "key", "prompt", 100, &cache).await.unwrap();
+        let _ = llm.send_request_cached(&client, "key", "prompt", 200, &cache).await.unwrap();
+
+        mock_server.verify().await;
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    /// Tests that expired cache entries are not returned.
+    #[test]
+    fn test_read_cached_response_respects_ttl() {
+        let cache_dir = temp_cache_dir("ttl");
+        let key = CacheKey::new("mock", "mock-model", "prompt", 100);
+        let response = json!({ "ok": true });
+
+        let fresh = LlmCacheConfig::new(&cache_dir, Duration::from_secs(60));
+        write_cached_response(&fresh, &key, &response).unwrap();
+        assert_eq!(read_cached_response(&fresh, &key), Some(response));
+
+        // Entries are timestamped in whole seconds, so wait long enough to exceed a zero TTL
+        std::thread::sleep(Duration::from_millis(1100));
+        let expired = LlmCacheConfig::new(&cache_dir, Duration::from_secs(0));
+        assert_eq!(read_cached_response(&expired, &key), None);
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    /// Tests that concurrent writers of the same key leave a valid cache entry behind.
+    #[test]
+    fn test_write_cached_response_concurrent_writers() {
+        let cache_dir = temp_cache_dir("concurrent");
+        let cache = Arc::new(LlmCacheConfig::new(&cache_dir, Duration::from_secs(60)));
+        let key = Arc::new(CacheKey::new("mock", "mock-model", "prompt", 100));
+        let response = json!({ "content": "x".repeat(64 * 1024) });
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let cache = Arc::clone(&cache);
+                let key = Arc::clone(&key);
+                let response = response.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        write_cached_response(&cache, &key, &response).unwrap();
+                        assert_eq!(read_cached_response(&cache, &key), Some(response.clone()));
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // Only the final entry remains, with no leftover temporary files
+        assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 1);
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use nalufx_llms::{
+        errors::LlmError,
+        llms::{chain::LlmChain, check_status, LLM},
+    };
+    use reqwest::Client;
+    use serde_json::{json, Value};
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    // An LLM that sends its requests to a mock server.
+    struct MockLLM {
+        name: &'static str,
+        url: String,
+    }
+
+    #[async_trait]
+    impl LLM for MockLLM {
+        async fn send_request(
+            &self,
+            client: &Client,
+            api_key: &str,
+            prompt: &str,
+            _max_tokens: usize,
+        ) -> Result<Value, LlmError> {
+            let response = client
+                .post(&self.url)
+                .bearer_auth(api_key)
+                .json(&json!({ "prompt": prompt }))
+                .send()
+                .await?;
+            Ok(check_status(response)?.json().await?)
+        }
+
+        fn provider(&self) -> &str {
+            self.name
+        }
+
+        fn model(&self) -> &str {
+            "mock-model"
+        }
+    }
+
+    /// Mounts a mock provider at `route` that responds with `response` to requests with the
+    /// API key `<name>-key`, and returns it with that key.
+    async fn mock_provider(
+        server: &MockServer,
+        name: &'static str,
+        route: &str,
+        response: ResponseTemplate,
+        expected_calls: u64,
+    ) -> (Box<dyn LLM>, String) {
+        Mock::given(method("POST"))
+            .and(path(route))
+            .and(header("Authorization", format!("Bearer {}-key", name).as_str()))
+            .respond_with(response)
+            .expect(expected_calls)
+            .mount(server)
+            .await;
+        (
+            Box::new(MockLLM { name, url: format!("{}{}", server.uri(), route) }),
+            format!("{}-key", name),
+        )
+    }
+
+    /// Tests that the chain fails over to the second provider when the first returns a 500.
+    #[tokio::test]
+    async fn test_chain_fails_over_on_server_error() {
+        let server = MockServer::start().await;
+        let failing =
+            mock_provider(&server, "first", "/first", ResponseTemplate::new(500), 1).await;
+        let answer = json!({ "choices": [{ "message": { "content": "Buy AAPL" } }] });
+        let serving = mock_provider(
+            &server,
+            "second",
+            "/second",
+            ResponseTemplate::new(200).set_body_json(answer.clone()),
+            1,
+        )
+        .await;
+
+        let chain = LlmChain::new(vec![failing, serving]);
+        let response = chain.send_request(&Client::new(), "", "Analyze AAPL", 100).await;
+
+        assert_eq!(response.unwrap(), answer);
+    }
+
+    /// Tests that the chain fails over to the second provider, with its own API key, when the
+    /// first rejects its API key.
+    #[tokio::test]
+    async fn test_chain_fails_over_on_auth_failure() {
+        let server = MockServer::start().await;
+        let rejected =
+            mock_provider(&server, "first", "/first", ResponseTemplate::new(401), 1).await;
+        let answer = json!({ "choices": [{ "message": { "content": "Buy AAPL" } }] });
+        let serving = mock_provider(
+            &server,
+            "second",
+            "/second",
+            ResponseTemplate::new(200).set_body_json(answer.clone()),
+            1,
+        )
+        .await;
+
+        let chain = LlmChain::new(vec![rejected, serving]);
+        let response = chain.send_request(&Client::new(), "unused", "Analyze AAPL", 100).await;
+
+        assert_eq!(response.unwrap(), answer);
+    }
+
+    /// Tests that the last error is returned when every provider fails.
+    #[tokio::test]
+    async fn test_chain_returns_last_error_when_all_fail() {
+        let server = MockServer::start().await;
+        let first = mock_provider(&server, "first", "/first", ResponseTemplate::new(503), 1).await;
+        let second =
+            mock_provider(&server, "second", "/second", ResponseTemplate::new(429), 1).await;
+
+        let chain = LlmChain::new(vec![first, second]);
+        let response = chain.send_request(&Client::new(), "", "Analyze AAPL", 100).await;
+
+        assert_eq!(response, Err(LlmError::RateLimited { retry_after: None }));
+        assert!(matches!(
+            LlmChain::new(Vec::new()).send_request(&Client::new(), "", "", 100).await,
+            Err(LlmError::Config(_))
+        ));
+    }
+}
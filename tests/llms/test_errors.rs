@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::NaluFxError;
+    use nalufx_llms::{
+        errors::LlmError,
+        llms::{
+            check_status, ollama::Ollama, openai::send_openai_request, send_with_retry,
+            RetryPolicy, LLM,
+        },
+    };
+    use reqwest::Client;
+    use serde_json::json;
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    /// Tests that a 429 response becomes `RateLimited`, with the delay of its `Retry-After`.
+    #[tokio::test]
+    async fn test_send_openai_request_rate_limited() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .mount(&mock_server)
+            .await;
+
+        let url = format!("{}/v1/chat/completions", mock_server.uri());
+        let result = send_openai_request(&Client::new(), &url, "test_api_key", json!({})).await;
+
+        let error = result.unwrap_err();
+        assert_eq!(error, LlmError::RateLimited { retry_after: Some(Duration::ZERO) });
+        assert!(error.is_retryable());
+    }
+
+    /// Tests that a 401 response becomes `AuthFailed` without being retried.
+    #[tokio::test]
+    async fn test_send_openai_request_auth_failed() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = format!("{}/v1/chat/completions", mock_server.uri());
+        let result = send_openai_request(&Client::new(), &url, "bad_api_key", json!({})).await;
+
+        let error = result.unwrap_err();
+        assert_eq!(error, LlmError::AuthFailed);
+        assert!(!error.is_retryable());
+    }
+
+    /// Tests that a 500 response becomes `Other`, with the status in its message.
+    #[tokio::test]
+    async fn test_check_status_server_error() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let request = Client::new().post(mock_server.uri()).json(&json!({}));
+        let response =
+            send_with_retry(request, &RetryPolicy::new(0, Duration::from_millis(1))).await.unwrap();
+
+        let error = check_status(response).unwrap_err();
+        assert_eq!(
+            error,
+            LlmError::Other("API call failed with status 500 Internal Server Error".to_string())
+        );
+        assert!(!error.is_retryable());
+    }
+
+    /// Tests that `LLM` implementations return the same errors as the request functions.
+    #[tokio::test]
+    async fn test_ollama_send_request_auth_failed() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(403))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let ollama = Ollama::new(&mock_server.uri(), "llama3");
+        let result = ollama.send_request(&Client::new(), "bad_api_key", "Analyze AAPL", 100).await;
+
+        assert_eq!(result.unwrap_err(), LlmError::AuthFailed);
+    }
+
+    /// Tests that the error of an LLM request converts into `NaluFxError::LlmError` with `?`.
+    #[tokio::test]
+    async fn test_llm_request_error_converts_into_llm_error() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .mount(&mock_server)
+            .await;
+
+        async fn request(url: &str) -> Result<String, NaluFxError> {
+            Ok(send_openai_request(&Client::new(), url, "test_api_key", json!({})).await?)
+        }
+        let error = request(&mock_server.uri()).await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            NaluFxError::LlmError(LlmError::RateLimited { retry_after: Some(Duration::ZERO) })
+        ));
+    }
+
+    /// Tests that the message of an LLM helper converts into `NaluFxError::LlmError`.
+    #[test]
+    fn test_str_converts_into_llm_error() {
+        let error = NaluFxError::from("OPENAI_API_KEY not found");
+
+        assert!(matches!(
+            &error,
+            NaluFxError::LlmError(LlmError::Config(message)) if message == "OPENAI_API_KEY not found"
+        ));
+        assert_eq!(error.to_string(), "LLM configuration error: OPENAI_API_KEY not found");
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    use nalufx_llms::{
+        errors::LlmError,
+        llms::factory::{llm_api_key, llm_from_name, LLM_NAMES},
+    };
+
+    /// Tests that every supported name constructs the provider of that name.
+    #[test]
+    fn test_llm_from_name_valid_names() {
+        for name in LLM_NAMES {
+            assert_eq!(llm_from_name(name).unwrap().provider(), name);
+        }
+        assert_eq!(llm_from_name(" OpenAI ").unwrap().model(), "gpt-3.5-turbo");
+        assert_eq!(llm_from_name("OLLAMA").unwrap().provider(), "ollama");
+    }
+
+    /// Tests that an unsupported or empty name is a configuration error.
+    #[test]
+    fn test_llm_from_name_invalid_names() {
+        for name in ["gpt", "", "open ai"] {
+            assert!(
+                matches!(llm_from_name(name), Err(LlmError::Config(_))),
+                "{:?} should be rejected",
+                name
+            );
+        }
+    }
+
+    /// Tests that Ollama is sent an empty API key.
+    #[test]
+    fn test_llm_api_key_ollama() {
+        let llm = llm_from_name("ollama").unwrap();
+        assert_eq!(llm_api_key(llm.as_ref()).unwrap(), "");
+    }
+}
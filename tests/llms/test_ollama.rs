@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use nalufx_llms::llms::{
+        ollama::{parse_ollama_stream, Ollama},
+        LLM,
+    };
+    use reqwest::Client;
+    use serde_json::json;
+    use wiremock::{
+        matchers::{body_partial_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    const STREAM: &str = include_str!("../fixtures/ollama_generate_stream.ndjson");
+
+    /// Tests that the fragments of an Ollama stream are concatenated in order.
+    #[test]
+    fn test_parse_ollama_stream() {
+        assert_eq!(parse_ollama_stream(STREAM).unwrap(), "AAPL looks attractive.\nHold cash.");
+    }
+
+    /// Tests that blank lines and lines after the final one are ignored.
+    #[test]
+    fn test_parse_ollama_stream_ignores_blank_and_trailing_lines() {
+        let body = "{\"response\":\"Hold\",\"done\":false}\n\n\
+                    {\"response\":\" cash.\",\"done\":true}\n\
+                    {\"response\":\" Ignored.\",\"done\":false}\n";
+        assert_eq!(parse_ollama_stream(body).unwrap(), "Hold cash.");
+        assert_eq!(parse_ollama_stream("").unwrap(), "");
+    }
+
+    /// Tests that invalid lines and errors reported by Ollama are rejected.
+    #[test]
+    fn test_parse_ollama_stream_errors() {
+        assert!(parse_ollama_stream("{\"response\":\"Hold\"}\nnot json\n").is_err());
+        assert!(parse_ollama_stream("{\"error\":\"model 'llama3' not found\"}\n").is_err());
+    }
+
+    /// Tests that the host is normalized like Ollama's own `OLLAMA_HOST`.
+    #[test]
+    fn test_ollama_host() {
+        assert_eq!(Ollama::new("localhost:11434", "llama3").host, "http://localhost:11434");
+        assert_eq!(
+            Ollama::new("https://ollama.example.com/", "llama3").generate_url(),
+            "https://ollama.example.com/api/generate"
+        );
+        assert_eq!(Ollama::default().generate_url(), "http://localhost:11434/api/generate");
+    }
+
+    /// Tests that `send_request` collects the streamed response into a chat completion.
+    #[tokio::test]
+    async fn test_ollama_send_request_streams_response() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .and(body_partial_json(json!({ "model": "llama3", "stream": true })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(STREAM, "application/x-ndjson"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let ollama = Ollama::new(&mock_server.uri(), "llama3");
+        let response = ollama.send_request(&Client::new(), "", "Analyze AAPL", 100).await.unwrap();
+
+        assert_eq!(
+            response["choices"][0]["message"]["content"],
+            "AAPL looks attractive.\nHold cash."
+        );
+        assert_eq!(ollama.provider(), "ollama");
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use nalufx_llms::llms::{send_with_retry, RetryPolicy};
+    use reqwest::{Client, StatusCode};
+    use serde_json::json;
+    use std::time::{Duration, Instant};
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    /// Tests that a 429 response is retried after the delay in its `Retry-After` header.
+    #[tokio::test]
+    async fn test_send_with_retry_honors_retry_after() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "ok": true })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = Client::new();
+        let request = client
+            .post(format!("{}/v1/chat/completions", mock_server.uri()))
+            .json(&json!({ "prompt": "Analyze AAPL" }));
+        let started = Instant::now();
+        let response = send_with_retry(request, &RetryPolicy::new(3, Duration::from_millis(10)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    /// Tests that a server error is retried until the retries are exhausted.
+    #[tokio::test]
+    async fn test_send_with_retry_returns_last_response_when_exhausted() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let request = Client::new().post(mock_server.uri()).json(&json!({}));
+        let response =
+            send_with_retry(request, &RetryPolicy::new(2, Duration::from_millis(1))).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    /// Tests that a client error other than 429 is returned without retrying.
+    #[tokio::test]
+    async fn test_send_with_retry_does_not_retry_client_errors() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let request = Client::new().post(mock_server.uri()).json(&json!({}));
+        let response =
+            send_with_retry(request, &RetryPolicy::new(3, Duration::from_millis(1))).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    /// Tests that the backoff doubles on every retry and is capped at the maximum delay.
+    #[test]
+    fn test_retry_policy_delay() {
+        let policy = RetryPolicy {
+            max_delay: Duration::from_secs(1),
+            ..RetryPolicy::new(10, Duration::from_millis(100))
+        };
+
+        assert_eq!(policy.delay(0, None), Duration::from_millis(100));
+        assert_eq!(policy.delay(2, None), Duration::from_millis(400));
+        assert_eq!(policy.delay(5, None), Duration::from_secs(1));
+        assert_eq!(policy.delay(40, None), Duration::from_secs(1));
+        assert_eq!(policy.delay(0, Some(Duration::from_secs(5))), Duration::from_secs(1));
+    }
+}
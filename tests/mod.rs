@@ -1,8 +1,33 @@
+use lazy_static::lazy_static;
+use std::sync::Mutex;
+
+lazy_static! {
+    /// Serializes the tests that set or remove environment variables. The tests run in
+    /// parallel in one process, so every test that changes a variable, or depends on one that
+    /// another test changes, must hold this lock while it does.
+    pub static ref ENV_MUTEX: Mutex<()> = Mutex::new(());
+}
+
 /// This module contains the tests for the `api` module.
 pub mod api;
 
+/// This module contains the tests for the `llms` module.
+pub mod llms;
+
 /// This module contains the tests for the `ascii` module.
 pub mod macros;
 
+/// This module contains the tests for the `models` module.
+pub mod models;
+
+/// This module contains the tests for the `prompts` module.
+pub mod prompts;
+
+/// This module contains the tests for the `services` module.
+pub mod services;
+
 /// This module contains the tests for the `utils` module.
 pub mod utils;
+
+/// This module contains the tests for the `ui` module.
+pub mod ui;
@@ -0,0 +1,2 @@
+/// This module contains the serialization tests for the data models and analysis results.
+pub mod test_serde;
@@ -0,0 +1,216 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use nalufx::models::{
+        allocation_dm::{AllocationOrder, AllocationRules, CashAllocation, Etf, MutualFund},
+        analysis_dm::{AnalysisMetrics, AnalysisResult},
+        cash_flow_dm, financial_dm,
+        health_dm::HealthResponse,
+    };
+    use nalufx::services::{
+        factor_svc::{FactorScores, FactorWeights, StockFactors},
+        fetch_data_svc::Ohlcv,
+        fundamentals_svc::Fundamentals,
+        monte_carlo_svc::SimulationResult,
+    };
+    use nalufx::utils::{date::SeriesIssue, indicators::IchimokuResult, portfolio::Holding};
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::{json, Value};
+    use std::fmt::Debug;
+
+    /// Serializes a value to JSON and back, and checks that nothing is lost.
+    fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) {
+        let serialized = serde_json::to_string(value).unwrap();
+        let deserialized: T = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(&deserialized, value, "round trip through {}", serialized);
+    }
+
+    #[test]
+    fn test_allocation_models_round_trip() {
+        assert_round_trip(&AllocationOrder {
+            symbol: "VTI".to_string(),
+            name: "Vanguard Total Stock Market ETF".to_string(),
+            amount: 1250.5,
+            shares: 5.0,
+        });
+        assert_round_trip(&Etf {
+            symbol: "VTI".to_string(),
+            name: "Vanguard Total Stock Market ETF".to_string(),
+            price: 250.25,
+            shares_outstanding: 1.5e9,
+        });
+        assert_round_trip(&MutualFund {
+            symbol: "VTSAX".to_string(),
+            name: "Vanguard Total Stock Market Index Fund".to_string(),
+            price: 120.5,
+            net_assets: 3.25e11,
+        });
+        assert_round_trip(&AllocationRules { etf_percentage: 0.6, mutual_fund_percentage: 0.4 });
+        assert_round_trip(&CashAllocation {
+            orders: vec![AllocationOrder {
+                symbol: "VTI".to_string(),
+                name: "Vanguard Total Stock Market ETF".to_string(),
+                amount: 1001.0,
+                shares: 4.0,
+            }],
+            allocated: 1001.0,
+            cash_remaining: 249.5,
+        });
+    }
+
+    #[test]
+    fn test_analysis_models_round_trip() {
+        let start_date = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+        let end_date = Utc.with_ymd_and_hms(2024, 1, 12, 0, 0, 0).unwrap();
+        assert_round_trip(&AnalysisResult {
+            ticker: "AAPL".to_string(),
+            start_date,
+            end_date,
+            initial_investment: 10_000.0,
+            allocations: vec![0.25, 0.5, 0.25],
+            sentiment: vec![0.5, -0.25],
+            actions: vec![],
+            metrics: AnalysisMetrics {
+                total_return: 0.125,
+                annualized_volatility: 0.25,
+                min_allocation: 0.25,
+                max_allocation: 0.5,
+                simulated_value_5th: 9_000.0,
+                simulated_value_95th: 12_500.0,
+                sharpe_ratio: 1.2,
+                sharpe_ratio_lower: 0.8,
+                sharpe_ratio_upper: 1.6,
+            },
+            key_findings: vec!["AAPL gained 12.5%.".to_string()],
+            llm_response: "AAPL gained 12.5%.\nThe outlook is positive.".to_string(),
+            continuity_issues: vec![
+                SeriesIssue::Gap { after: start_date, before: end_date, missing_days: 7 },
+                SeriesIssue::Duplicate(end_date),
+                SeriesIssue::OutOfOrder { previous: end_date, current: start_date },
+            ],
+        });
+    }
+
+    #[test]
+    fn test_api_models_round_trip() {
+        assert_round_trip(&cash_flow_dm::CashFlowRequest {
+            historical_data: vec![1.0, 2.5],
+            dates: Some(vec!["2024-06-27".to_string(), "2024-06-28".to_string()]),
+            market_indices: Some(vec![5460.5, 5482.9]),
+            fund_characteristics: None,
+        });
+        assert_round_trip(&cash_flow_dm::CashFlowResponse {
+            predictions: vec![1.0, 2.0],
+            optimal_allocation: vec![0.75, 0.25],
+            dates: None,
+        });
+        assert_round_trip(&cash_flow_dm::ErrorResponse { error: "Invalid data".to_string() });
+        assert_round_trip(&financial_dm::CashFlowRequest { historical_data: vec![1.0, 2.5] });
+        assert_round_trip(&financial_dm::CashFlowResponse {
+            predictions: vec![1.0, 2.0],
+            optimal_allocation: vec![0.75, 0.25],
+        });
+        assert_round_trip(&financial_dm::ErrorResponse { error: "Invalid data".to_string() });
+        assert_round_trip(&financial_dm::HistoricalData {
+            ticker: "AAPL".to_string(),
+            data: vec![150.0, 155.5],
+        });
+        assert_round_trip(&HealthResponse {
+            status: "unready".to_string(),
+            missing: vec!["OPENAI_API_KEY".to_string()],
+        });
+    }
+
+    #[test]
+    fn test_api_models_use_camel_case() {
+        let response = cash_flow_dm::CashFlowResponse {
+            predictions: vec![1.0],
+            optimal_allocation: vec![1.0],
+            dates: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({ "predictions": [1.0], "optimalAllocation": [1.0] })
+        );
+
+        let request = financial_dm::CashFlowRequest { historical_data: vec![1.0, 2.0] };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({ "historicalData": [1.0, 2.0] })
+        );
+
+        // Requests from clients that send snake case field names are still accepted.
+        let legacy: cash_flow_dm::CashFlowRequest =
+            serde_json::from_value(json!({ "historical_data": [1.0, 2.0] })).unwrap();
+        assert_eq!(legacy.historical_data, vec![1.0, 2.0]);
+        assert_eq!(legacy.dates, None);
+        assert_eq!(legacy.market_indices, None);
+    }
+
+    #[test]
+    fn test_analysis_results_round_trip() {
+        assert_round_trip(&Ohlcv {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 2, 14, 30, 0).unwrap(),
+            open: 187.25,
+            high: 188.5,
+            low: 183.875,
+            close: 185.625,
+            volume: 82_488_700.0,
+        });
+        assert_round_trip(&Fundamentals {
+            trailing_pe: Some(35.25),
+            price_to_book: None,
+            return_on_equity: Some(1.5),
+            debt_to_equity: Some(1.5),
+            market_cap: Some(3.5e12),
+        });
+        assert_round_trip(&SimulationResult {
+            percentile_5: 8_500.0,
+            median: 10_250.0,
+            percentile_95: 12_750.0,
+            mean: 10_375.0,
+        });
+        assert_round_trip(&FactorWeights::default());
+        assert_round_trip(&StockFactors {
+            symbol: "AAPL".to_string(),
+            value: Some(0.0625),
+            quality: None,
+            momentum: Some(0.25),
+            size: Some(0.5),
+        });
+        assert_round_trip(&FactorScores {
+            symbol: "AAPL".to_string(),
+            value_score: 0.5,
+            quality_score: -0.25,
+            momentum_score: Some(1.25),
+            size_score: 0.0,
+            composite_score: 0.375,
+        });
+        assert_round_trip(&IchimokuResult {
+            tenkan_sen: vec![None, Some(10.5)],
+            kijun_sen: vec![None, None],
+            senkou_span_a: vec![None, None, Some(10.25)],
+            senkou_span_b: vec![None, None, None],
+            chikou_span: vec![Some(11.0), None],
+        });
+        assert_round_trip(&Holding { ticker: "SPY".to_string(), shares_or_weight: 0.6 });
+    }
+
+    #[test]
+    fn test_analysis_result_keeps_snake_case() {
+        // The JSON output of the analyses is not an API model, so its field names are unchanged.
+        let value = serde_json::to_value(AnalysisMetrics {
+            total_return: 0.0,
+            annualized_volatility: 0.0,
+            min_allocation: 0.0,
+            max_allocation: 0.0,
+            simulated_value_5th: 0.0,
+            simulated_value_95th: 0.0,
+            sharpe_ratio: 0.0,
+            sharpe_ratio_lower: 0.0,
+            sharpe_ratio_upper: 0.0,
+        })
+        .unwrap();
+        assert!(matches!(value, Value::Object(ref fields) if fields.contains_key("total_return")));
+    }
+}
@@ -0,0 +1,2 @@
+/// This module contains the tests for the prompt templates.
+pub mod test_prompts;
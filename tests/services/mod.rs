@@ -0,0 +1,59 @@
+/// This module contains the tests for `pairs_trading_svc.rs`.
+pub mod test_pairs_trading_svc;
+
+/// This module contains the tests for `processing_svc.rs`.
+pub mod test_processing_svc;
+
+/// This module contains the tests for `esg_svc.rs`.
+pub mod test_esg_svc;
+
+/// This module contains the tests for `fetch_data_svc.rs`.
+pub mod test_fetch_data_svc;
+
+/// This module contains the tests for `concentration_svc.rs`.
+pub mod test_concentration_svc;
+
+/// This module contains the tests for `factor_svc.rs`.
+pub mod test_factor_svc;
+
+/// This module contains the tests for `fundamentals_svc.rs`.
+pub mod test_fundamentals_svc;
+
+/// This module contains the tests for `bellwether_stock_analysis_svc.rs`.
+pub mod test_bellwether_stock_analysis_svc;
+
+/// This module contains the tests for `monte_carlo_svc.rs`.
+pub mod test_monte_carlo_svc;
+
+/// This module contains the tests for `strategy_comparison_svc.rs`.
+pub mod test_strategy_comparison_svc;
+
+/// This module contains the tests for `backtest_svc.rs`.
+pub mod test_backtest_svc;
+
+/// This module contains the tests for `cointegration_svc.rs`.
+pub mod test_cointegration_svc;
+
+/// This module contains the tests for `yahoo_svc.rs`.
+pub mod test_yahoo_svc;
+
+/// This module contains the tests for `automated_cash_allocation_svc.rs`.
+pub mod test_automated_cash_allocation_svc;
+
+/// This module contains the tests for `sentiment_svc.rs`.
+pub mod test_sentiment_svc;
+
+/// This module contains the tests for `diversified_etf_portfolio_optimization_svc.rs`.
+pub mod test_diversified_etf_portfolio_optimization_svc;
+
+/// This module contains the tests for `data_quality_svc.rs`.
+pub mod test_data_quality_svc;
+
+/// This module contains the tests for `news_svc.rs`.
+pub mod test_news_svc;
+
+/// This module contains the tests for `period_comparison_svc.rs`.
+pub mod test_period_comparison_svc;
+
+/// This module contains the tests for `sensitivity_svc.rs`.
+pub mod test_sensitivity_svc;
@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::NaluFxError;
+    use nalufx::models::allocation_dm::{AllocationOrder, AllocationRules};
+    use nalufx::services::automated_cash_allocation_svc::{
+        execute_orders, load_allocation_rules, parse_allocation_rules, ConfigFormat,
+    };
+    use nalufx::utils::trading::ShareRounding;
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn order(symbol: &str, amount: f64) -> AllocationOrder {
+        AllocationOrder {
+            symbol: symbol.to_string(),
+            name: format!("{} Fund", symbol),
+            amount,
+            shares: 0.0,
+        }
+    }
+
+    fn prices() -> HashMap<String, (f64, f64)> {
+        HashMap::from([
+            ("SPY".to_string(), (500.0, 554.64)),
+            ("VTI".to_string(), (250.0, 272.12)),
+            ("VTSAX".to_string(), (120.0, 131.37)),
+        ])
+    }
+
+    #[test]
+    fn test_execute_orders_allocated_plus_remaining_equals_total() {
+        let orders = vec![order("SPY", 2_500.0), order("VTI", 1_234.56), order("VTSAX", 987.65)];
+        let total: f64 = orders.iter().map(|order| order.amount).sum();
+
+        for rounding in
+            [ShareRounding::Lots(1), ShareRounding::Lots(100), ShareRounding::Fractional(3)]
+        {
+            let allocation = execute_orders(&orders, &prices(), rounding);
+
+            assert!(
+                (allocation.allocated + allocation.cash_remaining - total).abs() < 1e-6,
+                "{:?}: {} + {} != {}",
+                rounding,
+                allocation.allocated,
+                allocation.cash_remaining,
+                total
+            );
+            assert!(allocation.cash_remaining >= 0.0);
+        }
+    }
+
+    #[test]
+    fn test_execute_orders_whole_shares() {
+        let allocation =
+            execute_orders(&[order("SPY", 2_500.0)], &prices(), ShareRounding::Lots(1));
+
+        assert_eq!(allocation.orders[0].shares, 4.0);
+        assert!((allocation.orders[0].amount - 4.0 * 554.64).abs() < 1e-9);
+        assert!((allocation.cash_remaining - (2_500.0 - 4.0 * 554.64)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_execute_orders_fractional_shares() {
+        let allocation =
+            execute_orders(&[order("VTSAX", 1_000.0)], &prices(), ShareRounding::Fractional(3));
+
+        assert_eq!(allocation.orders[0].shares, 7.612);
+        assert!(allocation.cash_remaining < 131.37 / 1000.0);
+    }
+
+    #[test]
+    fn test_execute_orders_keeps_cash_without_price() {
+        let allocation = execute_orders(
+            &[order("SPY", 2_500.0), order("UNKNOWN", 300.0)],
+            &prices(),
+            ShareRounding::Lots(1),
+        );
+
+        assert_eq!(allocation.orders[1].shares, 0.0);
+        assert_eq!(allocation.orders[1].amount, 0.0);
+        assert!((allocation.cash_remaining - (300.0 + 2_500.0 - 4.0 * 554.64)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_execute_orders_keeps_order_details() {
+        let allocation =
+            execute_orders(&[order("VTI", 1_000.0)], &prices(), ShareRounding::Lots(1));
+
+        assert_eq!(allocation.orders[0].symbol, "VTI");
+        assert_eq!(allocation.orders[0].name, "VTI Fund");
+    }
+
+    #[test]
+    fn test_execute_orders_no_orders() {
+        let allocation = execute_orders(&[], &prices(), ShareRounding::Lots(1));
+
+        assert!(allocation.orders.is_empty());
+        assert_eq!(allocation.allocated, 0.0);
+        assert_eq!(allocation.cash_remaining, 0.0);
+    }
+
+    #[test]
+    fn test_load_allocation_rules_from_every_format() {
+        let dir = tempdir().unwrap();
+        let files = [
+            ("rules.json", "{\"etf_percentage\": 70.0, \"mutual_fund_percentage\": 30.0}"),
+            ("rules.toml", "etf_percentage = 70.0\nmutual_fund_percentage = 30.0\n"),
+            ("rules.yaml", "etf_percentage: 70.0\nmutual_fund_percentage: 30.0\n"),
+            ("rules.yml", "etf_percentage: 70\nmutual_fund_percentage: 30\n"),
+        ];
+
+        for (name, content) in files {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            assert_eq!(
+                load_allocation_rules(&path).unwrap(),
+                AllocationRules { etf_percentage: 70.0, mutual_fund_percentage: 30.0 },
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_allocation_rules_over_100_percent_are_rejected() {
+        for (content, format) in [
+            ("{\"etf_percentage\": 80.0, \"mutual_fund_percentage\": 30.0}", ConfigFormat::Json),
+            ("etf_percentage = 80.0\nmutual_fund_percentage = 30.0\n", ConfigFormat::Toml),
+            ("etf_percentage: 80.0\nmutual_fund_percentage: 30.0\n", ConfigFormat::Yaml),
+        ] {
+            match parse_allocation_rules(content, format) {
+                Err(NaluFxError::ConfigError(message)) => assert!(message.contains("110%")),
+                other => panic!("Expected ConfigError for {}, got {:?}", format, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_load_allocation_rules_invalid_files() {
+        let dir = tempdir().unwrap();
+
+        let negative = dir.path().join("negative.toml");
+        fs::write(&negative, "etf_percentage = -10.0\nmutual_fund_percentage = 30.0\n").unwrap();
+        assert!(matches!(load_allocation_rules(&negative), Err(NaluFxError::ConfigError(_))));
+
+        let malformed = dir.path().join("malformed.yaml");
+        fs::write(&malformed, "etf_percentage: [70\n").unwrap();
+        assert!(matches!(load_allocation_rules(&malformed), Err(NaluFxError::ConfigError(_))));
+
+        let unsupported = dir.path().join("rules.ini");
+        fs::write(&unsupported, "etf_percentage=70\n").unwrap();
+        assert!(matches!(load_allocation_rules(&unsupported), Err(NaluFxError::ConfigError(_))));
+
+        assert!(matches!(
+            load_allocation_rules(&dir.path().join("missing.json")),
+            Err(NaluFxError::InputError(_))
+        ));
+    }
+}
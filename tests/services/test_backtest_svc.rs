@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use nalufx::errors::AllocationError;
+    use nalufx::services::backtest_svc::{backtest, backtest_with, calculate_turnover};
+    use std::collections::HashMap;
+
+    /// Builds a series of dated closing prices from the given closes, one per day.
+    fn dated(closes: &[f64]) -> Vec<(DateTime<Utc>, f64)> {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        closes
+            .iter()
+            .enumerate()
+            .map(|(day, &close)| (start + Duration::days(day as i64), close))
+            .collect()
+    }
+
+    /// A synthetic uptrend of 0.2% a day, with a small oscillation so the returns vary.
+    fn uptrend(num_days: usize) -> Vec<(DateTime<Utc>, f64)> {
+        let closes: Vec<f64> = (0..num_days)
+            .map(|day| 100.0 * 1.002_f64.powi(day as i32) * (1.0 + 0.0005 * (day as f64).sin()))
+            .collect();
+        dated(&closes)
+    }
+
+    #[test]
+    fn test_backtest_synthetic_uptrend() {
+        let prices = uptrend(120);
+        let result = backtest(&prices, 10, 40);
+
+        assert_eq!(result.values.len(), prices.len() - 40);
+        assert_eq!(result.values[0], (prices[40].0, 1.0));
+        let (last_date, final_value) = *result.values.last().unwrap();
+        assert_eq!(last_date, prices.last().unwrap().0);
+        assert!(final_value > 1.0);
+        assert!((result.cumulative_return - (final_value - 1.0)).abs() < 1e-12);
+        assert!(result.turnover > 0.0);
+    }
+
+    #[test]
+    fn test_backtest_with_gradual_deployment() {
+        let prices = dated(&[100.0, 100.0, 100.0, 110.0, 99.0]);
+        // Deploy half the portfolio on each day of a two-day period.
+        let result = backtest_with(&prices, 2, 2, |daily_returns, value, num_days| {
+            assert_eq!(daily_returns.len(), 2);
+            assert_eq!(value, 1.0);
+            assert_eq!(num_days, 2);
+            Ok(vec![0.5, 0.5])
+        });
+
+        // Half invested for the 10% gain, then fully invested for the 10% loss.
+        let values: Vec<f64> = result.values.iter().map(|&(_, value)| value).collect();
+        assert_eq!(values.len(), 3);
+        assert!((values[1] - 1.05).abs() < 1e-12);
+        assert!((values[2] - 1.05 * 0.9).abs() < 1e-12);
+        assert!((result.max_drawdown - 0.1).abs() < 1e-12);
+
+        // Buy half, which drifts to 0.55 / 1.05 of the portfolio, then top up to 100%.
+        assert!((result.turnover - (0.5 + (1.0 - 0.55 / 1.05))).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_backtest_with_failed_allocation_holds_cash() {
+        let prices = uptrend(30);
+        let result = backtest_with(&prices, 5, 10, |_, _, _| Err(AllocationError::EmptyInput));
+        assert!(result.values.iter().all(|&(_, value)| value == 1.0));
+        assert_eq!(result.cumulative_return, 0.0);
+        assert_eq!(result.turnover, 0.0);
+
+        let result = backtest_with(&prices, 5, 10, |_, _, num_days| Ok(vec![-1.0; num_days]));
+        assert_eq!(result.cumulative_return, 0.0);
+    }
+
+    #[test]
+    fn test_backtest_invalid_parameters() {
+        let prices = uptrend(30);
+        let allocate = |_: &[f64], _: f64, num_days: usize| Ok(vec![1.0; num_days]);
+        assert!(backtest_with(&prices, 0, 10, allocate).values.is_empty());
+        assert!(backtest_with(&prices, 5, 1, allocate).values.is_empty());
+        assert!(backtest_with(&prices, 5, 29, allocate).values.is_empty());
+        assert_eq!(backtest_with(&prices, 5, 28, allocate).values.len(), 2);
+    }
+
+    #[test]
+    fn test_calculate_turnover_full_swap() {
+        let old_weights = HashMap::from([("A".to_string(), 1.0)]);
+        let new_weights = HashMap::from([("B".to_string(), 1.0)]);
+
+        assert_eq!(calculate_turnover(&old_weights, &new_weights), 1.0);
+        assert_eq!(calculate_turnover(&new_weights, &old_weights), 1.0);
+    }
+
+    #[test]
+    fn test_calculate_turnover_partial_rebalance() {
+        let old_weights = HashMap::from([("A".to_string(), 0.6), ("B".to_string(), 0.4)]);
+        let new_weights =
+            HashMap::from([("A".to_string(), 0.5), ("B".to_string(), 0.3), ("C".to_string(), 0.2)]);
+
+        assert!((calculate_turnover(&old_weights, &new_weights) - 0.2).abs() < 1e-12);
+        assert_eq!(calculate_turnover(&old_weights, &old_weights), 0.0);
+        assert_eq!(calculate_turnover(&HashMap::new(), &HashMap::new()), 0.0);
+    }
+}
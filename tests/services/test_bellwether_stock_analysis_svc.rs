@@ -0,0 +1,319 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use chrono::{TimeZone, Utc};
+    use nalufx::errors::NaluFxError;
+    use nalufx::models::analysis_dm::{AnalysisMetrics, AnalysisResult};
+    use nalufx::services::{
+        bellwether_stock_analysis_svc::{analyze_series, format_analysis_json},
+        sentiment_svc::{MockSentimentProvider, SentimentProvider},
+        synthetic_data_svc::synthetic_dated_prices,
+    };
+    use nalufx::utils::date::SeriesIssue;
+    use nalufx_llms::{errors::LlmError, llms::LLM};
+    use reqwest::Client;
+    use serde_json::{json, Value};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        },
+        time::Duration,
+    };
+
+    // An LLM that answers every prompt with the same canned recommendation.
+    struct MockLLM;
+
+    #[async_trait]
+    impl LLM for MockLLM {
+        async fn send_request(
+            &self,
+            _client: &Client,
+            _api_key: &str,
+            _prompt: &str,
+            _max_tokens: usize,
+        ) -> Result<Value, LlmError> {
+            Ok(json!({
+                "choices": [{
+                    "message": {
+                        "content": "Summary\nAAPL looks attractive.\nHold cash.\nAAPL may be volatile."
+                    }
+                }]
+            }))
+        }
+
+        fn provider(&self) -> &str {
+            "mock"
+        }
+
+        fn model(&self) -> &str {
+            "mock-model"
+        }
+    }
+
+    // An LLM that fails with the given errors, in order, before answering like `MockLLM`.
+    struct FailingLLM {
+        errors: Mutex<Vec<LlmError>>,
+        calls: AtomicUsize,
+    }
+
+    impl FailingLLM {
+        fn new(mut errors: Vec<LlmError>) -> Self {
+            errors.reverse();
+            Self { errors: Mutex::new(errors), calls: AtomicUsize::new(0) }
+        }
+    }
+
+    #[async_trait]
+    impl LLM for FailingLLM {
+        async fn send_request(
+            &self,
+            client: &Client,
+            api_key: &str,
+            prompt: &str,
+            max_tokens: usize,
+        ) -> Result<Value, LlmError> {
+            let _ = self.calls.fetch_add(1, Ordering::SeqCst);
+            let error = self.errors.lock().unwrap().pop();
+            match error {
+                Some(error) => Err(error),
+                None => MockLLM.send_request(client, api_key, prompt, max_tokens).await,
+            }
+        }
+
+        fn provider(&self) -> &str {
+            "mock"
+        }
+
+        fn model(&self) -> &str {
+            "mock-model"
+        }
+    }
+
+    // A sentiment provider scoring every day the same, or failing if no score is given.
+    struct FixedSentimentProvider(Option<f64>);
+
+    #[async_trait]
+    impl SentimentProvider for FixedSentimentProvider {
+        async fn daily_sentiment(
+            &self,
+            _ticker: &str,
+            days: usize,
+        ) -> Result<Vec<f64>, NaluFxError> {
+            self.0
+                .map(|score| vec![score; days])
+                .ok_or_else(|| NaluFxError::SentimentAnalysisError("no news".to_string()))
+        }
+    }
+
+    /// Tests that `analyze_series` populates every field of the result for a mocked fetch.
+    #[tokio::test]
+    async fn test_analyze_series_populates_result() {
+        let end_date = Utc.with_ymd_and_hms(2024, 6, 28, 0, 0, 0).unwrap();
+        let series = synthetic_dated_prices("AAPL", 60, end_date);
+
+        let result = analyze_series(
+            &MockLLM,
+            &Client::new(),
+            "test-key",
+            &MockSentimentProvider,
+            "AAPL",
+            1000.0,
+            &series,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.ticker, "AAPL");
+        assert_eq!(result.start_date, series[0].0);
+        assert_eq!(result.end_date, end_date);
+        assert_eq!(result.initial_investment, 1000.0);
+
+        assert!(!result.allocations.is_empty());
+        assert!(result.allocations.iter().all(|&alloc| alloc >= 0.0));
+        assert!((result.allocations.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(result.sentiment.len(), result.allocations.len());
+        assert_eq!(result.actions.len(), result.allocations.len());
+
+        let expected_return = series[59].1 / series[0].1 - 1.0;
+        assert!((result.metrics.total_return - expected_return).abs() < 1e-12);
+        assert!(result.metrics.annualized_volatility > 0.0);
+        assert!(result.metrics.min_allocation <= result.metrics.max_allocation);
+        assert!(result.metrics.simulated_value_5th < result.metrics.simulated_value_95th);
+        assert!(result.metrics.sharpe_ratio_lower < result.metrics.sharpe_ratio);
+        assert!(result.metrics.sharpe_ratio < result.metrics.sharpe_ratio_upper);
+
+        assert_eq!(result.key_findings, vec!["AAPL looks attractive.", "AAPL may be volatile."]);
+        assert!(result.llm_response.contains("Hold cash."));
+        assert!(result.continuity_issues.is_empty());
+    }
+
+    /// Tests that `analyze_series` rejects an empty series.
+    #[tokio::test]
+    async fn test_analyze_series_empty_series() {
+        let result = analyze_series(
+            &MockLLM,
+            &Client::new(),
+            "test-key",
+            &MockSentimentProvider,
+            "AAPL",
+            1000.0,
+            &[],
+        )
+        .await;
+        assert!(matches!(result, Err(NaluFxError::EmptyInput)));
+    }
+
+    /// Tests that `analyze_series` rejects a zero, negative or `NaN` initial investment.
+    #[tokio::test]
+    async fn test_analyze_series_non_positive_investment() {
+        let end_date = Utc.with_ymd_and_hms(2024, 6, 28, 0, 0, 0).unwrap();
+        let series = synthetic_dated_prices("AAPL", 60, end_date);
+
+        for investment in [0.0, -100.0, f64::NAN] {
+            let result = analyze_series(
+                &MockLLM,
+                &Client::new(),
+                "test-key",
+                &MockSentimentProvider,
+                "AAPL",
+                investment,
+                &series,
+            )
+            .await;
+            assert!(matches!(result, Err(NaluFxError::InvalidData)), "accepted {}", investment);
+        }
+    }
+
+    /// Tests that JSON mode produces parseable JSON with every field of the result.
+    #[test]
+    fn test_format_analysis_json_is_parseable() {
+        let start_date = Utc.with_ymd_and_hms(2024, 6, 3, 0, 0, 0).unwrap();
+        let end_date = Utc.with_ymd_and_hms(2024, 6, 28, 0, 0, 0).unwrap();
+        let result = AnalysisResult {
+            ticker: "AAPL".to_string(),
+            start_date,
+            end_date,
+            initial_investment: 1000.0,
+            allocations: vec![0.25, 0.75],
+            sentiment: vec![0.5, -0.25],
+            actions: vec![1.0, 0.0],
+            metrics: AnalysisMetrics {
+                total_return: 0.1,
+                annualized_volatility: 0.2,
+                min_allocation: 0.25,
+                max_allocation: 0.75,
+                simulated_value_5th: 900.0,
+                simulated_value_95th: 1200.0,
+                sharpe_ratio: 1.2,
+                sharpe_ratio_lower: 0.8,
+                sharpe_ratio_upper: 1.6,
+            },
+            key_findings: vec!["AAPL looks \"attractive\".".to_string()],
+            llm_response: "Summary\nAAPL looks \"attractive\".".to_string(),
+            continuity_issues: vec![SeriesIssue::Duplicate(end_date)],
+        };
+
+        let json: Value = serde_json::from_str(&format_analysis_json(&result).unwrap()).unwrap();
+
+        assert_eq!(json["ticker"], "AAPL");
+        assert_eq!(json["start_date"], "2024-06-03T00:00:00Z");
+        assert_eq!(json["allocations"], json!([0.25, 0.75]));
+        assert_eq!(json["metrics"]["annualized_volatility"], 0.2);
+        assert_eq!(json["metrics"]["simulated_value_95th"], 1200.0);
+        assert_eq!(json["key_findings"][0], "AAPL looks \"attractive\".");
+        assert_eq!(json["continuity_issues"][0]["Duplicate"], "2024-06-28T00:00:00Z");
+    }
+
+    /// Tests that a rate-limited LLM request is sent again after the requested delay.
+    #[tokio::test]
+    async fn test_analyze_series_retries_rate_limited_request() {
+        let end_date = Utc.with_ymd_and_hms(2024, 6, 28, 0, 0, 0).unwrap();
+        let series = synthetic_dated_prices("AAPL", 60, end_date);
+        let llm =
+            FailingLLM::new(vec![LlmError::RateLimited { retry_after: Some(Duration::ZERO) }]);
+
+        let result = analyze_series(
+            &llm,
+            &Client::new(),
+            "test-key",
+            &MockSentimentProvider,
+            "AAPL",
+            1000.0,
+            &series,
+        )
+        .await
+        .unwrap();
+
+        assert!(result.llm_response.contains("Hold cash."));
+        assert_eq!(llm.calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Tests that `analyze_series` gives up when the retry is rate limited too.
+    #[tokio::test]
+    async fn test_analyze_series_rate_limited_twice() {
+        let end_date = Utc.with_ymd_and_hms(2024, 6, 28, 0, 0, 0).unwrap();
+        let series = synthetic_dated_prices("AAPL", 60, end_date);
+        let rate_limited = LlmError::RateLimited { retry_after: Some(Duration::ZERO) };
+        let llm = FailingLLM::new(vec![rate_limited.clone(), rate_limited.clone()]);
+
+        let result = analyze_series(
+            &llm,
+            &Client::new(),
+            "test-key",
+            &MockSentimentProvider,
+            "AAPL",
+            1000.0,
+            &series,
+        )
+        .await;
+
+        assert!(matches!(result, Err(NaluFxError::LlmError(error)) if error == rate_limited));
+        assert_eq!(llm.calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Tests that an authentication failure aborts the analysis without retrying.
+    #[tokio::test]
+    async fn test_analyze_series_auth_failed_is_not_retried() {
+        let end_date = Utc.with_ymd_and_hms(2024, 6, 28, 0, 0, 0).unwrap();
+        let series = synthetic_dated_prices("AAPL", 60, end_date);
+        let llm = FailingLLM::new(vec![LlmError::AuthFailed]);
+
+        let result = analyze_series(
+            &llm,
+            &Client::new(),
+            "test-key",
+            &MockSentimentProvider,
+            "AAPL",
+            1000.0,
+            &series,
+        )
+        .await;
+
+        assert!(matches!(result, Err(NaluFxError::LlmError(LlmError::AuthFailed))));
+        assert_eq!(llm.calls.load(Ordering::SeqCst), 1);
+    }
+
+    /// Tests that the sentiment of the result comes from the given provider, and that a
+    /// provider failure leaves it empty.
+    #[tokio::test]
+    async fn test_analyze_series_uses_the_sentiment_provider() {
+        let end_date = Utc.with_ymd_and_hms(2024, 6, 28, 0, 0, 0).unwrap();
+        let series = synthetic_dated_prices("AAPL", 60, end_date);
+        let client = Client::new();
+
+        let provider = FixedSentimentProvider(Some(0.75));
+        let result =
+            analyze_series(&MockLLM, &client, "test-key", &provider, "AAPL", 1000.0, &series)
+                .await
+                .unwrap();
+        assert_eq!(result.sentiment, vec![0.75; result.allocations.len()]);
+
+        let provider = FixedSentimentProvider(None);
+        let result =
+            analyze_series(&MockLLM, &client, "test-key", &provider, "AAPL", 1000.0, &series)
+                .await
+                .unwrap();
+        assert!(result.sentiment.is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::NaluFxError;
+    use nalufx::services::{
+        cointegration_svc::engle_granger_test, synthetic_data_svc::synthetic_prices,
+    };
+
+    /// Tests that a pair built from a random walk and a stationary spread is cointegrated.
+    #[test]
+    fn test_engle_granger_cointegrated_pair() {
+        let series_b = synthetic_prices("AAPL", 500);
+        // The daily returns of another ticker are a stationary spread.
+        let noise = synthetic_prices("MSFT", 501);
+        let series_a: Vec<f64> = series_b
+            .iter()
+            .zip(noise.windows(2))
+            .map(|(b, pair)| 10.0 + 1.5 * b + 100.0 * (pair[1] / pair[0] - 1.0))
+            .collect();
+
+        let result = engle_granger_test(&series_a, &series_b).unwrap();
+
+        assert!((result.hedge_ratio - 1.5).abs() < 0.05, "hedge ratio {}", result.hedge_ratio);
+        assert!(result.adf_statistic < result.critical_value);
+        assert!(result.cointegrated);
+    }
+
+    /// Tests that two independent random walks are not cointegrated.
+    #[test]
+    fn test_engle_granger_independent_random_walks() {
+        let series_a = synthetic_prices("AAPL", 500);
+        let series_b = synthetic_prices("MSFT", 500);
+
+        let result = engle_granger_test(&series_a, &series_b).unwrap();
+
+        assert!(result.adf_statistic > result.critical_value);
+        assert!(!result.cointegrated);
+    }
+
+    /// Tests that the longer series is truncated to its most recent observations.
+    #[test]
+    fn test_engle_granger_truncates_longer_series() {
+        let series_a = synthetic_prices("AAPL", 300);
+        let series_b = synthetic_prices("MSFT", 250);
+
+        let truncated = engle_granger_test(&series_a, &series_b).unwrap();
+        let aligned = engle_granger_test(&series_a[50..], &series_b).unwrap();
+
+        assert_eq!(truncated, aligned);
+    }
+
+    /// Tests that empty, short, non-finite and constant series are rejected.
+    #[test]
+    fn test_engle_granger_invalid_input() {
+        let prices = synthetic_prices("AAPL", 100);
+
+        assert!(matches!(engle_granger_test(&[], &prices), Err(NaluFxError::EmptyInput)));
+        assert!(matches!(
+            engle_granger_test(&prices[..10], &prices[..10]),
+            Err(NaluFxError::InvalidData)
+        ));
+
+        let mut with_nan = prices.clone();
+        with_nan[50] = f64::NAN;
+        assert!(matches!(engle_granger_test(&with_nan, &prices), Err(NaluFxError::InvalidData)));
+        assert!(matches!(
+            engle_granger_test(&prices, &[100.0; 100]),
+            Err(NaluFxError::InvalidData)
+        ));
+    }
+}
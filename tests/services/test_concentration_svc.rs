@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::services::concentration_svc::{effective_number_of_holdings, herfindahl_index};
+
+    #[test]
+    fn test_herfindahl_index_equal_weights() {
+        for n in [1, 2, 4, 10] {
+            let weights = vec![1.0 / n as f64; n];
+            assert!((herfindahl_index(&weights) - 1.0 / n as f64).abs() < 1e-12);
+            assert!((effective_number_of_holdings(&weights) - n as f64).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_herfindahl_index_single_asset() {
+        assert_eq!(herfindahl_index(&[1.0]), 1.0);
+        assert_eq!(herfindahl_index(&[0.0, 1.0, 0.0]), 1.0);
+        assert_eq!(effective_number_of_holdings(&[0.0, 1.0, 0.0]), 1.0);
+    }
+
+    #[test]
+    fn test_herfindahl_index_uneven_weights() {
+        let hhi = herfindahl_index(&[0.5, 0.3, 0.2]);
+        assert!((hhi - 0.38).abs() < 1e-12);
+        assert!(hhi > 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_herfindahl_index_empty() {
+        assert_eq!(herfindahl_index(&[]), 0.0);
+        assert_eq!(effective_number_of_holdings(&[]), 0.0);
+    }
+}
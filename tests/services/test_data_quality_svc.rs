@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+    use nalufx::services::data_quality_svc::{assess_quality, QualityFlag};
+    use serde_json::Value;
+
+    // Nine daily closes with the weekdays of 2024-03-11 to 2024-03-13 missing, and a zero
+    // close on 2024-03-15.
+    const DAILY_CLOSES_FIXTURE: &str = include_str!("../fixtures/daily_closes_gap.json");
+
+    fn date(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
+    }
+
+    fn fixture_series() -> Vec<(DateTime<Utc>, f64)> {
+        let rows: Vec<Value> = serde_json::from_str(DAILY_CLOSES_FIXTURE).unwrap();
+        rows.iter()
+            .map(|row| {
+                let day = NaiveDate::parse_from_str(row["date"].as_str().unwrap(), "%Y-%m-%d");
+                (
+                    day.unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc(),
+                    row["close"].as_f64().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_assess_quality_reports_the_gap_and_the_zero_price() {
+        let report = assess_quality(&fixture_series());
+
+        assert_eq!(report.count, 9);
+        assert_eq!(report.first_date, Some(date(2024, 3, 4)));
+        assert_eq!(report.last_date, Some(date(2024, 3, 19)));
+        assert_eq!(report.gaps, 1);
+        assert_eq!(report.nan_count, 0);
+        assert_eq!(report.min, Some(0.0));
+        assert_eq!(report.max, Some(103.0));
+        assert_eq!(
+            report.flags,
+            vec![QualityFlag::NonPositive { date: date(2024, 3, 15), value: 0.0 }]
+        );
+        assert!(!report.is_clean());
+
+        let preamble = report.to_string();
+        assert!(preamble.contains("- Gaps: 1"), "{}", preamble);
+        assert!(preamble.contains("2024-03-15: non-positive price 0.00"), "{}", preamble);
+    }
+
+    #[test]
+    fn test_assess_quality_flags_jumps_and_counts_nans() {
+        let series = vec![
+            (date(2024, 3, 4), 100.0),
+            (date(2024, 3, 5), f64::NAN),
+            (date(2024, 3, 6), 40.0),
+            (date(2024, 3, 7), 41.0),
+        ];
+        let report = assess_quality(&series);
+
+        assert_eq!(report.nan_count, 1);
+        assert_eq!(report.min, Some(40.0));
+        assert_eq!(report.flags, vec![QualityFlag::Jump { date: date(2024, 3, 6), change: -0.6 }]);
+    }
+
+    #[test]
+    fn test_assess_quality_of_a_clean_and_an_empty_series() {
+        let series = vec![(date(2024, 3, 4), 100.0), (date(2024, 3, 5), 101.0)];
+        assert!(assess_quality(&series).is_clean());
+
+        let report = assess_quality(&[]);
+        assert_eq!(report.count, 0);
+        assert_eq!(report.first_date, None);
+        assert_eq!(report.min, None);
+        assert!(report.is_clean());
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::services::{
+        diversified_etf_portfolio_optimization_svc::{
+            calculate_etf_allocations, calculate_etf_allocations_serial,
+        },
+        processing_svc::calculate_cash_flows,
+    };
+    use nalufx::utils::calculations::Objective;
+
+    const DAYS: usize = 60;
+
+    // A dozen ETFs with distinct but deterministic daily returns.
+    fn etf_data() -> Vec<(String, Vec<f64>, Vec<f64>)> {
+        (0..12)
+            .map(|etf| {
+                let daily_returns: Vec<f64> =
+                    (0..DAYS).map(|day| 0.001 * ((day * (etf + 1)) % 13) as f64 - 0.006).collect();
+                let cash_flows = calculate_cash_flows(&daily_returns, 1_000.0);
+                (format!("ETF{}", etf), daily_returns, cash_flows)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parallel_and_serial_allocations_are_identical() {
+        let etf_data = etf_data();
+        let market_indices: Vec<f64> = (0..DAYS).map(|day| 1_000.0 + day as f64).collect();
+        let fund_characteristics = vec![0.9; DAYS];
+
+        let parallel = calculate_etf_allocations(
+            &etf_data,
+            &market_indices,
+            &fund_characteristics,
+            Objective::MaxSharpe,
+        );
+        let serial = calculate_etf_allocations_serial(
+            &etf_data,
+            &market_indices,
+            &fund_characteristics,
+            Objective::MaxSharpe,
+        );
+
+        assert_eq!(parallel, serial);
+        assert_eq!(parallel.len(), etf_data.len());
+        for ((ticker, allocation), (expected, _, _)) in parallel.iter().zip(&etf_data) {
+            assert_eq!(ticker, expected);
+            assert_eq!(allocation.len(), DAYS);
+            assert!((allocation.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_etf_allocations_skip_invalid_etfs() {
+        let mut etf_data = etf_data();
+        etf_data[1].1[10] = f64::NAN;
+        let market_indices = vec![1_000.0; DAYS];
+        let fund_characteristics = vec![0.9; DAYS];
+
+        let parallel = calculate_etf_allocations(
+            &etf_data,
+            &market_indices,
+            &fund_characteristics,
+            Objective::MaxSharpe,
+        );
+        let serial = calculate_etf_allocations_serial(
+            &etf_data,
+            &market_indices,
+            &fund_characteristics,
+            Objective::MaxSharpe,
+        );
+
+        assert_eq!(parallel, serial);
+        assert_eq!(parallel.len(), etf_data.len() - 1);
+        assert!(parallel.iter().all(|(ticker, _)| ticker != "ETF1"));
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::NaluFxError;
+    use nalufx::services::esg_svc::{
+        CsvEsgRatingProvider, EsgRatingProvider, HttpEsgRatingProvider,
+    };
+    use reqwest::Client;
+    use serde_json::json;
+    use std::fs;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    const RATINGS_CSV: &str = "symbol,esg_rating\nESGU,7.4\nicln,8.3\nPBW,6.9\n";
+
+    #[tokio::test]
+    async fn test_csv_provider_lookups_from_file() {
+        let path = std::env::temp_dir().join("nalufx_test_esg_ratings.csv");
+        fs::write(&path, RATINGS_CSV).unwrap();
+
+        let provider = CsvEsgRatingProvider::from_path(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(provider.fetch_esg_rating("ESGU").await.unwrap(), 7.4);
+        assert_eq!(provider.fetch_esg_rating("ICLN").await.unwrap(), 8.3);
+        assert_eq!(provider.fetch_esg_rating(" pbw ").await.unwrap(), 6.9);
+    }
+
+    #[tokio::test]
+    async fn test_csv_provider_reports_missing_rating() {
+        let provider = CsvEsgRatingProvider::from_reader(RATINGS_CSV.as_bytes()).unwrap();
+
+        match provider.fetch_esg_rating("SPY").await {
+            Err(NaluFxError::EsgRatingNotFound(ticker)) => assert_eq!(ticker, "SPY"),
+            other => panic!("Expected EsgRatingNotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_csv_provider_rejects_invalid_rows() {
+        let invalid = "symbol,esg_rating\nESGU,not-a-number\n";
+        assert!(matches!(
+            CsvEsgRatingProvider::from_reader(invalid.as_bytes()),
+            Err(NaluFxError::CsvError(_))
+        ));
+
+        let non_finite = "symbol,esg_rating\nESGU,NaN\n";
+        assert!(matches!(
+            CsvEsgRatingProvider::from_reader(non_finite.as_bytes()),
+            Err(NaluFxError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn test_csv_provider_missing_file() {
+        assert!(CsvEsgRatingProvider::from_path("does/not/exist.csv").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_http_provider_encodes_the_ticker() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/ratings/BRK%2FB"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "esg_rating": 5.6 })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let base_url = format!("{}/ratings/", server.uri());
+        let provider = HttpEsgRatingProvider::new(Client::new(), &base_url);
+        assert_eq!(provider.fetch_esg_rating(" brk/b ").await.unwrap(), 5.6);
+    }
+
+    #[tokio::test]
+    async fn test_http_provider_rejects_an_invalid_base_url() {
+        let provider = HttpEsgRatingProvider::new(Client::new(), "not a url");
+        assert!(matches!(
+            provider.fetch_esg_rating("ESGU").await,
+            Err(NaluFxError::ConfigError(_))
+        ));
+    }
+}
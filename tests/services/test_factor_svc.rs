@@ -0,0 +1,221 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::NaluFxError;
+    use nalufx::services::factor_svc::{
+        factor_tilted_weights, rank_by_factors, FactorWeights, StockFactors, WeightingScheme,
+    };
+
+    fn stock(symbol: &str, value: f64, quality: f64, momentum: f64, size: f64) -> StockFactors {
+        StockFactors {
+            symbol: symbol.to_string(),
+            value: Some(value),
+            quality: Some(quality),
+            momentum: Some(momentum),
+            size: Some(size),
+        }
+    }
+
+    fn sample_stocks() -> Vec<StockFactors> {
+        // The momentum ranking (CCC, AAA, DDD, BBB) is the reverse of every other factor.
+        vec![
+            stock("AAA", 0.2, 0.2, 0.3, 2.0),
+            stock("BBB", 0.4, 0.4, -0.1, 4.0),
+            stock("CCC", 0.1, 0.1, 0.5, 1.0),
+            stock("DDD", 0.3, 0.3, 0.1, 3.0),
+        ]
+    }
+
+    #[test]
+    fn test_factor_weights_validation() {
+        assert!(FactorWeights::default().validate().is_ok());
+        assert!(FactorWeights::new(0.1, 0.2, 0.3, 0.4).is_ok());
+        assert!(FactorWeights::new(0.1, 0.2, 0.3, 0.4 + 1e-9).is_ok());
+        assert!(matches!(FactorWeights::new(0.5, 0.5, 0.5, 0.0), Err(NaluFxError::InvalidData)));
+        assert!(matches!(FactorWeights::new(1.5, -0.5, 0.0, 0.0), Err(NaluFxError::InvalidData)));
+        assert!(matches!(
+            FactorWeights::new(f64::NAN, 0.0, 1.0, 0.0),
+            Err(NaluFxError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn test_rank_by_factors_momentum_only() {
+        let weights = FactorWeights::new(0.0, 0.0, 1.0, 0.0).unwrap();
+        let ranked = rank_by_factors(&sample_stocks(), &weights).unwrap();
+
+        let symbols: Vec<&str> = ranked.iter().map(|s| s.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["CCC", "AAA", "DDD", "BBB"]);
+        for scores in &ranked {
+            assert_eq!(Some(scores.composite_score), scores.momentum_score);
+        }
+    }
+
+    #[test]
+    fn test_rank_by_factors_default_weights() {
+        let ranked = rank_by_factors(&sample_stocks(), &FactorWeights::default()).unwrap();
+
+        // Value, quality and size outweigh momentum, so the ranking follows them.
+        let symbols: Vec<&str> = ranked.iter().map(|s| s.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["BBB", "DDD", "AAA", "CCC"]);
+        for scores in &ranked {
+            let expected = 0.25
+                * (scores.value_score
+                    + scores.quality_score
+                    + scores.momentum_score.unwrap()
+                    + scores.size_score);
+            assert!((scores.composite_score - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_rank_by_factors_missing_factors() {
+        let mut stocks = sample_stocks();
+        stocks[2].value = None;
+        stocks.push(StockFactors {
+            symbol: "EEE".to_string(),
+            value: None,
+            quality: None,
+            momentum: Some(0.2),
+            size: None,
+        });
+
+        let ranked = rank_by_factors(&stocks, &FactorWeights::default()).unwrap();
+        let score = |symbol: &str| ranked.iter().find(|s| s.symbol == symbol).unwrap();
+
+        // Missing factors are neutral and do not shift the scores of the other stocks.
+        assert_eq!(score("CCC").value_score, 0.0);
+        assert_eq!(score("EEE").value_score, 0.0);
+        assert_eq!(score("EEE").quality_score, 0.0);
+        assert_eq!(score("EEE").size_score, 0.0);
+        let value_scores: f64 = ranked.iter().map(|s| s.value_score).sum();
+        assert!(value_scores.abs() < 1e-12);
+        assert!(score("BBB").value_score > score("DDD").value_score);
+        assert!(score("DDD").value_score > score("AAA").value_score);
+    }
+
+    #[test]
+    fn test_rank_by_factors_momentum_unavailable() {
+        // EEE has no price a year ago, so its momentum cannot be calculated.
+        let mut stocks = sample_stocks();
+        stocks.push(StockFactors { momentum: None, ..stock("EEE", 0.4, 0.4, 0.0, 4.0) });
+
+        let ranked = rank_by_factors(&stocks, &FactorWeights::default()).unwrap();
+        let score = |symbol: &str| ranked.iter().find(|s| s.symbol == symbol).unwrap();
+
+        // EEE is flagged rather than scored as average, and is excluded from the normalization.
+        assert_eq!(score("EEE").momentum_score, None);
+        let momentum_scores: Vec<f64> = ranked.iter().filter_map(|s| s.momentum_score).collect();
+        assert_eq!(momentum_scores.len(), 4);
+        assert!(momentum_scores.iter().sum::<f64>().abs() < 1e-12);
+        let unflagged = rank_by_factors(&sample_stocks(), &FactorWeights::default()).unwrap();
+        for scores in &unflagged {
+            assert_eq!(scores.momentum_score, score(&scores.symbol).momentum_score);
+        }
+
+        // Its composite score is the mean of its other factor scores.
+        let eee = score("EEE");
+        let expected = (eee.value_score + eee.quality_score + eee.size_score) / 3.0;
+        assert!((eee.composite_score - expected).abs() < 1e-12);
+
+        // Without weight on the other factors, it has no basis for a score.
+        let weights = FactorWeights::new(0.0, 0.0, 1.0, 0.0).unwrap();
+        let ranked = rank_by_factors(&stocks, &weights).unwrap();
+        let eee = ranked.iter().find(|s| s.symbol == "EEE").unwrap();
+        assert_eq!(eee.momentum_score, None);
+        assert_eq!(eee.composite_score, 0.0);
+    }
+
+    #[test]
+    fn test_rank_by_factors_invalid_input() {
+        assert!(matches!(
+            rank_by_factors(&[], &FactorWeights::default()),
+            Err(NaluFxError::EmptyInput)
+        ));
+
+        let invalid_weights = FactorWeights { value: 1.0, quality: 1.0, momentum: 0.0, size: 0.0 };
+        assert!(matches!(
+            rank_by_factors(&sample_stocks(), &invalid_weights),
+            Err(NaluFxError::InvalidData)
+        ));
+
+        let mut stocks = sample_stocks();
+        stocks[0].momentum = Some(f64::NAN);
+        assert!(matches!(
+            rank_by_factors(&stocks, &FactorWeights::default()),
+            Err(NaluFxError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn test_factor_tilted_weights_follow_composite_scores() {
+        let ranked = rank_by_factors(&sample_stocks(), &FactorWeights::default()).unwrap();
+
+        for scheme in [
+            WeightingScheme::default(),
+            WeightingScheme::Softmax { temperature: 0.5 },
+            WeightingScheme::RankBased,
+        ] {
+            let weights = factor_tilted_weights(&ranked, scheme).unwrap();
+
+            assert_eq!(weights.len(), ranked.len());
+            assert!((weights.values().sum::<f64>() - 1.0).abs() < 1e-12);
+            // The stocks are ranked from the highest composite score to the lowest.
+            for pair in ranked.windows(2) {
+                assert!(weights[&pair[0].symbol] > weights[&pair[1].symbol]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_factor_tilted_weights_rank_based() {
+        let mut ranked = rank_by_factors(&sample_stocks(), &FactorWeights::default()).unwrap();
+        let weights = factor_tilted_weights(&ranked, WeightingScheme::RankBased).unwrap();
+
+        // Ranks 4, 3, 2 and 1 out of a total of 10 parts.
+        assert!((weights["BBB"] - 0.4).abs() < 1e-12);
+        assert!((weights["DDD"] - 0.3).abs() < 1e-12);
+        assert!((weights["AAA"] - 0.2).abs() < 1e-12);
+        assert!((weights["CCC"] - 0.1).abs() < 1e-12);
+
+        // Equal composite scores get equal weights.
+        ranked[3].composite_score = ranked[2].composite_score;
+        let weights = factor_tilted_weights(&ranked, WeightingScheme::RankBased).unwrap();
+        assert_eq!(weights["AAA"], weights["CCC"]);
+        assert!((weights.values().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_factor_tilted_weights_softmax_temperature() {
+        let ranked = rank_by_factors(&sample_stocks(), &FactorWeights::default()).unwrap();
+        let top = &ranked[0].symbol;
+
+        let concentrated =
+            factor_tilted_weights(&ranked, WeightingScheme::Softmax { temperature: 0.1 }).unwrap();
+        let spread =
+            factor_tilted_weights(&ranked, WeightingScheme::Softmax { temperature: 100.0 })
+                .unwrap();
+
+        assert!(concentrated[top] > 0.9);
+        assert!(spread.values().all(|weight| (weight - 0.25).abs() < 0.01));
+    }
+
+    #[test]
+    fn test_factor_tilted_weights_invalid_input() {
+        assert!(matches!(
+            factor_tilted_weights(&[], WeightingScheme::default()),
+            Err(NaluFxError::EmptyInput)
+        ));
+
+        let mut ranked = rank_by_factors(&sample_stocks(), &FactorWeights::default()).unwrap();
+        assert!(matches!(
+            factor_tilted_weights(&ranked, WeightingScheme::Softmax { temperature: 0.0 }),
+            Err(NaluFxError::InvalidData)
+        ));
+
+        ranked[0].composite_score = f64::NAN;
+        assert!(matches!(
+            factor_tilted_weights(&ranked, WeightingScheme::RankBased),
+            Err(NaluFxError::InvalidData)
+        ));
+    }
+}
@@ -0,0 +1,383 @@
+#[cfg(test)]
+mod tests {
+    use crate::ENV_MUTEX;
+    use chrono::{TimeZone, Utc};
+    use nalufx::errors::NaluFxError;
+    use nalufx::services::{
+        fetch_data_svc::{
+            chart_url, fetch_chart_quotes, fetch_data, fetch_data_batch, fetch_data_interval,
+            fetch_data_with_dates, fetch_ohlcv_data, is_empty_chart, is_no_data_error, Interval,
+        },
+        synthetic_data_svc::synthetic_prices,
+        yahoo_svc::YahooClient,
+    };
+    use nalufx::utils::{batch::BatchOptions, date::check_series_continuity};
+    use serde_json::json;
+    use std::env;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    /// Tests that `fetch_data` returns synthetic prices in offline mode.
+    // The lock is held while the test awaits, since the code under test reads the environment.
+    #[allow(clippy::await_holding_lock)]
+    #[tokio::test]
+    async fn test_fetch_data_offline_mode() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        env::set_var("NALUFX_OFFLINE", "1");
+
+        let first = fetch_data("AAPL", None, None).await;
+        let second = fetch_data("AAPL", None, None).await;
+
+        env::remove_var("NALUFX_OFFLINE");
+
+        let first = first.expect("offline fetch_data should not fail");
+        assert!(!first.is_empty());
+        assert!(first.iter().all(|&price| price.is_finite() && price > 0.0));
+        assert_eq!(first, second.unwrap());
+    }
+
+    /// Tests that `fetch_data` sizes the synthetic series from the date range in offline mode.
+    // The lock is held while the test awaits, since the code under test reads the environment.
+    #[allow(clippy::await_holding_lock)]
+    #[tokio::test]
+    async fn test_fetch_data_offline_mode_date_range() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        env::set_var("NALUFX_OFFLINE", "1");
+
+        let end_date = chrono::Utc::now();
+        let start_date = end_date - chrono::Duration::days(70);
+        let closes = fetch_data("MSFT", Some(start_date), Some(end_date)).await;
+
+        env::remove_var("NALUFX_OFFLINE");
+
+        assert_eq!(closes.unwrap().len(), 50);
+    }
+
+    /// Tests that `fetch_data` covers the configured lookback without a start date in offline
+    /// mode, like an online fetch.
+    // The lock is held while the test awaits, since the code under test reads the environment.
+    #[allow(clippy::await_holding_lock)]
+    #[tokio::test]
+    async fn test_fetch_data_offline_mode_default_lookback() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        env::set_var("NALUFX_OFFLINE", "1");
+
+        env::remove_var("NALUFX_HISTORY_LOOKBACK_DAYS");
+        let default = fetch_data("MSFT", None, None).await;
+        env::set_var("NALUFX_HISTORY_LOOKBACK_DAYS", "70");
+        let configured = fetch_data("MSFT", None, None).await;
+
+        env::remove_var("NALUFX_HISTORY_LOOKBACK_DAYS");
+        env::remove_var("NALUFX_OFFLINE");
+
+        // One price per weekday: 730 * 5 / 7 and 70 * 5 / 7.
+        assert_eq!(default.unwrap().len(), 521);
+        assert_eq!(configured.unwrap().len(), 50);
+    }
+
+    /// Tests that `fetch_data_with_dates` returns a continuous dated series in offline mode.
+    // The lock is held while the test awaits, since the code under test reads the environment.
+    #[allow(clippy::await_holding_lock)]
+    #[tokio::test]
+    async fn test_fetch_data_with_dates_offline_mode() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        env::set_var("NALUFX_OFFLINE", "1");
+
+        let series = fetch_data_with_dates("AAPL", None, None).await;
+        let closes = fetch_data("AAPL", None, None).await;
+
+        env::remove_var("NALUFX_OFFLINE");
+
+        let series = series.unwrap();
+        assert!(check_series_continuity(&series, 0).is_ok());
+        assert_eq!(series.iter().map(|(_, close)| *close).collect::<Vec<_>>(), closes.unwrap());
+    }
+
+    /// Tests that `fetch_ohlcv_data` returns consistent sessions in offline mode.
+    // The lock is held while the test awaits, since the code under test reads the environment.
+    #[allow(clippy::await_holding_lock)]
+    #[tokio::test]
+    async fn test_fetch_ohlcv_data_offline_mode() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        env::set_var("NALUFX_OFFLINE", "1");
+
+        let end_date = chrono::Utc::now();
+        let start_date = end_date - chrono::Duration::days(70);
+        let sessions = fetch_ohlcv_data("AAPL", Some(start_date), Some(end_date)).await;
+        let series = fetch_data_with_dates("AAPL", Some(start_date), Some(end_date)).await;
+
+        env::remove_var("NALUFX_OFFLINE");
+
+        let sessions = sessions.unwrap();
+        let series = series.unwrap();
+        assert_eq!(sessions.len(), series.len());
+        for (session, (timestamp, close)) in sessions.iter().zip(&series) {
+            assert_eq!((session.timestamp, session.close), (*timestamp, *close));
+            assert!(session.low <= session.open.min(session.close));
+            assert!(session.high >= session.open.max(session.close));
+            assert!(session.volume > 0.0);
+        }
+        for pair in sessions.windows(2) {
+            assert_eq!(pair[1].open, pair[0].close);
+        }
+    }
+
+    /// Tests that `fetch_data_batch` fetches every ticker in order and reports the progress of
+    /// each one in offline mode.
+    // The lock is held while the test awaits, since the code under test reads the environment.
+    #[allow(clippy::await_holding_lock)]
+    #[tokio::test]
+    async fn test_fetch_data_batch_offline_mode() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        env::set_var("NALUFX_OFFLINE", "1");
+
+        let tickers: Vec<String> =
+            ["AAPL", "MSFT", "GOOGL", "AMZN", "NVDA"].iter().map(|t| t.to_string()).collect();
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&progress);
+        let options = BatchOptions::new(2).with_progress(move |completed, total| {
+            recorded.lock().unwrap().push((completed, total))
+        });
+        let results = fetch_data_batch(&tickers, None, None, &options).await;
+
+        let mut expected = Vec::new();
+        for ticker in &tickers {
+            expected.push(fetch_data(ticker, None, None).await.unwrap());
+        }
+
+        env::remove_var("NALUFX_OFFLINE");
+
+        let results: Vec<Vec<f64>> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(results, expected);
+        assert_eq!(*progress.lock().unwrap(), vec![(1, 5), (2, 5), (3, 5), (4, 5), (5, 5)]);
+    }
+
+    /// Tests that the chart URL holds the date range and the interval query parameter.
+    #[test]
+    fn test_chart_url_interval() {
+        let start_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end_date = Utc.with_ymd_and_hms(2024, 6, 28, 0, 0, 0).unwrap();
+
+        for (interval, param) in [
+            (Interval::Minute, "interval=1m"),
+            (Interval::Hour, "interval=1h"),
+            (Interval::Day, "interval=1d"),
+            (Interval::Week, "interval=1wk"),
+            (Interval::Month, "interval=1mo"),
+        ] {
+            let url = chart_url("AAPL", Some(start_date), Some(end_date), interval);
+            assert_eq!(
+                url,
+                format!(
+                    "https://query1.finance.yahoo.com/v8/finance/chart/AAPL?period1=1704067200&period2=1719532800&{}",
+                    param
+                )
+            );
+        }
+    }
+
+    /// Returns the `period1` and `period2` query parameters of a chart URL.
+    fn chart_period(url: &str) -> (i64, i64) {
+        let param = |name: &str| {
+            url.split(['?', '&'])
+                .find_map(|pair| pair.strip_prefix(name))
+                .and_then(|value| value.parse().ok())
+                .unwrap()
+        };
+        (param("period1="), param("period2="))
+    }
+
+    /// Tests that the chart URL bounds the period to the configured lookback without a start
+    /// date.
+    #[test]
+    fn test_chart_url_default_lookback() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let end_date = Utc.with_ymd_and_hms(2024, 6, 28, 0, 0, 0).unwrap();
+
+        env::remove_var("NALUFX_HISTORY_LOOKBACK_DAYS");
+        let (start, end) = chart_period(&chart_url("AAPL", None, Some(end_date), Interval::Day));
+        assert_eq!(end, end_date.timestamp());
+        assert_eq!(end - start, 730 * 24 * 60 * 60);
+
+        env::set_var("NALUFX_HISTORY_LOOKBACK_DAYS", "90");
+        let (start, end) = chart_period(&chart_url("AAPL", None, Some(end_date), Interval::Day));
+        env::remove_var("NALUFX_HISTORY_LOOKBACK_DAYS");
+        assert_eq!(end - start, 90 * 24 * 60 * 60);
+
+        // Without an end date, the period ends now.
+        let (start, end) = chart_period(&chart_url("AAPL", None, None, Interval::Day));
+        assert!((end - Utc::now().timestamp()).abs() < 60);
+        assert_eq!(end - start, 730 * 24 * 60 * 60);
+
+        // The whole history starts at the epoch.
+        let url = chart_url("AAPL", Some(chrono::DateTime::UNIX_EPOCH), None, Interval::Day);
+        assert_eq!(chart_period(&url).0, 0);
+    }
+
+    /// Tests that intraday intervals are rejected beyond the range Yahoo Finance serves.
+    #[test]
+    fn test_interval_validate_range() {
+        let end_date = Utc.with_ymd_and_hms(2024, 6, 28, 0, 0, 0).unwrap();
+        let week_ago = end_date - chrono::Duration::days(7);
+        let month_ago = end_date - chrono::Duration::days(30);
+
+        assert!(Interval::Minute.validate_range(Some(week_ago), Some(end_date)).is_ok());
+        assert!(matches!(
+            Interval::Minute.validate_range(Some(month_ago), Some(end_date)),
+            Err(NaluFxError::InvalidInterval(_))
+        ));
+        assert!(Interval::Minute.validate_range(None, Some(end_date)).is_err());
+        assert!(Interval::Hour.validate_range(Some(month_ago), Some(end_date)).is_ok());
+        for interval in [Interval::Day, Interval::Week, Interval::Month] {
+            assert!(interval.validate_range(None, None).is_ok());
+        }
+    }
+
+    /// Tests that `fetch_data_interval` rejects an invalid interval before fetching anything.
+    #[tokio::test]
+    async fn test_fetch_data_interval_rejects_long_intraday_range() {
+        let end_date = Utc::now();
+        let start_date = end_date - chrono::Duration::days(30);
+
+        let result =
+            fetch_data_interval("AAPL", Some(start_date), Some(end_date), Interval::Minute).await;
+
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<NaluFxError>(),
+            Some(NaluFxError::InvalidInterval(_))
+        ));
+    }
+
+    /// Tests that `fetch_data_interval` returns one synthetic price per bar in offline mode.
+    // The lock is held while the test awaits, since the code under test reads the environment.
+    #[allow(clippy::await_holding_lock)]
+    #[tokio::test]
+    async fn test_fetch_data_interval_offline_mode() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        env::set_var("NALUFX_OFFLINE", "1");
+
+        let end_date = Utc::now();
+        let start_date = end_date - chrono::Duration::days(70);
+        let daily =
+            fetch_data_interval("MSFT", Some(start_date), Some(end_date), Interval::Day).await;
+        let weekly =
+            fetch_data_interval("MSFT", Some(start_date), Some(end_date), Interval::Week).await;
+        let closes = fetch_data("MSFT", Some(start_date), Some(end_date)).await;
+
+        env::remove_var("NALUFX_OFFLINE");
+
+        assert_eq!(daily.unwrap(), closes.unwrap());
+        assert_eq!(weekly.unwrap().len(), 10);
+    }
+
+    /// Tests that synthetic prices are reproducible per ticker and differ between tickers.
+    #[test]
+    fn test_synthetic_prices_are_seeded_by_ticker() {
+        let aapl = synthetic_prices("AAPL", 252);
+        assert_eq!(aapl.len(), 252);
+        assert_eq!(aapl, synthetic_prices("aapl", 252));
+        assert_ne!(aapl, synthetic_prices("MSFT", 252));
+        assert!(synthetic_prices("AAPL", 0).is_empty());
+    }
+
+    /// Returns a `YahooClient` whose handshake fails on a server without handshake routes.
+    fn client_without_session(base_url: &str) -> YahooClient {
+        YahooClient::new("nalufx-test-agent")
+            .unwrap()
+            .with_handshake_urls(&format!("{}/cookie", base_url), &format!("{}/crumb", base_url))
+    }
+
+    /// Tests that charts without data are told apart from charts with data and other errors.
+    #[test]
+    fn test_is_empty_chart() {
+        assert!(is_empty_chart(&json!({ "chart": { "result": [], "error": null } })));
+        assert!(is_empty_chart(&json!({ "chart": { "result": null, "error": null } })));
+        assert!(is_empty_chart(&json!({
+            "chart": {
+                "result": null,
+                "error": { "code": "Not Found", "description": "No data found, symbol may be delisted" }
+            }
+        })));
+        assert!(is_empty_chart(&json!({
+            "chart": { "result": [{ "meta": { "symbol": "XYZ" }, "indicators": {} }], "error": null }
+        })));
+
+        assert!(!is_empty_chart(&json!({
+            "chart": { "result": [{ "timestamp": [1720000000] }], "error": null }
+        })));
+        assert!(!is_empty_chart(&json!({
+            "chart": { "result": null, "error": { "code": "Bad Request", "description": "Invalid input" } }
+        })));
+        assert!(!is_empty_chart(&json!({ "finance": { "error": "Too Many Requests" } })));
+    }
+
+    /// Tests that a successful response with an empty `chart.result` is a `NoDataForTicker`.
+    #[tokio::test]
+    async fn test_fetch_chart_quotes_empty_result() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v8/finance/chart/XYZ"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "chart": { "result": [], "error": null } })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client_without_session(&server.uri());
+        let url = format!("{}/v8/finance/chart/XYZ", server.uri());
+        let error = fetch_chart_quotes(&client, "XYZ", &url).await.err().unwrap();
+
+        assert!(is_no_data_error(error.as_ref()));
+        assert!(matches!(
+            error.downcast_ref::<NaluFxError>(),
+            Some(NaluFxError::NoDataForTicker(ticker)) if ticker == "XYZ"
+        ));
+    }
+
+    /// Tests that a `404` with a `Not Found` chart error is a `NoDataForTicker`.
+    #[tokio::test]
+    async fn test_fetch_chart_quotes_not_found() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v8/finance/chart/XYZ"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "chart": {
+                    "result": null,
+                    "error": { "code": "Not Found", "description": "No data found, symbol may be delisted" }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = client_without_session(&server.uri());
+        let url = format!("{}/v8/finance/chart/XYZ", server.uri());
+        let error = fetch_chart_quotes(&client, "XYZ", &url).await.err().unwrap();
+
+        assert!(is_no_data_error(error.as_ref()));
+    }
+
+    /// Tests that a refused connection is a transport error, not a `NoDataForTicker`.
+    #[tokio::test]
+    async fn test_fetch_chart_quotes_connection_refused() {
+        // Bind and release a port, so that nothing listens on it.
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let base_url = format!("http://127.0.0.1:{}", port);
+
+        let client = client_without_session(&base_url);
+        let url = format!("{}/v8/finance/chart/AAPL", base_url);
+        let error = fetch_chart_quotes(&client, "AAPL", &url).await.err().unwrap();
+
+        assert!(!is_no_data_error(error.as_ref()));
+        assert!(matches!(
+            error.downcast_ref::<NaluFxError>(),
+            Some(NaluFxError::HttpRequestError(e)) if e.is_connect()
+        ));
+    }
+}
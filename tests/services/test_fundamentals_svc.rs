@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::NaluFxError;
+    use nalufx::services::fundamentals_svc::{
+        parse_quote_summary, quote_summary_url, Fundamentals,
+    };
+    use serde_json::{json, Value};
+
+    /// A `quoteSummary` response for AAPL, trimmed to a few fields of each module.
+    const QUOTE_SUMMARY_FIXTURE: &str = include_str!("../fixtures/quote_summary_aapl.json");
+
+    #[test]
+    fn test_parse_quote_summary_fixture() {
+        let response: Value = serde_json::from_str(QUOTE_SUMMARY_FIXTURE).unwrap();
+        let fundamentals = parse_quote_summary(&response).unwrap();
+
+        assert_eq!(fundamentals.trailing_pe, Some(35.18417));
+        assert_eq!(fundamentals.price_to_book, Some(52.75216));
+        assert_eq!(fundamentals.return_on_equity, Some(1.60583));
+        assert!((fundamentals.debt_to_equity.unwrap() - 1.51862).abs() < 1e-12);
+        assert_eq!(fundamentals.market_cap, Some(3_514_617_348_096.0));
+    }
+
+    #[test]
+    fn test_parse_quote_summary_partial_fundamentals() {
+        // A loss-making company has no trailing P/E, and Yahoo returns `{}` for missing values.
+        let response = json!({
+            "quoteSummary": {
+                "result": [{
+                    "defaultKeyStatistics": { "priceToBook": {} },
+                    "summaryDetail": { "marketCap": { "raw": 1.2e9, "fmt": "1.2B" } }
+                }],
+                "error": null
+            }
+        });
+
+        assert_eq!(
+            parse_quote_summary(&response).unwrap(),
+            Fundamentals { market_cap: Some(1.2e9), ..Fundamentals::default() }
+        );
+    }
+
+    #[test]
+    fn test_parse_quote_summary_error() {
+        let response = json!({
+            "quoteSummary": {
+                "result": null,
+                "error": { "code": "Not Found", "description": "Quote not found for ticker symbol: XXXX" }
+            }
+        });
+        assert!(matches!(parse_quote_summary(&response), Err(NaluFxError::NaluFxError(_))));
+
+        let response = json!({ "quoteSummary": { "result": [], "error": null } });
+        assert!(matches!(parse_quote_summary(&response), Err(NaluFxError::InvalidData)));
+    }
+
+    #[test]
+    fn test_quote_summary_url_encodes_the_ticker() {
+        assert_eq!(
+            quote_summary_url("AAPL"),
+            "https://query2.finance.yahoo.com/v10/finance/quoteSummary/AAPL\
+             ?modules=defaultKeyStatistics,financialData,summaryDetail"
+        );
+        // A `/` or a space in the ticker stays within its path segment.
+        assert!(quote_summary_url("BRK/B").contains("/quoteSummary/BRK%2FB?"));
+        assert!(quote_summary_url("A B").contains("/quoteSummary/A%20B?"));
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::services::monte_carlo_svc::{bootstrap_metric, simulate_portfolio};
+    use nalufx::utils::risk::calculate_sharpe_ratio;
+    use nalufx::utils::stats::{mean, std_dev};
+
+    #[test]
+    fn test_zero_volatility_compounds_mean_return() {
+        let result = simulate_portfolio(1000.0, 0.001, 0.0, 252, 100, 1);
+
+        let expected = 1000.0 * 1.001_f64.powi(252);
+        assert!((result.median - expected).abs() < 1e-9);
+        assert!((result.percentile_5 - expected).abs() < 1e-9);
+        assert!((result.percentile_95 - expected).abs() < 1e-9);
+        assert!((result.mean - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_simulation_is_reproducible_with_seed() {
+        let first = simulate_portfolio(1000.0, 0.0005, 0.02, 100, 500, 42);
+
+        assert_eq!(first, simulate_portfolio(1000.0, 0.0005, 0.02, 100, 500, 42));
+        assert_ne!(first, simulate_portfolio(1000.0, 0.0005, 0.02, 100, 500, 43));
+    }
+
+    #[test]
+    fn test_percentile_bands_widen_with_volatility() {
+        let calm = simulate_portfolio(1000.0, 0.0, 0.005, 100, 1000, 7);
+        let volatile = simulate_portfolio(1000.0, 0.0, 0.03, 100, 1000, 7);
+
+        assert!(calm.percentile_5 < calm.median && calm.median < calm.percentile_95);
+        assert!(
+            volatile.percentile_95 - volatile.percentile_5 > calm.percentile_95 - calm.percentile_5
+        );
+        // The median of a driftless walk stays close to the initial value.
+        assert!((calm.median - 1000.0).abs() < 20.0);
+    }
+
+    #[test]
+    fn test_simulation_edge_cases() {
+        // No days leaves every path at the initial value.
+        let result = simulate_portfolio(1000.0, 0.01, 0.5, 0, 10, 1);
+        assert_eq!((result.percentile_5, result.percentile_95), (1000.0, 1000.0));
+
+        // At least one path is simulated.
+        let result = simulate_portfolio(1000.0, 0.01, 0.0, 1, 0, 1);
+        assert!((result.median - 1010.0).abs() < 1e-9);
+
+        // A portfolio cannot lose more than its whole value.
+        let result = simulate_portfolio(1000.0, -2.0, 0.0, 5, 10, 1);
+        assert_eq!(result.median, 0.0);
+    }
+
+    /// 250 deterministic daily returns with a positive drift.
+    fn fixed_returns() -> Vec<f64> {
+        (0..250).map(|i| 0.0005 + 0.01 * ((i * 7919) % 101) as f64 / 50.0 - 0.01).collect()
+    }
+
+    #[test]
+    fn test_bootstrap_interval_brackets_the_point_estimate() {
+        let returns = fixed_returns();
+        let (sharpe, lower, upper) = bootstrap_metric(&returns, calculate_sharpe_ratio, 1000, 42);
+
+        assert_eq!(sharpe, calculate_sharpe_ratio(&returns));
+        assert!(lower < sharpe && sharpe < upper, "{} not in ({}, {})", sharpe, lower, upper);
+        assert_eq!(
+            (sharpe, lower, upper),
+            bootstrap_metric(&returns, calculate_sharpe_ratio, 1000, 42)
+        );
+    }
+
+    #[test]
+    fn test_bootstrap_interval_narrows_as_resamples_grow() {
+        // With few resamples, the bounds depend on the seed. More resamples estimate the
+        // interval more precisely, so the spread of its bounds across seeds shrinks.
+        let returns = fixed_returns();
+        let bound_spread = |resamples: usize| {
+            let (lower, upper): (Vec<f64>, Vec<f64>) = (0..20)
+                .map(|seed| {
+                    let (_, lower, upper) =
+                        bootstrap_metric(&returns, calculate_sharpe_ratio, resamples, seed);
+                    (lower, upper)
+                })
+                .unzip();
+            std_dev(&lower, 1).unwrap() + std_dev(&upper, 1).unwrap()
+        };
+
+        let few = bound_spread(20);
+        let many = bound_spread(2000);
+        assert!(many < few / 2.0, "spread {} with 2000 resamples, {} with 20", many, few);
+    }
+
+    #[test]
+    fn test_bootstrap_of_an_empty_series_has_no_interval() {
+        let (point, lower, upper) =
+            bootstrap_metric(&[], |sample| mean(sample).unwrap_or(0.0), 100, 1);
+        assert_eq!(point, 0.0);
+        assert!(lower.is_nan() && upper.is_nan());
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    use crate::ENV_MUTEX;
+    use chrono::{TimeZone, Utc};
+    use nalufx::errors::NaluFxError;
+    use nalufx::services::news_svc::{fetch_recent_headlines, Headline, NewsApiClient};
+    use nalufx::services::sentiment_svc::NEWS_API_KEY_ENV;
+    use reqwest::Client;
+    use serde_json::json;
+    use std::env;
+    use wiremock::{
+        matchers::{header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[tokio::test]
+    async fn test_fetch_headlines_returns_dated_headlines() {
+        let from = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2024, 6, 30, 0, 0, 0).unwrap();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/everything"))
+            .and(query_param("q", "AAPL"))
+            .and(query_param("from", "2024-06-01T00:00:00Z"))
+            .and(query_param("to", "2024-06-30T00:00:00Z"))
+            .and(header("X-Api-Key", "news-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": "ok",
+                "totalResults": 3,
+                "articles": [
+                    {
+                        "title": "Apple unveils its AI features at WWDC",
+                        "publishedAt": "2024-06-10T18:00:00Z",
+                        "source": { "id": null, "name": "Reuters" },
+                        "url": "https://example.com/wwdc"
+                    },
+                    { "title": null, "publishedAt": "2024-06-05T12:00:00Z" },
+                    { "title": "Apple shares hit a record high", "publishedAt": "2024-06-03T14:30:00Z" }
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let news = NewsApiClient::new(Client::new(), &server.uri(), "news-key");
+        let headlines = news.fetch_headlines("aapl", from, to).await.unwrap();
+
+        assert_eq!(
+            headlines,
+            vec![
+                Headline {
+                    published_at: Utc.with_ymd_and_hms(2024, 6, 3, 14, 30, 0).unwrap(),
+                    title: "Apple shares hit a record high".to_string(),
+                    source: None,
+                    url: None,
+                },
+                Headline {
+                    published_at: Utc.with_ymd_and_hms(2024, 6, 10, 18, 0, 0).unwrap(),
+                    title: "Apple unveils its AI features at WWDC".to_string(),
+                    source: Some("Reuters".to_string()),
+                    url: Some("https://example.com/wwdc".to_string()),
+                },
+            ]
+        );
+        assert_eq!(
+            headlines[1].to_string(),
+            "2024-06-10: Apple unveils its AI features at WWDC (Reuters)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_headlines_reports_api_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/everything"))
+            .respond_with(ResponseTemplate::new(429).set_body_json(json!({
+                "status": "error",
+                "code": "rateLimited",
+                "message": "You have made too many requests recently."
+            })))
+            .mount(&server)
+            .await;
+
+        let news = NewsApiClient::new(Client::new(), &server.uri(), "news-key");
+        let now = Utc::now();
+        match news.fetch_headlines("AAPL", now, now).await {
+            Err(NaluFxError::NewsApiError(message)) => {
+                assert!(message.contains("429"), "{}", message);
+                assert!(message.contains("too many requests"), "{}", message);
+            },
+            other => panic!("Expected NewsApiError, got {:?}", other),
+        }
+    }
+
+    // The lock is held while the test awaits, since the code under test reads the environment.
+    #[allow(clippy::await_holding_lock)]
+    #[tokio::test]
+    async fn test_fetch_recent_headlines_without_api_key_is_empty() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        env::remove_var(NEWS_API_KEY_ENV);
+        assert!(NewsApiClient::from_env().is_none());
+
+        let now = Utc::now();
+        assert_eq!(fetch_recent_headlines("AAPL", now, now).await.unwrap(), Vec::new());
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::services::pairs_trading_svc::{pair_signals, PairSignal, PairSignalParams};
+
+    #[test]
+    fn test_pair_signals_threshold_transitions() {
+        let params = PairSignalParams::new(2.0, 0.0).unwrap();
+        let zscore = vec![0.0, 1.9, 2.0, 2.5, 0.8, -0.2, -1.5, -2.1, -3.0, -0.5, 0.1, 1.0];
+        let signals = pair_signals(&zscore, params);
+
+        assert_eq!(
+            signals,
+            vec![
+                PairSignal::Flat,
+                PairSignal::Flat,
+                PairSignal::ShortSpread,
+                PairSignal::ShortSpread,
+                PairSignal::ShortSpread,
+                PairSignal::Flat,
+                PairSignal::Flat,
+                PairSignal::LongSpread,
+                PairSignal::LongSpread,
+                PairSignal::LongSpread,
+                PairSignal::Flat,
+                PairSignal::Flat,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pair_signals_flips_direction_on_same_day() {
+        let params = PairSignalParams::default();
+        let zscore = vec![2.5, -2.5, 2.5];
+        let signals = pair_signals(&zscore, params);
+
+        assert_eq!(
+            signals,
+            vec![PairSignal::ShortSpread, PairSignal::LongSpread, PairSignal::ShortSpread,]
+        );
+    }
+
+    #[test]
+    fn test_pair_signals_non_zero_exit_band() {
+        let params = PairSignalParams::new(1.5, 0.5).unwrap();
+        let zscore = vec![1.6, 0.7, 0.5, -1.6, -0.6, -0.4];
+        let signals = pair_signals(&zscore, params);
+
+        assert_eq!(
+            signals,
+            vec![
+                PairSignal::ShortSpread,
+                PairSignal::ShortSpread,
+                PairSignal::Flat,
+                PairSignal::LongSpread,
+                PairSignal::LongSpread,
+                PairSignal::Flat,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pair_signals_nan_holds_position() {
+        let signals = pair_signals(&[2.2, f64::NAN, -0.1], PairSignalParams::default());
+        assert_eq!(
+            signals,
+            vec![PairSignal::ShortSpread, PairSignal::ShortSpread, PairSignal::Flat]
+        );
+    }
+
+    #[test]
+    fn test_pair_signal_params_validation() {
+        assert!(PairSignalParams::new(2.0, 0.0).is_ok());
+        assert!(PairSignalParams::new(0.0, 0.0).is_err());
+        assert!(PairSignalParams::new(1.0, 1.0).is_err());
+        assert!(PairSignalParams::new(1.0, -1.5).is_err());
+        assert!(PairSignalParams::new(f64::NAN, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_pair_signals_empty_input() {
+        assert!(pair_signals(&[], PairSignalParams::default()).is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use nalufx::errors::NaluFxError;
+    use nalufx::services::period_comparison_svc::{
+        compare_periods, compare_series_periods, format_period_comparison, period_metrics,
+    };
+
+    /// Builds a daily series of closes starting on January 1, 2024, from a sequence of daily
+    /// returns.
+    fn series_from_returns(returns: &[f64]) -> Vec<(DateTime<Utc>, f64)> {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut close = 100.0;
+        let mut series = vec![(start, close)];
+        for (day, daily_return) in returns.iter().enumerate() {
+            close *= 1.0 + daily_return;
+            series.push((start + Duration::days(day as i64 + 1), close));
+        }
+        series
+    }
+
+    /// Returns the first and last dates of the closes between the `from`-th and `to`-th days.
+    fn range(
+        series: &[(DateTime<Utc>, f64)],
+        from: usize,
+        to: usize,
+    ) -> (DateTime<Utc>, DateTime<Utc>) {
+        (series[from].0, series[to].0)
+    }
+
+    /// Tests that two segments of differing volatility are measured side by side.
+    #[test]
+    fn test_compare_series_periods_differing_volatility() {
+        // A calm segment of 60 days followed by a volatile segment of 60 days
+        let calm = (0..60).map(|day| if day % 2 == 0 { 0.005 } else { -0.004 });
+        let volatile = (0..60).map(|day| if day % 2 == 0 { 0.04 } else { -0.035 });
+        let returns: Vec<f64> = calm.chain(volatile).collect();
+        let series = series_from_returns(&returns);
+
+        let comparison =
+            compare_series_periods("AAPL", &series, range(&series, 0, 60), range(&series, 60, 120));
+
+        assert_eq!(comparison.ticker, "AAPL");
+        let calm = comparison.period_a.unwrap();
+        let volatile = comparison.period_b.unwrap();
+        assert_eq!(calm.observations, 61);
+        assert_eq!(volatile.observations, 61);
+        assert!(volatile.volatility > 5.0 * calm.volatility);
+        assert!(volatile.max_drawdown > calm.max_drawdown);
+        assert!((calm.total_return - (series[60].1 / series[0].1 - 1.0)).abs() < 1e-12);
+        assert!((volatile.total_return - (series[120].1 / series[60].1 - 1.0)).abs() < 1e-12);
+    }
+
+    /// Tests that a period without data is `None`, while the other period is still measured.
+    #[test]
+    fn test_compare_series_periods_empty_range() {
+        let series = series_from_returns(&[0.01, -0.02, 0.015, 0.005]);
+        let before = (series[0].0 - Duration::days(30), series[0].0 - Duration::days(1));
+
+        let comparison = compare_series_periods("AAPL", &series, before, range(&series, 0, 4));
+
+        assert_eq!(comparison.period_a, None);
+        assert!(comparison.period_b.is_some());
+        assert!(period_metrics(&series, range(&series, 2, 2)).is_none());
+        assert!(period_metrics(&[], range(&series, 0, 4)).is_none());
+    }
+
+    /// Tests that a period without data is formatted as `n/a`.
+    #[test]
+    fn test_format_period_comparison() {
+        let series = series_from_returns(&[0.1, -0.2]);
+        let before = (series[0].0 - Duration::days(30), series[0].0 - Duration::days(1));
+        let comparison = compare_series_periods("AAPL", &series, range(&series, 0, 2), before);
+
+        let table = format_period_comparison(&comparison);
+
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].contains("Period A") && lines[0].contains("Period B"));
+        assert!(lines[2].contains("2024-01-01 to 2024-01-03"));
+        assert!(lines[3].contains("-12.00%") && lines[3].contains("n/a"));
+        assert!(lines[5].contains("20.00%"));
+    }
+
+    /// Tests that a period ending before it starts is rejected before any data is fetched.
+    #[tokio::test]
+    async fn test_compare_periods_invalid_range() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let valid = (start, start + Duration::days(30));
+        let reversed = (start + Duration::days(30), start);
+
+        let result = compare_periods("AAPL", valid, reversed).await;
+
+        assert!(matches!(result, Err(NaluFxError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use nalufx::errors::NaluFxError;
+    use nalufx::services::fetch_data_svc::parse_dividends;
+    use nalufx::services::processing_svc::{
+        calculate_cagr, calculate_daily_returns, calculate_daily_returns_with_policy,
+        calculate_dividend_yield, calculate_total_return, calculate_volatility, years_between,
+        MissingClosePolicy,
+    };
+    use serde_json::Value;
+
+    /// A chart response for KO with two quarterly dividends of 0.485.
+    const CHART_DIVIDENDS_FIXTURE: &str = include_str!("../fixtures/chart_dividends_ko.json");
+
+    /// Returns the parsed fixture and the named series of its `indicators`.
+    fn fixture_series(indicator: &str, field: &str) -> (Value, Vec<f64>) {
+        let response: Value = serde_json::from_str(CHART_DIVIDENDS_FIXTURE).unwrap();
+        let series = response["chart"]["result"][0]["indicators"][indicator][0][field]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|value| value.as_f64().unwrap())
+            .collect();
+        (response, series)
+    }
+
+    #[test]
+    fn test_calculate_volatility_known_series() {
+        // Mean 0.0, squared deviations sum to 4 * 0.0001, so the sample variance is 0.0004 / 3.
+        let daily_returns = vec![0.01, -0.01, 0.01, -0.01];
+        let expected_daily = (0.0004_f64 / 3.0).sqrt();
+
+        let daily = calculate_volatility(&daily_returns, false).unwrap();
+        assert!((daily - expected_daily).abs() < 1e-12);
+
+        let annualized = calculate_volatility(&daily_returns, true).unwrap();
+        assert!((annualized - expected_daily * 252.0_f64.sqrt()).abs() < 1e-12);
+        assert!((annualized - 0.18330302779823363).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_calculate_volatility_single_return() {
+        assert_eq!(calculate_volatility(&[0.05], false).unwrap(), 0.0);
+        assert_eq!(calculate_volatility(&[0.05], true).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_calculate_volatility_empty_input() {
+        assert!(matches!(calculate_volatility(&[], true), Err(NaluFxError::EmptyInput)));
+    }
+
+    #[test]
+    fn test_calculate_daily_returns_forward_fills_nan_close() {
+        let closes = vec![100.0, 102.0, f64::NAN, 104.04, 103.0];
+
+        let returns = calculate_daily_returns(&closes);
+
+        assert_eq!(returns.len(), closes.len() - 1);
+        assert!(returns.iter().all(|r| !r.is_nan()));
+        assert!((returns[0] - 0.02).abs() < 1e-12);
+        // The missing close is filled with 102.0, so its day has no return.
+        assert_eq!(returns[1], 0.0);
+        assert!((returns[2] - 0.02).abs() < 1e-12);
+        assert!((returns[3] - (103.0 / 104.04 - 1.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_calculate_daily_returns_skips_nan_close() {
+        let closes = vec![100.0, 102.0, f64::NAN, 104.04, 103.0];
+
+        let returns = calculate_daily_returns_with_policy(&closes, MissingClosePolicy::Skip);
+
+        assert_eq!(returns.len(), closes.len() - 2);
+        assert!(returns.iter().all(|r| !r.is_nan()));
+        // The return after the gap spans it.
+        assert!((returns[1] - 0.02).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_calculate_daily_returns_leading_and_invalid_closes() {
+        // Leading missing closes cannot be filled, and zero or infinite closes are missing.
+        let closes = vec![f64::NAN, 0.0, 100.0, f64::INFINITY, 110.0];
+
+        let filled = calculate_daily_returns_with_policy(&closes, MissingClosePolicy::ForwardFill);
+        assert_eq!(filled.len(), 2);
+        assert_eq!(filled[0], 0.0);
+        assert!((filled[1] - 0.1).abs() < 1e-12);
+
+        let skipped = calculate_daily_returns_with_policy(&closes, MissingClosePolicy::Skip);
+        assert_eq!(skipped.len(), 1);
+
+        assert!(calculate_daily_returns(&[f64::NAN, f64::NAN]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_dividends_fixture() {
+        let (response, _) = fixture_series("quote", "close");
+        let dividends = parse_dividends(&response).unwrap();
+
+        // The events are keyed by timestamp, and returned in chronological order.
+        assert_eq!(
+            dividends,
+            vec![
+                (Utc.with_ymd_and_hms(2024, 3, 14, 13, 30, 0).unwrap(), 0.485),
+                (Utc.with_ymd_and_hms(2024, 6, 14, 13, 30, 0).unwrap(), 0.485),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_calculate_dividend_yield_fixture() {
+        let (response, closes) = fixture_series("quote", "close");
+        let dividends = parse_dividends(&response).unwrap();
+        let price = *closes.last().unwrap();
+
+        // (0.485 + 0.485) / 63.65
+        let dividend_yield = calculate_dividend_yield(&dividends, price);
+        assert!((dividend_yield - 0.970 / 63.65).abs() < 1e-12);
+
+        assert_eq!(calculate_dividend_yield(&[], price), 0.0);
+        assert!(calculate_dividend_yield(&dividends, 0.0).is_nan());
+    }
+
+    #[test]
+    fn test_calculate_total_return_includes_dividends() {
+        let (_, closes) = fixture_series("quote", "close");
+        let (_, adjusted_closes) = fixture_series("adjclose", "adjclose");
+
+        let price_return = calculate_total_return(&closes);
+        let total_return = calculate_total_return(&adjusted_closes);
+        assert!((price_return - (63.65 / 60.0 - 1.0)).abs() < 1e-12);
+        assert!((total_return - (63.65 / 58.8 - 1.0)).abs() < 1e-12);
+
+        // The reinvested dividends add to the return of the unadjusted closes.
+        assert!(total_return > price_return);
+    }
+
+    #[test]
+    fn test_calculate_total_return_edge_cases() {
+        assert_eq!(calculate_total_return(&[]), 0.0);
+        assert_eq!(calculate_total_return(&[42.0]), 0.0);
+        assert!(calculate_total_return(&[0.0, 42.0]).is_nan());
+    }
+
+    #[test]
+    fn test_calculate_cagr_of_a_two_year_doubling() {
+        let start_date = Utc.with_ymd_and_hms(2022, 1, 3, 0, 0, 0).unwrap();
+        let end_date = Utc.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap();
+        let years = years_between(start_date, end_date);
+        assert!((years - 2.0).abs() < 1e-2);
+
+        let cagr = calculate_cagr(1_000.0, 2_000.0, years).unwrap();
+        assert!((cagr - 0.414).abs() < 1e-3, "{}", cagr);
+        assert!((calculate_cagr(1_000.0, 2_000.0, 2.0).unwrap() - 0.41421356).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_calculate_cagr_of_a_sub_year_period_is_the_total_return() {
+        let cagr = calculate_cagr(100.0, 110.0, 0.25).unwrap();
+        assert!((cagr - 0.1).abs() < 1e-12);
+        assert!((calculate_cagr(100.0, 110.0, 1.0).unwrap() - 0.1).abs() < 1e-12);
+        assert!((calculate_cagr(100.0, 0.0, 3.0).unwrap() + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_calculate_cagr_rejects_invalid_values() {
+        for (start_value, end_value, years) in [
+            (0.0, 200.0, 2.0),
+            (-100.0, 200.0, 2.0),
+            (100.0, -1.0, 2.0),
+            (100.0, 200.0, 0.0),
+            (100.0, 200.0, -1.0),
+            (f64::NAN, 200.0, 2.0),
+        ] {
+            assert!(
+                matches!(
+                    calculate_cagr(start_value, end_value, years),
+                    Err(NaluFxError::InvalidData)
+                ),
+                "({}, {}, {}) should be rejected",
+                start_value,
+                end_value,
+                years
+            );
+        }
+    }
+}
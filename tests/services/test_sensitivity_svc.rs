@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::AllocationError;
+    use nalufx::services::sensitivity_svc::{sensitivity_analysis, AllocationInputs};
+    use nalufx::utils::calculations::{AllocationOptions, Objective};
+
+    /// Builds the inputs of an allocation over 40 days of history with the given objective.
+    fn inputs(objective: Objective) -> AllocationInputs {
+        let days = 0..40;
+        AllocationInputs {
+            daily_returns: days
+                .clone()
+                .map(|day| 0.002 + 0.01 * (day as f64 * 0.7).sin())
+                .collect(),
+            cash_flows: days.clone().map(|day| 1000.0 + 5.0 * day as f64).collect(),
+            market_indices: days.clone().map(|day| 1.0 + 0.01 * day as f64).collect(),
+            fund_characteristics: days.map(|day| 0.5 + 0.01 * day as f64).collect(),
+            options: AllocationOptions { objective, ..Default::default() },
+        }
+    }
+
+    /// Tests that a zero perturbation leaves the allocation of every day unchanged.
+    #[test]
+    fn test_sensitivity_analysis_zero_perturbation() {
+        let result = sensitivity_analysis(&inputs(Objective::MaxSharpe), 0.0, 5).unwrap();
+
+        assert_eq!(result.base.len(), 5);
+        assert_eq!(result.shifted_up, result.base);
+        assert_eq!(result.shifted_down, result.base);
+        assert_eq!(result.changes, vec![0.0; 5]);
+        assert_eq!(result.max_change(), 0.0);
+    }
+
+    /// Tests that the allocation of an objective that ignores the returns does not move.
+    #[test]
+    fn test_sensitivity_analysis_min_variance_is_insensitive() {
+        let result = sensitivity_analysis(&inputs(Objective::MinVariance), 0.5, 5).unwrap();
+
+        assert_eq!(result.perturbation_pct, 0.5);
+        assert_eq!(result.changes.len(), 5);
+        assert_eq!(result.max_change(), 0.0);
+    }
+
+    /// Tests that a non-finite perturbation is rejected.
+    #[test]
+    fn test_sensitivity_analysis_non_finite_perturbation() {
+        let result = sensitivity_analysis(&inputs(Objective::MaxSharpe), f64::NAN, 5);
+
+        assert_eq!(result, Err(AllocationError::InvalidData));
+    }
+
+    /// Tests that the errors of the allocation are returned.
+    #[test]
+    fn test_sensitivity_analysis_insufficient_data() {
+        let mut inputs = inputs(Objective::MaxSharpe);
+        inputs.daily_returns.truncate(1);
+        inputs.cash_flows.truncate(1);
+        inputs.market_indices.truncate(1);
+        inputs.fund_characteristics.truncate(1);
+
+        assert!(sensitivity_analysis(&inputs, 0.1, 5).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use chrono::{Duration, Utc};
+    use nalufx::errors::NaluFxError;
+    use nalufx::services::sentiment_svc::{
+        LlmSentimentProvider, MockSentimentProvider, NewsApiSentimentProvider, SentimentProvider,
+        NEUTRAL_SENTIMENT,
+    };
+    use nalufx_llms::{errors::LlmError, llms::LLM};
+    use reqwest::Client;
+    use serde_json::{json, Value};
+    use wiremock::{
+        matchers::{header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    // An LLM that answers every prompt with the same content.
+    struct CannedLLM(&'static str);
+
+    #[async_trait]
+    impl LLM for CannedLLM {
+        async fn send_request(
+            &self,
+            _client: &Client,
+            _api_key: &str,
+            _prompt: &str,
+            _max_tokens: usize,
+        ) -> Result<Value, LlmError> {
+            Ok(json!({ "choices": [{ "message": { "content": self.0 } }] }))
+        }
+
+        fn provider(&self) -> &str {
+            "mock"
+        }
+
+        fn model(&self) -> &str {
+            "mock-model"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_provider_scores_every_day() {
+        let scores = MockSentimentProvider.daily_sentiment("AAPL", 30).await.unwrap();
+        assert_eq!(scores.len(), 30);
+        assert!(scores.iter().all(|score| (0.0..=1.0).contains(score)));
+    }
+
+    #[tokio::test]
+    async fn test_llm_provider_parses_the_scores() {
+        let llm = CannedLLM("Here are the scores:\n[0.2, 0.5, 1.3]");
+        let provider = LlmSentimentProvider::new(Box::new(llm), Client::new(), "test-key");
+
+        let scores = provider.daily_sentiment("AAPL", 3).await.unwrap();
+        assert_eq!(scores, vec![0.2, 0.5, 1.0]);
+
+        assert!(matches!(
+            provider.daily_sentiment("AAPL", 2).await,
+            Err(NaluFxError::SentimentAnalysisError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_news_provider_scores_the_headlines_of_each_day() {
+        let now = Utc::now();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/everything"))
+            .and(query_param("q", "AAPL"))
+            .and(header("X-Api-Key", "news-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": "ok",
+                "totalResults": 3,
+                "articles": [
+                    { "title": "Apple shares surge to a record high", "publishedAt": now },
+                    { "title": "Apple to hold its annual meeting", "publishedAt": now },
+                    {
+                        "title": "Apple misses estimates as iPhone sales drop",
+                        "publishedAt": now - Duration::days(1)
+                    },
+                    { "title": null, "publishedAt": now - Duration::days(2) }
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = NewsApiSentimentProvider::new(Client::new(), &server.uri(), "news-key");
+        let scores = provider.daily_sentiment("aapl", 4).await.unwrap();
+
+        assert_eq!(scores, vec![NEUTRAL_SENTIMENT, NEUTRAL_SENTIMENT, 0.0, 0.75]);
+    }
+
+    #[tokio::test]
+    async fn test_news_provider_reports_api_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/everything"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+                "status": "error",
+                "code": "apiKeyInvalid",
+                "message": "Your API key is invalid or incorrect."
+            })))
+            .mount(&server)
+            .await;
+
+        let provider = NewsApiSentimentProvider::new(Client::new(), &server.uri(), "bad-key");
+        match provider.daily_sentiment("AAPL", 5).await {
+            Err(NaluFxError::SentimentAnalysisError(message)) => {
+                assert!(message.contains("401"), "{}", message);
+                assert!(message.contains("API key is invalid"), "{}", message);
+            },
+            other => panic!("Expected SentimentAnalysisError, got {:?}", other),
+        }
+    }
+}
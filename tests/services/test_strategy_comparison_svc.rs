@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::services::{
+        processing_svc::calculate_daily_returns,
+        strategy_comparison_svc::{
+            compare_strategies, constrained_mean_variance_weights, format_comparison_table,
+            max_risk_contribution_deviation, risk_contributions, risk_parity_weights,
+            solve_risk_parity, RiskParityOptions, Strategy,
+        },
+        synthetic_data_svc::synthetic_prices,
+    };
+    use nalufx::utils::{allocation::Constraints, risk::TRADING_DAYS_PER_YEAR};
+    use ndarray::{array, Array1, Array2};
+
+    const TICKERS: [&str; 4] = ["AAA", "BBB", "CCC", "DDD"];
+
+    /// Builds a matrix of synthetic daily returns, with one row per day and one column per
+    /// ticker.
+    fn synthetic_returns(num_days: usize) -> Array2<f64> {
+        let columns: Vec<Vec<f64>> = TICKERS
+            .iter()
+            .map(|ticker| calculate_daily_returns(&synthetic_prices(ticker, num_days + 1)))
+            .collect();
+        Array2::from_shape_fn((num_days, TICKERS.len()), |(day, asset)| columns[asset][day])
+    }
+
+    #[test]
+    fn test_equal_weight_versus_single_asset() {
+        let returns = synthetic_returns(500);
+        let results =
+            compare_strategies(&returns, &[Strategy::EqualWeight, Strategy::SingleAsset(0)]);
+        assert_eq!(results.len(), 2);
+
+        let (equal_weight, single_asset) = (&results[0], &results[1]);
+        assert_eq!(equal_weight.strategy, Strategy::EqualWeight);
+        assert_eq!(equal_weight.weights, vec![0.25; 4]);
+        assert_eq!(single_asset.weights, vec![1.0, 0.0, 0.0, 0.0]);
+
+        // The single-asset strategy has the return of its asset.
+        let first_asset = returns.column(0);
+        let expected_return = first_asset.mean().unwrap() * TRADING_DAYS_PER_YEAR;
+        assert!((single_asset.annualized_return - expected_return).abs() < 1e-12);
+
+        // Spreading the portfolio over independent assets lowers its volatility.
+        assert!(equal_weight.annualized_volatility < single_asset.annualized_volatility);
+        for result in &results {
+            let expected_sharpe = result.annualized_return / result.annualized_volatility;
+            assert!((result.sharpe_ratio - expected_sharpe).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_optimized_strategies_weights_sum_to_one() {
+        let returns = synthetic_returns(250);
+        let results = compare_strategies(&returns, &[Strategy::MeanVariance, Strategy::RiskParity]);
+        assert_eq!(results.len(), 2);
+        for result in results {
+            assert_eq!(result.weights.len(), TICKERS.len());
+            assert!((result.weights.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+            assert!(result.sharpe_ratio.is_finite());
+        }
+    }
+
+    #[test]
+    fn test_risk_parity_weights_inverse_volatility() {
+        // For uncorrelated assets, risk parity weights are proportional to 1 / volatility.
+        let cov = array![[0.01, 0.0], [0.0, 0.04]];
+        let weights = risk_parity_weights(&cov);
+        assert!((weights[0] - 2.0 / 3.0).abs() < 1e-9);
+        assert!((weights[1] - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_risk_parity_weights_equal_risk_contributions() {
+        let cov = array![[0.04, 0.006, 0.002], [0.006, 0.09, -0.01], [0.002, -0.01, 0.01]];
+        let weights = risk_parity_weights(&cov);
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        let marginal = cov.dot(&Array1::from(weights.clone()));
+        let contributions: Vec<f64> = weights.iter().zip(&marginal).map(|(w, m)| w * m).collect();
+        for contribution in &contributions {
+            assert!((contribution - contributions[0]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_solved_two_asset_risk_contributions_are_equal() {
+        let cov = array![[0.04, 0.012], [0.012, 0.09]];
+        let options = RiskParityOptions::default();
+        let solution = solve_risk_parity(&cov, &options);
+        assert!(solution.converged);
+        assert!(solution.sweeps <= options.max_sweeps);
+
+        let contributions = risk_contributions(&solution.weights, &cov);
+        assert!((contributions.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        for contribution in &contributions {
+            assert!((contribution - 0.5).abs() < 1e-9);
+        }
+        assert!(solution.max_deviation < 1e-9);
+        assert_eq!(solution.max_deviation, max_risk_contribution_deviation(&contributions));
+    }
+
+    #[test]
+    fn test_risk_parity_reports_poor_convergence() {
+        let cov = array![[0.04, 0.006, 0.002], [0.006, 0.09, -0.01], [0.002, -0.01, 0.01]];
+        let solution =
+            solve_risk_parity(&cov, &RiskParityOptions { tolerance: 0.0, max_sweeps: 1 });
+        assert!(!solution.converged);
+        assert_eq!(solution.sweeps, 1);
+        assert!(solution.max_deviation > 1e-6);
+
+        // Equal weights are far from risk parity for assets of different volatility.
+        let contributions = risk_contributions(&[1.0 / 3.0; 3], &cov);
+        assert!(max_risk_contribution_deviation(&contributions) > 0.1);
+        assert!(risk_contributions(&[0.5, 0.5], &cov).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let returns = synthetic_returns(20);
+        let results = compare_strategies(&returns, &[Strategy::SingleAsset(4)]);
+        assert!(results.is_empty());
+
+        assert!(compare_strategies(&Array2::zeros((1, 2)), &[Strategy::EqualWeight]).is_empty());
+        assert!(compare_strategies(&Array2::zeros((10, 0)), &[Strategy::EqualWeight]).is_empty());
+    }
+
+    #[test]
+    fn test_format_comparison_table() {
+        let returns = synthetic_returns(60);
+        let results =
+            compare_strategies(&returns, &[Strategy::EqualWeight, Strategy::SingleAsset(2)]);
+        let table = format_comparison_table(&results);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[2].starts_with("| Equal Weight "));
+        assert!(lines[3].starts_with("| Single Asset #3 "));
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+    }
+
+    #[test]
+    fn test_constrained_mean_variance_weights_respect_the_cap() {
+        let constraints = Constraints { max_weight: 0.4, ..Constraints::default() };
+        let mean_returns = array![0.05, 0.01, 0.01, 0.005, 0.002];
+        let variances = array![0.01, 0.02, 0.02, 0.03, 0.04];
+
+        let weights =
+            constrained_mean_variance_weights(&mean_returns, &variances, &constraints).unwrap();
+        assert!(weights.iter().all(|&w| w <= 0.4 + 1e-12));
+        assert!((weights[0] - 0.4).abs() < 1e-12);
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        // Two assets cannot be capped at 0.4 each.
+        assert!(constrained_mean_variance_weights(
+            &array![0.05, 0.01],
+            &array![0.01, 0.02],
+            &constraints
+        )
+        .is_err());
+    }
+}
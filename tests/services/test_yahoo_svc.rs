@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::services::yahoo_svc::YahooClient;
+    use wiremock::{
+        matchers::{header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    const USER_AGENT: &str = "nalufx-test-agent";
+
+    /// Mounts the two steps of the handshake: a cookie on `/cookie` and a crumb on `/crumb`.
+    async fn mount_handshake(server: &MockServer, crumb: &str, expected_handshakes: u64) {
+        Mock::given(method("GET"))
+            .and(path("/cookie"))
+            .respond_with(
+                ResponseTemplate::new(404)
+                    .append_header("Set-Cookie", "A3=session-token; Domain=.yahoo.com; Path=/")
+                    .append_header("Set-Cookie", "B=consent; Path=/; Secure"),
+            )
+            .expect(expected_handshakes)
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/crumb"))
+            .and(header("cookie", "A3=session-token; B=consent"))
+            .and(header("user-agent", USER_AGENT))
+            .respond_with(ResponseTemplate::new(200).set_body_string(crumb))
+            .expect(expected_handshakes)
+            .mount(server)
+            .await;
+    }
+
+    fn client(server: &MockServer) -> YahooClient {
+        YahooClient::new(USER_AGENT).unwrap().with_handshake_urls(
+            &format!("{}/cookie", server.uri()),
+            &format!("{}/crumb", server.uri()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_handshake_is_performed_once_and_reused() {
+        let server = MockServer::start().await;
+        mount_handshake(&server, "abc123", 1).await;
+        Mock::given(method("GET"))
+            .and(path("/v8/finance/chart/AAPL"))
+            .and(query_param("interval", "1d"))
+            .and(query_param("crumb", "abc123"))
+            .and(header("cookie", "A3=session-token; B=consent"))
+            .and(header("user-agent", USER_AGENT))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let url = format!("{}/v8/finance/chart/AAPL?interval=1d", server.uri());
+        for _ in 0..2 {
+            let response = client.get(&url).await.unwrap();
+            assert_eq!(response.status(), 200);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_response_repeats_the_handshake() {
+        let server = MockServer::start().await;
+        mount_handshake(&server, "abc123", 2).await;
+        Mock::given(method("GET"))
+            .and(path("/v10/finance/quoteSummary/AAPL"))
+            .respond_with(ResponseTemplate::new(401))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v10/finance/quoteSummary/AAPL"))
+            .and(query_param("crumb", "abc123"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/v10/finance/quoteSummary/AAPL", server.uri());
+        let response = client(&server).get(&url).await.unwrap();
+
+        assert_eq!(response.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_failed_handshake_sends_request_without_session() {
+        let server = MockServer::start().await;
+        // No `Set-Cookie` header, so the crumb is never requested.
+        Mock::given(method("GET"))
+            .and(path("/cookie"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/crumb"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("unused"))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v8/finance/chart/AAPL"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/v8/finance/chart/AAPL", server.uri());
+        let response = client(&server).get(&url).await.unwrap();
+
+        assert_eq!(response.status(), 200);
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests.last().unwrap().url.query_pairs().all(|(key, _)| key != "crumb"));
+    }
+}
@@ -0,0 +1,5 @@
+/// This module contains the tests for `kiosk.rs`.
+pub mod test_kiosk;
+
+/// This module contains the tests for `quotes.rs`.
+pub mod test_quotes;
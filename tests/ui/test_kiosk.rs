@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use nalufx_ui::controllers::kiosk::{
+        next_kiosk_page, next_page, KioskConfig, DEFAULT_KIOSK_INTERVAL_SECS,
+    };
+
+    #[test]
+    fn test_next_kiosk_page() {
+        for (current, count, expected) in [
+            (0, 4, 1),
+            (1, 4, 2),
+            (2, 4, 3),
+            (3, 4, 0),
+            (0, 1, 0),
+            // A page past the end or before the start moves back to the first page.
+            (7, 4, 0),
+            (-1, 4, 0),
+            // Without pages, there is nothing to advance to.
+            (0, 0, 0),
+            (3, 0, 0),
+            (-2, 0, 0),
+            (0, -1, 0),
+        ] {
+            assert_eq!(next_kiosk_page(current, count), expected, "({}, {})", current, count);
+        }
+    }
+
+    #[test]
+    fn test_next_page_cycles_all_pages_without_selection() {
+        assert_eq!(next_page(0, 4, &[]), 1);
+        assert_eq!(next_page(2, 4, &[]), 3);
+        assert_eq!(next_page(3, 4, &[]), 0);
+        assert_eq!(next_page(0, 0, &[]), 0);
+    }
+
+    #[test]
+    fn test_next_page_only_advances_among_selected_pages() {
+        let pages = [4, 1, 2];
+
+        assert_eq!(next_page(1, 6, &pages), 2);
+        assert_eq!(next_page(2, 6, &pages), 4);
+        assert_eq!(next_page(4, 6, &pages), 1);
+        // A page outside the selection moves on to the next selected page.
+        assert_eq!(next_page(0, 6, &pages), 1);
+        assert_eq!(next_page(5, 6, &pages), 1);
+    }
+
+    #[test]
+    fn test_next_page_ignores_missing_pages() {
+        assert_eq!(next_page(1, 3, &[1, 1, 7, -1]), 1);
+        assert_eq!(next_page(0, 3, &[1, 7]), 1);
+        // Without any existing selected page, every page is shown.
+        assert_eq!(next_page(2, 3, &[5, 9]), 0);
+    }
+
+    #[test]
+    fn test_kiosk_config_default_preserves_previous_behavior() {
+        let config = KioskConfig::default();
+
+        assert_eq!(config.interval_secs, DEFAULT_KIOSK_INTERVAL_SECS);
+        assert_eq!(DEFAULT_KIOSK_INTERVAL_SECS, 4);
+        assert!(config.pages.is_empty());
+    }
+}
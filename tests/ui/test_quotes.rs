@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use nalufx_ui::controllers::quotes::{parse_watchlist, quote_row, refresh_rows, QuoteRow};
+
+    fn row(ticker: &str, price: &str, change: &str, stale: bool) -> QuoteRow {
+        QuoteRow {
+            ticker: ticker.to_string(),
+            price: price.to_string(),
+            change: change.to_string(),
+            stale,
+        }
+    }
+
+    #[test]
+    fn test_parse_watchlist() {
+        assert_eq!(parse_watchlist(" aapl, MSFT,,aapl ,tsla "), vec!["AAPL", "MSFT", "TSLA"]);
+        assert!(parse_watchlist(" , ").is_empty());
+    }
+
+    #[test]
+    fn test_quote_row_formats_price_and_change() {
+        assert_eq!(
+            quote_row("AAPL", &[98.0, 100.0, 102.5]),
+            Some(row("AAPL", "102.50", "+2.50%", false))
+        );
+        assert_eq!(
+            quote_row("MSFT", &[400.0, 390.0]),
+            Some(row("MSFT", "390.00", "-2.50%", false))
+        );
+    }
+
+    #[test]
+    fn test_quote_row_without_previous_close() {
+        assert_eq!(quote_row("AAPL", &[101.234]), Some(row("AAPL", "101.23", "-", false)));
+        assert_eq!(quote_row("AAPL", &[0.0, 101.0]), Some(row("AAPL", "101.00", "-", false)));
+    }
+
+    #[test]
+    fn test_quote_row_rejects_unusable_prices() {
+        assert_eq!(quote_row("AAPL", &[]), None);
+        assert_eq!(quote_row("AAPL", &[100.0, f64::NAN]), None);
+        assert_eq!(quote_row("AAPL", &[100.0, -1.0]), None);
+    }
+
+    #[test]
+    fn test_refresh_rows_updates_quotes() {
+        let results = vec![
+            ("AAPL".to_string(), Ok(vec![100.0, 101.0])),
+            ("MSFT".to_string(), Ok(vec![400.0])),
+        ];
+        assert_eq!(
+            refresh_rows(&[], &results),
+            vec![row("AAPL", "101.00", "+1.00%", false), row("MSFT", "400.00", "-", false)]
+        );
+    }
+
+    #[test]
+    fn test_refresh_rows_keeps_previous_quotes_as_stale() {
+        let previous = vec![row("AAPL", "101.00", "+1.00%", false)];
+        let results = vec![
+            ("AAPL".to_string(), Err("timeout".to_string())),
+            ("MSFT".to_string(), Err("timeout".to_string())),
+            ("TSLA".to_string(), Ok(Vec::new())),
+        ];
+        assert_eq!(
+            refresh_rows(&previous, &results),
+            vec![
+                row("AAPL", "101.00", "+1.00%", true),
+                row("MSFT", "-", "-", true),
+                row("TSLA", "-", "-", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_refresh_rows_clears_stale_quotes_on_success() {
+        let previous = vec![row("AAPL", "101.00", "+1.00%", true)];
+        let results = vec![("AAPL".to_string(), Ok(vec![101.0, 99.99]))];
+        assert_eq!(refresh_rows(&previous, &results), vec![row("AAPL", "99.99", "-1.00%", false)]);
+    }
+}
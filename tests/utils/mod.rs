@@ -6,3 +6,51 @@
 
 /// This module contains the tests for `input.rs`.
 pub mod test_input;
+
+/// This module contains the tests for `stats.rs`.
+pub mod test_stats;
+
+/// This module contains the tests for `risk.rs`.
+pub mod test_risk;
+
+/// This module contains the tests for `indicators.rs`.
+pub mod test_indicators;
+
+/// This module contains the tests for `validation.rs`.
+pub mod test_validation;
+
+/// This module contains the tests for `reports.rs`.
+pub mod test_reports;
+
+/// This module contains the tests for `metrics.rs`.
+pub mod test_metrics;
+
+/// This module contains the tests for `resample.rs`.
+pub mod test_resample;
+
+/// This module contains the tests for `portfolio.rs`.
+pub mod test_portfolio;
+
+/// This module contains the tests for `batch.rs`.
+pub mod test_batch;
+
+/// This module contains the tests for `args.rs`.
+pub mod test_args;
+
+/// This module contains the tests for `trading.rs`.
+pub mod test_trading;
+
+/// This module contains the tests for `charts.rs`.
+pub mod test_charts;
+
+/// This module contains the tests for `allocation.rs`.
+pub mod test_allocation;
+
+/// This module contains the tests for `calendar.rs`.
+pub mod test_calendar;
+
+/// This module contains the tests for `benchmark.rs`.
+pub mod test_benchmark;
+
+/// This module contains the tests for `currency.rs`.
+pub mod test_currency;
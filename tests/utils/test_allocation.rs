@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::AllocationError;
+    use nalufx::utils::allocation::{apply_constraints, normalize_non_negative, Constraints};
+
+    #[test]
+    fn test_normalize_non_negative_all_negative() {
+        let result = normalize_non_negative(vec![-0.2, -0.5, -0.3]);
+
+        assert_eq!(result, Err(AllocationError::ZeroTotalAllocation));
+    }
+
+    #[test]
+    fn test_normalize_non_negative_all_zero() {
+        let result = normalize_non_negative(vec![0.0, 0.0]);
+
+        assert_eq!(result, Err(AllocationError::ZeroTotalAllocation));
+    }
+
+    #[test]
+    fn test_normalize_non_negative_mixed() {
+        let allocs = normalize_non_negative(vec![0.5, -0.25, 0.3, -0.1, 0.2]).unwrap();
+
+        assert_eq!(allocs.len(), 5);
+        assert_eq!(allocs[1], 0.0);
+        assert_eq!(allocs[3], 0.0);
+        assert!((allocs[0] - 0.5).abs() < 1e-12);
+        assert!((allocs[2] - 0.3).abs() < 1e-12);
+        assert!((allocs[4] - 0.2).abs() < 1e-12);
+        assert!((allocs.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_normalize_non_negative_already_normalized() {
+        let allocs = vec![0.25, 0.5, 0.125, 0.125];
+
+        assert_eq!(normalize_non_negative(allocs.clone()), Ok(allocs));
+    }
+
+    #[test]
+    fn test_normalize_non_negative_empty_input() {
+        assert_eq!(normalize_non_negative(Vec::new()), Err(AllocationError::EmptyInput));
+    }
+
+    #[test]
+    fn test_normalize_non_negative_non_finite_values() {
+        assert_eq!(normalize_non_negative(vec![0.5, f64::NAN]), Err(AllocationError::InvalidData));
+        assert_eq!(
+            normalize_non_negative(vec![0.5, f64::INFINITY]),
+            Err(AllocationError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn test_apply_constraints_caps_two_assets() {
+        let constraints = Constraints { max_weight: 0.4, ..Constraints::default() };
+
+        // Two weights capped at 0.4 cannot sum to 1.
+        assert_eq!(
+            apply_constraints(&[0.9, 0.1], &constraints),
+            Err(AllocationError::InfeasibleConstraints {
+                min_weight: 0.0,
+                max_weight: 0.4,
+                count: 2,
+            })
+        );
+
+        let constraints = Constraints { max_weight: 0.6, ..Constraints::default() };
+        let weights = apply_constraints(&[0.9, 0.1], &constraints).unwrap();
+        assert!((weights[0] - 0.6).abs() < 1e-12);
+        assert!((weights[1] - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_apply_constraints_caps_five_assets() {
+        let constraints = Constraints { max_weight: 0.4, ..Constraints::default() };
+        let weights = apply_constraints(&[0.7, 0.2, 0.05, 0.03, 0.02], &constraints).unwrap();
+
+        assert!(weights.iter().all(|&w| (0.0..=0.4 + 1e-12).contains(&w)));
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!((weights[0] - 0.4).abs() < 1e-12);
+        // The uncapped weights keep their proportions.
+        assert!((weights[1] / weights[2] - 4.0).abs() < 1e-9);
+        assert!((weights[3] / weights[4] - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_apply_constraints_cascading_caps() {
+        // Capping the first weight lifts the second above the cap too.
+        let constraints = Constraints { max_weight: 0.4, ..Constraints::default() };
+        let weights = apply_constraints(&[0.6, 0.3, 0.1], &constraints).unwrap();
+
+        assert!((weights[0] - 0.4).abs() < 1e-12);
+        assert!((weights[1] - 0.4).abs() < 1e-12);
+        assert!((weights[2] - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_apply_constraints_raises_minimum_weights() {
+        let constraints = Constraints { min_weight: 0.1, max_weight: 1.0 };
+        let weights = apply_constraints(&[0.9, 0.1, -0.5], &constraints).unwrap();
+
+        assert!((weights[2] - 0.1).abs() < 1e-12);
+        assert!(weights.iter().all(|&w| w >= 0.1 - 1e-12));
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_constraints_validate() {
+        assert!(Constraints::default().validate(1).is_ok());
+        assert!(Constraints { min_weight: 0.2, max_weight: 0.2 }.validate(5).is_ok());
+        assert!(Constraints { min_weight: 0.3, max_weight: 1.0 }.validate(4).is_err());
+        assert!(Constraints { min_weight: 0.5, max_weight: 0.4 }.validate(2).is_err());
+        assert!(Constraints { min_weight: -0.1, max_weight: 1.0 }.validate(2).is_err());
+        assert!(Constraints { min_weight: 0.0, max_weight: f64::NAN }.validate(2).is_err());
+    }
+}
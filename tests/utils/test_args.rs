@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use nalufx::errors::NaluFxError;
+    use nalufx::utils::args::{
+        BellwetherArgs, BellwetherInputs, ReportArgs, TechnicalAnalysisArgs,
+    };
+    use nalufx::utils::indicators::{EmaSeed, IndicatorWindows};
+    use std::path::{Path, PathBuf};
+
+    fn parse(args: &[&str]) -> BellwetherArgs {
+        BellwetherArgs::try_parse_from(
+            std::iter::once("bellwether_stock_analysis").chain(args.iter().copied()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_bellwether_args_resolve_to_validated_inputs() {
+        let args = parse(&[
+            "--llm",
+            "Ollama",
+            "--ticker",
+            "AAPL",
+            "--investment",
+            "10000",
+            "--start",
+            "2023-01-01",
+            "--end",
+            "2023-12-31",
+        ]);
+
+        assert_eq!(
+            args.resolve().unwrap(),
+            BellwetherInputs {
+                llm: "ollama".to_string(),
+                ticker: "AAPL".to_string(),
+                investment: 10000.0,
+                start_date: "2023-01-01".to_string(),
+                end_date: "2023-12-31".to_string(),
+                output_dir: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_bellwether_args_are_optional() {
+        assert_eq!(parse(&[]), BellwetherArgs::default());
+        assert_eq!(parse(&["--ticker", "MSFT"]).ticker.as_deref(), Some("MSFT"));
+        assert!(BellwetherArgs::try_parse_from(["bellwether_stock_analysis", "--unknown"]).is_err());
+    }
+
+    #[test]
+    fn test_output_dir_selects_the_report_sink() {
+        let args = parse(&["--output-dir", "/tmp/nalufx-args-reports"]);
+        assert_eq!(args.output_dir, Some(PathBuf::from("/tmp/nalufx-args-reports")));
+
+        let args = ReportArgs::try_parse_from(["example", "--output-dir", "out"]).unwrap();
+        assert_eq!(args.report_sink().dir(), Path::new("out"));
+    }
+
+    #[test]
+    fn test_bellwether_args_reject_invalid_inputs() {
+        let valid = [
+            "--llm",
+            "openai",
+            "--ticker",
+            "AAPL",
+            "--investment",
+            "10000",
+            "--start",
+            "2023-01-01",
+            "--end",
+            "2023-12-31",
+        ];
+        for (index, invalid) in [(3, "AA$L"), (5, "-100"), (5, "ten"), (7, "2023-13-01"), (9, "")] {
+            let mut args = valid;
+            args[index] = invalid;
+            assert!(
+                matches!(
+                    parse(&args).resolve(),
+                    Err(NaluFxError::InvalidTicker(_) | NaluFxError::InvalidInput(_))
+                ),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_technical_analysis_args_default_to_the_usual_windows() {
+        let args =
+            TechnicalAnalysisArgs::try_parse_from(["technical_analysis_indicators"]).unwrap();
+        assert_eq!(args.windows().unwrap(), IndicatorWindows::default());
+        assert_eq!(args.ema_seed, EmaSeed::FirstValue);
+        assert!(!args.cache_llm);
+
+        let args = TechnicalAnalysisArgs::try_parse_from([
+            "technical_analysis_indicators",
+            "--ema-seed",
+            "sma",
+            "--ema-window",
+            "20",
+            "--macd-short",
+            "5",
+            "--macd-long",
+            "35",
+            "--macd-signal",
+            "5",
+            "--cache-llm",
+        ])
+        .unwrap();
+        let windows = args.windows().unwrap();
+        assert_eq!((windows.ema, windows.rsi), (20, 14));
+        assert_eq!((windows.macd_short, windows.macd_long, windows.macd_signal), (5, 35, 5));
+        assert_eq!(args.ema_seed, EmaSeed::Sma);
+        assert!(args.cache_llm);
+
+        assert!(TechnicalAnalysisArgs::try_parse_from([
+            "technical_analysis_indicators",
+            "--ema-seed",
+            "median",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_technical_analysis_args_reject_invalid_windows() {
+        for invalid in [
+            ["--ema-window", "0"],
+            ["--rsi-window", "0"],
+            ["--macd-signal", "0"],
+            ["--macd-short", "26"],
+            ["--macd-long", "8"],
+        ] {
+            let args = TechnicalAnalysisArgs::try_parse_from(
+                std::iter::once("technical_analysis_indicators").chain(invalid),
+            )
+            .unwrap();
+            assert!(
+                matches!(args.windows(), Err(NaluFxError::TechnicalAnalysisError(_))),
+                "{:?} should be rejected",
+                invalid
+            );
+        }
+        assert!(TechnicalAnalysisArgs::try_parse_from([
+            "technical_analysis_indicators",
+            "--rsi-window",
+            "-1"
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::utils::batch::{run_batch, BatchOptions};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    };
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_progress_fires_once_per_completed_item() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&calls);
+        let options = BatchOptions::new(3).with_progress(move |completed, total| {
+            recorded.lock().unwrap().push((completed, total))
+        });
+
+        let items: Vec<u64> = (0..20).collect();
+        let outputs = run_batch(&items, &options, |&item| async move {
+            // Complete the items out of order.
+            tokio::time::sleep(Duration::from_millis((item * 7) % 5)).await;
+            item * 2
+        })
+        .await;
+
+        assert_eq!(outputs, items.iter().map(|item| item * 2).collect::<Vec<_>>());
+        let calls = calls.lock().unwrap();
+        assert_eq!(*calls, (1..=20).map(|completed| (completed, 20)).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        let items: Vec<usize> = (0..12).collect();
+        let outputs = run_batch(&items, &BatchOptions::new(4), |&item| {
+            let (in_flight, max_in_flight) = (&in_flight, &max_in_flight);
+            async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                let _ = max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(2)).await;
+                let _ = in_flight.fetch_sub(1, Ordering::SeqCst);
+                item
+            }
+        })
+        .await;
+
+        assert_eq!(outputs, items);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_empty_batch_and_zero_concurrency() {
+        assert_eq!(BatchOptions::new(0).concurrency(), 1);
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let options = BatchOptions::new(0).with_progress(move |_, _| {
+            let _ = counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let items: Vec<u8> = Vec::new();
+        let outputs = run_batch(&items, &options, |&item| async move { item }).await;
+        assert!(outputs.is_empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let outputs = run_batch(&[1, 2, 3], &options, |&item| async move { item + 1 }).await;
+        assert_eq!(outputs, vec![2, 3, 4]);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use crate::ENV_MUTEX;
+    use nalufx::utils::benchmark::{resolve_benchmark, resolve_portfolio_benchmark, AssetClass};
+    use std::env;
+
+    #[test]
+    fn test_sector_etf_resolves_to_its_sector_benchmark() {
+        let _guard = ENV_MUTEX.lock().unwrap();
+        assert_eq!(AssetClass::of("icln"), Some(AssetClass::CleanEnergy));
+        assert_eq!(resolve_benchmark("ICLN", None), "XLE");
+        assert_eq!(resolve_benchmark("SMH", None), "XLK");
+        assert_eq!(resolve_benchmark("tlt", Some(" ")), "AGG");
+    }
+
+    #[test]
+    fn test_unknown_ticker_falls_back_to_spy() {
+        let _guard = ENV_MUTEX.lock().unwrap();
+        assert_eq!(AssetClass::of("ZZZZ"), None);
+        assert_eq!(resolve_benchmark("ZZZZ", None), "SPY");
+        assert_eq!(resolve_benchmark("ZZZZ", Some("qqq")), "QQQ");
+    }
+
+    #[test]
+    fn test_portfolio_benchmark() {
+        let _guard = ENV_MUTEX.lock().unwrap();
+        assert_eq!(resolve_portfolio_benchmark(&["ICLN", "TAN", "PBW"], None), "XLE");
+        // A portfolio mixing asset classes is measured against the default benchmark.
+        assert_eq!(resolve_portfolio_benchmark(&["ICLN", "SMH"], None), "SPY");
+        assert_eq!(resolve_portfolio_benchmark(&["ICLN", "SMH"], Some("VT")), "VT");
+        assert_eq!(resolve_portfolio_benchmark(&[], None), "SPY");
+    }
+
+    #[test]
+    fn test_configured_benchmarks_take_precedence() {
+        let _guard = ENV_MUTEX.lock().unwrap();
+        env::set_var("NALUFX_BENCHMARKS", "ICLN=TAN, technology=QQQ, QCLN=QCLN");
+        env::set_var("NALUFX_BENCHMARK_TICKER", "VT");
+
+        let resolved = [
+            resolve_benchmark("ICLN", None),
+            resolve_benchmark("SMH", None),
+            // A fund is never its own benchmark, so the asset class benchmark is used.
+            resolve_benchmark("QCLN", None),
+            resolve_benchmark("ZZZZ", None),
+        ];
+        env::remove_var("NALUFX_BENCHMARKS");
+        env::remove_var("NALUFX_BENCHMARK_TICKER");
+
+        assert_eq!(resolved, ["TAN", "QQQ", "XLE", "VT"]);
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+    use nalufx::utils::calendar::{
+        is_trading_day, next_trading_days, next_trading_days_with, us_market_holidays,
+    };
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 14, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_next_trading_days_skips_weekend_from_friday() {
+        // Friday 7 June 2024
+        let days = next_trading_days(at(2024, 6, 7), 3);
+
+        assert_eq!(days, vec![at(2024, 6, 10), at(2024, 6, 11), at(2024, 6, 12)]);
+    }
+
+    #[test]
+    fn test_next_trading_days_skips_us_market_holidays() {
+        // Wednesday 27 November 2024, before Thanksgiving Day
+        let days = next_trading_days(at(2024, 11, 27), 2);
+
+        assert_eq!(days, vec![at(2024, 11, 29), at(2024, 12, 2)]);
+    }
+
+    #[test]
+    fn test_next_trading_days_across_years() {
+        // Tuesday 31 December 2024, New Year's Day is a holiday
+        let days = next_trading_days(at(2024, 12, 31), 1);
+
+        assert_eq!(days, vec![at(2025, 1, 2)]);
+    }
+
+    #[test]
+    fn test_next_trading_days_with_custom_holidays() {
+        let holidays = [date(2024, 6, 10)];
+
+        let days = next_trading_days_with(at(2024, 6, 7), 2, &holidays);
+
+        assert_eq!(days, vec![at(2024, 6, 11), at(2024, 6, 12)]);
+    }
+
+    #[test]
+    fn test_next_trading_days_none() {
+        assert!(next_trading_days(at(2024, 6, 7), 0).is_empty());
+    }
+
+    #[test]
+    fn test_us_market_holidays_2024() {
+        assert_eq!(
+            us_market_holidays(2024),
+            vec![
+                date(2024, 1, 1),
+                date(2024, 1, 15),
+                date(2024, 2, 19),
+                date(2024, 3, 29),
+                date(2024, 5, 27),
+                date(2024, 6, 19),
+                date(2024, 7, 4),
+                date(2024, 9, 2),
+                date(2024, 11, 28),
+                date(2024, 12, 25),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_us_market_holidays_observed_dates() {
+        // Christmas Day 2022 fell on a Sunday, Independence Day 2026 falls on a Saturday
+        assert!(us_market_holidays(2022).contains(&date(2022, 12, 26)));
+        assert!(us_market_holidays(2026).contains(&date(2026, 7, 3)));
+    }
+
+    #[test]
+    fn test_us_market_holidays_new_year_on_saturday_is_not_observed() {
+        let holidays = us_market_holidays(2022);
+
+        assert!(!holidays.contains(&date(2021, 12, 31)));
+        assert!(!holidays.contains(&date(2022, 1, 1)));
+        assert_eq!(holidays.len(), 9);
+    }
+
+    #[test]
+    fn test_us_market_holidays_juneteenth_from_2022() {
+        assert!(!us_market_holidays(2021).contains(&date(2021, 6, 18)));
+        assert!(us_market_holidays(2023).contains(&date(2023, 6, 19)));
+    }
+
+    #[test]
+    fn test_is_trading_day() {
+        let holidays = us_market_holidays(2024);
+
+        assert!(is_trading_day(date(2024, 6, 7), &holidays));
+        assert!(!is_trading_day(date(2024, 6, 8), &holidays));
+        assert!(!is_trading_day(date(2024, 6, 9), &holidays));
+        assert!(!is_trading_day(date(2024, 3, 29), &holidays));
+    }
+}
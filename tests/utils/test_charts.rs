@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::NaluFxError;
+    use nalufx::utils::charts::{
+        downsample_lttb, portfolio_value_series, value_chart_text, write_value_chart_svg,
+    };
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_portfolio_value_series_is_monotonic_for_positive_returns() {
+        let values = portfolio_value_series(1000.0, &[0.01, 0.02, 0.005, 0.03]);
+
+        assert_eq!(values.len(), 5);
+        assert_eq!(values[0], 1000.0);
+        assert!(values.windows(2).all(|pair| pair[1] > pair[0]));
+        assert!((values[4] - 1000.0 * 1.01 * 1.02 * 1.005 * 1.03).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_portfolio_value_series_without_returns() {
+        assert_eq!(portfolio_value_series(1000.0, &[]), vec![1000.0]);
+    }
+
+    #[test]
+    fn test_portfolio_value_series_compounds_losses() {
+        let values = portfolio_value_series(1000.0, &[-0.5, 0.5]);
+
+        assert_eq!(values, vec![1000.0, 500.0, 750.0]);
+    }
+
+    #[test]
+    fn test_value_chart_text_rejects_empty_series() {
+        assert!(matches!(value_chart_text(&[]), Err(NaluFxError::EmptyInput)));
+    }
+
+    #[test]
+    fn test_value_chart_text_rejects_non_finite_values() {
+        assert!(matches!(value_chart_text(&[1000.0, f64::NAN]), Err(NaluFxError::InvalidData)));
+    }
+
+    #[test]
+    fn test_write_value_chart_svg() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("value.svg");
+        let values = portfolio_value_series(1000.0, &[0.01, -0.02, 0.03]);
+
+        write_value_chart_svg(&path, "Value of $1,000.00 invested in SPY", &values).unwrap();
+
+        let svg = fs::read_to_string(&path).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Value of $1,000.00 invested in SPY"));
+    }
+
+    #[test]
+    fn test_write_value_chart_svg_flat_series() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("flat.svg");
+
+        write_value_chart_svg(&path, "Flat", &[1000.0]).unwrap();
+
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_downsample_lttb_to_budget() {
+        let points: Vec<(f64, f64)> =
+            (0..1000).map(|x| (x as f64, (x as f64 / 50.0).sin() * 100.0)).collect();
+
+        let sampled = downsample_lttb(&points, 100);
+
+        assert!(sampled.len() <= 100);
+        assert_eq!(sampled.first(), points.first());
+        assert_eq!(sampled.last(), points.last());
+        assert!(sampled.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(sampled.iter().all(|point| points.contains(point)));
+    }
+
+    #[test]
+    fn test_downsample_lttb_keeps_a_spike() {
+        let mut points: Vec<(f64, f64)> = (0..1000).map(|x| (x as f64, 1.0)).collect();
+        points[537].1 = 50.0;
+
+        let sampled = downsample_lttb(&points, 100);
+
+        assert!(sampled.contains(&(537.0, 50.0)));
+    }
+
+    #[test]
+    fn test_downsample_lttb_short_series_and_small_budgets() {
+        let points = [(0.0, 1.0), (1.0, 3.0), (2.0, 2.0), (3.0, 5.0)];
+
+        assert_eq!(downsample_lttb(&points, 4), points.to_vec());
+        assert_eq!(downsample_lttb(&points, 10), points.to_vec());
+        assert_eq!(downsample_lttb(&points, 3).len(), 3);
+        assert_eq!(downsample_lttb(&points, 1), vec![(0.0, 1.0), (3.0, 5.0)]);
+        assert!(downsample_lttb(&[], 100).is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::utils::currency::{format_currency, format_currency_with, CurrencyRounding};
+
+    #[test]
+    fn test_format_currency_groups_thousands() {
+        assert_eq!(format_currency(0.0), "$0.00");
+        assert_eq!(format_currency(999.99), "$999.99");
+        assert_eq!(format_currency(1_000.0), "$1,000.00");
+        assert_eq!(format_currency(1_234_567.891), "$1,234,567.89");
+    }
+
+    #[test]
+    fn test_format_currency_rounds_float_midpoints_as_decimals() {
+        // 2.675 and 1.005 are stored as 2.67499... and 1.00499... by a `f64`.
+        assert_eq!(format_currency(2.675), "$2.68");
+        assert_eq!(format_currency(1.005), "$1.01");
+        assert_eq!(format_currency(0.005), "$0.01");
+    }
+
+    #[test]
+    fn test_format_currency_with_half_even() {
+        assert_eq!(format_currency_with(2.675, CurrencyRounding::HalfEven), "$2.68");
+        assert_eq!(format_currency_with(2.665, CurrencyRounding::HalfEven), "$2.66");
+        assert_eq!(format_currency_with(0.005, CurrencyRounding::HalfEven), "$0.00");
+        assert_eq!(format_currency_with(0.015, CurrencyRounding::HalfEven), "$0.02");
+    }
+
+    #[test]
+    fn test_format_currency_negative_amounts() {
+        assert_eq!(format_currency(-1234.5678), "-$1,234.57");
+        assert_eq!(format_currency(-2.675), "-$2.68");
+        assert_eq!(format_currency(-0.005), "-$0.01");
+        assert_eq!(format_currency_with(-0.005, CurrencyRounding::HalfEven), "$0.00");
+        assert_eq!(format_currency(-0.001), "$0.00");
+    }
+
+    #[test]
+    fn test_format_currency_non_finite() {
+        assert_eq!(format_currency(f64::NAN), "$0.00");
+        assert_eq!(format_currency(f64::INFINITY), "$0.00");
+    }
+}
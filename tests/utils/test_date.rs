@@ -1,119 +1,124 @@
 #[cfg(test)]
 mod tests {
-    use nalufx::utils::date::validate_date;
+    use chrono::{DateTime, Datelike, Months, NaiveDate, TimeZone, Utc};
+    use nalufx::errors::NaluFxError;
+    use nalufx::utils::date::{check_series_continuity, parse_range_preset, SeriesIssue};
+
+    fn day(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 14, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_check_series_continuity_continuous_over_weekend() {
+        // Thursday, Friday, Monday, Tuesday
+        let series = vec![
+            (day(2024, 3, 7), 100.0),
+            (day(2024, 3, 8), 101.0),
+            (day(2024, 3, 11), 102.0),
+            (day(2024, 3, 12), 103.0),
+        ];
+        assert!(check_series_continuity(&series, 0).is_ok());
+    }
+
+    #[test]
+    fn test_check_series_continuity_missing_day_and_duplicate() {
+        // Wednesday 2024-03-13 is missing and Friday 2024-03-15 is duplicated
+        let series = vec![
+            (day(2024, 3, 11), 100.0),
+            (day(2024, 3, 12), 101.0),
+            (day(2024, 3, 14), 102.0),
+            (day(2024, 3, 15), 103.0),
+            (day(2024, 3, 15), 103.5),
+            (day(2024, 3, 18), 104.0),
+        ];
+
+        match check_series_continuity(&series, 0) {
+            Err(NaluFxError::DiscontinuousSeries(issues)) => {
+                assert_eq!(
+                    issues,
+                    vec![
+                        SeriesIssue::Gap {
+                            after: day(2024, 3, 12),
+                            before: day(2024, 3, 14),
+                            missing_days: 1,
+                        },
+                        SeriesIssue::Duplicate(day(2024, 3, 15)),
+                    ]
+                );
+            },
+            other => panic!("Expected DiscontinuousSeries, got {:?}", other),
+        }
+
+        // Tolerating one missing day still reports the duplicate
+        match check_series_continuity(&series, 1) {
+            Err(NaluFxError::DiscontinuousSeries(issues)) => {
+                assert_eq!(issues, vec![SeriesIssue::Duplicate(day(2024, 3, 15))]);
+            },
+            other => panic!("Expected DiscontinuousSeries, got {:?}", other),
+        }
+    }
 
     #[test]
-    fn test_validate_date() {
-        // Test with a valid date
-        let valid_date = "2024-01-01";
-        assert!(validate_date(valid_date).is_ok());
-
-        // Test with an invalid date
-        let invalid_date = "2024-13-01";
-        assert!(validate_date(invalid_date).is_err());
-
-        // Test with an invalid format
-        let invalid_format = "01-01-2024";
-        assert!(validate_date(invalid_format).is_err());
-
-        // Test with an empty string
-        let empty_string = "";
-        assert!(validate_date(empty_string).is_err());
+    fn test_check_series_continuity_out_of_order() {
+        let series = vec![(day(2024, 3, 12), 100.0), (day(2024, 3, 11), 101.0)];
+
+        match check_series_continuity(&series, 5) {
+            Err(NaluFxError::DiscontinuousSeries(issues)) => {
+                assert_eq!(
+                    issues,
+                    vec![SeriesIssue::OutOfOrder {
+                        previous: day(2024, 3, 12),
+                        current: day(2024, 3, 11),
+                    }]
+                );
+            },
+            other => panic!("Expected DiscontinuousSeries, got {:?}", other),
+        }
     }
 
     #[test]
-    fn test_validate_date_error_message() {
-        // Test with an invalid date
-        let invalid_date = "2024-13-01";
-        assert_eq!(
-            validate_date(invalid_date).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
-
-        // Test with an invalid format
-        let invalid_format = "01-01-2024";
-        assert_eq!(
-            validate_date(invalid_format).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
-
-        // Test with an empty string
-        let empty_string = "";
-        assert_eq!(
-            validate_date(empty_string).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
+    fn test_check_series_continuity_short_series() {
+        assert!(check_series_continuity(&[], 0).is_ok());
+        assert!(check_series_continuity(&[(day(2024, 3, 11), 100.0)], 0).is_ok());
     }
 
     #[test]
-    fn test_validate_date_time_component_error_message() {
-        // Test with an invalid date
-        let invalid_date = "2024-13-01";
-        assert_eq!(
-            validate_date(invalid_date).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
-
-        // Test with an invalid format
-        let invalid_format = "01-01-2024";
-        assert_eq!(
-            validate_date(invalid_format).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
-
-        // Test with an empty string
-        let empty_string = "";
-        assert_eq!(
-            validate_date(empty_string).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
+    fn test_parse_range_preset_ytd() {
+        let (start, end) = parse_range_preset("YTD").unwrap();
+        let today = Utc::now().date_naive();
+
+        assert_eq!(start, Utc.with_ymd_and_hms(today.year(), 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(end.date_naive(), today);
+    }
+
+    #[test]
+    fn test_parse_range_preset_one_year() {
+        let (start, end) = parse_range_preset("1y").unwrap();
+        let today = Utc::now().date_naive();
+
+        assert_eq!(start.date_naive(), today.checked_sub_months(Months::new(12)).unwrap());
+        assert_eq!((start.timestamp() % 86_400), 0);
+        assert_eq!(end.date_naive(), today);
     }
 
     #[test]
-    fn test_validate_date_conversion_error_message() {
-        // Test with an invalid date
-        let invalid_date = "2024-13-01";
-        assert_eq!(
-            validate_date(invalid_date).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
-
-        // Test with an invalid format
-        let invalid_format = "01-01-2024";
-        assert_eq!(
-            validate_date(invalid_format).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
-
-        // Test with an empty string
-        let empty_string = "";
-        assert_eq!(
-            validate_date(empty_string).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
+    fn test_parse_range_preset_other_presets() {
+        let today = Utc::now().date_naive();
+
+        let (start, _) = parse_range_preset("6M").unwrap();
+        assert_eq!(start.date_naive(), today.checked_sub_months(Months::new(6)).unwrap());
+
+        let (start, _) = parse_range_preset(" 5Y ").unwrap();
+        assert_eq!(start.date_naive(), today.checked_sub_months(Months::new(60)).unwrap());
+
+        let (start, _) = parse_range_preset("MAX").unwrap();
+        assert_eq!(start.date_naive(), NaiveDate::from_ymd_opt(1970, 1, 1).unwrap());
     }
 
     #[test]
-    fn test_validate_date_multiple_error_messages() {
-        // Test with an invalid date
-        let invalid_date = "2024-13-01";
-        assert_eq!(
-            validate_date(invalid_date).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
-
-        // Test with an invalid format
-        let invalid_format = "01-01-2024";
-        assert_eq!(
-            validate_date(invalid_format).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
-
-        // Test with an empty string
-        let empty_string = "";
-        assert_eq!(
-            validate_date(empty_string).unwrap_err(),
-            "Please enter a valid date in the format YYYY-MM-DD."
-        );
+    fn test_parse_range_preset_invalid() {
+        for preset in ["", "M", "0M", "1Q", "-1Y", "YTDX", "2024-01-01", "1É"] {
+            assert!(parse_range_preset(preset).is_none(), "{} should be invalid", preset);
+        }
     }
 }
@@ -0,0 +1,290 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::NaluFxError;
+    use nalufx::utils::indicators::{
+        calculate_atr, calculate_bollinger_bands, calculate_ema, calculate_ema_with_seed,
+        calculate_ichimoku, calculate_keltner_channels, calculate_obv, is_squeeze, EmaSeed,
+        IndicatorWindows, ICHIMOKU_DISPLACEMENT,
+    };
+
+    /// A 60-period series rising by one per period, with a range of 10 per period.
+    fn rising_series() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let highs = (0..60).map(|i| 10.0 + i as f64).collect();
+        let lows = (0..60).map(|i| i as f64).collect();
+        let closes = (0..60).map(|i| 5.0 + i as f64).collect();
+        (highs, lows, closes)
+    }
+
+    #[test]
+    fn test_calculate_ichimoku_midpoints() {
+        let (highs, lows, closes) = rising_series();
+        let ichimoku = calculate_ichimoku(&highs, &lows, &closes).unwrap();
+
+        // Tenkan-sen: (highs[i] + lows[i - 8]) / 2 = i + 1
+        assert!(ichimoku.tenkan_sen[..8].iter().all(Option::is_none));
+        for i in 8..60 {
+            assert_eq!(ichimoku.tenkan_sen[i], Some(i as f64 + 1.0));
+        }
+
+        // Kijun-sen: (highs[i] + lows[i - 25]) / 2 = i - 7.5
+        assert!(ichimoku.kijun_sen[..25].iter().all(Option::is_none));
+        for i in 25..60 {
+            assert_eq!(ichimoku.kijun_sen[i], Some(i as f64 - 7.5));
+        }
+    }
+
+    #[test]
+    fn test_calculate_ichimoku_alignment() {
+        let (highs, lows, closes) = rising_series();
+        let ichimoku = calculate_ichimoku(&highs, &lows, &closes).unwrap();
+
+        assert_eq!(ichimoku.tenkan_sen.len(), 60);
+        assert_eq!(ichimoku.kijun_sen.len(), 60);
+        assert_eq!(ichimoku.chikou_span.len(), 60);
+        assert_eq!(ichimoku.senkou_span_a.len(), 60 + ICHIMOKU_DISPLACEMENT);
+        assert_eq!(ichimoku.senkou_span_b.len(), 60 + ICHIMOKU_DISPLACEMENT);
+
+        // Senkou Span A is plotted 26 periods after the Tenkan-sen and Kijun-sen it averages.
+        assert!(ichimoku.senkou_span_a[..25 + ICHIMOKU_DISPLACEMENT].iter().all(Option::is_none));
+        for i in 25..60 {
+            assert_eq!(ichimoku.senkou_span_a[i + ICHIMOKU_DISPLACEMENT], Some(i as f64 - 3.25));
+        }
+
+        // Senkou Span B: (highs[i] + lows[i - 51]) / 2 = i - 20.5, plotted 26 periods later.
+        assert!(ichimoku.senkou_span_b[..51 + ICHIMOKU_DISPLACEMENT].iter().all(Option::is_none));
+        for i in 51..60 {
+            assert_eq!(ichimoku.senkou_span_b[i + ICHIMOKU_DISPLACEMENT], Some(i as f64 - 20.5));
+        }
+
+        // Chikou Span is the close plotted 26 periods earlier.
+        for i in 0..34 {
+            assert_eq!(ichimoku.chikou_span[i], Some(closes[i + ICHIMOKU_DISPLACEMENT]));
+        }
+        assert!(ichimoku.chikou_span[34..].iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_calculate_ichimoku_spike_stays_in_window() {
+        let mut highs = vec![11.0; 20];
+        let lows = vec![9.0; 20];
+        let closes = vec![10.0; 20];
+        highs[5] = 21.0;
+
+        let ichimoku = calculate_ichimoku(&highs, &lows, &closes).unwrap();
+
+        // The spike is in the nine-period window for periods 8 to 13 only.
+        for i in 8..20 {
+            let expected = if i <= 13 { 15.0 } else { 10.0 };
+            assert_eq!(ichimoku.tenkan_sen[i], Some(expected));
+        }
+        assert!(ichimoku.kijun_sen.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_calculate_ichimoku_invalid_input() {
+        assert!(matches!(calculate_ichimoku(&[], &[], &[]), Err(NaluFxError::EmptyInput)));
+        assert!(matches!(
+            calculate_ichimoku(&[2.0, 2.0], &[1.0], &[1.5, 1.5]),
+            Err(NaluFxError::InputMismatch)
+        ));
+        assert!(matches!(
+            calculate_ichimoku(&[1.0], &[2.0], &[1.5]),
+            Err(NaluFxError::InvalidData)
+        ));
+        assert!(matches!(
+            calculate_ichimoku(&[2.0], &[1.0], &[f64::NAN]),
+            Err(NaluFxError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn test_calculate_obv_hand_computed() {
+        let closes = vec![10.0, 10.5, 10.2, 10.2, 10.8, 10.6];
+        let volumes = vec![1000.0, 1200.0, 900.0, 700.0, 1500.0, 800.0];
+
+        // Up +1200, down -900, flat (unchanged), up +1500, down -800
+        assert_eq!(
+            calculate_obv(&closes, &volumes).unwrap(),
+            vec![0.0, 1200.0, 300.0, 300.0, 1800.0, 1000.0]
+        );
+    }
+
+    #[test]
+    fn test_calculate_obv_edge_cases() {
+        assert!(calculate_obv(&[], &[]).unwrap().is_empty());
+        assert_eq!(calculate_obv(&[10.0], &[500.0]).unwrap(), vec![0.0]);
+        assert!(matches!(
+            calculate_obv(&[10.0, 11.0, 12.0], &[100.0, 200.0]),
+            Err(NaluFxError::InputMismatch)
+        ));
+        assert!(matches!(calculate_obv(&[], &[100.0]), Err(NaluFxError::InputMismatch)));
+    }
+
+    #[test]
+    fn test_calculate_ema_and_atr_alignment() {
+        let (highs, lows, closes) = rising_series();
+
+        let ema = calculate_ema(&closes, 20);
+        assert_eq!(ema.len(), 41);
+        // A linear series lags its EMA seed, the average of the first 20 closes, by 9.5.
+        assert_eq!(ema[0], 14.5);
+
+        // Every true range is the range of 10, so the ATR is constant.
+        let atr = calculate_atr(&highs, &lows, &closes, 10).unwrap();
+        assert_eq!(atr.len(), 51);
+        assert!(atr.iter().all(|&value| value == 10.0));
+
+        assert!(calculate_atr(&highs, &lows, &closes, 0).unwrap().is_empty());
+        assert!(calculate_atr(&highs[..9], &lows[..9], &closes[..9], 10).unwrap().is_empty());
+        assert!(matches!(
+            calculate_atr(&highs, &lows[..59], &closes, 10),
+            Err(NaluFxError::InputMismatch)
+        ));
+        assert!(matches!(
+            calculate_atr(&highs, &lows, &closes[..59], 10),
+            Err(NaluFxError::InputMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_calculate_ema_seeds() {
+        let (_, _, closes) = rising_series();
+
+        let first_value = calculate_ema_with_seed(&closes, 20, EmaSeed::FirstValue);
+        let sma = calculate_ema_with_seed(&closes, 20, EmaSeed::Sma);
+        assert_eq!(first_value.len(), 60);
+        assert_eq!(sma, calculate_ema(&closes, 20));
+
+        // The SMA seed lags the series by the steady-state lag of (20 - 1) / 2 = 9.5 from the
+        // start. Seeded with the first close, the EMA has no lag at first, and its lag grows
+        // towards 9.5 as the seed decays by a factor of 19/21 per period.
+        assert_eq!(first_value[0], 5.0);
+        assert_eq!(sma[0], 14.5);
+        let decay = 19.0_f64 / 21.0;
+        for (period, value) in first_value.iter().enumerate() {
+            let expected = closes[period] - 9.5 * (1.0 - decay.powi(period as i32));
+            assert!((value - expected).abs() < 1e-9);
+        }
+
+        // The seeds converge, so the early values, and the MACD built on them, differ most.
+        let offset = first_value.len() - sma.len();
+        let gaps: Vec<f64> =
+            first_value[offset..].iter().zip(&sma).map(|(first, sma)| first - sma).collect();
+        assert!(gaps[0] > 1.0);
+        assert!(gaps.windows(2).all(|pair| pair[1] < pair[0]));
+        assert!(gaps[gaps.len() - 1] < 0.1);
+
+        assert!(calculate_ema_with_seed(&closes, 0, EmaSeed::Sma).is_empty());
+        assert!(calculate_ema_with_seed(&closes, 0, EmaSeed::FirstValue).is_empty());
+        assert!(calculate_ema_with_seed(&[], 20, EmaSeed::FirstValue).is_empty());
+    }
+
+    #[test]
+    fn test_calculate_ema_series_shorter_than_window() {
+        let (_, _, closes) = rising_series();
+        let short = &closes[..10];
+
+        // Seeded with the first value, the EMA has one value per period, as before the seed
+        // was configurable, and they match the start of the EMA of the longer series.
+        let first_value = calculate_ema_with_seed(short, 20, EmaSeed::FirstValue);
+        assert_eq!(first_value.len(), short.len());
+        assert_eq!(
+            first_value[..],
+            calculate_ema_with_seed(&closes, 20, EmaSeed::FirstValue)[..10]
+        );
+
+        // The SMA seed needs a full window.
+        assert!(calculate_ema_with_seed(short, 20, EmaSeed::Sma).is_empty());
+        assert!(calculate_ema(short, 20).is_empty());
+    }
+
+    #[test]
+    fn test_calculate_keltner_channels_width_scales_with_multiplier() {
+        let (highs, lows, closes) = rising_series();
+
+        let (upper_1, middle_1, lower_1) =
+            calculate_keltner_channels(&highs, &lows, &closes, 20, 10, 1.0).unwrap();
+        let (upper_3, middle_3, lower_3) =
+            calculate_keltner_channels(&highs, &lows, &closes, 20, 10, 3.0).unwrap();
+
+        assert_eq!(middle_1.len(), 41);
+        assert_eq!(middle_1, middle_3);
+        assert_eq!(middle_1, calculate_ema(&closes, 20));
+        for i in 0..middle_1.len() {
+            let width_1 = upper_1[i] - lower_1[i];
+            let width_3 = upper_3[i] - lower_3[i];
+            assert!((width_1 - 20.0).abs() < 1e-9);
+            assert!((width_3 - 3.0 * width_1).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_calculate_keltner_channels_short_series() {
+        let (highs, lows, closes) = rising_series();
+
+        let empty = (Vec::new(), Vec::new(), Vec::new());
+        assert_eq!(
+            calculate_keltner_channels(&highs[..19], &lows[..19], &closes[..19], 20, 10, 2.0)
+                .unwrap(),
+            empty
+        );
+        assert_eq!(calculate_keltner_channels(&[], &[], &[], 20, 10, 2.0).unwrap(), empty);
+        assert_eq!(calculate_keltner_channels(&highs, &lows, &closes, 0, 10, 2.0).unwrap(), empty);
+        assert_eq!(calculate_bollinger_bands(&closes[..19], 20, 2.0), empty);
+        assert!(matches!(
+            calculate_keltner_channels(&highs, &lows[..59], &closes, 20, 10, 2.0),
+            Err(NaluFxError::InputMismatch)
+        ));
+
+        // Exactly the longer window gives a single value.
+        let (upper, middle, lower) =
+            calculate_keltner_channels(&highs[..20], &lows[..20], &closes[..20], 20, 10, 2.0)
+                .unwrap();
+        assert_eq!((upper.len(), middle.len(), lower.len()), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_is_squeeze() {
+        // Closes that barely move inside a wide daily range squeeze the Bollinger Bands.
+        let closes: Vec<f64> = (0..30).map(|i| if i % 2 == 0 { 100.0 } else { 100.5 }).collect();
+        let highs: Vec<f64> = closes.iter().map(|close| close + 2.0).collect();
+        let lows: Vec<f64> = closes.iter().map(|close| close - 2.0).collect();
+
+        let (bb_upper, _, bb_lower) = calculate_bollinger_bands(&closes, 20, 2.0);
+        let (kc_upper, _, kc_lower) =
+            calculate_keltner_channels(&highs, &lows, &closes, 20, 10, 1.5).unwrap();
+        assert!(is_squeeze(&bb_upper, &bb_lower, &kc_upper, &kc_lower));
+
+        // A steady trend with narrow daily ranges widens the Bollinger Bands past the channels.
+        let closes: Vec<f64> = (0..30).map(|i| 100.0 + i as f64).collect();
+        let highs: Vec<f64> = closes.iter().map(|close| close + 0.25).collect();
+        let lows: Vec<f64> = closes.iter().map(|close| close - 0.25).collect();
+
+        let (bb_upper, _, bb_lower) = calculate_bollinger_bands(&closes, 20, 2.0);
+        let (kc_upper, _, kc_lower) =
+            calculate_keltner_channels(&highs, &lows, &closes, 20, 10, 1.5).unwrap();
+        assert!(!is_squeeze(&bb_upper, &bb_lower, &kc_upper, &kc_lower));
+        assert!(!is_squeeze(&[], &[], &kc_upper, &kc_lower));
+    }
+
+    #[test]
+    fn test_indicator_windows_validation() {
+        assert!(IndicatorWindows::default().validate().is_ok());
+
+        let invalid = [
+            IndicatorWindows { ema: 0, ..IndicatorWindows::default() },
+            IndicatorWindows { rsi: 0, ..IndicatorWindows::default() },
+            IndicatorWindows { macd_short: 0, ..IndicatorWindows::default() },
+            IndicatorWindows { macd_signal: 0, ..IndicatorWindows::default() },
+            IndicatorWindows { macd_short: 26, macd_long: 26, ..IndicatorWindows::default() },
+            IndicatorWindows { macd_short: 26, macd_long: 12, ..IndicatorWindows::default() },
+        ];
+        for windows in invalid {
+            assert!(
+                matches!(windows.validate(), Err(NaluFxError::TechnicalAnalysisError(_))),
+                "{:?} should be rejected",
+                windows
+            );
+        }
+    }
+}
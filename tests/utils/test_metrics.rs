@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::utils::metrics::Metrics;
+    use std::time::Duration;
+
+    #[test]
+    fn test_render_new_metrics() {
+        let output = Metrics::new().render();
+
+        assert!(output.contains("# TYPE nalufx_predictions_total counter\n"));
+        assert!(output.contains("nalufx_predictions_total 0\n"));
+        assert!(output.contains("nalufx_fetch_failures_total 0\n"));
+        assert!(output.contains("# TYPE nalufx_llm_request_duration_seconds summary\n"));
+        assert!(output.contains("nalufx_llm_request_duration_seconds_count 0\n"));
+    }
+
+    #[test]
+    fn test_render_updated_metrics() {
+        let metrics = Metrics::new();
+        metrics.record_prediction();
+        metrics.record_prediction();
+        metrics.record_prediction_error();
+        metrics.record_fetch_failure();
+        metrics.record_llm_request(Duration::from_millis(250), true);
+        metrics.record_llm_request(Duration::from_millis(500), false);
+
+        let output = metrics.render();
+
+        assert_eq!(metrics.predictions_total(), 2);
+        assert_eq!(metrics.fetch_failures_total(), 1);
+        assert!(output.contains("nalufx_predictions_total 2\n"));
+        assert!(output.contains("nalufx_prediction_errors_total 1\n"));
+        assert!(output.contains("nalufx_fetch_failures_total 1\n"));
+        assert!(output.contains("nalufx_llm_request_errors_total 1\n"));
+        assert!(output.contains("nalufx_llm_request_duration_seconds_sum 0.75\n"));
+        assert!(output.contains("nalufx_llm_request_duration_seconds_count 2\n"));
+    }
+
+    #[test]
+    fn test_render_every_sample_has_help_and_type() {
+        let output = Metrics::new().render();
+
+        for line in output.lines().filter(|line| !line.starts_with('#')) {
+            let name = line.split_whitespace().next().unwrap();
+            let family = name.trim_end_matches("_sum").trim_end_matches("_count");
+            assert!(output.contains(&format!("# HELP {} ", family)), "no HELP for {}", name);
+            assert!(output.contains(&format!("# TYPE {} ", family)), "no TYPE for {}", name);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::ENV_MUTEX;
+    use nalufx::errors::NaluFxError;
+    use nalufx::utils::portfolio::{
+        get_portfolio_tickers, load_portfolio, parse_portfolio, save_portfolio, Holding,
+        PORTFOLIO_CSV_ENV,
+    };
+    use std::{env, fs};
+    use tempfile::tempdir;
+
+    fn holding(ticker: &str, shares_or_weight: f64) -> Holding {
+        Holding { ticker: ticker.to_string(), shares_or_weight }
+    }
+
+    #[test]
+    fn test_save_and_load_portfolio_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("portfolio.csv");
+        let holdings = vec![holding("SPY", 0.4), holding("EFA", 0.25), holding("GLD", 0.35)];
+
+        save_portfolio(&path, &holdings).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "ticker,shares_or_weight\nSPY,0.4\nEFA,0.25\nGLD,0.35\n"
+        );
+        assert_eq!(load_portfolio(&path).unwrap(), holdings);
+    }
+
+    #[test]
+    fn test_parse_portfolio_normalizes_tickers() {
+        let csv = "ticker, shares_or_weight\n aapl , 10\nMsft,2.5\n";
+        assert_eq!(
+            parse_portfolio(csv.as_bytes()).unwrap(),
+            vec![holding("AAPL", 10.0), holding("MSFT", 2.5)]
+        );
+    }
+
+    #[test]
+    fn test_parse_portfolio_invalid_input() {
+        assert!(matches!(
+            parse_portfolio("ticker,shares_or_weight\nAAPL$,10\n".as_bytes()),
+            Err(NaluFxError::InvalidTicker(_))
+        ));
+        assert!(matches!(
+            parse_portfolio("ticker,shares_or_weight\nAAPL,-1\n".as_bytes()),
+            Err(NaluFxError::InvalidData)
+        ));
+        assert!(matches!(
+            parse_portfolio("ticker,shares_or_weight\nAAPL,ten\n".as_bytes()),
+            Err(NaluFxError::CsvError(_))
+        ));
+        assert!(matches!(
+            parse_portfolio("ticker,shares_or_weight\n".as_bytes()),
+            Err(NaluFxError::EmptyInput)
+        ));
+        assert!(matches!(
+            load_portfolio(&tempdir().unwrap().path().join("missing.csv")),
+            Err(NaluFxError::CsvError(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_portfolio_tickers_from_env() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("portfolio.csv");
+        save_portfolio(&path, &[holding("SPY", 1.0), holding("IEF", 1.0)]).unwrap();
+
+        let _lock = ENV_MUTEX.lock().unwrap();
+        env::set_var(PORTFOLIO_CSV_ENV, &path);
+        let tickers = get_portfolio_tickers("unused prompt");
+        env::remove_var(PORTFOLIO_CSV_ENV);
+
+        assert_eq!(tickers.unwrap(), vec!["SPY", "IEF"]);
+    }
+}
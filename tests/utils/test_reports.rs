@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use nalufx::errors::NaluFxError;
+    use nalufx::utils::reports::{fmt_vec, format_daily_rows, ReportSink};
+    use std::fs;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_report_sink_creates_missing_dir() {
+        let temp = tempdir().unwrap();
+        let reports_dir = temp.path().join("nested").join("reports");
+        assert!(!reports_dir.exists());
+
+        let timestamp = Utc.with_ymd_and_hms(2024, 6, 28, 14, 30, 15).unwrap();
+        let (path, mut file) =
+            ReportSink::new(&reports_dir).create_at(timestamp, "report", "md").unwrap();
+        writeln!(file, "# Report").unwrap();
+        drop(file);
+
+        assert_eq!(path, reports_dir.join("2024-06-28_143015_report.md"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# Report\n");
+    }
+
+    #[test]
+    fn test_report_sink_keeps_existing_report() {
+        let temp = tempdir().unwrap();
+        let existing = temp.path().join("2024-06-28_143015_report.md");
+        fs::write(&existing, "previous run").unwrap();
+
+        let timestamp = Utc.with_ymd_and_hms(2024, 6, 28, 14, 30, 15).unwrap();
+        let (path, _) = ReportSink::new(temp.path()).create_at(timestamp, "report", "md").unwrap();
+
+        assert_eq!(path, temp.path().join("2024-06-28_143015_report_2.md"));
+        assert_eq!(fs::read_to_string(existing).unwrap(), "previous run");
+    }
+
+    #[test]
+    fn test_report_sink_uncreatable_dir() {
+        let temp = tempdir().unwrap();
+        let blocker = temp.path().join("blocker");
+        fs::write(&blocker, "not a directory").unwrap();
+
+        let result = ReportSink::new(blocker.join("reports")).write("report", "md", "# Report");
+
+        match result {
+            Err(NaluFxError::InputError(e)) => {
+                assert!(e.to_string().contains("Failed to create reports directory"));
+            },
+            other => panic!("expected InputError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_report_sink_runs_in_the_same_second_get_distinct_files() {
+        let temp = tempdir().unwrap();
+        let sink = ReportSink::new(temp.path().join("reports"));
+        let timestamp = Utc.with_ymd_and_hms(2024, 6, 28, 14, 30, 15).unwrap();
+
+        let (first, mut file) = sink.create_at(timestamp, "bellwether_AAPL", "md").unwrap();
+        writeln!(file, "# First run").unwrap();
+        let (second, _) = sink.create_at(timestamp, "bellwether_AAPL", "md").unwrap();
+        let (third, _) = sink.create_at(timestamp, "bellwether_AAPL", "md").unwrap();
+
+        let name = |path: &std::path::Path| path.file_name().unwrap().to_str().unwrap().to_owned();
+        assert_eq!(name(&first), "2024-06-28_143015_bellwether_AAPL.md");
+        assert_eq!(name(&second), "2024-06-28_143015_bellwether_AAPL_2.md");
+        assert_eq!(name(&third), "2024-06-28_143015_bellwether_AAPL_3.md");
+        // The first report is kept, not truncated by the later runs.
+        assert_eq!(fs::read_to_string(&first).unwrap(), "# First run\n");
+    }
+
+    #[test]
+    fn test_report_sink_write() {
+        let temp = tempdir().unwrap();
+        let sink = ReportSink::from_output_dir(Some(temp.path().to_path_buf()));
+        assert_eq!(sink.dir(), temp.path());
+
+        let first = sink.write("technical_analysis_MSFT", "md", "report").unwrap();
+        let second = sink.write("technical_analysis_MSFT", "md", "report").unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), Some(temp.path()));
+        assert_eq!(fs::read_to_string(&second).unwrap(), "report");
+    }
+
+    #[test]
+    fn test_format_daily_rows_truncates_long_series() {
+        let allocations = vec![0.01; 100];
+        let rows = format_daily_rows(&allocations, 10, |i, allocation| {
+            format!("- Day {}: {:.2}%", i + 1, allocation * 100.0)
+        });
+
+        assert_eq!(rows.len(), 11);
+        assert_eq!(rows[0], "- Day 1: 1.00%");
+        assert_eq!(rows[9], "- Day 10: 1.00%");
+        assert_eq!(rows[10], "...and 90 more days");
+    }
+
+    #[test]
+    fn test_format_daily_rows_short_series() {
+        let format_row = |i: usize, value: &f64| format!("{}: {}", i, value);
+
+        // A series within the limit is printed in full, without a summary line.
+        assert_eq!(format_daily_rows(&[1.0, 2.0], 2, format_row), vec!["0: 1", "1: 2"]);
+        assert!(format_daily_rows(&[], 10, format_row).is_empty());
+        assert_eq!(format_daily_rows(&[1.0, 2.0], 0, format_row), vec!["...and 2 more days"]);
+    }
+
+    #[test]
+    fn test_fmt_vec_rounds_to_precision() {
+        let allocations = [1.0 / 30.0, 0.5, 2.0 / 3.0];
+
+        assert_eq!(fmt_vec(&allocations, 4), "[0.0333, 0.5000, 0.6667]");
+        assert_eq!(fmt_vec(&allocations, 2), "[0.03, 0.50, 0.67]");
+        assert_eq!(fmt_vec(&allocations, 0), "[0, 0, 1]");
+        assert_eq!(fmt_vec(&[-0.125], 2), "[-0.12]");
+        assert_eq!(fmt_vec(&[], 4), "[]");
+    }
+}
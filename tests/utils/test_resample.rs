@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Datelike, Duration, TimeZone, Utc, Weekday};
+    use nalufx::services::processing_svc::calculate_daily_returns;
+    use nalufx::utils::resample::{resample, Frequency};
+
+    /// Returns a close for each weekday from `start`, rising by one per trading day.
+    fn trading_days(start: DateTime<Utc>, count: usize) -> Vec<(DateTime<Utc>, f64)> {
+        (0..)
+            .map(|offset| start + Duration::days(offset))
+            .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+            .take(count)
+            .enumerate()
+            .map(|(i, date)| (date, 100.0 + i as f64))
+            .collect()
+    }
+
+    #[test]
+    fn test_resample_weekly_takes_last_close_of_each_week() {
+        // Monday 3 June 2024 to Friday 14 June 2024.
+        let daily = trading_days(Utc.with_ymd_and_hms(2024, 6, 3, 0, 0, 0).unwrap(), 10);
+
+        let weekly = resample(&daily, Frequency::Weekly);
+
+        assert_eq!(weekly.len(), 2);
+        assert_eq!(weekly[0], (Utc.with_ymd_and_hms(2024, 6, 7, 0, 0, 0).unwrap(), 104.0));
+        assert_eq!(weekly[1], (Utc.with_ymd_and_hms(2024, 6, 14, 0, 0, 0).unwrap(), 109.0));
+    }
+
+    #[test]
+    fn test_resample_keeps_partial_trailing_period() {
+        // Wednesday 5 June 2024 to Tuesday 18 June 2024: a partial first and last week.
+        let daily = trading_days(Utc.with_ymd_and_hms(2024, 6, 5, 0, 0, 0).unwrap(), 10);
+
+        let weekly = resample(&daily, Frequency::Weekly);
+
+        assert_eq!(weekly.len(), 3);
+        assert_eq!(weekly[2], daily[9]);
+        assert_eq!(weekly[2].0.weekday(), Weekday::Tue);
+    }
+
+    #[test]
+    fn test_resample_monthly_across_year_end() {
+        let daily = trading_days(Utc.with_ymd_and_hms(2023, 11, 27, 0, 0, 0).unwrap(), 60);
+
+        let monthly = resample(&daily, Frequency::Monthly);
+
+        let months: Vec<(i32, u32)> =
+            monthly.iter().map(|(date, _)| (date.year(), date.month())).collect();
+        assert_eq!(months, vec![(2023, 11), (2023, 12), (2024, 1), (2024, 2)]);
+        // Friday 29 December 2023 is the last trading day of the year.
+        assert_eq!(monthly[1].0, Utc.with_ymd_and_hms(2023, 12, 29, 0, 0, 0).unwrap());
+        assert_eq!(monthly[3], daily[59]);
+    }
+
+    #[test]
+    fn test_resample_weekly_iso_week_spans_year_end() {
+        // Monday 30 December 2024 to Friday 3 January 2025 is ISO week 1 of 2025.
+        let daily = trading_days(Utc.with_ymd_and_hms(2024, 12, 30, 0, 0, 0).unwrap(), 5);
+
+        assert_eq!(resample(&daily, Frequency::Weekly), vec![daily[4]]);
+        assert_eq!(resample(&daily, Frequency::Monthly), vec![daily[1], daily[4]]);
+    }
+
+    #[test]
+    fn test_resample_feeds_period_returns() {
+        let daily = trading_days(Utc.with_ymd_and_hms(2024, 6, 3, 0, 0, 0).unwrap(), 10);
+        let weekly: Vec<f64> =
+            resample(&daily, Frequency::Weekly).iter().map(|&(_, close)| close).collect();
+
+        let returns = calculate_daily_returns(&weekly);
+
+        assert_eq!(returns.len(), 1);
+        assert!((returns[0] - (109.0 / 104.0 - 1.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_resample_empty() {
+        assert!(resample(&[], Frequency::Weekly).is_empty());
+        assert!(resample(&[], Frequency::Monthly).is_empty());
+    }
+}
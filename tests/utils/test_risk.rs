@@ -0,0 +1,232 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::NaluFxError;
+    use nalufx::utils::risk::{
+        calculate_beta, calculate_calmar_ratio, calculate_information_ratio,
+        calculate_max_drawdown, calculate_sharpe_ratio, calculate_tracking_error,
+        calculate_treynor_ratio, diversification_ratio, rolling_correlation, rolling_sharpe,
+        TRADING_DAYS_PER_YEAR,
+    };
+    use ndarray::Array2;
+
+    const BENCHMARK: [f64; 6] = [0.01, -0.02, 0.015, 0.005, -0.01, 0.02];
+
+    #[test]
+    fn test_portfolio_equal_to_benchmark() {
+        assert!((calculate_beta(&BENCHMARK, &BENCHMARK).unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(calculate_tracking_error(&BENCHMARK, &BENCHMARK).unwrap(), 0.0);
+
+        let information_ratio = calculate_information_ratio(&BENCHMARK, &BENCHMARK).unwrap();
+        assert!(!information_ratio.is_nan());
+        assert_eq!(information_ratio, 0.0);
+    }
+
+    #[test]
+    fn test_treynor_ratio() {
+        let returns: Vec<f64> = BENCHMARK.iter().map(|r| 2.0 * r).collect();
+        let mean_return = returns.iter().sum::<f64>() / returns.len() as f64;
+        let expected = (mean_return * TRADING_DAYS_PER_YEAR - 0.02) / 2.0;
+
+        let ratio = calculate_treynor_ratio(&returns, &BENCHMARK, 0.02).unwrap();
+        assert!((ratio - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_information_ratio() {
+        let active = [0.001, 0.002, -0.001, 0.003, 0.0, 0.001];
+        let returns: Vec<f64> = BENCHMARK.iter().zip(active).map(|(b, a)| b + a).collect();
+
+        let mean_active = active.iter().sum::<f64>() / active.len() as f64;
+        let variance = active.iter().map(|a| (a - mean_active).powi(2)).sum::<f64>()
+            / (active.len() - 1) as f64;
+        let expected = mean_active * TRADING_DAYS_PER_YEAR.sqrt() / variance.sqrt();
+
+        let ratio = calculate_information_ratio(&returns, &BENCHMARK).unwrap();
+        assert!((ratio - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_constant_active_return_is_invalid() {
+        // Binary-exact values keep the active return exactly constant.
+        let benchmark = [0.25, -0.5, 0.125, 0.0];
+        let returns: Vec<f64> = benchmark.iter().map(|r| r + 0.0625).collect();
+        assert!(matches!(
+            calculate_information_ratio(&returns, &benchmark),
+            Err(NaluFxError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn test_empty_inputs() {
+        assert!(matches!(calculate_treynor_ratio(&[], &[], 0.0), Err(NaluFxError::EmptyInput)));
+        assert!(matches!(calculate_information_ratio(&[], &[]), Err(NaluFxError::EmptyInput)));
+        assert!(matches!(
+            calculate_information_ratio(&BENCHMARK, &[]),
+            Err(NaluFxError::EmptyInput)
+        ));
+    }
+
+    #[test]
+    fn test_mismatched_inputs() {
+        assert!(matches!(
+            calculate_treynor_ratio(&BENCHMARK[..3], &BENCHMARK, 0.0),
+            Err(NaluFxError::InputMismatch)
+        ));
+        assert!(matches!(
+            calculate_information_ratio(&BENCHMARK[..3], &BENCHMARK),
+            Err(NaluFxError::InputMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_treynor_ratio_constant_benchmark() {
+        assert!(matches!(
+            calculate_treynor_ratio(&BENCHMARK, &[0.01; 6], 0.0),
+            Err(NaluFxError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn test_diversification_ratio_uncorrelated_assets() {
+        for n in [2, 4, 9] {
+            let cov = Array2::from_diag_elem(n, 0.04);
+            let weights = vec![1.0 / n as f64; n];
+            let ratio = diversification_ratio(&weights, &cov).unwrap();
+            assert!((ratio - (n as f64).sqrt()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_diversification_ratio_perfectly_correlated_assets() {
+        let volatilities = [0.1, 0.2, 0.3];
+        let cov = Array2::from_shape_fn((3, 3), |(i, j)| volatilities[i] * volatilities[j]);
+        let ratio = diversification_ratio(&[0.2, 0.3, 0.5], &cov).unwrap();
+        assert!((ratio - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_diversification_ratio_invalid_inputs() {
+        let cov = Array2::from_diag_elem(2, 0.04);
+        assert!(matches!(diversification_ratio(&[], &cov), Err(NaluFxError::EmptyInput)));
+        assert!(matches!(
+            diversification_ratio(&[0.2, 0.3, 0.5], &cov),
+            Err(NaluFxError::InputMismatch)
+        ));
+        assert!(matches!(
+            diversification_ratio(&[0.5, 0.5], &Array2::zeros((2, 2))),
+            Err(NaluFxError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn test_max_drawdown() {
+        assert_eq!(calculate_max_drawdown(&[]), 0.0);
+        assert_eq!(calculate_max_drawdown(&[1.0, 2.0, 3.0]), 0.0);
+        assert!((calculate_max_drawdown(&[100.0, 80.0, 120.0, 60.0, 90.0]) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_calmar_ratio_known_series() {
+        let closes = [100.0, 105.0, 84.0, 92.4, 96.6];
+        let returns: Vec<f64> = closes.windows(2).map(|w| w[1] / w[0] - 1.0).collect();
+        let annualized_return = returns.iter().sum::<f64>() / 4.0 * TRADING_DAYS_PER_YEAR;
+
+        // The maximum drawdown is from 105 to 84, or 20%.
+        let calmar = calculate_calmar_ratio(&returns, &closes);
+        assert!((calmar - annualized_return / 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_calmar_ratio_undefined() {
+        assert!(calculate_calmar_ratio(&[], &[100.0, 90.0]).is_nan());
+        assert!(calculate_calmar_ratio(&[0.01, 0.02], &[100.0, 101.0, 103.02]).is_nan());
+    }
+
+    #[test]
+    fn test_rolling_sharpe_length() {
+        for window in 2..=BENCHMARK.len() {
+            assert_eq!(rolling_sharpe(&BENCHMARK, window).len(), BENCHMARK.len() - window + 1);
+        }
+        let full_window = rolling_sharpe(&BENCHMARK, BENCHMARK.len());
+        let mean = BENCHMARK.iter().sum::<f64>() / BENCHMARK.len() as f64;
+        let variance = BENCHMARK.iter().map(|r| (r - mean).powi(2)).sum::<f64>()
+            / (BENCHMARK.len() - 1) as f64;
+        let expected = mean / variance.sqrt() * TRADING_DAYS_PER_YEAR.sqrt();
+        assert!((full_window[0] - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sharpe_ratio_matches_the_full_rolling_window() {
+        let full_window = rolling_sharpe(&BENCHMARK, BENCHMARK.len());
+        assert_eq!(calculate_sharpe_ratio(&BENCHMARK), full_window[0]);
+        assert!(calculate_sharpe_ratio(&[]).is_nan());
+        assert!(calculate_sharpe_ratio(&[0.01, 0.01, 0.01]).is_nan());
+    }
+
+    #[test]
+    fn test_rolling_sharpe_invalid_windows() {
+        assert!(rolling_sharpe(&BENCHMARK, BENCHMARK.len() + 1).is_empty());
+        assert!(rolling_sharpe(&BENCHMARK, 1).is_empty());
+        assert!(rolling_sharpe(&BENCHMARK, 0).is_empty());
+        assert!(rolling_sharpe(&[0.01, 0.01, 0.01], 2).iter().all(|ratio| ratio.is_nan()));
+    }
+
+    #[test]
+    fn test_rolling_correlation_worked_example() {
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = [2.0, 4.0, 5.0, 4.0, 6.0];
+
+        // [1, 2, 3] and [2, 4, 5] have a covariance sum of 3 and squared deviation sums of 2
+        // and 42/9; [2, 3, 4] and [4, 5, 4] are uncorrelated; [3, 4, 5] and [5, 4, 6] have a
+        // covariance sum of 1 and squared deviation sums of 2 and 2.
+        let expected = [3.0 / (2.0_f64 * 42.0 / 9.0).sqrt(), 0.0, 0.5];
+        let correlations = rolling_correlation(&a, &b, 3);
+
+        assert_eq!(correlations.len(), expected.len());
+        for (correlation, expected) in correlations.iter().zip(expected) {
+            assert!((correlation - expected).abs() < 1e-12, "{} != {}", correlation, expected);
+        }
+    }
+
+    #[test]
+    fn test_rolling_correlation_captures_sign_flip() {
+        // The second series moves with the first for ten days, then against it.
+        let a: Vec<f64> = (0..20).map(|i| if i % 2 == 0 { 0.01 } else { -0.01 }).collect();
+        let b: Vec<f64> = a.iter().enumerate().map(|(i, &x)| if i < 10 { x } else { -x }).collect();
+
+        let correlations = rolling_correlation(&a, &b, 4);
+        assert_eq!(correlations.len(), a.len() - 4 + 1);
+
+        // Windows entirely in the first half are perfectly positively correlated.
+        assert!((correlations[0] - 1.0).abs() < 1e-12);
+        assert!((correlations[6] - 1.0).abs() < 1e-12);
+
+        // Windows entirely in the second half are perfectly negatively correlated.
+        assert!((correlations[10] + 1.0).abs() < 1e-12);
+        assert!((correlations[16] + 1.0).abs() < 1e-12);
+
+        // Windows straddling the change sit strictly in between.
+        assert!(correlations[8] > -1.0 && correlations[8] < 1.0);
+    }
+
+    #[test]
+    fn test_rolling_correlation_bounds_and_constant_windows() {
+        let a: Vec<f64> = BENCHMARK.iter().map(|r| r * 1e-3).collect();
+        let b: Vec<f64> = BENCHMARK.iter().map(|r| -3.0 * r).collect();
+        assert!(rolling_correlation(&a, &b, 3).iter().all(|&c| (-1.0..=-1.0 + 1e-12).contains(&c)));
+
+        let constant = [0.01; 6];
+        assert!(rolling_correlation(&BENCHMARK, &constant, 3).iter().all(|c| c.is_nan()));
+        assert!(rolling_correlation(&BENCHMARK, &BENCHMARK, 1).is_empty());
+        assert!(rolling_correlation(&BENCHMARK, &BENCHMARK, 7).is_empty());
+    }
+
+    #[test]
+    fn test_rolling_correlation_truncates_longer_series() {
+        let longer = [0.5, 0.1, -0.3, 0.2, 0.4, -0.1, 0.0];
+        assert_eq!(
+            rolling_correlation(&longer, &BENCHMARK, 4),
+            rolling_correlation(&longer[1..], &BENCHMARK, 4)
+        );
+    }
+}
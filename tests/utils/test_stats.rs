@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::errors::NaluFxError;
+    use nalufx::utils::stats::{
+        mean, mean_with_policy, median, min_max_normalize, percentile, percentile_with_policy,
+        std_dev, std_dev_with_policy, zscore, NanPolicy,
+    };
+
+    const DATA_WITH_NAN: [f64; 5] = [1.0, 2.0, f64::NAN, 3.0, 4.0];
+
+    #[test]
+    fn test_nan_policy_default_is_error() {
+        assert_eq!(NanPolicy::default(), NanPolicy::Error);
+    }
+
+    #[test]
+    fn test_nan_policy_error() {
+        assert!(matches!(
+            mean_with_policy(&DATA_WITH_NAN, NanPolicy::Error),
+            Err(NaluFxError::InvalidData)
+        ));
+        assert!(matches!(
+            std_dev_with_policy(&DATA_WITH_NAN, 1, NanPolicy::Error),
+            Err(NaluFxError::InvalidData)
+        ));
+        assert!(matches!(
+            percentile_with_policy(&DATA_WITH_NAN, 50.0, NanPolicy::Error),
+            Err(NaluFxError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn test_nan_policy_propagate() {
+        assert!(mean_with_policy(&DATA_WITH_NAN, NanPolicy::Propagate).unwrap().is_nan());
+        assert!(std_dev_with_policy(&DATA_WITH_NAN, 1, NanPolicy::Propagate).unwrap().is_nan());
+        assert!(percentile_with_policy(&DATA_WITH_NAN, 50.0, NanPolicy::Propagate)
+            .unwrap()
+            .is_nan());
+    }
+
+    #[test]
+    fn test_nan_policy_skip() {
+        assert_eq!(mean_with_policy(&DATA_WITH_NAN, NanPolicy::Skip).unwrap(), 2.5);
+        let std_dev = std_dev_with_policy(&DATA_WITH_NAN, 1, NanPolicy::Skip).unwrap();
+        assert!((std_dev - (5.0_f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert_eq!(percentile_with_policy(&DATA_WITH_NAN, 50.0, NanPolicy::Skip).unwrap(), 2.5);
+    }
+
+    #[test]
+    fn test_nan_policy_skip_all_nan() {
+        let data = [f64::NAN, f64::NAN];
+        assert!(matches!(mean_with_policy(&data, NanPolicy::Skip), Err(NaluFxError::EmptyInput)));
+    }
+
+    #[test]
+    fn test_policies_agree_without_nan() {
+        let data = [1.0, 2.0, 3.0, 4.0];
+        for policy in [NanPolicy::Propagate, NanPolicy::Skip, NanPolicy::Error] {
+            assert_eq!(mean_with_policy(&data, policy).unwrap(), 2.5);
+            assert_eq!(percentile_with_policy(&data, 25.0, policy).unwrap(), 1.75);
+        }
+    }
+
+    #[test]
+    fn test_empty_input_and_invalid_arguments() {
+        assert!(matches!(mean_with_policy(&[], NanPolicy::Skip), Err(NaluFxError::EmptyInput)));
+        assert!(matches!(
+            std_dev_with_policy(&[1.0], 1, NanPolicy::Error),
+            Err(NaluFxError::InvalidData)
+        ));
+        assert!(matches!(
+            percentile_with_policy(&[1.0, 2.0], 101.0, NanPolicy::Error),
+            Err(NaluFxError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn test_mean_known_sample() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(mean(&data), Some(5.0));
+        assert_eq!(mean(&[]), None);
+        assert_eq!(mean(&DATA_WITH_NAN), None);
+    }
+
+    #[test]
+    fn test_std_dev_population_vs_sample() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+
+        // Sum of squared deviations is 32 over 8 values
+        let population = std_dev(&data, 0).unwrap();
+        let sample = std_dev(&data, 1).unwrap();
+        assert_eq!(population, 2.0);
+        assert!((sample - (32.0_f64 / 7.0).sqrt()).abs() < 1e-12);
+        assert!(sample > population);
+    }
+
+    #[test]
+    fn test_std_dev_insufficient_data() {
+        assert_eq!(std_dev(&[], 0), None);
+        assert_eq!(std_dev(&[1.0], 0), Some(0.0));
+        assert_eq!(std_dev(&[1.0], 1), None);
+        assert_eq!(std_dev(&DATA_WITH_NAN, 0), None);
+    }
+
+    #[test]
+    fn test_percentile_known_quantiles() {
+        // The sample quantiles of R's type 7 and NumPy's linear method
+        let data = [7.0, 15.0, 36.0, 39.0, 40.0, 41.0];
+        for (p, expected) in [
+            (0.0, 7.0),
+            (10.0, 11.0),
+            (25.0, 20.25),
+            (50.0, 37.5),
+            (75.0, 39.75),
+            (90.0, 40.5),
+            (100.0, 41.0),
+        ] {
+            let actual = percentile(&data, p).unwrap();
+            assert!((actual - expected).abs() < 1e-12, "p{}: {} != {}", p, actual, expected);
+        }
+
+        // The order of the input does not matter
+        let shuffled = [40.0, 7.0, 41.0, 36.0, 15.0, 39.0];
+        assert_eq!(percentile(&shuffled, 25.0), percentile(&data, 25.0));
+        assert_eq!(percentile(&[42.0], 95.0), Some(42.0));
+    }
+
+    #[test]
+    fn test_percentile_invalid_inputs() {
+        assert_eq!(percentile(&[], 50.0), None);
+        assert_eq!(percentile(&DATA_WITH_NAN, 50.0), None);
+        assert_eq!(percentile(&[1.0, 2.0], -1.0), None);
+        assert_eq!(percentile(&[1.0, 2.0], 100.5), None);
+        assert_eq!(percentile(&[1.0, 2.0], f64::NAN), None);
+    }
+
+    #[test]
+    fn test_median_odd_and_even_lengths() {
+        assert_eq!(median(&[5.0, 1.0, 3.0]), Some(3.0));
+        assert_eq!(median(&[7.0, 15.0, 36.0, 39.0, 40.0, 41.0]), Some(37.5));
+        assert_eq!(median(&[]), None);
+        assert_eq!(median(&DATA_WITH_NAN), None);
+    }
+
+    #[test]
+    fn test_zscore_has_zero_mean_and_unit_std() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let scores = zscore(&data);
+
+        assert_eq!(scores.len(), data.len());
+        assert!(mean(&scores).unwrap().abs() < 1e-12);
+        assert!((std_dev(&scores, 0).unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(scores[0], -1.5);
+    }
+
+    #[test]
+    fn test_zscore_constant_input_returns_zeros() {
+        assert_eq!(zscore(&[3.0, 3.0, 3.0]), vec![0.0, 0.0, 0.0]);
+        assert_eq!(zscore(&[3.0]), vec![0.0]);
+        assert!(zscore(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_zscore_nan_input() {
+        assert!(zscore(&DATA_WITH_NAN).iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn test_min_max_normalize_range() {
+        let normalized = min_max_normalize(&[10.0, 20.0, 15.0, 30.0]);
+        assert_eq!(normalized, vec![0.0, 0.5, 0.25, 1.0]);
+    }
+
+    #[test]
+    fn test_min_max_normalize_constant_input() {
+        assert_eq!(min_max_normalize(&[2.0, 2.0, 2.0]), vec![0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_min_max_normalize_single_element() {
+        assert_eq!(min_max_normalize(&[42.0]), vec![0.5]);
+        assert!(min_max_normalize(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_min_max_normalize_nan_input() {
+        assert!(min_max_normalize(&DATA_WITH_NAN).iter().all(|x| x.is_nan()));
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use nalufx::utils::trading::{round_to_fractional_shares, round_to_lots, ShareRounding};
+
+    #[test]
+    fn test_round_to_lots_whole_shares() {
+        let (shares, residual) = round_to_lots(1000.0, 97.0, 1);
+
+        assert_eq!(shares, 10);
+        assert!((residual - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_round_to_lots_round_lots() {
+        // $25,000 buys two lots of 100 shares at $97, leaving $5,600.
+        let (shares, residual) = round_to_lots(25_000.0, 97.0, 100);
+
+        assert_eq!(shares, 200);
+        assert!((residual - 5_600.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_round_to_lots_amount_below_one_lot() {
+        assert_eq!(round_to_lots(50.0, 97.0, 1), (0, 50.0));
+    }
+
+    #[test]
+    fn test_round_to_lots_exact_amount_has_no_residual() {
+        assert_eq!(round_to_lots(970.0, 97.0, 1), (10, 0.0));
+    }
+
+    #[test]
+    fn test_round_to_lots_skips_invalid_prices() {
+        assert_eq!(round_to_lots(1000.0, 0.0, 1), (0, 1000.0));
+        assert_eq!(round_to_lots(1000.0, -97.0, 1), (0, 1000.0));
+        assert_eq!(round_to_lots(1000.0, f64::NAN, 1), (0, 1000.0));
+    }
+
+    #[test]
+    fn test_round_to_lots_skips_zero_lot_size() {
+        assert_eq!(round_to_lots(1000.0, 97.0, 0), (0, 1000.0));
+    }
+
+    #[test]
+    fn test_round_to_lots_invalid_amount_buys_nothing() {
+        assert_eq!(round_to_lots(-1000.0, 97.0, 1), (0, 0.0));
+        assert_eq!(round_to_lots(f64::INFINITY, 97.0, 1), (0, 0.0));
+    }
+
+    #[test]
+    fn test_round_to_fractional_shares() {
+        let (shares, residual) = round_to_fractional_shares(1000.0, 131.37, 3);
+
+        assert_eq!(shares, 7.612);
+        assert!((residual - (1000.0 - 7.612 * 131.37)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_round_to_fractional_shares_skips_invalid_prices() {
+        assert_eq!(round_to_fractional_shares(1000.0, 0.0, 3), (0.0, 1000.0));
+        assert_eq!(round_to_fractional_shares(-1000.0, 97.0, 3), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_share_rounding() {
+        assert_eq!(ShareRounding::Lots(1).round(1000.0, 97.0).0, 10.0);
+        assert_eq!(ShareRounding::Lots(100).round(1000.0, 97.0), (0.0, 1000.0));
+        assert_eq!(ShareRounding::Fractional(2).round(1000.0, 97.0).0, 10.3);
+    }
+}
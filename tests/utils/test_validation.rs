@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use nalufx::errors::NaluFxError;
+    use nalufx::utils::validation::{
+        get_float_validation_error_message, get_ticker_validation_error_message, validate_date,
+        validate_positive_amount, validate_positive_float, validate_ticker,
+    };
+
+    fn assert_invalid_input<T: std::fmt::Debug>(result: Result<T, NaluFxError>, message: &str) {
+        match result {
+            Err(NaluFxError::InvalidInput(reason)) => assert_eq!(reason, message),
+            other => panic!("Expected an invalid input error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_positive_amount_accepts_positive() {
+        assert_eq!(validate_positive_amount(1000.0).unwrap(), 1000.0);
+        assert_eq!(validate_positive_amount(0.01).unwrap(), 0.01);
+    }
+
+    #[test]
+    fn test_validate_positive_amount_rejects_non_positive() {
+        assert!(matches!(validate_positive_amount(0.0), Err(NaluFxError::InvalidData)));
+        assert!(matches!(validate_positive_amount(-100.0), Err(NaluFxError::InvalidData)));
+        assert!(matches!(validate_positive_amount(f64::NAN), Err(NaluFxError::InvalidData)));
+        assert!(matches!(validate_positive_amount(f64::INFINITY), Err(NaluFxError::InvalidData)));
+    }
+
+    #[test]
+    fn test_validate_positive_float() {
+        assert_eq!(validate_positive_float("123.45").unwrap(), 123.45);
+        assert_invalid_input(validate_positive_float("0"), "The number is not positive.");
+        assert_invalid_input(validate_positive_float("-100.0"), "The number is not positive.");
+        assert_invalid_input(validate_positive_float("NaN"), "The number is not finite.");
+        assert_invalid_input(validate_positive_float("inf"), "The number is not finite.");
+        assert_invalid_input(validate_positive_float("abc"), "The input is not a valid float.");
+    }
+
+    #[test]
+    fn test_validate_ticker() {
+        assert_eq!(validate_ticker("AAPL").unwrap(), "AAPL");
+        assert_eq!(validate_ticker("BRK1").unwrap(), "BRK1");
+        assert_eq!(
+            validate_ticker("AAPL$").unwrap_err().to_string(),
+            "Invalid ticker symbol AAPL$: The ticker symbol must be alphanumeric."
+        );
+        assert!(matches!(validate_ticker(""), Err(NaluFxError::InvalidTicker(_))));
+        assert!(matches!(validate_ticker("BRK.B"), Err(NaluFxError::InvalidTicker(_))));
+    }
+
+    #[test]
+    fn test_get_ticker_validation_error_message() {
+        assert_eq!(get_ticker_validation_error_message(""), "The ticker symbol cannot be empty.");
+        assert_eq!(
+            get_ticker_validation_error_message("AAPL$"),
+            "The ticker symbol must be alphanumeric."
+        );
+        assert_eq!(get_ticker_validation_error_message("AAPL"), "Unknown validation error.");
+    }
+
+    #[test]
+    fn test_validate_date() {
+        assert_eq!(
+            validate_date("2024-01-01").unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+        );
+
+        // An invalid month, a day-first format, a non-existent day and an empty string
+        for invalid in ["2024-13-01", "01-01-2024", "2023-02-29", ""] {
+            assert_invalid_input(
+                validate_date(invalid),
+                "Please enter a valid date in the format YYYY-MM-DD.",
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_float_validation_error_message() {
+        assert_eq!(get_float_validation_error_message("123.45"), "No validation error.");
+        assert_eq!(get_float_validation_error_message("-100.0"), "The number is not positive.");
+        assert_eq!(get_float_validation_error_message("NaN"), "The number is not finite.");
+        assert_eq!(get_float_validation_error_message(""), "The input is not a valid float.");
+    }
+}